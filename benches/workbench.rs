@@ -0,0 +1,107 @@
+//! Benchmarks for large worlds: hierarchy rendering, inspector snapshots and console filtering.
+//!
+//! Run with `cargo bench --bench workbench`.
+
+use bevy::prelude::*;
+use bevy_workbench::console::{ConsoleFilter, LogEntry, LogLevel};
+use bevy_workbench::inspector::{InspectorPanel, InspectorSelection};
+use bevy_workbench::testing::WorkbenchTestHarness;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// A large reflected component, snapshotted when its entity is selected.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Samples(Vec<f32>);
+
+/// Spawn `roots` chains `depth` levels deep with `leaves` leaves per level; returns the roots.
+fn spawn_world(world: &mut World, roots: usize, depth: usize, leaves: usize) -> Vec<Entity> {
+    (0..roots)
+        .map(|root| {
+            let root = world
+                .spawn((Name::new(format!("Root {root}")), Samples(vec![0.0; 1024])))
+                .id();
+            let mut parent = root;
+            for level in 0..depth {
+                for leaf in 0..leaves {
+                    world.spawn((Name::new(format!("Leaf {level}.{leaf}")), ChildOf(parent)));
+                }
+                parent = world
+                    .spawn((Name::new(format!("Node {level}")), ChildOf(parent)))
+                    .id();
+            }
+            root
+        })
+        .collect()
+}
+
+fn inspector_harness(roots: usize) -> (WorkbenchTestHarness, Vec<Entity>) {
+    let mut harness = WorkbenchTestHarness::new(InspectorPanel);
+    harness.app.register_type::<Samples>();
+    harness.app.insert_resource(InspectorSelection::default());
+    let roots = spawn_world(harness.app.world_mut(), roots, 10, 4);
+    harness.step();
+    (harness, roots)
+}
+
+fn hierarchy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hierarchy");
+    group.sample_size(20);
+    for roots in [100, 1_000] {
+        let (mut harness, _) = inspector_harness(roots);
+        group.bench_function(format!("{}_entities", roots * 50), |b| {
+            b.iter(|| harness.step())
+        });
+    }
+    group.finish();
+}
+
+fn snapshot(c: &mut Criterion) {
+    let (mut harness, roots) = inspector_harness(100);
+    let mut next = 0;
+    c.bench_function("inspector_select_and_snapshot", |b| {
+        b.iter(|| {
+            next = (next + 1) % roots.len();
+            let mut selection = harness.app.world_mut().resource_mut::<InspectorSelection>();
+            selection.selected.select_replace(roots[next]);
+            harness.step();
+        })
+    });
+}
+
+fn console_filter(c: &mut Criterion) {
+    let levels = [
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+        LogLevel::Debug,
+    ];
+    let entries: Vec<LogEntry> = (0..100_000)
+        .map(|i| LogEntry {
+            level: levels[i % levels.len()],
+            message: format!("Frame {i}: loaded asset textures/tile_{}.png", i % 97),
+            target: ["game::assets", "bevy_render", "game::ai"][i % 3].to_string(),
+            session: None,
+        })
+        .collect();
+    let mut group = c.benchmark_group("console_filter");
+    for (name, query, regex) in [
+        ("substring", "tile_42", false),
+        ("target_level", "target:game level:warn,error", false),
+        ("regex", r"tile_\d+3\.png", true),
+    ] {
+        let filter = ConsoleFilter::parse(query, regex).expect("valid query");
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                entries
+                    .iter()
+                    .filter(|e| filter.matches(black_box(e)))
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hierarchy, snapshot, console_filter);
+criterion_main!(benches);
@@ -1,7 +1,8 @@
 //! Control Link example — drag connections to assign control.
 //!
-//! A custom "Control" panel shows 4 scene objects and a "控制权" node.
-//! Drag from "控制权" to an object to assign WASD/right-click control.
+//! A custom "Control" panel shows 4 scene objects and a "控制权" node as a
+//! `bench_ui::node_graph`. Drag from "控制权" to an object to assign
+//! WASD/right-click control; drag the link off the object to release it.
 //!
 //! ## Adding localization for custom panels
 //!
@@ -20,6 +21,7 @@
 mod common;
 
 use bevy::prelude::*;
+use bevy_workbench::bench_ui::{self, GraphNode, NodeGraph, NodeId, PortRef};
 use bevy_workbench::console::console_log_layer;
 use bevy_workbench::dock::WorkbenchPanel;
 use bevy_workbench::i18n::{I18n, Locale};
@@ -48,8 +50,7 @@ fn main() {
     common::register_types(&mut app);
     register_panels(&mut app);
 
-    app.init_resource::<ControlLinkState>()
-        .add_systems(Startup, (setup, setup_i18n))
+    app.add_systems(Startup, (setup, setup_i18n))
         .add_systems(
             OnEnter(EditorMode::Play),
             common::setup_game.run_if(on_fresh_play),
@@ -75,22 +76,20 @@ fn setup_i18n(mut i18n: ResMut<I18n>) {
 }
 
 fn register_panels(app: &mut App) {
-    app.register_panel(ControlLinkPanel);
+    app.register_panel(ControlLinkPanel::default());
 }
 
-/// Tracks the drag state and current control link.
-#[derive(Resource, Default)]
-struct ControlLinkState {
-    /// Entity currently linked (controlled).
-    linked_entity: Option<Entity>,
-    /// True while dragging from the control node.
-    dragging: bool,
-    /// Screen position of the drag start (control node center).
-    drag_start: egui::Pos2,
-}
+/// Node id of the "控制权" node; object nodes use their entity bits.
+const CONTROL_NODE: NodeId = NodeId(u64::MAX);
+
+/// The port type linking the control node to objects.
+const CONTROL_PORT: &str = "control";
 
 /// Custom panel: shows scene objects + "控制权" node with drag-to-link.
-struct ControlLinkPanel;
+#[derive(Default)]
+struct ControlLinkPanel {
+    graph: NodeGraph,
+}
 
 impl WorkbenchPanel for ControlLinkPanel {
     fn id(&self) -> &str {
@@ -106,15 +105,10 @@ impl WorkbenchPanel for ControlLinkPanel {
     }
 
     fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
-        let node_size = egui::vec2(100.0, 36.0);
-        let control_color = egui::Color32::from_rgb(255, 180, 50);
-        let object_color = egui::Color32::from_rgb(80, 160, 255);
-        let linked_color = egui::Color32::from_rgb(100, 255, 100);
-
         // Localized label for the control node
         let control_label = world
             .get_resource::<I18n>()
-            .map(|i18n| i18n.t("control-node"))
+            .map(|i18n| i18n.t("control-node").to_owned())
             .unwrap_or_else(|| "Control".into());
 
         // Collect scene objects
@@ -123,117 +117,29 @@ impl WorkbenchPanel for ControlLinkPanel {
         for (entity, obj) in scene_q.iter(world) {
             objects.push((entity, obj.name.clone()));
         }
+        let mut controlled_q = world.query_filtered::<Entity, With<common::Controlled>>();
+        let controlled = controlled_q.iter(world).next();
 
-        let mut state = world
-            .remove_resource::<ControlLinkState>()
-            .unwrap_or_default();
-
-        // Layout: control node on the left, objects on the right
-        let panel_rect = ui.available_rect_before_wrap();
-        let control_pos = egui::pos2(
-            panel_rect.left() + 30.0,
-            panel_rect.center().y - node_size.y / 2.0,
-        );
-        let control_rect = egui::Rect::from_min_size(control_pos, node_size);
-
-        // Draw control node "控制权"
-        let control_response = ui.allocate_rect(control_rect, egui::Sense::drag());
-
-        // Object node positions (stacked vertically on the right)
-        let obj_x = panel_rect.right() - node_size.x - 30.0;
-        let obj_start_y = panel_rect.top() + 20.0;
-        let obj_spacing = node_size.y + 16.0;
-
-        let mut object_rects: Vec<(Entity, egui::Rect)> = Vec::new();
-        let mut clicked_linked: Option<Entity> = None;
-        for (i, (entity, _name)) in objects.iter().enumerate() {
-            let y = obj_start_y + i as f32 * obj_spacing;
-            let rect = egui::Rect::from_min_size(egui::pos2(obj_x, y), node_size);
-            object_rects.push((*entity, rect));
-
-            let obj_response = ui.allocate_rect(rect, egui::Sense::click());
-            let is_linked = state.linked_entity == Some(*entity);
-            if is_linked && obj_response.clicked() {
-                clicked_linked = Some(*entity);
-            }
-        }
-
-        // Handle click-to-unlink
-        if let Some(old) = clicked_linked
-            && state.linked_entity == Some(old)
-        {
-            state.linked_entity = None;
-            if let Ok(mut e) = world.get_entity_mut(old) {
-                e.remove::<common::Controlled>();
-            }
-        }
-
-        // Handle drag from control node
-        if control_response.drag_started() {
-            state.dragging = true;
-            state.drag_start = control_rect.right_center();
-        }
-
-        if state.dragging && ui.input(|i| i.pointer.any_released()) {
-            state.dragging = false;
-            if let Some(pointer_pos) = ui.ctx().pointer_latest_pos() {
-                handle_drop_link(&mut state, world, pointer_pos, &object_rects);
-            }
-        }
-
-        // --- All painting after allocations ---
-        let painter = ui.painter();
-
-        // Control node
-        painter.rect_filled(control_rect, 6.0, control_color);
-        painter.text(
-            control_rect.center(),
-            egui::Align2::CENTER_CENTER,
-            &control_label,
-            egui::FontId::proportional(14.0),
-            egui::Color32::BLACK,
-        );
-
-        // Object nodes
-        for (i, (entity, name)) in objects.iter().enumerate() {
-            let (_, rect) = object_rects[i];
-            let is_linked = state.linked_entity == Some(*entity);
-            let fill = if is_linked {
-                linked_color
-            } else {
-                object_color
-            };
-            painter.rect_filled(rect, 6.0, fill);
-            painter.text(
-                rect.center(),
-                egui::Align2::CENTER_CENTER,
-                name,
-                egui::FontId::proportional(13.0),
-                egui::Color32::WHITE,
-            );
+        sync_graph(&mut self.graph, &control_label, &objects, controlled);
+        if !bench_ui::node_graph(ui, "control_link", &mut self.graph) {
+            return;
         }
 
-        // Drag line
-        if state.dragging
-            && let Some(pointer_pos) = ui.ctx().pointer_latest_pos()
-        {
-            painter.line_segment(
-                [state.drag_start, pointer_pos],
-                egui::Stroke::new(2.5, control_color),
-            );
+        // Only the most recent link keeps control
+        let linked = self
+            .graph
+            .links
+            .iter()
+            .rev()
+            .find(|l| l.from.node == CONTROL_NODE)
+            .copied();
+        self.graph
+            .links
+            .retain(|l| l.from.node != CONTROL_NODE || Some(*l) == linked);
+        let linked_entity = linked.map(|l| Entity::from_bits(l.to.node.0));
+        if linked_entity != controlled {
+            apply_control(world, controlled, linked_entity);
         }
-
-        // Permanent link line
-        if let Some(linked) = state.linked_entity
-            && let Some(&(_, rect)) = object_rects.iter().find(|&&(e, _)| e == linked)
-        {
-            painter.line_segment(
-                [control_rect.right_center(), rect.left_center()],
-                egui::Stroke::new(2.0, linked_color),
-            );
-        }
-
-        world.insert_resource(state);
     }
 
     fn needs_world(&self) -> bool {
@@ -245,28 +151,57 @@ impl WorkbenchPanel for ControlLinkPanel {
     }
 }
 
-/// Handles dropping a link onto an object node.
-fn handle_drop_link(
-    state: &mut ControlLinkState,
-    world: &mut World,
-    pointer_pos: egui::Pos2,
-    object_rects: &[(Entity, egui::Rect)],
+/// Rebuilds the graph when the scene objects change and mirrors the current control link.
+fn sync_graph(
+    graph: &mut NodeGraph,
+    control_label: &str,
+    objects: &[(Entity, String)],
+    controlled: Option<Entity>,
 ) {
-    for &(entity, rect) in object_rects {
-        if !rect.contains(pointer_pos) {
-            continue;
-        }
-        // Unlink previous
-        if let Some(old) = state.linked_entity.take()
-            && let Ok(mut e) = world.get_entity_mut(old)
-        {
-            e.remove::<common::Controlled>();
-        }
-        // Link new
-        if let Ok(mut e) = world.get_entity_mut(entity) {
-            e.insert(common::Controlled);
+    let object_ids: Vec<NodeId> = objects.iter().map(|(e, _)| NodeId(e.to_bits())).collect();
+    let current: Vec<NodeId> = graph.nodes.iter().skip(1).map(|n| n.id).collect();
+    if graph.nodes.is_empty() || current != object_ids {
+        graph.nodes.clear();
+        graph.nodes.push(
+            GraphNode::new(CONTROL_NODE, control_label, egui::pos2(30.0, 80.0))
+                .with_output("", CONTROL_PORT)
+                .with_color(egui::Color32::from_rgb(255, 180, 50)),
+        );
+        for (i, ((_, name), id)) in objects.iter().zip(&object_ids).enumerate() {
+            graph.nodes.push(
+                GraphNode::new(*id, name, egui::pos2(260.0, 20.0 + i as f32 * 60.0))
+                    .with_input("", CONTROL_PORT)
+                    .with_color(egui::Color32::from_rgb(80, 160, 255)),
+            );
         }
-        state.linked_entity = Some(entity);
-        break;
+    }
+    graph.nodes[0].title = control_label.to_string();
+
+    // Keep the link in sync with `Controlled` (e.g. after a play session restarts).
+    graph.links.retain(|l| l.from.node != CONTROL_NODE);
+    if let Some(entity) = controlled {
+        let from = PortRef {
+            node: CONTROL_NODE,
+            port: 0,
+        };
+        let to = PortRef {
+            node: NodeId(entity.to_bits()),
+            port: 0,
+        };
+        graph.connect(from, to);
+    }
+}
+
+/// Moves the `Controlled` marker from the old entity to the new one.
+fn apply_control(world: &mut World, old: Option<Entity>, new: Option<Entity>) {
+    if let Some(old) = old
+        && let Ok(mut e) = world.get_entity_mut(old)
+    {
+        e.remove::<common::Controlled>();
+    }
+    if let Some(new) = new
+        && let Ok(mut e) = world.get_entity_mut(new)
+    {
+        e.insert(common::Controlled);
     }
 }
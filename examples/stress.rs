@@ -0,0 +1,80 @@
+//! Stress test for large worlds.
+//!
+//! Spawns 50 000 entities in deep hierarchies (1 000 roots, each a chain 10 levels deep with
+//! four leaves per level) so the inspector hierarchy, undo snapshots and world stats can be
+//! checked at scale. Every root carries a large reflected component for snapshot costs.
+
+use bevy::prelude::*;
+use bevy_workbench::console::console_log_layer;
+use bevy_workbench::prelude::*;
+
+/// Number of root entities.
+const ROOTS: usize = 1_000;
+/// Depth of the chain below each root.
+const DEPTH: usize = 10;
+/// Leaves hanging off every chain node.
+const LEAVES: usize = 4;
+
+/// A large reflected component, to make inspector snapshots measurable.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Samples(Vec<f32>);
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "Workbench — Stress".into(),
+                    resolution: (1280u32, 720u32).into(),
+                    ..default()
+                }),
+                ..default()
+            })
+            .set(bevy::log::LogPlugin {
+                custom_layer: console_log_layer,
+                ..default()
+            }),
+    )
+    .add_plugins(WorkbenchPlugin::default())
+    .register_type::<Samples>()
+    .add_systems(Startup, (setup, spawn_world))
+    .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+fn spawn_world(mut commands: Commands) {
+    for root in 0..ROOTS {
+        let mut parent = commands
+            .spawn((
+                Name::new(format!("Root {root}")),
+                Transform::default(),
+                Visibility::default(),
+                Samples(vec![0.0; 1024]),
+            ))
+            .id();
+        for level in 0..DEPTH {
+            for leaf in 0..LEAVES {
+                commands.spawn((
+                    Name::new(format!("Leaf {root}.{level}.{leaf}")),
+                    Transform::from_xyz(leaf as f32, 0.0, 0.0),
+                    Visibility::default(),
+                    ChildOf(parent),
+                ));
+            }
+            parent = commands
+                .spawn((
+                    Name::new(format!("Node {root}.{level}")),
+                    Transform::default(),
+                    Visibility::default(),
+                    ChildOf(parent),
+                ))
+                .id();
+        }
+    }
+    info!("Spawned {} entities", ROOTS * (1 + DEPTH * (LEAVES + 1)));
+}
@@ -3,6 +3,7 @@ menu-file = File
 menu-edit = Edit
 menu-view = View
 menu-window = Window
+menu-help = Help
 
 menu-file-new = New
 menu-file-open = Open...
@@ -10,18 +11,34 @@ menu-file-save = Save
 menu-file-save-as = Save As...
 menu-file-settings = Settings
 
-menu-edit-undo = Undo (Ctrl+Z)
-menu-edit-redo = Redo (Ctrl+Shift+Z)
+menu-edit-undo = Undo
+menu-edit-redo = Redo
 
 menu-view-save-layout = Save Layout...
 menu-view-load-layout = Load Layout...
 menu-view-reset-layout = Reset Layout
 
+menu-view-presentation = Presentation Mode
+
+menu-window-reopen = Reopen Closed Panel
+
+menu-help-shortcuts = Keyboard Shortcuts
+menu-help-panels = Panel Help
+
 # Toolbar
 toolbar-play = Play
 toolbar-pause = Pause
 toolbar-stop = Stop
 toolbar-resume = Resume
+mode-edit = Edit
+toolbar-hide-panels = Hide panels while playing
+toolbar-mute-audio = Mute the game's audio during Play
+toolbar-slow-motion = Slow motion: run the game at quarter speed
+toolbar-per-mode-layouts = Separate dock layout for Edit, Play and Pause
+toolbar-clock = Game time of the current Play session
+toolbar-scrub = Scrub the game clock (re-runs EvaluateAtTime)
+toolbar-undo-history = Undo history
+toolbar-redo-history = Redo history
 
 # Settings panel
 settings-title = Editor Settings
@@ -35,17 +52,104 @@ settings-save = Save
 
 # Game view
 game-view-press-play = Press Play to Start Game
+game-view-in-window = Playing in a separate window
 
 # File dialogs
 dialog-save-layout = Save Layout
 dialog-load-layout = Load Layout
+dialog-ok = OK
+dialog-cancel = Cancel
+dialog-reset-layout-confirm = Reset the dock layout to its default arrangement?
+dialog-confirm-mode-change = Confirm Mode Change
+
+# Autosave
+autosave-recover-title = Restore Unsaved Work
+autosave-recover-message = The editor did not shut down cleanly. Restore the autosaved layout, panels and undo history?
 
 # Inspector panel
 inspector-hierarchy = Hierarchy
 inspector-components = Components
 inspector-select-hint = Select an entity to inspect
+inspector-saved-selections = Saved selections
+inspector-selection-name = Name
+inspector-save-selection = Save Selection
+inspector-no-saved-selections = No saved selections
+inspector-delete-saved-selection = Delete saved selection
+inspector-select-all-with = Select All With
+inspector-locked = Locked
+inspector-expand = Expand
+inspector-toggle-visibility = Toggle visibility
+inspector-lock-hint = Lock (not pickable or editable)
 
 # Console panel
 console-clear = Clear
 console-auto-clear = Auto-clear on Play
+console-pause-on-error = Pause on Error
+console-pause-on-error-hint = Switch to Pause when an error is logged during Play
+console-pause-exceptions = Pause on Error exceptions
+console-pause-exceptions-prefix = Ignore errors from targets starting with:
+console-pause-add-exception = + Add exception
+console-levels = Levels
+console-levels-apply-to-layer = Apply to tracing layer
+console-levels-apply-to-layer-hint = Drop filtered events before they reach the console
+console-levels-add-target = + Add target
+console-levels-target-hint = target prefix (empty = all)
 console-filter-hint = Filter...
+console-group-sessions = Group by session
+console-current-session-only = Current session only
+console-session-header = ▶ Play session #{ $id } ({ $time })
+console-export = Export
+console-export-filtered-log = Filtered (.log)...
+console-export-filtered-json = Filtered (.json)...
+console-export-all-log = All (.log)...
+console-export-all-json = All (.json)...
+console-export-title = Export Console Log
+console-copy = Copy
+console-tab-log = Log
+console-tab-eval = Eval
+console-eval-hint = get <entity> <Component>.field — type help for commands
+
+# Welcome panel
+welcome-title = Welcome
+welcome-start = Start
+welcome-open-keybindings = Keybindings
+welcome-copy-template = Copy Panel Template
+welcome-copy-template-hint = Copy a WorkbenchPanel skeleton to the clipboard
+welcome-docs = Documentation
+welcome-recent-layouts = Recent Layouts
+welcome-recent-layouts-empty = Layouts saved or loaded from the View menu appear here
+welcome-tip = Tip
+welcome-next-tip = Next tip
+welcome-show-on-startup = Show on startup
+welcome-tip-detach = Drag a tab outside the window to move the panel into its own OS window.
+welcome-tip-keybindings = Every shortcut can be re-recorded in **Edit ▸ Keybindings**.
+welcome-tip-menu-keys = **Alt** + the first letter of a menu opens it from the keyboard.
+welcome-tip-undo-history = The **Undo History** panel can jump to any step, and keeps branches when enabled.
+welcome-tip-apply = Settings can be applied without saving to try them out first.
+welcome-tip-settings = Tools can add their own settings with `app.register_settings`.
+welcome-tip-help = Right-click a tab and pick **?** to read the panel's help.
+
+# Audio panel
+audio-title = Audio
+audio-master = Master
+audio-mute-on-pause = Mute on pause
+audio-mute-on-pause-hint = Silence every sound while the game is paused
+audio-no-sinks = No sounds playing. Sinks appear here while the game plays audio.
+audio-mute = Mute
+audio-solo = Solo: mute every other sound
+audio-volume = Volume, on top of the game's own
+
+# Tab context menu
+tab-menu-close = Close
+tab-menu-close-others = Close Others
+tab-menu-close-all = Close All in Group
+tab-menu-lock = Lock Tab
+tab-menu-unlock = Unlock Tab
+tab-menu-lock-hint = Locked tabs can't be closed or dragged
+tab-menu-float = Float in New Window
+tab-menu-dock-back = Dock to Main Window
+tab-menu-help = Help
+tab-menu-open-help = Open in Help Panel
+
+# Maximized panel
+dock-restore-layout = Restore layout
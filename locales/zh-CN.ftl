@@ -3,6 +3,7 @@ menu-file = 文件
 menu-edit = 编辑
 menu-view = 视图
 menu-window = 窗口
+menu-help = 帮助
 
 menu-file-new = 新建
 menu-file-open = 打开...
@@ -10,18 +11,34 @@ menu-file-save = 保存
 menu-file-save-as = 另存为...
 menu-file-settings = 设置
 
-menu-edit-undo = 撤销 (Ctrl+Z)
-menu-edit-redo = 重做 (Ctrl+Shift+Z)
+menu-edit-undo = 撤销
+menu-edit-redo = 重做
 
 menu-view-save-layout = 保存布局...
 menu-view-load-layout = 加载布局...
 menu-view-reset-layout = 重置布局
 
+menu-view-presentation = 演示模式
+
+menu-window-reopen = 重新打开已关闭的面板
+
+menu-help-shortcuts = 键盘快捷键
+menu-help-panels = 面板帮助
+
 # 工具栏
 toolbar-play = 播放
 toolbar-pause = 暂停
 toolbar-stop = 停止
 toolbar-resume = 继续
+mode-edit = 编辑
+toolbar-hide-panels = 运行时隐藏面板
+toolbar-mute-audio = 播放期间将游戏静音
+toolbar-slow-motion = 慢动作：以四分之一速度运行游戏
+toolbar-per-mode-layouts = 编辑、播放和暂停使用各自的停靠布局
+toolbar-clock = 当前播放会话的游戏时间
+toolbar-scrub = 拖动游戏时钟（重新运行 EvaluateAtTime）
+toolbar-undo-history = 撤销历史
+toolbar-redo-history = 重做历史
 
 # 设置面板
 settings-title = 编辑器设置
@@ -35,17 +52,104 @@ settings-save = 保存
 
 # 游戏视图
 game-view-press-play = 点击播放以启动游戏
+game-view-in-window = 正在独立窗口中运行
 
 # 文件对话框
 dialog-save-layout = 保存布局
 dialog-load-layout = 加载布局
+dialog-ok = 确定
+dialog-cancel = 取消
+dialog-reset-layout-confirm = 将停靠布局重置为默认排列？
+dialog-confirm-mode-change = 确认切换模式
+
+# Autosave
+autosave-recover-title = 恢复未保存的工作
+autosave-recover-message = 编辑器上次未正常关闭。是否恢复自动保存的布局、面板和撤销历史？
 
 # 检查器面板
 inspector-hierarchy = 层级
 inspector-components = 组件 (Components)
 inspector-select-hint = 选择一个实体来检查
+inspector-saved-selections = 保存的选择
+inspector-selection-name = 名称
+inspector-save-selection = 保存选择
+inspector-no-saved-selections = 没有保存的选择
+inspector-delete-saved-selection = 删除保存的选择
+inspector-select-all-with = 选择所有带此组件的实体
+inspector-locked = 已锁定
+inspector-expand = 展开
+inspector-toggle-visibility = 切换可见性
+inspector-lock-hint = 锁定（不可拾取或编辑）
 
 # 控制台面板
 console-clear = 清除
 console-auto-clear = 播放时自动清除
+console-pause-on-error = 出错时暂停
+console-pause-on-error-hint = 运行期间记录到错误时切换到暂停
+console-pause-exceptions = 出错暂停的例外
+console-pause-exceptions-prefix = 忽略目标以下列前缀开头的错误：
+console-pause-add-exception = + 添加例外
+console-levels = 级别
+console-levels-apply-to-layer = 应用到 tracing 层
+console-levels-apply-to-layer-hint = 在事件进入控制台之前丢弃被筛选的事件
+console-levels-add-target = + 添加 target
+console-levels-target-hint = target 前缀（留空 = 全部）
 console-filter-hint = 筛选...
+console-group-sessions = 按会话分组
+console-current-session-only = 仅当前会话
+console-session-header = ▶ 播放会话 #{ $id }（{ $time }）
+console-export = 导出
+console-export-filtered-log = 筛选结果 (.log)...
+console-export-filtered-json = 筛选结果 (.json)...
+console-export-all-log = 全部 (.log)...
+console-export-all-json = 全部 (.json)...
+console-export-title = 导出控制台日志
+console-copy = 复制
+console-tab-log = 日志
+console-tab-eval = 求值
+console-eval-hint = get <实体> <组件>.字段 — 输入 help 查看命令
+
+# 欢迎面板
+welcome-title = 欢迎
+welcome-start = 开始
+welcome-open-keybindings = 快捷键
+welcome-copy-template = 复制面板模板
+welcome-copy-template-hint = 将 WorkbenchPanel 骨架代码复制到剪贴板
+welcome-docs = 文档
+welcome-recent-layouts = 最近的布局
+welcome-recent-layouts-empty = 从视图菜单保存或加载的布局会显示在这里
+welcome-tip = 提示
+welcome-next-tip = 下一条提示
+welcome-show-on-startup = 启动时显示
+welcome-tip-detach = 将标签页拖出窗口，即可把面板移到独立的系统窗口中。
+welcome-tip-keybindings = 每个快捷键都可以在 **编辑 ▸ 快捷键** 中重新录制。
+welcome-tip-menu-keys = 按 **Alt** + 菜单首字母即可用键盘打开菜单。
+welcome-tip-undo-history = **撤销历史** 面板可以跳转到任意一步，启用后还会保留分支。
+welcome-tip-apply = 设置可以先应用而不保存，方便先试用。
+welcome-tip-settings = 工具可以通过 `app.register_settings` 添加自己的设置。
+welcome-tip-help = 右键点击标签页并选择 **?** 即可查看面板帮助。
+
+# 音频面板
+audio-title = 音频
+audio-master = 主音量
+audio-mute-on-pause = 暂停时静音
+audio-mute-on-pause-hint = 游戏暂停时静音所有声音
+audio-no-sinks = 没有正在播放的声音。游戏播放音频时，音频接收器会显示在这里。
+audio-mute = 静音
+audio-solo = 独奏：静音其他所有声音
+audio-volume = 音量，叠加在游戏自身音量之上
+
+# 标签页右键菜单
+tab-menu-close = 关闭
+tab-menu-close-others = 关闭其他
+tab-menu-close-all = 关闭组内全部
+tab-menu-lock = 锁定标签页
+tab-menu-unlock = 解锁标签页
+tab-menu-lock-hint = 锁定的标签页无法关闭或拖动
+tab-menu-float = 浮动到新窗口
+tab-menu-dock-back = 停靠回主窗口
+tab-menu-help = 帮助
+tab-menu-open-help = 在帮助面板中打开
+
+# 最大化面板
+dock-restore-layout = 恢复布局
@@ -0,0 +1,111 @@
+//! Keyboard accessibility: a configurable outline around the widget with keyboard focus, so
+//! Tab / arrow-key navigation through menus, panels and the hierarchy stays visible. With the
+//! `accesskit` feature, the primary egui context is also exposed to screen readers.
+
+use bevy::prelude::*;
+use bevy_egui::{EguiContexts, EguiPrimaryContextPass};
+
+#[cfg(feature = "accesskit")]
+mod accesskit;
+
+/// Draws the focus outline in the primary window and, with the `accesskit` feature, publishes
+/// the primary egui context to screen readers (added by `WorkbenchPlugin`).
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            EguiPrimaryContextPass,
+            focus_outline_system.after(crate::dock::tiles_ui_system),
+        );
+
+        #[cfg(feature = "accesskit")]
+        {
+            use bevy_egui::{EguiPostUpdateSet, EguiPreUpdateSet};
+            app.init_resource::<accesskit::EguiAccessNodes>()
+                .init_resource::<bevy::input_focus::InputFocus>()
+                .add_systems(
+                    PreUpdate,
+                    (
+                        accesskit::enable_accesskit_system,
+                        accesskit::forward_actions_system,
+                    )
+                        .after(EguiPreUpdateSet::ProcessInput)
+                        .before(EguiPreUpdateSet::BeginPass),
+                )
+                .add_systems(
+                    PostUpdate,
+                    accesskit::mirror_tree_system
+                        .after(EguiPostUpdateSet::ProcessOutput)
+                        .before(bevy::a11y::AccessibilitySystems::Update),
+                );
+        }
+    }
+}
+
+/// Accessibility configuration stored in settings.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AccessibilityConfig {
+    /// Outline the widget (or panel) that has keyboard focus.
+    #[serde(default = "default_focus_outline")]
+    pub focus_outline: bool,
+    /// Outline width in points.
+    #[serde(default = "default_focus_outline_width")]
+    pub focus_outline_width: f32,
+    /// Outline color as RGB (`None` = the palette's selection color).
+    #[serde(default)]
+    pub focus_outline_color: Option<[u8; 3]>,
+}
+
+fn default_focus_outline() -> bool {
+    true
+}
+
+fn default_focus_outline_width() -> f32 {
+    2.0
+}
+
+impl Default for AccessibilityConfig {
+    fn default() -> Self {
+        Self {
+            focus_outline: true,
+            focus_outline_width: 2.0,
+            focus_outline_color: None,
+        }
+    }
+}
+
+fn focus_outline_system(
+    mut contexts: EguiContexts,
+    settings: Res<crate::config::WorkbenchSettings>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    draw_focus_outline(ctx, &settings.accessibility);
+}
+
+/// Outline the widget with keyboard focus in `ctx`, on top of everything else.
+pub(crate) fn draw_focus_outline(ctx: &egui::Context, config: &AccessibilityConfig) {
+    if !config.focus_outline {
+        return;
+    }
+    let Some(response) = ctx
+        .memory(|m| m.focused())
+        .and_then(|id| ctx.read_response(id))
+    else {
+        return;
+    };
+    let color = config.focus_outline_color.map_or_else(
+        || crate::theme::palette(ctx).selection,
+        |[r, g, b]| egui::Color32::from_rgb(r, g, b),
+    );
+    let layer = egui::LayerId::new(
+        egui::Order::Tooltip,
+        egui::Id::new("workbench_focus_outline"),
+    );
+    ctx.layer_painter(layer).rect_stroke(
+        response.rect,
+        2.0,
+        egui::Stroke::new(config.focus_outline_width, color),
+        egui::StrokeKind::Inside,
+    );
+}
@@ -0,0 +1,140 @@
+//! # accesskit.rs
+//!
+//! # accesskit.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Screen-reader support for the primary egui context (feature `accesskit`). While an assistive
+//! technology is attached (`AccessibilityRequested`), egui builds an AccessKit tree every pass.
+//! Bevy's winit backend only publishes the `AccessibilityNode` entities under the primary
+//! window, so each egui node is mirrored onto an entity (children re-linked with `ChildOf`,
+//! stale nodes despawned), egui's focused node becomes `InputFocus`, and action requests from
+//! the screen reader (click, focus, scroll …) are fed back into egui's input.
+//!
+//! 为主 egui 上下文提供屏幕阅读器支持（`accesskit` 特性）。当辅助技术接入时
+//! （`AccessibilityRequested`），egui 每一帧都会构建 AccessKit 树。Bevy 的 winit 后端只发布主窗口下的
+//! `AccessibilityNode` 实体，因此每个 egui 节点都会镜像到一个实体上（子节点通过 `ChildOf` 重新关联，
+//! 过期节点被销毁），egui 的焦点节点成为 `InputFocus`，屏幕阅读器发出的操作请求
+//! （点击、聚焦、滚动等）则回送到 egui 的输入中。
+
+use bevy::a11y::accesskit::{NodeId, TreeUpdate};
+use bevy::a11y::{AccessibilityNode, AccessibilityRequested, ActionRequest};
+use bevy::input_focus::InputFocus;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_egui::{EguiContext, EguiInput, EguiOutput, PrimaryEguiContext};
+
+use crate::inspector::WorkbenchInternal;
+
+/// Entities mirroring the egui AccessKit nodes of the primary context.
+#[derive(Resource, Default)]
+pub(super) struct EguiAccessNodes {
+    entities: HashMap<NodeId, Entity>,
+}
+
+impl EguiAccessNodes {
+    /// The egui node mirrored by the entity Bevy publishes as `target`.
+    fn egui_node(&self, target: NodeId) -> Option<NodeId> {
+        self.entities
+            .iter()
+            .find_map(|(&node, e)| (e.to_bits() == target.0).then_some(node))
+    }
+}
+
+/// Turn egui's AccessKit output on while a screen reader is attached.
+pub(super) fn enable_accesskit_system(
+    requested: Res<AccessibilityRequested>,
+    mut contexts: Query<&mut EguiContext, With<PrimaryEguiContext>>,
+) {
+    if !requested.get() {
+        return;
+    }
+    for mut context in &mut contexts {
+        context.get_mut().enable_accesskit();
+    }
+}
+
+/// Mirror the last pass's AccessKit tree onto `AccessibilityNode` entities.
+pub(super) fn mirror_tree_system(
+    mut commands: Commands,
+    mut nodes: ResMut<EguiAccessNodes>,
+    mut focus: ResMut<InputFocus>,
+    mut outputs: Query<&mut EguiOutput, With<PrimaryEguiContext>>,
+) {
+    let Some(update) = outputs
+        .iter_mut()
+        .find_map(|mut output| output.platform_output.accesskit_update.take())
+    else {
+        return;
+    };
+    let TreeUpdate {
+        nodes: updated,
+        tree,
+        focus: focused,
+    } = update;
+
+    let mut seen = HashMap::default();
+    let mut children = Vec::new();
+    for (id, mut node) in updated {
+        let entity = match nodes.entities.get(&id) {
+            Some(&entity) => entity,
+            None => commands
+                .spawn((Name::new("egui AccessKit node"), WorkbenchInternal))
+                .id(),
+        };
+        children.push((entity, node.children().to_vec()));
+        // Bevy derives children from the entity hierarchy.
+        node.clear_children();
+        commands.entity(entity).insert(AccessibilityNode(node));
+        seen.insert(id, entity);
+    }
+    for (parent, child_ids) in children {
+        let child_entities: Vec<Entity> = child_ids
+            .iter()
+            .filter_map(|id| seen.get(id).or_else(|| nodes.entities.get(id)).copied())
+            .collect();
+        commands.entity(parent).replace_children(&child_entities);
+    }
+    let owns_focus = focus
+        .0
+        .is_some_and(|e| nodes.entities.values().any(|&n| n == e));
+    // egui sends its whole tree each pass: anything not in it is gone.
+    for (id, entity) in nodes.entities.drain() {
+        if !seen.contains_key(&id) {
+            commands.entity(entity).despawn();
+        }
+    }
+    nodes.entities = seen;
+
+    let root = tree.map(|tree| tree.root);
+    let focused_entity = (Some(focused) != root)
+        .then(|| nodes.entities.get(&focused).copied())
+        .flatten();
+    if focused_entity.is_some() || owns_focus {
+        focus.0 = focused_entity;
+    }
+}
+
+/// Forward screen-reader action requests on mirrored nodes to egui.
+pub(super) fn forward_actions_system(
+    mut requests: MessageReader<ActionRequest>,
+    nodes: Res<EguiAccessNodes>,
+    mut inputs: Query<&mut EguiInput, With<PrimaryEguiContext>>,
+) {
+    let Ok(mut input) = inputs.single_mut() else {
+        requests.clear();
+        return;
+    };
+    for request in requests.read() {
+        if let Some(target) = nodes.egui_node(request.target) {
+            let mut request = request.0.clone();
+            request.target = target;
+            input
+                .0
+                .events
+                .push(egui::Event::AccessKitActionRequest(request));
+        }
+    }
+}
@@ -0,0 +1,354 @@
+//! Audio preview panel: per-sink volume, mute and solo, master volume and mute on pause, plus
+//! the toolbar's Mute audio toggle (`audio` feature). The preview's volume and mutes are applied
+//! on top of whatever the game sets and undone when cleared.
+
+use bevy::audio::{AudioSink, AudioSinkPlayback, GlobalVolume, Volume};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::bench_ui::{Icon, icon};
+use crate::config::{ConfigPath, WorkbenchSettings};
+use crate::dock::WorkbenchPanel;
+use crate::mode::{EditorMode, ModeController};
+
+/// Audio preview settings, stored in `WorkbenchSettings`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    /// Multiplier applied on top of every sink's own volume.
+    pub master_volume: f32,
+    /// Mute every sink while the editor is in Pause mode.
+    pub mute_on_pause: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            mute_on_pause: true,
+        }
+    }
+}
+
+/// Mute, solo and volume set from the [`AudioPanel`]. The preview only layers its own changes
+/// on top of each sink: the game's volume and mutes are kept, and restored once the panel no
+/// longer changes a sink.
+#[derive(Resource, Default)]
+pub struct AudioPreview {
+    /// Sinks muted from the panel.
+    pub muted: HashSet<Entity>,
+    /// Sink playing solo; every other sink is muted.
+    pub solo: Option<Entity>,
+    /// Volume multiplier of each sink set from the panel (1.0 when absent).
+    gains: HashMap<Entity, f32>,
+    /// What the preview changed on each sink, to update and undo it.
+    overrides: HashMap<Entity, SinkOverride>,
+}
+
+/// The preview's change to one sink.
+#[derive(Debug, Clone, Copy)]
+struct SinkOverride {
+    /// The sink's own volume, as last set by the game.
+    game_volume: f32,
+    /// Volume the preview last wrote; anything else was set by the game since.
+    written: f32,
+    /// Whether the preview muted the sink (rather than the game).
+    muted: bool,
+}
+
+impl AudioPreview {
+    /// Whether the panel keeps `entity` silent (muted, or another sink is solo).
+    pub fn is_silenced(&self, entity: Entity) -> bool {
+        self.muted.contains(&entity) || self.solo.is_some_and(|solo| solo != entity)
+    }
+
+    /// Volume multiplier of `entity` set from the panel.
+    pub fn gain(&self, entity: Entity) -> f32 {
+        self.gains.get(&entity).copied().unwrap_or(1.0)
+    }
+
+    /// Set the volume multiplier of `entity`; 1.0 leaves the game's volume alone.
+    pub fn set_gain(&mut self, entity: Entity, gain: f32) {
+        if gain == 1.0 {
+            self.gains.remove(&entity);
+        } else {
+            self.gains.insert(entity, gain);
+        }
+    }
+}
+
+/// Plugin for the audio preview panel.
+pub struct AudioPreviewPlugin;
+
+impl Plugin for AudioPreviewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioPreview>()
+            .init_resource::<MutedGlobalVolume>()
+            .add_systems(Update, (mute_audio_system, audio_preview_system));
+    }
+}
+
+/// `GlobalVolume` saved while [`ModeController::mute_audio`] silences a Play session.
+#[derive(Resource, Default)]
+struct MutedGlobalVolume(Option<Volume>);
+
+/// Silences `GlobalVolume` during Play and Pause while [`ModeController::mute_audio`] is on,
+/// and restores it on Stop or when the toggle is turned off.
+fn mute_audio_system(
+    mode: Res<State<EditorMode>>,
+    controller: Res<ModeController>,
+    mut global: ResMut<GlobalVolume>,
+    mut saved: ResMut<MutedGlobalVolume>,
+) {
+    let mute = controller.mute_audio && *mode.get() != EditorMode::Edit;
+    match (mute, saved.0) {
+        (true, None) => saved.0 = Some(std::mem::replace(&mut global.volume, Volume::SILENT)),
+        (false, Some(volume)) => {
+            global.volume = volume;
+            saved.0 = None;
+        }
+        _ => {}
+    }
+}
+
+/// Layers the panel's volume, the master volume and the panel's (and editor mode's) mutes on
+/// top of every sink, and restores a sink's own state once none of them applies any more.
+pub fn audio_preview_system(
+    mode: Res<State<EditorMode>>,
+    controller: Res<ModeController>,
+    settings: Res<WorkbenchSettings>,
+    mut preview: ResMut<AudioPreview>,
+    mut sinks: Query<(Entity, &mut AudioSink)>,
+) {
+    let master = settings.audio.master_volume;
+    // `GlobalVolume` only reaches sinks spawned after it changes; mute the playing ones too.
+    let silenced = match mode.get() {
+        EditorMode::Edit => false,
+        EditorMode::Play => controller.mute_audio,
+        EditorMode::Pause => controller.mute_audio || settings.audio.mute_on_pause,
+    };
+    // Bookkeeping only; the panel reads it but needs no change notification.
+    let preview = preview.bypass_change_detection();
+    for (entity, mut sink) in &mut sinks {
+        let previous = preview.overrides.get(&entity).copied();
+        let current = sink.volume().to_linear();
+        let game_volume = match previous {
+            Some(change) if change.written == current => change.game_volume,
+            _ => current,
+        };
+
+        let mut muted = previous.is_some_and(|change| change.muted);
+        if silenced || preview.is_silenced(entity) {
+            if !sink.is_muted() {
+                sink.mute();
+                muted = true;
+            }
+        } else if muted {
+            if sink.is_muted() {
+                sink.unmute();
+            }
+            muted = false;
+        }
+
+        let gain = preview.gain(entity) * master;
+        if gain == 1.0 && !muted {
+            if previous.is_some() && current != game_volume {
+                sink.set_volume(Volume::Linear(game_volume));
+            }
+            preview.overrides.remove(&entity);
+            continue;
+        }
+        let written = game_volume * gain;
+        if current != written {
+            sink.set_volume(Volume::Linear(written));
+        }
+        preview.overrides.insert(
+            entity,
+            SinkOverride {
+                game_volume,
+                written,
+                muted,
+            },
+        );
+    }
+    preview
+        .overrides
+        .retain(|entity, _| sinks.contains(*entity));
+    preview.gains.retain(|entity, _| sinks.contains(*entity));
+    preview.muted.retain(|entity| sinks.contains(*entity));
+    preview.solo = preview.solo.filter(|solo| sinks.contains(*solo));
+}
+
+/// One playing sink as listed in the panel.
+struct SinkRow {
+    entity: Entity,
+    label: String,
+    /// The panel's volume multiplier.
+    gain: f32,
+}
+
+/// Localized strings of the audio panel.
+struct AudioStrings {
+    title: String,
+    master: String,
+    mute_on_pause: String,
+    mute_on_pause_hint: String,
+    no_sinks: String,
+    mute: String,
+    solo: String,
+    volume: String,
+}
+
+impl AudioStrings {
+    fn new(world: &World) -> Self {
+        let i18n = world.get_resource::<crate::i18n::I18n>();
+        let t = |id: &str| i18n.map_or(id, |i18n| i18n.t(id)).to_owned();
+        Self {
+            title: t("audio-title"),
+            master: t("audio-master"),
+            mute_on_pause: t("audio-mute-on-pause"),
+            mute_on_pause_hint: t("audio-mute-on-pause-hint"),
+            no_sinks: t("audio-no-sinks"),
+            mute: t("audio-mute"),
+            solo: t("audio-solo"),
+            volume: t("audio-volume"),
+        }
+    }
+}
+
+/// Built-in panel listing the playing audio sinks with volume, mute and solo controls.
+#[derive(Default)]
+pub struct AudioPanel {
+    /// Localized tab title, once drawn.
+    title: Option<String>,
+}
+
+impl WorkbenchPanel for AudioPanel {
+    fn id(&self) -> &str {
+        "workbench_audio"
+    }
+
+    fn title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| "Audio".to_string())
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let strings = AudioStrings::new(world);
+        master_ui(ui, world, &strings);
+        ui.separator();
+
+        let rows = sink_rows(world);
+        if rows.is_empty() {
+            ui.weak(&strings.no_sinks);
+        } else {
+            world.resource_scope(|_, mut preview: Mut<AudioPreview>| {
+                egui::Grid::new("audio_sinks")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for row in rows {
+                            sink_row_ui(ui, &mut preview, row, &strings);
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+        self.title = Some(strings.title);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+/// Master volume slider and mute-on-pause toggle, persisted to the settings file.
+fn master_ui(ui: &mut egui::Ui, world: &mut World, strings: &AudioStrings) {
+    let mut audio = world.resource::<WorkbenchSettings>().audio.clone();
+    let mut save = false;
+    ui.horizontal(|ui| {
+        ui.label(&strings.master);
+        let response = ui.add(
+            egui::Slider::new(&mut audio.master_volume, 0.0..=1.0)
+                .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+        );
+        save |= response.drag_stopped() || (response.changed() && !response.dragged());
+        save |= ui
+            .checkbox(&mut audio.mute_on_pause, &strings.mute_on_pause)
+            .on_hover_text(&strings.mute_on_pause_hint)
+            .changed();
+    });
+    let path = world.get_resource::<ConfigPath>().map(|p| p.0.clone());
+    let mut settings = world.resource_mut::<WorkbenchSettings>();
+    if settings.audio != audio {
+        settings.audio = audio;
+    }
+    if save && let Some(path) = path {
+        settings.save(&path);
+    }
+}
+
+/// Every playing sink, labeled by `Name` when it has one.
+fn sink_rows(world: &mut World) -> Vec<SinkRow> {
+    let mut query = world.query_filtered::<(Entity, Option<&Name>), With<AudioSink>>();
+    let preview = world.resource::<AudioPreview>();
+    let mut rows: Vec<SinkRow> = query
+        .iter(world)
+        .map(|(entity, name)| SinkRow {
+            entity,
+            label: name.map_or_else(|| format!("{entity}"), |n| n.as_str().to_string()),
+            gain: preview.gain(entity),
+        })
+        .collect();
+    rows.sort_by_key(|row| row.entity);
+    rows
+}
+
+/// Mute and solo toggles and the volume slider of one sink.
+fn sink_row_ui(
+    ui: &mut egui::Ui,
+    preview: &mut AudioPreview,
+    row: SinkRow,
+    strings: &AudioStrings,
+) {
+    let SinkRow {
+        entity,
+        label,
+        mut gain,
+    } = row;
+    ui.label(label);
+
+    let muted = preview.muted.contains(&entity);
+    if ui
+        .selectable_label(muted, icon(Icon::Mute))
+        .on_hover_text(&strings.mute)
+        .clicked()
+    {
+        if muted {
+            preview.muted.remove(&entity);
+        } else {
+            preview.muted.insert(entity);
+        }
+    }
+    let solo = preview.solo == Some(entity);
+    if ui
+        .selectable_label(solo, "S")
+        .on_hover_text(&strings.solo)
+        .clicked()
+    {
+        preview.solo = (!solo).then_some(entity);
+    }
+
+    if ui
+        .add(egui::Slider::new(&mut gain, 0.0..=1.0).show_value(false))
+        .on_hover_text(&strings.volume)
+        .changed()
+    {
+        preview.set_gain(entity, gain);
+    }
+}
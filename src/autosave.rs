@@ -0,0 +1,193 @@
+//! Periodic autosave of editor state, with crash recovery on the next launch.
+//!
+//! A lock file marks a running session; it is removed on a clean exit. If it is still there at
+//! startup the previous session crashed, and the user is offered to restore the autosaved
+//! layout, panel states and serializable undo history.
+
+use crate::dock::TileLayoutState;
+use crate::undo::{SerializedAction, UndoAction, UndoStack};
+use bevy::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Restores a serialized undo action of one registered kind.
+pub type UndoDeserializer = fn(&str) -> Option<Box<dyn UndoAction>>;
+
+/// Resource holding the autosave directory (project-local).
+#[derive(Resource)]
+pub struct AutosaveDir(pub PathBuf);
+
+impl Default for AutosaveDir {
+    /// Default: `.workbench/autosave/` in the current working directory.
+    fn default() -> Self {
+        Self(PathBuf::from(".workbench/autosave"))
+    }
+}
+
+impl AutosaveDir {
+    fn lock(&self) -> PathBuf {
+        self.0.join("session.lock")
+    }
+
+    fn layout(&self) -> PathBuf {
+        self.0.join("layout.json")
+    }
+
+    fn panels(&self) -> PathBuf {
+        self.0.join("panels.json")
+    }
+
+    fn undo(&self) -> PathBuf {
+        self.0.join("undo.json")
+    }
+
+    /// Delete all autosaved state (keeps the session lock).
+    fn clear(&self) {
+        for path in [self.layout(), self.panels(), self.undo()] {
+            crate::storage::remove(&path);
+        }
+    }
+}
+
+/// Deserializers for undo actions that opt into autosave, keyed by `SerializedAction::kind`.
+#[derive(Resource, Default)]
+pub struct UndoKinds(pub HashMap<String, UndoDeserializer>);
+
+/// Autosave timer and the last content written to each file.
+#[derive(Resource, Default)]
+pub(crate) struct AutosaveState {
+    elapsed: f32,
+    written: HashMap<PathBuf, String>,
+    /// Set at startup when the previous session didn't exit cleanly; cleared once answered.
+    recovery_pending: bool,
+    prompted: bool,
+}
+
+/// Marks the session as running and checks whether the last one crashed.
+pub fn autosave_startup_system(dir: Res<AutosaveDir>, mut state: ResMut<AutosaveState>) {
+    let crashed = crate::storage::read_string(&dir.lock()).is_some();
+    let has_data = [dir.layout(), dir.panels(), dir.undo()]
+        .iter()
+        .any(|path| crate::storage::read_string(path).is_some());
+    state.recovery_pending = crashed && has_data;
+    if !crashed {
+        dir.clear();
+    }
+    if let Err(e) = crate::storage::write_string(&dir.lock(), "running") {
+        warn!("Failed to write autosave lock: {e}");
+    }
+}
+
+/// Offers to restore autosaved state after a crash (once, on the first frame).
+pub fn autosave_recovery_prompt_system(
+    mut state: ResMut<AutosaveState>,
+    mut dialogs: ResMut<crate::dialogs::Dialogs>,
+    i18n: Res<crate::i18n::I18n>,
+) {
+    if !state.recovery_pending || state.prompted {
+        return;
+    }
+    state.prompted = true;
+    dialogs.confirm(
+        i18n.t("autosave-recover-title"),
+        i18n.t("autosave-recover-message"),
+        |world, ok| {
+            if ok {
+                restore_autosave(world);
+            }
+            world.resource::<AutosaveDir>().clear();
+            world.resource_mut::<AutosaveState>().recovery_pending = false;
+        },
+    );
+}
+
+/// Loads the autosaved layout, panel states and undo history into the world.
+fn restore_autosave(world: &mut World) {
+    let dir = world.resource::<AutosaveDir>();
+    let layout = crate::storage::read_string(&dir.layout());
+    let panels: BTreeMap<String, String> = crate::storage::read_string(&dir.panels())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let undo: Vec<SerializedAction> = crate::storage::read_string(&dir.undo())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let mut tile_state = world.resource_mut::<TileLayoutState>();
+    if let Some(layout) = layout {
+        tile_state.pending_layout_load = Some((layout, "autosave".to_string()));
+    }
+    for (panel_id, panel_state) in &panels {
+        if let Some(panel) = tile_state.panel_dyn_mut(panel_id) {
+            panel.load_state(panel_state);
+        }
+    }
+
+    let kinds = world.resource::<UndoKinds>();
+    let actions: Vec<_> = undo
+        .iter()
+        .filter_map(|action| {
+            let deserialize = kinds.0.get(&action.kind);
+            if deserialize.is_none() {
+                warn!("No undo deserializer registered for '{}'", action.kind);
+            }
+            deserialize.and_then(|f| f(&action.data))
+        })
+        .collect();
+    let mut undo_stack = world.resource_mut::<UndoStack>();
+    for action in actions {
+        undo_stack.push_boxed(action);
+    }
+    info!("Restored autosaved editor state");
+}
+
+/// Writes `content` unless it matches what was last written there.
+fn write_if_changed(state: &mut AutosaveState, path: &Path, content: String) {
+    if state.written.get(path) == Some(&content) {
+        return;
+    }
+    if let Err(e) = crate::storage::write_string(path, &content) {
+        warn!("Failed to autosave {}: {e}", path.display());
+        return;
+    }
+    state.written.insert(path.to_path_buf(), content);
+}
+
+/// Periodically writes layout, panel states and the serializable undo tail.
+pub fn autosave_system(
+    time: Res<Time<Real>>,
+    config: Res<crate::WorkbenchConfig>,
+    dir: Res<AutosaveDir>,
+    mut state: ResMut<AutosaveState>,
+    tile_state: Res<TileLayoutState>,
+    undo_stack: Res<UndoStack>,
+) {
+    let Some(interval) = config.autosave_interval else {
+        return;
+    };
+    state.elapsed += time.delta_secs();
+    // Don't overwrite a crashed session's data before the user has answered the prompt.
+    if state.elapsed < interval || state.recovery_pending {
+        return;
+    }
+    state.elapsed = 0.0;
+
+    // The play layout is temporary; keep the last edit-mode layout instead.
+    if !tile_state.play_layout_active()
+        && let Some(layout) = tile_state.layout_json()
+    {
+        write_if_changed(&mut state, &dir.layout(), layout);
+    }
+    let panels = serde_json::to_string_pretty(&tile_state.panel_states()).expect("serialize");
+    write_if_changed(&mut state, &dir.panels(), panels);
+    let undo = serde_json::to_string(&undo_stack.serialize_tail()).expect("serialize");
+    write_if_changed(&mut state, &dir.undo(), undo);
+}
+
+/// Clears autosave data and the session lock on a clean exit.
+pub fn autosave_exit_system(mut exits: MessageReader<AppExit>, dir: Res<AutosaveDir>) {
+    if exits.read().next().is_none() {
+        return;
+    }
+    dir.clear();
+    crate::storage::remove(&dir.lock());
+}
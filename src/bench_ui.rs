@@ -2,8 +2,29 @@
 //!
 //! These provide editing widgets for Bevy types that egui doesn't natively support.
 //! Basic egui controls (button, slider, checkbox) should be used directly from egui.
+//! Wrap them in [`undo_scope`] to make each drag one undo step.
 
-use bevy::math::{Vec2, Vec3};
+mod curve;
+#[cfg(feature = "game-view")]
+pub(crate) mod eyedropper;
+mod icon;
+mod markdown;
+mod node_graph;
+mod plot;
+mod undo_scope;
+
+pub use curve::{CurveKey, curve, sample_curve};
+#[cfg(feature = "game-view")]
+pub use eyedropper::eyedropper_button;
+#[cfg(feature = "icon-font")]
+pub(crate) use icon::install_icon_font;
+pub use icon::{Icon, icon};
+pub use markdown::markdown;
+pub use node_graph::{GraphLink, GraphNode, NodeGraph, NodeId, NodePort, PortRef, node_graph};
+pub use plot::{PlotSeries, plot_series};
+pub use undo_scope::undo_scope;
+
+use bevy::math::{EulerRot, Quat, Vec2, Vec3};
 use bevy::transform::components::Transform;
 use egui::Ui;
 
@@ -62,55 +83,82 @@ pub fn vec3(ui: &mut Ui, label: &str, value: &mut Vec3) -> bool {
     changed
 }
 
-/// Displays a color picker for a Bevy `Color`.
+/// Displays a color picker for a Bevy `Color`, with an eyedropper button sampling the Game
+/// View when the `game-view` feature is enabled.
 /// Returns `true` if the value was changed.
 pub fn color(ui: &mut Ui, label: &str, value: &mut bevy::color::Color) -> bool {
     let linear = value.to_linear();
     let mut rgba = [linear.red, linear.green, linear.blue, linear.alpha];
-    let response = ui.horizontal(|ui| {
+    let edited = ui.horizontal(|ui| {
         ui.label(label);
-        ui.color_edit_button_rgba_unmultiplied(&mut rgba)
+        let changed = ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed();
+        #[cfg(feature = "game-view")]
+        {
+            let id = ui.id().with("bench_ui_eyedropper");
+            if let Some(sampled) = eyedropper::eyedropper_button(ui, id) {
+                return Some(sampled);
+            }
+        }
+        changed.then(|| {
+            bevy::color::Color::LinearRgba(bevy::color::LinearRgba::new(
+                rgba[0], rgba[1], rgba[2], rgba[3],
+            ))
+        })
     });
-    if response.inner.changed() {
-        *value = bevy::color::Color::LinearRgba(bevy::color::LinearRgba::new(
-            rgba[0], rgba[1], rgba[2], rgba[3],
-        ));
-        true
-    } else {
-        false
+    match edited.inner {
+        Some(color) => {
+            *value = color;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Displays an editable rotation as Euler angles in degrees (pitch X, yaw Y, roll Z).
+/// Returns `true` if the value was changed.
+///
+/// The last edited angles are remembered while they still describe `value`, so dragging
+/// through ±90° pitch doesn't snap to an equivalent angle triple mid-drag.
+pub fn rotation(ui: &mut Ui, label: &str, value: &mut Quat) -> bool {
+    let id = ui.id().with(("bench_ui_euler", label));
+    let cached = ui.data(|d| d.get_temp::<(Quat, Vec3)>(id));
+    let mut rot = match cached {
+        Some((quat, rot)) if quat.abs_diff_eq(*value, 1e-5) => rot,
+        _ => {
+            let (yaw, pitch, roll) = value.to_euler(EulerRot::YXZ);
+            Vec3::new(pitch, yaw, roll).map(f32::to_degrees)
+        }
+    };
+    let changed = vec3(ui, label, &mut rot);
+    if changed {
+        *value = Quat::from_euler(
+            EulerRot::YXZ,
+            rot.y.to_radians(),
+            rot.x.to_radians(),
+            rot.z.to_radians(),
+        );
     }
+    ui.data_mut(|d| d.insert_temp(id, (*value, rot)));
+    changed
+}
+
+/// Displays Position/Rotation/Scale rows for a Transform, without a header.
+/// Returns `true` if any value was changed.
+pub fn transform_fields(ui: &mut Ui, value: &mut Transform) -> bool {
+    let mut changed = vec3(ui, "Position", &mut value.translation);
+    changed |= rotation(ui, "Rotation", &mut value.rotation);
+    changed |= vec3(ui, "Scale", &mut value.scale);
+    changed
 }
 
 /// Displays a full Transform editor with collapsible Position/Rotation/Scale groups.
 /// Returns `true` if any value was changed.
 pub fn transform(ui: &mut Ui, label: &str, value: &mut Transform) -> bool {
-    let mut changed = false;
     egui::CollapsingHeader::new(label)
         .default_open(true)
-        .show(ui, |ui| {
-            if vec3(ui, "Position", &mut value.translation) {
-                changed = true;
-            }
-            // Rotation as euler angles (degrees)
-            let (mut yaw, mut pitch, mut roll) = value.rotation.to_euler(bevy::math::EulerRot::YXZ);
-            yaw = yaw.to_degrees();
-            pitch = pitch.to_degrees();
-            roll = roll.to_degrees();
-            let mut rot = Vec3::new(pitch, yaw, roll);
-            if vec3(ui, "Rotation", &mut rot) {
-                value.rotation = bevy::math::Quat::from_euler(
-                    bevy::math::EulerRot::YXZ,
-                    rot.y.to_radians(),
-                    rot.x.to_radians(),
-                    rot.z.to_radians(),
-                );
-                changed = true;
-            }
-            if vec3(ui, "Scale", &mut value.scale) {
-                changed = true;
-            }
-        });
-    changed
+        .show(ui, |ui| transform_fields(ui, value))
+        .body_returned
+        .unwrap_or(false)
 }
 
 /// Displays an entity picker dropdown from a provided list.
@@ -147,6 +195,71 @@ pub fn entity_picker(
     changed
 }
 
+/// Display name for an entity: its `Name` (if any) followed by the entity id.
+fn entity_display_name(entity: bevy::ecs::world::EntityRef) -> String {
+    match entity.get::<bevy::prelude::Name>() {
+        Some(name) => format!("{name} ({:?})", entity.id()),
+        None => format!("Entity {:?}", entity.id()),
+    }
+}
+
+/// Displays a searchable entity picker dropdown populated from the World.
+/// Only entities passing `filter` are listed; restrict by component type with
+/// e.g. `|e| e.contains::<Camera>()`. Returns `true` if the selection changed.
+pub fn entity_picker_world(
+    ui: &mut Ui,
+    world: &bevy::prelude::World,
+    filter: impl Fn(bevy::ecs::world::EntityRef) -> bool,
+    selected: &mut Option<bevy::prelude::Entity>,
+) -> bool {
+    let mut changed = false;
+    let label_text = selected
+        .and_then(|e| world.get_entity(e).ok())
+        .map_or_else(|| "None".to_string(), entity_display_name);
+    let search_id = ui.id().with("bench_ui_entity_picker_search");
+    let mut search: String = ui.data(|d| d.get_temp(search_id)).unwrap_or_default();
+
+    egui::ComboBox::from_label("Entity")
+        .selected_text(label_text)
+        .show_ui(ui, |ui| {
+            let search_box = ui.add(egui::TextEdit::singleline(&mut search).hint_text("🔍 Search"));
+            if ui.memory(|m| m.focused().is_none()) {
+                search_box.request_focus();
+            }
+            let needle = search.to_lowercase();
+            let Some(mut query) = world.try_query::<bevy::ecs::world::EntityRef>() else {
+                return;
+            };
+            let mut entries: Vec<_> = query
+                .iter(world)
+                .filter(|e| filter(*e))
+                .map(|e| (e.id(), entity_display_name(e)))
+                .filter(|(_, name)| name.to_lowercase().contains(&needle))
+                .collect();
+            entries.sort_by_key(|(entity, _)| *entity);
+
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    if ui.selectable_label(selected.is_none(), "None").clicked() {
+                        *selected = None;
+                        changed = true;
+                    }
+                    for (entity, name) in entries {
+                        if ui
+                            .selectable_label(*selected == Some(entity), name)
+                            .clicked()
+                        {
+                            *selected = Some(entity);
+                            changed = true;
+                        }
+                    }
+                });
+        });
+    ui.data_mut(|d| d.insert_temp(search_id, search));
+    changed
+}
+
 /// Displays a read-only list of component names attached to an entity.
 pub fn component_list(ui: &mut Ui, world: &bevy::prelude::World, entity: bevy::prelude::Entity) {
     let Some(entity_ref) = world.get_entity(entity).ok() else {
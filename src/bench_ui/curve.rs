@@ -0,0 +1,499 @@
+//! # curve.rs
+//!
+//! # curve.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! A keyframe curve editor for 1D animation and tween curves. Keys are interpolated as a cubic
+//! Hermite spline; the widget draws a grid, lets users drag keys and tangent handles, add keys
+//! with a double-click, remove them with a right-click, and zoom/pan the view. View state lives
+//! in egui memory, so callers only own the key list.
+//!
+//! 用于一维动画与补间曲线的关键帧曲线编辑器。关键帧之间使用三次 Hermite 样条插值；该控件绘制网格，
+//! 支持拖拽关键帧与切线手柄、双击添加关键帧、右键删除关键帧，以及缩放和平移视图。视图状态保存在
+//! egui 内存中，调用方只需持有关键帧列表。
+
+use egui::{Color32, Pos2, Rect, Response, Sense, Stroke, Ui, Vec2};
+
+/// Height of the curve canvas in points.
+const CANVAS_HEIGHT: f32 = 180.0;
+/// Radius of a key handle in points.
+const KEY_RADIUS: f32 = 4.5;
+/// Length of a tangent handle in points.
+const TANGENT_LENGTH: f32 = 40.0;
+/// Minimum time gap kept between neighboring keys while dragging.
+const MIN_KEY_GAP: f32 = 1e-3;
+/// Smallest and largest span the view zooms to on either axis, in curve units.
+const MIN_VIEW_SPAN: f32 = 1e-3;
+const MAX_VIEW_SPAN: f32 = 1e6;
+/// Upper bound on grid lines drawn per axis.
+const MAX_GRID_LINES: usize = 500;
+
+/// A keyframe of a 1D curve, interpolated as a cubic Hermite spline.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CurveKey {
+    pub time: f32,
+    pub value: f32,
+    /// Slope arriving at this key (value per unit of time).
+    pub in_tangent: f32,
+    /// Slope leaving this key (value per unit of time).
+    pub out_tangent: f32,
+}
+
+impl CurveKey {
+    /// A key with flat tangents.
+    pub fn new(time: f32, value: f32) -> Self {
+        Self {
+            time,
+            value,
+            in_tangent: 0.0,
+            out_tangent: 0.0,
+        }
+    }
+}
+
+/// Evaluate a key list (sorted by time) at `time`. Values are held flat outside the keys.
+pub fn sample_curve(keys: &[CurveKey], time: f32) -> f32 {
+    let (Some(first), Some(last)) = (keys.first(), keys.last()) else {
+        return 0.0;
+    };
+    if time <= first.time {
+        return first.value;
+    }
+    if time >= last.time {
+        return last.value;
+    }
+    let i = keys.partition_point(|k| k.time <= time);
+    let (a, b) = (keys[i - 1], keys[i]);
+    let dt = b.time - a.time;
+    if dt <= f32::EPSILON {
+        return b.value;
+    }
+    let s = (time - a.time) / dt;
+    let (s2, s3) = (s * s, s * s * s);
+    (2.0 * s3 - 3.0 * s2 + 1.0) * a.value
+        + (s3 - 2.0 * s2 + s) * dt * a.out_tangent
+        + (-2.0 * s3 + 3.0 * s2) * b.value
+        + (s3 - s2) * dt * b.in_tangent
+}
+
+/// Visible region and selection, kept in egui memory per widget.
+#[derive(Clone, Copy)]
+struct CurveView {
+    /// Curve-space bounds: x = time, y = value.
+    bounds: Rect,
+    selected: Option<usize>,
+}
+
+impl CurveView {
+    /// A view framing all keys with some padding.
+    fn fit(keys: &[CurveKey]) -> Self {
+        let mut bounds = Rect::NOTHING;
+        for key in keys {
+            bounds.extend_with(Pos2::new(key.time, key.value));
+        }
+        if !bounds.is_positive() {
+            let center = if bounds.is_finite() {
+                bounds.center()
+            } else {
+                Pos2::new(0.5, 0.5)
+            };
+            bounds = Rect::from_center_size(center, Vec2::splat(1.0)).union(bounds);
+        }
+        Self {
+            bounds: bounds.expand2(bounds.size() * 0.1),
+            selected: None,
+        }
+    }
+
+    /// Curve units per screen point on each axis.
+    fn units_per_point(&self, rect: Rect) -> Vec2 {
+        self.bounds.size() / rect.size()
+    }
+
+    fn screen_pos(&self, rect: Rect, time: f32, value: f32) -> Pos2 {
+        let upp = self.units_per_point(rect);
+        Pos2::new(
+            rect.left() + (time - self.bounds.left()) / upp.x,
+            rect.bottom() - (value - self.bounds.top()) / upp.y,
+        )
+    }
+
+    fn curve_pos(&self, rect: Rect, pos: Pos2) -> (f32, f32) {
+        let upp = self.units_per_point(rect);
+        (
+            self.bounds.left() + (pos.x - rect.left()) * upp.x,
+            self.bounds.top() + (rect.bottom() - pos.y) * upp.y,
+        )
+    }
+}
+
+/// Displays an editable keyframe curve with draggable keys and tangent handles.
+/// Returns `true` if any key was changed, added or removed.
+///
+/// Double-click empty space to add a key, right-click a key (or press Delete) to remove the
+/// selected one. Drag the background to pan, scroll to zoom; hold Alt while dragging a tangent
+/// handle to break the in/out tangents apart.
+pub fn curve(ui: &mut Ui, label: &str, keys: &mut Vec<CurveKey>) -> bool {
+    let id = ui.id().with(("bench_ui_curve", label));
+    if !keys.is_sorted_by(|a, b| a.time <= b.time) {
+        keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+    let mut view = ui
+        .data(|d| d.get_temp::<CurveView>(id))
+        .unwrap_or_else(|| CurveView::fit(keys));
+
+    let fit = ui
+        .horizontal(|ui| {
+            ui.label(label);
+            ui.small_button("⊡ Fit").clicked()
+        })
+        .inner;
+    if fit {
+        view = CurveView::fit(keys);
+    }
+
+    let size = Vec2::new(ui.available_width().max(120.0), CANVAS_HEIGHT);
+    let (response, painter) = ui.allocate_painter(size, Sense::click_and_drag());
+    let rect = response.rect;
+
+    let mut changed = key_handles(ui, id, rect, &mut view, keys);
+    changed |= tangent_handles(ui, id, rect, &view, keys);
+    changed |= background_input(ui, &response, &mut view, keys);
+
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    paint_grid(ui, &painter, rect, &view);
+    paint_curve(ui, &painter, rect, &view, keys);
+    paint_keys(ui, &painter, rect, &view, keys);
+
+    ui.data_mut(|d| d.insert_temp(id, view));
+    changed
+}
+
+/// Key dragging, selection and right-click removal.
+fn key_handles(
+    ui: &Ui,
+    id: egui::Id,
+    rect: Rect,
+    view: &mut CurveView,
+    keys: &mut Vec<CurveKey>,
+) -> bool {
+    let upp = view.units_per_point(rect);
+    let mut changed = false;
+    let mut remove = None;
+    for i in 0..keys.len() {
+        let pos = view.screen_pos(rect, keys[i].time, keys[i].value);
+        let hit = Rect::from_center_size(pos, Vec2::splat(KEY_RADIUS * 3.0));
+        let response = ui.interact(hit, id.with(("key", i)), Sense::click_and_drag());
+        if response.clicked() || response.drag_started() {
+            view.selected = Some(i);
+        }
+        if response.dragged() {
+            let delta = response.drag_delta();
+            let min = i
+                .checked_sub(1)
+                .map_or(f32::MIN, |p| keys[p].time + MIN_KEY_GAP);
+            let max = keys.get(i + 1).map_or(f32::MAX, |n| n.time - MIN_KEY_GAP);
+            let key = &mut keys[i];
+            key.time = (key.time + delta.x * upp.x).clamp(min, max.max(min));
+            key.value -= delta.y * upp.y;
+            changed = true;
+        }
+        if response.secondary_clicked() {
+            remove = Some(i);
+        }
+    }
+    let delete_pressed = ui.rect_contains_pointer(rect)
+        && ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace));
+    if let Some(i) = remove.or(view.selected.filter(|_| delete_pressed)) {
+        if i < keys.len() {
+            keys.remove(i);
+            changed = true;
+        }
+        view.selected = None;
+    }
+    changed
+}
+
+/// Screen-space offset of a tangent handle from its key.
+fn tangent_offset(upp: Vec2, tangent: f32) -> Vec2 {
+    Vec2::new(1.0 / upp.x, -tangent / upp.y).normalized() * TANGENT_LENGTH
+}
+
+/// Tangent handle dragging for the selected key.
+fn tangent_handles(
+    ui: &Ui,
+    id: egui::Id,
+    rect: Rect,
+    view: &CurveView,
+    keys: &mut [CurveKey],
+) -> bool {
+    let Some(key) = view.selected.and_then(|i| keys.get_mut(i)) else {
+        return false;
+    };
+    let upp = view.units_per_point(rect);
+    let pos = view.screen_pos(rect, key.time, key.value);
+    let handles = [
+        (pos - tangent_offset(upp, key.in_tangent), true),
+        (pos + tangent_offset(upp, key.out_tangent), false),
+    ];
+    let mut changed = false;
+    for (handle, incoming) in handles {
+        let hit = Rect::from_center_size(handle, Vec2::splat(KEY_RADIUS * 3.0));
+        let response = ui.interact(hit, id.with(("tangent", incoming)), Sense::drag());
+        let Some(pointer) = response
+            .interact_pointer_pos()
+            .filter(|_| response.dragged())
+        else {
+            continue;
+        };
+        let d = pointer - pos;
+        let dx = if incoming {
+            (d.x * upp.x).min(-f32::EPSILON)
+        } else {
+            (d.x * upp.x).max(f32::EPSILON)
+        };
+        let tangent = -d.y * upp.y / dx;
+        let broken = ui.input(|i| i.modifiers.alt);
+        if incoming || !broken {
+            key.in_tangent = tangent;
+        }
+        if !incoming || !broken {
+            key.out_tangent = tangent;
+        }
+        changed = true;
+    }
+    changed
+}
+
+/// Pan, zoom and double-click-to-add on the empty canvas.
+fn background_input(
+    ui: &Ui,
+    response: &Response,
+    view: &mut CurveView,
+    keys: &mut Vec<CurveKey>,
+) -> bool {
+    let rect = response.rect;
+    let upp = view.units_per_point(rect);
+    if response.dragged() {
+        let delta = response.drag_delta();
+        view.bounds = view
+            .bounds
+            .translate(Vec2::new(-delta.x * upp.x, delta.y * upp.y));
+    }
+    if let Some(pointer) = response.hover_pos() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta);
+        let zoom = ui.input(|i| i.zoom_delta()) * (scroll.y * 0.002).exp();
+        if zoom != 1.0 {
+            let (t, v) = view.curve_pos(rect, pointer);
+            let anchor = Pos2::new(t, v);
+            let zoomed = Rect::from_min_max(
+                anchor + (view.bounds.min - anchor) / zoom,
+                anchor + (view.bounds.max - anchor) / zoom,
+            );
+            let span = zoomed.size();
+            if span.min_elem() >= MIN_VIEW_SPAN && span.max_elem() <= MAX_VIEW_SPAN {
+                view.bounds = zoomed;
+            }
+        }
+        if scroll.x != 0.0 {
+            view.bounds = view.bounds.translate(Vec2::new(-scroll.x * upp.x, 0.0));
+        }
+    }
+    if response.clicked() {
+        view.selected = None;
+    }
+    let Some(pointer) = response
+        .interact_pointer_pos()
+        .filter(|_| response.double_clicked())
+    else {
+        return false;
+    };
+    let (time, _) = view.curve_pos(rect, pointer);
+    let slope = (sample_curve(keys, time + 1e-3) - sample_curve(keys, time - 1e-3)) / 2e-3;
+    let value = if keys.is_empty() {
+        view.curve_pos(rect, pointer).1
+    } else {
+        sample_curve(keys, time)
+    };
+    let index = keys.partition_point(|k| k.time <= time);
+    keys.insert(
+        index,
+        CurveKey {
+            in_tangent: slope,
+            out_tangent: slope,
+            ..CurveKey::new(time, value)
+        },
+    );
+    view.selected = Some(index);
+    true
+}
+
+/// Grid spacing giving roughly one line per 60 points, snapped to 1/2/5 × 10ⁿ.
+fn grid_step(units_per_point: f32) -> f32 {
+    let raw = units_per_point * 60.0;
+    let base = 10f32.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|m| m * base)
+        .find(|step| *step >= raw)
+        .unwrap_or(base * 10.0)
+}
+
+/// Positions of the grid lines every `step` within `min..=max`, at most [`MAX_GRID_LINES`].
+fn grid_lines(min: f32, max: f32, step: f32) -> impl Iterator<Item = f32> {
+    let (first, last) = if step > 0.0 {
+        ((min / step).floor() as i64, (max / step).ceil() as i64)
+    } else {
+        (1, 0)
+    };
+    (first..=last)
+        .map(move |i| i as f32 * step)
+        .filter(move |x| (min..=max).contains(x))
+        .take(MAX_GRID_LINES)
+}
+
+fn paint_grid(ui: &Ui, painter: &egui::Painter, rect: Rect, view: &CurveView) {
+    let upp = view.units_per_point(rect);
+    let minor = Stroke::new(1.0, ui.visuals().faint_bg_color);
+    let axis = ui.visuals().widgets.noninteractive.bg_stroke;
+    let text_color = ui.visuals().weak_text_color();
+    let font = egui::FontId::monospace(9.0);
+
+    let step = grid_step(upp.x);
+    for t in grid_lines(view.bounds.left(), view.bounds.right(), step) {
+        let x = view.screen_pos(rect, t, 0.0).x;
+        let stroke = if t.abs() < step * 0.5 { axis } else { minor };
+        painter.vline(x, rect.y_range(), stroke);
+        painter.text(
+            Pos2::new(x + 2.0, rect.bottom() - 2.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("{t:.2}"),
+            font.clone(),
+            text_color,
+        );
+    }
+
+    let step = grid_step(upp.y);
+    for v in grid_lines(view.bounds.top(), view.bounds.bottom(), step) {
+        let y = view.screen_pos(rect, 0.0, v).y;
+        let stroke = if v.abs() < step * 0.5 { axis } else { minor };
+        painter.hline(rect.x_range(), y, stroke);
+        painter.text(
+            Pos2::new(rect.left() + 2.0, y - 1.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("{v:.2}"),
+            font.clone(),
+            text_color,
+        );
+    }
+}
+
+fn paint_curve(ui: &Ui, painter: &egui::Painter, rect: Rect, view: &CurveView, keys: &[CurveKey]) {
+    if keys.is_empty() {
+        return;
+    }
+    let samples = (rect.width() / 2.0).max(2.0) as usize;
+    let points = (0..=samples)
+        .map(|i| {
+            let x = rect.left() + rect.width() * i as f32 / samples as f32;
+            let (time, _) = view.curve_pos(rect, Pos2::new(x, rect.top()));
+            view.screen_pos(rect, time, sample_curve(keys, time))
+        })
+        .collect();
+    let stroke = Stroke::new(1.5, ui.visuals().widgets.active.fg_stroke.color);
+    painter.add(egui::Shape::line(points, stroke));
+}
+
+fn paint_keys(ui: &Ui, painter: &egui::Painter, rect: Rect, view: &CurveView, keys: &[CurveKey]) {
+    let normal = ui.visuals().text_color();
+    let selected_color = ui.visuals().selection.bg_fill;
+    for (i, key) in keys.iter().enumerate() {
+        let pos = view.screen_pos(rect, key.time, key.value);
+        let color = if view.selected == Some(i) {
+            selected_color
+        } else {
+            normal
+        };
+        painter.circle_filled(pos, KEY_RADIUS, color);
+    }
+
+    let Some(key) = view.selected.and_then(|i| keys.get(i)) else {
+        return;
+    };
+    let upp = view.units_per_point(rect);
+    let pos = view.screen_pos(rect, key.time, key.value);
+    let stroke = Stroke::new(1.0, selected_color);
+    for handle in [
+        pos - tangent_offset(upp, key.in_tangent),
+        pos + tangent_offset(upp, key.out_tangent),
+    ] {
+        painter.line_segment([pos, handle], stroke);
+        painter.circle(handle, KEY_RADIUS * 0.7, Color32::TRANSPARENT, stroke);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_and_single_key() {
+        assert_eq!(sample_curve(&[], 1.0), 0.0);
+        let keys = [CurveKey::new(1.0, 3.0)];
+        assert_eq!(sample_curve(&keys, 0.0), 3.0);
+        assert_eq!(sample_curve(&keys, 5.0), 3.0);
+    }
+
+    #[test]
+    fn held_flat_outside_keys_and_exact_at_keys() {
+        let keys = [CurveKey::new(0.0, 1.0), CurveKey::new(2.0, 5.0)];
+        assert_eq!(sample_curve(&keys, -1.0), 1.0);
+        assert_eq!(sample_curve(&keys, 3.0), 5.0);
+        assert_eq!(sample_curve(&keys, 0.0), 1.0);
+        assert_eq!(sample_curve(&keys, 2.0), 5.0);
+    }
+
+    #[test]
+    fn flat_tangents_ease_symmetrically() {
+        let keys = [CurveKey::new(0.0, 0.0), CurveKey::new(1.0, 1.0)];
+        assert!((sample_curve(&keys, 0.5) - 0.5).abs() < 1e-6);
+        // Smoothstep: slow near the keys.
+        assert!(sample_curve(&keys, 0.1) < 0.1);
+        assert!(sample_curve(&keys, 0.9) > 0.9);
+    }
+
+    #[test]
+    fn matching_tangents_are_linear() {
+        let key = |time: f32, value: f32| CurveKey {
+            in_tangent: 2.0,
+            out_tangent: 2.0,
+            ..CurveKey::new(time, value)
+        };
+        let keys = [key(0.0, 0.0), key(1.0, 2.0), key(3.0, 6.0)];
+        for time in [0.25, 0.5, 1.5, 2.75] {
+            assert!((sample_curve(&keys, time) - 2.0 * time).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn coincident_keys_step() {
+        let keys = [
+            CurveKey::new(0.0, 0.0),
+            CurveKey::new(1.0, 1.0),
+            CurveKey::new(1.0, 4.0),
+        ];
+        assert_eq!(sample_curve(&keys, 1.0), 4.0);
+    }
+
+    #[test]
+    fn grid_lines_stay_in_range_and_capped() {
+        let lines: Vec<f32> = grid_lines(-0.3, 1.1, 0.5).collect();
+        assert_eq!(lines, [0.0, 0.5, 1.0]);
+        // A step below float precision at this magnitude must not hang.
+        assert_eq!(grid_lines(1000.0, 1000.001, 1e-9).count(), MAX_GRID_LINES);
+        assert_eq!(grid_lines(0.0, 1.0, 0.0).count(), 0);
+    }
+}
@@ -0,0 +1,76 @@
+//! # eyedropper.rs
+//!
+//! # eyedropper.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Eyedropper button for color widgets. Clicking it arms the eyedropper for that widget; the
+//! next click on the Game View image reads the pixel under the cursor back from the GPU, and
+//! once the readback arrives the color is handed to the widget that armed it. The hand-off lives
+//! in egui memory, so widgets need no world access. Escape or a second click disarms it.
+//!
+//! 颜色控件的吸管按钮。点击后吸管为该控件待命；随后在游戏视图画面上点击，会从 GPU 回读光标下的像素，
+//! 回读完成后颜色交给发起吸取的控件。交接信息保存在 egui 内存中，因此控件无需访问 World。按 Esc
+//! 或再次点击按钮可取消。
+
+use bevy::color::Color;
+use egui::Ui;
+
+/// Eyedropper hand-off, kept in egui memory.
+#[derive(Clone, Default)]
+struct EyedropperState {
+    /// Widget waiting for a color.
+    armed: Option<egui::Id>,
+    /// Sampled color not yet picked up by its widget.
+    sampled: Option<(egui::Id, Color)>,
+}
+
+fn state_id() -> egui::Id {
+    egui::Id::new("bench_ui_eyedropper")
+}
+
+fn with_state<R>(ctx: &egui::Context, f: impl FnOnce(&mut EyedropperState) -> R) -> R {
+    ctx.data_mut(|d| f(d.get_temp_mut_or_default::<EyedropperState>(state_id())))
+}
+
+/// Eyedropper toggle for the widget `id`. Returns the color sampled for it, once.
+pub fn eyedropper_button(ui: &mut Ui, id: egui::Id) -> Option<Color> {
+    let (armed, sampled) = with_state(ui.ctx(), |state| {
+        let sampled = state.sampled.take_if(|(target, _)| *target == id);
+        (state.armed == Some(id), sampled.map(|(_, color)| color))
+    });
+    let response = ui
+        .selectable_label(armed, "💧")
+        .on_hover_text("Pick a color from the game view");
+    if response.clicked() {
+        with_state(ui.ctx(), |state| {
+            state.armed = (!armed).then_some(id);
+        });
+    }
+    sampled
+}
+
+/// The widget waiting for a color, if any. Escape disarms the eyedropper.
+pub(crate) fn armed(ctx: &egui::Context) -> Option<egui::Id> {
+    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        with_state(ctx, |state| state.armed = None);
+    }
+    with_state(ctx, |state| state.armed)
+}
+
+/// Disarm the eyedropper once a pixel has been picked for `id`.
+pub(crate) fn disarm(ctx: &egui::Context, id: egui::Id) {
+    with_state(ctx, |state| {
+        if state.armed == Some(id) {
+            state.armed = None;
+        }
+    });
+}
+
+/// Hand `color` to the widget `id`; it picks the color up the next time it is drawn.
+pub(crate) fn deliver(ctx: &egui::Context, id: egui::Id, color: Color) {
+    with_state(ctx, |state| state.sampled = Some((id, color)));
+    ctx.request_repaint();
+}
@@ -0,0 +1,98 @@
+//! # icon.rs
+//!
+//! # icon.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Built-in icon set shared by panels, menus and the toolbar. `icon(Icon::Play)` returns the
+//! glyph to put in a label or button. With the `icon-font` feature the glyphs come from the
+//! bundled Phosphor icon font, installed next to the CJK font; without it each icon falls back
+//! to the closest symbol in egui's default emoji font, so call sites never change.
+//!
+//! 面板、菜单与工具栏共用的内置图标集。`icon(Icon::Play)` 返回可放入标签或按钮的字形。启用
+//! `icon-font` feature 时字形来自随库打包的 Phosphor 图标字体（与 CJK 字体一同安装）；未启用时每个
+//! 图标退回到 egui 默认 emoji 字体中最接近的符号，调用处无需改动。
+
+/// Declares [`Icon`] with its Phosphor glyph and emoji fallback.
+macro_rules! icons {
+    ($($(#[$doc:meta])* $name:ident => $phosphor:ident, $fallback:literal;)*) => {
+        /// A built-in icon; see [`icon`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Icon {
+            $($(#[$doc])* $name,)*
+        }
+
+        impl Icon {
+            /// Every icon, in declaration order.
+            pub const ALL: &[Icon] = &[$(Icon::$name,)*];
+
+            /// Glyph of the icon in the installed icon font.
+            pub const fn glyph(self) -> &'static str {
+                match self {
+                    $(
+                        #[cfg(feature = "icon-font")]
+                        Icon::$name => egui_phosphor::regular::$phosphor,
+                        #[cfg(not(feature = "icon-font"))]
+                        Icon::$name => $fallback,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+icons! {
+    Play => PLAY, "▶";
+    Pause => PAUSE, "⏸";
+    Stop => STOP, "⏹";
+    Record => RECORD, "⏺";
+    Undo => ARROW_COUNTER_CLOCKWISE, "↶";
+    Redo => ARROW_CLOCKWISE, "↷";
+    Close => X, "✖";
+    Search => MAGNIFYING_GLASS, "🔍";
+    Settings => GEAR, "⚙";
+    Help => QUESTION, "?";
+    Mute => SPEAKER_SLASH, "🔇";
+    Trash => TRASH, "🗑";
+    Copy => CLIPBOARD, "📋";
+    Save => FLOPPY_DISK, "💾";
+    Star => STAR, "⭐";
+    /// Debug and trace log entries.
+    Debug => BUG, "🔍";
+    Info => INFO, "ℹ";
+    Warning => WARNING, "⚠";
+    Error => X_CIRCLE, "❌";
+    /// Verbosity and level filters.
+    Levels => CELL_SIGNAL_FULL, "📶";
+    /// Collapsed disclosure arrow.
+    CaretRight => CARET_RIGHT, "⏵";
+    /// Expanded disclosure arrow, also used for dropdowns.
+    CaretDown => CARET_DOWN, "⏷";
+    Lock => LOCK, "🔒";
+    Unlock => LOCK_OPEN, "🔓";
+    Visible => EYE, "👁";
+    Hidden => EYE_SLASH, "◌";
+    /// Game View point tool.
+    Polygon => POLYGON, "⛶";
+    /// Toolbar toggle: hide panels while playing.
+    HidePanels => CORNERS_OUT, "⛶";
+    /// Toolbar toggle: per-mode layouts.
+    Layout => LAYOUT, "▦";
+    /// Toolbar toggle: slow motion.
+    SlowMotion => HOURGLASS, "¼×";
+    /// Restore the layout around a maximized panel.
+    RestoreLayout => CORNERS_IN, "🗗";
+}
+
+/// Glyph of `icon`, e.g. `ui.button(format!("{} Clear", icon(Icon::Trash)))`.
+pub const fn icon(icon: Icon) -> &'static str {
+    icon.glyph()
+}
+
+/// Add the Phosphor icon font to `fonts`, before the CJK fallback is appended.
+#[cfg(feature = "icon-font")]
+pub(crate) fn install_icon_font(fonts: &mut egui::FontDefinitions) {
+    egui_phosphor::add_to_fonts(fonts, egui_phosphor::Variant::Regular);
+}
@@ -0,0 +1,377 @@
+//! # markdown.rs
+//!
+//! # markdown.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Minimal Markdown renderer for help pages, tips and changelogs. It covers the subset such
+//! text uses: ATX headings, paragraphs, bullet and numbered lists, block quotes, rules, fenced
+//! code blocks, and inline bold, italics, code spans and links. Anything else is shown as
+//! plain text rather than rejected, so unsupported syntax degrades gracefully.
+//!
+//! 为帮助页、提示与更新日志提供的最小 Markdown 渲染器。覆盖这类文本常用的子集：ATX 标题、段落、
+//! 无序与有序列表、引用块、分隔线、围栏代码块，以及行内粗体、斜体、代码与链接。其余语法按纯文本显示
+//! 而不是报错，因此不受支持的写法也能平稳降级。
+
+use egui::{RichText, Ui};
+
+/// Inline emphasis in effect for a span.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Emphasis {
+    bold: bool,
+    italic: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SpanKind {
+    Text,
+    Code,
+    Link(String),
+}
+
+/// A run of inline text with one formatting.
+#[derive(Debug, Clone, PartialEq)]
+struct Span {
+    text: String,
+    emphasis: Emphasis,
+    kind: SpanKind,
+}
+
+/// A line that forms a block on its own.
+enum Block<'a> {
+    Heading(usize, &'a str),
+    /// List item with its indentation (in spaces) and marker ("•" or "1.").
+    Item(usize, String, &'a str),
+    Quote(&'a str),
+    Rule,
+}
+
+impl<'a> Block<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if matches!(trimmed.trim_end(), "---" | "***" | "___") {
+            return Some(Block::Rule);
+        }
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level)
+            && let Some(text) = trimmed[level..].strip_prefix(' ')
+        {
+            return Some(Block::Heading(level, text.trim_end_matches('#').trim()));
+        }
+        if let Some(text) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            return Some(Block::Item(indent, "•".to_string(), text));
+        }
+        let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+        if digits > 0
+            && let Some(text) = trimmed[digits..].strip_prefix(". ")
+        {
+            return Some(Block::Item(indent, trimmed[..=digits].to_string(), text));
+        }
+        trimmed
+            .strip_prefix('>')
+            .map(|text| Block::Quote(text.trim_start()))
+    }
+
+    fn ui(&self, ui: &mut Ui) {
+        match self {
+            Block::Heading(level, text) => {
+                ui.add_space(4.0);
+                if *level == 1 {
+                    inline_ui(ui, text, &|rich| rich.heading());
+                } else {
+                    let size = heading_size(ui.style(), *level);
+                    inline_ui(ui, text, &|rich| rich.size(size).strong());
+                }
+            }
+            Block::Item(indent, marker, text) => {
+                ui.horizontal_top(|ui| {
+                    ui.add_space(*indent as f32 * 6.0 + 4.0);
+                    ui.label(marker.as_str());
+                    ui.vertical(|ui| inline_ui(ui, text, &|rich| rich));
+                });
+            }
+            Block::Quote(text) => {
+                ui.horizontal_top(|ui| {
+                    ui.add_space(12.0);
+                    ui.vertical(|ui| inline_ui(ui, text, &|rich| rich.weak().italics()));
+                });
+            }
+            Block::Rule => {
+                ui.separator();
+            }
+        }
+    }
+}
+
+/// Font size of a level-`level` heading: from the heading size (level 1) down to the body
+/// size (level 6) in even steps.
+fn heading_size(style: &egui::Style, level: usize) -> f32 {
+    let size = |text_style| style.text_styles.get(&text_style).map_or(14.0, |f| f.size);
+    let (heading, body) = (size(egui::TextStyle::Heading), size(egui::TextStyle::Body));
+    let step = 6usize.saturating_sub(level).min(5) as f32 / 5.0;
+    body + (heading - body) * step
+}
+
+/// Render `text` as Markdown.
+pub fn markdown(ui: &mut Ui, text: &str) {
+    let mut paragraph = String::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            flush_paragraph(ui, &mut paragraph);
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| !line.trim_start().starts_with("```"))
+                .collect();
+            code_block_ui(ui, &code.join("\n"));
+        } else if trimmed.is_empty() {
+            flush_paragraph(ui, &mut paragraph);
+        } else if let Some(block) = Block::parse(line) {
+            flush_paragraph(ui, &mut paragraph);
+            block.ui(ui);
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(trimmed);
+        }
+    }
+    flush_paragraph(ui, &mut paragraph);
+}
+
+fn flush_paragraph(ui: &mut Ui, paragraph: &mut String) {
+    if !paragraph.is_empty() {
+        inline_ui(ui, paragraph, &|rich| rich);
+        paragraph.clear();
+    }
+}
+
+fn code_block_ui(ui: &mut Ui, code: &str) {
+    egui::Frame::group(ui.style())
+        .fill(ui.visuals().code_bg_color)
+        .show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.label(RichText::new(code).monospace());
+        });
+}
+
+/// Wrapped inline text; `style` applies the block's look (heading, quote) to every span.
+fn inline_ui(ui: &mut Ui, text: &str, style: &dyn Fn(RichText) -> RichText) {
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        for span in parse_inline(text) {
+            let mut rich = style(RichText::new(span.text));
+            if span.emphasis.bold {
+                rich = rich.strong();
+            }
+            if span.emphasis.italic {
+                rich = rich.italics();
+            }
+            match span.kind {
+                SpanKind::Text => ui.label(rich),
+                SpanKind::Code => ui.label(rich.code()),
+                SpanKind::Link(url) => ui.hyperlink_to(rich, url),
+            };
+        }
+    });
+}
+
+/// Split `text` into spans at emphasis markers, code spans and links. `\` escapes a marker.
+/// Following CommonMark's flanking rules (simplified), a marker opens emphasis only when it is
+/// followed by non-whitespace and a matching marker after non-whitespace closes it later; any
+/// other `*` is literal.
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut emphasis = Emphasis::default();
+    let mut prev = None;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some((span, after)) = code_or_link(rest, emphasis) {
+            flush_run(&mut spans, &mut run, emphasis);
+            spans.push(span);
+            prev = rest[..rest.len() - after.len()].chars().last();
+            rest = after;
+        } else if let Some(marker) = ["**", "*"].into_iter().find(|m| rest.starts_with(m)) {
+            let bold = marker.len() == 2;
+            let active = if bold { emphasis.bold } else { emphasis.italic };
+            let toggle = if active {
+                prev.is_some_and(|c: char| !c.is_whitespace())
+            } else {
+                opens_emphasis(&rest[marker.len()..], marker)
+            };
+            if toggle {
+                flush_run(&mut spans, &mut run, emphasis);
+                if bold {
+                    emphasis.bold = !active;
+                } else {
+                    emphasis.italic = !active;
+                }
+            } else {
+                run.push_str(marker);
+            }
+            prev = Some('*');
+            rest = &rest[marker.len()..];
+        } else if let Some(escaped) = rest.strip_prefix('\\').and_then(|r| r.chars().next()) {
+            run.push(escaped);
+            prev = Some(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else {
+            run.push(c);
+            prev = Some(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    flush_run(&mut spans, &mut run, emphasis);
+    spans
+}
+
+/// Whether a `marker` followed by `after` opens emphasis: `after` starts with non-whitespace and
+/// holds a closing marker, i.e. a run of `*` after non-whitespace (an odd run closes `*`, a run
+/// of two or more closes `**`).
+fn opens_emphasis(after: &str, marker: &str) -> bool {
+    if after.chars().next().is_none_or(char::is_whitespace) {
+        return false;
+    }
+    let mut prev = None;
+    let mut rest = after;
+    while let Some(c) = rest.chars().next() {
+        if c == '*' {
+            let len = rest.len() - rest.trim_start_matches('*').len();
+            let fits = if marker.len() == 2 {
+                len >= 2
+            } else {
+                len % 2 == 1
+            };
+            if fits && prev.is_some_and(|p: char| !p.is_whitespace()) {
+                return true;
+            }
+            prev = Some('*');
+            rest = &rest[len..];
+        } else if let Some(escaped) = rest.strip_prefix('\\').and_then(|r| r.chars().next()) {
+            prev = Some(escaped);
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else {
+            prev = Some(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    false
+}
+
+fn flush_run(spans: &mut Vec<Span>, run: &mut String, emphasis: Emphasis) {
+    if !run.is_empty() {
+        spans.push(Span {
+            text: std::mem::take(run),
+            emphasis,
+            kind: SpanKind::Text,
+        });
+    }
+}
+
+/// A code span or link at the start of `rest`, with the text after it.
+fn code_or_link(rest: &str, emphasis: Emphasis) -> Option<(Span, &str)> {
+    if let Some(after) = rest.strip_prefix('`') {
+        let end = after.find('`')?;
+        let span = Span {
+            text: after[..end].to_string(),
+            emphasis,
+            kind: SpanKind::Code,
+        };
+        return Some((span, &after[end + 1..]));
+    }
+    let after = rest.strip_prefix('[')?;
+    let label_end = after.find("](")?;
+    let target = &after[label_end + 2..];
+    let url_end = target.find(')')?;
+    let span = Span {
+        text: after[..label_end].to_string(),
+        emphasis,
+        kind: SpanKind::Link(target[..url_end].to_string()),
+    };
+    Some((span, &target[url_end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str, bold: bool, italic: bool) -> Span {
+        Span {
+            text: text.to_string(),
+            emphasis: Emphasis { bold, italic },
+            kind: SpanKind::Text,
+        }
+    }
+
+    #[test]
+    fn emphasis_toggles_at_markers() {
+        assert_eq!(
+            parse_inline("a **b *c* d** e"),
+            vec![
+                text("a ", false, false),
+                text("b ", true, false),
+                text("c", true, true),
+                text(" d", true, false),
+                text(" e", false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn lone_asterisks_are_literal() {
+        assert_eq!(
+            parse_inline("2 * 3 = 6"),
+            vec![text("2 * 3 = 6", false, false)]
+        );
+        assert_eq!(parse_inline("a *b"), vec![text("a *b", false, false)]);
+        assert_eq!(parse_inline("*a * b"), vec![text("*a * b", false, false)]);
+        assert_eq!(parse_inline("x ** y"), vec![text("x ** y", false, false)]);
+    }
+
+    #[test]
+    fn italics_close_inside_bold() {
+        assert_eq!(
+            parse_inline("*a **b** c*"),
+            vec![
+                text("a ", false, true),
+                text("b", true, true),
+                text(" c", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn headings_shrink_by_level() {
+        let style = egui::Style::default();
+        let sizes: Vec<f32> = (1..=6).map(|level| heading_size(&style, level)).collect();
+        assert!(sizes.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(sizes[5], style.text_styles[&egui::TextStyle::Body].size);
+    }
+
+    #[test]
+    fn code_spans_and_links_keep_their_text() {
+        let spans = parse_inline("run `a*b` or see [the *docs*](https://x.y)");
+        assert_eq!(spans[1].kind, SpanKind::Code);
+        assert_eq!(spans[1].text, "a*b");
+        assert_eq!(spans[3].kind, SpanKind::Link("https://x.y".to_string()));
+        assert_eq!(spans[3].text, "the *docs*");
+    }
+
+    #[test]
+    fn escapes_and_unclosed_code_are_literal() {
+        assert_eq!(
+            parse_inline(r"\*not\* italic"),
+            vec![text("*not* italic", false, false)]
+        );
+        assert_eq!(parse_inline("a `b"), vec![text("a `b", false, false)]);
+        assert_eq!(parse_inline("[a](b"), vec![text("[a](b", false, false)]);
+    }
+}
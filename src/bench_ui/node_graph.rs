@@ -0,0 +1,423 @@
+//! # node_graph.rs
+//!
+//! # node_graph.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! A node-graph canvas for dialogue, shader and behavior-tree style editors. The caller owns a
+//! [`NodeGraph`] (nodes with typed input/output ports, plus links between them); the widget
+//! draws it with bezier links, lets users drag nodes, connect and detach ports, box-select,
+//! delete the selection, and pan/zoom. Pan, zoom and in-progress drags live in egui memory.
+//!
+//! 用于对话、着色器和行为树等编辑器的节点图画布。调用方持有 [`NodeGraph`]（带有类型化输入/输出端口的
+//! 节点以及它们之间的连线）；该控件以贝塞尔曲线绘制连线，支持拖拽节点、连接与断开端口、框选、删除
+//! 选中项以及平移/缩放。平移、缩放和进行中的拖拽状态保存在 egui 内存中。
+
+mod paint;
+
+use egui::{Color32, PointerButton, Pos2, Rect, Response, Sense, Ui, Vec2};
+use paint::{paint_grid, paint_links, paint_nodes, paint_overlays};
+
+/// Node width in graph units.
+const NODE_WIDTH: f32 = 140.0;
+/// Height of a node's title bar in graph units.
+const HEADER_HEIGHT: f32 = 22.0;
+/// Height of one port row in graph units.
+const ROW_HEIGHT: f32 = 18.0;
+/// Port circle radius in graph units.
+const PORT_RADIUS: f32 = 5.0;
+/// Background grid spacing in graph units.
+const GRID_SPACING: f32 = 24.0;
+
+/// Identifies a node within a [`NodeGraph`]. Chosen by the caller and must be unique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(pub u64);
+
+/// A port on a node: an index into its `inputs` or `outputs`, depending on context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortRef {
+    pub node: NodeId,
+    pub port: usize,
+}
+
+/// A named, typed node port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePort {
+    pub name: String,
+    /// Type tag; ports link only when tags match. An empty tag accepts anything.
+    pub kind: String,
+}
+
+impl NodePort {
+    fn accepts(&self, other: &NodePort) -> bool {
+        self.kind.is_empty() || other.kind.is_empty() || self.kind == other.kind
+    }
+}
+
+/// A node in a [`NodeGraph`].
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: NodeId,
+    pub title: String,
+    /// Top-left corner in graph units.
+    pub pos: Pos2,
+    pub inputs: Vec<NodePort>,
+    pub outputs: Vec<NodePort>,
+    /// Title bar color (theme default if `None`).
+    pub color: Option<Color32>,
+}
+
+impl GraphNode {
+    pub fn new(id: NodeId, title: impl Into<String>, pos: Pos2) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            pos,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            color: None,
+        }
+    }
+
+    pub fn with_input(mut self, name: impl Into<String>, kind: impl Into<String>) -> Self {
+        self.inputs.push(NodePort {
+            name: name.into(),
+            kind: kind.into(),
+        });
+        self
+    }
+
+    pub fn with_output(mut self, name: impl Into<String>, kind: impl Into<String>) -> Self {
+        self.outputs.push(NodePort {
+            name: name.into(),
+            kind: kind.into(),
+        });
+        self
+    }
+
+    pub fn with_color(mut self, color: Color32) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    fn size(&self) -> Vec2 {
+        let rows = self.inputs.len().max(self.outputs.len()) as f32;
+        Vec2::new(NODE_WIDTH, HEADER_HEIGHT + rows * ROW_HEIGHT + 6.0)
+    }
+
+    fn port(&self, index: usize, output: bool) -> Option<&NodePort> {
+        if output {
+            self.outputs.get(index)
+        } else {
+            self.inputs.get(index)
+        }
+    }
+}
+
+/// A link from an output port (`from`) to an input port (`to`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GraphLink {
+    pub from: PortRef,
+    pub to: PortRef,
+}
+
+/// Nodes, links and selection edited by [`node_graph`].
+#[derive(Debug, Clone, Default)]
+pub struct NodeGraph {
+    pub nodes: Vec<GraphNode>,
+    pub links: Vec<GraphLink>,
+    pub selected: Vec<NodeId>,
+}
+
+impl NodeGraph {
+    pub fn node(&self, id: NodeId) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// The link feeding an input port, if any.
+    pub fn input_link(&self, to: PortRef) -> Option<GraphLink> {
+        self.links.iter().copied().find(|l| l.to == to)
+    }
+
+    /// Connect an output to an input, replacing the input's previous link.
+    /// Returns `false` if either port is missing or their types don't match.
+    pub fn connect(&mut self, from: PortRef, to: PortRef) -> bool {
+        let output = self.node(from.node).and_then(|n| n.outputs.get(from.port));
+        let input = self.node(to.node).and_then(|n| n.inputs.get(to.port));
+        let (Some(output), Some(input)) = (output, input) else {
+            return false;
+        };
+        if from.node == to.node || !output.accepts(input) {
+            return false;
+        }
+        self.links.retain(|l| l.to != to);
+        self.links.push(GraphLink { from, to });
+        true
+    }
+
+    /// Remove a node along with its links.
+    pub fn remove_node(&mut self, id: NodeId) {
+        self.nodes.retain(|n| n.id != id);
+        self.links.retain(|l| l.from.node != id && l.to.node != id);
+        self.selected.retain(|s| *s != id);
+    }
+}
+
+/// A link being dragged out of a port.
+#[derive(Clone, Copy)]
+struct PendingLink {
+    port: PortRef,
+    from_output: bool,
+}
+
+/// Pan/zoom and in-progress drags, kept in egui memory per widget.
+#[derive(Clone)]
+struct GraphView {
+    offset: Vec2,
+    zoom: f32,
+    box_start: Option<Pos2>,
+    pending: Option<PendingLink>,
+}
+
+impl Default for GraphView {
+    fn default() -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            zoom: 1.0,
+            box_start: None,
+            pending: None,
+        }
+    }
+}
+
+impl GraphView {
+    fn screen_pos(&self, origin: Pos2, pos: Pos2) -> Pos2 {
+        origin + self.offset + pos.to_vec2() * self.zoom
+    }
+
+    fn node_rect(&self, origin: Pos2, node: &GraphNode) -> Rect {
+        Rect::from_min_size(self.screen_pos(origin, node.pos), node.size() * self.zoom)
+    }
+
+    fn port_pos(&self, origin: Pos2, node: &GraphNode, index: usize, output: bool) -> Pos2 {
+        let rect = self.node_rect(origin, node);
+        let y = rect.top() + (HEADER_HEIGHT + ROW_HEIGHT * (index as f32 + 0.5)) * self.zoom;
+        Pos2::new(if output { rect.right() } else { rect.left() }, y)
+    }
+}
+
+/// Displays an editable node graph filling the available space.
+/// Returns `true` if nodes were moved or deleted, or links changed.
+///
+/// Drag a port to another compatible port to link them; drag a linked input away to detach
+/// it. Drag empty space to box-select (Shift adds), right/middle-drag to pan, scroll to zoom,
+/// and press Delete to remove the selected nodes.
+pub fn node_graph(ui: &mut Ui, label: &str, graph: &mut NodeGraph) -> bool {
+    let id = ui.id().with(("bench_ui_node_graph", label));
+    let mut view = ui.data(|d| d.get_temp::<GraphView>(id)).unwrap_or_default();
+    let size = ui.available_size().max(Vec2::new(200.0, 150.0));
+    let (response, painter) = ui.allocate_painter(size, Sense::click_and_drag());
+    let origin = response.rect.min;
+
+    let mut changed = node_interaction(ui, id, origin, &view, graph);
+    changed |= port_interaction(ui, id, origin, &mut view, graph);
+    background_input(ui, &response, &mut view, graph);
+    changed |= delete_selected(ui, &response, graph);
+
+    painter.rect_filled(response.rect, 0.0, ui.visuals().extreme_bg_color);
+    paint_grid(ui, &painter, response.rect, &view);
+    paint_links(ui, &painter, origin, &view, graph);
+    paint_nodes(ui, &painter, origin, &view, graph);
+    paint_overlays(ui, &painter, origin, &view, graph);
+
+    ui.data_mut(|d| d.insert_temp(id, view));
+    changed
+}
+
+/// Node selection and dragging (moves every selected node).
+fn node_interaction(
+    ui: &Ui,
+    id: egui::Id,
+    origin: Pos2,
+    view: &GraphView,
+    graph: &mut NodeGraph,
+) -> bool {
+    let shift = ui.input(|i| i.modifiers.shift);
+    let mut drag = Vec2::ZERO;
+    for i in 0..graph.nodes.len() {
+        let node_id = graph.nodes[i].id;
+        let rect = view.node_rect(origin, &graph.nodes[i]);
+        let response = ui.interact(rect, id.with(("node", node_id)), Sense::click_and_drag());
+        let is_selected = graph.selected.contains(&node_id);
+        if response.clicked() && shift && is_selected {
+            graph.selected.retain(|s| *s != node_id);
+        } else if (response.clicked() || response.drag_started()) && !is_selected {
+            if !shift {
+                graph.selected.clear();
+            }
+            graph.selected.push(node_id);
+        }
+        if response.dragged() {
+            drag += response.drag_delta() / view.zoom;
+        }
+    }
+    if drag == Vec2::ZERO {
+        return false;
+    }
+    for node in &mut graph.nodes {
+        if graph.selected.contains(&node.id) {
+            node.pos += drag;
+        }
+    }
+    true
+}
+
+/// Port under `pointer` that the pending link could attach to.
+fn drop_target(
+    origin: Pos2,
+    view: &GraphView,
+    graph: &NodeGraph,
+    pending: PendingLink,
+    pointer: Pos2,
+) -> Option<PortRef> {
+    let source = graph
+        .node(pending.port.node)?
+        .port(pending.port.port, pending.from_output)?;
+    let want_output = !pending.from_output;
+    graph.nodes.iter().find_map(|node| {
+        let ports = if want_output {
+            &node.outputs
+        } else {
+            &node.inputs
+        };
+        let index = ports.iter().enumerate().position(|(i, port)| {
+            let pos = view.port_pos(origin, node, i, want_output);
+            pos.distance(pointer) <= PORT_RADIUS * 2.0 * view.zoom && port.accepts(source)
+        })?;
+        Some(PortRef {
+            node: node.id,
+            port: index,
+        })
+    })
+}
+
+/// Starting, detaching and dropping links on ports.
+fn port_interaction(
+    ui: &Ui,
+    id: egui::Id,
+    origin: Pos2,
+    view: &mut GraphView,
+    graph: &mut NodeGraph,
+) -> bool {
+    let hit = Vec2::splat(PORT_RADIUS * 3.0 * view.zoom);
+    let mut started = None;
+    for node in &graph.nodes {
+        let ports = (0..node.inputs.len())
+            .map(|i| (i, false))
+            .chain((0..node.outputs.len()).map(|i| (i, true)));
+        for (index, output) in ports {
+            let rect = Rect::from_center_size(view.port_pos(origin, node, index, output), hit);
+            let port_id = id.with(("port", node.id, index, output));
+            if ui.interact(rect, port_id, Sense::drag()).drag_started() {
+                let port = PortRef {
+                    node: node.id,
+                    port: index,
+                };
+                started = Some(PendingLink {
+                    port,
+                    from_output: output,
+                });
+            }
+        }
+    }
+
+    let mut changed = false;
+    if let Some(pending) = started {
+        view.pending = Some(pending);
+        // Dragging a linked input picks the link up by its output end.
+        if !pending.from_output
+            && let Some(link) = graph.input_link(pending.port)
+        {
+            graph.links.retain(|l| *l != link);
+            view.pending = Some(PendingLink {
+                port: link.from,
+                from_output: true,
+            });
+            changed = true;
+        }
+    }
+    if let Some(pending) = view.pending
+        && ui.input(|i| i.pointer.primary_released())
+    {
+        view.pending = None;
+        let target = ui
+            .ctx()
+            .pointer_latest_pos()
+            .and_then(|pointer| drop_target(origin, view, graph, pending, pointer));
+        if let Some(target) = target {
+            changed |= if pending.from_output {
+                graph.connect(pending.port, target)
+            } else {
+                graph.connect(target, pending.port)
+            };
+        }
+    }
+    changed
+}
+
+/// Pan, zoom, box selection and click-to-deselect on the empty canvas.
+fn background_input(ui: &Ui, response: &Response, view: &mut GraphView, graph: &mut NodeGraph) {
+    let origin = response.rect.min;
+    if response.dragged_by(PointerButton::Secondary) || response.dragged_by(PointerButton::Middle) {
+        view.offset += response.drag_delta();
+    }
+    if let Some(pointer) = response.hover_pos() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        let factor = ui.input(|i| i.zoom_delta()) * (scroll * 0.002).exp();
+        let zoom = (view.zoom * factor).clamp(0.25, 2.5);
+        if zoom != view.zoom {
+            // Keep the graph point under the pointer fixed.
+            let anchor = (pointer - origin - view.offset) / view.zoom;
+            view.offset = pointer - origin - anchor * zoom;
+            view.zoom = zoom;
+        }
+    }
+    if response.drag_started_by(PointerButton::Primary) {
+        view.box_start = response.interact_pointer_pos();
+    }
+    if response.drag_stopped()
+        && let Some(start) = view.box_start.take()
+        && let Some(end) = ui.ctx().pointer_latest_pos()
+    {
+        let selection = Rect::from_two_pos(start, end);
+        if !ui.input(|i| i.modifiers.shift) {
+            graph.selected.clear();
+        }
+        for node in &graph.nodes {
+            if selection.intersects(view.node_rect(origin, node))
+                && !graph.selected.contains(&node.id)
+            {
+                graph.selected.push(node.id);
+            }
+        }
+    }
+    if response.clicked() {
+        graph.selected.clear();
+    }
+}
+
+/// Delete/Backspace removes the selected nodes while the pointer is over the canvas.
+fn delete_selected(ui: &Ui, response: &Response, graph: &mut NodeGraph) -> bool {
+    let pressed = ui.rect_contains_pointer(response.rect)
+        && ui.memory(|m| m.focused().is_none())
+        && ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace));
+    if !pressed || graph.selected.is_empty() {
+        return false;
+    }
+    for id in std::mem::take(&mut graph.selected) {
+        graph.remove_node(id);
+    }
+    true
+}
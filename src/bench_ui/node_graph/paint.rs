@@ -0,0 +1,182 @@
+//! # paint.rs
+//!
+//! # paint.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Drawing for the node-graph widget: the background grid, bezier links colored by port type,
+//! node boxes with their ports, and overlays for the link being dragged and the selection box.
+//!
+//! 节点图控件的绘制部分：背景网格、按端口类型着色的贝塞尔连线、带端口的节点框，以及拖拽中的连线与
+//! 框选矩形的覆盖层。
+
+use super::{GRID_SPACING, GraphView, HEADER_HEIGHT, NodeGraph, PORT_RADIUS};
+use egui::epaint::CubicBezierShape;
+use egui::{Color32, Pos2, Rect, Stroke, Ui, Vec2};
+
+/// Stable color for a port type tag.
+fn kind_color(kind: &str) -> Color32 {
+    if kind.is_empty() {
+        return Color32::GRAY;
+    }
+    let hash = kind
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(u32::from(b)));
+    egui::ecolor::Hsva::new((hash % 360) as f32 / 360.0, 0.55, 0.9, 1.0).into()
+}
+
+/// Horizontal-tangent bezier between two ports.
+fn link_shape(from: Pos2, to: Pos2, stroke: Stroke) -> CubicBezierShape {
+    let bend = Vec2::new(((to.x - from.x).abs() * 0.5).max(40.0), 0.0);
+    CubicBezierShape::from_points_stroke(
+        [from, from + bend, to - bend, to],
+        false,
+        Color32::TRANSPARENT,
+        stroke,
+    )
+}
+
+pub(super) fn paint_grid(ui: &Ui, painter: &egui::Painter, rect: Rect, view: &GraphView) {
+    let spacing = GRID_SPACING * view.zoom;
+    let stroke = Stroke::new(1.0, ui.visuals().faint_bg_color);
+    let start = rect.min
+        + Vec2::new(
+            view.offset.x.rem_euclid(spacing),
+            view.offset.y.rem_euclid(spacing),
+        );
+    let mut x = start.x;
+    while x <= rect.right() {
+        painter.vline(x, rect.y_range(), stroke);
+        x += spacing;
+    }
+    let mut y = start.y;
+    while y <= rect.bottom() {
+        painter.hline(rect.x_range(), y, stroke);
+        y += spacing;
+    }
+}
+
+pub(super) fn paint_links(
+    ui: &Ui,
+    painter: &egui::Painter,
+    origin: Pos2,
+    view: &GraphView,
+    graph: &NodeGraph,
+) {
+    let width = 2.0 * view.zoom.max(0.5);
+    for link in &graph.links {
+        let (Some(from), Some(to)) = (graph.node(link.from.node), graph.node(link.to.node)) else {
+            continue;
+        };
+        let Some(port) = from.outputs.get(link.from.port) else {
+            continue;
+        };
+        let selected = graph.selected.contains(&from.id) || graph.selected.contains(&to.id);
+        let color = if selected {
+            ui.visuals().selection.stroke.color
+        } else {
+            kind_color(&port.kind)
+        };
+        painter.add(link_shape(
+            view.port_pos(origin, from, link.from.port, true),
+            view.port_pos(origin, to, link.to.port, false),
+            Stroke::new(width, color),
+        ));
+    }
+}
+
+pub(super) fn paint_nodes(
+    ui: &Ui,
+    painter: &egui::Painter,
+    origin: Pos2,
+    view: &GraphView,
+    graph: &NodeGraph,
+) {
+    let visuals = ui.visuals();
+    let rounding = 4.0 * view.zoom;
+    let title_font = egui::FontId::proportional(13.0 * view.zoom);
+    let port_font = egui::FontId::proportional(11.0 * view.zoom);
+    for node in &graph.nodes {
+        let rect = view.node_rect(origin, node);
+        let header =
+            Rect::from_min_size(rect.min, Vec2::new(rect.width(), HEADER_HEIGHT * view.zoom));
+        let outline = if graph.selected.contains(&node.id) {
+            visuals.selection.stroke
+        } else {
+            visuals.widgets.noninteractive.bg_stroke
+        };
+        painter.rect_filled(rect, rounding, visuals.window_fill);
+        painter.rect_filled(
+            header,
+            rounding,
+            node.color.unwrap_or(visuals.widgets.inactive.bg_fill),
+        );
+        painter.rect_stroke(rect, rounding, outline, egui::StrokeKind::Outside);
+        painter.text(
+            header.left_center() + Vec2::new(6.0 * view.zoom, 0.0),
+            egui::Align2::LEFT_CENTER,
+            &node.title,
+            title_font.clone(),
+            visuals.strong_text_color(),
+        );
+        for (output, ports) in [(false, &node.inputs), (true, &node.outputs)] {
+            for (i, port) in ports.iter().enumerate() {
+                let pos = view.port_pos(origin, node, i, output);
+                painter.circle_filled(pos, PORT_RADIUS * view.zoom, kind_color(&port.kind));
+                let (align, dx) = if output {
+                    (egui::Align2::RIGHT_CENTER, -8.0)
+                } else {
+                    (egui::Align2::LEFT_CENTER, 8.0)
+                };
+                painter.text(
+                    pos + Vec2::new(dx * view.zoom, 0.0),
+                    align,
+                    &port.name,
+                    port_font.clone(),
+                    visuals.text_color(),
+                );
+            }
+        }
+    }
+}
+
+/// The link being dragged and the selection box.
+pub(super) fn paint_overlays(
+    ui: &Ui,
+    painter: &egui::Painter,
+    origin: Pos2,
+    view: &GraphView,
+    graph: &NodeGraph,
+) {
+    let Some(pointer) = ui.ctx().pointer_latest_pos() else {
+        return;
+    };
+    if let Some(pending) = view.pending
+        && let Some(node) = graph.node(pending.port.node)
+        && let Some(port) = node.port(pending.port.port, pending.from_output)
+    {
+        let pos = view.port_pos(origin, node, pending.port.port, pending.from_output);
+        let (from, to) = if pending.from_output {
+            (pos, pointer)
+        } else {
+            (pointer, pos)
+        };
+        painter.add(link_shape(
+            from,
+            to,
+            Stroke::new(2.0, kind_color(&port.kind)),
+        ));
+    }
+    if let Some(start) = view.box_start {
+        let selection = ui.visuals().selection;
+        painter.rect(
+            Rect::from_two_pos(start, pointer),
+            0.0,
+            selection.bg_fill.gamma_multiply(0.2),
+            selection.stroke,
+            egui::StrokeKind::Inside,
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! # plot.rs
+//!
+//! # plot.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! A time-series plot for live values. Each series is a list of `(time, value)` samples,
+//! oldest first; the widget shows a sliding time window ending at the newest sample, fits the
+//! value axis to what is visible, and reads out the values under the cursor. Scrolling zooms
+//! the window, dragging pans back through history and double-clicking returns to the live
+//! edge. View state lives in egui memory, so callers only own the samples.
+//!
+//! 用于实时数值的时间序列图。每条序列是按时间先后排列的 `(时间, 数值)` 采样；该控件显示以最新
+//! 采样为终点的滑动时间窗口，按可见部分自动适配数值轴，并读出光标处的数值。滚轮缩放时间窗口，
+//! 拖拽可回看历史，双击回到实时末端。视图状态保存在 egui 内存中，调用方只需持有采样数据。
+
+use egui::{Color32, Pos2, Rect, Sense, Stroke, Ui, Vec2};
+use std::collections::VecDeque;
+
+/// Height of the plot canvas in points.
+const CANVAS_HEIGHT: f32 = 160.0;
+/// Initial and minimum visible time window, in seconds.
+const DEFAULT_WINDOW: f32 = 10.0;
+const MIN_WINDOW: f32 = 0.1;
+
+/// One line of a plot.
+pub struct PlotSeries<'a> {
+    pub name: &'a str,
+    pub color: Color32,
+    /// `(time, value)` samples, oldest first.
+    pub samples: &'a VecDeque<(f32, f32)>,
+}
+
+/// Visible time window, kept in egui memory per widget.
+#[derive(Clone, Copy)]
+struct PlotView {
+    /// Width of the window, in seconds.
+    window: f32,
+    /// How far the window's right edge sits before the newest sample, in seconds.
+    offset: f32,
+}
+
+impl Default for PlotView {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            offset: 0.0,
+        }
+    }
+}
+
+/// Displays a time-series plot of `series`.
+///
+/// Scroll to zoom the time window, drag to pan back through history, double-click to follow
+/// the newest samples again.
+pub fn plot_series(ui: &mut Ui, id_salt: impl std::hash::Hash, series: &[PlotSeries<'_>]) {
+    let id = ui.id().with(("bench_ui_plot", id_salt));
+    let mut view = ui.data(|d| d.get_temp::<PlotView>(id)).unwrap_or_default();
+
+    let size = Vec2::new(ui.available_width().max(120.0), CANVAS_HEIGHT);
+    let (response, painter) = ui.allocate_painter(size, Sense::click_and_drag());
+    let rect = response.rect;
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    if response.hovered() {
+        let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+        if scroll != 0.0 {
+            view.window = (view.window * (-scroll * 0.005).exp()).max(MIN_WINDOW);
+        }
+    }
+    if response.dragged() {
+        view.offset = (view.offset + response.drag_delta().x * view.window / rect.width()).max(0.0);
+    }
+    if response.double_clicked() {
+        view = PlotView::default();
+    }
+    ui.data_mut(|d| d.insert_temp(id, view));
+
+    let Some(latest) = series
+        .iter()
+        .filter_map(|s| s.samples.back().map(|&(t, _)| t))
+        .reduce(f32::max)
+    else {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "No samples",
+            egui::FontId::proportional(12.0),
+            ui.visuals().weak_text_color(),
+        );
+        return;
+    };
+    let end = latest - view.offset;
+    let start = end - view.window;
+
+    let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+    for s in series {
+        for &(_, v) in visible(s.samples, start, end) {
+            min = min.min(v);
+            max = max.max(v);
+        }
+    }
+    if !min.is_finite() {
+        return;
+    }
+    if max - min < f32::EPSILON {
+        (min, max) = (min - 0.5, max + 0.5);
+    }
+    let pad = (max - min) * 0.05;
+    let bounds = Rect::from_x_y_ranges(start..=end, (min - pad)..=(max + pad));
+    let to_screen = |t: f32, v: f32| {
+        Pos2::new(
+            egui::remap(t, bounds.x_range(), rect.x_range()),
+            egui::remap(v, bounds.y_range(), rect.bottom()..=rect.top()),
+        )
+    };
+
+    paint_axes(ui, &painter, rect, bounds);
+    for s in series {
+        let points: Vec<Pos2> = visible(s.samples, start, end)
+            .map(|&(t, v)| to_screen(t, v))
+            .collect();
+        painter.add(egui::Shape::line(points, Stroke::new(1.5, s.color)));
+    }
+    if let Some(pointer) = response.hover_pos() {
+        hover_readout(ui, &painter, rect, bounds, pointer, series);
+    }
+}
+
+/// Samples inside `[start, end]`, plus one on each side so lines reach the edges.
+fn visible(
+    samples: &VecDeque<(f32, f32)>,
+    start: f32,
+    end: f32,
+) -> impl Iterator<Item = &(f32, f32)> {
+    let first = samples
+        .partition_point(|&(t, _)| t < start)
+        .saturating_sub(1);
+    let last = (samples.partition_point(|&(t, _)| t <= end) + 1).min(samples.len());
+    samples.range(first..last.max(first))
+}
+
+/// Value labels for the top and bottom of the visible range and the time span.
+fn paint_axes(ui: &Ui, painter: &egui::Painter, rect: Rect, bounds: Rect) {
+    let color = ui.visuals().weak_text_color();
+    let font = egui::FontId::monospace(10.0);
+    let zero = egui::remap(0.0, bounds.y_range(), rect.bottom()..=rect.top());
+    if rect.y_range().contains(zero) {
+        painter.hline(
+            rect.x_range(),
+            zero,
+            Stroke::new(1.0, ui.visuals().widgets.noninteractive.bg_stroke.color),
+        );
+    }
+    let inset = rect.shrink(3.0);
+    painter.text(
+        inset.left_top(),
+        egui::Align2::LEFT_TOP,
+        format!("{:.3}", bounds.max.y),
+        font.clone(),
+        color,
+    );
+    painter.text(
+        inset.left_bottom(),
+        egui::Align2::LEFT_BOTTOM,
+        format!("{:.3}", bounds.min.y),
+        font.clone(),
+        color,
+    );
+    painter.text(
+        inset.right_bottom(),
+        egui::Align2::RIGHT_BOTTOM,
+        format!("{:.1} s", bounds.width()),
+        font,
+        color,
+    );
+}
+
+/// Vertical cursor line with the value of each series at that time.
+fn hover_readout(
+    ui: &Ui,
+    painter: &egui::Painter,
+    rect: Rect,
+    bounds: Rect,
+    pointer: Pos2,
+    series: &[PlotSeries<'_>],
+) {
+    painter.vline(
+        pointer.x,
+        rect.y_range(),
+        Stroke::new(1.0, ui.visuals().weak_text_color()),
+    );
+    let time = egui::remap(pointer.x, rect.x_range(), bounds.x_range());
+    let font = egui::FontId::monospace(11.0);
+    let mut y = rect.top() + 4.0;
+    for s in series {
+        let i = s.samples.partition_point(|&(t, _)| t < time);
+        let Some(&(_, value)) = s.samples.get(i).or_else(|| s.samples.back()) else {
+            continue;
+        };
+        let galley =
+            painter.layout_no_wrap(format!("{}: {value:.4}", s.name), font.clone(), s.color);
+        let x = if pointer.x + galley.size().x + 8.0 > rect.right() {
+            pointer.x - galley.size().x - 6.0
+        } else {
+            pointer.x + 6.0
+        };
+        painter.galley(Pos2::new(x, y), galley, s.color);
+        y += 14.0;
+    }
+}
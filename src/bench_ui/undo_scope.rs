@@ -0,0 +1,91 @@
+//! # undo_scope.rs
+//!
+//! # undo_scope.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Debounced undo for custom panels. `undo_scope` wraps the widgets that edit one entity: when a
+//! pointer press starts inside the scope (or one of its widgets takes keyboard focus) it takes a
+//! reflected baseline of the entity's components, and when the press ends (or focus leaves) it
+//! pushes a single undo action for everything that changed in between, so a whole slider drag
+//! undoes in one step. It reuses the inspector's change-tick-based tracking.
+//!
+//! 为自定义面板提供防抖撤销。`undo_scope` 包裹编辑某个实体的控件：指针在作用域内按下（或其中的控件
+//! 获得键盘焦点）时为该实体的组件拍摄反射基线，松开（或焦点离开）时把期间的全部变化
+//! 作为一个撤销操作推入，因此一次完整的滑块拖拽只需撤销一步。
+//! 它复用检查器基于变更 tick 的跟踪逻辑。
+
+use bevy::prelude::*;
+use egui::Ui;
+use std::collections::HashMap;
+
+use crate::inspector::InspectorUndoState;
+use crate::undo::UndoStack;
+
+/// Baselines of the scopes whose edit is in progress, by scope ID, with the egui pass that last
+/// drew each scope.
+#[derive(Resource, Default)]
+struct UndoScopes(HashMap<egui::Id, (u64, InspectorUndoState)>);
+
+/// Whether a pointer press that started in `rect` is held, or a widget in `rect` has focus.
+fn editing_inside(ui: &Ui, rect: egui::Rect) -> bool {
+    let pressed_inside = ui.input(|i| {
+        i.pointer.any_down() && i.pointer.press_origin().is_some_and(|p| rect.contains(p))
+    });
+    let focused_inside = ui
+        .memory(|m| m.focused())
+        .and_then(|focused| ui.ctx().read_response(focused))
+        .is_some_and(|response| rect.contains_rect(response.rect));
+    pressed_inside || focused_inside
+}
+
+/// Run `add_contents` (typically `bench_ui` widgets editing `entity`'s components) and record
+/// the edits of each pointer drag (or text edit) as one undo action. Only a press that starts
+/// inside the scope, or keyboard focus on one of its widgets, opens an edit.
+///
+/// ```ignore
+/// bench_ui::undo_scope(ui, world, entity, |ui, world| {
+///     if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+///         bench_ui::transform(ui, "Transform", &mut transform);
+///     }
+/// });
+/// ```
+pub fn undo_scope<R>(
+    ui: &mut Ui,
+    world: &mut World,
+    entity: Entity,
+    add_contents: impl FnOnce(&mut Ui, &mut World) -> R,
+) -> R {
+    let id = ui.make_persistent_id(("workbench_undo_scope", entity));
+    let pass = ui.ctx().cumulative_pass_nr();
+    // The scope's rect as of the previous pass.
+    let editing = ui
+        .data(|d| d.get_temp::<egui::Rect>(id))
+        .is_some_and(|rect| editing_inside(ui, rect));
+    let mut scopes = world.remove_resource::<UndoScopes>().unwrap_or_default();
+    // Drop the baselines of scopes that are no longer drawn.
+    scopes.0.retain(|_, (drawn, _)| *drawn + 1 >= pass);
+    if editing && !scopes.0.contains_key(&id) {
+        let mut state = InspectorUndoState::default();
+        state.rebaseline(world, entity);
+        scopes.0.insert(id, (pass, state));
+    }
+    if let Some((drawn, _)) = scopes.0.get_mut(&id) {
+        *drawn = pass;
+    }
+
+    let inner = ui.scope(|ui| add_contents(ui, world));
+    ui.data_mut(|d| d.insert_temp(id, inner.response.rect));
+
+    if !editing
+        && let Some((_, mut state)) = scopes.0.remove(&id)
+        && let Some(action) = state.take_edits(world, entity)
+        && let Some(mut undo_stack) = world.get_resource_mut::<UndoStack>()
+    {
+        undo_stack.push(action);
+    }
+    world.insert_resource(scopes);
+    inner.inner
+}
@@ -1,59 +1,176 @@
 //! TOML-based editor configuration.
 
+use bevy::camera::RenderTarget;
 use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowRef};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+mod migrate;
+mod registry;
+
+pub use migrate::{SETTINGS_VERSION, migrate_v1_to_v2};
+pub use registry::{SettingsDrawFn, SettingsRegistry, ToolSettings};
+
 /// Persistent editor settings, stored as TOML.
 #[derive(Resource, Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WorkbenchSettings {
+    /// Schema version the settings were written with (see [`SETTINGS_VERSION`]).
+    #[serde(default = "migrate::unversioned")]
+    pub version: u32,
     /// UI scale factor (1.0 = default).
     #[serde(default = "default_ui_scale")]
     pub ui_scale: f32,
+    /// Multiply the UI scale by each window's monitor DPI scale (when false, `ui_scale`
+    /// counts physical pixels, so the UI has the same pixel size on every monitor).
+    #[serde(default = "default_dpi_aware")]
+    pub dpi_aware: bool,
+    /// UI scale overriding `ui_scale` per window: `"main"` or `"window_<n>"` for secondary
+    /// dock windows.
+    #[serde(default)]
+    pub window_scales: BTreeMap<String, f32>,
     /// Theme configuration.
     #[serde(default)]
     pub theme: crate::theme::ThemeConfig,
+    /// Keyboard accessibility configuration.
+    #[serde(default)]
+    pub accessibility: crate::accessibility::AccessibilityConfig,
     /// Interface language.
     #[serde(default)]
     pub locale: crate::i18n::Locale,
     /// Font configuration.
     #[serde(default)]
     pub font: crate::font::FontConfig,
+    /// Console configuration.
+    #[serde(default)]
+    pub console: crate::console::ConsoleConfig,
+    /// Use the shorter toolbar with smaller mode buttons.
+    #[serde(default)]
+    pub compact_toolbar: bool,
+    /// Primary window title; `{app}`, `{workspace}` and `{mode}` expand to the app's own
+    /// title, the working directory name and the editor mode. Empty keeps the app's title.
+    #[serde(default = "default_window_title")]
+    pub window_title: String,
+    /// Hide editor panels (maximize the game view) while playing.
+    #[serde(default)]
+    pub hide_panels_on_play: bool,
+    /// Keep a separate dock layout for each editor mode.
+    #[serde(default)]
+    pub per_mode_layouts: bool,
+    /// Record dock layout changes in their own undo history (Ctrl+Alt+Z) instead of the main one.
+    #[serde(default = "default_separate_layout_undo")]
+    pub separate_layout_undo: bool,
+    /// What the game does while the editor window is unfocused.
+    #[serde(default)]
+    pub unfocused_behavior: crate::mode::UnfocusedBehavior,
+    /// Editor keybindings.
+    #[serde(default)]
+    pub keybindings: crate::keybind::KeyBindings,
+    /// Editor camera sensitivities.
+    #[cfg(feature = "game-view")]
+    #[serde(default)]
+    pub editor_camera: crate::editor_camera::EditorCameraSettings,
+    /// Audio preview master volume and mute on pause.
+    #[cfg(feature = "audio")]
+    #[serde(default)]
+    pub audio: crate::audio::AudioSettings,
+    /// Open the welcome panel on startup.
+    #[serde(default = "default_show_welcome")]
+    pub show_welcome: bool,
+    /// Layout files recently saved or loaded, most recent first.
+    #[serde(default)]
+    pub recent_layouts: Vec<String>,
+    /// Settings registered by tools with `WorkbenchApp::register_settings`, by namespace.
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub tools: toml::Table,
 }
 
 fn default_ui_scale() -> f32 {
     1.0
 }
 
+fn default_dpi_aware() -> bool {
+    true
+}
+
+fn default_window_title() -> String {
+    crate::window_title::DEFAULT_WINDOW_TITLE.to_string()
+}
+
+fn default_show_welcome() -> bool {
+    true
+}
+
+fn default_separate_layout_undo() -> bool {
+    true
+}
+
+/// Number of layout files kept in [`WorkbenchSettings::recent_layouts`].
+const MAX_RECENT_LAYOUTS: usize = 8;
+
 impl Default for WorkbenchSettings {
     fn default() -> Self {
         Self {
+            version: SETTINGS_VERSION,
             ui_scale: 1.0,
+            dpi_aware: true,
+            window_scales: BTreeMap::new(),
             theme: crate::theme::ThemeConfig::default(),
+            accessibility: crate::accessibility::AccessibilityConfig::default(),
             locale: crate::i18n::Locale::default(),
             font: crate::font::FontConfig::default(),
+            console: crate::console::ConsoleConfig::default(),
+            compact_toolbar: false,
+            window_title: default_window_title(),
+            hide_panels_on_play: false,
+            per_mode_layouts: false,
+            separate_layout_undo: true,
+            unfocused_behavior: crate::mode::UnfocusedBehavior::default(),
+            keybindings: crate::keybind::KeyBindings::default(),
+            #[cfg(feature = "game-view")]
+            editor_camera: crate::editor_camera::EditorCameraSettings::default(),
+            #[cfg(feature = "audio")]
+            audio: crate::audio::AudioSettings::default(),
+            show_welcome: true,
+            recent_layouts: Vec::new(),
+            tools: toml::Table::new(),
         }
     }
 }
 
 impl WorkbenchSettings {
-    /// Load from a TOML file, or return defaults if not found.
+    /// Load from a TOML file (browser `localStorage` on wasm), or return defaults if not found.
+    /// Files from older schema versions are migrated; a file that still can't be read is kept
+    /// as a `.bak` copy before defaults are used.
     pub fn load(path: &std::path::Path) -> Self {
-        match std::fs::read_to_string(path) {
-            Ok(content) => toml::from_str(&content).unwrap_or_else(|e| {
-                warn!("Failed to parse {}: {e}", path.display());
-                Self::default()
-            }),
-            Err(_) => Self::default(),
-        }
+        let Some(content) = crate::storage::read_string(path) else {
+            return Self::default();
+        };
+        Self::parse(&content).unwrap_or_else(|e| {
+            warn!("Failed to parse {}: {e}", path.display());
+            crate::storage::back_up(path, &content);
+            Self::default()
+        })
+    }
+
+    /// Move `path` to the front of the recent layouts.
+    pub fn push_recent_layout(&mut self, path: String) {
+        self.recent_layouts.retain(|p| *p != path);
+        self.recent_layouts.insert(0, path);
+        self.recent_layouts.truncate(MAX_RECENT_LAYOUTS);
+    }
+
+    /// Parse TOML text of any schema version, migrating it to the current one.
+    pub fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        migrate::migrate_settings(&mut table);
+        toml::Value::Table(table).try_into()
     }
 
     /// Save to a TOML file.
     pub fn save(&self, path: &std::path::Path) {
         let content = toml::to_string_pretty(self).expect("serialize WorkbenchSettings");
-        if let Some(parent) = path.parent() {
-            let _ = std::fs::create_dir_all(parent);
-        }
-        if let Err(e) = std::fs::write(path, content) {
+        if let Err(e) = crate::storage::write_string(path, &content) {
             warn!("Failed to save config to {}: {e}", path.display());
         }
     }
@@ -70,43 +187,104 @@ impl Default for ConfigPath {
     }
 }
 
-/// System that applies settings and handles save requests from SettingsPanel.
+/// System that applies settings and handles apply, save and revert requests from SettingsPanel.
 pub fn config_apply_system(
     mut settings: ResMut<WorkbenchSettings>,
     config_path: Res<ConfigPath>,
-    mut egui_contexts: Query<&mut bevy_egui::EguiContextSettings>,
+    mut egui_contexts: Query<(&mut bevy_egui::EguiContextSettings, Option<&RenderTarget>)>,
+    windows: Query<(Entity, &Window)>,
+    primary: Query<Entity, With<PrimaryWindow>>,
     mut tile_state: ResMut<crate::dock::TileLayoutState>,
     mut theme_state: ResMut<crate::theme::ThemeState>,
     mut i18n: ResMut<crate::i18n::I18n>,
     mut font_state: ResMut<crate::font::FontState>,
+    mut mode_controller: ResMut<crate::mode::ModeController>,
+    mut undo_stack: ResMut<crate::undo::UndoStack>,
+    mut console: Option<ResMut<crate::console::ConsoleState>>,
+    mut registry: Option<ResMut<SettingsRegistry>>,
 ) {
-    // Check if SettingsPanel has a pending save
-    if let Some(panel) = tile_state.get_panel_mut::<crate::menu_bar::SettingsPanel>("settings")
-        && panel.save_requested
-    {
-        panel.save_requested = false;
-        settings.ui_scale = panel.edited_scale;
-        settings.theme.edit_theme = panel.edited_edit_theme;
-        settings.theme.play_theme = panel.edited_play_theme;
-        settings.theme.edit_brightness = panel.edited_edit_brightness;
-        settings.theme.play_brightness = panel.edited_play_brightness;
-        settings.locale = panel.edited_locale;
-        // Check if font changed
-        if settings.font.custom_font_path != panel.edited_font_path {
-            settings.font.custom_font_path = panel.edited_font_path.clone();
-            font_state.installed = false; // Force font reinstall
+    // Check if SettingsPanel has pending edits to apply
+    if let Some(panel) = tile_state.get_panel_mut::<crate::menu_bar::SettingsPanel>("settings") {
+        if std::mem::take(&mut panel.revert_requested) {
+            let saved = panel.saved.clone();
+            panel.load_edits(&saved);
+            panel.apply_requested = true;
+            if let Some(registry) = registry.as_deref_mut() {
+                registry.revert();
+            }
+        }
+        let save = std::mem::take(&mut panel.save_requested);
+        if std::mem::take(&mut panel.apply_requested) || save {
+            let (locale, font) = (settings.locale, settings.font.custom_font_path.clone());
+            panel.write_edits(&mut settings);
+            // A locale switch may need a different fallback font
+            if settings.locale != locale || settings.font.custom_font_path != font {
+                font_state.installed = false; // Force font reinstall
+            }
+            mode_controller.unfocused = settings.unfocused_behavior;
+            undo_stack.separate_layout = settings.separate_layout_undo;
+            // Apply theme changes to runtime state
+            theme_state.config = settings.theme.clone();
+            // Apply locale change
+            i18n.set_locale(settings.locale);
+            if let Some(console) = console.as_deref_mut() {
+                console.apply_config(&settings.console);
+            }
+            if let Some(registry) = registry.as_deref_mut() {
+                registry.apply();
+            }
+            if save {
+                if let Some(registry) = registry.as_deref_mut() {
+                    registry.save_into(&mut settings.tools);
+                }
+                settings.save(&config_path.0);
+                panel.saved = settings.clone();
+            }
         }
-        // Apply theme changes to runtime state
-        theme_state.config = settings.theme.clone();
-        // Apply locale change
-        i18n.set_locale(settings.locale);
-        settings.save(&config_path.0);
     }
 
-    // Apply scale via EguiContextSettings (bevy_egui handles viewport sync)
-    for mut ctx_settings in &mut egui_contexts {
-        if (ctx_settings.scale_factor - settings.ui_scale).abs() > f32::EPSILON {
-            ctx_settings.scale_factor = settings.ui_scale;
+    let primary = primary.single().ok();
+    let mut open_windows: Vec<String> = windows
+        .iter()
+        .filter_map(|(entity, _)| window_scale_key(entity, primary, &tile_state))
+        .collect();
+    open_windows.sort();
+    if let Some(panel) = tile_state.get_panel_mut::<crate::menu_bar::SettingsPanel>("settings") {
+        panel.open_windows = open_windows;
+    }
+
+    // Apply scale via EguiContextSettings (bevy_egui handles viewport sync and multiplies
+    // by the window's DPI scale)
+    for (mut ctx_settings, target) in &mut egui_contexts {
+        let window = match target {
+            Some(RenderTarget::Window(WindowRef::Entity(entity))) => Some(*entity),
+            Some(RenderTarget::Window(WindowRef::Primary)) | None => primary,
+            Some(_) => None,
+        };
+        let mut scale = window
+            .and_then(|w| window_scale_key(w, primary, &tile_state))
+            .and_then(|key| settings.window_scales.get(&key).copied())
+            .unwrap_or(settings.ui_scale);
+        if !settings.dpi_aware
+            && let Some((_, os_window)) = window.and_then(|w| windows.get(w).ok())
+        {
+            scale /= os_window.scale_factor();
         }
+        if (ctx_settings.scale_factor - scale).abs() > f32::EPSILON {
+            ctx_settings.scale_factor = scale;
+        }
+    }
+}
+
+/// Key of `window` in [`WorkbenchSettings::window_scales`], if it is a workbench window.
+fn window_scale_key(
+    window: Entity,
+    primary: Option<Entity>,
+    tile_state: &crate::dock::TileLayoutState,
+) -> Option<String> {
+    if Some(window) == primary {
+        Some("main".to_string())
+    } else {
+        tile_state.window_scale_key(window)
     }
 }
@@ -0,0 +1,88 @@
+//! # migrate.rs
+//!
+//! # migrate.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Versioning of `settings.toml`. The file records the schema version it was written with;
+//! loading parses it into a plain TOML table first, runs the migration for every version step
+//! between the file and `SETTINGS_VERSION`, and only then deserializes `WorkbenchSettings`. A
+//! future schema change adds a `migrate_vN_to_vN+1` step here instead of making old files fail
+//! to parse and fall back to defaults.
+//!
+//! `settings.toml` 的版本管理。文件中记录了写入时使用的结构版本；加载时会先将其解析为普通的
+//! TOML 表，依次执行从文件版本到 `SETTINGS_VERSION` 之间每一步的迁移，然后才反序列化为
+//! `WorkbenchSettings`。今后修改结构时，在此添加一个 `migrate_vN_to_vN+1` 步骤，而不是让旧文件
+//! 解析失败并回退到默认值。
+
+use bevy::prelude::*;
+
+/// Current version of the `settings.toml` schema.
+pub const SETTINGS_VERSION: u32 = 2;
+
+/// Version of settings files written before versioning.
+pub(super) fn unversioned() -> u32 {
+    1
+}
+
+/// Bring a parsed settings table up to [`SETTINGS_VERSION`]. Tables from a newer version are
+/// left alone (unknown fields are ignored when deserializing).
+pub(super) fn migrate_settings(table: &mut toml::Table) {
+    let version = table
+        .get("version")
+        .and_then(toml::Value::as_integer)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or_else(unversioned);
+    if version > SETTINGS_VERSION {
+        warn!("Settings were written by a newer version (schema {version}); loading what we can");
+        return;
+    }
+    if version < 2 {
+        migrate_v1_to_v2(table);
+    }
+    table.insert(
+        "version".into(),
+        toml::Value::Integer(SETTINGS_VERSION.into()),
+    );
+}
+
+/// Version 1 files predate the `version` key. Every field added since has a serde default, so
+/// the schema is otherwise unchanged and the step only stamps the version.
+pub fn migrate_v1_to_v2(table: &mut toml::Table) {
+    table.insert("version".into(), toml::Value::Integer(2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(table: &toml::Table) -> Option<i64> {
+        table.get("version").and_then(toml::Value::as_integer)
+    }
+
+    #[test]
+    fn unversioned_settings_are_stamped() {
+        let mut table: toml::Table = toml::from_str("ui_scale = 1.5").unwrap();
+        migrate_settings(&mut table);
+        assert_eq!(version(&table), Some(i64::from(SETTINGS_VERSION)));
+        assert_eq!(table.get("ui_scale"), Some(&toml::Value::Float(1.5)));
+    }
+
+    #[test]
+    fn current_settings_are_unchanged() {
+        let source = format!("version = {SETTINGS_VERSION}\nui_scale = 2.0");
+        let mut table: toml::Table = toml::from_str(&source).unwrap();
+        let before = table.clone();
+        migrate_settings(&mut table);
+        assert_eq!(table, before);
+    }
+
+    #[test]
+    fn newer_settings_are_left_alone() {
+        let mut table: toml::Table = toml::from_str("version = 99").unwrap();
+        migrate_settings(&mut table);
+        assert_eq!(version(&table), Some(99));
+    }
+}
@@ -0,0 +1,201 @@
+//! # registry.rs
+//!
+//! # registry.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Settings registered by downstream tools. A tool registers a serde-able struct under a
+//! namespace together with a closure drawing its fields; the struct is loaded from and saved to
+//! the `[tools.<namespace>]` table of `settings.toml`, and edited in the Settings panel like the
+//! built-in settings (Apply, Save, Revert, dirty markers). Tools read the applied value with
+//! `SettingsRegistry::get`.
+//!
+//! 下游工具注册的设置。工具以命名空间注册一个可序列化的结构体，并附带绘制其字段的闭包；该结构体
+//! 从 `settings.toml` 的 `[tools.<namespace>]` 表中加载并保存到其中，并像内置设置一样在设置面板
+//! 中编辑（应用、保存、还原、修改标记）。工具通过 `SettingsRegistry::get` 读取已应用的值。
+
+use std::any::Any;
+
+use bevy::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// A settings struct that can be registered with [`crate::WorkbenchApp::register_settings`].
+pub trait ToolSettings:
+    Serialize + DeserializeOwned + Default + Clone + PartialEq + Send + Sync + 'static
+{
+}
+
+impl<T> ToolSettings for T where
+    T: Serialize + DeserializeOwned + Default + Clone + PartialEq + Send + Sync + 'static
+{
+}
+
+/// Closure drawing the fields of registered settings.
+pub type SettingsDrawFn<T> = Box<dyn FnMut(&mut egui::Ui, &mut T) + Send + Sync>;
+
+/// Type-erased registered settings.
+trait ErasedSettings: Send + Sync {
+    fn ui(&mut self, ui: &mut egui::Ui);
+    fn dirty(&self) -> bool;
+    fn apply(&mut self);
+    fn revert(&mut self);
+    /// Serialize the edited value and take it as saved.
+    fn save(&mut self) -> Option<toml::Value>;
+    fn value(&self) -> &dyn Any;
+}
+
+/// Registered settings of type `T`.
+struct Entry<T> {
+    /// Applied value, read by the tool.
+    value: T,
+    /// Value being edited in the Settings panel.
+    edited: T,
+    /// Value in the settings file.
+    saved: T,
+    draw: SettingsDrawFn<T>,
+}
+
+impl<T: ToolSettings> ErasedSettings for Entry<T> {
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        (self.draw)(ui, &mut self.edited);
+    }
+
+    fn dirty(&self) -> bool {
+        self.edited != self.saved
+    }
+
+    fn apply(&mut self) {
+        if self.value != self.edited {
+            self.value = self.edited.clone();
+        }
+    }
+
+    fn revert(&mut self) {
+        self.edited = self.saved.clone();
+    }
+
+    fn save(&mut self) -> Option<toml::Value> {
+        self.saved = self.edited.clone();
+        toml::Value::try_from(&self.saved)
+            .inspect_err(|e| warn!("Failed to serialize tool settings: {e}"))
+            .ok()
+    }
+
+    fn value(&self) -> &dyn Any {
+        &self.value
+    }
+}
+
+/// A registered settings section.
+struct Section {
+    namespace: String,
+    label: String,
+    settings: Box<dyn ErasedSettings>,
+}
+
+/// Settings sections registered by tools, shown in the Advanced tab of the Settings panel and
+/// persisted under `[tools.<namespace>]` in `settings.toml`.
+#[derive(Resource, Default)]
+pub struct SettingsRegistry {
+    sections: Vec<Section>,
+}
+
+impl SettingsRegistry {
+    /// Register `T` under `namespace`, starting from its `stored` value in the settings file
+    /// (defaults when missing or invalid). Registering a namespace again replaces it.
+    pub fn register<T: ToolSettings>(
+        &mut self,
+        namespace: impl Into<String>,
+        label: impl Into<String>,
+        stored: Option<&toml::Value>,
+        draw: SettingsDrawFn<T>,
+    ) {
+        let namespace = namespace.into();
+        let value: T = stored
+            .and_then(|stored| {
+                stored
+                    .clone()
+                    .try_into()
+                    .inspect_err(|e| warn!("Invalid settings for tool `{namespace}`: {e}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+        let section = Section {
+            label: label.into(),
+            settings: Box::new(Entry {
+                edited: value.clone(),
+                saved: value.clone(),
+                value,
+                draw,
+            }),
+            namespace,
+        };
+        match self
+            .sections
+            .iter_mut()
+            .find(|s| s.namespace == section.namespace)
+        {
+            Some(existing) => *existing = section,
+            None => self.sections.push(section),
+        }
+    }
+
+    /// The applied settings registered under `namespace`, if they are a `T`.
+    pub fn get<T: ToolSettings>(&self, namespace: &str) -> Option<&T> {
+        self.sections
+            .iter()
+            .find(|s| s.namespace == namespace)?
+            .settings
+            .value()
+            .downcast_ref()
+    }
+
+    /// Whether any section has edits that differ from the settings file.
+    pub fn is_dirty(&self) -> bool {
+        self.sections.iter().any(|s| s.settings.dirty())
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Make the edited values the applied ones.
+    pub(crate) fn apply(&mut self) {
+        for section in &mut self.sections {
+            section.settings.apply();
+        }
+    }
+
+    /// Reset the edited values to the saved ones.
+    pub(crate) fn revert(&mut self) {
+        for section in &mut self.sections {
+            section.settings.revert();
+        }
+    }
+
+    /// Write the edited values into the `tools` table of the settings.
+    pub(crate) fn save_into(&mut self, tools: &mut toml::Table) {
+        for section in &mut self.sections {
+            if let Some(value) = section.settings.save() {
+                tools.insert(section.namespace.clone(), value);
+            }
+        }
+    }
+
+    /// Headed sections for the Settings panel, marked when they have unsaved edits.
+    pub(crate) fn ui(&mut self, ui: &mut egui::Ui) {
+        for section in &mut self.sections {
+            ui.separator();
+            let label = if section.settings.dirty() {
+                format!("{} ●", section.label)
+            } else {
+                section.label.clone()
+            };
+            ui.heading(label);
+            section.settings.ui(ui);
+        }
+    }
+}
@@ -1,23 +1,44 @@
 //! Console panel: collects and displays tracing logs.
 
+use crate::bench_ui::Icon;
 use bevy::log::tracing_subscriber::Layer;
 use bevy::log::{BoxedLayer, tracing, tracing_subscriber};
 use bevy::prelude::*;
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::sync::{Arc, Mutex, mpsc};
 
-use crate::dock::WorkbenchPanel;
-use crate::i18n::I18n;
+mod eval;
+mod export;
+mod filter;
+mod panel;
+mod pause;
+mod session;
+mod verbosity;
+mod view;
+
+pub(crate) use eval::reflect_component;
+pub use eval::{EvalLine, EvalState};
+#[cfg(feature = "file-dialogs")]
+pub(crate) use export::EXPORT_DIALOG;
+pub use export::ExportFormat;
+pub use filter::{ConsoleFilter, SavedFilter};
+pub use panel::{ConsolePanel, ConsoleTab};
+pub use pause::console_pause_on_error_system;
+pub use session::{PlaySession, console_session_end_observer, console_session_start_observer};
+pub use verbosity::{SharedTargetLevels, TargetLevel, TargetLevels};
 
 /// A single log entry.
-#[derive(Clone)]
+#[derive(Clone, serde::Serialize)]
 pub struct LogEntry {
     pub level: LogLevel,
     pub message: String,
     pub target: String,
+    /// Play session this entry was logged in (`None` = logged while editing).
+    pub session: Option<usize>,
 }
 
-/// Log severity level.
-#[derive(Clone, Copy, PartialEq, Eq)]
+/// Log severity level (ordered from least to most severe).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
 pub enum LogLevel {
     Trace,
     Debug,
@@ -27,17 +48,26 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
-    fn color(&self) -> egui::Color32 {
+    /// All levels, least severe first.
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Trace,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+    ];
+
+    fn color(&self, ui: &egui::Ui) -> egui::Color32 {
+        let palette = crate::theme::palette(ui.ctx());
         match self {
-            LogLevel::Trace => egui::Color32::GRAY,
-            LogLevel::Debug => egui::Color32::LIGHT_BLUE,
-            LogLevel::Info => egui::Color32::WHITE,
-            LogLevel::Warn => egui::Color32::YELLOW,
-            LogLevel::Error => egui::Color32::RED,
+            LogLevel::Trace => ui.visuals().weak_text_color(),
+            LogLevel::Debug => palette.info,
+            LogLevel::Info => ui.visuals().strong_text_color(),
+            LogLevel::Warn => palette.warn,
+            LogLevel::Error => palette.error,
         }
     }
 
-    #[allow(dead_code)]
     fn label(&self) -> &str {
         match self {
             LogLevel::Trace => "TRACE",
@@ -48,13 +78,14 @@ impl LogLevel {
         }
     }
 
-    fn icon(&self) -> &str {
-        match self {
-            LogLevel::Trace | LogLevel::Debug => "🔍",
-            LogLevel::Info => "ℹ",
-            LogLevel::Warn => "⚠",
-            LogLevel::Error => "❌",
-        }
+    fn icon(&self) -> &'static str {
+        let icon = match self {
+            LogLevel::Trace | LogLevel::Debug => Icon::Debug,
+            LogLevel::Info => Icon::Info,
+            LogLevel::Warn => Icon::Warning,
+            LogLevel::Error => Icon::Error,
+        };
+        icon.glyph()
     }
 }
 
@@ -71,6 +102,8 @@ pub fn log_channel() -> (LogSender, LogReceiver) {
 /// A tracing [`Layer`] that forwards log events to the console panel.
 struct ConsoleLayer {
     sender: LogSender,
+    /// Source-side verbosity table (empty unless `filter_at_source` is enabled).
+    filter: SharedTargetLevels,
 }
 
 impl<S: tracing::Subscriber> Layer<S> for ConsoleLayer {
@@ -86,7 +119,13 @@ impl<S: tracing::Subscriber> Layer<S> for ConsoleLayer {
             tracing::Level::DEBUG => LogLevel::Debug,
             tracing::Level::TRACE => LogLevel::Trace,
         };
-        let target = event.metadata().target().to_string();
+        let target = event.metadata().target();
+        if let Ok(filter) = self.filter.read()
+            && !filter.allows(target, level)
+        {
+            return;
+        }
+        let target = target.to_string();
 
         // Extract the message from the event fields
         let mut visitor = MessageVisitor(String::new());
@@ -96,6 +135,7 @@ impl<S: tracing::Subscriber> Layer<S> for ConsoleLayer {
             level,
             message: visitor.0,
             target,
+            session: None,
         });
     }
 }
@@ -138,43 +178,165 @@ impl tracing::field::Visit for MessageVisitor {
 /// ```
 pub fn console_log_layer(app: &mut App) -> Option<BoxedLayer> {
     let (sender, receiver) = log_channel();
-    app.insert_resource(ConsoleState::with_receiver(receiver));
-    Some(Box::new(ConsoleLayer { sender }))
+    let filter = SharedTargetLevels::default();
+    let mut state = ConsoleState::with_receiver(receiver);
+    state.layer_filter = Some(filter.clone());
+    app.insert_resource(state);
+    Some(Box::new(ConsoleLayer { sender, filter }))
+}
+
+/// Console configuration stored in settings.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConsoleConfig {
+    /// Named filters shown in the console's saved-filters dropdown.
+    #[serde(default)]
+    pub saved_filters: Vec<SavedFilter>,
+    /// Maximum number of entries kept; the oldest are dropped first.
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    /// Maximum approximate memory used by entries, in bytes.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+}
+
+fn default_max_entries() -> usize {
+    10_000
+}
+
+fn default_max_bytes() -> usize {
+    16 * 1024 * 1024
+}
+
+impl Default for ConsoleConfig {
+    fn default() -> Self {
+        Self {
+            saved_filters: Vec::new(),
+            max_entries: default_max_entries(),
+            max_bytes: default_max_bytes(),
+        }
+    }
 }
 
 /// Resource holding console log state.
 #[derive(Resource)]
 pub struct ConsoleState {
-    pub logs: Vec<LogEntry>,
+    /// Bounded log buffer (oldest first).
+    pub logs: VecDeque<LogEntry>,
+    /// Maximum number of entries kept in `logs`.
+    pub max_entries: usize,
+    /// Maximum approximate memory of `logs`, in bytes.
+    pub max_bytes: usize,
+    /// Approximate memory currently used by `logs`, in bytes.
+    bytes: usize,
     pub auto_scroll: bool,
+    /// Show Trace/Debug entries. Note that `LogPlugin::level` and `LogPlugin::filter`
+    /// still decide which events reach the console in the first place.
+    pub show_trace: bool,
+    pub show_debug: bool,
     pub show_info: bool,
     pub show_warn: bool,
     pub show_error: bool,
+    /// Per-target minimum levels, applied at display time.
+    pub target_levels: TargetLevels,
+    /// Whether `target_levels` is also applied in the tracing layer.
+    pub filter_at_source: bool,
     pub filter_text: String,
+    /// Whether the free-text part of `filter_text` is a regular expression.
+    pub filter_regex: bool,
+    /// Compiled form of `filter_text`.
+    filter: ConsoleFilter,
+    /// Query text and regex flag `filter` was compiled from.
+    filter_source: Option<(String, bool)>,
+    /// Regex compile error for the current query, if any.
+    filter_error: Option<String>,
+    /// Name typed into the "save current filter" field.
+    new_filter_name: String,
     /// Whether to auto-clear logs when entering Play mode.
     pub auto_clear_on_play: bool,
+    /// Whether an `Error` entry logged during Play switches to Pause.
+    pub pause_on_error: bool,
+    /// Target prefixes whose errors never trigger Pause on Error.
+    pub pause_on_error_exceptions: Vec<String>,
+    /// Index into `logs` of the entry that triggered the last error pause (highlighted).
+    error_pause_entry: Option<usize>,
+    /// Set when a qualifying error arrived and the pause hasn't been applied yet.
+    error_pause_pending: bool,
+    /// Whether to group entries under collapsible Play session headers.
+    pub group_by_session: bool,
+    /// When true, only entries from the most recent Play session are shown.
+    pub show_current_session_only: bool,
+    /// Play sessions recorded since the last clear (oldest first).
+    pub sessions: Vec<PlaySession>,
+    /// Session headers the user collapsed.
+    collapsed_sessions: HashSet<usize>,
+    /// The Play session currently running, if any.
+    current_session: Option<usize>,
+    /// Number of sessions started so far (used for session numbering).
+    session_counter: usize,
+    /// Indices into `logs` of the selected rows (for copying).
+    pub selected: BTreeSet<usize>,
+    /// Row the last plain/ctrl click landed on (anchor for Shift+click ranges).
+    selection_anchor: Option<usize>,
     /// Receiver end of the log channel (drained each frame).
     receiver: Option<Arc<Mutex<LogReceiver>>>,
-    /// Counts by level for badge display.
-    info_count: usize,
-    warn_count: usize,
-    error_count: usize,
+    /// Verbosity table shared with the tracing layer (set by [`console_log_layer`]).
+    layer_filter: Option<SharedTargetLevels>,
+    /// Number of entries received since startup (including evicted and cleared ones).
+    received: u64,
+    /// Entry the log view should scroll to on its next frame.
+    scroll_to: Option<usize>,
+    /// Counts of buffered entries by level (indexed by `LogLevel as usize`).
+    level_counts: [usize; 5],
+    /// Which tab of the console panel is shown.
+    pub tab: ConsoleTab,
+    /// Eval tab prompt and history.
+    pub eval: EvalState,
+    /// Rows and format chosen in the Export menu, waiting for their save dialog.
+    export_request: Option<(Vec<usize>, ExportFormat)>,
 }
 
 impl Default for ConsoleState {
     fn default() -> Self {
         Self {
-            logs: Vec::new(),
+            logs: VecDeque::new(),
+            max_entries: default_max_entries(),
+            max_bytes: default_max_bytes(),
+            bytes: 0,
             auto_scroll: true,
+            show_trace: false,
+            show_debug: false,
             show_info: true,
             show_warn: true,
             show_error: true,
+            target_levels: TargetLevels::default(),
+            filter_at_source: false,
             filter_text: String::new(),
+            filter_regex: false,
+            filter: ConsoleFilter::default(),
+            filter_source: None,
+            filter_error: None,
+            new_filter_name: String::new(),
             auto_clear_on_play: false,
+            pause_on_error: false,
+            pause_on_error_exceptions: Vec::new(),
+            error_pause_entry: None,
+            error_pause_pending: false,
+            group_by_session: true,
+            show_current_session_only: false,
+            sessions: Vec::new(),
+            collapsed_sessions: HashSet::new(),
+            current_session: None,
+            session_counter: 0,
+            selected: BTreeSet::new(),
+            selection_anchor: None,
             receiver: None,
-            info_count: 0,
-            warn_count: 0,
-            error_count: 0,
+            layer_filter: None,
+            received: 0,
+            scroll_to: None,
+            level_counts: [0; 5],
+            tab: ConsoleTab::default(),
+            eval: EvalState::default(),
+            export_request: None,
         }
     }
 }
@@ -188,181 +350,110 @@ impl ConsoleState {
         }
     }
 
+    /// Apply buffer limits from settings.
+    pub fn apply_config(&mut self, config: &ConsoleConfig) {
+        self.max_entries = config.max_entries;
+        self.max_bytes = config.max_bytes;
+        self.enforce_limits();
+    }
+
     /// Push a new log entry.
     pub fn push(&mut self, level: LogLevel, target: &str, message: String) {
-        match level {
-            LogLevel::Info => self.info_count += 1,
-            LogLevel::Warn => self.warn_count += 1,
-            LogLevel::Error => self.error_count += 1,
-            _ => {}
-        }
-        self.logs.push(LogEntry {
+        self.push_entry(LogEntry {
             level,
             message,
             target: target.to_string(),
+            session: self.current_session,
         });
+        self.enforce_limits();
     }
 
     /// Drain any pending log entries from the channel.
     pub fn drain_channel(&mut self) {
-        let Some(receiver) = &self.receiver else {
+        let Some(receiver) = self.receiver.clone() else {
             return;
         };
         let Ok(rx) = receiver.lock() else { return };
-        while let Ok(entry) = rx.try_recv() {
-            match entry.level {
-                LogLevel::Info => self.info_count += 1,
-                LogLevel::Warn => self.warn_count += 1,
-                LogLevel::Error => self.error_count += 1,
-                _ => {}
-            }
-            self.logs.push(entry);
+        while let Ok(mut entry) = rx.try_recv() {
+            entry.session = self.current_session;
+            self.push_entry(entry);
         }
+        drop(rx);
+        self.enforce_limits();
     }
 
-    /// Clear all logs.
-    pub fn clear(&mut self) {
-        self.logs.clear();
-        self.info_count = 0;
-        self.warn_count = 0;
-        self.error_count = 0;
-    }
-}
-
-/// System that drains the log channel each frame.
-pub fn console_drain_system(mut state: ResMut<ConsoleState>) {
-    state.drain_channel();
-}
-
-/// System that auto-clears console when entering Play mode.
-pub fn console_auto_clear_system(mut state: ResMut<ConsoleState>) {
-    if state.auto_clear_on_play {
-        state.clear();
+    /// Number of buffered entries at `level`.
+    pub fn level_count(&self, level: LogLevel) -> usize {
+        self.level_counts[level as usize]
     }
-}
-
-/// Built-in console panel.
-#[derive(Default)]
-pub struct ConsolePanel;
 
-impl WorkbenchPanel for ConsolePanel {
-    fn id(&self) -> &str {
-        "workbench_console"
+    /// Number of entries received since startup; the newest buffered entry is number
+    /// `received_count() - 1` and sits at `logs.len() - 1`.
+    pub fn received_count(&self) -> u64 {
+        self.received
     }
 
-    fn title(&self) -> String {
-        "Console".to_string()
+    fn push_entry(&mut self, entry: LogEntry) {
+        self.received += 1;
+        self.level_counts[entry.level as usize] += 1;
+        self.bytes += entry_size(&entry);
+        self.check_pause_on_error(&entry);
+        self.logs.push_back(entry);
     }
 
-    fn ui(&mut self, _ui: &mut egui::Ui) {}
-
-    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
-        let mut console = world.remove_resource::<ConsoleState>().unwrap_or_default();
-
-        // Pre-fetch translated strings
-        let (s_clear, s_auto_clear, s_filter_hint) = {
-            let i18n = world.get_resource::<I18n>();
-            let t = |id: &str| i18n.map_or_else(|| id.to_string(), |i| i.t(id));
-            (
-                t("console-clear"),
-                t("console-auto-clear"),
-                t("console-filter-hint"),
-            )
-        };
-
-        // Toolbar row
-        ui.horizontal(|ui| {
-            // Clear button
-            if ui.button(format!("🗑 {s_clear}")).clicked() {
-                console.clear();
-            }
-
-            ui.separator();
-
-            // Level filter toggles with counts
-            let info_label = format!("ℹ {} ({})", "Info", console.info_count);
-            let warn_label = format!("⚠ {} ({})", "Warn", console.warn_count);
-            let error_label = format!("❌ {} ({})", "Error", console.error_count);
-
-            toggle_button(ui, &info_label, &mut console.show_info);
-            toggle_button(ui, &warn_label, &mut console.show_warn);
-            toggle_button(ui, &error_label, &mut console.show_error);
-
-            ui.separator();
-
-            // Auto-clear toggle
-            ui.checkbox(&mut console.auto_clear_on_play, &s_auto_clear);
-
-            ui.separator();
-
-            // Search field
-            ui.label("🔍");
-            ui.add(
-                egui::TextEdit::singleline(&mut console.filter_text)
-                    .desired_width(150.0)
-                    .hint_text(&s_filter_hint),
-            );
-        });
-
-        ui.separator();
-
-        // Log area
-        let filter_lower = console.filter_text.to_lowercase();
-        let scroll = egui::ScrollArea::vertical()
-            .auto_shrink([false; 2])
-            .stick_to_bottom(console.auto_scroll);
-
-        scroll.show(ui, |ui| {
-            render_log_entries(ui, &console, &filter_lower);
-        });
-
-        world.insert_resource(console);
+    /// Drop the oldest entries until both limits hold, keeping counts and
+    /// selection indices consistent.
+    fn enforce_limits(&mut self) {
+        let mut evicted = 0;
+        while self.logs.len() > self.max_entries.max(1)
+            || (self.bytes > self.max_bytes && self.logs.len() > 1)
+        {
+            let Some(entry) = self.logs.pop_front() else {
+                break;
+            };
+            self.level_counts[entry.level as usize] -= 1;
+            self.bytes -= entry_size(&entry);
+            evicted += 1;
+        }
+        if evicted > 0 {
+            self.selected = self
+                .selected
+                .iter()
+                .filter_map(|i| i.checked_sub(evicted))
+                .collect();
+            self.selection_anchor = self.selection_anchor.and_then(|i| i.checked_sub(evicted));
+            self.error_pause_entry = self.error_pause_entry.and_then(|i| i.checked_sub(evicted));
+            self.scroll_to = self.scroll_to.and_then(|i| i.checked_sub(evicted));
+        }
     }
 
-    fn needs_world(&self) -> bool {
-        true
+    /// Clear all logs (and the recorded session headers).
+    pub fn clear(&mut self) {
+        self.logs.clear();
+        self.bytes = 0;
+        self.level_counts = [0; 5];
+        self.sessions.retain(|s| Some(s.id) == self.current_session);
+        self.collapsed_sessions.clear();
+        self.selected.clear();
+        self.selection_anchor = None;
+        self.error_pause_entry = None;
+        self.scroll_to = None;
     }
+}
 
-    fn closable(&self) -> bool {
-        true
-    }
+/// Approximate heap + inline size of an entry.
+fn entry_size(entry: &LogEntry) -> usize {
+    std::mem::size_of::<LogEntry>() + entry.message.len() + entry.target.len()
 }
 
-/// Renders filtered log entries in the scroll area.
-fn render_log_entries(ui: &mut egui::Ui, console: &ConsoleState, filter_lower: &str) {
-    for entry in &console.logs {
-        let show = match entry.level {
-            LogLevel::Trace | LogLevel::Debug => false,
-            LogLevel::Info => console.show_info,
-            LogLevel::Warn => console.show_warn,
-            LogLevel::Error => console.show_error,
-        };
-        if !show {
-            continue;
-        }
-        if !filter_lower.is_empty()
-            && !entry.message.to_lowercase().contains(filter_lower)
-            && !entry.target.to_lowercase().contains(filter_lower)
-        {
-            continue;
-        }
-        ui.horizontal(|ui| {
-            let color = entry.level.color();
-            ui.colored_label(color, entry.level.icon());
-            ui.colored_label(egui::Color32::DARK_GRAY, format!("[{}]", entry.target));
-            ui.colored_label(color, &entry.message);
-        });
-    }
+/// System that drains the log channel each frame.
+pub fn console_drain_system(mut state: ResMut<ConsoleState>) {
+    state.drain_channel();
 }
 
-/// Helper to draw a toggle button that changes appearance based on state.
-fn toggle_button(ui: &mut egui::Ui, label: &str, value: &mut bool) {
-    let text = if *value {
-        egui::RichText::new(label)
-    } else {
-        egui::RichText::new(label).weak().strikethrough()
-    };
-    if ui.button(text).clicked() {
-        *value = !*value;
+/// System that auto-clears console when entering Play mode.
+pub fn console_auto_clear_system(mut state: ResMut<ConsoleState>) {
+    if state.auto_clear_on_play {
+        state.clear();
     }
 }
@@ -0,0 +1,343 @@
+//! # eval.rs
+//!
+//! # eval.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Implements the console's Eval tab: a tiny command language driven by Bevy reflection. It can
+//! read and write component fields by reflect path (`get` / `set`) and call functions registered
+//! in the `AppFunctionRegistry` (`call`), so quick World pokes don't need a custom panel.
+//!
+//! 实现控制台的求值（Eval）标签页：一个由 Bevy 反射驱动的微型命令语言。它可以按反射路径读写
+//! 组件字段（`get` / `set`），并调用在 `AppFunctionRegistry` 中注册的函数（`call`），
+//! 这样临时修改 World 时无需编写自定义面板。
+
+use bevy::ecs::reflect::AppFunctionRegistry;
+use bevy::prelude::*;
+use bevy::reflect::func::{ArgList, Return};
+use bevy::reflect::{GetPath, PartialReflect};
+
+const HELP: &str = "\
+get <entity>                       list reflected components
+get <entity> <Component>[.path]    print a component or field
+set <entity> <Component>.path <v>  assign a bool, number, string or vector (x,y[,z])
+call <function> [args...]          call a function registered with `register_function`
+clear                              clear the output
+<entity> is an entity id (`12v1`), its index (`12`) or its `Name`.";
+
+/// One evaluated command and its result.
+#[derive(Clone)]
+pub struct EvalLine {
+    pub input: String,
+    pub output: Result<String, String>,
+}
+
+/// State of the console's Eval tab.
+#[derive(Default)]
+pub struct EvalState {
+    /// Text in the prompt.
+    pub input: String,
+    /// Evaluated commands (oldest first).
+    pub history: Vec<EvalLine>,
+    /// Position while browsing earlier inputs with the arrow keys.
+    recall: Option<usize>,
+}
+
+impl EvalState {
+    /// Evaluate `input` against `world` and record it in the history.
+    pub fn submit(&mut self, world: &mut World, input: &str) {
+        let input = input.trim();
+        if input.is_empty() {
+            return;
+        }
+        if input == "clear" {
+            self.history.clear();
+            return;
+        }
+        let output = evaluate(world, input);
+        self.history.push(EvalLine {
+            input: input.to_string(),
+            output,
+        });
+        self.recall = None;
+    }
+
+    /// Step through earlier inputs (`back` = older).
+    fn recall_step(&mut self, back: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let last = self.history.len() - 1;
+        self.recall = match (self.recall, back) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) if i < last => Some(i + 1),
+            (Some(_), false) => None,
+        };
+        self.input = self
+            .recall
+            .map(|i| self.history[i].input.clone())
+            .unwrap_or_default();
+    }
+}
+
+/// Evaluate a single command.
+fn evaluate(world: &mut World, input: &str) -> Result<String, String> {
+    let (command, rest) = split_token(input);
+    match command.as_str() {
+        "help" => Ok(HELP.to_string()),
+        "get" => eval_get(world, rest),
+        "set" => eval_set(world, rest),
+        "call" => eval_call(world, rest),
+        other => Err(format!("unknown command `{other}` (try `help`)")),
+    }
+}
+
+/// Split off the first token. Double quotes group a token containing spaces.
+fn split_token(input: &str) -> (String, &str) {
+    let input = input.trim_start();
+    if let Some(quoted) = input.strip_prefix('"') {
+        let end = quoted.find('"').unwrap_or(quoted.len());
+        let rest = quoted.get(end + 1..).unwrap_or("");
+        return (quoted[..end].to_string(), rest.trim_start());
+    }
+    let end = input.find(char::is_whitespace).unwrap_or(input.len());
+    (input[..end].to_string(), input[end..].trim_start())
+}
+
+/// Split `Component.path.to.field` into the type name and the reflect path.
+fn split_path(spec: &str) -> (&str, &str) {
+    match spec.find(['.', '[']) {
+        Some(i) => (&spec[..i], &spec[i..]),
+        None => (spec, ""),
+    }
+}
+
+/// Resolve an entity from its id (`12v1`), index (`12`) or `Name`.
+fn resolve_entity(world: &mut World, spec: &str) -> Result<Entity, String> {
+    let index_prefix = spec
+        .chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| format!("{spec}v"));
+    let mut query = world.query::<(Entity, Option<&Name>)>();
+    let found = query.iter(world).find(|(entity, name)| {
+        let id = entity.to_string();
+        id == spec
+            || index_prefix.as_deref().is_some_and(|p| id.starts_with(p))
+            || name.is_some_and(|n| n.as_str() == spec)
+    });
+    found
+        .map(|(entity, _)| entity)
+        .ok_or_else(|| format!("no entity matches `{spec}`"))
+}
+
+/// Look up the `ReflectComponent` data for a (short or full) type path.
+pub(crate) fn reflect_component(
+    registry: &bevy::reflect::TypeRegistry,
+    type_name: &str,
+) -> Result<ReflectComponent, String> {
+    let registration = registry
+        .get_with_short_type_path(type_name)
+        .or_else(|| registry.get_with_type_path(type_name))
+        .ok_or_else(|| format!("unknown or ambiguous type `{type_name}`"))?;
+    registration
+        .data::<ReflectComponent>()
+        .cloned()
+        .ok_or_else(|| format!("`{type_name}` is not a reflected component"))
+}
+
+fn eval_get(world: &mut World, args: &str) -> Result<String, String> {
+    let (entity_spec, rest) = split_token(args);
+    let entity = resolve_entity(world, &entity_spec)?;
+    let (spec, _) = split_token(rest);
+    if spec.is_empty() {
+        return Ok(list_components(world, entity));
+    }
+    let (type_name, path) = split_path(&spec);
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let component = reflect_component(&registry.read(), type_name)?;
+    let entity_ref = world.entity(entity);
+    let value = component
+        .reflect(entity_ref)
+        .ok_or_else(|| format!("{entity} has no `{type_name}`"))?;
+    if path.is_empty() {
+        return Ok(format!("{value:#?}"));
+    }
+    let field = value
+        .as_partial_reflect()
+        .reflect_path(path)
+        .map_err(|e| e.to_string())?;
+    Ok(format!("{field:#?}"))
+}
+
+/// Short type paths of an entity's reflected components.
+fn list_components(world: &World, entity: Entity) -> String {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let entity_ref = world.entity(entity);
+    let mut names: Vec<&str> = entity_ref
+        .archetype()
+        .components()
+        .iter()
+        .filter_map(|&id| world.components().get_info(id)?.type_id())
+        .filter_map(|type_id| registry.get(type_id))
+        .filter(|registration| registration.data::<ReflectComponent>().is_some())
+        .map(|registration| registration.type_info().type_path_table().short_path())
+        .collect();
+    names.sort_unstable();
+    format!("{entity}: {}", names.join(", "))
+}
+
+fn eval_set(world: &mut World, args: &str) -> Result<String, String> {
+    let (entity_spec, rest) = split_token(args);
+    let (spec, value) = split_token(rest);
+    let (type_name, path) = split_path(&spec);
+    if path.is_empty() || value.is_empty() {
+        return Err("usage: set <entity> <Component>.path <value>".to_string());
+    }
+    let entity = resolve_entity(world, &entity_spec)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let component = reflect_component(&registry.read(), type_name)?;
+    let mut reflected = component
+        .reflect_mut(world.entity_mut(entity))
+        .ok_or_else(|| format!("{entity} has no `{type_name}`"))?;
+    let field = reflected
+        .as_partial_reflect_mut()
+        .reflect_path_mut(path)
+        .map_err(|e| e.to_string())?;
+    assign(field, value.trim_matches('"'))?;
+    Ok(format!("{field:?}"))
+}
+
+/// Parse `text` into the concrete type behind `field` and assign it.
+fn assign(field: &mut dyn PartialReflect, text: &str) -> Result<(), String> {
+    macro_rules! parse_as {
+        ($($ty:ty),*) => {$(
+            if let Some(slot) = field.try_downcast_mut::<$ty>() {
+                *slot = text.parse::<$ty>().map_err(|e| e.to_string())?;
+                return Ok(());
+            }
+        )*};
+    }
+    parse_as!(
+        f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, bool, String
+    );
+
+    let parts: Result<Vec<f32>, _> = text.split(',').map(|s| s.trim().parse::<f32>()).collect();
+    let parts = parts.map_err(|e| e.to_string())?;
+    if let Some(slot) = field.try_downcast_mut::<Vec2>() {
+        *slot = <[f32; 2]>::try_from(parts.as_slice())
+            .map_err(|_| "expected x,y".to_string())?
+            .into();
+        return Ok(());
+    }
+    if let Some(slot) = field.try_downcast_mut::<Vec3>() {
+        *slot = <[f32; 3]>::try_from(parts.as_slice())
+            .map_err(|_| "expected x,y,z".to_string())?
+            .into();
+        return Ok(());
+    }
+    Err(format!(
+        "cannot assign to `{}` from text",
+        field.reflect_type_path()
+    ))
+}
+
+fn eval_call(world: &mut World, args: &str) -> Result<String, String> {
+    let (name, mut rest) = split_token(args);
+    let functions = world
+        .get_resource::<AppFunctionRegistry>()
+        .ok_or("no AppFunctionRegistry in this app")?
+        .clone();
+    let functions = functions.read();
+    let suffix = format!("::{name}");
+    let function = functions
+        .get(&name)
+        .or_else(|| {
+            functions
+                .iter()
+                .find(|f| f.name().is_some_and(|n| n.ends_with(&suffix)))
+        })
+        .ok_or_else(|| format!("no registered function `{name}`"))?;
+
+    let mut arg_list = ArgList::new();
+    while !rest.is_empty() {
+        let quoted = rest.starts_with('"');
+        let (token, next) = split_token(rest);
+        arg_list = arg_list.with_boxed(parse_arg(&token, quoted));
+        rest = next;
+    }
+
+    match function.call(arg_list).map_err(|e| e.to_string())? {
+        Return::Owned(value) => Ok(format!("{value:?}")),
+        Return::Ref(value) => Ok(format!("{value:?}")),
+        Return::Mut(value) => Ok(format!("{value:?}")),
+    }
+}
+
+/// Guess an argument type: quoted → `String`, `true`/`false` → `bool`,
+/// decimal → `f32`, integer → `i32`, anything else → `String`.
+fn parse_arg(token: &str, quoted: bool) -> Box<dyn PartialReflect> {
+    if quoted {
+        return Box::new(token.to_string());
+    }
+    if let Ok(value) = token.parse::<bool>() {
+        return Box::new(value);
+    }
+    if let Ok(value) = token.parse::<i32>() {
+        return Box::new(value);
+    }
+    match token.parse::<f32>() {
+        Ok(value) => Box::new(value),
+        Err(_) => Box::new(token.to_string()),
+    }
+}
+
+/// Draw the Eval tab: output history above a single-line prompt.
+pub(super) fn eval_ui(ui: &mut egui::Ui, eval: &mut EvalState, world: &mut World, hint: &str) {
+    let prompt_height = ui.spacing().interact_size.y + ui.spacing().item_spacing.y * 2.0;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .max_height((ui.available_height() - prompt_height).max(0.0))
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for line in &eval.history {
+                eval_line_ui(ui, line);
+            }
+        });
+
+    ui.separator();
+    let response = ui.add(
+        egui::TextEdit::singleline(&mut eval.input)
+            .hint_text(hint)
+            .font(egui::TextStyle::Monospace)
+            .desired_width(f32::INFINITY),
+    );
+    if response.has_focus() {
+        if ui.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
+            eval.recall_step(true);
+        } else if ui.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+            eval.recall_step(false);
+        }
+    }
+    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+        let input = std::mem::take(&mut eval.input);
+        eval.submit(world, &input);
+        response.request_focus();
+    }
+}
+
+fn eval_line_ui(ui: &mut egui::Ui, line: &EvalLine) {
+    ui.label(
+        egui::RichText::new(format!("> {}", line.input))
+            .monospace()
+            .weak(),
+    );
+    let (text, color) = match &line.output {
+        Ok(text) => (text, ui.visuals().text_color()),
+        Err(text) => (text, crate::theme::palette(ui.ctx()).error),
+    };
+    ui.label(egui::RichText::new(text).monospace().color(color));
+}
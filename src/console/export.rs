@@ -0,0 +1,225 @@
+//! # export.rs
+//!
+//! # export.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Exports console logs to a file and copies selected rows to the clipboard. It owns the row
+//! selection logic (click, Ctrl+click, Shift+click), formats entries as plain text or JSON, and
+//! requests the save dialog (through `FileDialogs`, native and browser alike) used by the
+//! console's Export menu.
+//!
+//! 负责把控制台日志导出到文件，并把选中的行复制到剪贴板。它管理行选择逻辑
+//! （单击、Ctrl+单击、Shift+单击），把日志条目格式化为纯文本或 JSON，并（通过 `FileDialogs`，
+//! 本地与浏览器均可）请求导出菜单使用的保存对话框。
+
+use super::{ConsoleState, LogEntry};
+use crate::bench_ui::{Icon, icon};
+#[cfg(feature = "file-dialogs")]
+use crate::file_dialog::{FileDialogRequest, FileDialogs};
+use bevy::prelude::*;
+
+/// File dialog ID of console exports.
+#[cfg(feature = "file-dialogs")]
+pub(crate) const EXPORT_DIALOG: &str = "workbench_console_export";
+
+/// File format used when exporting console logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One `[LEVEL] target: message` line per entry, with session separators.
+    Text,
+    /// A JSON array of entries.
+    Json,
+}
+
+impl ExportFormat {
+    #[cfg(feature = "file-dialogs")]
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "log",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    #[cfg(feature = "file-dialogs")]
+    fn filter_name(self) -> &'static str {
+        match self {
+            ExportFormat::Text => "Text",
+            ExportFormat::Json => "JSON",
+        }
+    }
+}
+
+impl ConsoleState {
+    /// Update the row selection after a click, honoring Ctrl (toggle) and Shift (range).
+    pub fn select_entry(&mut self, index: usize, modifiers: egui::Modifiers) {
+        if modifiers.shift
+            && let Some(anchor) = self.selection_anchor
+        {
+            let (lo, hi) = (anchor.min(index), anchor.max(index));
+            if !modifiers.command {
+                self.selected.clear();
+            }
+            self.selected.extend(lo..=hi);
+            return;
+        }
+        if modifiers.command {
+            if !self.selected.remove(&index) {
+                self.selected.insert(index);
+            }
+        } else {
+            self.selected.clear();
+            self.selected.insert(index);
+        }
+        self.selection_anchor = Some(index);
+    }
+
+    /// Selected rows formatted as plain text (for the clipboard).
+    pub fn selection_text(&self) -> String {
+        self.format_entries(self.selected.iter().copied(), ExportFormat::Text)
+    }
+
+    /// Format the entries at `indices` in the given export format.
+    pub fn format_entries(
+        &self,
+        indices: impl IntoIterator<Item = usize>,
+        format: ExportFormat,
+    ) -> String {
+        let entries = indices.into_iter().filter_map(|i| self.logs.get(i));
+        match format {
+            ExportFormat::Text => self.format_text(entries),
+            ExportFormat::Json => {
+                let entries: Vec<&LogEntry> = entries.collect();
+                serde_json::to_string_pretty(&entries).expect("serialize log entries")
+            }
+        }
+    }
+
+    fn format_text<'a>(&self, entries: impl Iterator<Item = &'a LogEntry>) -> String {
+        let mut out = String::new();
+        let mut prev_session = None;
+        for entry in entries {
+            if entry.session != prev_session
+                && let Some(session) = entry.session.and_then(|id| self.session(id))
+            {
+                // Exported logs keep English headers whatever the interface language.
+                out.push_str(&format!("--- {} ---\n", session.header(None)));
+            }
+            prev_session = entry.session;
+            out.push_str(&format!(
+                "[{}] {}: {}\n",
+                entry.level.label(),
+                entry.target,
+                entry.message
+            ));
+        }
+        out
+    }
+}
+
+/// Copies the selection when Ctrl+C is pressed and no text field has focus.
+pub(super) fn handle_copy_shortcut(ui: &egui::Ui, console: &ConsoleState) {
+    if console.selected.is_empty() || ui.ctx().wants_keyboard_input() {
+        return;
+    }
+    let copy = ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)));
+    if copy {
+        ui.ctx().copy_text(console.selection_text());
+    }
+}
+
+/// Localized strings of the Export menu and its save dialog.
+#[cfg_attr(not(feature = "file-dialogs"), allow(dead_code))]
+pub(super) struct ExportStrings {
+    label: String,
+    filtered_text: String,
+    filtered_json: String,
+    all_text: String,
+    all_json: String,
+    dialog_title: String,
+}
+
+impl ExportStrings {
+    pub(super) fn new(t: impl Fn(&str) -> String) -> Self {
+        Self {
+            label: t("console-export"),
+            filtered_text: t("console-export-filtered-log"),
+            filtered_json: t("console-export-filtered-json"),
+            all_text: t("console-export-all-log"),
+            all_json: t("console-export-all-json"),
+            dialog_title: t("console-export-title"),
+        }
+    }
+}
+
+/// Export dropdown: filtered or full log, as text or JSON.
+#[cfg(feature = "file-dialogs")]
+pub(super) fn export_menu_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    visible: &[usize],
+    strings: &ExportStrings,
+) {
+    let options = [
+        (&strings.filtered_text, false, ExportFormat::Text),
+        (&strings.filtered_json, false, ExportFormat::Json),
+        (&strings.all_text, true, ExportFormat::Text),
+        (&strings.all_json, true, ExportFormat::Json),
+    ];
+    ui.menu_button(format!("{} {}", icon(Icon::Save), strings.label), |ui| {
+        for (text, all, format) in options {
+            if ui.button(text.as_str()).clicked() {
+                let indices: Vec<usize> = if all {
+                    (0..console.logs.len()).collect()
+                } else {
+                    visible.to_vec()
+                };
+                console.export_request = Some((indices, format));
+                ui.close();
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "file-dialogs"))]
+pub(super) fn export_menu_ui(
+    _ui: &mut egui::Ui,
+    _console: &mut ConsoleState,
+    _visible: &[usize],
+    _strings: &ExportStrings,
+) {
+}
+
+/// Shows the save dialog for the export chosen in the Export menu, if any. The outcome is
+/// logged when the [`EXPORT_DIALOG`] response arrives.
+#[cfg(feature = "file-dialogs")]
+pub(super) fn request_export(
+    console: &mut ConsoleState,
+    world: &mut World,
+    strings: &ExportStrings,
+) {
+    let Some((indices, format)) = console.export_request.take() else {
+        return;
+    };
+    let Some(mut file_dialogs) = world.get_resource_mut::<FileDialogs>() else {
+        return;
+    };
+    let timestamp = super::session::unix_secs();
+    let contents = console.format_entries(indices, format).into_bytes();
+    file_dialogs.request(
+        FileDialogRequest::save(EXPORT_DIALOG, contents)
+            .with_title(&strings.dialog_title)
+            .with_filter(format.filter_name(), &[format.extension()])
+            .with_file_name(format!("console-{timestamp}.{}", format.extension())),
+    );
+}
+
+#[cfg(not(feature = "file-dialogs"))]
+pub(super) fn request_export(
+    _console: &mut ConsoleState,
+    _world: &mut World,
+    _strings: &ExportStrings,
+) {
+}
@@ -0,0 +1,285 @@
+//! # filter.rs
+//!
+//! # filter.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Parses and applies the console filter query. A query may combine `target:` and `level:`
+//! prefixes with free text that is matched as a case-insensitive substring or, in regex mode, as
+//! a regular expression. Named filters can be saved into the workbench settings and re-applied
+//! from a dropdown next to the search box.
+//!
+//! 负责解析并应用控制台的筛选查询。查询可以组合 `target:` 与 `level:` 前缀以及自由文本，
+//! 自由文本按不区分大小写的子串匹配，或在正则模式下按正则表达式匹配。
+//! 命名筛选器可以保存到 workbench 设置中，并通过搜索框旁的下拉菜单重新应用。
+
+use super::{ConsoleState, LogEntry, LogLevel};
+use crate::bench_ui::{Icon, icon};
+
+/// A named console filter persisted in settings.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SavedFilter {
+    /// Display name in the saved-filters dropdown.
+    pub name: String,
+    /// Filter query text (may contain `target:` / `level:` prefixes).
+    pub query: String,
+    /// Whether the free-text part is a regular expression.
+    #[serde(default)]
+    pub regex: bool,
+}
+
+/// How the free-text part of a query is matched.
+#[derive(Default)]
+enum TextMatcher {
+    #[default]
+    Any,
+    /// Lowercased substring.
+    Substring(String),
+    Regex(regex::Regex),
+}
+
+/// A compiled console filter query.
+#[derive(Default)]
+pub struct ConsoleFilter {
+    /// Lowercased substrings; an entry's target must contain one of them.
+    targets: Vec<String>,
+    /// Allowed levels (empty = any).
+    levels: Vec<LogLevel>,
+    text: TextMatcher,
+}
+
+impl ConsoleFilter {
+    /// Parse a query such as `target:bevy_render level:warn shader`.
+    /// Returns an error message when regex mode is on and the pattern is invalid.
+    pub fn parse(query: &str, regex: bool) -> Result<Self, String> {
+        let mut filter = Self::default();
+        let mut words = Vec::new();
+        for word in query.split_whitespace() {
+            if let Some(target) = word.strip_prefix("target:") {
+                filter.targets.push(target.to_lowercase());
+            } else if let Some(level) = word.strip_prefix("level:") {
+                filter
+                    .levels
+                    .extend(level.split(',').filter_map(parse_level));
+            } else {
+                words.push(word);
+            }
+        }
+        let text = words.join(" ");
+        filter.text = if text.is_empty() {
+            TextMatcher::Any
+        } else if regex {
+            regex::RegexBuilder::new(&text)
+                .case_insensitive(true)
+                .build()
+                .map(TextMatcher::Regex)
+                .map_err(|e| e.to_string())?
+        } else {
+            TextMatcher::Substring(text.to_lowercase())
+        };
+        Ok(filter)
+    }
+
+    /// Whether an entry matches the query (level toggles are checked separately).
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if !self.levels.is_empty() && !self.levels.contains(&entry.level) {
+            return false;
+        }
+        if !self.targets.is_empty() {
+            let target = entry.target.to_lowercase();
+            if !self.targets.iter().any(|t| target.contains(t.as_str())) {
+                return false;
+            }
+        }
+        match &self.text {
+            TextMatcher::Any => true,
+            TextMatcher::Substring(s) => {
+                entry.message.to_lowercase().contains(s.as_str())
+                    || entry.target.to_lowercase().contains(s.as_str())
+            }
+            TextMatcher::Regex(re) => re.is_match(&entry.message) || re.is_match(&entry.target),
+        }
+    }
+}
+
+fn parse_level(s: &str) -> Option<LogLevel> {
+    match s.to_lowercase().as_str() {
+        "trace" => Some(LogLevel::Trace),
+        "debug" => Some(LogLevel::Debug),
+        "info" => Some(LogLevel::Info),
+        "warn" | "warning" => Some(LogLevel::Warn),
+        "error" => Some(LogLevel::Error),
+        _ => None,
+    }
+}
+
+impl ConsoleState {
+    /// Recompile the filter if the query text or regex mode changed.
+    pub(super) fn update_filter(&mut self) {
+        let source = (self.filter_text.clone(), self.filter_regex);
+        if self.filter_source.as_ref() == Some(&source) {
+            return;
+        }
+        match ConsoleFilter::parse(&source.0, source.1) {
+            Ok(filter) => {
+                self.filter = filter;
+                self.filter_error = None;
+            }
+            Err(e) => self.filter_error = Some(e),
+        }
+        self.filter_source = Some(source);
+    }
+
+    /// Whether an entry passes the level toggles, the verbosity table, and the filter query.
+    pub(super) fn entry_matches(&self, entry: &LogEntry) -> bool {
+        let show = match entry.level {
+            LogLevel::Trace => self.show_trace,
+            LogLevel::Debug => self.show_debug,
+            LogLevel::Info => self.show_info,
+            LogLevel::Warn => self.show_warn,
+            LogLevel::Error => self.show_error,
+        };
+        show && self.target_levels.allows(&entry.target, entry.level) && self.filter.matches(entry)
+    }
+}
+
+/// Search field, regex toggle, and saved-filters dropdown.
+/// Returns the updated saved-filter list when the user saved or deleted one.
+pub(super) fn filter_bar_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    saved: &[SavedFilter],
+    hint: &str,
+) -> Option<Vec<SavedFilter>> {
+    ui.label(icon(Icon::Search));
+    let mut edit = egui::TextEdit::singleline(&mut console.filter_text)
+        .desired_width(150.0)
+        .hint_text(hint);
+    if console.filter_error.is_some() {
+        edit = edit.text_color(crate::theme::palette(ui.ctx()).error);
+    }
+    let response = ui.add(edit);
+    if let Some(err) = &console.filter_error {
+        response.on_hover_text(err);
+    }
+    ui.toggle_value(&mut console.filter_regex, ".*")
+        .on_hover_text("Regex mode");
+
+    let mut updated = None;
+    ui.menu_button(icon(Icon::Star), |ui| {
+        updated = saved_filters_menu_ui(ui, console, saved);
+    });
+    updated
+}
+
+/// Contents of the saved-filters dropdown.
+fn saved_filters_menu_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    saved: &[SavedFilter],
+) -> Option<Vec<SavedFilter>> {
+    let mut updated = None;
+    for (i, filter) in saved.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui
+                .button(&filter.name)
+                .on_hover_text(&filter.query)
+                .clicked()
+            {
+                console.filter_text = filter.query.clone();
+                console.filter_regex = filter.regex;
+                ui.close();
+            }
+            if ui.small_button("×").clicked() {
+                let mut list = saved.to_vec();
+                list.remove(i);
+                updated = Some(list);
+            }
+        });
+    }
+    if !saved.is_empty() {
+        ui.separator();
+    }
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut console.new_filter_name)
+                .desired_width(100.0)
+                .hint_text("Name"),
+        );
+        let can_save = !console.new_filter_name.is_empty() && !console.filter_text.is_empty();
+        if ui
+            .add_enabled(can_save, egui::Button::new("Save current"))
+            .clicked()
+        {
+            let mut list = saved.to_vec();
+            list.retain(|f| f.name != console.new_filter_name);
+            list.push(SavedFilter {
+                name: std::mem::take(&mut console.new_filter_name),
+                query: console.filter_text.clone(),
+                regex: console.filter_regex,
+            });
+            updated = Some(list);
+        }
+    });
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: LogLevel, target: &str, message: &str) -> LogEntry {
+        LogEntry {
+            level,
+            message: message.to_string(),
+            target: target.to_string(),
+            session: None,
+        }
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        let filter = ConsoleFilter::parse("", false).unwrap();
+        assert!(filter.matches(&entry(LogLevel::Trace, "a", "b")));
+        assert!(filter.matches(&entry(LogLevel::Error, "", "")));
+    }
+
+    #[test]
+    fn prefixes_and_text_combine() {
+        let filter =
+            ConsoleFilter::parse("target:Bevy_Render level:warn,error Shader", false).unwrap();
+        assert!(filter.matches(&entry(
+            LogLevel::Warn,
+            "bevy_render::pipeline",
+            "bad shader"
+        )));
+        assert!(!filter.matches(&entry(LogLevel::Info, "bevy_render", "bad shader")));
+        assert!(!filter.matches(&entry(LogLevel::Warn, "bevy_audio", "bad shader")));
+        assert!(!filter.matches(&entry(LogLevel::Error, "bevy_render", "texture")));
+    }
+
+    #[test]
+    fn substring_also_matches_target() {
+        let filter = ConsoleFilter::parse("physics", false).unwrap();
+        assert!(filter.matches(&entry(LogLevel::Info, "game::physics", "step")));
+        assert!(!filter.matches(&entry(LogLevel::Info, "game::ai", "step")));
+    }
+
+    #[test]
+    fn unknown_levels_are_ignored() {
+        let filter = ConsoleFilter::parse("level:loud,warning", false).unwrap();
+        assert!(filter.matches(&entry(LogLevel::Warn, "a", "b")));
+        assert!(!filter.matches(&entry(LogLevel::Error, "a", "b")));
+    }
+
+    #[test]
+    fn regex_mode() {
+        let filter = ConsoleFilter::parse(r"^frame \d+$", true).unwrap();
+        assert!(filter.matches(&entry(LogLevel::Info, "a", "Frame 12")));
+        assert!(!filter.matches(&entry(LogLevel::Info, "a", "frame x")));
+        assert!(ConsoleFilter::parse("(", true).is_err());
+        assert!(ConsoleFilter::parse("(", false).is_ok());
+    }
+}
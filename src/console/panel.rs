@@ -0,0 +1,229 @@
+//! # panel.rs
+//!
+//! # panel.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Implements the built-in console dock panel. It switches between the Log and Eval tabs,
+//! renders the log toolbar (clear, level toggles, session and pause options, export, search), and persists
+//! saved filters back into the workbench settings.
+//!
+//! 实现内置的控制台停靠面板。它在日志（Log）与求值（Eval）标签页之间切换，
+//! 渲染日志工具栏（清除、级别开关、会话与暂停选项、导出、搜索），并把保存的筛选器写回 workbench 设置。
+
+use super::{ConsoleState, LogLevel, SavedFilter, eval, export, filter, pause, verbosity, view};
+use crate::bench_ui::{Icon, icon};
+use crate::dock::{PanelSlot, WorkbenchPanel};
+use crate::i18n::I18n;
+use bevy::prelude::*;
+
+/// Which tab of the console panel is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConsoleTab {
+    /// Tracing log view.
+    #[default]
+    Log,
+    /// Reflection-driven expression prompt.
+    Eval,
+}
+
+/// Built-in console panel.
+#[derive(Default)]
+pub struct ConsolePanel;
+
+/// Localized strings used by the console panel, fetched once per frame.
+struct ConsoleStrings {
+    clear: String,
+    auto_clear: String,
+    pause: pause::PauseStrings,
+    verbosity: verbosity::VerbosityStrings,
+    filter_hint: String,
+    group_sessions: String,
+    current_session_only: String,
+    export: export::ExportStrings,
+    copy: String,
+    log_tab: String,
+    eval_tab: String,
+    eval_hint: String,
+}
+
+impl ConsoleStrings {
+    fn new(i18n: Option<&I18n>) -> Self {
+        let t = |id: &str| i18n.map_or(id, |i| i.t(id)).to_owned();
+        Self {
+            clear: t("console-clear"),
+            auto_clear: t("console-auto-clear"),
+            pause: pause::PauseStrings::new(t),
+            verbosity: verbosity::VerbosityStrings::new(t),
+            filter_hint: t("console-filter-hint"),
+            group_sessions: t("console-group-sessions"),
+            current_session_only: t("console-current-session-only"),
+            export: export::ExportStrings::new(t),
+            copy: t("console-copy"),
+            log_tab: t("console-tab-log"),
+            eval_tab: t("console-tab-eval"),
+            eval_hint: t("console-eval-hint"),
+        }
+    }
+}
+
+impl WorkbenchPanel for ConsolePanel {
+    fn id(&self) -> &str {
+        "workbench_console"
+    }
+
+    fn title(&self) -> String {
+        "Console".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let mut console = world.remove_resource::<ConsoleState>().unwrap_or_default();
+        let strings = ConsoleStrings::new(world.get_resource::<I18n>());
+
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut console.tab, ConsoleTab::Log, &strings.log_tab);
+            ui.selectable_value(&mut console.tab, ConsoleTab::Eval, &strings.eval_tab);
+        });
+        ui.separator();
+
+        if console.tab == ConsoleTab::Eval {
+            eval::eval_ui(ui, &mut console.eval, world, &strings.eval_hint);
+            world.insert_resource(console);
+            return;
+        }
+
+        let saved_filters = world
+            .get_resource::<crate::config::WorkbenchSettings>()
+            .map(|s| s.console.saved_filters.clone())
+            .unwrap_or_default();
+
+        console.update_filter();
+        let visible = console.visible_indices();
+
+        // Toolbar row
+        let updated_filters = ui
+            .horizontal(|ui| {
+                console_toolbar_ui(ui, &mut console, &strings, &visible, &saved_filters)
+            })
+            .inner;
+        if let Some(list) = updated_filters {
+            save_filters(world, list);
+        }
+        export::request_export(&mut console, world, &strings.export);
+
+        ui.separator();
+
+        // Log area
+        view::log_area_ui(ui, &mut console, &visible, world.get_resource::<I18n>());
+        export::handle_copy_shortcut(ui, &console);
+
+        world.insert_resource(console);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Bottom
+    }
+
+    fn closable(&self) -> bool {
+        true
+    }
+}
+
+/// Console toolbar: clear, level toggles, session and pause options, export and search.
+fn console_toolbar_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    strings: &ConsoleStrings,
+    visible: &[usize],
+    saved_filters: &[SavedFilter],
+) -> Option<Vec<SavedFilter>> {
+    // Clear button
+    if ui
+        .button(format!("{} {}", icon(Icon::Trash), strings.clear))
+        .clicked()
+    {
+        console.clear();
+    }
+
+    ui.separator();
+
+    // Level filter toggles with counts
+    let count = |level| console.level_count(level);
+    let trace_label = format!("{} ({})", "Trace", count(LogLevel::Trace));
+    let label = |level: LogLevel, name| format!("{} {name} ({})", level.icon(), count(level));
+    let debug_label = label(LogLevel::Debug, "Debug");
+    let info_label = label(LogLevel::Info, "Info");
+    let warn_label = label(LogLevel::Warn, "Warn");
+    let error_label = label(LogLevel::Error, "Error");
+
+    toggle_button(ui, &trace_label, &mut console.show_trace);
+    toggle_button(ui, &debug_label, &mut console.show_debug);
+    toggle_button(ui, &info_label, &mut console.show_info);
+    toggle_button(ui, &warn_label, &mut console.show_warn);
+    toggle_button(ui, &error_label, &mut console.show_error);
+    verbosity::verbosity_menu_ui(ui, console, &strings.verbosity);
+
+    ui.separator();
+
+    // Auto-clear and session toggles
+    ui.checkbox(&mut console.auto_clear_on_play, &strings.auto_clear);
+    pause::pause_on_error_ui(ui, console, &strings.pause);
+    ui.checkbox(&mut console.group_by_session, &strings.group_sessions);
+    ui.checkbox(
+        &mut console.show_current_session_only,
+        &strings.current_session_only,
+    );
+
+    ui.separator();
+
+    // Export / copy
+    export::export_menu_ui(ui, console, visible, &strings.export);
+    if ui
+        .add_enabled(
+            !console.selected.is_empty(),
+            egui::Button::new(format!("{} {}", icon(Icon::Copy), strings.copy)),
+        )
+        .clicked()
+    {
+        ui.ctx().copy_text(console.selection_text());
+    }
+
+    ui.separator();
+
+    // Search field and saved filters
+    filter::filter_bar_ui(ui, console, saved_filters, &strings.filter_hint)
+}
+
+/// Persist the saved-filter list into the workbench settings file.
+fn save_filters(world: &mut World, filters: Vec<SavedFilter>) {
+    let Some(path) = world
+        .get_resource::<crate::config::ConfigPath>()
+        .map(|p| p.0.clone())
+    else {
+        return;
+    };
+    if let Some(mut settings) = world.get_resource_mut::<crate::config::WorkbenchSettings>() {
+        settings.console.saved_filters = filters;
+        settings.save(&path);
+    }
+}
+
+/// Helper to draw a toggle button that changes appearance based on state.
+fn toggle_button(ui: &mut egui::Ui, label: &str, value: &mut bool) {
+    let text = if *value {
+        egui::RichText::new(label)
+    } else {
+        egui::RichText::new(label).weak().strikethrough()
+    };
+    if ui.button(text).clicked() {
+        *value = !*value;
+    }
+}
@@ -0,0 +1,121 @@
+//! # pause.rs
+//!
+//! # pause.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Implements the console's "Pause on Error" option. While a Play session is running, an
+//! `Error` entry whose target does not start with one of the configured exception prefixes
+//! sends a `ModeTransitionRequest` to `EditorMode::Pause` and marks that entry so the log view
+//! can highlight it, mirroring Unity's error pause.
+//!
+//! 实现控制台的 "Pause on Error"（出错时暂停）选项。在 Play 会话运行期间，若收到目标不以任何
+//! 例外前缀开头的 `Error` 条目，会发送切换到 `EditorMode::Pause` 的 `ModeTransitionRequest`，并标记该条目以便日志视图
+//! 高亮显示，效果类似 Unity 的错误暂停。
+
+use super::{ConsoleState, LogEntry, LogLevel};
+use crate::bench_ui::{Icon, icon};
+use crate::mode::{EditorMode, ModeTransitionRequest};
+use bevy::prelude::*;
+
+impl ConsoleState {
+    /// Index into `logs` of the entry that last triggered Pause on Error.
+    pub fn error_pause_entry(&self) -> Option<usize> {
+        self.error_pause_entry
+    }
+
+    /// Whether an error from `target` is exempt from Pause on Error.
+    pub fn is_pause_exception(&self, target: &str) -> bool {
+        self.pause_on_error_exceptions
+            .iter()
+            .any(|prefix| !prefix.is_empty() && target.starts_with(prefix.as_str()))
+    }
+
+    /// Called for each new entry just before it is appended to `logs`: requests a pause if it
+    /// qualifies.
+    pub(super) fn check_pause_on_error(&mut self, entry: &LogEntry) {
+        if !self.pause_on_error
+            || self.error_pause_pending
+            || entry.level != LogLevel::Error
+            || self.current_session.is_none()
+            || self.is_pause_exception(&entry.target)
+        {
+            return;
+        }
+        self.error_pause_entry = Some(self.logs.len());
+        self.error_pause_pending = true;
+    }
+}
+
+/// System that requests Play → Pause after a qualifying error was logged; the request goes
+/// through the mode guards like a toolbar click.
+pub fn console_pause_on_error_system(
+    mut state: ResMut<ConsoleState>,
+    mode: Res<State<EditorMode>>,
+    mut requests: MessageWriter<ModeTransitionRequest>,
+) {
+    if !state.error_pause_pending {
+        return;
+    }
+    state.error_pause_pending = false;
+    if *mode.get() == EditorMode::Play {
+        requests.write(ModeTransitionRequest::new(EditorMode::Pause));
+    }
+}
+
+/// Localized strings of the Pause on Error controls.
+pub(super) struct PauseStrings {
+    label: String,
+    hint: String,
+    exceptions: String,
+    exceptions_prefix: String,
+    add_exception: String,
+}
+
+impl PauseStrings {
+    pub(super) fn new(t: impl Fn(&str) -> String) -> Self {
+        Self {
+            label: t("console-pause-on-error"),
+            hint: t("console-pause-on-error-hint"),
+            exceptions: t("console-pause-exceptions"),
+            exceptions_prefix: t("console-pause-exceptions-prefix"),
+            add_exception: t("console-pause-add-exception"),
+        }
+    }
+}
+
+/// "Pause on Error" checkbox with a dropdown for per-target exceptions.
+pub(super) fn pause_on_error_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    strings: &PauseStrings,
+) {
+    ui.checkbox(&mut console.pause_on_error, &strings.label)
+        .on_hover_text(&strings.hint);
+    ui.menu_button(icon(Icon::CaretDown), |ui| {
+        ui.label(&strings.exceptions_prefix);
+        let mut remove = None;
+        for (i, prefix) in console.pause_on_error_exceptions.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(prefix)
+                        .hint_text("my_game::audio")
+                        .desired_width(160.0),
+                );
+                if ui.small_button("×").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            console.pause_on_error_exceptions.remove(i);
+        }
+        if ui.button(&strings.add_exception).clicked() {
+            console.pause_on_error_exceptions.push(String::new());
+        }
+    })
+    .response
+    .on_hover_text(&strings.exceptions);
+}
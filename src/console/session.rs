@@ -0,0 +1,111 @@
+//! # session.rs
+//!
+//! # session.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Tracks Play session boundaries for the console from the `PlaySessionStarted` /
+//! `PlaySessionEnded` events. Each fresh Play opens a new numbered session, log entries are tagged with the session they arrived in, and the console panel uses
+//! these records to render collapsible "Play session #N" headers.
+//!
+//! 根据 `PlaySessionStarted` / `PlaySessionEnded` 事件为控制台记录 Play 会话的边界。每次全新进入 Play 都会开启一个带编号的新会话，
+//! 日志条目会被标记为所属的会话，控制台面板据此渲染可折叠的 "Play session #N" 标题。
+
+use super::ConsoleState;
+use crate::i18n::I18n;
+use crate::mode::{PlaySessionEnded, PlaySessionStarted};
+use bevy::prelude::*;
+
+/// A recorded Play session boundary.
+#[derive(Debug, Clone)]
+pub struct PlaySession {
+    /// Session number (1-based, increasing for the lifetime of the app).
+    pub id: usize,
+    /// Wall-clock start time formatted as `HH:MM:SS` (UTC).
+    pub started_at: String,
+}
+
+impl PlaySession {
+    /// Header text shown above the session's log entries, in English without `i18n`.
+    pub fn header(&self, i18n: Option<&I18n>) -> String {
+        let id = self.id.to_string();
+        match i18n {
+            Some(i18n) => i18n.t_with(
+                "console-session-header",
+                &[("id", &id), ("time", &self.started_at)],
+            ),
+            None => format!("▶ Play session #{id} ({})", self.started_at),
+        }
+    }
+}
+
+impl ConsoleState {
+    /// Start a new Play session; subsequent entries are tagged with it.
+    pub fn begin_session(&mut self) {
+        self.session_counter += 1;
+        let id = self.session_counter;
+        self.sessions.push(PlaySession {
+            id,
+            started_at: wall_clock_hms(),
+        });
+        self.current_session = Some(id);
+    }
+
+    /// End the running Play session (entries logged afterwards are ungrouped).
+    pub fn end_session(&mut self) {
+        self.current_session = None;
+    }
+
+    /// The Play session currently running, if any.
+    pub fn current_session(&self) -> Option<usize> {
+        self.current_session
+    }
+
+    /// Look up a recorded session by ID.
+    pub fn session(&self, id: usize) -> Option<&PlaySession> {
+        self.sessions.iter().find(|s| s.id == id)
+    }
+}
+
+/// Observer that opens a new console session on fresh Play (not Resume).
+pub fn console_session_start_observer(
+    started: On<PlaySessionStarted>,
+    mut state: ResMut<ConsoleState>,
+) {
+    if started.fresh {
+        state.begin_session();
+    }
+}
+
+/// Observer that closes the console session when returning to Edit.
+pub fn console_session_end_observer(_ended: On<PlaySessionEnded>, mut state: ResMut<ConsoleState>) {
+    state.end_session();
+}
+
+/// Seconds since the Unix epoch (`SystemTime` panics on wasm, so use `Date` there).
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn unix_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) fn unix_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/// Current UTC wall-clock time as `HH:MM:SS`.
+fn wall_clock_hms() -> String {
+    let secs = unix_secs();
+    let day_secs = secs % 86_400;
+    format!(
+        "{:02}:{:02}:{:02}",
+        day_secs / 3600,
+        (day_secs / 60) % 60,
+        day_secs % 60
+    )
+}
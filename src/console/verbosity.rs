@@ -0,0 +1,208 @@
+//! # verbosity.rs
+//!
+//! # verbosity.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Implements the console's per-target verbosity table, similar to `RUST_LOG`. Each row sets
+//! the minimum level shown for targets starting with a prefix (the longest prefix wins). The table
+//! filters entries at display time and can optionally be shared with the tracing layer so that
+//! suppressed events are dropped before they reach the console channel.
+//!
+//! 实现控制台按 target 划分的日志级别表，类似 `RUST_LOG`。每一行为以某个前缀开头的
+//! target 设置最低显示级别（最长前缀优先）。该表在显示时筛选日志条目，也可以选择共享给
+//! tracing 层，使被屏蔽的事件在进入控制台通道之前就被丢弃。
+
+use super::{ConsoleState, LogLevel};
+use crate::bench_ui::{Icon, icon};
+use std::sync::{Arc, RwLock};
+
+/// Minimum level for targets starting with `target` (empty = all targets).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetLevel {
+    pub target: String,
+    pub level: LogLevel,
+}
+
+/// Per-target verbosity table.
+#[derive(Debug, Clone, Default)]
+pub struct TargetLevels {
+    pub rules: Vec<TargetLevel>,
+}
+
+impl TargetLevels {
+    /// The minimum level configured for a target (longest matching prefix wins).
+    pub fn level_for(&self, target: &str) -> Option<LogLevel> {
+        self.rules
+            .iter()
+            .filter(|r| target.starts_with(r.target.as_str()))
+            .max_by_key(|r| r.target.len())
+            .map(|r| r.level)
+    }
+
+    /// Whether an event at `level` from `target` passes the table.
+    pub fn allows(&self, target: &str, level: LogLevel) -> bool {
+        self.level_for(target).is_none_or(|min| level >= min)
+    }
+}
+
+/// Verbosity table shared between [`ConsoleState`] and the tracing layer.
+pub type SharedTargetLevels = Arc<RwLock<TargetLevels>>;
+
+impl ConsoleState {
+    /// Push the display-time table to the tracing layer (or clear it there when
+    /// `filter_at_source` is off).
+    pub fn sync_layer_filter(&self) {
+        let Some(shared) = &self.layer_filter else {
+            return;
+        };
+        if let Ok(mut levels) = shared.write() {
+            *levels = if self.filter_at_source {
+                self.target_levels.clone()
+            } else {
+                TargetLevels::default()
+            };
+        }
+    }
+}
+
+/// Localized strings of the verbosity dropdown.
+pub(super) struct VerbosityStrings {
+    label: String,
+    apply_to_layer: String,
+    apply_to_layer_hint: String,
+    add_target: String,
+    target_hint: String,
+}
+
+impl VerbosityStrings {
+    pub(super) fn new(t: impl Fn(&str) -> String) -> Self {
+        Self {
+            label: t("console-levels"),
+            apply_to_layer: t("console-levels-apply-to-layer"),
+            apply_to_layer_hint: t("console-levels-apply-to-layer-hint"),
+            add_target: t("console-levels-add-target"),
+            target_hint: t("console-levels-target-hint"),
+        }
+    }
+}
+
+/// Dropdown with the verbosity table editor.
+pub(super) fn verbosity_menu_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    strings: &VerbosityStrings,
+) {
+    ui.menu_button(format!("{} {}", icon(Icon::Levels), strings.label), |ui| {
+        let mut changed = verbosity_table_ui(ui, console, strings);
+        ui.separator();
+        changed |= ui
+            .checkbox(&mut console.filter_at_source, &strings.apply_to_layer)
+            .on_hover_text(&strings.apply_to_layer_hint)
+            .changed();
+        if changed {
+            console.sync_layer_filter();
+        }
+    });
+}
+
+/// Rows of (target prefix, minimum level). Returns `true` if anything changed.
+fn verbosity_table_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    strings: &VerbosityStrings,
+) -> bool {
+    let mut changed = false;
+    let mut remove = None;
+    egui::Grid::new("console_verbosity_grid")
+        .num_columns(3)
+        .show(ui, |ui| {
+            for (i, rule) in console.target_levels.rules.iter_mut().enumerate() {
+                changed |= verbosity_row_ui(ui, i, rule, &strings.target_hint);
+                if ui.small_button("×").clicked() {
+                    remove = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(i) = remove {
+        console.target_levels.rules.remove(i);
+        changed = true;
+    }
+    if ui.button(&strings.add_target).clicked() {
+        console.target_levels.rules.push(TargetLevel {
+            target: String::new(),
+            level: LogLevel::Info,
+        });
+        changed = true;
+    }
+    changed
+}
+
+/// A single row: target prefix field and level selector.
+fn verbosity_row_ui(
+    ui: &mut egui::Ui,
+    index: usize,
+    rule: &mut TargetLevel,
+    target_hint: &str,
+) -> bool {
+    let mut changed = ui
+        .add(
+            egui::TextEdit::singleline(&mut rule.target)
+                .desired_width(160.0)
+                .hint_text(target_hint),
+        )
+        .changed();
+    egui::ComboBox::from_id_salt(("console_verbosity_level", index))
+        .selected_text(rule.level.label())
+        .show_ui(ui, |ui| {
+            for level in LogLevel::ALL {
+                changed |= ui
+                    .selectable_value(&mut rule.level, level, level.label())
+                    .changed();
+            }
+        });
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(rules: &[(&str, LogLevel)]) -> TargetLevels {
+        TargetLevels {
+            rules: rules
+                .iter()
+                .map(|&(target, level)| TargetLevel {
+                    target: target.to_string(),
+                    level,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let table = levels(&[
+            ("", LogLevel::Info),
+            ("bevy", LogLevel::Warn),
+            ("bevy_render", LogLevel::Error),
+        ]);
+        assert_eq!(table.level_for("game"), Some(LogLevel::Info));
+        assert_eq!(table.level_for("bevy_ecs"), Some(LogLevel::Warn));
+        assert_eq!(table.level_for("bevy_render::view"), Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn allows_at_or_above_minimum() {
+        let table = levels(&[("bevy", LogLevel::Warn)]);
+        assert!(table.allows("bevy_ecs", LogLevel::Warn));
+        assert!(table.allows("bevy_ecs", LogLevel::Error));
+        assert!(!table.allows("bevy_ecs", LogLevel::Info));
+        // No matching rule: everything passes.
+        assert!(table.allows("game", LogLevel::Trace));
+        assert!(TargetLevels::default().allows("bevy", LogLevel::Trace));
+    }
+}
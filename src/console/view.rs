@@ -0,0 +1,222 @@
+//! # view.rs
+//!
+//! # view.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Renders the console's log area. Visible entries are flattened into uniform-height rows
+//! (session headers plus log lines) so the scroll area only lays out the rows on screen, which
+//! keeps the panel responsive even with a full ring buffer.
+//!
+//! 负责渲染控制台的日志区域。可见条目会被展开为等高的行（会话标题与日志行），
+//! 使滚动区域只布局屏幕上可见的行，即使环形缓冲区已满，面板也能保持流畅。
+
+use super::{ConsoleState, ConsoleTab, LogEntry, LogLevel};
+use crate::bench_ui::{Icon, icon};
+use crate::i18n::I18n;
+
+/// A row in the virtualized log list.
+enum Row {
+    /// Collapsible Play session header.
+    Header(usize),
+    /// Index into `ConsoleState::logs`.
+    Entry(usize),
+}
+
+impl ConsoleState {
+    /// Select the entry at `index` in `logs` and scroll the log view to it, expanding its
+    /// session and enabling its level if needed.
+    pub fn reveal_entry(&mut self, index: usize) {
+        let Some(entry) = self.logs.get(index) else {
+            return;
+        };
+        if let Some(session) = entry.session {
+            self.collapsed_sessions.remove(&session);
+        }
+        match entry.level {
+            LogLevel::Trace => self.show_trace = true,
+            LogLevel::Debug => self.show_debug = true,
+            LogLevel::Info => self.show_info = true,
+            LogLevel::Warn => self.show_warn = true,
+            LogLevel::Error => self.show_error = true,
+        }
+        self.tab = ConsoleTab::Log;
+        self.auto_scroll = false;
+        self.selected.clear();
+        self.selected.insert(index);
+        self.selection_anchor = Some(index);
+        self.scroll_to = Some(index);
+    }
+
+    /// Indices into `logs` of entries passing the current session, level, and query filters.
+    pub fn visible_indices(&self) -> Vec<usize> {
+        let latest_session = self.sessions.last().map(|s| s.id);
+        self.logs
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                !self.show_current_session_only
+                    || latest_session.is_none()
+                    || entry.session == latest_session
+            })
+            .filter(|(_, entry)| self.entry_matches(entry))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Flatten visible entries into rows, inserting session headers and
+    /// skipping entries of collapsed sessions.
+    fn rows(&self, visible: &[usize]) -> Vec<Row> {
+        let mut rows = Vec::with_capacity(visible.len());
+        let mut prev_session = None;
+        for &index in visible {
+            let session = self.logs[index].session;
+            if !self.group_by_session || session.is_none() {
+                rows.push(Row::Entry(index));
+                prev_session = session;
+                continue;
+            }
+            if session != prev_session
+                && let Some(id) = session
+                && self.session(id).is_some()
+            {
+                rows.push(Row::Header(id));
+            }
+            prev_session = session;
+            if !session.is_some_and(|id| self.collapsed_sessions.contains(&id)) {
+                rows.push(Row::Entry(index));
+            }
+        }
+        rows
+    }
+}
+
+/// Renders the scrollable log list and applies row clicks (selection / collapse).
+pub(super) fn log_area_ui(
+    ui: &mut egui::Ui,
+    console: &mut ConsoleState,
+    visible: &[usize],
+    i18n: Option<&I18n>,
+) {
+    let rows = console.rows(visible);
+    let row_height =
+        ui.text_style_height(&egui::TextStyle::Body) + 2.0 * ui.spacing().button_padding.y;
+
+    let mut scroll_area = egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .stick_to_bottom(console.auto_scroll);
+    if let Some(index) = console.scroll_to.take()
+        && let Some(row) = rows
+            .iter()
+            .position(|r| matches!(r, Row::Entry(i) if *i == index))
+    {
+        let spacing = ui.spacing().item_spacing.y;
+        scroll_area = scroll_area.vertical_scroll_offset(row as f32 * (row_height + spacing));
+    }
+    let clicked = scroll_area
+        .show_rows(ui, row_height, rows.len(), |ui, range| {
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Truncate);
+            let mut clicked = None;
+            for row in &rows[range] {
+                if row_ui(ui, console, row, i18n).clicked() {
+                    clicked = Some(row);
+                }
+            }
+            clicked
+        })
+        .inner;
+
+    match clicked {
+        Some(Row::Header(id)) => {
+            if !console.collapsed_sessions.remove(id) {
+                console.collapsed_sessions.insert(*id);
+            }
+        }
+        Some(Row::Entry(index)) => {
+            let modifiers = ui.input(|i| i.modifiers);
+            console.select_entry(*index, modifiers);
+        }
+        None => {}
+    }
+}
+
+fn row_ui(
+    ui: &mut egui::Ui,
+    console: &ConsoleState,
+    row: &Row,
+    i18n: Option<&I18n>,
+) -> egui::Response {
+    match row {
+        Row::Header(id) => {
+            let collapsed = console.collapsed_sessions.contains(id);
+            let arrow = if collapsed {
+                icon(Icon::CaretRight)
+            } else {
+                icon(Icon::CaretDown)
+            };
+            let header = console
+                .session(*id)
+                .map(|s| s.header(i18n))
+                .unwrap_or_default();
+            let response = ui.selectable_label(
+                false,
+                egui::RichText::new(format!("{arrow} {header}")).strong(),
+            );
+            response.widget_info(|| {
+                egui::WidgetInfo::selected(
+                    egui::WidgetType::CollapsingHeader,
+                    true,
+                    !collapsed,
+                    &header,
+                )
+            });
+            response
+        }
+        Row::Entry(index) => {
+            let selected = console.selected.contains(index);
+            if console.error_pause_entry() == Some(*index) {
+                // Highlight the entry that triggered Pause on Error.
+                let height = ui.text_style_height(&egui::TextStyle::Body)
+                    + 2.0 * ui.spacing().button_padding.y;
+                let rect = egui::Rect::from_min_size(
+                    ui.cursor().min,
+                    egui::vec2(ui.available_width(), height),
+                );
+                let fill = crate::theme::palette(ui.ctx()).error.gamma_multiply(0.25);
+                ui.painter().rect_filled(rect, 2.0, fill);
+            }
+            log_entry_ui(ui, &console.logs[*index], selected)
+        }
+    }
+}
+
+/// Renders a single selectable log row.
+fn log_entry_ui(ui: &mut egui::Ui, entry: &LogEntry, selected: bool) -> egui::Response {
+    let color = entry.level.color(ui);
+    let format = |color: egui::Color32| egui::TextFormat {
+        color,
+        ..Default::default()
+    };
+    let mut job = egui::text::LayoutJob::default();
+    job.append(entry.level.icon(), 0.0, format(color));
+    job.append(
+        &format!("[{}]", entry.target),
+        8.0,
+        format(egui::Color32::DARK_GRAY),
+    );
+    job.append(&entry.message, 8.0, format(color));
+    let response = ui.selectable_label(selected, egui::WidgetText::from(job));
+    // Spoken without the level glyph: "WARN, my_crate: message".
+    response.widget_info(|| {
+        let label = format!(
+            "{}, {}: {}",
+            entry.level.label(),
+            entry.target,
+            entry.message
+        );
+        egui::WidgetInfo::selected(egui::WidgetType::SelectableLabel, true, selected, label)
+    });
+    response
+}
@@ -0,0 +1,288 @@
+//! Modal dialog service: confirmations, text prompts and progress dialogs drawn over the dock.
+
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy_egui::PrimaryEguiContext;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+type ConfirmCallback = Box<dyn FnOnce(&mut World, bool) + Send + Sync>;
+type PromptCallback = Box<dyn FnOnce(&mut World, Option<String>) + Send + Sync>;
+
+enum DialogKind {
+    Confirm(ConfirmCallback),
+    Prompt {
+        text: String,
+        on_result: PromptCallback,
+    },
+}
+
+/// A queued confirm/prompt dialog.
+struct Dialog {
+    id: u64,
+    title: String,
+    message: String,
+    kind: DialogKind,
+}
+
+/// How the user answered a dialog.
+enum Answer {
+    Confirm(bool),
+    Prompt(Option<String>),
+}
+
+/// Shared state of a progress dialog.
+#[derive(Debug, Default)]
+struct ProgressInner {
+    title: String,
+    message: String,
+    /// `None` shows an indeterminate spinner.
+    fraction: Option<f32>,
+    cancellable: bool,
+    cancelled: bool,
+    finished: bool,
+}
+
+/// Handle to a progress dialog. Clone it into tasks or threads to report progress;
+/// the dialog closes once [`finish`](Self::finish) is called or the token is cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressToken(Arc<Mutex<ProgressInner>>);
+
+impl ProgressToken {
+    /// Create a token for a progress dialog with the given title.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self(Arc::new(Mutex::new(ProgressInner {
+            title: title.into(),
+            ..Default::default()
+        })))
+    }
+
+    /// Show a Cancel button; check [`is_cancelled`](Self::is_cancelled) from the worker.
+    pub fn cancellable(self) -> Self {
+        self.0.lock().unwrap().cancellable = true;
+        self
+    }
+
+    /// Set completion in `0.0..=1.0`.
+    pub fn set_progress(&self, fraction: f32) {
+        self.0.lock().unwrap().fraction = Some(fraction.clamp(0.0, 1.0));
+    }
+
+    /// Set the status line shown under the progress bar.
+    pub fn set_message(&self, message: impl Into<String>) {
+        self.0.lock().unwrap().message = message.into();
+    }
+
+    /// Close the dialog.
+    pub fn finish(&self) {
+        self.0.lock().unwrap().finished = true;
+    }
+
+    /// Request cancellation (same as the user pressing Cancel).
+    pub fn cancel(&self) {
+        self.0.lock().unwrap().cancelled = true;
+    }
+
+    /// Whether the user pressed Cancel.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.lock().unwrap().cancelled
+    }
+
+    /// Current completion fraction and status message.
+    pub(crate) fn snapshot(&self) -> (Option<f32>, String) {
+        let inner = self.0.lock().unwrap();
+        (inner.fraction, inner.message.clone())
+    }
+
+    fn is_done(&self) -> bool {
+        let inner = self.0.lock().unwrap();
+        inner.finished || inner.cancelled
+    }
+}
+
+/// Resource for showing modal dialogs from panels and systems.
+///
+/// Dialogs are shown one at a time in request order. Result callbacks get `&mut World`,
+/// so they can act on any resource (including queueing further dialogs).
+#[derive(Resource, Default)]
+pub struct Dialogs {
+    queue: VecDeque<Dialog>,
+    progress: Vec<ProgressToken>,
+    next_id: u64,
+}
+
+impl Dialogs {
+    /// Ask a yes/no question; `on_result` receives `true` when confirmed.
+    pub fn confirm(
+        &mut self,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        on_result: impl FnOnce(&mut World, bool) + Send + Sync + 'static,
+    ) {
+        self.push(
+            title.into(),
+            message.into(),
+            DialogKind::Confirm(Box::new(on_result)),
+        );
+    }
+
+    /// Ask for a line of text; `on_result` receives `None` when cancelled.
+    pub fn prompt_text(
+        &mut self,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        initial: impl Into<String>,
+        on_result: impl FnOnce(&mut World, Option<String>) + Send + Sync + 'static,
+    ) {
+        let kind = DialogKind::Prompt {
+            text: initial.into(),
+            on_result: Box::new(on_result),
+        };
+        self.push(title.into(), message.into(), kind);
+    }
+
+    /// Show a progress dialog until the token is finished or cancelled.
+    pub fn progress(&mut self, token: ProgressToken) {
+        self.progress.push(token);
+    }
+
+    /// Whether any dialog is currently shown.
+    pub fn is_open(&self) -> bool {
+        !self.queue.is_empty() || self.progress.iter().any(|t| !t.is_done())
+    }
+
+    fn push(&mut self, title: String, message: String, kind: DialogKind) {
+        self.next_id += 1;
+        self.queue.push_back(Dialog {
+            id: self.next_id,
+            title,
+            message,
+            kind,
+        });
+    }
+}
+
+/// Localized button labels.
+struct DialogStrings {
+    ok: String,
+    cancel: String,
+}
+
+impl DialogStrings {
+    fn new(world: &World) -> Self {
+        match world.get_resource::<crate::i18n::I18n>() {
+            Some(i18n) => Self {
+                ok: i18n.t("dialog-ok").to_owned(),
+                cancel: i18n.t("dialog-cancel").to_owned(),
+            },
+            None => Self {
+                ok: "OK".to_string(),
+                cancel: "Cancel".to_string(),
+            },
+        }
+    }
+}
+
+/// Exclusive system drawing the front dialog and dispatching its result.
+pub fn dialogs_ui_system(world: &mut World) {
+    let ctx = {
+        let mut sys =
+            SystemState::<Query<&mut bevy_egui::EguiContext, With<PrimaryEguiContext>>>::new(world);
+        let mut query = sys.get_mut(world);
+        let Ok(mut egui_ctx) = query.single_mut() else {
+            return;
+        };
+        egui_ctx.get_mut().clone()
+    };
+    let strings = DialogStrings::new(world);
+
+    let mut dialogs = world.resource_mut::<Dialogs>();
+    dialogs.progress.retain(|t| !t.is_done());
+    let Some(dialog) = dialogs.queue.front_mut() else {
+        if let Some(token) = dialogs.progress.first() {
+            progress_ui(&ctx, token, &strings);
+        }
+        return;
+    };
+    let Some(answer) = dialog_ui(&ctx, dialog, &strings) else {
+        return;
+    };
+    let Some(dialog) = dialogs.queue.pop_front() else {
+        return;
+    };
+    match (dialog.kind, answer) {
+        (DialogKind::Confirm(on_result), Answer::Confirm(ok)) => on_result(world, ok),
+        (DialogKind::Prompt { on_result, .. }, Answer::Prompt(text)) => on_result(world, text),
+        _ => {}
+    }
+}
+
+fn dialog_ui(ctx: &egui::Context, dialog: &mut Dialog, strings: &DialogStrings) -> Option<Answer> {
+    let mut answer = None;
+    let modal = egui::Modal::new(egui::Id::new(("workbench_dialog", dialog.id))).show(ctx, |ui| {
+        ui.set_min_width(280.0);
+        ui.heading(&dialog.title);
+        if !dialog.message.is_empty() {
+            ui.label(&dialog.message);
+        }
+        let mut submitted = false;
+        if let DialogKind::Prompt { text, .. } = &mut dialog.kind {
+            let edit = ui.text_edit_singleline(text);
+            if ui.memory(|m| m.focused().is_none()) {
+                edit.request_focus();
+            }
+            submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(&strings.ok).clicked() || submitted {
+                answer = Some(accept(&dialog.kind));
+            }
+            if ui.button(&strings.cancel).clicked() {
+                answer = Some(reject(&dialog.kind));
+            }
+        });
+    });
+    if modal.should_close() && answer.is_none() {
+        answer = Some(reject(&dialog.kind));
+    }
+    answer
+}
+
+fn accept(kind: &DialogKind) -> Answer {
+    match kind {
+        DialogKind::Confirm(_) => Answer::Confirm(true),
+        DialogKind::Prompt { text, .. } => Answer::Prompt(Some(text.clone())),
+    }
+}
+
+fn reject(kind: &DialogKind) -> Answer {
+    match kind {
+        DialogKind::Confirm(_) => Answer::Confirm(false),
+        DialogKind::Prompt { .. } => Answer::Prompt(None),
+    }
+}
+
+fn progress_ui(ctx: &egui::Context, token: &ProgressToken, strings: &DialogStrings) {
+    let mut inner = token.0.lock().unwrap();
+    egui::Modal::new(egui::Id::new("workbench_progress")).show(ctx, |ui| {
+        ui.set_min_width(280.0);
+        ui.heading(&inner.title);
+        match inner.fraction {
+            Some(fraction) => {
+                ui.add(egui::ProgressBar::new(fraction).show_percentage());
+            }
+            None => {
+                ui.spinner();
+            }
+        }
+        if !inner.message.is_empty() {
+            ui.label(&inner.message);
+        }
+        if inner.cancellable && ui.button(&strings.cancel).clicked() {
+            inner.cancelled = true;
+        }
+    });
+    // Keep animating while the worker reports progress.
+    ctx.request_repaint();
+}
@@ -3,60 +3,40 @@
 //! Sets up a desktop editor layout using egui_tiles with the structure:
 //! - Root: Vertical split (main area + bottom console)
 //! - Main area: Horizontal split (game view + right inspector)
+//!
+//! The default can be replaced in code with a [`LayoutBuilder`].
 
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet};
-use std::sync::Mutex;
 
+mod arrange;
+mod builder;
+mod focus;
+mod layout_undo;
+mod maximize;
+mod mode_layout;
 mod persistence;
+mod play_layout;
+mod reopen;
+mod style;
+mod system_panel;
+mod tab_menu;
+mod tab_overflow;
 mod ui;
-
+mod window;
+
+pub use arrange::Direction;
+pub use builder::{LayoutBuilder, column, panel, row, tabs};
+pub use focus::FocusedPanel;
+pub(crate) use focus::pane_focus_id;
+pub(crate) use layout_undo::{LayoutSnapshot, LayoutUndoAction, record_layout_change};
+pub use mode_layout::mode_layout_system;
+pub use persistence::LAYOUT_VERSION;
+pub use play_layout::{hide_panels_on_play_system, restore_panels_on_stop_system};
+pub use style::{PanelStyle, PanelStyles};
+pub use system_panel::{PanelSystemFn, SystemPanel};
 pub use ui::{LayoutPath, tiles_ui_system};
-
-/// Snapshot of the layout for undo/redo (tree + tile mapping).
-#[derive(Clone)]
-pub(crate) struct LayoutSnapshot {
-    pub tree: egui_tiles::Tree<PaneEntry>,
-    pub panel_tile_map: HashMap<PanelId, egui_tiles::TileId>,
-}
-
-/// Undo action that restores a layout snapshot.
-/// Uses Mutex for interior mutability since UndoAction takes &self.
-pub(crate) struct LayoutUndoAction {
-    before: Mutex<LayoutSnapshot>,
-    after: Mutex<LayoutSnapshot>,
-    desc: String,
-}
-
-impl LayoutUndoAction {
-    pub fn new(desc: impl Into<String>, before: LayoutSnapshot, after: LayoutSnapshot) -> Self {
-        Self {
-            before: Mutex::new(before),
-            after: Mutex::new(after),
-            desc: desc.into(),
-        }
-    }
-}
-
-impl crate::undo::UndoAction for LayoutUndoAction {
-    fn undo(&self, world: &mut World) {
-        let snapshot = self.before.lock().unwrap().clone();
-        world
-            .resource_mut::<TileLayoutState>()
-            .restore_snapshot(snapshot);
-    }
-
-    fn redo(&self, world: &mut World) {
-        let snapshot = self.after.lock().unwrap().clone();
-        world
-            .resource_mut::<TileLayoutState>()
-            .restore_snapshot(snapshot);
-    }
-
-    fn description(&self) -> &str {
-        &self.desc
-    }
-}
+pub(crate) use window::dock_windows_system;
 
 /// Trait for user-defined editor panels.
 pub trait WorkbenchPanel: Send + Sync + std::any::Any + 'static {
@@ -90,19 +70,68 @@ pub trait WorkbenchPanel: Send + Sync + std::any::Any + 'static {
         true
     }
 
+    /// Optional icon (e.g. an emoji glyph) shown before the tab title.
+    fn icon(&self) -> Option<&str> {
+        None
+    }
+
+    /// Optional Markdown documentation for the panel, shown behind "?" in its tab's context
+    /// menu and in the Help panel.
+    fn help(&self) -> Option<&str> {
+        None
+    }
+
+    /// Background of the panel's content area (default: the theme's). Overridden by
+    /// [`PanelStyles`].
+    fn style(&self) -> PanelStyle {
+        PanelStyle::default()
+    }
+
+    /// Whether the panel has unsaved changes. Dirty panels show a "•" on their tab
+    /// and ask for confirmation before closing.
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    /// Slot this panel occupies in the default layout (default: [`PanelSlot::Left`]).
+    /// A slot passed to `register_panel_in` takes precedence.
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Left
+    }
+
+    /// Relative size of this panel's slot in the default layout, or `None` for the
+    /// slot's built-in share. If several panels in one slot give a hint, the largest wins.
+    fn default_share(&self) -> Option<f32> {
+        None
+    }
+
+    /// Whether the panel is shown in `mode` (default: always). Its tab is hidden when the
+    /// editor enters a mode where this returns `false` and restored when it leaves.
+    fn visible_in(&self, _mode: crate::mode::EditorMode) -> bool {
+        true
+    }
+
     /// Whether this panel appears in the built-in Window menu (default: true).
     /// Return `false` to hide the panel from the Window menu, useful when the panel
     /// is managed via a custom top-level menu instead.
     fn show_in_window_menu(&self) -> bool {
         true
     }
+
+    /// Panel state to autosave for crash recovery, or `None` if there is nothing to keep.
+    fn save_state(&self) -> Option<String> {
+        None
+    }
+
+    /// Restore state produced by [`save_state`](Self::save_state) after a crash.
+    fn load_state(&mut self, _state: &str) {}
 }
 
 /// Identifies a panel in the tile tree.
 pub type PanelId = usize;
 
 /// Where a panel should be placed in the desktop layout.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PanelSlot {
     /// Right side (e.g. Inspector).
     Right,
@@ -114,6 +143,18 @@ pub enum PanelSlot {
     Left,
 }
 
+impl PanelSlot {
+    /// Share of the slot in the default layout when no panel gives a size hint.
+    fn default_share(self) -> f32 {
+        match self {
+            PanelSlot::Left => 1.0,
+            PanelSlot::Center => 4.0, // center takes most space
+            PanelSlot::Right => 1.5,
+            PanelSlot::Bottom => 1.0,
+        }
+    }
+}
+
 /// A pane entry stored in the egui_tiles tree.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PaneEntry {
@@ -138,32 +179,58 @@ pub struct TileLayoutState {
     tree_built: bool,
     /// Maps panel string IDs to PanelIds for lookup.
     pub(crate) panel_id_map: HashMap<String, PanelId>,
+    /// Slot each registered panel was assigned to (for the default layout).
+    pub(crate) panel_slots: HashMap<PanelId, PanelSlot>,
     /// Maps PanelIds to TileIds in the tree (for visibility control).
     pub(crate) panel_tile_map: HashMap<PanelId, egui_tiles::TileId>,
     /// Set by menu to request layout reset to default.
     pub(crate) layout_reset_requested: bool,
-    /// Path to save layout to (set via file dialog).
-    pub(crate) layout_save_path: Option<std::path::PathBuf>,
-    /// Path to load layout from (set via file dialog).
-    pub(crate) layout_load_path: Option<std::path::PathBuf>,
+    /// Layout JSON picked in the Load Layout file dialog: (contents, file name).
+    pub(crate) pending_layout_load: Option<(String, String)>,
     /// Panels requested to open (processed in exclusive system with undo recording).
     pub(crate) pending_open_requests: Vec<String>,
+    /// Panels closed in the main tree, most recent last (for reopening).
+    recently_closed: Vec<reopen::ClosedPanel>,
+    /// Set by menu to reopen the most recently closed panel.
+    pub(crate) reopen_requested: bool,
+    /// Panel temporarily filling the whole dock area (never persisted).
+    pub(crate) maximized: Option<PanelId>,
+    /// Maximized panel drawn without its restore header (presentation mode).
+    chromeless: bool,
+    /// Secondary OS windows with their own tile trees.
+    pub(crate) windows: Vec<window::DockWindow>,
+    /// Tab being dragged in the main tree (to detect drops outside the window).
+    dragged_tile: Option<egui_tiles::TileId>,
+    /// Panel reached with the focus shortcuts; its focus anchor takes keyboard focus.
+    focus_entered: Option<PanelId>,
+    /// Dirty panel tabs waiting for close confirmation, prompted one at a time.
+    pub(crate) pending_dirty_close: Vec<egui_tiles::TileId>,
+    /// Programmatic split/move/focus operations (applied in the exclusive system with undo).
+    pub(crate) pending_layout_ops: Vec<arrange::LayoutOp>,
+    /// Layout backed up while editor panels are hidden for Play.
+    pub(crate) play_layout: Option<play_layout::PlayLayoutBackup>,
+    /// Separate layouts per editor mode.
+    pub(crate) mode_layouts: mode_layout::ModeLayouts,
     /// Panel IDs hidden from the Window menu at runtime (overrides `show_in_window_menu()`).
     window_menu_hidden: HashSet<String>,
+    /// Panel IDs whose tabs are locked against closing and dragging (saved with the layout).
+    pub(crate) locked_tabs: HashSet<String>,
     /// Panel IDs that should be hidden in the default layout
     /// (overrides `default_visible()` when building tree from scratch).
     default_hidden: HashSet<String>,
+    /// Code-first default layout (replaces the slot heuristic when set).
+    default_layout: Option<LayoutBuilder>,
 }
 
 impl TileLayoutState {
-    /// Register a panel. Auto-detects slot by panel ID convention.
+    /// Register a panel in the slot given by [`WorkbenchPanel::default_slot`].
     pub fn add_panel(&mut self, panel: Box<dyn WorkbenchPanel>) -> PanelId {
-        let slot = match panel.id() {
-            id if id.contains("inspector") => PanelSlot::Right,
-            id if id.contains("console") || id.contains("timeline") => PanelSlot::Bottom,
-            id if id.contains("game_view") || id.contains("preview") => PanelSlot::Center,
-            _ => PanelSlot::Left,
-        };
+        let slot = panel.default_slot();
+        self.add_panel_in(panel, slot)
+    }
+
+    /// Register a panel in an explicit slot of the default layout.
+    pub fn add_panel_in(&mut self, panel: Box<dyn WorkbenchPanel>, slot: PanelSlot) -> PanelId {
         let visible = panel.default_visible();
         let id = self.next_id;
         self.next_id += 1;
@@ -188,6 +255,7 @@ impl TileLayoutState {
             let id = self.panels.len();
             let str_id = pending.panel.id().to_string();
             self.panels.insert(id, pending.panel);
+            self.panel_slots.insert(id, pending.slot);
             self.panel_id_map.insert(str_id, id);
         }
 
@@ -203,8 +271,11 @@ impl TileLayoutState {
         self.build_default_tree();
     }
 
-    /// Build the default layout from panel slots.
+    /// Build the default layout from the [`LayoutBuilder`] if one is set, else from panel slots.
     fn build_default_tree(&mut self) {
+        if self.build_tree_from_layout() {
+            return;
+        }
         let mut tiles = egui_tiles::Tiles::default();
 
         // Collect panels by slot
@@ -224,13 +295,19 @@ impl TileLayoutState {
             .collect();
         visible_panels.sort_by_key(|(s, _)| *s);
 
-        for &(str_id, panel_id) in &visible_panels {
-            let slot = match str_id {
-                id if id.contains("inspector") => PanelSlot::Right,
-                id if id.contains("console") || id.contains("timeline") => PanelSlot::Bottom,
-                id if id.contains("game_view") || id.contains("preview") => PanelSlot::Center,
-                _ => PanelSlot::Left,
-            };
+        // Largest size hint per slot
+        let mut slot_shares: HashMap<PanelSlot, f32> = HashMap::new();
+
+        for &(_, panel_id) in &visible_panels {
+            let slot = self
+                .panel_slots
+                .get(&panel_id)
+                .copied()
+                .unwrap_or(PanelSlot::Left);
+            if let Some(share) = self.panels[&panel_id].default_share() {
+                let entry = slot_shares.entry(slot).or_insert(share);
+                *entry = entry.max(share);
+            }
             let tile_id = tiles.insert_pane(PaneEntry { panel_id });
             self.panel_tile_map.insert(panel_id, tile_id);
             match slot {
@@ -241,15 +318,10 @@ impl TileLayoutState {
             }
         }
 
-        // Sort center panes: non-game_view tabs before game_view (first tab is active by default)
-        center_panes.sort_by_key(|tile_id| {
-            if let Some(egui_tiles::Tile::Pane(pane)) = tiles.get(*tile_id) {
-                visible_panels
-                    .iter()
-                    .any(|&(s, pid)| pid == pane.panel_id && s.contains("game_view"))
-            } else {
-                false
-            }
+        // Center tabs follow registration order (the first tab is active by default)
+        center_panes.sort_by_key(|tile_id| match tiles.get(*tile_id) {
+            Some(egui_tiles::Tile::Pane(pane)) => pane.panel_id,
+            _ => PanelId::MAX,
         });
 
         // Build tab containers for each slot (always with tab headers for drag support)
@@ -258,20 +330,27 @@ impl TileLayoutState {
         let right_tile = Self::make_tab(&mut tiles, right_panes);
         let bottom_tile = Self::make_tab(&mut tiles, bottom_panes);
 
+        let share = |slot: PanelSlot| {
+            slot_shares
+                .get(&slot)
+                .copied()
+                .unwrap_or_else(|| slot.default_share())
+        };
+
         // Build main horizontal row: [left? | center | right?]
         let mut main_children = Vec::new();
         let mut main_shares = Vec::new();
         if let Some(left) = left_tile {
             main_children.push(left);
-            main_shares.push((left, 1.0));
+            main_shares.push((left, share(PanelSlot::Left)));
         }
         if let Some(center) = center_tile {
             main_children.push(center);
-            main_shares.push((center, 4.0)); // center takes most space
+            main_shares.push((center, share(PanelSlot::Center)));
         }
         if let Some(right) = right_tile {
             main_children.push(right);
-            main_shares.push((right, 1.5));
+            main_shares.push((right, share(PanelSlot::Right)));
         }
 
         let root = if main_children.is_empty() && bottom_tile.is_none() {
@@ -294,7 +373,8 @@ impl TileLayoutState {
             if let Some(bottom) = bottom_tile {
                 // Vertical split: main row on top, bottom panel below
                 let root_id = tiles.insert_vertical_tile(vec![main_row, bottom]);
-                ui::set_linear_shares(&mut tiles, root_id, &[(main_row, 4.0), (bottom, 1.0)]);
+                let shares = [(main_row, 4.0), (bottom, share(PanelSlot::Bottom))];
+                ui::set_linear_shares(&mut tiles, root_id, &shares);
                 root_id
             } else {
                 main_row
@@ -321,6 +401,10 @@ impl TileLayoutState {
         let Some(&panel_id) = self.panel_id_map.get(panel_str_id) else {
             return;
         };
+        if let Some(index) = self.window_of(panel_id) {
+            self.focus_in_window(index, panel_id);
+            return;
+        }
         let Some(tree) = &mut self.tree else { return };
 
         if let Some(&tile_id) = self.panel_tile_map.get(&panel_id) {
@@ -344,8 +428,17 @@ impl TileLayoutState {
 
     /// Close a panel by removing its tile from the tree entirely.
     pub fn hide_tile(&mut self, tile_id: egui_tiles::TileId) {
-        if let Some(tree) = &mut self.tree {
-            tree.tiles.remove(tile_id);
+        let Some(tree) = &mut self.tree else { return };
+        let closed = match tree.tiles.get(tile_id) {
+            Some(egui_tiles::Tile::Pane(pane)) => self
+                .panels
+                .get(&pane.panel_id)
+                .map(|panel| reopen::ClosedPanel::new(tree, tile_id, panel.id().to_string())),
+            _ => None,
+        };
+        tree.tiles.remove(tile_id);
+        if let Some(closed) = closed {
+            self.push_closed(closed);
         }
     }
 
@@ -354,6 +447,10 @@ impl TileLayoutState {
         let Some(&panel_id) = self.panel_id_map.get(panel_str_id) else {
             return false;
         };
+        if let Some(index) = self.window_of(panel_id) {
+            self.close_in_window(index, panel_id);
+            return true;
+        }
         if let Some(&tile_id) = self.panel_tile_map.get(&panel_id) {
             self.hide_tile(tile_id);
             true
@@ -383,6 +480,7 @@ impl TileLayoutState {
         self.tree.as_ref().map(|tree| LayoutSnapshot {
             tree: tree.clone(),
             panel_tile_map: self.panel_tile_map.clone(),
+            windows: self.windows.clone(),
         })
     }
 
@@ -390,6 +488,7 @@ impl TileLayoutState {
     pub(crate) fn restore_snapshot(&mut self, snapshot: LayoutSnapshot) {
         self.tree = Some(snapshot.tree);
         self.panel_tile_map = snapshot.panel_tile_map;
+        self.restore_windows(snapshot.windows);
     }
 
     /// Returns list of (panel_str_id, title, is_visible) for building the Window menu.
@@ -404,18 +503,27 @@ impl TileLayoutState {
             if !panel.show_in_window_menu() || self.window_menu_hidden.contains(str_id) {
                 continue;
             }
-            let title = panel.title();
-            let visible = self
-                .panel_tile_map
-                .get(&panel_id)
-                .and_then(|&tid| self.tree.as_ref().map(|t| t.tiles.get(tid).is_some()))
-                .unwrap_or(false);
-            result.push((str_id.clone(), title, visible));
+            let visible = self.is_panel_visible(str_id);
+            result.push((str_id.clone(), panel.title(), visible));
         }
         result.sort_by(|a, b| a.1.cmp(&b.1));
         result
     }
 
+    /// Lock or unlock the tab of a panel. Locked tabs can't be closed or dragged.
+    pub fn set_tab_locked(&mut self, panel_str_id: &str, locked: bool) {
+        if locked {
+            self.locked_tabs.insert(panel_str_id.to_string());
+        } else {
+            self.locked_tabs.remove(panel_str_id);
+        }
+    }
+
+    /// Whether the tab of a panel is locked.
+    pub fn is_tab_locked(&self, panel_str_id: &str) -> bool {
+        self.locked_tabs.contains(panel_str_id)
+    }
+
     /// Hide a panel from the built-in Window menu (it can still be managed
     /// programmatically or via a custom menu).
     pub fn hide_from_window_menu(&mut self, panel_str_id: &str) {
@@ -437,6 +545,7 @@ impl TileLayoutState {
             .get(&panel_id)
             .and_then(|&tid| self.tree.as_ref().map(|t| t.tiles.get(tid).is_some()))
             .unwrap_or(false)
+            || self.window_of(panel_id).is_some()
     }
 
     /// Get a mutable reference to a panel by its string ID, with downcasting.
@@ -445,18 +554,30 @@ impl TileLayoutState {
         &mut self,
         panel_str_id: &str,
     ) -> Option<&mut T> {
+        let panel = self.panel_dyn_mut(panel_str_id)?;
+        (panel as &mut dyn std::any::Any).downcast_mut::<T>()
+    }
+
+    /// Get a panel by its string ID without knowing its type (built or pending).
+    pub fn panel_dyn_mut(&mut self, panel_str_id: &str) -> Option<&mut dyn WorkbenchPanel> {
         // First try the built panels map
         if let Some(&panel_id) = self.panel_id_map.get(panel_str_id)
             && let Some(panel) = self.panels.get_mut(&panel_id)
         {
-            return (panel.as_mut() as &mut dyn std::any::Any).downcast_mut::<T>();
+            return Some(panel.as_mut());
         }
         // Fall back to pending panels (not yet moved into the map)
-        for pending in &mut self.pending {
-            if pending.panel.id() == panel_str_id {
-                return (pending.panel.as_mut() as &mut dyn std::any::Any).downcast_mut::<T>();
-            }
-        }
-        None
+        self.pending
+            .iter_mut()
+            .find(|pending| pending.panel.id() == panel_str_id)
+            .map(|pending| pending.panel.as_mut())
+    }
+
+    /// Autosave state of every panel that has some, keyed by panel string ID.
+    pub(crate) fn panel_states(&self) -> std::collections::BTreeMap<String, String> {
+        self.panels
+            .values()
+            .filter_map(|panel| Some((panel.id().to_string(), panel.save_state()?)))
+            .collect()
     }
 }
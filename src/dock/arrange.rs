@@ -0,0 +1,279 @@
+//! # arrange.rs
+//!
+//! # arrange.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Programmatic layout editing for the dock. Tools queue split, move-to-slot and focus
+//! operations on `TileLayoutState`; the tiles UI system applies them once the tree exists and
+//! records a layout undo snapshot, so a preferred layout can be built in code on first run.
+//!
+//! 停靠布局的程序化编辑。工具在 `TileLayoutState` 上排队拆分、移动到槽位和聚焦操作；
+//! tiles UI 系统会在 tile 树建立后执行它们并记录布局撤销快照，从而可以在首次运行时用代码搭建偏好的布局。
+
+use super::{LayoutUndoAction, PaneEntry, PanelSlot, TileLayoutState, ui};
+use crate::undo::LayoutUndoStack;
+use bevy::prelude::*;
+use egui_tiles::{Container, Tile, TileId};
+
+/// Side of an existing panel (or of the whole dock) to place a panel on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A queued programmatic layout change.
+pub(crate) enum LayoutOp {
+    Split {
+        target: String,
+        panel: String,
+        dir: Direction,
+        share: f32,
+    },
+    MoveToSlot {
+        panel: String,
+        slot: PanelSlot,
+    },
+    Focus {
+        panel: String,
+    },
+}
+
+impl TileLayoutState {
+    /// Place `panel` next to `target` on side `dir`, taking `share` (0..1) of the split.
+    ///
+    /// The change is applied by the dock on its next frame and can be undone.
+    pub fn split_panel(&mut self, target: &str, panel: &str, dir: Direction, share: f32) {
+        self.pending_layout_ops.push(LayoutOp::Split {
+            target: target.to_string(),
+            panel: panel.to_string(),
+            dir,
+            share: share.clamp(0.05, 0.95),
+        });
+    }
+
+    /// Move `panel` into the tab group of `slot` (creating the slot at the dock edge if empty).
+    pub fn move_panel_to_slot(&mut self, panel: &str, slot: PanelSlot) {
+        self.pending_layout_ops.push(LayoutOp::MoveToSlot {
+            panel: panel.to_string(),
+            slot,
+        });
+    }
+
+    /// Open `panel` if needed and make it the active tab of its group.
+    pub fn focus_panel(&mut self, panel: &str) {
+        self.pending_layout_ops.push(LayoutOp::Focus {
+            panel: panel.to_string(),
+        });
+    }
+
+    /// Apply one queued operation. Returns an undo description for structural changes.
+    fn apply_layout_op(&mut self, op: LayoutOp) -> Option<String> {
+        match op {
+            LayoutOp::Split {
+                target,
+                panel,
+                dir,
+                share,
+            } => self
+                .apply_split(&target, &panel, dir, share)
+                .then(|| format!("Split {panel}")),
+            LayoutOp::MoveToSlot { panel, slot } => self
+                .apply_move_to_slot(&panel, slot)
+                .then(|| format!("Move {panel}")),
+            LayoutOp::Focus { panel } => {
+                self.apply_focus(&panel);
+                None
+            }
+        }
+    }
+
+    fn apply_split(&mut self, target: &str, panel: &str, dir: Direction, share: f32) -> bool {
+        if target == panel {
+            return false;
+        }
+        let Some(target_tile) = self.tree_pane_of(target) else {
+            return false;
+        };
+        let Some(panel_tile) = self.detached_pane_of(panel) else {
+            return false;
+        };
+        let Some(tree) = &mut self.tree else {
+            return false;
+        };
+        // Split around the target's whole tab group rather than the single pane.
+        let anchor = tree
+            .tiles
+            .parent_of(target_tile)
+            .filter(|&p| matches!(tree.tiles.get(p), Some(Tile::Container(Container::Tabs(_)))))
+            .unwrap_or(target_tile);
+        split_around(tree, anchor, panel_tile, dir, share);
+        true
+    }
+
+    fn apply_move_to_slot(&mut self, panel: &str, slot: PanelSlot) -> bool {
+        let group = self.slot_tab_group(panel, slot);
+        let Some(panel_tile) = self.detached_pane_of(panel) else {
+            return false;
+        };
+        let Some(tree) = &mut self.tree else {
+            return false;
+        };
+        if let Some(group) = group {
+            tree.move_tile_to_container(panel_tile, group, usize::MAX, false);
+            return true;
+        }
+        let Some(root) = tree.root() else {
+            tree.root = Some(panel_tile);
+            return true;
+        };
+        match slot {
+            PanelSlot::Left => split_around(tree, root, panel_tile, Direction::Left, 0.2),
+            PanelSlot::Right => split_around(tree, root, panel_tile, Direction::Right, 0.25),
+            PanelSlot::Bottom => split_around(tree, root, panel_tile, Direction::Down, 0.2),
+            PanelSlot::Center => ui::insert_pane_into_tree(tree, panel_tile),
+        }
+        true
+    }
+
+    fn apply_focus(&mut self, panel: &str) {
+        let Some(&panel_id) = self.panel_id_map.get(panel) else {
+            return;
+        };
+        self.open_or_focus_panel(panel);
+        if let Some(tree) = &mut self.tree {
+            tree.make_active(|_, tile| matches!(tile, Tile::Pane(p) if p.panel_id == panel_id));
+        }
+    }
+
+    /// Tile of a panel that is currently in the tree.
+    fn tree_pane_of(&self, panel: &str) -> Option<TileId> {
+        let panel_id = self.panel_id_map.get(panel)?;
+        let tile = *self.panel_tile_map.get(panel_id)?;
+        self.tree
+            .as_ref()?
+            .tiles
+            .get(tile)
+            .is_some()
+            .then_some(tile)
+    }
+
+    /// Tile of a panel, detached from its current parent (inserted fresh if it was closed or
+    /// shown in a secondary window, which gives it up).
+    fn detached_pane_of(&mut self, panel: &str) -> Option<TileId> {
+        let panel_id = *self.panel_id_map.get(panel)?;
+        self.tree.as_ref()?;
+        if let Some(index) = self.window_of(panel_id) {
+            self.close_in_window(index, panel_id);
+        }
+        let existing = self.tree_pane_of(panel);
+        let tree = self.tree.as_mut()?;
+        let Some(tile) = existing else {
+            let tile = tree.tiles.insert_pane(PaneEntry { panel_id });
+            self.panel_tile_map.insert(panel_id, tile);
+            return Some(tile);
+        };
+        if tree.root() == Some(tile) {
+            return None;
+        }
+        if let Some(parent) = tree.tiles.parent_of(tile)
+            && let Some(Tile::Container(container)) = tree.tiles.get_mut(parent)
+        {
+            container.remove_child(tile);
+        }
+        tree.tiles.set_visible(tile, true);
+        Some(tile)
+    }
+
+    /// Tab group holding another visible panel assigned to `slot`.
+    fn slot_tab_group(&self, except: &str, slot: PanelSlot) -> Option<TileId> {
+        let tree = self.tree.as_ref()?;
+        self.panel_id_map
+            .iter()
+            .filter(|(id, pid)| id.as_str() != except && self.panel_slots.get(*pid) == Some(&slot))
+            .filter_map(|(id, _)| self.tree_pane_of(id))
+            .filter_map(|tile| tree.tiles.parent_of(tile))
+            .find(|&p| matches!(tree.tiles.get(p), Some(Tile::Container(Container::Tabs(_)))))
+    }
+}
+
+/// Replace `anchor` with a linear split of `anchor` and a new tab group holding `pane`.
+fn split_around(
+    tree: &mut egui_tiles::Tree<PaneEntry>,
+    anchor: TileId,
+    pane: TileId,
+    dir: Direction,
+    share: f32,
+) {
+    let parent = tree.tiles.parent_of(anchor);
+    let group = tree.tiles.insert_tab_tile(vec![pane]);
+    let children = match dir {
+        Direction::Left | Direction::Up => vec![group, anchor],
+        Direction::Right | Direction::Down => vec![anchor, group],
+    };
+    let split = match dir {
+        Direction::Left | Direction::Right => tree.tiles.insert_horizontal_tile(children),
+        Direction::Up | Direction::Down => tree.tiles.insert_vertical_tile(children),
+    };
+    ui::set_linear_shares(
+        &mut tree.tiles,
+        split,
+        &[(group, share), (anchor, 1.0 - share)],
+    );
+    match parent.and_then(|p| tree.tiles.get_mut(p)) {
+        Some(Tile::Container(container)) => replace_child(container, anchor, split),
+        _ => tree.root = Some(split),
+    }
+}
+
+/// Swap `old` for `new` in a container, keeping its position, share and active state.
+fn replace_child(container: &mut Container, old: TileId, new: TileId) {
+    match container {
+        Container::Tabs(tabs) => {
+            if let Some(child) = tabs.children.iter_mut().find(|c| **c == old) {
+                *child = new;
+            }
+            if tabs.active == Some(old) {
+                tabs.active = Some(new);
+            }
+        }
+        Container::Linear(linear) => {
+            let share = linear.shares[old];
+            if let Some(child) = linear.children.iter_mut().find(|c| **c == old) {
+                *child = new;
+            }
+            linear.shares.set_share(new, share);
+        }
+        Container::Grid(_) => {
+            container.remove_child(old);
+            container.add_child(new);
+        }
+    }
+}
+
+/// Apply queued layout operations, recording one undo entry for structural changes.
+pub(super) fn process_layout_ops(world: &mut World) {
+    let mut state = world.resource_mut::<TileLayoutState>();
+    if state.pending_layout_ops.is_empty() || state.tree.is_none() {
+        return;
+    }
+    let before = state.snapshot();
+    let ops = std::mem::take(&mut state.pending_layout_ops);
+    let descs: Vec<String> = ops
+        .into_iter()
+        .filter_map(|op| state.apply_layout_op(op))
+        .collect();
+    let after = state.snapshot();
+    if descs.is_empty() {
+        return;
+    }
+    if let (Some(before), Some(after)) = (before, after) {
+        let action = LayoutUndoAction::new(descs.join(", "), before, after);
+        LayoutUndoStack::record(world, action);
+    }
+}
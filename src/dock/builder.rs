@@ -0,0 +1,295 @@
+//! # builder.rs
+//!
+//! # builder.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Code-first description of the default dock layout. Plugin authors nest rows, columns and
+//! tab groups of panel IDs with relative shares, and `TileLayoutState` turns the description
+//! into an egui_tiles tree whenever no saved layout exists (first run or Reset Layout).
+//! Unknown panel IDs are skipped, and registered default-visible panels the description leaves
+//! out are appended to the root, so a layout written against one set of plugins keeps working
+//! when another is added.
+//!
+//! 以代码描述默认停靠布局。插件作者用面板 ID 嵌套组合行、列和标签组并指定相对占比，
+//! 当不存在已保存的布局（首次运行或重置布局）时，`TileLayoutState` 会把该描述转换为 egui_tiles 树。
+//! 未知的面板 ID 会被跳过，描述中遗漏的已注册且默认可见的面板会追加到根节点，
+//! 因此针对一组插件编写的布局在加入其他插件后仍可使用。
+
+use super::{PaneEntry, TileLayoutState, ui};
+use egui_tiles::{TileId, Tiles};
+
+/// Node of a default layout, built with [`panel`], [`row`], [`column`] and [`tabs`].
+///
+/// ```rust,ignore
+/// use bevy_workbench::dock::{column, panel, row};
+///
+/// app.set_default_layout(row([
+///     panel("hierarchy").share(1.0),
+///     column([panel("workbench_game_view").share(3.0), panel("workbench_console")]).share(4.0),
+///     panel("workbench_inspector").share(1.5),
+/// ]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayoutBuilder {
+    node: LayoutNode,
+    share: Option<f32>,
+}
+
+#[derive(Debug, Clone)]
+enum LayoutNode {
+    Panel(String),
+    Row(Vec<LayoutBuilder>),
+    Column(Vec<LayoutBuilder>),
+    Tabs(Vec<LayoutBuilder>),
+}
+
+impl LayoutBuilder {
+    /// A single panel, shown in its own tab group.
+    pub fn panel(id: impl Into<String>) -> Self {
+        Self::new(LayoutNode::Panel(id.into()))
+    }
+
+    /// Children side by side, left to right.
+    pub fn row(children: impl IntoIterator<Item = LayoutBuilder>) -> Self {
+        Self::new(LayoutNode::Row(children.into_iter().collect()))
+    }
+
+    /// Children stacked, top to bottom.
+    pub fn column(children: impl IntoIterator<Item = LayoutBuilder>) -> Self {
+        Self::new(LayoutNode::Column(children.into_iter().collect()))
+    }
+
+    /// Children as tabs of one group; the first is active.
+    pub fn tabs(children: impl IntoIterator<Item = LayoutBuilder>) -> Self {
+        Self::new(LayoutNode::Tabs(children.into_iter().collect()))
+    }
+
+    /// Relative size of this node in its parent row or column (default `1.0`).
+    pub fn share(mut self, share: f32) -> Self {
+        self.share = Some(share);
+        self
+    }
+
+    fn new(node: LayoutNode) -> Self {
+        Self { node, share: None }
+    }
+
+    /// Insert this node's tiles, returning the top tile (`None` if nothing was placed).
+    fn insert(&self, state: &mut TileLayoutState, tiles: &mut Tiles<PaneEntry>) -> Option<TileId> {
+        match &self.node {
+            LayoutNode::Panel(id) => {
+                let pane = insert_pane(state, tiles, id)?;
+                Some(tiles.insert_tab_tile(vec![pane]))
+            }
+            LayoutNode::Tabs(children) => {
+                let panes: Vec<TileId> = children
+                    .iter()
+                    .filter_map(|child| match &child.node {
+                        LayoutNode::Panel(id) => insert_pane(state, tiles, id),
+                        _ => child.insert(state, tiles),
+                    })
+                    .collect();
+                (!panes.is_empty()).then(|| tiles.insert_tab_tile(panes))
+            }
+            LayoutNode::Row(children) | LayoutNode::Column(children) => {
+                let mut ids = Vec::new();
+                let mut shares = Vec::new();
+                for child in children {
+                    if let Some(tile) = child.insert(state, tiles) {
+                        ids.push(tile);
+                        shares.push((tile, child.share.unwrap_or(1.0)));
+                    }
+                }
+                match ids.len() {
+                    0 => None,
+                    1 => Some(ids[0]),
+                    _ => {
+                        let container = if matches!(self.node, LayoutNode::Row(_)) {
+                            tiles.insert_horizontal_tile(ids)
+                        } else {
+                            tiles.insert_vertical_tile(ids)
+                        };
+                        ui::set_linear_shares(tiles, container, &shares);
+                        Some(container)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Insert a pane for a registered, not yet placed panel.
+fn insert_pane(
+    state: &mut TileLayoutState,
+    tiles: &mut Tiles<PaneEntry>,
+    id: &str,
+) -> Option<TileId> {
+    let &panel_id = state.panel_id_map.get(id)?;
+    if state.panel_tile_map.contains_key(&panel_id) {
+        return None;
+    }
+    let tile_id = tiles.insert_pane(PaneEntry { panel_id });
+    state.panel_tile_map.insert(panel_id, tile_id);
+    Some(tile_id)
+}
+
+/// A single panel; see [`LayoutBuilder::panel`].
+pub fn panel(id: impl Into<String>) -> LayoutBuilder {
+    LayoutBuilder::panel(id)
+}
+
+/// Children side by side; see [`LayoutBuilder::row`].
+pub fn row(children: impl IntoIterator<Item = LayoutBuilder>) -> LayoutBuilder {
+    LayoutBuilder::row(children)
+}
+
+/// Children stacked; see [`LayoutBuilder::column`].
+pub fn column(children: impl IntoIterator<Item = LayoutBuilder>) -> LayoutBuilder {
+    LayoutBuilder::column(children)
+}
+
+/// Children as tabs; see [`LayoutBuilder::tabs`].
+pub fn tabs(children: impl IntoIterator<Item = LayoutBuilder>) -> LayoutBuilder {
+    LayoutBuilder::tabs(children)
+}
+
+impl TileLayoutState {
+    /// Use `layout` instead of the slot heuristic whenever the tree is built without a saved
+    /// layout (first run or Reset Layout).
+    pub fn set_default_layout(&mut self, layout: LayoutBuilder) {
+        self.default_layout = Some(layout);
+    }
+
+    /// Build the tree from the default layout description. Returns `false` if none is set.
+    pub(super) fn build_tree_from_layout(&mut self) -> bool {
+        let Some(layout) = self.default_layout.clone() else {
+            return false;
+        };
+        self.build_tree_from(&layout);
+        true
+    }
+
+    /// Replace the tree with one built from `layout`, appending unmentioned default-visible
+    /// panels. Expects `panel_tile_map` to be empty.
+    pub(super) fn build_tree_from(&mut self, layout: &LayoutBuilder) {
+        let mut tiles = Tiles::default();
+        let root = layout.insert(self, &mut tiles);
+        let mut tree = match root {
+            Some(root) => egui_tiles::Tree::new("workbench", root, tiles),
+            None => egui_tiles::Tree::empty("workbench"),
+        };
+
+        // Default-visible panels the description doesn't mention, sorted for a stable order
+        let mut rest: Vec<(String, super::PanelId)> = self
+            .panel_id_map
+            .iter()
+            .filter(|(s, pid)| {
+                !self.panel_tile_map.contains_key(pid)
+                    && self.panels[pid].default_visible()
+                    && !self.default_hidden.contains(s.as_str())
+            })
+            .map(|(s, pid)| (s.clone(), *pid))
+            .collect();
+        rest.sort();
+        for (_, panel_id) in rest {
+            let tile_id = tree.tiles.insert_pane(PaneEntry { panel_id });
+            self.panel_tile_map.insert(panel_id, tile_id);
+            ui::insert_pane_into_tree(&mut tree, tile_id);
+        }
+
+        self.tree = tree.root().is_some().then_some(tree);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dock::WorkbenchPanel;
+    use egui_tiles::{Container, LinearDir, Tile};
+
+    struct TestPanel(&'static str);
+
+    impl WorkbenchPanel for TestPanel {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn title(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn ui(&mut self, _ui: &mut egui::Ui) {}
+    }
+
+    fn build(ids: &[&'static str], hidden: &[&str], layout: LayoutBuilder) -> TileLayoutState {
+        let mut state = TileLayoutState::default();
+        for &id in ids {
+            state.add_panel(Box::new(TestPanel(id)));
+        }
+        for &id in hidden {
+            state.set_default_hidden(id);
+        }
+        state.set_default_layout(layout);
+        state.build_tree(None);
+        state
+    }
+
+    fn pane_tile(state: &TileLayoutState, id: &str) -> TileId {
+        state.panel_tile_map[&state.panel_id_map[id]]
+    }
+
+    #[test]
+    fn row_with_shares_and_appended_panels() {
+        let state = build(
+            &["a", "b", "c", "d"],
+            &[],
+            row([
+                panel("a").share(2.0),
+                column([panel("b"), panel("missing")]),
+                tabs([panel("c")]),
+            ]),
+        );
+        let tree = state.tree.as_ref().unwrap();
+        let Some(Tile::Container(Container::Linear(root))) = tree.tiles.get(tree.root().unwrap())
+        else {
+            panic!("root should be a linear container");
+        };
+        assert_eq!(root.dir, LinearDir::Horizontal);
+        // a, b (the column collapses to its only placed child), c, then the unmentioned d.
+        assert_eq!(root.children.len(), 4);
+        let a_tabs = tree.tiles.parent_of(pane_tile(&state, "a")).unwrap();
+        assert_eq!(root.children[0], a_tabs);
+        assert_eq!(root.shares[a_tabs], 2.0);
+        assert_eq!(root.children[3], pane_tile(&state, "d"));
+        for id in ["a", "b", "c", "d"] {
+            assert!(state.is_panel_visible(id), "{id} should be placed");
+        }
+    }
+
+    #[test]
+    fn tabs_activate_first_and_skip_hidden() {
+        let state = build(
+            &["a", "b", "c"],
+            &["c"],
+            tabs([panel("b"), panel("a"), panel("b")]),
+        );
+        let tree = state.tree.as_ref().unwrap();
+        let Some(Tile::Container(Container::Tabs(root))) = tree.tiles.get(tree.root().unwrap())
+        else {
+            panic!("root should be a tab container");
+        };
+        // The duplicate "b" is placed once.
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.active, Some(pane_tile(&state, "b")));
+        assert!(!state.is_panel_visible("c"));
+    }
+
+    #[test]
+    fn unknown_panels_only_leave_an_empty_tree() {
+        let state = build(&[], &[], row([panel("missing"), tabs([])]));
+        assert!(state.tree.is_none());
+    }
+}
@@ -0,0 +1,145 @@
+//! # focus.rs
+//!
+//! # focus.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Keyboard focus between dock panels. Tracks the focused panel in the `FocusedPanel` resource
+//! (set by clicking into a pane or by shortcuts), and handles the configurable next/previous
+//! panel cycling and direct "focus panel N" bindings.
+//!
+//! 停靠面板之间的键盘焦点。在 `FocusedPanel` 资源中记录当前获得焦点的面板（通过点击面板或快捷键设置），
+//! 并处理可配置的上一个/下一个面板循环切换以及"聚焦第 N 个面板"的快捷键。
+
+use super::{PaneEntry, PanelId, TileLayoutState, WorkbenchPanel};
+use crate::keybind::{KeyBindings, KeyInput};
+use bevy::prelude::*;
+use egui_tiles::{Tile, TileId, Tiles};
+
+/// The panel that currently has keyboard focus (last clicked or cycled to).
+///
+/// Panels can query this to decide whether to consume keyboard shortcuts.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FocusedPanel(pub Option<String>);
+
+impl FocusedPanel {
+    /// Whether the panel with the given string ID has focus.
+    pub fn is(&self, panel_str_id: &str) -> bool {
+        self.0.as_deref() == Some(panel_str_id)
+    }
+}
+
+/// Visible panes in tree order (left-to-right, top-to-bottom, tab order).
+fn pane_order(tree: &egui_tiles::Tree<PaneEntry>) -> Vec<PanelId> {
+    let mut order = Vec::new();
+    if let Some(root) = tree.root() {
+        collect_panes(&tree.tiles, root, &mut order);
+    }
+    order
+}
+
+fn collect_panes(tiles: &Tiles<PaneEntry>, tile_id: TileId, out: &mut Vec<PanelId>) {
+    if !tiles.is_visible(tile_id) {
+        return;
+    }
+    match tiles.get(tile_id) {
+        Some(Tile::Pane(pane)) => out.push(pane.panel_id),
+        Some(Tile::Container(container)) => {
+            for child in container.children_vec() {
+                collect_panes(tiles, child, out);
+            }
+        }
+        None => {}
+    }
+}
+
+/// Handles panel cycling / direct focus shortcuts.
+pub(super) fn process_focus_keys(world: &mut World) {
+    let bindings = world
+        .get_resource::<KeyBindings>()
+        .cloned()
+        .unwrap_or_default();
+    let Some(input) = KeyInput::from_world(world) else {
+        return;
+    };
+    let step: Option<isize> = if bindings.next_panel.just_pressed(input) {
+        Some(1)
+    } else if bindings.prev_panel.just_pressed(input) {
+        Some(-1)
+    } else {
+        None
+    };
+    let direct = bindings
+        .focus_panel
+        .iter()
+        .position(|slot| slot.just_pressed(input));
+    if step.is_none() && direct.is_none() {
+        return;
+    }
+
+    let focused = world.resource::<FocusedPanel>().0.clone();
+    let mut state = world.resource_mut::<TileLayoutState>();
+    let Some(tree) = &state.tree else {
+        return;
+    };
+    let order = pane_order(tree);
+    if order.is_empty() {
+        return;
+    }
+    let current = focused
+        .and_then(|s| state.panel_id_map.get(&s).copied())
+        .and_then(|pid| order.iter().position(|&p| p == pid));
+    let target = match (direct, step) {
+        (Some(index), _) => order.get(index).copied(),
+        (None, Some(step)) => {
+            let len = order.len() as isize;
+            let from = current.map_or(if step > 0 { -1 } else { 0 }, |c| c as isize);
+            Some(order[(from + step).rem_euclid(len) as usize])
+        }
+        (None, None) => None,
+    };
+    let Some(target) = target else {
+        return;
+    };
+
+    if let Some(tree) = &mut state.tree {
+        tree.make_active(|_, tile| matches!(tile, Tile::Pane(p) if p.panel_id == target));
+    }
+    state.focus_entered = Some(target);
+    let str_id = state.panels.get(&target).map(|p| p.id().to_string());
+    world.resource_mut::<FocusedPanel>().0 = str_id;
+}
+
+/// Widget ID of a panel's focus anchor (focused while the panel itself, not one of its
+/// widgets, has keyboard focus).
+pub(crate) fn pane_focus_id(panel: &str) -> egui::Id {
+    egui::Id::new(("workbench_pane_focus", panel))
+}
+
+/// Invisible Tab stop covering a pane, registered before the panel's widgets so that Tab from
+/// it enters the panel. Takes keyboard focus when `enter` (the panel was reached by shortcut).
+/// Screen readers announce it as a panel named by the panel title.
+pub(super) fn pane_focus_anchor(
+    ui: &mut egui::Ui,
+    rect: egui::Rect,
+    panel: &dyn WorkbenchPanel,
+    enter: bool,
+) {
+    let id = pane_focus_id(panel.id());
+    let response = ui.interact(rect, id, egui::Sense::focusable_noninteractive());
+    response
+        .widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Panel, true, panel.title()));
+    if enter {
+        response.request_focus();
+    }
+}
+
+/// Whether the widget with keyboard focus lies inside `rect` on this layer.
+pub(super) fn has_keyboard_focus(ui: &egui::Ui, rect: egui::Rect) -> bool {
+    let ctx = ui.ctx();
+    ctx.memory(|m| m.focused())
+        .and_then(|id| ctx.read_response(id))
+        .is_some_and(|r| r.layer_id == ui.layer_id() && rect.contains_rect(r.rect))
+}
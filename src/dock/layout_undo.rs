@@ -0,0 +1,88 @@
+//! # layout_undo.rs
+//!
+//! # layout_undo.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Undo support for dock layout edits. A `LayoutSnapshot` captures the tile tree, the
+//! panel-to-tile mapping and the secondary windows; a `LayoutUndoAction` holds the snapshots
+//! before and after a layout change (closing a tab, opening a panel, resetting the layout) and
+//! restores one of them on undo or redo.
+//!
+//! 停靠布局编辑的撤销支持。`LayoutSnapshot` 记录 tile 树、面板到 tile 的映射以及副窗口；
+//! `LayoutUndoAction` 保存一次布局变更（关闭标签页、打开面板、重置布局）前后的快照，并在撤销或
+//! 重做时恢复其中之一。
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::window::DockWindow;
+use super::{PaneEntry, PanelId, TileLayoutState};
+use crate::undo::LayoutUndoStack;
+
+/// Snapshot of the layout for undo/redo (tree + tile mapping + secondary windows).
+#[derive(Clone)]
+pub(crate) struct LayoutSnapshot {
+    pub tree: egui_tiles::Tree<PaneEntry>,
+    pub panel_tile_map: HashMap<PanelId, egui_tiles::TileId>,
+    /// Secondary windows with their trees.
+    pub windows: Vec<DockWindow>,
+}
+
+/// Undo action that restores a layout snapshot.
+/// Uses Mutex for interior mutability since UndoAction takes &self.
+pub(crate) struct LayoutUndoAction {
+    before: Mutex<LayoutSnapshot>,
+    after: Mutex<LayoutSnapshot>,
+    desc: String,
+}
+
+impl LayoutUndoAction {
+    pub fn new(desc: impl Into<String>, before: LayoutSnapshot, after: LayoutSnapshot) -> Self {
+        Self {
+            before: Mutex::new(before),
+            after: Mutex::new(after),
+            desc: desc.into(),
+        }
+    }
+}
+
+/// Apply `change` to the layout and, if it reports a change, record it as an undo step.
+pub(crate) fn record_layout_change(
+    world: &mut World,
+    desc: impl Into<String>,
+    change: impl FnOnce(&mut TileLayoutState) -> bool,
+) {
+    let mut state = world.resource_mut::<TileLayoutState>();
+    let before = state.snapshot();
+    if !change(&mut state) {
+        return;
+    }
+    let after = state.snapshot();
+    if let (Some(before), Some(after)) = (before, after) {
+        LayoutUndoStack::record(world, LayoutUndoAction::new(desc, before, after));
+    }
+}
+
+impl crate::undo::UndoAction for LayoutUndoAction {
+    fn undo(&self, world: &mut World) {
+        let snapshot = self.before.lock().unwrap().clone();
+        world
+            .resource_mut::<TileLayoutState>()
+            .restore_snapshot(snapshot);
+    }
+
+    fn redo(&self, world: &mut World) {
+        let snapshot = self.after.lock().unwrap().clone();
+        world
+            .resource_mut::<TileLayoutState>()
+            .restore_snapshot(snapshot);
+    }
+
+    fn description(&self) -> &str {
+        &self.desc
+    }
+}
@@ -0,0 +1,118 @@
+//! # maximize.rs
+//!
+//! # maximize.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Temporarily maximizes a single pane to fill the whole dock area. The tile tree itself is left
+//! untouched while a pane is maximized, so restoring brings back the exact previous layout and
+//! the transient state never ends up in a saved layout file.
+//!
+//! 临时将单个面板最大化以填满整个停靠区域。最大化期间 tile 树本身保持不变，
+//! 因此还原时会恢复到之前完全相同的布局，而这一临时状态也不会被写入保存的布局文件。
+
+use super::{PaneEntry, PanelId, TileLayoutState};
+use crate::bench_ui::{Icon, icon};
+use crate::keybind::{KeyBindings, KeyInput};
+use bevy::prelude::*;
+use egui_tiles::{Behavior, Tile, TileId};
+
+impl TileLayoutState {
+    /// Maximize a panel, or restore the layout if it is already maximized.
+    pub fn toggle_maximize(&mut self, panel_str_id: &str) {
+        let Some(&panel_id) = self.panel_id_map.get(panel_str_id) else {
+            return;
+        };
+        self.maximized = if self.maximized == Some(panel_id) {
+            None
+        } else {
+            Some(panel_id)
+        };
+    }
+
+    /// Restore the normal layout if a panel is maximized.
+    pub fn restore_maximized(&mut self) {
+        self.maximized = None;
+    }
+
+    /// Hide the restore header of the maximized panel (used by presentation mode).
+    pub fn set_chromeless(&mut self, chromeless: bool) {
+        self.chromeless = chromeless;
+    }
+
+    /// String ID of the maximized panel, if any.
+    pub fn maximized_panel(&self) -> Option<&str> {
+        let panel_id = self.maximized?;
+        self.panels.get(&panel_id).map(|p| p.id())
+    }
+}
+
+/// Tile showing the given panel, if it is in the tree.
+pub(super) fn pane_tile(tree: &egui_tiles::Tree<PaneEntry>, panel_id: PanelId) -> Option<TileId> {
+    tree.tiles.iter().find_map(|(&tile_id, tile)| match tile {
+        Tile::Pane(pane) if pane.panel_id == panel_id => Some(tile_id),
+        _ => None,
+    })
+}
+
+/// Draw a maximized pane with a small restore header (omitted when `chromeless`).
+/// Returns true when the user asked to restore the layout.
+pub(super) fn maximized_ui<B: Behavior<PaneEntry>>(
+    ui: &mut egui::Ui,
+    behavior: &mut B,
+    tree: &mut egui_tiles::Tree<PaneEntry>,
+    tile_id: TileId,
+    chromeless: bool,
+    restore_hint: &str,
+) -> bool {
+    let Some(Tile::Pane(pane)) = tree.tiles.get_mut(tile_id) else {
+        return true;
+    };
+    if chromeless {
+        behavior.pane_ui(ui, tile_id, pane);
+        return false;
+    }
+    let title = behavior.tab_title_for_pane(pane);
+    let mut restore = false;
+    ui.horizontal(|ui| {
+        let header = ui.add(egui::Label::new(title).sense(egui::Sense::click()));
+        restore |= header.double_clicked();
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            restore |= ui
+                .small_button(icon(Icon::RestoreLayout))
+                .on_hover_text(restore_hint)
+                .clicked();
+        });
+    });
+    ui.separator();
+    behavior.pane_ui(ui, tile_id, pane);
+    restore
+}
+
+/// Toggles maximize on the focused panel via the `maximize_panel` binding.
+pub(super) fn process_maximize_key(world: &mut World) {
+    let pressed = {
+        let Some(input) = KeyInput::from_world(world) else {
+            return;
+        };
+        world
+            .get_resource::<KeyBindings>()
+            .cloned()
+            .unwrap_or_default()
+            .maximize_panel
+            .just_pressed(input)
+    };
+    if !pressed {
+        return;
+    }
+    let focused = world
+        .get_resource::<super::FocusedPanel>()
+        .and_then(|f| f.0.clone());
+    let mut state = world.resource_mut::<TileLayoutState>();
+    match focused {
+        Some(panel) => state.toggle_maximize(&panel),
+        None => state.restore_maximized(),
+    }
+}
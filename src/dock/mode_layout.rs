@@ -0,0 +1,170 @@
+//! # mode_layout.rs
+//!
+//! # mode_layout.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Implements `ModeController::per_mode_layouts`: a separate dock arrangement for each
+//! `EditorMode`. Leaving a mode stores the current tree as that mode's layout (Play and Pause
+//! layouts are also written next to the layout file); entering a mode restores its stored
+//! layout, or builds the `LayoutBuilder` registered for it. Pause shares Play's layout unless
+//! it has its own, and a mode with no layout at all keeps the current tree unchanged.
+//! Independently of layouts, tabs of panels whose `WorkbenchPanel::visible_in` rejects the
+//! new mode are hidden on each transition and shown again once a mode accepts them.
+//!
+//! 实现 `ModeController::per_mode_layouts`：为每个 `EditorMode` 使用独立的停靠布局。
+//! 离开某个模式时把当前 tile 树保存为该模式的布局（Play 和 Pause 的布局还会写入布局文件旁边）；
+//! 进入某个模式时恢复其已保存的布局，或根据为其注册的 `LayoutBuilder` 构建布局。
+//! Pause 在没有自己的布局时与 Play 共用布局，没有任何布局的模式则保持当前 tile 树不变。
+//! 此外，每次模式切换时，`WorkbenchPanel::visible_in` 不接受新模式的面板标签会被隐藏，
+//! 直到某个接受它的模式再次显示。
+
+use super::{LayoutBuilder, LayoutPath, PanelId, TileLayoutState};
+use crate::mode::{EditorMode, ModeController};
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Per-mode layouts and the mode the current tree belongs to.
+#[derive(Default)]
+pub(crate) struct ModeLayouts {
+    /// Layout JSON stored when leaving each mode (or read from disk).
+    saved: HashMap<EditorMode, String>,
+    /// Code-defined layouts used when a mode has nothing saved.
+    defaults: HashMap<EditorMode, LayoutBuilder>,
+    /// Mode whose layout the tree currently shows.
+    current: EditorMode,
+    /// Whether the last switch replaced the tree.
+    applied: bool,
+    /// Mode `visible_in` was last applied for.
+    visibility_mode: Option<EditorMode>,
+    /// Panels hidden because `visible_in` rejected the mode.
+    hidden: HashSet<PanelId>,
+}
+
+impl TileLayoutState {
+    /// Layout used for `mode` when per-mode layouts are enabled and none was saved yet.
+    pub fn set_mode_layout(&mut self, mode: EditorMode, layout: LayoutBuilder) {
+        self.mode_layouts.defaults.insert(mode, layout);
+    }
+
+    /// Whether entering the current mode replaced the dock tree with a per-mode layout.
+    pub fn mode_layout_applied(&self) -> bool {
+        self.mode_layouts.applied
+    }
+
+    /// Store the tree for the current mode and switch to the layout of `to`.
+    pub(crate) fn switch_mode_layout(&mut self, to: EditorMode, layout_path: &Path) {
+        let from = self.mode_layout_key(self.mode_layouts.current, layout_path);
+        let target = self.mode_layout_key(to, layout_path);
+        self.mode_layouts.current = to;
+        if from == target {
+            return;
+        }
+        self.mode_layouts.applied = false;
+        let Some(json) = self.layout_json() else {
+            return;
+        };
+        if from != EditorMode::Edit
+            && let Err(e) = crate::storage::write_string(&mode_file(layout_path, from), &json)
+        {
+            warn!("Failed to save {from:?} layout: {e}");
+        }
+        self.mode_layouts.saved.insert(from, json);
+
+        if let Some(json) = self.mode_layouts.saved.get(&target).cloned() {
+            if !self.load_layout_json(&json, &format!("{target:?} layout")) {
+                return;
+            }
+        } else if let Some(layout) = self.mode_layouts.defaults.get(&target).cloned() {
+            self.panel_tile_map.clear();
+            self.build_tree_from(&layout);
+        } else {
+            return;
+        }
+        // The new tree replaces whatever Play had hidden or maximized.
+        self.mode_layouts.applied = true;
+        self.play_layout = None;
+        self.maximized = None;
+    }
+
+    /// Hide or restore tabs according to [`WorkbenchPanel::visible_in`](super::WorkbenchPanel::visible_in)
+    /// when the mode changed since the last call. Tabs opened by hand stay until the next change.
+    pub(crate) fn apply_mode_visibility(&mut self, mode: EditorMode) {
+        if self.mode_layouts.visibility_mode == Some(mode) {
+            return;
+        }
+        let Some(tree) = &mut self.tree else {
+            return;
+        };
+        self.mode_layouts.visibility_mode = Some(mode);
+        for (&panel_id, panel) in &self.panels {
+            let Some(&tile_id) = self.panel_tile_map.get(&panel_id) else {
+                continue;
+            };
+            if !panel.visible_in(mode) {
+                if tree.tiles.is_visible(tile_id) {
+                    tree.tiles.set_visible(tile_id, false);
+                    self.mode_layouts.hidden.insert(panel_id);
+                }
+            } else if self.mode_layouts.hidden.remove(&panel_id) {
+                tree.tiles.set_visible(tile_id, true);
+            }
+        }
+    }
+
+    /// Forget which tabs [`Self::apply_mode_visibility`] hid, for a freshly loaded or reset
+    /// tree; visibility is applied to it again on the next frame.
+    pub(crate) fn reset_mode_visibility(&mut self) {
+        self.mode_layouts.hidden.clear();
+        self.mode_layouts.visibility_mode = None;
+    }
+
+    /// The mode whose layout `mode` uses: Pause falls back to Play's unless it has its own.
+    fn mode_layout_key(&mut self, mode: EditorMode, layout_path: &Path) -> EditorMode {
+        if mode == EditorMode::Pause && !self.has_mode_layout(EditorMode::Pause, layout_path) {
+            EditorMode::Play
+        } else {
+            mode
+        }
+    }
+
+    /// Whether `mode` has a saved (in memory or on disk) or code-defined layout.
+    fn has_mode_layout(&mut self, mode: EditorMode, layout_path: &Path) -> bool {
+        if !self.mode_layouts.saved.contains_key(&mode)
+            && mode != EditorMode::Edit
+            && let Some(json) = crate::storage::read_string(&mode_file(layout_path, mode))
+        {
+            self.mode_layouts.saved.insert(mode, json);
+        }
+        self.mode_layouts.saved.contains_key(&mode)
+            || self.mode_layouts.defaults.contains_key(&mode)
+    }
+}
+
+/// `layout.json` → `layout.play.json` / `layout.pause.json`.
+fn mode_file(layout_path: &Path, mode: EditorMode) -> PathBuf {
+    let stem = layout_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "layout".into());
+    let mode = format!("{mode:?}").to_lowercase();
+    layout_path.with_file_name(format!("{stem}.{mode}.json"))
+}
+
+/// On entering any mode: switch to that mode's layout if enabled.
+pub fn mode_layout_system(
+    mode: Res<State<EditorMode>>,
+    controller: Res<ModeController>,
+    layout_path: Res<LayoutPath>,
+    mut state: ResMut<TileLayoutState>,
+) {
+    if controller.per_mode_layouts {
+        state.switch_mode_layout(*mode.get(), &layout_path.0);
+    } else {
+        state.mode_layouts.current = *mode.get();
+        state.mode_layouts.applied = false;
+    }
+}
@@ -8,40 +8,90 @@
 //!
 //! Serializes and restores the dock layout used by `bevy_workbench`. It translates the
 //! in-memory tile tree into a stable on-disk snapshot and remaps saved panel ids back onto the
-//! current session's panel registry when a layout is loaded.
+//! current session's panel registry when a layout is loaded. Snapshots carry a schema version;
+//! older ones are migrated step by step (`migrate_v1_to_v2`) before they are deserialized.
 //!
 //! 负责序列化和恢复 `bevy_workbench` 使用的停靠布局。它会把内存中的 tile 树转换成
 //! 稳定的磁盘快照，并在加载布局时把保存下来的 panel id 重新映射回当前会话的 panel 注册表。
+//! 快照带有结构版本号；较旧的快照会在反序列化之前逐步迁移（`migrate_v1_to_v2`）。
 
 use super::{PaneEntry, PanelId, TileLayoutState};
 use bevy::prelude::*;
 use std::collections::HashMap;
 
+/// Current version of the layout snapshot schema.
+pub const LAYOUT_VERSION: u32 = 2;
+
 /// Serializable snapshot of the dock layout.
 #[derive(serde::Serialize, serde::Deserialize)]
 struct LayoutData {
+    /// Schema version (see [`LAYOUT_VERSION`]).
+    version: u32,
     tree: egui_tiles::Tree<PaneEntry>,
     panel_names: HashMap<PanelId, String>,
+    /// Secondary windows.
+    windows: Vec<super::window::DockWindow>,
+    /// Panel IDs with locked tabs.
+    #[serde(default)]
+    locked: Vec<String>,
+}
+
+/// Bring a parsed layout snapshot up to [`LAYOUT_VERSION`].
+fn migrate_layout(data: &mut serde_json::Value) {
+    let version = data
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(1);
+    if version > u64::from(LAYOUT_VERSION) {
+        warn!("Layout was written by a newer version (schema {version}); loading what we can");
+        return;
+    }
+    if version < 2 {
+        migrate_v1_to_v2(data);
+    }
+}
+
+/// Version 1 snapshots have no `version` key and, when saved before secondary windows
+/// existed, no `windows` list either.
+fn migrate_v1_to_v2(data: &mut serde_json::Value) {
+    let Some(object) = data.as_object_mut() else {
+        return;
+    };
+    object
+        .entry("windows")
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+    object.insert("version".into(), 2.into());
 }
 
 impl TileLayoutState {
-    /// Save the current layout to a file (JSON format).
-    pub fn save_layout(&self, path: &std::path::Path) {
-        let Some(tree) = &self.tree else { return };
+    /// Serialize the current layout to JSON.
+    pub fn layout_json(&self) -> Option<String> {
+        let tree = self.tree.as_ref()?;
         let id_to_str: HashMap<PanelId, String> = self
             .panel_id_map
             .iter()
             .map(|(s, &id)| (id, s.clone()))
             .collect();
         let data = LayoutData {
+            version: LAYOUT_VERSION,
             tree: tree.clone(),
             panel_names: id_to_str,
+            windows: self.windows.clone(),
+            locked: {
+                let mut locked: Vec<String> = self.locked_tabs.iter().cloned().collect();
+                locked.sort();
+                locked
+            },
         };
-        let content = serde_json::to_string_pretty(&data).expect("serialize layout");
-        if let Some(parent) = path.parent() {
-            let _ = std::fs::create_dir_all(parent);
-        }
-        if let Err(e) = std::fs::write(path, content) {
+        Some(serde_json::to_string_pretty(&data).expect("serialize layout"))
+    }
+
+    /// Save the current layout to a file (JSON format).
+    pub fn save_layout(&self, path: &std::path::Path) {
+        let Some(content) = self.layout_json() else {
+            return;
+        };
+        if let Err(e) = crate::storage::write_string(path, &content) {
             warn!("Failed to save layout to {}: {e}", path.display());
         }
     }
@@ -49,14 +99,26 @@ impl TileLayoutState {
     /// Load layout from a JSON file. Returns true if successful.
     /// Must be called after all panels are registered but before the tree is built.
     pub fn load_layout(&mut self, path: &std::path::Path) -> bool {
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return false,
+        let Some(content) = crate::storage::read_string(path) else {
+            return false;
         };
-        let data: LayoutData = match serde_json::from_str(&content) {
+        let loaded = self.load_layout_json(&content, &path.display().to_string());
+        if !loaded {
+            crate::storage::back_up(path, &content);
+        }
+        loaded
+    }
+
+    /// Load layout from JSON text; `source` names it in warnings. Returns true if successful.
+    pub fn load_layout_json(&mut self, content: &str, source: &str) -> bool {
+        let data = serde_json::from_str(content).and_then(|mut value| {
+            migrate_layout(&mut value);
+            serde_json::from_value::<LayoutData>(value)
+        });
+        let data = match data {
             Ok(d) => d,
             Err(e) => {
-                warn!("Failed to parse layout {}: {e}", path.display());
+                warn!("Failed to parse layout {source}: {e}");
                 return false;
             }
         };
@@ -74,7 +136,9 @@ impl TileLayoutState {
         }
 
         let mut tree = data.tree;
-        for tile in tree.tiles.tiles_mut() {
+        let mut windows = data.windows;
+        let trees = std::iter::once(&mut tree).chain(windows.iter_mut().map(|w| &mut w.tree));
+        for tile in trees.flat_map(|t| t.tiles.tiles_mut()) {
             if let egui_tiles::Tile::Pane(pane) = tile
                 && let Some(&new_id) = id_remap.get(&pane.panel_id)
             {
@@ -90,7 +154,83 @@ impl TileLayoutState {
         }
 
         self.tree = Some(tree);
+        self.windows = windows;
+        self.locked_tabs = data.locked.into_iter().collect();
+        self.reset_mode_visibility();
         self.tree_built = true;
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dock::WorkbenchPanel;
+
+    struct TestPanel(&'static str);
+
+    impl WorkbenchPanel for TestPanel {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn title(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn ui(&mut self, _ui: &mut egui::Ui) {}
+    }
+
+    fn state(ids: &[&'static str]) -> TileLayoutState {
+        let mut state = TileLayoutState::default();
+        for &id in ids {
+            state.add_panel(Box::new(TestPanel(id)));
+        }
+        state.build_tree(None);
+        state
+    }
+
+    #[test]
+    fn v1_snapshots_gain_version_and_windows() {
+        let mut data = serde_json::json!({ "tree": {}, "panel_names": {} });
+        migrate_layout(&mut data);
+        assert_eq!(data["version"], LAYOUT_VERSION);
+        assert_eq!(data["windows"], serde_json::json!([]));
+
+        let mut data = serde_json::json!({ "windows": [1] });
+        migrate_layout(&mut data);
+        assert_eq!(data["windows"], serde_json::json!([1]));
+    }
+
+    #[test]
+    fn newer_snapshots_are_left_alone() {
+        let mut data = serde_json::json!({ "version": 99 });
+        migrate_layout(&mut data);
+        assert_eq!(data, serde_json::json!({ "version": 99 }));
+    }
+
+    #[test]
+    fn layouts_round_trip_across_registration_order() {
+        let json = state(&["a", "b", "c"]).layout_json().unwrap();
+        // Panel IDs are remapped by name when panels register in another order.
+        let mut loaded = state(&["c", "a", "b"]);
+        assert!(loaded.load_layout_json(&json, "test"));
+        for id in ["a", "b", "c"] {
+            assert!(loaded.is_panel_visible(id), "{id} should be placed");
+        }
+    }
+
+    #[test]
+    fn v1_layouts_still_load() {
+        let json = state(&["a", "b"]).layout_json().unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("version");
+        object.remove("windows");
+        object.remove("locked");
+        let mut loaded = state(&["a", "b"]);
+        assert!(loaded.load_layout_json(&value.to_string(), "v1"));
+        assert!(loaded.is_panel_visible("a") && loaded.is_panel_visible("b"));
+        assert!(!loaded.load_layout_json("{ not json", "broken"));
+    }
+}
@@ -0,0 +1,112 @@
+//! # play_layout.rs
+//!
+//! # play_layout.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Implements `ModeController::hide_panels_on_play`. Entering Play backs up the current layout
+//! and either maximizes the game view or, if there is none, hides every non-center panel.
+//! Stopping restores the backup, so any edits to the dock made during Play are discarded.
+//!
+//! 实现 `ModeController::hide_panels_on_play`。进入 Play 时备份当前布局，
+//! 并最大化游戏视图；若没有游戏视图，则隐藏所有非中央区域的面板。
+//! 停止时恢复该备份，因此 Play 期间对停靠布局所做的修改都会被丢弃。
+
+use super::{LayoutSnapshot, PanelId, PanelSlot, TileLayoutState};
+use crate::mode::ModeController;
+use bevy::prelude::*;
+use egui_tiles::Tile;
+
+/// Panel maximized during Play when present.
+const GAME_VIEW_PANEL: &str = "workbench_game_view";
+
+/// Layout saved when panels were hidden for Play.
+pub(crate) struct PlayLayoutBackup {
+    snapshot: LayoutSnapshot,
+    maximized: Option<PanelId>,
+}
+
+impl TileLayoutState {
+    /// Whether the editor panels are currently hidden for Play.
+    pub fn play_layout_active(&self) -> bool {
+        self.play_layout.is_some()
+    }
+
+    /// Back up the layout and collapse it to the game view.
+    pub(crate) fn enter_play_layout(&mut self) {
+        if self.play_layout.is_some() {
+            return;
+        }
+        let Some(snapshot) = self.snapshot() else {
+            return;
+        };
+        self.play_layout = Some(PlayLayoutBackup {
+            snapshot,
+            maximized: self.maximized,
+        });
+        if let Some(&game_view) = self.panel_id_map.get(GAME_VIEW_PANEL)
+            && self.tree_contains(game_view)
+        {
+            self.maximized = Some(game_view);
+            return;
+        }
+        self.hide_side_panes();
+    }
+
+    /// Restore the layout backed up by [`enter_play_layout`](Self::enter_play_layout).
+    pub(crate) fn exit_play_layout(&mut self) {
+        let Some(backup) = self.play_layout.take() else {
+            return;
+        };
+        self.restore_snapshot(backup.snapshot);
+        self.maximized = backup.maximized;
+    }
+
+    fn tree_contains(&self, panel_id: PanelId) -> bool {
+        let Some(tree) = &self.tree else {
+            return false;
+        };
+        self.panel_tile_map
+            .get(&panel_id)
+            .is_some_and(|&tile| tree.tiles.get(tile).is_some())
+    }
+
+    /// Hide every pane not assigned to the center slot.
+    fn hide_side_panes(&mut self) {
+        let Some(tree) = &mut self.tree else {
+            return;
+        };
+        let side_tiles: Vec<_> = tree
+            .tiles
+            .iter()
+            .filter_map(|(&tile_id, tile)| match tile {
+                Tile::Pane(pane)
+                    if self.panel_slots.get(&pane.panel_id) != Some(&PanelSlot::Center) =>
+                {
+                    Some(tile_id)
+                }
+                _ => None,
+            })
+            .collect();
+        for tile_id in side_tiles {
+            tree.tiles.set_visible(tile_id, false);
+        }
+    }
+}
+
+/// On fresh Play: hide the editor panels if enabled and no per-mode Play layout was applied.
+pub fn hide_panels_on_play_system(
+    controller: Res<ModeController>,
+    mut state: ResMut<TileLayoutState>,
+) {
+    if controller.hide_panels_on_play && !state.mode_layout_applied() {
+        state.enter_play_layout();
+    }
+}
+
+/// On Stop: restore the layout from before Play.
+pub fn restore_panels_on_stop_system(mut state: ResMut<TileLayoutState>) {
+    state.exit_play_layout();
+}
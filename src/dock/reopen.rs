@@ -0,0 +1,228 @@
+//! # reopen.rs
+//!
+//! # reopen.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Reopening recently closed panels. Every tab closed in the main dock tree is remembered with
+//! the container it sat in and its position there; the `reopen_panel` binding (Ctrl+Shift+T by
+//! default) or the Window menu brings back the most recent one at that spot, falling back to the
+//! root when the container is gone. The history is separate from the undo stack, so it still
+//! works after later edits have been undone or pushed past; undoing a reopen puts the panel
+//! back into the history.
+//!
+//! 重新打开最近关闭的面板。主停靠树中每个被关闭的标签页都会连同其所在容器及位置一起记录；
+//! `reopen_panel` 快捷键（默认 Ctrl+Shift+T）或窗口菜单会把最近关闭的面板放回原处，若容器已不存在
+//! 则放到根节点。该历史独立于撤销栈，因此在之后的编辑被撤销或覆盖后依然可用；撤销一次重新打开会
+//! 把该面板放回历史中。
+
+use super::{LayoutUndoAction, PaneEntry, TileLayoutState, ui};
+use crate::keybind::{KeyBindings, KeyInput};
+use crate::undo::{LayoutUndoStack, UndoAction};
+use bevy::prelude::*;
+use egui_tiles::{Tile, TileId};
+
+/// How many closed panels are remembered.
+const CLOSED_HISTORY: usize = 20;
+
+/// A panel closed in the main tree and where its tab was.
+#[derive(Debug, Clone)]
+pub(crate) struct ClosedPanel {
+    panel: String,
+    /// Container holding the tab when it was closed.
+    parent: Option<TileId>,
+    /// Position of the tab among the container's children.
+    index: usize,
+}
+
+impl ClosedPanel {
+    /// Record where `tile_id` (showing `panel`) sits in `tree`, before it is removed.
+    pub(super) fn new(tree: &egui_tiles::Tree<PaneEntry>, tile_id: TileId, panel: String) -> Self {
+        let parent = tree.tiles.parent_of(tile_id);
+        let index = parent
+            .and_then(|parent| match tree.tiles.get(parent) {
+                Some(Tile::Container(container)) => container
+                    .children_vec()
+                    .iter()
+                    .position(|&child| child == tile_id),
+                _ => None,
+            })
+            .unwrap_or(usize::MAX);
+        Self {
+            panel,
+            parent,
+            index,
+        }
+    }
+}
+
+impl TileLayoutState {
+    /// Remember a closed panel, most recent last.
+    pub(super) fn push_closed(&mut self, closed: ClosedPanel) {
+        self.recently_closed.retain(|c| c.panel != closed.panel);
+        self.recently_closed.push(closed);
+        if self.recently_closed.len() > CLOSED_HISTORY {
+            self.recently_closed.remove(0);
+        }
+    }
+
+    /// String ID of the panel [`reopen_closed_panel`](Self::reopen_closed_panel) would bring
+    /// back, if any.
+    pub fn last_closed_panel(&self) -> Option<&str> {
+        self.recently_closed
+            .iter()
+            .rev()
+            .map(|closed| closed.panel.as_str())
+            .find(|panel| !self.is_panel_visible(panel))
+    }
+
+    /// Reopen the most recently closed panel at its previous position and return its string ID.
+    /// Panels opened again in the meantime are skipped.
+    pub fn reopen_closed_panel(&mut self) -> Option<String> {
+        self.reopen_last().map(|closed| closed.panel)
+    }
+
+    /// Reopen the most recently closed panel, returning its history entry.
+    fn reopen_last(&mut self) -> Option<ClosedPanel> {
+        self.tree.as_ref()?;
+        while let Some(closed) = self.recently_closed.pop() {
+            if self.is_panel_visible(&closed.panel) {
+                continue;
+            }
+            let Some(&panel_id) = self.panel_id_map.get(&closed.panel) else {
+                continue;
+            };
+            let tree = self.tree.as_mut()?;
+            let tile_id = tree.tiles.insert_pane(PaneEntry { panel_id });
+            self.panel_tile_map.insert(panel_id, tile_id);
+            match closed
+                .parent
+                .filter(|&parent| matches!(tree.tiles.get(parent), Some(Tile::Container(_))))
+            {
+                Some(parent) => {
+                    tree.move_tile_to_container(tile_id, parent, closed.index, false);
+                    tree.make_active(|id, _| id == tile_id);
+                }
+                None => ui::insert_pane_into_tree(tree, tile_id),
+            }
+            return Some(closed);
+        }
+        None
+    }
+}
+
+/// Reopens the last closed panel via the `reopen_panel` binding or a Window menu request,
+/// recording the change as a layout undo step.
+pub(super) fn process_reopen(world: &mut World) {
+    let pressed = KeyInput::from_world(world).is_some_and(|input| {
+        world
+            .get_resource::<KeyBindings>()
+            .cloned()
+            .unwrap_or_default()
+            .reopen_panel
+            .just_pressed(input)
+    });
+    let mut state = world.resource_mut::<TileLayoutState>();
+    let requested = std::mem::take(&mut state.reopen_requested);
+    if !pressed && !requested {
+        return;
+    }
+    let before = state.snapshot();
+    let Some(closed) = state.reopen_last() else {
+        return;
+    };
+    let after = state.snapshot();
+    if let (Some(before), Some(after)) = (before, after) {
+        let desc = format!("Reopen {}", closed.panel);
+        let layout = LayoutUndoAction::new(desc, before, after);
+        LayoutUndoStack::record(world, ReopenUndoAction { layout, closed });
+    }
+}
+
+/// Undo action of a reopen: restores the layout and keeps the closed-panel history in step.
+struct ReopenUndoAction {
+    layout: LayoutUndoAction,
+    closed: ClosedPanel,
+}
+
+impl UndoAction for ReopenUndoAction {
+    fn undo(&self, world: &mut World) {
+        self.layout.undo(world);
+        world
+            .resource_mut::<TileLayoutState>()
+            .push_closed(self.closed.clone());
+    }
+
+    fn redo(&self, world: &mut World) {
+        self.layout.redo(world);
+        world
+            .resource_mut::<TileLayoutState>()
+            .recently_closed
+            .retain(|c| c.panel != self.closed.panel);
+    }
+
+    fn description(&self) -> &str {
+        self.layout.description()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dock::WorkbenchPanel;
+
+    struct TestPanel(&'static str);
+
+    impl WorkbenchPanel for TestPanel {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn title(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn ui(&mut self, _ui: &mut egui::Ui) {}
+    }
+
+    /// Close `panel`'s tab the way the dock does.
+    fn close(state: &mut TileLayoutState, panel: &'static str) {
+        let tile = state.panel_tile_map[&state.panel_id_map[panel]];
+        let tree = state.tree.as_mut().unwrap();
+        let closed = ClosedPanel::new(tree, tile, panel.to_string());
+        tree.tiles.remove(tile);
+        state.push_closed(closed);
+    }
+
+    #[test]
+    fn undoing_a_reopen_remembers_the_panel_again() {
+        let mut state = TileLayoutState::default();
+        for id in ["a", "b"] {
+            state.add_panel(Box::new(TestPanel(id)));
+        }
+        state.build_tree(None);
+        close(&mut state, "a");
+
+        let before = state.snapshot().unwrap();
+        let closed = state.reopen_last().unwrap();
+        let after = state.snapshot().unwrap();
+        assert!(state.is_panel_visible("a"));
+        assert_eq!(state.last_closed_panel(), None);
+
+        let layout = LayoutUndoAction::new("Reopen a", before, after);
+        let action = ReopenUndoAction { layout, closed };
+        let mut world = World::new();
+        world.insert_resource(state);
+        action.undo(&mut world);
+        let state = world.resource::<TileLayoutState>();
+        assert!(!state.is_panel_visible("a"));
+        assert_eq!(state.last_closed_panel(), Some("a"));
+
+        action.redo(&mut world);
+        let state = world.resource::<TileLayoutState>();
+        assert!(state.is_panel_visible("a"));
+        assert_eq!(state.last_closed_panel(), None);
+    }
+}
@@ -0,0 +1,83 @@
+//! # style.rs
+//!
+//! # style.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Per-panel and per-slot look of the dock panes, so tool areas can be grouped visually (a
+//! darker console, a lighter inspector). A panel describes itself with `WorkbenchPanel::style`;
+//! the app can override any panel by ID, or every panel of a default-layout slot, through the
+//! `PanelStyles` resource (`WorkbenchApp::set_panel_style` / `set_slot_style`). The resolved
+//! style is painted behind the pane before the panel draws.
+//!
+//! 停靠面板的逐面板与逐槽位外观，便于在视觉上分组工具区域（更暗的控制台、更亮的检查器）。面板通过
+//! `WorkbenchPanel::style` 描述自身样式；应用可通过 `PanelStyles` 资源（`WorkbenchApp::set_panel_style`
+//! / `set_slot_style`）按 ID 覆盖任一面板，或覆盖默认布局某个槽位中的所有面板。解析后的样式会在面板
+//! 绘制前画在窗格背后。
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::{PanelId, PanelSlot, TileLayoutState, WorkbenchPanel};
+
+/// Look of a panel's content area, returned by [`WorkbenchPanel::style`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PanelStyle {
+    /// Background behind the panel content; `None` keeps the theme's panel fill.
+    pub background: Option<egui::Color32>,
+}
+
+impl PanelStyle {
+    /// Style with a background color.
+    pub fn background(color: egui::Color32) -> Self {
+        Self {
+            background: Some(color),
+        }
+    }
+
+    /// This style with the fields it leaves unset taken from `fallback`.
+    pub fn or(self, fallback: PanelStyle) -> Self {
+        Self {
+            background: self.background.or(fallback.background),
+        }
+    }
+}
+
+/// Panel styles set by the app. A style by panel ID wins over the panel's own
+/// [`WorkbenchPanel::style`], which wins over the style of its default-layout slot.
+#[derive(Resource, Default)]
+pub struct PanelStyles {
+    /// Styles by panel string ID.
+    pub panels: HashMap<String, PanelStyle>,
+    /// Styles of every panel registered in a slot.
+    pub slots: HashMap<PanelSlot, PanelStyle>,
+}
+
+/// Resolved style of `panel`, registered as `panel_id`.
+pub(super) fn resolve_panel_style(
+    world: &World,
+    panel_id: PanelId,
+    panel: &dyn WorkbenchPanel,
+) -> PanelStyle {
+    let own = panel.style();
+    let Some(styles) = world.get_resource::<PanelStyles>() else {
+        return own;
+    };
+    let slot = world
+        .get_resource::<TileLayoutState>()
+        .and_then(|state| state.panel_slots.get(&panel_id).copied())
+        .unwrap_or_else(|| panel.default_slot());
+    let by_id = styles.panels.get(panel.id()).copied().unwrap_or_default();
+    let by_slot = styles.slots.get(&slot).copied().unwrap_or_default();
+    by_id.or(own).or(by_slot)
+}
+
+/// Paint `style` behind the pane at `rect`; nested egui panels inherit the background.
+pub(super) fn apply_panel_style(ui: &mut egui::Ui, rect: egui::Rect, style: PanelStyle) {
+    if let Some(fill) = style.background {
+        ui.painter().rect_filled(rect, 0.0, fill);
+        ui.visuals_mut().panel_fill = fill;
+    }
+}
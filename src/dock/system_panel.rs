@@ -0,0 +1,112 @@
+//! # system_panel.rs
+//!
+//! # system_panel.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! A `WorkbenchPanel` built from a closure that takes `SystemParam`s instead of `&mut World`.
+//! Each panel keeps a cached `SystemState`, so simple panels get cheap, typed ECS access
+//! (queries, resources, commands) without removing and re-inserting resources by hand.
+//!
+//! 由接收 `SystemParam` 而非 `&mut World` 的闭包构建的 `WorkbenchPanel`。
+//! 每个面板缓存一个 `SystemState`，让简单面板以低开销、类型化的方式访问 ECS
+//! （查询、资源、命令），无需手动移除并重新插入资源。
+
+use super::{PanelSlot, WorkbenchPanel};
+use bevy::ecs::system::{SystemParam, SystemParamItem, SystemState};
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// A closure usable as a system-param panel body: `|ui, params: P| { ... }`.
+///
+/// `P` can be any [`SystemParam`], including tuples and `#[derive(SystemParam)]` structs.
+pub trait PanelSystemFn<Marker>: Send + Sync + 'static {
+    /// Parameters fetched from the World each frame.
+    type Param: SystemParam;
+
+    /// Draw the panel with freshly fetched parameters.
+    fn run(&mut self, ui: &mut egui::Ui, param: SystemParamItem<Self::Param>);
+}
+
+impl<Func, P> PanelSystemFn<fn(P)> for Func
+where
+    Func: Send + Sync + 'static,
+    P: SystemParam,
+    for<'a> &'a mut Func: FnMut(&mut egui::Ui, P) + FnMut(&mut egui::Ui, SystemParamItem<P>),
+{
+    type Param = P;
+
+    fn run(&mut self, ui: &mut egui::Ui, param: SystemParamItem<P>) {
+        // Calling through a helper lets the compiler pick the `SystemParamItem` impl.
+        fn call_inner<P>(mut f: impl FnMut(&mut egui::Ui, P), ui: &mut egui::Ui, param: P) {
+            f(ui, param);
+        }
+        call_inner(self, ui, param);
+    }
+}
+
+/// Panel whose body is a [`PanelSystemFn`], with a cached [`SystemState`].
+pub struct SystemPanel<Marker, F: PanelSystemFn<Marker>> {
+    id: String,
+    title: String,
+    slot: PanelSlot,
+    func: F,
+    state: Option<SystemState<F::Param>>,
+    _marker: PhantomData<fn() -> Marker>,
+}
+
+impl<Marker: 'static, F: PanelSystemFn<Marker>> SystemPanel<Marker, F> {
+    /// Create a panel; the ID doubles as the title until [`with_title`](Self::with_title).
+    pub fn new(id: impl Into<String>, func: F) -> Self {
+        let id = id.into();
+        Self {
+            title: id.clone(),
+            id,
+            slot: PanelSlot::Left,
+            func,
+            state: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the tab title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the default layout slot.
+    pub fn with_slot(mut self, slot: PanelSlot) -> Self {
+        self.slot = slot;
+        self
+    }
+}
+
+impl<Marker: 'static, F: PanelSystemFn<Marker>> WorkbenchPanel for SystemPanel<Marker, F> {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let Self { func, state, .. } = self;
+        let state = state.get_or_insert_with(|| SystemState::new(world));
+        func.run(ui, state.get_mut(world));
+        state.apply(world);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_slot(&self) -> PanelSlot {
+        self.slot
+    }
+}
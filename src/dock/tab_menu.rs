@@ -0,0 +1,133 @@
+//! # tab_menu.rs
+//!
+//! # tab_menu.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Context menu of a dock tab. It offers closing the tab, the other tabs of its group or the
+//! whole group, locking the tab against closing and dragging, floating it in a new OS window (or
+//! docking it back into the main one), and the "?" submenu rendering the panel's
+//! `WorkbenchPanel::help` text. Its labels come from the `tab-menu-*` messages. The menu only
+//! reports what was picked; the dock behavior applies it to the tile tree. Closing a dirty panel
+//! goes through the confirmation modal defined here as well, one prompt per dirty tab.
+//!
+//! 停靠标签页的右键菜单。提供关闭标签页、关闭同组其他标签页或整组、锁定标签页以防关闭和拖动、
+//! 浮动到新的系统窗口（或停靠回主窗口），以及显示面板 `WorkbenchPanel::help` 文本的 "?" 子菜单。
+//! 菜单文字来自 `tab-menu-*` 消息。菜单只报告所选操作，由停靠行为将其应用到 tile 树。关闭含有
+//! 未保存修改的面板时，同样经由此处定义的确认对话框，每个未保存的标签页各询问一次。
+
+use super::WorkbenchPanel;
+use crate::i18n::I18n;
+
+/// Action picked in a tab's context menu.
+pub(super) enum TabMenuAction {
+    Close,
+    /// Close the other tabs of the group.
+    CloseOthers,
+    /// Close every tab of the group.
+    CloseAll,
+    /// Lock or unlock the tab.
+    ToggleLock,
+    /// Move to a new window, or back to the main window from a secondary one.
+    Detach,
+    /// Open the panel's help in the Help panel.
+    OpenHelp,
+}
+
+/// Context menu of the tab showing `panel`; `in_window` is true in a secondary window.
+pub(super) fn tab_menu_ui(
+    ui: &mut egui::Ui,
+    panel: Option<&dyn WorkbenchPanel>,
+    in_window: bool,
+    locked: bool,
+    i18n: Option<&I18n>,
+) -> Option<TabMenuAction> {
+    let t = |id: &'static str| i18n.map_or(id, |i18n| i18n.t(id));
+    let mut action = None;
+    if ui
+        .add_enabled(!locked, egui::Button::new(t("tab-menu-close")))
+        .clicked()
+    {
+        action = Some(TabMenuAction::Close);
+    }
+    if ui.button(t("tab-menu-close-others")).clicked() {
+        action = Some(TabMenuAction::CloseOthers);
+    }
+    if ui.button(t("tab-menu-close-all")).clicked() {
+        action = Some(TabMenuAction::CloseAll);
+    }
+    ui.separator();
+    let lock = if locked {
+        "tab-menu-unlock"
+    } else {
+        "tab-menu-lock"
+    };
+    if ui
+        .button(t(lock))
+        .on_hover_text(t("tab-menu-lock-hint"))
+        .clicked()
+    {
+        action = Some(TabMenuAction::ToggleLock);
+    }
+    let detach = if in_window {
+        "tab-menu-dock-back"
+    } else {
+        "tab-menu-float"
+    };
+    if ui.button(t(detach)).clicked() {
+        action = Some(TabMenuAction::Detach);
+    }
+    if let Some(help) = panel.and_then(|panel| panel.help())
+        && help_menu_ui(ui, help, t("tab-menu-help"), t("tab-menu-open-help"))
+    {
+        action = Some(TabMenuAction::OpenHelp);
+    }
+    action
+}
+
+/// "?" submenu showing a panel's help, labelled `title`. Returns true when asked (with the
+/// `open_label` button) to open the Help panel.
+fn help_menu_ui(ui: &mut egui::Ui, text: &str, title: &str, open_label: &str) -> bool {
+    let mut open = false;
+    ui.menu_button(format!("?  {title}"), |ui| {
+        ui.set_max_width(360.0);
+        egui::ScrollArea::vertical()
+            .max_height(320.0)
+            .show(ui, |ui| crate::help::help_text_ui(ui, text));
+        ui.separator();
+        open = ui.button(open_label).clicked();
+    });
+    open
+}
+
+/// Modal asking whether to close a panel with unsaved changes.
+/// Returns `Some(true)` to close, `Some(false)` to cancel, `None` while undecided.
+pub(super) fn dirty_close_prompt_ui(
+    ctx: &egui::Context,
+    panel: Option<&dyn WorkbenchPanel>,
+) -> Option<bool> {
+    let Some(panel) = panel else {
+        return Some(false);
+    };
+    let title = panel.title();
+    let mut choice = None;
+    let modal = egui::Modal::new(egui::Id::new("workbench_dirty_close")).show(ctx, |ui| {
+        ui.heading(format!("Close \"{title}\"?"));
+        ui.label("This panel has unsaved changes that will be lost.");
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Close").clicked() {
+                choice = Some(true);
+            }
+            if ui.button("Cancel").clicked() {
+                choice = Some(false);
+            }
+        });
+    });
+    if modal.should_close() && choice.is_none() {
+        choice = Some(false);
+    }
+    choice
+}
@@ -0,0 +1,84 @@
+//! # tab_overflow.rs
+//!
+//! # tab_overflow.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Keeps crowded tab bars usable. `egui_tiles` already clips the tab strip and scrolls it with
+//! arrow buttons; on top of that the mouse wheel scrolls a hovered tab bar, and when some tabs
+//! are out of view a "»" dropdown at the right end lists them. Picking one activates the tab
+//! and scrolls it into view. Tab widths come from the laid-out tab titles plus the spacing and
+//! close button `egui_tiles` adds around them.
+//!
+//! 让拥挤的标签栏保持可用。`egui_tiles` 本身会裁剪标签条并提供箭头按钮滚动；在此之上，鼠标滚轮可以
+//! 滚动悬停的标签栏，当有标签不在可见范围内时，右端的 "»" 下拉菜单会列出它们。选中其中一项会激活该
+//! 标签并将其滚动到可见位置。标签宽度取自排版后的标签标题，加上 `egui_tiles` 在其周围添加的
+//! 间距与关闭按钮。
+
+use egui_tiles::TileId;
+
+/// Scroll a hovered tab bar with the mouse wheel and show the "»" menu of the tabs out of
+/// view. `titles` are the tabs in order, with the width of their tab buttons. Returns the tab
+/// picked from the menu.
+pub(super) fn tab_overflow_ui(
+    ui: &mut egui::Ui,
+    titles: &[(TileId, String, f32)],
+    active: Option<TileId>,
+    scroll_offset: &mut f32,
+) -> Option<TileId> {
+    let mut spans = Vec::with_capacity(titles.len());
+    let mut start = 0.0;
+    for &(_, _, width) in titles {
+        spans.push((start, start + width));
+        start += width;
+    }
+    let total = start;
+    let visible = ui.available_width() - ui.spacing().interact_size.x;
+    if total <= ui.available_width() {
+        return None;
+    }
+    if ui.rect_contains_pointer(ui.max_rect()) {
+        let delta = ui.input(|i| i.smooth_scroll_delta);
+        // Vertical wheels scroll the strip sideways.
+        let delta = if delta.x != 0.0 { delta.x } else { delta.y };
+        *scroll_offset -= delta;
+    }
+    *scroll_offset = scroll_offset.clamp(0.0, total - visible);
+
+    let view = *scroll_offset..=*scroll_offset + visible;
+    let hidden: Vec<usize> = spans
+        .iter()
+        .enumerate()
+        .filter(|(_, (start, end))| !(view.contains(start) && view.contains(end)))
+        .map(|(i, _)| i)
+        .collect();
+    if hidden.is_empty() {
+        return None;
+    }
+    let mut picked = None;
+    ui.menu_button("»", |ui| {
+        for &i in &hidden {
+            let (tile_id, title, _) = &titles[i];
+            if ui
+                .selectable_label(active == Some(*tile_id), title)
+                .clicked()
+            {
+                picked = Some(i);
+                ui.close();
+            }
+        }
+    })
+    .response
+    .on_hover_text(format!("{} more tabs", hidden.len()));
+    let i = picked?;
+    // Scroll just enough to show the whole tab.
+    let (tab_start, tab_end) = spans[i];
+    if tab_start < *scroll_offset {
+        *scroll_offset = tab_start;
+    } else if tab_end > *scroll_offset + visible {
+        *scroll_offset = tab_end - visible;
+    }
+    Some(titles[i].0)
+}
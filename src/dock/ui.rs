@@ -13,12 +13,20 @@
 //! 负责渲染并更新 workbench 的停靠式 UI。它把 `egui_tiles` 与 Bevy `World`
 //! 访问连接起来，处理标签页关闭和布局撤销快照，并把待打开的面板请求落实为 tile 树上的具体变更。
 
+use super::reopen::ClosedPanel;
+use super::tab_menu::dirty_close_prompt_ui;
 use super::{LayoutUndoAction, PaneEntry, PanelId, TileLayoutState, WorkbenchPanel};
+use crate::i18n::I18n;
+use crate::undo::LayoutUndoStack;
 use bevy::ecs::system::SystemState;
 use bevy::prelude::*;
 use bevy_egui::PrimaryEguiContext;
 use std::collections::HashMap;
 
+mod behavior;
+
+use behavior::{WorkbenchBehavior, pane_panel};
+
 pub(super) fn set_linear_shares(
     tiles: &mut egui_tiles::Tiles<PaneEntry>,
     tile_id: egui_tiles::TileId,
@@ -44,77 +52,101 @@ pub(super) fn insert_pane_into_tree(
     }
 }
 
-struct WorkbenchBehavior<'a> {
-    panels: &'a mut HashMap<PanelId, Box<dyn WorkbenchPanel>>,
-    world: Option<&'a mut World>,
-    tiles_to_remove: Vec<egui_tiles::TileId>,
+/// What happened while drawing a secondary window's tree.
+#[derive(Default)]
+pub(super) struct TreeOutcome {
+    /// Panel the user clicked into.
+    pub clicked: Option<String>,
+    /// Tab to dock back into the main window.
+    pub dock_back: Option<egui_tiles::TileId>,
+    /// Panel whose help should open in the Help panel.
+    pub help: Option<String>,
+    /// Tree before tabs were closed this frame, with the string IDs of the closed panels.
+    pub closed: Option<(egui_tiles::Tree<PaneEntry>, Vec<String>)>,
 }
 
-impl egui_tiles::Behavior<PaneEntry> for WorkbenchBehavior<'_> {
-    fn tab_title_for_pane(&mut self, pane: &PaneEntry) -> egui::WidgetText {
-        self.panels
-            .get(&pane.panel_id)
-            .map(|p| p.title())
-            .unwrap_or_else(|| "Unknown".to_string())
-            .into()
-    }
-
-    fn pane_ui(
-        &mut self,
-        ui: &mut egui::Ui,
-        _tile_id: egui_tiles::TileId,
-        pane: &mut PaneEntry,
-    ) -> egui_tiles::UiResponse {
-        if let Some(panel) = self.panels.get_mut(&pane.panel_id) {
-            if panel.needs_world()
-                && let Some(world) = self.world.as_deref_mut()
-            {
-                panel.ui_world(ui, world);
-            } else {
-                panel.ui(ui);
-            }
+/// Queue the dirty tabs `asked` to close and prompt for the first queued one, adding it to
+/// `tiles_to_remove` once confirmed.
+fn dirty_close_ui(
+    ctx: &egui::Context,
+    tiles: &egui_tiles::Tiles<PaneEntry>,
+    panels: &HashMap<PanelId, Box<dyn WorkbenchPanel>>,
+    queue: &mut Vec<egui_tiles::TileId>,
+    asked: Vec<egui_tiles::TileId>,
+    tiles_to_remove: &mut Vec<egui_tiles::TileId>,
+) {
+    for tile_id in asked {
+        if !queue.contains(&tile_id) {
+            queue.push(tile_id);
         }
-        egui_tiles::UiResponse::None
-    }
-
-    fn is_tab_closable(
-        &self,
-        _tiles: &egui_tiles::Tiles<PaneEntry>,
-        _tile_id: egui_tiles::TileId,
-    ) -> bool {
-        true
-    }
-
-    fn on_tab_close(
-        &mut self,
-        _tiles: &mut egui_tiles::Tiles<PaneEntry>,
-        tile_id: egui_tiles::TileId,
-    ) -> bool {
-        self.tiles_to_remove.push(tile_id);
-        true
-    }
-
-    fn on_tab_button(
-        &mut self,
-        _tiles: &egui_tiles::Tiles<PaneEntry>,
-        tile_id: egui_tiles::TileId,
-        button_response: egui::Response,
-    ) -> egui::Response {
-        button_response.context_menu(|ui| {
-            if ui.button("Close").clicked() {
-                self.tiles_to_remove.push(tile_id);
-                ui.close();
-            }
-        });
-        button_response
     }
+    if let Some(&tile_id) = queue.first()
+        && let Some(close) = dirty_close_prompt_ui(ctx, pane_panel(tiles, panels, tile_id))
+    {
+        if close {
+            tiles_to_remove.push(tile_id);
+        }
+        queue.remove(0);
+    }
+}
 
-    fn simplification_options(&self) -> egui_tiles::SimplificationOptions {
-        egui_tiles::SimplificationOptions {
-            all_panes_must_have_tabs: true,
-            ..Default::default()
+/// Draw the tree of a secondary window, closing tabs (after the dirty prompts) in place.
+pub(super) fn window_tree_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    panels: &mut HashMap<PanelId, Box<dyn WorkbenchPanel>>,
+    tree: &mut egui_tiles::Tree<PaneEntry>,
+    focused: Option<String>,
+    dirty_close: &mut Vec<egui_tiles::TileId>,
+) -> TreeOutcome {
+    let mut behavior = WorkbenchBehavior {
+        panels,
+        world: Some(world),
+        tiles_to_remove: Vec::new(),
+        dirty_close: Vec::new(),
+        focused,
+        clicked: None,
+        focus_entered: None,
+        maximize_toggled: None,
+        detach: None,
+        help_requested: None,
+        activate_tab: None,
+        in_window: true,
+    };
+    tree.ui(&mut behavior, ui);
+    if let Some(tab) = behavior.activate_tab {
+        tree.make_active(|tile_id, _| tile_id == tab);
+    }
+    let mut tiles_to_remove = behavior.tiles_to_remove;
+    let mut outcome = TreeOutcome {
+        clicked: behavior.clicked,
+        dock_back: behavior.detach,
+        help: behavior.help_requested,
+        closed: None,
+    };
+    let asked = behavior.dirty_close;
+    dirty_close_ui(
+        ui.ctx(),
+        &tree.tiles,
+        panels,
+        dirty_close,
+        asked,
+        &mut tiles_to_remove,
+    );
+    if tiles_to_remove.is_empty() {
+        return outcome;
+    }
+    // Keep the tree from before the removal so the close can be undone.
+    let before = tree.clone();
+    let mut closed = Vec::new();
+    for tile_id in tiles_to_remove {
+        if let Some(panel) = pane_panel(&tree.tiles, panels, tile_id) {
+            closed.push(panel.id().to_string());
         }
+        tree.tiles.remove(tile_id);
     }
+    outcome.closed = Some((before, closed));
+    outcome
 }
 
 /// Resource holding the layout file path.
@@ -133,33 +165,42 @@ pub fn tiles_ui_system(world: &mut World) {
         let layout_path = world.resource::<LayoutPath>();
         state.build_tree(Some(&layout_path.0));
 
-        if let Some(path) = state.layout_save_path.take() {
-            state.save_layout(&path);
-            info!("Layout saved to {}", path.display());
-        }
-        if let Some(path) = state.layout_load_path.take()
-            && state.load_layout(&path)
+        if let Some((content, name)) = state.pending_layout_load.take()
+            && state.load_layout_json(&content, &name)
         {
-            info!("Layout loaded from {}", path.display());
+            info!("Layout loaded from {name}");
         }
         if state.layout_reset_requested {
             state.layout_reset_requested = false;
             let before = state.snapshot();
             state.tree = None;
             state.panel_tile_map.clear();
+            state.windows.clear();
+            state.reset_mode_visibility();
             state.build_default_tree();
             let after = state.snapshot();
-            let _ = std::fs::remove_file(&layout_path.0);
+            crate::storage::remove(&layout_path.0);
             info!("Layout reset to default");
 
-            if let (Some(before), Some(after)) = (before, after)
-                && let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>()
-            {
-                undo_stack.push(LayoutUndoAction::new("Reset layout", before, after));
+            if let (Some(before), Some(after)) = (before, after) {
+                let action = LayoutUndoAction::new("Reset layout", before, after);
+                LayoutUndoStack::record(world, action);
             }
         }
     });
 
+    if let Some(mode) = world.get_resource::<State<crate::mode::EditorMode>>() {
+        let mode = *mode.get();
+        world
+            .resource_mut::<TileLayoutState>()
+            .apply_mode_visibility(mode);
+    }
+    super::arrange::process_layout_ops(world);
+    super::focus::process_focus_keys(world);
+    super::maximize::process_maximize_key(world);
+    super::reopen::process_reopen(world);
+    crate::help::refresh_panel_help(world);
+
     let ctx = {
         let mut sys =
             SystemState::<Query<&mut bevy_egui::EguiContext, With<PrimaryEguiContext>>>::new(world);
@@ -182,12 +223,31 @@ pub fn tiles_ui_system(world: &mut World) {
         state.tile_to_panel_str_id_map()
     };
 
-    let (mut tree, mut panels) = {
+    let focus_entered = world.resource_mut::<TileLayoutState>().focus_entered.take();
+    let (mut tree, mut panels, mut dirty_close, mut maximized, chromeless, mut dragged_tile) = {
         let mut state = world.resource_mut::<TileLayoutState>();
-        (state.tree.take(), std::mem::take(&mut state.panels))
+        (
+            state.tree.take(),
+            std::mem::take(&mut state.panels),
+            std::mem::take(&mut state.pending_dirty_close),
+            state.maximized,
+            state.chromeless,
+            state.dragged_tile,
+        )
     };
 
     let mut closed_panel_ids: Vec<String> = Vec::new();
+    let mut closed_panels: Vec<ClosedPanel> = Vec::new();
+    let focused = world
+        .get_resource::<super::FocusedPanel>()
+        .and_then(|f| f.0.clone());
+    let mut clicked_panel = None;
+    let mut detach_tile = None;
+    let mut help_requested = None;
+    let restore_hint = world
+        .get_resource::<I18n>()
+        .map_or("dock-restore-layout", |i18n| i18n.t("dock-restore-layout"))
+        .to_owned();
 
     if let Some(ref mut tree) = tree {
         egui::CentralPanel::default().show(&ctx, |ui| {
@@ -195,31 +255,104 @@ pub fn tiles_ui_system(world: &mut World) {
                 panels: &mut panels,
                 world: Some(world),
                 tiles_to_remove: Vec::new(),
+                dirty_close: Vec::new(),
+                focused,
+                clicked: None,
+                focus_entered,
+                maximize_toggled: None,
+                detach: None,
+                help_requested: None,
+                activate_tab: None,
+                in_window: false,
             };
-            tree.ui(&mut behavior, ui);
+            let maximized_tile = maximized.and_then(|pid| super::maximize::pane_tile(tree, pid));
+            match maximized_tile {
+                Some(tile_id) => {
+                    if super::maximize::maximized_ui(
+                        ui,
+                        &mut behavior,
+                        tree,
+                        tile_id,
+                        chromeless,
+                        &restore_hint,
+                    ) {
+                        maximized = None;
+                    }
+                }
+                None => {
+                    maximized = None;
+                    tree.ui(&mut behavior, ui);
+                }
+            }
+            if let Some(tab) = behavior.activate_tab.take() {
+                tree.make_active(|tile_id, _| tile_id == tab);
+            }
+            if let Some(tile_id) = behavior.maximize_toggled.take()
+                && let Some(egui_tiles::Tile::Pane(pane)) = tree.tiles.get(tile_id)
+            {
+                maximized = Some(pane.panel_id);
+            }
+            clicked_panel = behavior.clicked.take();
+            help_requested = behavior.help_requested.take();
+            detach_tile = behavior
+                .detach
+                .take()
+                .or_else(|| super::window::drag_out(ui.ctx(), tree, &mut dragged_tile));
+            let mut tiles_to_remove = behavior.tiles_to_remove;
+            let asked = behavior.dirty_close;
+            dirty_close_ui(
+                ui.ctx(),
+                &tree.tiles,
+                &panels,
+                &mut dirty_close,
+                asked,
+                &mut tiles_to_remove,
+            );
 
-            for tile_id in behavior.tiles_to_remove {
-                closed_panel_ids.extend(tile_to_str_id.get(&tile_id).cloned());
+            for tile_id in tiles_to_remove {
+                if let Some(panel) = tile_to_str_id.get(&tile_id) {
+                    closed_panel_ids.push(panel.clone());
+                    closed_panels.push(ClosedPanel::new(tree, tile_id, panel.clone()));
+                }
                 tree.tiles.remove(tile_id);
             }
         });
     }
 
+    if let Some(panel) = clicked_panel
+        && let Some(mut focus) = world.get_resource_mut::<super::FocusedPanel>()
+    {
+        focus.0 = Some(panel);
+    }
+
+    if let Some(panel) = help_requested {
+        crate::help::show_panel_help(world, panel);
+    }
+
     let mut state = world.resource_mut::<TileLayoutState>();
     state.tree = tree;
     state.panels = panels;
+    state.pending_dirty_close = dirty_close;
+    state.maximized = maximized;
+    state.dragged_tile = dragged_tile;
+    for closed in closed_panels {
+        state.push_closed(closed);
+    }
 
     if !closed_panel_ids.is_empty() {
         let after_snapshot = state.snapshot();
         if let (Some(before), Some(after)) = (before_snapshot.clone(), after_snapshot) {
             let desc = format!("Close {}", closed_panel_ids.join(", "));
             let _ = state;
-            if let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>() {
-                undo_stack.push(LayoutUndoAction::new(desc, before, after));
-            }
+            LayoutUndoStack::record(world, LayoutUndoAction::new(desc, before, after));
         }
     }
 
+    if let Some(panel) = detach_tile.and_then(|tile| tile_to_str_id.get(&tile)) {
+        let desc = format!("Float {panel}");
+        super::record_layout_change(world, desc, |state| state.detach_panel(panel));
+    }
+
     let pending_opens = {
         let mut state = world.resource_mut::<TileLayoutState>();
         std::mem::take(&mut state.pending_open_requests)
@@ -243,9 +376,7 @@ pub fn tiles_ui_system(world: &mut World) {
 
         if let (Some(before), Some(after)) = (open_before, open_after) {
             let desc = format!("Open {}", pending_opens.join(", "));
-            if let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>() {
-                undo_stack.push(LayoutUndoAction::new(desc, before, after));
-            }
+            LayoutUndoStack::record(world, LayoutUndoAction::new(desc, before, after));
         }
     }
 }
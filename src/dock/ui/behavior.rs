@@ -0,0 +1,290 @@
+//! # behavior.rs
+//!
+//! # behavior.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The `egui_tiles::Behavior` of the workbench dock. It draws panes (with their panel style,
+//! focus highlight and World access), titles tabs, handles tab clicks, double-clicks and context
+//! menus (including bulk closing and tab locks) and the tab bar overflow menu, and collects what
+//! happened so `tiles_ui_system` can apply it after the tree is drawn.
+//!
+//! workbench 停靠区的 `egui_tiles::Behavior`。它绘制窗格（含面板样式、焦点高亮与 World 访问）、生成
+//! 标签标题、处理标签的单击、双击与右键菜单（包括批量关闭与标签锁定）以及标签栏溢出菜单，并记录
+//! 发生的操作，供 `tiles_ui_system` 在绘制完 tile 树后统一应用。
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::bench_ui::{Icon, icon};
+use crate::dock::tab_menu::TabMenuAction;
+use crate::dock::{PaneEntry, PanelId, TileLayoutState, WorkbenchPanel};
+use crate::i18n::I18n;
+
+/// Gap `egui_tiles` leaves between a tab's title and its close button.
+const CLOSE_BUTTON_GAP: f32 = 4.0;
+
+pub(super) struct WorkbenchBehavior<'a> {
+    pub(super) panels: &'a mut HashMap<PanelId, Box<dyn WorkbenchPanel>>,
+    pub(super) world: Option<&'a mut World>,
+    pub(super) tiles_to_remove: Vec<egui_tiles::TileId>,
+    /// Dirty tabs the user tried to close this frame.
+    pub(super) dirty_close: Vec<egui_tiles::TileId>,
+    /// String ID of the focused panel (drawn with a highlight).
+    pub(super) focused: Option<String>,
+    /// String ID of the panel the user clicked (or tabbed) into this frame.
+    pub(super) clicked: Option<String>,
+    /// Panel whose focus anchor should take keyboard focus this frame.
+    pub(super) focus_entered: Option<PanelId>,
+    /// Tab the user double-clicked this frame (maximize toggle).
+    pub(super) maximize_toggled: Option<egui_tiles::TileId>,
+    /// Tab moved to a new OS window (main dock) or back to the main dock (secondary window).
+    pub(super) detach: Option<egui_tiles::TileId>,
+    /// Panel whose help should open in the Help panel.
+    pub(super) help_requested: Option<String>,
+    /// Tab picked from a tab bar's overflow menu, activated after drawing.
+    pub(super) activate_tab: Option<egui_tiles::TileId>,
+    /// Whether this tree belongs to a secondary window.
+    pub(super) in_window: bool,
+}
+
+impl WorkbenchBehavior<'_> {
+    /// Close a tab, deferring to a confirmation prompt if its panel is dirty. Locked tabs stay.
+    fn request_close(
+        &mut self,
+        tiles: &egui_tiles::Tiles<PaneEntry>,
+        tile_id: egui_tiles::TileId,
+    ) -> bool {
+        if self.is_locked(tiles, tile_id) {
+            return false;
+        }
+        if pane_panel(tiles, self.panels, tile_id).is_some_and(|p| p.is_dirty()) {
+            self.dirty_close.push(tile_id);
+            return false;
+        }
+        self.tiles_to_remove.push(tile_id);
+        true
+    }
+
+    /// Close the panel tabs of the tab group holding `tile_id`, except `keep`. They are removed
+    /// in one frame, so a single undo step restores them all.
+    fn close_group(
+        &mut self,
+        tiles: &egui_tiles::Tiles<PaneEntry>,
+        tile_id: egui_tiles::TileId,
+        keep: Option<egui_tiles::TileId>,
+    ) {
+        let group = tiles
+            .parent_of(tile_id)
+            .and_then(|parent| match tiles.get(parent) {
+                Some(egui_tiles::Tile::Container(egui_tiles::Container::Tabs(tabs))) => {
+                    Some(tabs.children.clone())
+                }
+                _ => None,
+            });
+        for child in group.unwrap_or_else(|| vec![tile_id]) {
+            if Some(child) != keep && pane_panel(tiles, self.panels, child).is_some() {
+                self.request_close(tiles, child);
+            }
+        }
+    }
+
+    /// Whether the tab `tile_id` shows a panel with a locked tab.
+    fn is_locked(&self, tiles: &egui_tiles::Tiles<PaneEntry>, tile_id: egui_tiles::TileId) -> bool {
+        pane_panel(tiles, self.panels, tile_id)
+            .is_some_and(|panel| self.is_panel_locked(panel.id()))
+    }
+
+    fn is_panel_locked(&self, panel_id: &str) -> bool {
+        self.world
+            .as_deref()
+            .and_then(|world| world.get_resource::<TileLayoutState>())
+            .is_some_and(|state| state.is_tab_locked(panel_id))
+    }
+}
+
+/// Panel shown in a pane tile, if any.
+pub(super) fn pane_panel<'p>(
+    tiles: &egui_tiles::Tiles<PaneEntry>,
+    panels: &'p HashMap<PanelId, Box<dyn WorkbenchPanel>>,
+    tile_id: egui_tiles::TileId,
+) -> Option<&'p dyn WorkbenchPanel> {
+    match tiles.get(tile_id) {
+        Some(egui_tiles::Tile::Pane(pane)) => panels.get(&pane.panel_id).map(|p| p.as_ref()),
+        _ => None,
+    }
+}
+
+/// Tab text: optional icon, title and a dirty marker.
+fn tab_title(panel: &dyn WorkbenchPanel) -> String {
+    let title = panel.title();
+    let dirty = if panel.is_dirty() { " •" } else { "" };
+    match panel.icon() {
+        Some(icon) => format!("{icon} {title}{dirty}"),
+        None => format!("{title}{dirty}"),
+    }
+}
+
+impl egui_tiles::Behavior<PaneEntry> for WorkbenchBehavior<'_> {
+    fn tab_title_for_pane(&mut self, pane: &PaneEntry) -> egui::WidgetText {
+        let Some(panel) = self.panels.get(&pane.panel_id) else {
+            return "Unknown".into();
+        };
+        let title = tab_title(panel.as_ref());
+        if self.is_panel_locked(panel.id()) {
+            format!("{title} {}", icon(Icon::Lock)).into()
+        } else {
+            title.into()
+        }
+    }
+
+    fn pane_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        _tile_id: egui_tiles::TileId,
+        pane: &mut PaneEntry,
+    ) -> egui_tiles::UiResponse {
+        let Some(panel) = self.panels.get_mut(&pane.panel_id) else {
+            return egui_tiles::UiResponse::None;
+        };
+        let rect = ui.max_rect();
+        let style = match self.world.as_deref() {
+            Some(world) => {
+                crate::dock::style::resolve_panel_style(world, pane.panel_id, panel.as_ref())
+            }
+            None => panel.style(),
+        };
+        crate::dock::style::apply_panel_style(ui, rect, style);
+        if ui.input(|i| i.pointer.any_pressed()) && ui.rect_contains_pointer(rect) {
+            self.clicked = Some(panel.id().to_string());
+        }
+        let focused = self.focused.as_deref() == Some(panel.id());
+        let enter = self.focus_entered == Some(pane.panel_id);
+        crate::dock::focus::pane_focus_anchor(ui, rect, panel.as_ref(), enter);
+        if !focused && crate::dock::focus::has_keyboard_focus(ui, rect) {
+            self.clicked = Some(panel.id().to_string());
+        }
+
+        if panel.needs_world()
+            && let Some(world) = self.world.as_deref_mut()
+        {
+            panel.ui_world(ui, world);
+        } else {
+            panel.ui(ui);
+        }
+
+        if focused {
+            let stroke = ui.visuals().selection.stroke;
+            ui.painter()
+                .rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Inside);
+        }
+        egui_tiles::UiResponse::None
+    }
+
+    fn is_tab_closable(
+        &self,
+        tiles: &egui_tiles::Tiles<PaneEntry>,
+        tile_id: egui_tiles::TileId,
+    ) -> bool {
+        !self.is_locked(tiles, tile_id)
+    }
+
+    fn on_tab_close(
+        &mut self,
+        tiles: &mut egui_tiles::Tiles<PaneEntry>,
+        tile_id: egui_tiles::TileId,
+    ) -> bool {
+        self.request_close(tiles, tile_id)
+    }
+
+    fn on_tab_button(
+        &mut self,
+        tiles: &egui_tiles::Tiles<PaneEntry>,
+        tile_id: egui_tiles::TileId,
+        button_response: egui::Response,
+    ) -> egui::Response {
+        if button_response.double_clicked() {
+            self.maximize_toggled = Some(tile_id);
+        }
+        let locked = self.is_locked(tiles, tile_id);
+        if locked && button_response.dragged() {
+            button_response.ctx.stop_dragging();
+        }
+        let mut action = None;
+        let i18n = self.world.as_deref().and_then(|w| w.get_resource::<I18n>());
+        button_response.context_menu(|ui| {
+            let panel = pane_panel(tiles, self.panels, tile_id);
+            action = crate::dock::tab_menu::tab_menu_ui(ui, panel, self.in_window, locked, i18n);
+            if action.is_some() {
+                ui.close();
+            }
+        });
+        match action {
+            Some(TabMenuAction::Close) => {
+                self.request_close(tiles, tile_id);
+            }
+            Some(TabMenuAction::CloseOthers) => self.close_group(tiles, tile_id, Some(tile_id)),
+            Some(TabMenuAction::CloseAll) => self.close_group(tiles, tile_id, None),
+            Some(TabMenuAction::ToggleLock) => {
+                if let Some(panel) = pane_panel(tiles, self.panels, tile_id)
+                    && let Some(world) = self.world.as_deref_mut()
+                {
+                    let id = panel.id().to_string();
+                    world
+                        .resource_mut::<TileLayoutState>()
+                        .set_tab_locked(&id, !locked);
+                }
+            }
+            Some(TabMenuAction::Detach) => self.detach = Some(tile_id),
+            Some(TabMenuAction::OpenHelp) => {
+                self.help_requested =
+                    pane_panel(tiles, self.panels, tile_id).map(|p| p.id().to_string());
+            }
+            None => {}
+        }
+        button_response
+    }
+
+    fn top_bar_right_ui(
+        &mut self,
+        tiles: &egui_tiles::Tiles<PaneEntry>,
+        ui: &mut egui::Ui,
+        _tile_id: egui_tiles::TileId,
+        tabs: &egui_tiles::Tabs,
+        scroll_offset: &mut f32,
+    ) {
+        let mut titles = Vec::with_capacity(tabs.children.len());
+        for &child in &tabs.children {
+            let title = self.tab_title_for_tile(tiles, child);
+            let text = title.text().to_string();
+            // Lay the tab out the way `egui_tiles` sizes its button.
+            let galley = title.into_galley(
+                ui,
+                Some(egui::TextWrapMode::Extend),
+                f32::INFINITY,
+                egui::TextStyle::Button,
+            );
+            let close = if self.is_tab_closable(tiles, child) {
+                self.close_button_outer_size() + CLOSE_BUTTON_GAP
+            } else {
+                0.0
+            };
+            let width = galley.size().x + 2.0 * self.tab_title_spacing(ui.visuals()) + close;
+            titles.push((child, text, width));
+        }
+        if let Some(tab) =
+            crate::dock::tab_overflow::tab_overflow_ui(ui, &titles, tabs.active, scroll_offset)
+        {
+            self.activate_tab = Some(tab);
+        }
+    }
+
+    fn simplification_options(&self) -> egui_tiles::SimplificationOptions {
+        egui_tiles::SimplificationOptions {
+            all_panes_must_have_tabs: true,
+            ..Default::default()
+        }
+    }
+}
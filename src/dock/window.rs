@@ -0,0 +1,398 @@
+//! # window.rs
+//!
+//! # window.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Secondary OS windows hosting dock panels. A panel detached from the main dock (tab context
+//! menu, or dropping a dragged tab outside the main window) moves into its own tile tree shown
+//! in a new Bevy `Window` with a dedicated camera and egui context, drawn in a per-window egui
+//! pass schedule. Windows belong to `TileLayoutState`, so panel lookup, the Window menu and the
+//! layout file cover them; closing a window docks its panels back into the main tree.
+//!
+//! 承载停靠面板的辅助系统窗口。从主停靠区分离的面板（通过标签右键菜单，或把拖动中的标签放到主窗口
+//! 之外）会移入自己的 tile 树，并显示在新的 Bevy `Window` 中；该窗口拥有独立的相机和 egui 上下文，
+//! 在每个窗口专属的 egui pass 调度中绘制。窗口归属于 `TileLayoutState`，因此面板查找、Window 菜单
+//! 和布局文件都会包含它们；关闭窗口时，其中的面板会停靠回主 tile 树。
+
+use super::{LayoutUndoAction, PaneEntry, PanelId, TileLayoutState, ui};
+use crate::inspector::WorkbenchInternal;
+use crate::undo::LayoutUndoStack;
+use bevy::camera::RenderTarget;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use bevy::window::{WindowPosition, WindowRef};
+use bevy_egui::{EguiContext, EguiMultipassSchedule};
+use egui_tiles::{Tile, TileId};
+
+/// Default size of a new secondary window, in logical pixels.
+const DEFAULT_SIZE: [f32; 2] = [480.0, 360.0];
+
+/// Egui pass schedule of the secondary window with this ID.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct DockWindowPass(u32);
+
+/// Marks the OS window and the camera of a secondary dock window, naming the OS window.
+#[derive(Component)]
+struct DockWindowEntity(Entity);
+
+/// UI camera of the secondary window with this ID.
+#[derive(Component)]
+struct DockWindowCamera(u32);
+
+/// A secondary OS window holding its own tile tree.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DockWindow {
+    id: u32,
+    pub(crate) tree: egui_tiles::Tree<PaneEntry>,
+    /// Logical size.
+    size: [f32; 2],
+    /// Screen position of the top-left corner, if known.
+    position: Option<[i32; 2]>,
+    #[serde(skip)]
+    entity: Option<Entity>,
+    #[serde(skip)]
+    focus_requested: bool,
+    #[serde(skip)]
+    dirty_close: Vec<TileId>,
+}
+
+impl DockWindow {
+    fn panel_ids(&self) -> Vec<PanelId> {
+        panes(&self.tree).into_iter().map(|(_, id)| id).collect()
+    }
+}
+
+/// Pane tiles of a tree with their panels.
+fn panes(tree: &egui_tiles::Tree<PaneEntry>) -> Vec<(TileId, PanelId)> {
+    tree.tiles
+        .iter()
+        .filter_map(|(&tile_id, tile)| match tile {
+            Tile::Pane(pane) => Some((tile_id, pane.panel_id)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl TileLayoutState {
+    /// Move `panel` out of the dock into a new OS window. Returns `false` if it is unknown
+    /// or already in a secondary window.
+    pub fn detach_panel(&mut self, panel: &str) -> bool {
+        let Some(&panel_id) = self.panel_id_map.get(panel) else {
+            return false;
+        };
+        if self.window_of(panel_id).is_some() {
+            return false;
+        }
+        if let Some(tile) = self.panel_tile_map.remove(&panel_id)
+            && let Some(tree) = &mut self.tree
+        {
+            tree.tiles.remove(tile);
+        }
+        if self.maximized == Some(panel_id) {
+            self.maximized = None;
+        }
+        let id = self.windows.iter().map(|w| w.id + 1).max().unwrap_or(0);
+        self.windows.push(DockWindow {
+            id,
+            tree: egui_tiles::Tree::new_tabs(
+                format!("workbench_window_{id}"),
+                vec![PaneEntry { panel_id }],
+            ),
+            size: DEFAULT_SIZE,
+            position: None,
+            entity: None,
+            focus_requested: false,
+            dirty_close: Vec::new(),
+        });
+        true
+    }
+
+    /// Move `panel` from its secondary window back into the main dock.
+    pub fn attach_panel(&mut self, panel: &str) -> bool {
+        let Some(&panel_id) = self.panel_id_map.get(panel) else {
+            return false;
+        };
+        let Some(index) = self.window_of(panel_id) else {
+            return false;
+        };
+        let window = &mut self.windows[index];
+        for (tile_id, id) in panes(&window.tree) {
+            if id == panel_id {
+                window.tree.tiles.remove(tile_id);
+            }
+        }
+        self.dock_back(panel_id);
+        true
+    }
+
+    /// Whether `panel` is shown in a secondary window.
+    pub fn is_panel_detached(&self, panel: &str) -> bool {
+        self.panel_id_map
+            .get(panel)
+            .is_some_and(|&id| self.window_of(id).is_some())
+    }
+
+    /// Index of the secondary window showing `panel_id`.
+    pub(crate) fn window_of(&self, panel_id: PanelId) -> Option<usize> {
+        self.windows
+            .iter()
+            .position(|w| w.panel_ids().contains(&panel_id))
+    }
+
+    /// Key of a secondary window's OS window in `WorkbenchSettings::window_scales`.
+    pub(crate) fn window_scale_key(&self, window: Entity) -> Option<String> {
+        self.windows
+            .iter()
+            .find(|w| w.entity == Some(window))
+            .map(|w| format!("window_{}", w.id))
+    }
+
+    /// Make `panel_id` the active tab of its window and raise the window.
+    pub(crate) fn focus_in_window(&mut self, index: usize, panel_id: PanelId) {
+        let window = &mut self.windows[index];
+        window
+            .tree
+            .make_active(|_, tile| matches!(tile, Tile::Pane(p) if p.panel_id == panel_id));
+        window.focus_requested = true;
+    }
+
+    /// Remove `panel_id` from its window without docking it back.
+    pub(crate) fn close_in_window(&mut self, index: usize, panel_id: PanelId) {
+        let tree = &mut self.windows[index].tree;
+        for (tile_id, id) in panes(tree) {
+            if id == panel_id {
+                tree.tiles.remove(tile_id);
+            }
+        }
+    }
+
+    /// Insert `panel_id` into the main tree (creating it if there is none).
+    fn dock_back(&mut self, panel_id: PanelId) {
+        let tree = self
+            .tree
+            .get_or_insert_with(|| egui_tiles::Tree::empty("workbench"));
+        let tile_id = tree.tiles.insert_pane(PaneEntry { panel_id });
+        self.panel_tile_map.insert(panel_id, tile_id);
+        ui::insert_pane_into_tree(tree, tile_id);
+    }
+
+    /// Make the secondary windows exactly those of a layout snapshot. Windows still open keep
+    /// their OS window; others are spawned again, and windows missing from the snapshot are
+    /// despawned by [`dock_windows_system`].
+    pub(super) fn restore_windows(&mut self, windows: Vec<DockWindow>) {
+        let live = std::mem::take(&mut self.windows);
+        self.windows = windows
+            .into_iter()
+            .map(|mut window| {
+                window.entity = live
+                    .iter()
+                    .find(|w| w.id == window.id)
+                    .and_then(|w| w.entity);
+                window.focus_requested = false;
+                window.dirty_close.clear();
+                window
+            })
+            .collect();
+    }
+
+    /// Dock every panel of window `index` back and forget the window.
+    fn attach_window(&mut self, index: usize) {
+        let window = self.windows.remove(index);
+        for panel_id in window.panel_ids() {
+            self.dock_back(panel_id);
+        }
+    }
+}
+
+/// Tab dragged out of the main window and released outside it. `dragged` tracks the dragged
+/// tab across frames, since egui_tiles ends the drag on the release frame.
+pub(super) fn drag_out(
+    ctx: &egui::Context,
+    tree: &egui_tiles::Tree<PaneEntry>,
+    dragged: &mut Option<TileId>,
+) -> Option<TileId> {
+    if let Some(tile) = tree.dragged_id(ctx) {
+        *dragged = Some(tile);
+    }
+    let (released, down, pos, viewport) = ctx.input(|i| {
+        (
+            i.pointer.any_released(),
+            i.pointer.any_down(),
+            i.pointer.latest_pos(),
+            i.viewport_rect(),
+        )
+    });
+    if !released {
+        if !down {
+            *dragged = None;
+        }
+        return None;
+    }
+    dragged
+        .take()
+        .filter(|_| pos.is_some_and(|p| !viewport.contains(p)))
+}
+
+/// Spawns OS windows for new dock windows, docks back panels of closed ones, despawns
+/// windows that were emptied or dropped, and records size and position for the layout file.
+pub(crate) fn dock_windows_system(
+    mut commands: Commands,
+    mut state: ResMut<TileLayoutState>,
+    mut schedules: ResMut<Schedules>,
+    mut windows: Query<&mut Window>,
+    entities: Query<(Entity, &DockWindowEntity)>,
+) {
+    let closed: Vec<usize> = (0..state.windows.len())
+        .filter(|&i| {
+            let window = &state.windows[i];
+            window.entity.is_some_and(|e| !windows.contains(e)) || window.panel_ids().is_empty()
+        })
+        .collect();
+    for index in closed.into_iter().rev() {
+        state.attach_window(index);
+    }
+
+    for (entity, marker) in &entities {
+        if !state.windows.iter().any(|w| w.entity == Some(marker.0)) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for window in &mut state.windows {
+        let Some(entity) = window.entity else {
+            window.entity = Some(spawn_window(&mut commands, &mut schedules, window));
+            continue;
+        };
+        let Ok(mut os_window) = windows.get_mut(entity) else {
+            continue;
+        };
+        window.size = [os_window.width(), os_window.height()];
+        if let WindowPosition::At(pos) = os_window.position {
+            window.position = Some([pos.x, pos.y]);
+        }
+        if std::mem::take(&mut window.focus_requested) {
+            os_window.focused = true;
+        }
+    }
+}
+
+/// Spawn the OS window and its UI camera, adding the window's egui pass schedule once.
+fn spawn_window(commands: &mut Commands, schedules: &mut Schedules, window: &DockWindow) -> Entity {
+    let id = window.id;
+    let position = match window.position {
+        Some([x, y]) => WindowPosition::At(IVec2::new(x, y)),
+        None => WindowPosition::Automatic,
+    };
+    let entity = commands
+        .spawn(Window {
+            title: "Workbench".into(),
+            resolution: (window.size[0] as u32, window.size[1] as u32).into(),
+            position,
+            ..default()
+        })
+        .id();
+    commands.entity(entity).insert(DockWindowEntity(entity));
+    commands.spawn((
+        Camera2d,
+        Camera {
+            order: 100 + id as isize,
+            ..default()
+        },
+        RenderTarget::Window(WindowRef::Entity(entity)),
+        EguiContext::default(),
+        EguiMultipassSchedule::new(DockWindowPass(id)),
+        DockWindowEntity(entity),
+        DockWindowCamera(id),
+        WorkbenchInternal,
+        Name::new(format!("workbench_window_{id}_camera")),
+    ));
+    if schedules.get(DockWindowPass(id)).is_none() {
+        schedules.add_systems(DockWindowPass(id), move |world: &mut World| {
+            dock_window_ui(world, id);
+        });
+    }
+    entity
+}
+
+/// Draw the tile tree of window `id` in its own egui context.
+fn dock_window_ui(world: &mut World, id: u32) {
+    let ctx = {
+        let mut query = world.query::<(&mut EguiContext, &DockWindowCamera)>();
+        let Some((mut context, _)) = query.iter_mut(world).find(|(_, w)| w.0 == id) else {
+            return;
+        };
+        context.get_mut().clone()
+    };
+    let empty = egui_tiles::Tree::empty(format!("workbench_window_{id}"));
+    let (mut tree, mut dirty_close, mut panels) = {
+        let mut state = world.resource_mut::<TileLayoutState>();
+        let panels = std::mem::take(&mut state.panels);
+        let Some(window) = state.windows.iter_mut().find(|w| w.id == id) else {
+            state.panels = panels;
+            return;
+        };
+        let tree = std::mem::replace(&mut window.tree, empty);
+        (tree, std::mem::take(&mut window.dirty_close), panels)
+    };
+    let focused = world
+        .get_resource::<super::FocusedPanel>()
+        .and_then(|f| f.0.clone());
+
+    let mut outcome = ui::TreeOutcome::default();
+    egui::CentralPanel::default().show(&ctx, |ui| {
+        outcome = ui::window_tree_ui(ui, world, &mut panels, &mut tree, focused, &mut dirty_close);
+    });
+    if let Some(settings) = world.get_resource::<crate::config::WorkbenchSettings>() {
+        crate::accessibility::draw_focus_outline(&ctx, &settings.accessibility);
+    }
+
+    let dock_back = outcome
+        .dock_back
+        .and_then(|tile| match tree.tiles.get(tile) {
+            Some(Tile::Pane(pane)) => Some(pane.panel_id),
+            _ => None,
+        });
+    {
+        let mut state = world.resource_mut::<TileLayoutState>();
+        state.panels = panels;
+        if let Some(window) = state.windows.iter_mut().find(|w| w.id == id) {
+            window.tree = tree;
+            window.dirty_close = dirty_close;
+        }
+    }
+    if let Some((before_tree, closed)) = outcome.closed {
+        let after = world.resource::<TileLayoutState>().snapshot();
+        if let Some(after) = after {
+            let mut before = after.clone();
+            if let Some(window) = before.windows.iter_mut().find(|w| w.id == id) {
+                window.tree = before_tree;
+            }
+            let desc = format!("Close {}", closed.join(", "));
+            LayoutUndoStack::record(world, LayoutUndoAction::new(desc, before, after));
+        }
+    }
+    if let Some(panel_id) = dock_back {
+        let state = world.resource::<TileLayoutState>();
+        let panel = state.panels.get(&panel_id).map(|p| p.id().to_string());
+        let desc = format!("Dock {}", panel.unwrap_or_default());
+        super::record_layout_change(world, desc, |state| {
+            let Some(index) = state.window_of(panel_id) else {
+                return false;
+            };
+            state.close_in_window(index, panel_id);
+            state.dock_back(panel_id);
+            true
+        });
+    }
+    if let Some(panel) = outcome.help {
+        crate::help::show_panel_help(world, panel);
+    }
+    if let Some(panel) = outcome.clicked
+        && let Some(mut focus) = world.get_resource_mut::<super::FocusedPanel>()
+    {
+        focus.0 = Some(panel);
+    }
+}
@@ -0,0 +1,201 @@
+//! Editor camera controller: orbit, fly, pan, zoom and focus-selected for scene cameras.
+//!
+//! Add [`EditorCameraController`] to any camera. It only responds in Edit mode while the
+//! Scene View or Game View is hovered; sensitivities come from `WorkbenchSettings`.
+
+use crate::game_view::{GameViewFocus, SceneViewFocus};
+use crate::inspector::InspectorSelection;
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+
+/// Keep pitch just short of straight up/down so yaw stays well defined.
+const MAX_PITCH: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// Sensitivities for [`EditorCameraController`], stored in `WorkbenchSettings`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct EditorCameraSettings {
+    /// Radians per pixel of mouse motion when orbiting or looking around.
+    pub look_sensitivity: f32,
+    /// Pan distance per pixel, as a fraction of the orbit distance.
+    pub pan_sensitivity: f32,
+    /// Fraction of the orbit distance zoomed per scroll line.
+    pub zoom_sensitivity: f32,
+    /// Fly speed in units per second (Shift doubles it).
+    pub fly_speed: f32,
+}
+
+impl Default for EditorCameraSettings {
+    fn default() -> Self {
+        Self {
+            look_sensitivity: 0.005,
+            pan_sensitivity: 0.0015,
+            zoom_sensitivity: 0.1,
+            fly_speed: 5.0,
+        }
+    }
+}
+
+/// Edit-mode camera controls for the camera it is attached to:
+///
+/// - Right-drag: look around; hold W/A/S/D/Q/E while dragging to fly
+/// - Alt + left-drag: orbit around the focus point
+/// - Middle-drag: pan
+/// - Scroll: zoom toward the focus point
+/// - F: focus the entity selected in the inspector
+#[derive(Component, Debug, Clone)]
+pub struct EditorCameraController {
+    /// Point the camera orbits around and zooms toward.
+    pub focus: Vec3,
+    /// Distance from the camera to `focus`.
+    pub distance: f32,
+    yaw: f32,
+    pitch: f32,
+    initialized: bool,
+    /// A drag that started over a view is still held (keeps working outside the view).
+    dragging: bool,
+}
+
+impl Default for EditorCameraController {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            distance: 10.0,
+            yaw: 0.0,
+            pitch: 0.0,
+            initialized: false,
+            dragging: false,
+        }
+    }
+}
+
+impl EditorCameraController {
+    /// Adopt the camera's current placement, keeping `distance`.
+    fn sync_from_transform(&mut self, transform: &Transform) {
+        let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.focus = transform.translation + transform.forward() * self.distance;
+        self.initialized = true;
+    }
+
+    fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.0)
+    }
+
+    fn eye(&self) -> Vec3 {
+        self.focus + self.rotation() * Vec3::Z * self.distance
+    }
+
+    fn turn(&mut self, delta: Vec2, sensitivity: f32) {
+        self.yaw -= delta.x * sensitivity;
+        self.pitch = (self.pitch - delta.y * sensitivity).clamp(-MAX_PITCH, MAX_PITCH);
+    }
+
+    /// Rotate in place around the eye, then move it with WASD/QE.
+    fn look_and_fly(&mut self, delta: Vec2, move_dir: Vec3, settings: &EditorCameraSettings) {
+        let eye = self.eye();
+        self.turn(delta, settings.look_sensitivity);
+        let rotation = self.rotation();
+        let offset = rotation * Vec3::new(move_dir.x, 0.0, move_dir.z) + Vec3::Y * move_dir.y;
+        self.focus = eye + offset - rotation * Vec3::Z * self.distance;
+    }
+
+    fn pan(&mut self, delta: Vec2, settings: &EditorCameraSettings) {
+        let rotation = self.rotation();
+        let scale = settings.pan_sensitivity * self.distance;
+        self.focus += rotation * Vec3::new(-delta.x * scale, delta.y * scale, 0.0);
+    }
+
+    fn zoom(&mut self, lines: f32, settings: &EditorCameraSettings) {
+        let factor = (1.0 - lines * settings.zoom_sensitivity).clamp(0.5, 1.5);
+        self.distance = (self.distance * factor).max(0.05);
+    }
+
+    fn apply(&self, transform: &mut Transform) {
+        transform.rotation = self.rotation();
+        transform.translation = self.eye();
+    }
+}
+
+/// WASD/QE fly direction scaled by speed and frame time (camera-local x/z, world y).
+fn fly_direction(keys: &ButtonInput<KeyCode>, speed: f32, dt: f32) -> Vec3 {
+    let axis = |pos: KeyCode, neg: KeyCode| {
+        f32::from(u8::from(keys.pressed(pos))) - f32::from(u8::from(keys.pressed(neg)))
+    };
+    let dir = Vec3::new(
+        axis(KeyCode::KeyD, KeyCode::KeyA),
+        axis(KeyCode::KeyE, KeyCode::KeyQ),
+        axis(KeyCode::KeyS, KeyCode::KeyW),
+    );
+    let boost = if keys.pressed(KeyCode::ShiftLeft) {
+        2.0
+    } else {
+        1.0
+    };
+    dir.normalize_or_zero() * speed * boost * dt
+}
+
+/// Scroll distance in lines.
+fn scroll_lines(scroll: &AccumulatedMouseScroll) -> f32 {
+    match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / 40.0,
+    }
+}
+
+/// Drives every [`EditorCameraController`]. Registered to run only in Edit mode.
+pub fn editor_camera_system(
+    settings: Res<crate::config::WorkbenchSettings>,
+    scene_focus: Option<Res<SceneViewFocus>>,
+    game_focus: Option<Res<GameViewFocus>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    time: Res<Time<Real>>,
+    selection: Option<Res<InspectorSelection>>,
+    targets: Query<&GlobalTransform, Without<EditorCameraController>>,
+    mut cameras: Query<(&mut EditorCameraController, &mut Transform)>,
+) {
+    let settings = &settings.editor_camera;
+    let hovered = scene_focus.is_some_and(|f| f.hovered) || game_focus.is_some_and(|f| f.hovered);
+    let buttons = [MouseButton::Left, MouseButton::Right, MouseButton::Middle];
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let focus_target = selection
+        .filter(|_| hovered && keys.just_pressed(KeyCode::KeyF))
+        .and_then(|s| s.selected.as_slice().first().copied())
+        .and_then(|entity| targets.get(entity).ok())
+        .map(GlobalTransform::translation);
+
+    for (mut controller, mut transform) in &mut cameras {
+        if !controller.initialized {
+            controller.sync_from_transform(&transform);
+        }
+        if mouse.any_just_pressed(buttons) {
+            controller.dragging = hovered;
+        } else if !mouse.any_pressed(buttons) {
+            controller.dragging = false;
+        }
+        let before = (controller.focus, controller.distance, controller.rotation());
+
+        if controller.dragging && mouse.pressed(MouseButton::Right) {
+            let dir = fly_direction(&keys, settings.fly_speed, time.delta_secs());
+            controller.look_and_fly(motion.delta, dir, settings);
+        } else if controller.dragging && alt && mouse.pressed(MouseButton::Left) {
+            controller.turn(motion.delta, settings.look_sensitivity);
+        } else if controller.dragging && mouse.pressed(MouseButton::Middle) {
+            controller.pan(motion.delta, settings);
+        }
+        if hovered {
+            controller.zoom(scroll_lines(&scroll), settings);
+        }
+        if let Some(target) = focus_target {
+            controller.focus = target;
+        }
+
+        if before != (controller.focus, controller.distance, controller.rotation()) {
+            controller.apply(&mut transform);
+        }
+    }
+}
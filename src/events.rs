@@ -0,0 +1,257 @@
+//! Events panel: a scrolling timeline of watched Bevy messages and observer events.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use crate::dock::{PanelSlot, WorkbenchPanel};
+
+/// Default maximum number of entries kept in the event log.
+const DEFAULT_MAX_ENTRIES: usize = 5_000;
+
+/// One recorded message or event.
+#[derive(Debug, Clone)]
+pub struct EventLogEntry {
+    /// Real time since startup, in seconds.
+    pub time: f32,
+    /// Short type name of the message/event.
+    pub type_name: String,
+    /// `Debug` output of the payload.
+    pub payload: String,
+}
+
+/// A watched message/event type.
+#[derive(Debug, Clone)]
+pub struct WatchedType {
+    pub type_name: String,
+    /// Whether new occurrences of this type are recorded.
+    pub enabled: bool,
+}
+
+/// Resource holding the event timeline shown by [`EventsPanel`].
+#[derive(Resource)]
+pub struct EventLog {
+    /// Recorded entries (oldest first).
+    pub entries: VecDeque<EventLogEntry>,
+    /// Maximum number of entries kept in `entries`.
+    pub max_entries: usize,
+    /// Types registered with `watch_message` / `watch_event`.
+    pub watched: Vec<WatchedType>,
+    /// When true, nothing new is recorded.
+    pub paused: bool,
+    /// Substring filter on type name and payload.
+    pub filter_text: String,
+    pub auto_scroll: bool,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            watched: Vec::new(),
+            paused: false,
+            filter_text: String::new(),
+            auto_scroll: true,
+        }
+    }
+}
+
+impl EventLog {
+    /// Register a type so it appears in the type filter menu. Returns false if it already was.
+    pub fn watch(&mut self, type_name: String) -> bool {
+        if self.watched.iter().any(|w| w.type_name == type_name) {
+            return false;
+        }
+        self.watched.push(WatchedType {
+            type_name,
+            enabled: true,
+        });
+        true
+    }
+
+    /// Record an occurrence of a watched type (ignored while paused or disabled).
+    pub fn record(&mut self, time: f32, type_name: &str, payload: String) {
+        if self.paused || !self.is_enabled(type_name) {
+            return;
+        }
+        self.entries.push_back(EventLogEntry {
+            time,
+            type_name: type_name.to_string(),
+            payload,
+        });
+        while self.entries.len() > self.max_entries {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Whether occurrences of `type_name` are currently recorded.
+    pub fn is_enabled(&self, type_name: &str) -> bool {
+        self.watched
+            .iter()
+            .find(|w| w.type_name == type_name)
+            .is_none_or(|w| w.enabled)
+    }
+
+    /// Remove all recorded entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Indices of entries matching the filter text.
+    fn visible_indices(&self) -> Vec<usize> {
+        let needle = self.filter_text.to_lowercase();
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                needle.is_empty()
+                    || e.type_name.to_lowercase().contains(&needle)
+                    || e.payload.to_lowercase().contains(&needle)
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Short, module-less name of `T` (e.g. `Damage` instead of `my_game::combat::Damage`).
+pub fn short_type_name<T>() -> String {
+    let full = std::any::type_name::<T>();
+    let mut out = String::with_capacity(full.len());
+    let mut segment_start = 0;
+    for (i, c) in full.char_indices() {
+        if matches!(c, '<' | '>' | ',' | ' ' | '(' | ')' | '[' | ']' | '&' | ';') {
+            out.push_str(last_path_segment(&full[segment_start..i]));
+            out.push(c);
+            segment_start = i + c.len_utf8();
+        }
+    }
+    out.push_str(last_path_segment(&full[segment_start..]));
+    out
+}
+
+fn last_path_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Records every `M` message written this frame.
+pub(crate) fn record_messages_system<M: Message + Debug>(
+    mut reader: MessageReader<M>,
+    mut log: ResMut<EventLog>,
+    time: Res<Time<Real>>,
+) {
+    let type_name = short_type_name::<M>();
+    let now = time.elapsed_secs();
+    for message in reader.read() {
+        log.record(now, &type_name, format!("{message:?}"));
+    }
+}
+
+/// Observer recording every triggered `E` event.
+pub(crate) fn record_event_observer<E: Event + Debug>(
+    event: On<E>,
+    mut log: ResMut<EventLog>,
+    time: Res<Time<Real>>,
+) {
+    let payload = format!("{:?}", event.event());
+    log.record(time.elapsed_secs(), &short_type_name::<E>(), payload);
+}
+
+/// Built-in panel showing the [`EventLog`] timeline.
+#[derive(Default)]
+pub struct EventsPanel;
+
+impl WorkbenchPanel for EventsPanel {
+    fn id(&self) -> &str {
+        "workbench_events"
+    }
+
+    fn title(&self) -> String {
+        "Events".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let Some(mut log) = world.get_resource_mut::<EventLog>() else {
+            ui.label("No watched events");
+            return;
+        };
+        events_toolbar_ui(ui, &mut log);
+        ui.separator();
+        events_list_ui(ui, &log);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Bottom
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+/// Toolbar: clear, pause, type toggles, auto-scroll and filter.
+fn events_toolbar_ui(ui: &mut egui::Ui, log: &mut EventLog) {
+    ui.horizontal(|ui| {
+        if ui.button("🗑 Clear").clicked() {
+            log.clear();
+        }
+        let pause_label = if log.paused {
+            "▶ Resume"
+        } else {
+            "⏸ Pause"
+        };
+        if ui.button(pause_label).clicked() {
+            log.paused = !log.paused;
+        }
+        ui.menu_button(format!("Types ({})", log.watched.len()), |ui| {
+            for watched in &mut log.watched {
+                ui.checkbox(&mut watched.enabled, &watched.type_name);
+            }
+        });
+        ui.checkbox(&mut log.auto_scroll, "Auto-scroll");
+        ui.separator();
+        ui.add(
+            egui::TextEdit::singleline(&mut log.filter_text)
+                .hint_text("Filter...")
+                .desired_width(160.0),
+        );
+        ui.label(format!("{} entries", log.entries.len()));
+    });
+}
+
+/// Virtualized list of entries.
+fn events_list_ui(ui: &mut egui::Ui, log: &EventLog) {
+    let visible = log.visible_indices();
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .stick_to_bottom(log.auto_scroll)
+        .show_rows(ui, row_height, visible.len(), |ui, range| {
+            for &index in &visible[range] {
+                event_row_ui(ui, &log.entries[index]);
+            }
+        });
+}
+
+fn event_row_ui(ui: &mut egui::Ui, entry: &EventLogEntry) {
+    ui.horizontal(|ui| {
+        ui.label(
+            egui::RichText::new(format!("{:>9.3}s", entry.time))
+                .monospace()
+                .weak(),
+        );
+        ui.label(
+            egui::RichText::new(&entry.type_name)
+                .monospace()
+                .color(ui.visuals().hyperlink_color),
+        );
+        ui.add(egui::Label::new(egui::RichText::new(&entry.payload).monospace()).truncate())
+            .on_hover_text(&entry.payload);
+    });
+}
@@ -0,0 +1,192 @@
+//! Non-blocking file dialogs (native and wasm) behind a request/response resource.
+//!
+//! Queue a [`FileDialogRequest`] on [`FileDialogs`] and read the matching
+//! [`FileDialogResponse`] message (same `id`) in a later frame.
+
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future};
+use std::path::PathBuf;
+
+/// Whether the dialog picks an existing file or a save destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDialogKind {
+    Open,
+    Save,
+}
+
+/// A file dialog to show.
+#[derive(Debug, Clone)]
+pub struct FileDialogRequest {
+    /// Caller-chosen ID echoed in the [`FileDialogResponse`].
+    pub id: String,
+    pub kind: FileDialogKind,
+    pub title: String,
+    /// `(name, extensions)` filters, e.g. `("JSON", ["json"])`.
+    pub filters: Vec<(String, Vec<String>)>,
+    /// Suggested file name (Save).
+    pub file_name: Option<String>,
+    /// Bytes written to the chosen file (Save). On wasm this is the only way to save.
+    pub contents: Option<Vec<u8>>,
+    /// Read the picked file into [`PickedFile::contents`] (Open).
+    pub read_contents: bool,
+}
+
+impl FileDialogRequest {
+    /// Pick an existing file.
+    pub fn open(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            kind: FileDialogKind::Open,
+            title: String::new(),
+            filters: Vec::new(),
+            file_name: None,
+            contents: None,
+            read_contents: false,
+        }
+    }
+
+    /// Choose a destination and write `contents` to it.
+    pub fn save(id: impl Into<String>, contents: Vec<u8>) -> Self {
+        Self {
+            kind: FileDialogKind::Save,
+            contents: Some(contents),
+            ..Self::open(id)
+        }
+    }
+
+    /// Set the dialog title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Add an extension filter.
+    pub fn with_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        let extensions = extensions.iter().map(|e| e.to_string()).collect();
+        self.filters.push((name.into(), extensions));
+        self
+    }
+
+    /// Set the suggested file name.
+    pub fn with_file_name(mut self, name: impl Into<String>) -> Self {
+        self.file_name = Some(name.into());
+        self
+    }
+
+    /// Read the picked file's bytes (required on wasm, where there are no paths).
+    pub fn reading_contents(mut self) -> Self {
+        self.read_contents = true;
+        self
+    }
+}
+
+/// The file chosen in a dialog.
+#[derive(Debug, Clone)]
+pub struct PickedFile {
+    pub name: String,
+    /// Filesystem path (always `None` on wasm).
+    pub path: Option<PathBuf>,
+    /// File bytes if [`FileDialogRequest::read_contents`] was set.
+    pub contents: Option<Vec<u8>>,
+    /// Why writing the contents failed (Save); the dialog itself still succeeded.
+    pub error: Option<String>,
+}
+
+/// Result of a [`FileDialogRequest`]; `file` is `None` when the dialog was cancelled.
+#[derive(Message, Debug, Clone)]
+pub struct FileDialogResponse {
+    pub id: String,
+    pub file: Option<PickedFile>,
+}
+
+/// Resource queueing file dialogs. Dialogs run on the async compute pool so the frame never blocks.
+#[derive(Resource, Default)]
+pub struct FileDialogs {
+    pending: Vec<Task<FileDialogResponse>>,
+}
+
+impl FileDialogs {
+    /// Show a dialog; the response arrives as a [`FileDialogResponse`] message.
+    pub fn request(&mut self, request: FileDialogRequest) {
+        self.pending
+            .push(AsyncComputeTaskPool::get().spawn(run_dialog(request)));
+    }
+
+    /// Whether a dialog is still open.
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+async fn run_dialog(request: FileDialogRequest) -> FileDialogResponse {
+    let mut dialog = rfd::AsyncFileDialog::new();
+    if !request.title.is_empty() {
+        dialog = dialog.set_title(&request.title);
+    }
+    for (name, extensions) in &request.filters {
+        dialog = dialog.add_filter(name, extensions);
+    }
+    if let Some(name) = &request.file_name {
+        dialog = dialog.set_file_name(name);
+    }
+    let handle = match request.kind {
+        FileDialogKind::Open => dialog.pick_file().await,
+        FileDialogKind::Save => dialog.save_file().await,
+    };
+    let Some(handle) = handle else {
+        return FileDialogResponse {
+            id: request.id,
+            file: None,
+        };
+    };
+
+    let mut file = PickedFile {
+        name: handle.file_name(),
+        path: handle_path(&handle),
+        contents: None,
+        error: None,
+    };
+    match request.kind {
+        FileDialogKind::Open if request.read_contents => {
+            file.contents = Some(handle.read().await);
+        }
+        FileDialogKind::Save => {
+            if let Some(bytes) = &request.contents
+                && let Err(e) = handle.write(bytes).await
+            {
+                file.error = Some(e.to_string());
+            }
+        }
+        FileDialogKind::Open => {}
+    }
+    FileDialogResponse {
+        id: request.id,
+        file: Some(file),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn handle_path(handle: &rfd::FileHandle) -> Option<PathBuf> {
+    Some(handle.path().to_path_buf())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn handle_path(_handle: &rfd::FileHandle) -> Option<PathBuf> {
+    None
+}
+
+/// Emits responses for dialogs that have closed.
+pub fn file_dialog_poll_system(
+    mut dialogs: ResMut<FileDialogs>,
+    mut responses: MessageWriter<FileDialogResponse>,
+) {
+    dialogs
+        .pending
+        .retain_mut(|task| match block_on(future::poll_once(task)) {
+            Some(response) => {
+                responses.write(response);
+                false
+            }
+            None => true,
+        });
+}
@@ -1,12 +1,15 @@
 //! Font management for the workbench editor.
 //!
-//! Embeds Source Han Sans CN as the default CJK font and provides
-//! configuration to override with a custom font file.
+//! Embeds Source Han Sans CN as the default CJK font (`embedded-cjk-font` feature) and
+//! provides configuration to override with a custom font file. Without the embedded font,
+//! the `system-fonts` feature looks up an installed CJK font when the locale needs one.
 
+use crate::i18n::Locale;
 use bevy::prelude::*;
-use bevy_egui::EguiContexts;
+use bevy_egui::EguiContext;
 
 /// Embedded CJK font (Source Han Sans CN Regular, ~8 MB).
+#[cfg(feature = "embedded-cjk-font")]
 const EMBEDDED_CJK_FONT: &[u8] = include_bytes!("../fonts/SourceHanSansCN-Regular.otf");
 
 /// Font configuration stored in settings.
@@ -18,43 +21,103 @@ pub struct FontConfig {
     pub custom_font_path: Option<String>,
 }
 
-/// Resource tracking whether fonts have been installed into the egui context.
+/// Resource tracking whether fonts have been installed into the egui contexts.
 #[derive(Resource, Default)]
 pub struct FontState {
     pub installed: bool,
+    /// Fonts last installed, for egui contexts added later (secondary windows).
+    fonts: Option<egui::FontDefinitions>,
 }
 
-/// System that installs CJK font into the egui context on first run.
-pub fn install_fonts_system(
-    mut contexts: EguiContexts,
-    settings: Res<crate::config::WorkbenchSettings>,
-    mut font_state: ResMut<FontState>,
-) {
-    if font_state.installed {
-        return;
-    }
-    let Ok(ctx) = contexts.ctx_mut() else { return };
+/// Embedded CJK font.
+#[cfg(feature = "embedded-cjk-font")]
+fn embedded_cjk_font() -> Option<egui::FontData> {
+    Some(egui::FontData::from_static(EMBEDDED_CJK_FONT))
+}
 
-    let font_data = if let Some(ref path) = settings.font.custom_font_path {
-        match std::fs::read(path) {
-            Ok(data) => {
-                info!("Loaded custom font from: {path}");
-                data
-            }
-            Err(e) => {
-                warn!("Failed to load custom font '{path}': {e}, using embedded CJK font");
-                EMBEDDED_CJK_FONT.to_vec()
-            }
-        }
-    } else {
-        EMBEDDED_CJK_FONT.to_vec()
+/// Built without the `embedded-cjk-font` feature.
+#[cfg(not(feature = "embedded-cjk-font"))]
+fn embedded_cjk_font() -> Option<egui::FontData> {
+    None
+}
+
+/// Well-known CJK families, tried in order.
+#[cfg(feature = "system-fonts")]
+const SYSTEM_CJK_FAMILIES: &[&str] = &[
+    "Noto Sans CJK SC",
+    "Noto Sans SC",
+    "Source Han Sans SC",
+    "Source Han Sans CN",
+    "PingFang SC",
+    "Hiragino Sans GB",
+    "Microsoft YaHei",
+    "WenQuanYi Micro Hei",
+    "SimHei",
+];
+
+/// Find an installed CJK font.
+#[cfg(feature = "system-fonts")]
+fn system_cjk_font() -> Option<egui::FontData> {
+    let mut db = fontdb::Database::new();
+    db.load_system_fonts();
+    let families: Vec<fontdb::Family> = SYSTEM_CJK_FAMILIES
+        .iter()
+        .map(|name| fontdb::Family::Name(*name))
+        .collect();
+    let id = db.query(&fontdb::Query {
+        families: &families,
+        ..Default::default()
+    })?;
+    let family = db
+        .face(id)
+        .and_then(|f| f.families.first().map(|(n, _)| n.clone()));
+    let (data, index) = db.with_face_data(id, |data, index| (data.to_vec(), index))?;
+    info!("Using system CJK font: {}", family.unwrap_or_default());
+    let mut font = egui::FontData::from_owned(data);
+    font.index = index;
+    Some(font)
+}
+
+#[cfg(not(feature = "system-fonts"))]
+fn system_cjk_font() -> Option<egui::FontData> {
+    None
+}
+
+/// Embedded font, else (when the locale needs CJK glyphs) a discovered system font.
+fn fallback_cjk_font(locale: Locale) -> Option<egui::FontData> {
+    embedded_cjk_font().or_else(|| locale.needs_cjk().then(system_cjk_font).flatten())
+}
+
+/// Custom font if configured and readable, else the fallback font (if any).
+fn cjk_font_data(custom_path: Option<&str>, locale: Locale) -> Option<egui::FontData> {
+    let Some(path) = custom_path else {
+        return fallback_cjk_font(locale);
     };
+    match std::fs::read(path) {
+        Ok(data) => {
+            info!("Loaded custom font from: {path}");
+            Some(egui::FontData::from_owned(data))
+        }
+        Err(e) => {
+            warn!("Failed to load custom font '{path}': {e}, using fallback CJK font");
+            fallback_cjk_font(locale)
+        }
+    }
+}
 
+/// Fonts for the current settings: the icon font (with `icon-font`) and the CJK fallback.
+fn font_definitions(settings: &crate::config::WorkbenchSettings) -> egui::FontDefinitions {
     let mut fonts = egui::FontDefinitions::default();
-    fonts.font_data.insert(
-        "cjk".to_owned(),
-        egui::FontData::from_owned(font_data).into(),
-    );
+    #[cfg(feature = "icon-font")]
+    crate::bench_ui::install_icon_font(&mut fonts);
+    let Some(font_data) = cjk_font_data(settings.font.custom_font_path.as_deref(), settings.locale)
+    else {
+        // No CJK font to add; reset in case a previous one was installed.
+        return fonts;
+    };
+    fonts
+        .font_data
+        .insert("cjk".to_owned(), std::sync::Arc::new(font_data));
     // Append CJK as fallback for Proportional family
     fonts
         .families
@@ -67,8 +130,28 @@ pub fn install_fonts_system(
         .entry(egui::FontFamily::Monospace)
         .or_default()
         .push("cjk".to_owned());
-
-    ctx.set_fonts(fonts);
-    font_state.installed = true;
     info!("CJK font installed into egui context");
+    fonts
+}
+
+/// System that installs the fonts into every egui context on first run (and after a settings
+/// change), and into each context added later.
+pub fn install_fonts_system(
+    mut contexts: Query<&mut EguiContext>,
+    settings: Res<crate::config::WorkbenchSettings>,
+    mut font_state: ResMut<FontState>,
+) {
+    let reinstall = !font_state.installed;
+    if reinstall {
+        font_state.fonts = Some(font_definitions(&settings));
+        font_state.installed = true;
+    }
+    let Some(fonts) = &font_state.fonts else {
+        return;
+    };
+    for mut context in &mut contexts {
+        if reinstall || context.is_added() {
+            context.get_mut().set_fonts(fonts.clone());
+        }
+    }
 }
@@ -6,7 +6,37 @@ use bevy::prelude::*;
 use bevy::render::render_resource::TextureFormat;
 use bevy::state::prelude::DespawnOnEnter;
 
-use crate::dock::{TileLayoutState, WorkbenchPanel};
+mod brush;
+mod eyedropper;
+mod game_window;
+mod handles;
+mod input_record;
+mod overlay;
+mod point_handles;
+mod readback;
+mod rect_handles;
+mod render_config;
+mod ruler;
+mod scene_view;
+
+pub use brush::{BrushDab, BrushFn, GameViewBrushes};
+pub use game_window::{GameWindow, game_window_closed_system, game_window_focus_system};
+pub use input_record::{
+    InputFrame, InputRecorder, InputRecording, RecorderMode, input_record_start_system,
+    input_record_stop_system, input_record_system,
+};
+pub use overlay::{
+    EDITOR_GIZMO_LAYER, GridPlane, ViewportGizmos, ViewportOverlay, ViewportOverlays,
+    draw_viewport_overlay_system, editor_gizmo_layer_system,
+};
+pub use point_handles::PointHandles;
+pub use readback::{GameViewPixels, GameViewReadback, ReadbackRequest, game_view_readback_system};
+pub use rect_handles::{RectHandles, RectShape};
+pub use render_config::{GameViewConfig, apply_game_view_config_system};
+pub use ruler::GameViewRuler;
+pub use scene_view::{SceneViewFocus, SceneViewPanel, SceneViewState, scene_view_camera_system};
+
+use crate::dock::{PanelSlot, TileLayoutState, WorkbenchPanel};
 use crate::mode::EditorMode;
 use crate::theme::gray;
 
@@ -41,6 +71,8 @@ pub struct GameViewState {
     pub egui_texture_id: Option<egui::TextureId>,
     /// Resolution of the render target.
     pub resolution: UVec2,
+    /// The separate game window while playing in play-in-window mode.
+    pub game_window: Option<Entity>,
 }
 
 impl Default for GameViewState {
@@ -49,6 +81,7 @@ impl Default for GameViewState {
             render_target: Handle::default(),
             egui_texture_id: None,
             resolution: UVec2::new(1280, 720),
+            game_window: None,
         }
     }
 }
@@ -60,9 +93,62 @@ impl Plugin for GameViewPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(GameViewState::default())
             .insert_resource(GameViewFocus::default())
-            .add_systems(Startup, setup_render_target)
+            .init_resource::<GameViewConfig>()
+            .init_resource::<SceneViewState>()
+            .init_resource::<SceneViewFocus>()
+            .init_resource::<ViewportOverlays>()
+            .init_resource::<GameViewRuler>()
+            .init_resource::<RectHandles>()
+            .init_resource::<PointHandles>()
+            .init_resource::<GameViewBrushes>()
+            .init_resource::<eyedropper::EyedropperSamples>()
+            .init_resource::<GameViewReadback>()
+            .add_message::<GameViewPixels>()
+            .init_resource::<InputRecorder>()
+            .init_gizmo_group::<ViewportGizmos>()
+            .add_systems(
+                Startup,
+                (
+                    setup_render_target,
+                    scene_view::setup_scene_view_target,
+                    overlay::setup_viewport_gizmos,
+                ),
+            )
             .add_systems(OnEnter(EditorMode::Play), activate_game_view_camera)
-            .add_systems(OnEnter(EditorMode::Edit), deactivate_external_camera);
+            .add_systems(
+                OnEnter(EditorMode::Play),
+                input_record_start_system
+                    .run_if(crate::mode::on_fresh_play)
+                    .before(crate::mode::on_enter_play),
+            )
+            .add_systems(OnEnter(EditorMode::Edit), input_record_stop_system)
+            .add_systems(
+                Update,
+                input_record_system
+                    .run_if(in_state(EditorMode::Play))
+                    .before(crate::mode::run_game_schedule_system),
+            )
+            .add_systems(OnEnter(EditorMode::Edit), deactivate_external_camera)
+            .add_systems(
+                PreUpdate,
+                (game_window_closed_system, game_window_focus_system).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    scene_view_camera_system,
+                    editor_gizmo_layer_system,
+                    draw_viewport_overlay_system,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    game_view_readback_system,
+                    eyedropper::eyedropper_readback_system,
+                ),
+            )
+            .add_systems(PostUpdate, apply_game_view_config_system);
     }
 }
 
@@ -82,16 +168,23 @@ fn setup_render_target(mut images: ResMut<Assets<Image>>, mut state: ResMut<Game
 ///
 /// If `ExternalGameCamera` resource exists, hijacks that camera by redirecting its
 /// render target to the game view texture. Otherwise, spawns a new internal camera.
+/// In play-in-window mode the camera targets a newly opened [`GameWindow`] instead.
 fn activate_game_view_camera(
     mut commands: Commands,
-    state: Res<GameViewState>,
+    mut state: ResMut<GameViewState>,
     existing: Query<(), With<GameViewCamera>>,
     external: Option<Res<ExternalGameCamera>>,
+    config: Option<Res<crate::WorkbenchConfig>>,
     mut cameras: Query<(&mut Camera, &mut Projection)>,
 ) {
     if !existing.is_empty() {
         return;
     }
+    let target = if game_window::play_in_window(config.as_deref()) {
+        game_window::spawn_game_window(&mut commands, &mut state)
+    } else {
+        RenderTarget::from(state.render_target.clone())
+    };
 
     if let Some(ext) = external {
         if let Ok((mut camera, mut projection)) = cameras.get_mut(ext.0) {
@@ -101,10 +194,7 @@ fn activate_game_view_camera(
             // the projection using the render target texture dimensions.
             projection.set_changed();
         }
-        commands.entity(ext.0).insert((
-            GameViewCamera,
-            RenderTarget::from(state.render_target.clone()),
-        ));
+        commands.entity(ext.0).insert((GameViewCamera, target));
         return;
     }
 
@@ -116,7 +206,7 @@ fn activate_game_view_camera(
             clear_color: ClearColorConfig::Custom(Color::BLACK),
             ..default()
         },
-        RenderTarget::from(state.render_target.clone()),
+        target,
         GameViewCamera,
         DespawnOnEnter(EditorMode::Edit),
     ));
@@ -126,18 +216,25 @@ fn activate_game_view_camera(
 /// automatically by `DespawnOnEnter(Edit)`.
 fn deactivate_external_camera(
     mut commands: Commands,
+    mut state: ResMut<GameViewState>,
     external: Option<Res<ExternalGameCamera>>,
-    mut cameras: Query<&mut Camera, With<GameViewCamera>>,
+    mut cameras: Query<
+        (&mut Camera, Option<&bevy::camera::visibility::RenderLayers>),
+        With<GameViewCamera>,
+    >,
 ) {
+    // The game window itself is despawned by `DespawnOnEnter(Edit)`.
+    state.game_window = None;
     let Some(ext) = external else { return };
-    let Ok(mut camera) = cameras.get_mut(ext.0) else {
+    let Ok((mut camera, layers)) = cameras.get_mut(ext.0) else {
         return;
     };
     camera.is_active = false;
-    commands
-        .entity(ext.0)
-        .remove::<GameViewCamera>()
-        .remove::<RenderTarget>();
+    let mut entity = commands.entity(ext.0);
+    entity.remove::<GameViewCamera>().remove::<RenderTarget>();
+    if let Some(layers) = layers {
+        entity.insert(layers.clone().without(EDITOR_GIZMO_LAYER));
+    }
 }
 
 /// System that registers the render target as an egui texture and syncs to the panel.
@@ -163,7 +260,11 @@ pub fn game_view_sync_system(
         panel.egui_texture_id = state.egui_texture_id;
         panel.resolution = state.resolution;
         panel.is_playing = is_playing;
-        panel.press_play_text = i18n.t("game-view-press-play");
+        panel.in_window = state.game_window.is_some();
+        if i18n.is_changed() || panel.press_play_text.is_empty() {
+            panel.press_play_text = i18n.t("game-view-press-play").to_owned();
+            panel.in_window_text = i18n.t("game-view-in-window").to_owned();
+        }
     }
 }
 
@@ -212,8 +313,12 @@ pub struct GameViewPanel {
     pub resolution: UVec2,
     /// Whether the game is currently playing (has a camera rendering).
     pub is_playing: bool,
+    /// Whether the game is playing in a separate window instead of this panel.
+    pub in_window: bool,
     /// Localized "press play" text.
     pub press_play_text: String,
+    /// Localized "playing in a separate window" text.
+    pub in_window_text: String,
     /// Current zoom mode.
     pub zoom: ViewZoom,
 }
@@ -237,6 +342,10 @@ impl WorkbenchPanel for GameViewPanel {
         true
     }
 
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Center
+    }
+
     fn closable(&self) -> bool {
         false
     }
@@ -259,6 +368,23 @@ fn game_view_toolbar_ui(ui: &mut egui::Ui, zoom: &mut ViewZoom, world: &mut Worl
             ui.selectable_value(zoom, ViewZoom::Fixed(1.5), "150%");
             ui.selectable_value(zoom, ViewZoom::Fixed(2.0), "200%");
         });
+    ui.menu_button("⚙", |ui| {
+        render_config::render_settings_menu_ui(ui, world)
+    })
+    .response
+    .on_hover_text("Render settings");
+    ui.menu_button("▦", |ui| {
+        overlay::overlay_menu_ui(ui, world, |o| &mut o.game_view)
+    })
+    .response
+    .on_hover_text("Overlays (shown while paused)");
+    ruler::ruler_toggle_ui(ui, world);
+    rect_handles::rect_handles_toggle_ui(ui, world);
+    point_handles::point_handles_toggle_ui(ui, world);
+    brush::brush_selector_ui(ui, world);
+    input_record::recorder_toolbar_ui(ui, world);
+    ui.separator();
+    crate::snap::snap_toolbar_world_ui(ui, world);
 
     if let Some(mut toolbar) = world.get_resource_mut::<GameViewToolbar>() {
         for toggle in &mut toolbar.toggles {
@@ -306,12 +432,21 @@ fn reset_game_view_focus(world: &mut World) {
 fn game_view_panel_ui(panel: &mut GameViewPanel, ui: &mut egui::Ui, world: &mut World) {
     if !panel.is_playing {
         reset_game_view_focus(world);
+        ui.horizontal(|ui| input_record::recorder_toolbar_ui(ui, world));
         ui.centered_and_justified(|ui| {
             ui.label(&panel.press_play_text);
         });
         return;
     }
 
+    // Focus is tracked from the game window by `game_window_focus_system`.
+    if panel.in_window {
+        ui.centered_and_justified(|ui| {
+            ui.label(&panel.in_window_text);
+        });
+        return;
+    }
+
     let Some(tex_id) = panel.egui_texture_id else {
         reset_game_view_focus(world);
         ui.centered_and_justified(|ui| {
@@ -326,11 +461,12 @@ fn game_view_panel_ui(panel: &mut GameViewPanel, ui: &mut egui::Ui, world: &mut
         UVec2::new(1280, 720)
     };
 
-    ui.horizontal(|ui| {
-        game_view_toolbar_ui(ui, &mut panel.zoom, world, res);
-    });
-
-    ui.separator();
+    if !crate::presentation::is_presenting(world) {
+        ui.horizontal(|ui| {
+            game_view_toolbar_ui(ui, &mut panel.zoom, world, res);
+        });
+        ui.separator();
+    }
 
     let available = ui.available_size();
     let display_size = compute_display_size(panel.zoom, res, available);
@@ -353,8 +489,14 @@ fn game_view_panel_ui(panel: &mut GameViewPanel, ui: &mut egui::Ui, world: &mut
         .inner
     };
 
-    let hovered = response.hovered();
     let image_rect = response.rect;
+    // While a tool is in use, pointer input belongs to it rather than the game.
+    let hovered = !eyedropper::eyedropper_ui(ui, world, image_rect)
+        && !ruler::ruler_ui(ui, world, image_rect, res)
+        && !rect_handles::rect_handles_ui(ui, world, image_rect, res)
+        && !point_handles::point_handles_ui(ui, world, image_rect, res)
+        && !brush::brush_ui(ui, world, image_rect, res)
+        && response.hovered();
 
     let cursor_viewport_pos = if hovered {
         ui.ctx().pointer_latest_pos().and_then(|pointer_pos| {
@@ -375,7 +517,7 @@ fn game_view_panel_ui(panel: &mut GameViewPanel, ui: &mut egui::Ui, world: &mut
         painter.rect_stroke(
             image_rect,
             0.0,
-            egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 180, 255)),
+            egui::Stroke::new(2.0, crate::theme::palette(ui.ctx()).selection),
             egui::StrokeKind::Outside,
         );
     }
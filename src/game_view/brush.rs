@@ -0,0 +1,201 @@
+//! # brush.rs
+//!
+//! # brush.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Paint mode for the Game View. Tools register a brush with a callback; picking it in the
+//! toolbar's brush selector routes pointer input over the game image to the brush instead of
+//! the game. Presses and drags are turned into dabs at world-space positions on the Game View
+//! grid plane, spaced along the drag so fast strokes leave no gaps, and carry the modifier keys
+//! and the mouse button, so tile and foliage painters don't handle viewport input themselves.
+//!
+//! 游戏视图的绘制模式。工具通过回调注册画笔；在工具栏的画笔选择器中选中后，游戏画面上的指针输入会交给
+//! 画笔而不是游戏。按下与拖拽会转换为游戏视图网格平面上世界坐标的笔触点，并沿拖拽路径按间距插值，
+//! 快速划动也不会留下空隙；每个笔触点附带修饰键与鼠标按键信息，瓦片与植被绘制工具无需自行处理视口输入。
+
+use super::handles::ImageProjection;
+use super::{GridPlane, ViewportOverlays};
+use bevy::prelude::*;
+
+/// One brush application at a world-space position.
+#[derive(Debug, Clone, Copy)]
+pub struct BrushDab {
+    /// Position on the Game View grid plane.
+    pub position: Vec3,
+    /// Whether this is the first dab of a stroke (the button was just pressed).
+    pub stroke_start: bool,
+    /// Painting with the secondary mouse button, e.g. to erase.
+    pub secondary: bool,
+    pub shift: bool,
+    /// Ctrl, or Cmd on macOS.
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+/// Brush callback, called once per dab.
+pub type BrushFn = Box<dyn FnMut(&mut World, &BrushDab) + Send + Sync>;
+
+struct Brush {
+    label: String,
+    /// World-space distance between dabs along a drag (0 = one dab per frame).
+    spacing: f32,
+    paint: BrushFn,
+}
+
+/// Registered brushes and the one painting in the Game View.
+#[derive(Resource, Default)]
+pub struct GameViewBrushes {
+    brushes: Vec<Brush>,
+    /// Index of the selected brush; `None` leaves pointer input to the game.
+    active: Option<usize>,
+    /// Position of the last dab of the current stroke.
+    last_dab: Option<Vec3>,
+}
+
+impl GameViewBrushes {
+    /// Register a brush shown as `label` in the brush selector. While a stroke is dragged,
+    /// dabs are placed every `spacing` world units (0 = once per frame).
+    pub fn register(
+        &mut self,
+        label: impl Into<String>,
+        spacing: f32,
+        paint: impl FnMut(&mut World, &BrushDab) + Send + Sync + 'static,
+    ) {
+        self.brushes.push(Brush {
+            label: label.into(),
+            spacing,
+            paint: Box::new(paint),
+        });
+    }
+
+    /// Label of the selected brush.
+    pub fn active(&self) -> Option<&str> {
+        self.active.map(|index| self.brushes[index].label.as_str())
+    }
+
+    /// Select the brush registered as `label`, or none to give pointer input back to the game.
+    pub fn set_active(&mut self, label: Option<&str>) {
+        self.active = label.and_then(|label| self.brushes.iter().position(|b| b.label == label));
+        self.last_dab = None;
+    }
+
+    /// Paint the selected brush from the last dab of the stroke to `target`. `dab` carries the
+    /// button and modifiers; its position and stroke start are filled in per dab.
+    fn paint_to(&mut self, world: &mut World, target: Vec3, mut dab: BrushDab) {
+        let Some(index) = self.active else { return };
+        let stroke_start = self.last_dab.is_none();
+        let spacing = self.brushes[index].spacing;
+        for (i, position) in self.dabs_towards(target, spacing).into_iter().enumerate() {
+            dab.position = position;
+            dab.stroke_start = stroke_start && i == 0;
+            (self.brushes[index].paint)(world, &dab);
+        }
+    }
+
+    /// Dabs from the last one towards `target`, `spacing` apart; just `target` without spacing.
+    fn dabs_towards(&mut self, target: Vec3, spacing: f32) -> Vec<Vec3> {
+        let Some(last) = self.last_dab else {
+            self.last_dab = Some(target);
+            return vec![target];
+        };
+        if spacing <= 0.0 {
+            self.last_dab = Some(target);
+            return vec![target];
+        }
+        let steps = (last.distance(target) / spacing).floor() as usize;
+        let direction = (target - last).normalize_or_zero();
+        let dabs: Vec<Vec3> = (1..=steps)
+            .map(|step| last + direction * spacing * step as f32)
+            .collect();
+        if let Some(&end) = dabs.last() {
+            self.last_dab = Some(end);
+        }
+        dabs
+    }
+}
+
+/// Toolbar brush selector, shown once a brush is registered.
+pub(super) fn brush_selector_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut brushes) = world.get_resource_mut::<GameViewBrushes>() else {
+        return;
+    };
+    if brushes.brushes.is_empty() {
+        return;
+    }
+    let selected = brushes.active().unwrap_or("Off").to_string();
+    let mut active = brushes.active;
+    egui::ComboBox::from_id_salt("game_view_brush")
+        .selected_text(format!("🖌 {selected}"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut active, None, "Off");
+            for (index, brush) in brushes.brushes.iter().enumerate() {
+                ui.selectable_value(&mut active, Some(index), &brush.label);
+            }
+        })
+        .response
+        .on_hover_text("Brush: paint in the game view instead of sending input to the game");
+    if active != brushes.active {
+        brushes.active = active;
+        brushes.last_dab = None;
+    }
+}
+
+/// Turns presses and drags over the game image into dabs of the selected brush.
+/// Returns true while a brush is selected (the game should not receive pointer input).
+pub(super) fn brush_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    image_rect: egui::Rect,
+    res: UVec2,
+) -> bool {
+    if world
+        .get_resource::<GameViewBrushes>()
+        .is_none_or(|brushes| brushes.active.is_none())
+    {
+        return false;
+    }
+    let Some(projection) = ImageProjection::new(world, image_rect, res) else {
+        return true;
+    };
+    let plane = world
+        .get_resource::<ViewportOverlays>()
+        .map_or(GridPlane::Xy, |o| o.game_view.plane);
+    let normal = match plane {
+        GridPlane::Xz => Dir3::Y,
+        GridPlane::Xy => Dir3::Z,
+    };
+
+    let response = ui.interact(
+        image_rect,
+        ui.id().with("game_view_brush"),
+        egui::Sense::click_and_drag(),
+    );
+    if let Some(pos) = response.hover_pos() {
+        let color = crate::theme::palette(ui.ctx()).success;
+        ui.painter()
+            .circle_stroke(pos, 6.0, egui::Stroke::new(1.5, color));
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+    }
+    let target = response
+        .interact_pointer_pos()
+        .filter(|_| response.is_pointer_button_down_on())
+        .and_then(|pos| projection.to_plane(pos, Vec3::ZERO, normal));
+    let (modifiers, secondary) = ui.input(|i| (i.modifiers, i.pointer.secondary_down()));
+    let dab = BrushDab {
+        position: Vec3::ZERO,
+        stroke_start: false,
+        secondary,
+        shift: modifiers.shift,
+        ctrl: modifiers.command,
+        alt: modifiers.alt,
+    };
+
+    world.resource_scope(|world, mut brushes: Mut<GameViewBrushes>| match target {
+        Some(target) => brushes.paint_to(world, target, dab),
+        None => brushes.last_dab = None,
+    });
+    true
+}
@@ -0,0 +1,92 @@
+//! # eyedropper.rs
+//!
+//! # eyedropper.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Game View side of the color eyedropper. While a color widget has the eyedropper armed, a
+//! click on the game image requests that pixel through `GameViewReadback`, and when its
+//! `GameViewPixels` message arrives the color is handed to the widget through egui memory.
+//!
+//! 颜色吸管的游戏视图部分。当某个颜色控件的吸管处于待命状态时，点击游戏画面会通过
+//! `GameViewReadback` 请求光标下的像素，对应的 `GameViewPixels` 消息到达后，颜色经由 egui 内存交给控件。
+
+use super::{GameViewPixels, GameViewReadback, GameViewState, ReadbackRequest};
+use crate::bench_ui::eyedropper;
+use bevy::prelude::*;
+
+/// Eyedropper readbacks in flight, and colors waiting to be handed to their widgets.
+#[derive(Resource, Default)]
+pub(super) struct EyedropperSamples {
+    pending: Vec<(ReadbackRequest, egui::Id)>,
+    sampled: Vec<(egui::Id, Color)>,
+}
+
+/// Picks a pixel on click while the eyedropper is armed and hands finished samples over.
+/// Returns true while armed (the game should not receive pointer input).
+pub(super) fn eyedropper_ui(ui: &mut egui::Ui, world: &mut World, image_rect: egui::Rect) -> bool {
+    if let Some(mut samples) = world.get_resource_mut::<EyedropperSamples>() {
+        for (id, color) in samples.sampled.drain(..) {
+            eyedropper::deliver(ui.ctx(), id, color);
+        }
+    }
+    let Some(target) = eyedropper::armed(ui.ctx()) else {
+        return false;
+    };
+    let response = ui.interact(
+        image_rect,
+        ui.id().with("game_view_eyedropper"),
+        egui::Sense::click(),
+    );
+    if response.hovered() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+    }
+    if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        let uv = (pos - image_rect.min) / image_rect.size();
+        request_sample(world, target, Vec2::new(uv.x, uv.y));
+        eyedropper::disarm(ui.ctx(), target);
+    }
+    true
+}
+
+/// Reads back the pixel at `uv` of the render target for the widget `target`.
+fn request_sample(world: &mut World, target: egui::Id, uv: Vec2) {
+    let Some(size) = world
+        .get_resource::<GameViewState>()
+        .map(|state| state.resolution)
+    else {
+        return;
+    };
+    let pixel = (uv * size.as_vec2()).as_uvec2().min(size - UVec2::ONE);
+    let request = world
+        .resource_mut::<GameViewReadback>()
+        .request(Some(URect::from_corners(pixel, pixel + UVec2::ONE)));
+    world
+        .resource_mut::<EyedropperSamples>()
+        .pending
+        .push((request, target));
+}
+
+/// Collects the pixels read back for the eyedropper.
+pub(super) fn eyedropper_readback_system(
+    mut pixels: MessageReader<GameViewPixels>,
+    mut samples: ResMut<EyedropperSamples>,
+) {
+    for message in pixels.read() {
+        let Some(index) = samples
+            .pending
+            .iter()
+            .position(|(request, _)| *request == message.request)
+        else {
+            continue;
+        };
+        let (_, target) = samples.pending.swap_remove(index);
+        if let Some(color) = message.color_at(0, 0) {
+            samples.sampled.push((target, color));
+        }
+    }
+}
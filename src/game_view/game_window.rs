@@ -0,0 +1,87 @@
+//! # game_window.rs
+//!
+//! # game_window.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Play-in-window mode for the game view. When `WorkbenchConfig::play_in_window` is set, Play
+//! opens a second OS window and the game camera renders into it instead of the game view
+//! texture. Input arrives natively in that window; `GameViewFocus` is filled from the window's
+//! focus and cursor so game code gates input the same way in both modes. Closing the window
+//! stops play.
+//!
+//! 游戏视图的独立窗口运行模式。当设置了 `WorkbenchConfig::play_in_window` 时，点击播放会打开第二个
+//! 系统窗口，游戏相机渲染到该窗口而不是游戏视图纹理。输入直接由该窗口接收；`GameViewFocus`
+//! 根据窗口的焦点与光标位置填充，因此游戏代码在两种模式下以相同方式过滤输入。关闭该窗口会停止播放。
+
+use super::{GameViewFocus, GameViewState};
+use crate::mode::{EditorMode, ModeTransitionRequest};
+use bevy::camera::RenderTarget;
+use bevy::prelude::*;
+use bevy::state::prelude::DespawnOnEnter;
+use bevy::window::WindowRef;
+
+/// Marker for the OS window the game runs in during play-in-window mode.
+#[derive(Component)]
+pub struct GameWindow;
+
+/// Whether Play should open a separate window (set in [`crate::WorkbenchConfig`]).
+pub(super) fn play_in_window(config: Option<&crate::WorkbenchConfig>) -> bool {
+    config.is_some_and(|c| c.play_in_window)
+}
+
+/// Spawns the game window and returns a render target pointing at it.
+pub(super) fn spawn_game_window(
+    commands: &mut Commands,
+    state: &mut GameViewState,
+) -> RenderTarget {
+    let window = commands
+        .spawn((
+            Window {
+                title: "Game".into(),
+                resolution: (state.resolution.x, state.resolution.y).into(),
+                ..default()
+            },
+            GameWindow,
+            DespawnOnEnter(EditorMode::Edit),
+        ))
+        .id();
+    state.game_window = Some(window);
+    RenderTarget::Window(WindowRef::Entity(window))
+}
+
+/// Mirrors the game window's focus and cursor into [`GameViewFocus`].
+pub fn game_window_focus_system(
+    state: Res<GameViewState>,
+    windows: Query<&Window, With<GameWindow>>,
+    mut focus: ResMut<GameViewFocus>,
+) {
+    let Some(window) = state.game_window.and_then(|e| windows.get(e).ok()) else {
+        return;
+    };
+    focus.hovered = window.focused;
+    focus.image_rect = None;
+    focus.resolution = UVec2::new(window.width() as u32, window.height() as u32);
+    focus.cursor_viewport_pos = window.cursor_position();
+}
+
+/// Stops play when the user closes the game window.
+pub fn game_window_closed_system(
+    mut state: ResMut<GameViewState>,
+    windows: Query<(), With<GameWindow>>,
+    mode: Res<State<EditorMode>>,
+    mut mode_requests: MessageWriter<ModeTransitionRequest>,
+) {
+    let Some(window) = state.game_window else {
+        return;
+    };
+    if windows.contains(window) {
+        return;
+    }
+    state.game_window = None;
+    if matches!(mode.get(), EditorMode::Play | EditorMode::Pause) {
+        mode_requests.write(ModeTransitionRequest::new(EditorMode::Edit));
+    }
+}
@@ -0,0 +1,103 @@
+//! # handles.rs
+//!
+//! # handles.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Shared pieces of the Game View editing tools: the mapping between the game camera's world
+//! space and the game image on screen, the entity the handles edit (the inspector selection)
+//! and how a handle is painted.
+//!
+//! 游戏视图编辑工具的共享部分：游戏相机世界空间与屏幕上游戏画面之间的映射、手柄所编辑的实体
+//! （检查器当前选中项）以及手柄的绘制方式。
+
+use super::ruler::game_camera;
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+
+/// Color of handles and outlines: the theme's selection color.
+pub(super) fn handle_color(ctx: &egui::Context) -> egui::Color32 {
+    crate::theme::palette(ctx).selection
+}
+
+/// Side of a handle square in points.
+pub(super) const HANDLE_SIZE: f32 = 8.0;
+
+/// The entity the handles edit: the first entity selected in the inspector.
+pub(super) fn selected_entity(world: &World) -> Option<Entity> {
+    world
+        .get_resource::<crate::inspector::InspectorSelection>()
+        .and_then(|selection| selection.selected.as_slice().first().copied())
+}
+
+/// Maps between the game camera's world space and the game image on screen.
+pub(super) struct ImageProjection {
+    camera: Camera,
+    camera_transform: GlobalTransform,
+    image_rect: egui::Rect,
+    res: UVec2,
+}
+
+impl ImageProjection {
+    /// Projection through the active game camera onto `image_rect`.
+    pub(super) fn new(world: &mut World, image_rect: egui::Rect, res: UVec2) -> Option<Self> {
+        let (camera, camera_transform) = game_camera(world)?;
+        Some(Self {
+            camera,
+            camera_transform,
+            image_rect,
+            res,
+        })
+    }
+
+    pub(super) fn contains(&self, pos: egui::Pos2) -> bool {
+        self.image_rect.contains(pos)
+    }
+
+    pub(super) fn to_screen(&self, point: Vec3) -> Option<egui::Pos2> {
+        let vp = self
+            .camera
+            .world_to_viewport(&self.camera_transform, point)
+            .ok()?;
+        let uv = egui::vec2(vp.x / self.res.x as f32, vp.y / self.res.y as f32);
+        Some(self.image_rect.min + uv * self.image_rect.size())
+    }
+
+    /// The world-space point under `pos` on the plane through `origin` facing `normal`.
+    pub(super) fn to_plane(&self, pos: egui::Pos2, origin: Vec3, normal: Dir3) -> Option<Vec3> {
+        let uv = (pos - self.image_rect.min) / self.image_rect.size();
+        let viewport = Vec2::new(uv.x * self.res.x as f32, uv.y * self.res.y as f32);
+        let ray = self
+            .camera
+            .viewport_to_world(&self.camera_transform, viewport)
+            .ok()?;
+        ray.plane_intersection_point(origin, InfinitePlane3d::new(normal))
+    }
+
+    /// The point under `pos` on the XY plane of `to_world`, in that local space.
+    pub(super) fn to_local(&self, pos: egui::Pos2, to_world: Affine3A) -> Option<Vec2> {
+        let normal = Dir3::new(Vec3::from(to_world.matrix3.z_axis)).ok()?;
+        let hit = self.to_plane(pos, Vec3::from(to_world.translation), normal)?;
+        Some(to_world.inverse().transform_point3(hit).truncate())
+    }
+}
+
+/// A handle square, filled while hovered or dragged.
+pub(super) fn paint_handle(ui: &egui::Ui, pos: egui::Pos2, hot: bool) {
+    let rect = egui::Rect::from_center_size(pos, egui::Vec2::splat(HANDLE_SIZE));
+    let fill = if hot {
+        handle_color(ui.ctx())
+    } else {
+        egui::Color32::WHITE
+    };
+    let painter = ui.painter();
+    painter.rect_filled(rect, 1.0, fill);
+    painter.rect_stroke(
+        rect,
+        1.0,
+        egui::Stroke::new(1.0, egui::Color32::BLACK),
+        egui::StrokeKind::Inside,
+    );
+}
@@ -0,0 +1,375 @@
+//! # input_record.rs
+//!
+//! # input_record.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Records the input the game sees during Play and replays it later, for capturing bug
+//! repros. Each Play frame stores the pressed keys, mouse buttons and the first gamepad's
+//! buttons/axes, accumulated mouse motion and scroll, and the game view's hover state and
+//! cursor position. Recording and replay are armed in the game view toolbar and start on the
+//! next fresh Play; by default both run with a fixed timestep (`TimeUpdateStrategy::ManualDuration`),
+//! so frame-indexed replay reproduces the recorded session deterministically. Recordings are
+//! saved as JSON (`.workbench/input_recording.json` by default) when returning to Edit.
+//!
+//! 记录 Play 期间游戏接收到的输入并在之后回放，用于捕获 bug 复现步骤。每个 Play 帧保存按下的按键、
+//! 鼠标按键、第一个手柄的按键与摇杆轴、累计的鼠标移动与滚轮，以及游戏视图的悬停状态和光标位置。
+//! 录制与回放在游戏视图工具栏中预备，并在下一次全新 Play 时开始；默认两者都以固定时间步长
+//! （`TimeUpdateStrategy::ManualDuration`）运行，因此按帧回放可以确定性地重现录制的会话。
+//! 返回编辑模式时，录制内容以 JSON 保存（默认 `.workbench/input_recording.json`）。
+
+use super::GameViewFocus;
+use crate::inspector::WorkbenchInternal;
+use bevy::input::gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadInput};
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::hash::Hash;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Input state of one Play frame.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct InputFrame {
+    pub keys: Vec<KeyCode>,
+    pub mouse_buttons: Vec<MouseButton>,
+    /// Cursor position in render target coordinates, as in [`GameViewFocus`].
+    pub cursor: Option<Vec2>,
+    /// Whether the game view was hovered (i.e. input was routed to the game).
+    pub hovered: bool,
+    pub mouse_motion: Vec2,
+    pub mouse_scroll: Vec2,
+    pub gamepad_buttons: Vec<GamepadButton>,
+    pub gamepad_axes: Vec<(GamepadAxis, f32)>,
+}
+
+/// A recorded Play session.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct InputRecording {
+    /// Fixed frame time used while recording, in seconds (`None` = variable frame time).
+    pub timestep: Option<f32>,
+    pub frames: Vec<InputFrame>,
+}
+
+/// What the recorder does (or will do on the next fresh Play).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecorderMode {
+    #[default]
+    Idle,
+    Recording,
+    Replaying,
+}
+
+/// Input recorder state.
+#[derive(Resource)]
+pub struct InputRecorder {
+    /// Where recordings are saved and loaded from.
+    pub path: PathBuf,
+    /// Fixed frame time applied while recording, in seconds (`None` = real frame time).
+    pub fixed_timestep: Option<f32>,
+    /// Action taken on the next fresh Play.
+    pub armed: RecorderMode,
+    active: RecorderMode,
+    recording: InputRecording,
+    /// Next frame to replay.
+    frame: usize,
+    /// Time strategy replaced by the fixed timestep, restored afterwards.
+    previous_strategy: Option<TimeUpdateStrategy>,
+    replay_gamepad: Option<Entity>,
+}
+
+impl Default for InputRecorder {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(".workbench/input_recording.json"),
+            fixed_timestep: Some(1.0 / 60.0),
+            armed: RecorderMode::Idle,
+            active: RecorderMode::Idle,
+            recording: InputRecording::default(),
+            frame: 0,
+            previous_strategy: None,
+            replay_gamepad: None,
+        }
+    }
+}
+
+impl InputRecorder {
+    /// Whether a recording or replay is running.
+    pub fn active(&self) -> RecorderMode {
+        self.active
+    }
+}
+
+/// Starts the armed recording or replay (runs on fresh Play).
+pub fn input_record_start_system(world: &mut World) {
+    let mut recorder = world.resource_mut::<InputRecorder>();
+    let armed = std::mem::take(&mut recorder.armed);
+    let recording = match armed {
+        RecorderMode::Idle => return,
+        RecorderMode::Recording => InputRecording {
+            timestep: recorder.fixed_timestep,
+            frames: Vec::new(),
+        },
+        RecorderMode::Replaying => {
+            let loaded = crate::storage::read_string(&recorder.path)
+                .ok_or_else(|| format!("{} not found", recorder.path.display()))
+                .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()));
+            match loaded {
+                Ok(recording) => recording,
+                Err(error) => {
+                    warn!("Input replay failed: {error}");
+                    return;
+                }
+            }
+        }
+    };
+    let timestep = recording.timestep;
+    recorder.recording = recording;
+    recorder.active = armed;
+    recorder.frame = 0;
+    if let Some(step) = timestep {
+        let previous = world.remove_resource::<TimeUpdateStrategy>();
+        world.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            step,
+        )));
+        world.resource_mut::<InputRecorder>().previous_strategy = previous;
+    }
+}
+
+/// Captures or replays one frame of input before the game schedule runs.
+pub fn input_record_system(world: &mut World) {
+    match world.resource::<InputRecorder>().active {
+        RecorderMode::Idle => {}
+        RecorderMode::Recording => {
+            let frame = capture_frame(world);
+            world
+                .resource_mut::<InputRecorder>()
+                .recording
+                .frames
+                .push(frame);
+        }
+        RecorderMode::Replaying => {
+            let recorder = world.resource::<InputRecorder>();
+            let index = recorder.frame;
+            let Some(frame) = recorder.recording.frames.get(index).cloned() else {
+                finish(world);
+                info!("Input replay finished");
+                return;
+            };
+            let previous = index
+                .checked_sub(1)
+                .map(|i| recorder.recording.frames[i].clone())
+                .unwrap_or_default();
+            apply_frame(world, &frame, &previous);
+            world.resource_mut::<InputRecorder>().frame += 1;
+        }
+    }
+}
+
+/// Saves a running recording (runs on returning to Edit).
+pub fn input_record_stop_system(world: &mut World) {
+    let recorder = world.resource::<InputRecorder>();
+    if recorder.active == RecorderMode::Recording {
+        let count = recorder.recording.frames.len();
+        let path = recorder.path.clone();
+        let saved = serde_json::to_string(&recorder.recording)
+            .map_err(|e| e.to_string())
+            .and_then(|json| crate::storage::write_string(&path, &json));
+        match saved {
+            Ok(()) => info!("Recorded {count} input frames to {}", path.display()),
+            Err(error) => warn!("Failed to save input recording: {error}"),
+        }
+    }
+    finish(world);
+}
+
+/// Ends recording/replay and restores the time strategy.
+fn finish(world: &mut World) {
+    let mut recorder = world.resource_mut::<InputRecorder>();
+    if recorder.active == RecorderMode::Idle {
+        return;
+    }
+    recorder.active = RecorderMode::Idle;
+    let previous = recorder.previous_strategy.take();
+    let gamepad = recorder.replay_gamepad.take();
+    let fixed = recorder.recording.timestep.is_some();
+    if fixed {
+        world.insert_resource(previous.unwrap_or_default());
+    }
+    if let Some(entity) = gamepad
+        && let Ok(entity_mut) = world.get_entity_mut(entity)
+    {
+        entity_mut.despawn();
+    }
+}
+
+fn capture_frame(world: &mut World) -> InputFrame {
+    let mut frame = InputFrame {
+        keys: pressed(world.get_resource::<ButtonInput<KeyCode>>()),
+        mouse_buttons: pressed(world.get_resource::<ButtonInput<MouseButton>>()),
+        mouse_motion: world
+            .get_resource::<AccumulatedMouseMotion>()
+            .map_or(Vec2::ZERO, |m| m.delta),
+        mouse_scroll: world
+            .get_resource::<AccumulatedMouseScroll>()
+            .map_or(Vec2::ZERO, |s| s.delta),
+        ..Default::default()
+    };
+    if let Some(focus) = world.get_resource::<GameViewFocus>() {
+        frame.hovered = focus.hovered;
+        frame.cursor = focus.cursor_viewport_pos;
+    }
+    let replay_gamepad = world.resource::<InputRecorder>().replay_gamepad;
+    let mut gamepads = world.query::<(Entity, &Gamepad)>();
+    if let Some((_, gamepad)) = gamepads
+        .iter(world)
+        .find(|(entity, _)| Some(*entity) != replay_gamepad)
+    {
+        frame.gamepad_buttons = gamepad.digital().get_pressed().copied().collect();
+        frame.gamepad_axes = gamepad
+            .analog()
+            .all_axes()
+            .filter_map(|input| match input {
+                GamepadInput::Axis(axis) => Some((*axis, gamepad.get(*axis)?)),
+                GamepadInput::Button(_) => None,
+            })
+            .filter(|(_, value)| *value != 0.0)
+            .collect();
+    }
+    frame
+}
+
+fn apply_frame(world: &mut World, frame: &InputFrame, previous: &InputFrame) {
+    if let Some(mut keys) = world.get_resource_mut::<ButtonInput<KeyCode>>() {
+        replay_buttons(&mut keys, &previous.keys, &frame.keys);
+    }
+    if let Some(mut buttons) = world.get_resource_mut::<ButtonInput<MouseButton>>() {
+        replay_buttons(&mut buttons, &previous.mouse_buttons, &frame.mouse_buttons);
+    }
+    if let Some(mut motion) = world.get_resource_mut::<AccumulatedMouseMotion>() {
+        motion.delta = frame.mouse_motion;
+    }
+    if let Some(mut scroll) = world.get_resource_mut::<AccumulatedMouseScroll>() {
+        scroll.delta = frame.mouse_scroll;
+    }
+    if let Some(mut focus) = world.get_resource_mut::<GameViewFocus>() {
+        focus.hovered = frame.hovered;
+        focus.cursor_viewport_pos = frame.cursor;
+    }
+
+    let uses_gamepad = !frame.gamepad_buttons.is_empty()
+        || !frame.gamepad_axes.is_empty()
+        || !previous.gamepad_buttons.is_empty();
+    if !uses_gamepad {
+        return;
+    }
+    let entity = match world.resource::<InputRecorder>().replay_gamepad {
+        Some(entity) => entity,
+        None => {
+            let entity = world
+                .spawn((
+                    Name::new("Replay Gamepad"),
+                    Gamepad::default(),
+                    WorkbenchInternal,
+                ))
+                .id();
+            world.resource_mut::<InputRecorder>().replay_gamepad = Some(entity);
+            entity
+        }
+    };
+    let Some(mut gamepad) = world.get_mut::<Gamepad>(entity) else {
+        return;
+    };
+    replay_buttons(
+        gamepad.digital_mut(),
+        &previous.gamepad_buttons,
+        &frame.gamepad_buttons,
+    );
+    for (axis, _) in &previous.gamepad_axes {
+        gamepad.analog_mut().set(*axis, 0.0);
+    }
+    for (axis, value) in &frame.gamepad_axes {
+        gamepad.analog_mut().set(*axis, *value);
+    }
+}
+
+fn pressed<T>(input: Option<&ButtonInput<T>>) -> Vec<T>
+where
+    T: Copy + Eq + Hash + Send + Sync + 'static,
+{
+    input.map_or_else(Vec::new, |i| i.get_pressed().copied().collect())
+}
+
+/// Replaces real input with the recorded state, producing the same `just_pressed` /
+/// `just_released` transitions as the recorded session.
+fn replay_buttons<T>(input: &mut ButtonInput<T>, previous: &[T], current: &[T])
+where
+    T: Copy + Eq + Hash + Send + Sync + 'static,
+{
+    input.reset_all();
+    for &button in previous {
+        input.press(button);
+        input.clear_just_pressed(button);
+    }
+    for &button in previous {
+        if !current.contains(&button) {
+            input.release(button);
+        }
+    }
+    for &button in current {
+        input.press(button);
+    }
+}
+
+/// Toolbar controls: arm recording / replay, or show progress while running.
+pub(super) fn recorder_toolbar_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut recorder) = world.get_resource_mut::<InputRecorder>() else {
+        return;
+    };
+    match recorder.active {
+        RecorderMode::Recording => {
+            let frames = recorder.recording.frames.len();
+            ui.colored_label(
+                crate::theme::palette(ui.ctx()).error,
+                format!("⏺ REC {frames}"),
+            )
+            .on_hover_text("Recording input; Stop saves the recording");
+        }
+        RecorderMode::Replaying => {
+            let total = recorder.recording.frames.len();
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                format!("⟲ {}/{total}", recorder.frame),
+            )
+            .on_hover_text("Replaying recorded input");
+        }
+        RecorderMode::Idle => {
+            let path = recorder.path.display().to_string();
+            let armed = recorder.armed;
+            if ui
+                .selectable_label(armed == RecorderMode::Recording, "⏺")
+                .on_hover_text("Record input on the next Play")
+                .clicked()
+            {
+                recorder.armed = toggle(armed, RecorderMode::Recording);
+            }
+            if ui
+                .selectable_label(armed == RecorderMode::Replaying, "⟲")
+                .on_hover_text(format!("Replay {path} on the next Play"))
+                .clicked()
+            {
+                recorder.armed = toggle(armed, RecorderMode::Replaying);
+            }
+        }
+    }
+}
+
+fn toggle(armed: RecorderMode, mode: RecorderMode) -> RecorderMode {
+    if armed == mode {
+        RecorderMode::Idle
+    } else {
+        mode
+    }
+}
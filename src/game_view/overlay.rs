@@ -0,0 +1,199 @@
+//! # overlay.rs
+//!
+//! # overlay.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Viewport overlays drawn with bevy_gizmos: a ground grid (XZ plane for 3D scenes, XY plane for
+//! 2D) and world axis lines through the origin. The Game View and the Scene View each keep their
+//! own toggles in `ViewportOverlays`. Overlays are never drawn during Play; in Edit the Scene
+//! View settings apply (only editor cameras render then) and in Pause the Game View settings do.
+//!
+//! 使用 bevy_gizmos 绘制的视口叠加层：地面网格（3D 场景为 XZ 平面，2D 为 XY 平面）以及穿过原点的
+//! 世界坐标轴线。游戏视图与场景视图在 `ViewportOverlays` 中各自保存开关。播放时从不绘制；编辑模式下
+//! 使用场景视图的设置（此时只有编辑器相机在渲染），暂停时使用游戏视图的设置。
+
+use super::GameViewCamera;
+use crate::editor_camera::EditorCameraController;
+use crate::mode::EditorMode;
+use bevy::camera::visibility::RenderLayers;
+use bevy::prelude::*;
+
+const AXIS_X: Color = Color::srgb(0.9, 0.3, 0.3);
+const AXIS_Y: Color = Color::srgb(0.4, 0.8, 0.3);
+const AXIS_Z: Color = Color::srgb(0.3, 0.5, 0.95);
+
+/// Render layer of the viewport overlays. Only editor cameras and the game view camera get it,
+/// so the game's own cameras never draw editor gizmos.
+pub const EDITOR_GIZMO_LAYER: usize = 31;
+
+/// Gizmo config group for viewport overlays (adjust line width etc. via `GizmoConfigStore`).
+/// Drawn on [`EDITOR_GIZMO_LAYER`].
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct ViewportGizmos;
+
+/// Puts [`ViewportGizmos`] on [`EDITOR_GIZMO_LAYER`].
+pub(super) fn setup_viewport_gizmos(mut store: ResMut<GizmoConfigStore>) {
+    let (config, _) = store.config_mut::<ViewportGizmos>();
+    config.render_layers = RenderLayers::layer(EDITOR_GIZMO_LAYER);
+}
+
+/// Adds [`EDITOR_GIZMO_LAYER`] to editor and game view cameras, keeping their other layers.
+pub fn editor_gizmo_layer_system(
+    mut commands: Commands,
+    cameras: Query<
+        (Entity, Option<&RenderLayers>),
+        Or<(Added<EditorCameraController>, Added<GameViewCamera>)>,
+    >,
+) {
+    for (entity, layers) in &cameras {
+        let layers = layers.cloned().unwrap_or_default();
+        commands
+            .entity(entity)
+            .insert(layers.with(EDITOR_GIZMO_LAYER));
+    }
+}
+
+/// Plane the grid is drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridPlane {
+    /// Ground plane for 3D scenes.
+    Xz,
+    /// Screen plane for 2D scenes.
+    Xy,
+}
+
+/// Overlay toggles for one viewport panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewportOverlay {
+    pub grid: bool,
+    pub axes: bool,
+    pub plane: GridPlane,
+    /// Size of one grid cell in world units.
+    pub spacing: f32,
+    /// Number of cells along each side.
+    pub cells: u32,
+    pub color: Color,
+}
+
+impl ViewportOverlay {
+    fn new(plane: GridPlane, spacing: f32, enabled: bool) -> Self {
+        Self {
+            grid: enabled,
+            axes: enabled,
+            plane,
+            spacing,
+            cells: 40,
+            color: Color::srgba(0.5, 0.5, 0.5, 0.35),
+        }
+    }
+}
+
+/// Per-panel overlay settings.
+#[derive(Resource, Debug, Clone)]
+pub struct ViewportOverlays {
+    /// Drawn while paused (the game view camera).
+    pub game_view: ViewportOverlay,
+    /// Drawn while editing (cameras with `EditorCameraController`).
+    pub scene_view: ViewportOverlay,
+}
+
+impl Default for ViewportOverlays {
+    fn default() -> Self {
+        Self {
+            // The fallback game camera is 2D (pixel units); editor cameras are usually 3D.
+            game_view: ViewportOverlay::new(GridPlane::Xy, 32.0, false),
+            scene_view: ViewportOverlay::new(GridPlane::Xz, 1.0, true),
+        }
+    }
+}
+
+/// Draws the grid and axes for whichever viewport is rendering in the current mode.
+pub fn draw_viewport_overlay_system(
+    mode: Res<State<EditorMode>>,
+    overlays: Res<ViewportOverlays>,
+    mut gizmos: Gizmos<ViewportGizmos>,
+) {
+    let overlay = match mode.get() {
+        EditorMode::Edit => &overlays.scene_view,
+        EditorMode::Pause => &overlays.game_view,
+        EditorMode::Play => return,
+    };
+    let half = overlay.spacing * overlay.cells as f32 / 2.0;
+    match overlay.plane {
+        GridPlane::Xz => {
+            if overlay.grid {
+                gizmos.grid(
+                    Quat::from_rotation_x(std::f32::consts::FRAC_PI_2),
+                    UVec2::splat(overlay.cells),
+                    Vec2::splat(overlay.spacing),
+                    overlay.color,
+                );
+            }
+            if overlay.axes {
+                gizmos.line(Vec3::NEG_X * half, Vec3::X * half, AXIS_X);
+                gizmos.line(Vec3::ZERO, Vec3::Y * half, AXIS_Y);
+                gizmos.line(Vec3::NEG_Z * half, Vec3::Z * half, AXIS_Z);
+            }
+        }
+        GridPlane::Xy => {
+            if overlay.grid {
+                gizmos.grid_2d(
+                    Isometry2d::IDENTITY,
+                    UVec2::splat(overlay.cells),
+                    Vec2::splat(overlay.spacing),
+                    overlay.color,
+                );
+            }
+            if overlay.axes {
+                gizmos.line_2d(Vec2::NEG_X * half, Vec2::X * half, AXIS_X);
+                gizmos.line_2d(Vec2::NEG_Y * half, Vec2::Y * half, AXIS_Y);
+            }
+        }
+    }
+}
+
+/// Overlay menu for a viewport toolbar.
+pub(super) fn overlay_menu_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    select: fn(&mut ViewportOverlays) -> &mut ViewportOverlay,
+) {
+    let Some(mut overlays) = world.get_resource_mut::<ViewportOverlays>() else {
+        return;
+    };
+    let mut edited = select(overlays.bypass_change_detection()).clone();
+
+    ui.checkbox(&mut edited.grid, "Grid");
+    ui.checkbox(&mut edited.axes, "Axes");
+    ui.horizontal(|ui| {
+        ui.selectable_value(&mut edited.plane, GridPlane::Xz, "3D (XZ)");
+        ui.selectable_value(&mut edited.plane, GridPlane::Xy, "2D (XY)");
+    });
+    egui::Grid::new("viewport_overlay_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Spacing");
+            ui.add(
+                egui::DragValue::new(&mut edited.spacing)
+                    .speed(0.1)
+                    .range(0.01..=f32::MAX),
+            );
+            ui.end_row();
+
+            ui.label("Cells");
+            ui.add(egui::DragValue::new(&mut edited.cells).range(1..=1000));
+            ui.end_row();
+
+            ui.label("Color");
+            crate::bench_ui::color(ui, "", &mut edited.color);
+            ui.end_row();
+        });
+
+    // Only write back on edits so change detection stays quiet.
+    if *select(overlays.bypass_change_detection()) != edited {
+        *select(&mut *overlays) = edited;
+    }
+}
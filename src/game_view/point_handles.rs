@@ -0,0 +1,392 @@
+//! # point_handles.rs
+//!
+//! # point_handles.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Point list editing for the Game View, for polygon colliders, paths and spline waypoints. A
+//! tool registers a reflected `Vec<Vec2>` field of a component by its reflect path; with the
+//! point tool toggled on, the list of the selected entity is drawn over the game image in the
+//! entity's local space. Dragging a point moves it (snapped to the translate step), clicking a
+//! segment inserts a point and right-clicking a point deletes it; each edit is one undo action.
+//!
+//! 游戏视图的点列表编辑，用于多边形碰撞体、路径与样条路点。工具按反射路径注册组件中的
+//! `Vec<Vec2>` 字段；开启点工具后，选中实体的点列表会在其局部空间中绘制在游戏画面上。拖拽点即可移动
+//! （按平移步长吸附），点击线段插入新点，右键点击点将其删除；每次编辑都是一条撤销操作。
+
+use super::handles::{HANDLE_SIZE, ImageProjection, handle_color, paint_handle, selected_entity};
+use crate::bench_ui::{Icon, icon};
+use crate::snap::SnapSettings;
+use bevy::ecs::component::Mutable;
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+
+/// How close to a segment, in points, a click inserts a point.
+const SEGMENT_PICK_DISTANCE: f32 = 6.0;
+
+/// Type-erased access to one registered point list.
+struct PointListAccess {
+    name: String,
+    /// Reflect path of the `Vec<Vec2>` field, e.g. `"points"` or `"path.waypoints"`.
+    path: String,
+    /// Whether the last point connects back to the first (polygons).
+    closed: bool,
+    read: fn(&World, Entity, &str) -> Option<Vec<Vec2>>,
+    write: fn(&mut World, Entity, &str, &[Vec2]),
+}
+
+/// A point being dragged.
+struct PointDrag {
+    entity: Entity,
+    /// Index into `PointHandles::lists`.
+    list: usize,
+    before: Vec<Vec2>,
+}
+
+/// One edit of the point list from a frame of input.
+enum PointEdit {
+    Move(usize, Vec2),
+    Insert(usize, Vec2),
+    Delete(usize),
+}
+
+/// Point tool state and the point lists it can edit.
+#[derive(Resource, Default)]
+pub struct PointHandles {
+    /// Whether the point list of the selected entity is shown.
+    pub enabled: bool,
+    lists: Vec<PointListAccess>,
+    drag: Option<PointDrag>,
+}
+
+impl PointHandles {
+    /// Make the `Vec<Vec2>` field at reflect path `path` of `T` editable with the point tool,
+    /// named `name` in undo entries. `closed` lists (polygons) also connect the last point back
+    /// to the first. When an entity has several lists, the one registered last is edited.
+    pub fn register<T: Component<Mutability = Mutable> + Reflect>(
+        &mut self,
+        name: impl Into<String>,
+        path: impl Into<String>,
+        closed: bool,
+    ) {
+        self.lists.push(PointListAccess {
+            name: name.into(),
+            path: path.into(),
+            closed,
+            read: read_points::<T>,
+            write: write_points::<T>,
+        });
+    }
+
+    /// The last registered list `entity` has, with its points.
+    fn find_list(&self, world: &World, entity: Entity) -> Option<(usize, Vec<Vec2>)> {
+        self.lists
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, list)| Some((index, (list.read)(world, entity, &list.path)?)))
+    }
+
+    fn write(&self, world: &mut World, entity: Entity, list: usize, points: &[Vec2]) {
+        let access = &self.lists[list];
+        (access.write)(world, entity, &access.path, points);
+        crate::inspector::InspectorUndoState::absorb_edit(world, entity);
+    }
+
+    /// Record `before` → `after` of `list` as one undo action.
+    fn record(&self, world: &mut World, entity: Entity, list: usize, edit: PointChange) {
+        let access = &self.lists[list];
+        let desc = format!("{} {} point", edit.verb, access.name);
+        let (write, undo_path, redo_path) =
+            (access.write, access.path.clone(), access.path.clone());
+        let PointChange { before, after, .. } = edit;
+        if let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>() {
+            undo_stack.push(crate::undo::ClosureUndoAction::new(
+                desc,
+                move |world| write(world, entity, &undo_path, &before),
+                move |world| write(world, entity, &redo_path, &after),
+            ));
+        }
+    }
+
+    /// Apply `edit` to `points` and the component, recording inserts and deletes right away.
+    /// Moves are recorded when the drag ends.
+    fn apply(
+        &mut self,
+        world: &mut World,
+        entity: Entity,
+        list: usize,
+        points: &mut Vec<Vec2>,
+        edit: PointEdit,
+    ) {
+        let before = points.clone();
+        let verb = match edit {
+            PointEdit::Move(index, point) => {
+                points[index] = point;
+                None
+            }
+            PointEdit::Insert(index, point) => {
+                points.insert(index, point);
+                Some("Insert")
+            }
+            PointEdit::Delete(index) => {
+                points.remove(index);
+                Some("Delete")
+            }
+        };
+        self.write(world, entity, list, points);
+        if let Some(verb) = verb {
+            let after = points.clone();
+            let change = PointChange {
+                verb,
+                before,
+                after,
+            };
+            self.record(world, entity, list, change);
+        }
+    }
+
+    /// Record the finished drag as one undo action.
+    fn finish_drag(&mut self, world: &mut World, points: &[Vec2]) {
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+        if drag.before != points {
+            let change = PointChange {
+                verb: "Move",
+                before: drag.before,
+                after: points.to_vec(),
+            };
+            self.record(world, drag.entity, drag.list, change);
+        }
+    }
+}
+
+/// A finished edit of a point list.
+struct PointChange {
+    verb: &'static str,
+    before: Vec<Vec2>,
+    after: Vec<Vec2>,
+}
+
+fn read_points<T: Component + Reflect>(
+    world: &World,
+    entity: Entity,
+    path: &str,
+) -> Option<Vec<Vec2>> {
+    let component = world.get::<T>(entity)?;
+    let field = component.reflect_path(path).ok()?;
+    field.try_downcast_ref::<Vec<Vec2>>().cloned()
+}
+
+fn write_points<T: Component<Mutability = Mutable> + Reflect>(
+    world: &mut World,
+    entity: Entity,
+    path: &str,
+    points: &[Vec2],
+) {
+    let Some(mut component) = world.get_mut::<T>(entity) else {
+        return;
+    };
+    if let Ok(field) = component.reflect_path_mut(path)
+        && let Some(list) = field.try_downcast_mut::<Vec<Vec2>>()
+    {
+        list.clear();
+        list.extend_from_slice(points);
+    }
+}
+
+/// Toolbar toggle for the point tool.
+pub(super) fn point_handles_toggle_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut handles) = world.get_resource_mut::<PointHandles>() else {
+        return;
+    };
+    if ui
+        .selectable_label(handles.enabled, icon(Icon::Polygon))
+        .on_hover_text(
+            "Point tool: drag points, click a segment to insert, right-click a point to delete",
+        )
+        .clicked()
+    {
+        handles.enabled = !handles.enabled;
+    }
+}
+
+/// Draws the selected entity's point list over the game image and handles edits.
+/// Returns true while a point or segment is hovered or a point is dragged (the game should not
+/// receive pointer input).
+pub(super) fn point_handles_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    image_rect: egui::Rect,
+    res: UVec2,
+) -> bool {
+    if !world
+        .get_resource::<PointHandles>()
+        .is_some_and(|handles| handles.enabled)
+    {
+        return false;
+    }
+    let Some(entity) = selected_entity(world) else {
+        return false;
+    };
+    let Some(projection) = ImageProjection::new(world, image_rect, res) else {
+        return false;
+    };
+    world.resource_scope(|world, mut handles: Mut<PointHandles>| {
+        point_list_ui(ui, world, &mut handles, entity, &projection)
+    })
+}
+
+fn point_list_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    handles: &mut PointHandles,
+    entity: Entity,
+    projection: &ImageProjection,
+) -> bool {
+    let Some((list, mut points)) = handles.find_list(world, entity) else {
+        return false;
+    };
+    let Some(to_world) = world.get::<GlobalTransform>(entity).map(|t| t.affine()) else {
+        return false;
+    };
+    let screen: Vec<Option<egui::Pos2>> = points
+        .iter()
+        .map(|p| projection.to_screen(to_world.transform_point3(p.extend(0.0))))
+        .collect();
+    let closed = handles.lists[list].closed;
+    paint_outline(ui, &screen, closed);
+    let snap = world.get_resource::<SnapSettings>().cloned();
+    let to_local = |pos: egui::Pos2| {
+        let local = projection.to_local(pos, to_world)?;
+        Some(snap.as_ref().map_or(local, |snap| {
+            snap.snap_translation(local.extend(0.0)).truncate()
+        }))
+    };
+
+    let mut active = false;
+    let mut edit = None;
+    for (index, pos) in screen.iter().enumerate() {
+        let Some(pos) = *pos else { continue };
+        let response = ui.interact(
+            egui::Rect::from_center_size(pos, egui::Vec2::splat(HANDLE_SIZE + 4.0)),
+            ui.id().with(("point_handle", index)),
+            egui::Sense::click_and_drag(),
+        );
+        if response.drag_started() {
+            handles.drag = Some(PointDrag {
+                entity,
+                list,
+                before: points.clone(),
+            });
+        } else if response.dragged()
+            && let Some(point) = response.interact_pointer_pos().and_then(to_local)
+            && point != points[index]
+        {
+            edit = Some(PointEdit::Move(index, point));
+        } else if response.secondary_clicked() {
+            edit = Some(PointEdit::Delete(index));
+        }
+        let hot = response.hovered() || response.dragged();
+        active |= hot;
+        paint_handle(ui, pos, hot);
+    }
+
+    if !active && edit.is_none() && handles.drag.is_none() {
+        let (hovered, insert) = insert_ui(ui, projection, &screen, closed, to_local);
+        active = hovered;
+        edit = insert;
+    }
+    if let Some(edit) = edit {
+        handles.apply(world, entity, list, &mut points, edit);
+    }
+    if !ui.input(|i| i.pointer.primary_down())
+        && let Some(drag) = &handles.drag
+    {
+        if drag.entity == entity && drag.list == list {
+            handles.finish_drag(world, &points);
+        } else {
+            handles.drag = None;
+        }
+    }
+    active
+}
+
+/// Segment hover and click-to-insert. Lists with fewer than two points take a click anywhere
+/// on the image as a new point. Returns whether the pointer is on a segment, and the insert.
+fn insert_ui(
+    ui: &egui::Ui,
+    projection: &ImageProjection,
+    screen: &[Option<egui::Pos2>],
+    closed: bool,
+    to_local: impl Fn(egui::Pos2) -> Option<Vec2>,
+) -> (bool, Option<PointEdit>) {
+    let Some(pointer) = ui
+        .ctx()
+        .pointer_hover_pos()
+        .filter(|p| projection.contains(*p))
+    else {
+        return (false, None);
+    };
+    let index = if screen.len() < 2 {
+        screen.len()
+    } else {
+        let Some((index, closest)) = closest_segment(screen, closed, pointer) else {
+            return (false, None);
+        };
+        ui.painter().circle_stroke(
+            closest,
+            HANDLE_SIZE / 2.0,
+            egui::Stroke::new(1.5, handle_color(ui.ctx())),
+        );
+        index + 1
+    };
+    ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+    let clicked = ui.input(|i| i.pointer.primary_clicked());
+    let insert = to_local(pointer)
+        .filter(|_| clicked)
+        .map(|point| PointEdit::Insert(index, point));
+    (true, insert)
+}
+
+/// The segment within [`SEGMENT_PICK_DISTANCE`] of `pointer`: its first point and the closest
+/// position on it.
+fn closest_segment(
+    screen: &[Option<egui::Pos2>],
+    closed: bool,
+    pointer: egui::Pos2,
+) -> Option<(usize, egui::Pos2)> {
+    let count = if closed {
+        screen.len()
+    } else {
+        screen.len() - 1
+    };
+    (0..count)
+        .filter_map(|index| {
+            let a = screen[index]?;
+            let b = screen[(index + 1) % screen.len()]?;
+            let ab = b - a;
+            let t = ((pointer - a).dot(ab) / ab.length_sq().max(f32::EPSILON)).clamp(0.0, 1.0);
+            let closest = a + ab * t;
+            Some((index, closest, closest.distance(pointer)))
+        })
+        .filter(|(_, _, distance)| *distance <= SEGMENT_PICK_DISTANCE)
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .map(|(index, closest, _)| (index, closest))
+}
+
+fn paint_outline(ui: &egui::Ui, screen: &[Option<egui::Pos2>], closed: bool) {
+    let Some(points) = screen.iter().copied().collect::<Option<Vec<_>>>() else {
+        return;
+    };
+    let stroke = egui::Stroke::new(1.5, handle_color(ui.ctx()));
+    let shape = if closed && points.len() > 2 {
+        egui::Shape::closed_line(points, stroke)
+    } else {
+        egui::Shape::line(points, stroke)
+    };
+    ui.painter().add(shape);
+}
@@ -0,0 +1,162 @@
+//! # readback.rs
+//!
+//! # readback.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! GPU readback of the Game View render target. `GameViewReadback::request` queues a region;
+//! the next update spawns a bevy `Readback` of the `GameViewState` target, and when the GPU copy
+//! arrives the region is cropped, converted to RGBA8 and sent as a `GameViewPixels` message
+//! carrying the request handle. Screenshots, the eyedropper and pixel-diff tests build on it.
+//!
+//! 游戏视图渲染目标的 GPU 回读。`GameViewReadback::request` 将一个区域加入队列；下一次更新时为
+//! `GameViewState` 的渲染目标生成 bevy `Readback`，GPU 拷贝完成后裁剪该区域、转换为 RGBA8，并以携带
+//! 请求句柄的 `GameViewPixels` 消息发送。截图、吸管与像素比对测试都基于它实现。
+
+use super::GameViewState;
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::gpu_readback::{Readback, ReadbackComplete};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Handle of a readback request, echoed by its [`GameViewPixels`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadbackRequest(u64);
+
+/// Queue of Game View readback requests.
+#[derive(Resource, Default)]
+pub struct GameViewReadback {
+    next: u64,
+    pending: Vec<(ReadbackRequest, Option<URect>)>,
+}
+
+impl GameViewReadback {
+    /// Read back `region` of the Game View render target in pixels (`None` = all of it). The
+    /// pixels arrive as a [`GameViewPixels`] message once the GPU copy completes, usually the
+    /// next frame.
+    pub fn request(&mut self, region: Option<URect>) -> ReadbackRequest {
+        let request = ReadbackRequest(self.next);
+        self.next += 1;
+        self.pending.push((request, region));
+        request
+    }
+}
+
+/// Pixels read back from the Game View render target.
+#[derive(Message, Debug, Clone)]
+pub struct GameViewPixels {
+    pub request: ReadbackRequest,
+    /// Region read, clamped to the render target.
+    pub region: URect,
+    /// Row-major sRGB RGBA8 pixels of `region`.
+    pub rgba: Vec<u8>,
+}
+
+impl GameViewPixels {
+    /// Color at `(x, y)` relative to the region's top-left corner.
+    pub fn color_at(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.region.width() {
+            return None;
+        }
+        let offset = ((y * self.region.width() + x) * 4) as usize;
+        let [r, g, b, a] = self.rgba.get(offset..offset + 4)?.try_into().ok()?;
+        Some(Color::srgba_u8(r, g, b, a))
+    }
+
+    /// The pixels as an image, e.g. to save a screenshot.
+    pub fn to_image(&self) -> Image {
+        Image::new(
+            Extent3d {
+                width: self.region.width(),
+                height: self.region.height(),
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            self.rgba.clone(),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+/// Starts a readback of the render target for each queued request.
+pub fn game_view_readback_system(
+    mut commands: Commands,
+    mut readback: ResMut<GameViewReadback>,
+    state: Res<GameViewState>,
+    images: Res<Assets<Image>>,
+) {
+    if readback.pending.is_empty() {
+        return;
+    }
+    let Some(size) = images.get(&state.render_target).map(Image::size) else {
+        return;
+    };
+    let bounds = URect::from_corners(UVec2::ZERO, size);
+    for (request, region) in readback.pending.drain(..) {
+        let region = region.map_or(bounds, |region| region.intersect(bounds));
+        if region.is_empty() {
+            continue;
+        }
+        commands
+            .spawn(Readback::texture(state.render_target.clone()))
+            .observe(
+                move |complete: On<ReadbackComplete>,
+                      mut commands: Commands,
+                      mut pixels: MessageWriter<GameViewPixels>| {
+                    // A `Readback` reads every frame until it is removed.
+                    commands.entity(complete.entity).despawn();
+                    pixels.write(GameViewPixels {
+                        request,
+                        region,
+                        rgba: crop_bgra(&complete.data, size, region),
+                    });
+                },
+            );
+    }
+}
+
+/// Crops `region` out of `Bgra8UnormSrgb` readback data, whose rows may be padded, as RGBA8.
+fn crop_bgra(data: &[u8], size: UVec2, region: URect) -> Vec<u8> {
+    let stride = data.len() / size.y.max(1) as usize;
+    let mut rgba = Vec::with_capacity((region.width() * region.height() * 4) as usize);
+    for y in region.min.y..region.max.y {
+        let start = y as usize * stride + region.min.x as usize * 4;
+        let row = data
+            .get(start..start + region.width() as usize * 4)
+            .unwrap_or_default();
+        rgba.extend(row.chunks_exact(4).flat_map(|p| [p[2], p[1], p[0], p[3]]));
+    }
+    rgba
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crops_padded_rows_and_swizzles() {
+        // 2×2 pixels, each row padded to 12 bytes.
+        let data = [
+            1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, //
+            9, 10, 11, 12, 13, 14, 15, 16, 0, 0, 0, 0,
+        ];
+        let size = UVec2::new(2, 2);
+        assert_eq!(
+            crop_bgra(&data, size, URect::new(0, 0, 2, 2)),
+            [3, 2, 1, 4, 7, 6, 5, 8, 11, 10, 9, 12, 15, 14, 13, 16]
+        );
+        assert_eq!(
+            crop_bgra(&data, size, URect::new(1, 1, 2, 2)),
+            [15, 14, 13, 16]
+        );
+    }
+
+    #[test]
+    fn short_data_yields_nothing_for_missing_rows() {
+        let data = [1, 2, 3, 4];
+        assert!(crop_bgra(&data, UVec2::new(1, 1), URect::new(0, 0, 2, 1)).is_empty());
+    }
+}
@@ -0,0 +1,367 @@
+//! # rect_handles.rs
+//!
+//! # rect_handles.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Rect editing handles for the Game View. With the handles toggled on in the toolbar, the
+//! selected entity's rectangular 2D shape is outlined over the game image with corner and edge
+//! handles; dragging one resizes the shape in the entity's local space, snapped to the translate
+//! step of `SnapSettings`, and releasing it records one undo action. Sprites (`custom_size`) are
+//! supported out of the box; other components opt in by implementing `RectShape` and calling
+//! `app.register_rect_shape::<T>(...)`.
+//!
+//! 游戏视图的矩形编辑手柄。在工具栏中开启后，选中实体的二维矩形形状会以轮廓叠加在游戏画面上，并带有
+//! 角点与边中点手柄；拖拽手柄即在实体的局部空间中调整形状大小，按 `SnapSettings` 的平移步长吸附，
+//! 松开时记录一条撤销操作。精灵（`custom_size`）开箱即用；其他组件实现 `RectShape` 并调用
+//! `app.register_rect_shape::<T>(...)` 即可接入。
+
+use super::handles::{HANDLE_SIZE, ImageProjection, handle_color, paint_handle, selected_entity};
+use crate::snap::SnapSettings;
+use bevy::ecs::component::Mutable;
+use bevy::math::Affine3A;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// Handle positions as signs along each axis of the rect (0 = the middle of that axis).
+const HANDLES: [Vec2; 8] = [
+    Vec2::new(-1.0, -1.0),
+    Vec2::new(0.0, -1.0),
+    Vec2::new(1.0, -1.0),
+    Vec2::new(1.0, 0.0),
+    Vec2::new(1.0, 1.0),
+    Vec2::new(0.0, 1.0),
+    Vec2::new(-1.0, 1.0),
+    Vec2::new(-1.0, 0.0),
+];
+
+/// A component with a rectangular 2D shape that the Game View rect handles can edit, such as
+/// a box collider. Register it with `app.register_rect_shape::<T>("Collider")`.
+pub trait RectShape: Component<Mutability = Mutable> + Clone {
+    /// The shape in the entity's local space.
+    fn rect(&self) -> Rect;
+    /// Replace the shape with `rect`, in the entity's local space.
+    fn set_rect(&mut self, rect: Rect);
+}
+
+/// Puts an entity back the way it was when the snapshot was taken.
+type Restore = Box<dyn Fn(&mut World) + Send + Sync>;
+
+/// Type-erased access to one kind of rect shape.
+struct ShapeAccess {
+    name: String,
+    read: fn(&World, Entity) -> Option<Rect>,
+    write: fn(&mut World, Entity, Rect),
+    snapshot: fn(&World, Entity) -> Option<Restore>,
+}
+
+/// A handle being dragged.
+struct RectDrag {
+    entity: Entity,
+    /// Index into `RectHandles::shapes`.
+    shape: usize,
+    /// Entry of [`HANDLES`].
+    handle: Vec2,
+    start: Rect,
+    current: Rect,
+    /// Local to world space of the entity when the drag started.
+    to_world: Affine3A,
+    before: Restore,
+}
+
+/// Rect handle mode and the shapes it can edit.
+#[derive(Resource)]
+pub struct RectHandles {
+    /// Whether handles are shown for the selected entity.
+    pub enabled: bool,
+    shapes: Vec<ShapeAccess>,
+    drag: Option<RectDrag>,
+}
+
+impl Default for RectHandles {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shapes: vec![ShapeAccess {
+                name: "Sprite".to_string(),
+                read: read_sprite,
+                write: write_sprite,
+                snapshot: snapshot_sprite,
+            }],
+            drag: None,
+        }
+    }
+}
+
+impl RectHandles {
+    /// Make `T` editable with the handles, named `name` in undo entries. When an entity has
+    /// several shapes, the one registered last is edited.
+    pub fn register<T: RectShape>(&mut self, name: impl Into<String>) {
+        self.shapes.push(ShapeAccess {
+            name: name.into(),
+            read: |world, entity| world.get::<T>(entity).map(T::rect),
+            write: |world, entity, rect| {
+                if let Some(mut shape) = world.get_mut::<T>(entity) {
+                    shape.set_rect(rect);
+                }
+            },
+            snapshot: snapshot_component::<T>,
+        });
+    }
+
+    /// The last registered shape `entity` has, with its rect.
+    fn find_shape(&self, world: &World, entity: Entity) -> Option<(usize, Rect)> {
+        self.shapes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, shape)| Some((index, (shape.read)(world, entity)?)))
+    }
+
+    /// Resize the dragged shape so the handle follows `local`, relative to the drag start.
+    fn drag_to(&mut self, world: &mut World, local: Vec2) {
+        let Some(drag) = self.drag.as_mut() else {
+            return;
+        };
+        let point = world.get_resource::<SnapSettings>().map_or(local, |snap| {
+            snap.snap_translation(local.extend(0.0)).truncate()
+        });
+        let (mut min, mut max) = (drag.start.min, drag.start.max);
+        if drag.handle.x < 0.0 {
+            min.x = point.x;
+        } else if drag.handle.x > 0.0 {
+            max.x = point.x;
+        }
+        if drag.handle.y < 0.0 {
+            min.y = point.y;
+        } else if drag.handle.y > 0.0 {
+            max.y = point.y;
+        }
+        let rect = Rect::from_corners(min, max);
+        if rect == drag.current {
+            return;
+        }
+        // Writing from the start state keeps sprites, which move their transform, from drifting.
+        (drag.before)(world);
+        (self.shapes[drag.shape].write)(world, drag.entity, rect);
+        drag.current = rect;
+        crate::inspector::InspectorUndoState::absorb_edit(world, drag.entity);
+    }
+
+    /// Record the finished drag as one undo action.
+    fn finish_drag(&mut self, world: &mut World) {
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+        let shape = &self.shapes[drag.shape];
+        if drag.current == drag.start {
+            return;
+        }
+        let Some(after) = (shape.snapshot)(world, drag.entity) else {
+            return;
+        };
+        let before = drag.before;
+        if let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>() {
+            undo_stack.push(crate::undo::ClosureUndoAction::new(
+                format!("Resize {}", shape.name),
+                move |world| before(world),
+                move |world| after(world),
+            ));
+        }
+        crate::inspector::InspectorUndoState::absorb_edit(world, drag.entity);
+    }
+}
+
+fn snapshot_component<T: RectShape>(world: &World, entity: Entity) -> Option<Restore> {
+    let value = world.get::<T>(entity)?.clone();
+    Some(Box::new(move |world| {
+        if let Some(mut shape) = world.get_mut::<T>(entity) {
+            *shape = value.clone();
+        }
+    }))
+}
+
+fn sprite_anchor(world: &World, entity: Entity) -> Vec2 {
+    world
+        .get::<Anchor>(entity)
+        .map_or(Vec2::ZERO, Anchor::as_vec)
+}
+
+/// The sprite's drawn rect: its custom size, else its texture rect or image size.
+fn read_sprite(world: &World, entity: Entity) -> Option<Rect> {
+    let sprite = world.get::<Sprite>(entity)?;
+    let size = sprite
+        .custom_size
+        .or_else(|| sprite.rect.map(|rect| rect.size()))
+        .or_else(|| {
+            let images = world.get_resource::<Assets<Image>>()?;
+            images.get(&sprite.image).map(Image::size_f32)
+        })?;
+    Some(Rect::from_center_size(
+        -sprite_anchor(world, entity) * size,
+        size,
+    ))
+}
+
+/// Sets `custom_size` and moves the transform so the sprite covers `rect` whatever its anchor.
+fn write_sprite(world: &mut World, entity: Entity, rect: Rect) {
+    let size = rect.size();
+    let offset = rect.center() + sprite_anchor(world, entity) * size;
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if let Some(mut sprite) = entity_mut.get_mut::<Sprite>() {
+        sprite.custom_size = Some(size);
+    }
+    if let Some(mut transform) = entity_mut.get_mut::<Transform>() {
+        let shift = transform.rotation * (offset * transform.scale.truncate()).extend(0.0);
+        transform.translation += shift;
+    }
+}
+
+fn snapshot_sprite(world: &World, entity: Entity) -> Option<Restore> {
+    let sprite = world.get::<Sprite>(entity)?.clone();
+    let transform = world.get::<Transform>(entity).copied();
+    Some(Box::new(move |world| {
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            return;
+        };
+        if let Some(mut current) = entity_mut.get_mut::<Sprite>() {
+            *current = sprite.clone();
+        }
+        if let Some(transform) = transform
+            && let Some(mut current) = entity_mut.get_mut::<Transform>()
+        {
+            *current = transform;
+        }
+    }))
+}
+
+/// Toolbar toggle for the rect handles.
+pub(super) fn rect_handles_toggle_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut handles) = world.get_resource_mut::<RectHandles>() else {
+        return;
+    };
+    if ui
+        .selectable_label(handles.enabled, "⬚")
+        .on_hover_text("Rect handles: resize the selected sprite or collider")
+        .clicked()
+    {
+        handles.enabled = !handles.enabled;
+    }
+}
+
+/// Draws the handles of the selected entity's shape over the game image and handles drags.
+/// Returns true while a handle is hovered or dragged (the game should not receive pointer input).
+pub(super) fn rect_handles_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    image_rect: egui::Rect,
+    res: UVec2,
+) -> bool {
+    if !world
+        .get_resource::<RectHandles>()
+        .is_some_and(|handles| handles.enabled)
+    {
+        return false;
+    }
+    let Some(entity) = selected_entity(world) else {
+        return false;
+    };
+    let Some(projection) = ImageProjection::new(world, image_rect, res) else {
+        return false;
+    };
+    world.resource_scope(|world, mut handles: Mut<RectHandles>| {
+        shape_handles_ui(ui, world, &mut handles, entity, &projection)
+    })
+}
+
+fn shape_handles_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    handles: &mut RectHandles,
+    entity: Entity,
+    projection: &ImageProjection,
+) -> bool {
+    // While dragging, draw in the frame the drag started in: transforms moved by the drag only
+    // propagate at the end of the frame.
+    let (shape, rect, to_world) = match &handles.drag {
+        Some(drag) if drag.entity == entity => (drag.shape, drag.current, drag.to_world),
+        _ => {
+            let Some((shape, rect)) = handles.find_shape(world, entity) else {
+                return false;
+            };
+            let Some(transform) = world.get::<GlobalTransform>(entity) else {
+                return false;
+            };
+            (shape, rect, transform.affine())
+        }
+    };
+    let screen = |local: Vec2| projection.to_screen(to_world.transform_point3(local.extend(0.0)));
+    let corners = [
+        rect.min,
+        rect.min.with_x(rect.max.x),
+        rect.max,
+        rect.max.with_x(rect.min.x),
+    ];
+    let outline: Option<Vec<egui::Pos2>> = corners.into_iter().map(&screen).collect();
+    if let Some(outline) = outline {
+        ui.painter().add(egui::Shape::closed_line(
+            outline,
+            egui::Stroke::new(1.5, handle_color(ui.ctx())),
+        ));
+    }
+
+    let mut active = false;
+    for handle in HANDLES {
+        let Some(pos) = screen(rect.center() + handle * rect.half_size()) else {
+            continue;
+        };
+        let response = ui.interact(
+            egui::Rect::from_center_size(pos, egui::Vec2::splat(HANDLE_SIZE + 4.0)),
+            ui.id()
+                .with(("rect_handle", handle.x as i32, handle.y as i32)),
+            egui::Sense::drag(),
+        );
+        if response.drag_started() {
+            let before = (handles.shapes[shape].snapshot)(world, entity);
+            handles.drag = before.map(|before| RectDrag {
+                entity,
+                shape,
+                handle,
+                start: rect,
+                current: rect,
+                to_world,
+                before,
+            });
+        } else if response.dragged()
+            && let Some(local) = response
+                .interact_pointer_pos()
+                .and_then(|pos| projection.to_local(pos, to_world))
+        {
+            handles.drag_to(world, local);
+        }
+        if response.drag_stopped() {
+            handles.finish_drag(world);
+        }
+        let hot = response.hovered() || response.dragged();
+        if hot {
+            ui.ctx().set_cursor_icon(resize_cursor(handle));
+        }
+        active |= hot;
+        paint_handle(ui, pos, hot);
+    }
+    active
+}
+
+/// Resize cursor for a handle (local Y points up, screen Y down).
+fn resize_cursor(handle: Vec2) -> egui::CursorIcon {
+    match (handle.x == 0.0, handle.y == 0.0) {
+        (true, _) => egui::CursorIcon::ResizeVertical,
+        (_, true) => egui::CursorIcon::ResizeHorizontal,
+        _ if handle.x * handle.y > 0.0 => egui::CursorIcon::ResizeNeSw,
+        _ => egui::CursorIcon::ResizeNwSe,
+    }
+}
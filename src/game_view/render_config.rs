@@ -0,0 +1,193 @@
+//! # render_config.rs
+//!
+//! # render_config.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Render settings for the game view camera: MSAA, HDR, tonemapping and clear color. They live
+//! in the `GameViewConfig` resource as optional overrides: unset ones keep whatever the game
+//! configured on its camera. Set overrides are applied to every `GameViewCamera` whenever the
+//! config changes or a camera starts rendering, and can be edited from the gear menu in the
+//! Game View toolbar so previews match the shipped game.
+//!
+//! 游戏视图相机的渲染设置：MSAA、HDR、色调映射和清屏颜色。这些设置以可选覆盖项的形式保存在
+//! `GameViewConfig` 资源中：未设置的项保留游戏在其相机上的配置。已设置的覆盖项会在配置变化或有相机
+//! 开始渲染时应用到所有 `GameViewCamera`，并可通过游戏视图工具栏的齿轮菜单编辑，使预览效果与最终
+//! 发布的游戏一致。
+
+use super::GameViewCamera;
+use bevy::core_pipeline::tonemapping::Tonemapping;
+use bevy::prelude::*;
+use bevy::render::view::Hdr;
+
+/// Render settings applied to the game view camera. `None` keeps the camera's own value;
+/// clearing an override leaves the last applied value until the camera is spawned again.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct GameViewConfig {
+    pub msaa: Option<Msaa>,
+    pub hdr: Option<bool>,
+    pub tonemapping: Option<Tonemapping>,
+    pub clear_color: Option<Color>,
+}
+
+const MSAA_OPTIONS: [(Msaa, &str); 4] = [
+    (Msaa::Off, "Off"),
+    (Msaa::Sample2, "2×"),
+    (Msaa::Sample4, "4×"),
+    (Msaa::Sample8, "8×"),
+];
+
+/// Tonemapping methods that don't need the `tonemapping_luts` feature.
+const TONEMAPPING_OPTIONS: [(Tonemapping, &str); 5] = [
+    (Tonemapping::None, "None"),
+    (Tonemapping::Reinhard, "Reinhard"),
+    (Tonemapping::ReinhardLuminance, "Reinhard Luminance"),
+    (Tonemapping::AcesFitted, "ACES Fitted"),
+    (
+        Tonemapping::SomewhatBoringDisplayTransform,
+        "Somewhat Boring",
+    ),
+];
+
+/// Applies the overrides of [`GameViewConfig`] to game view cameras when it changes or a
+/// camera is added.
+pub fn apply_game_view_config_system(
+    mut commands: Commands,
+    config: Res<GameViewConfig>,
+    mut cameras: Query<(Entity, &mut Camera, Ref<GameViewCamera>)>,
+) {
+    for (entity, mut camera, marker) in &mut cameras {
+        if !config.is_changed() && !marker.is_added() {
+            continue;
+        }
+        if let Some(color) = config.clear_color {
+            camera.clear_color = ClearColorConfig::Custom(color);
+        }
+        let mut entity = commands.entity(entity);
+        if let Some(msaa) = config.msaa {
+            entity.insert(msaa);
+        }
+        if let Some(tonemapping) = config.tonemapping {
+            entity.insert(tonemapping);
+        }
+        match config.hdr {
+            Some(true) => {
+                entity.insert(Hdr);
+            }
+            Some(false) => {
+                entity.remove::<Hdr>();
+            }
+            None => {}
+        }
+    }
+}
+
+/// The settings of the first game view camera, used as the starting value of a new override.
+fn camera_settings(world: &mut World) -> (Msaa, bool, Tonemapping, Color) {
+    let clear_color = world
+        .get_resource::<ClearColor>()
+        .map_or(Color::BLACK, |c| c.0);
+    let mut cameras = world.query_filtered::<(
+        &Camera,
+        Option<&Msaa>,
+        Has<Hdr>,
+        Option<&Tonemapping>,
+    ), With<GameViewCamera>>();
+    match cameras.iter(world).next() {
+        Some((camera, msaa, hdr, tonemapping)) => (
+            msaa.copied().unwrap_or_default(),
+            hdr,
+            tonemapping.copied().unwrap_or(Tonemapping::None),
+            match camera.clear_color {
+                ClearColorConfig::Custom(color) => color,
+                _ => clear_color,
+            },
+        ),
+        None => (Msaa::default(), false, Tonemapping::None, clear_color),
+    }
+}
+
+/// Gear menu contents for the Game View toolbar. Each row's checkbox turns its override on.
+pub(super) fn render_settings_menu_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut edited) = world.get_resource::<GameViewConfig>().cloned() else {
+        return;
+    };
+    let (msaa, hdr, tonemapping, clear_color) = camera_settings(world);
+
+    egui::Grid::new("game_view_render_settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("MSAA");
+            override_ui(ui, &mut edited.msaa, msaa, |ui, value| {
+                option_combo(ui, "game_view_msaa", value, &MSAA_OPTIONS);
+            });
+            ui.end_row();
+
+            ui.label("HDR");
+            override_ui(ui, &mut edited.hdr, hdr, |ui, value| {
+                ui.checkbox(value, "");
+            });
+            ui.end_row();
+
+            ui.label("Tonemapping");
+            override_ui(ui, &mut edited.tonemapping, tonemapping, |ui, value| {
+                option_combo(ui, "game_view_tonemapping", value, &TONEMAPPING_OPTIONS);
+            });
+            ui.end_row();
+
+            ui.label("Clear color");
+            override_ui(ui, &mut edited.clear_color, clear_color, |ui, value| {
+                crate::bench_ui::color(ui, "", value);
+            });
+            ui.end_row();
+        });
+
+    // Only write back on edits so change detection doesn't re-apply every frame.
+    let mut config = world.resource_mut::<GameViewConfig>();
+    if *config != edited {
+        *config = edited;
+    }
+}
+
+/// Checkbox enabling an override (starting from `current`), then `editor` while it is set.
+fn override_ui<T: Copy>(
+    ui: &mut egui::Ui,
+    value: &mut Option<T>,
+    current: T,
+    editor: impl FnOnce(&mut egui::Ui, &mut T),
+) {
+    ui.horizontal(|ui| {
+        let mut enabled = value.is_some();
+        if ui
+            .checkbox(&mut enabled, "")
+            .on_hover_text("Override the camera's own setting")
+            .changed()
+        {
+            *value = enabled.then_some(value.unwrap_or(current));
+        }
+        if let Some(value) = value {
+            editor(ui, value);
+        }
+    });
+}
+
+fn option_combo<T: Copy + PartialEq>(
+    ui: &mut egui::Ui,
+    id: &str,
+    value: &mut T,
+    options: &[(T, &str)],
+) {
+    let selected = options
+        .iter()
+        .find(|(v, _)| v == value)
+        .map_or("?", |(_, name)| name);
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (option, name) in options {
+                ui.selectable_value(value, *option, *name);
+            }
+        });
+}
@@ -0,0 +1,147 @@
+//! # ruler.rs
+//!
+//! # ruler.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Measurement tool for the Game View. With the ruler toggled on in the toolbar, click-dragging
+//! over the game image casts rays through the `GameViewCamera` onto the grid plane of the Game
+//! View overlay and draws the measured segment with its world-space length. The last
+//! measurement stays in `GameViewRuler` so level design tools can read it.
+//!
+//! 游戏视图的测量工具。在工具栏中开启标尺后，在游戏画面上拖拽会通过 `GameViewCamera` 向游戏视图叠加层
+//! 的网格平面投射射线，并绘制测量线段及其世界空间长度。最近一次测量结果保存在 `GameViewRuler` 中，
+//! 供关卡设计工具读取。
+
+use super::{GameViewCamera, GridPlane, ViewportOverlays};
+use bevy::prelude::*;
+
+const RULER_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 200, 60);
+
+/// Ruler mode state and the last measurement.
+#[derive(Resource, Default)]
+pub struct GameViewRuler {
+    /// Whether drags in the game view measure instead of reaching the game.
+    pub enabled: bool,
+    /// World-space start and end of the last measurement.
+    pub measurement: Option<(Vec3, Vec3)>,
+}
+
+impl GameViewRuler {
+    /// World-space length of the last measurement.
+    pub fn distance(&self) -> Option<f32> {
+        self.measurement.map(|(a, b)| a.distance(b))
+    }
+}
+
+/// Toolbar toggle for ruler mode.
+pub(super) fn ruler_toggle_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut ruler) = world.get_resource_mut::<GameViewRuler>() else {
+        return;
+    };
+    if ui
+        .selectable_label(ruler.enabled, "📏")
+        .on_hover_text("Ruler: drag to measure world distance")
+        .clicked()
+    {
+        ruler.enabled = !ruler.enabled;
+        ruler.measurement = None;
+    }
+}
+
+/// Handles ruler drags over the game image and paints the measurement.
+/// Returns true while ruler mode is on (the game should not receive pointer input).
+pub(super) fn ruler_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    image_rect: egui::Rect,
+    res: UVec2,
+) -> bool {
+    if !world
+        .get_resource::<GameViewRuler>()
+        .is_some_and(|r| r.enabled)
+    {
+        return false;
+    }
+    let Some((camera, camera_transform)) = game_camera(world) else {
+        return true;
+    };
+    let plane = world
+        .get_resource::<ViewportOverlays>()
+        .map_or(GridPlane::Xy, |o| o.game_view.plane);
+
+    let to_viewport = |p: egui::Pos2| {
+        let uv = (p - image_rect.min) / image_rect.size();
+        Vec2::new(uv.x * res.x as f32, uv.y * res.y as f32)
+    };
+    let to_world = |p: egui::Pos2| {
+        let ray = camera
+            .viewport_to_world(&camera_transform, to_viewport(p))
+            .ok()?;
+        let normal = match plane {
+            GridPlane::Xz => Dir3::Y,
+            GridPlane::Xy => Dir3::Z,
+        };
+        ray.plane_intersection_point(Vec3::ZERO, InfinitePlane3d::new(normal))
+    };
+
+    let response = ui.interact(
+        image_rect,
+        ui.id().with("game_view_ruler"),
+        egui::Sense::drag(),
+    );
+    let mut ruler = world.resource_mut::<GameViewRuler>();
+    if response.drag_started() {
+        ruler.measurement = response
+            .interact_pointer_pos()
+            .and_then(to_world)
+            .map(|p| (p, p));
+    } else if response.dragged()
+        && let Some(end) = response.interact_pointer_pos().and_then(to_world)
+        && let Some((_, current)) = ruler.measurement.as_mut()
+    {
+        *current = end;
+    }
+
+    let Some((start, end)) = ruler.measurement else {
+        return true;
+    };
+    let to_screen = |p: Vec3| {
+        let vp = camera.world_to_viewport(&camera_transform, p).ok()?;
+        Some(
+            image_rect.min
+                + egui::vec2(vp.x / res.x as f32, vp.y / res.y as f32) * image_rect.size(),
+        )
+    };
+    if let (Some(a), Some(b)) = (to_screen(start), to_screen(end)) {
+        paint_measurement(ui, a, b, start.distance(end));
+    }
+    true
+}
+
+pub(super) fn game_camera(world: &mut World) -> Option<(Camera, GlobalTransform)> {
+    world
+        .query_filtered::<(&Camera, &GlobalTransform), With<GameViewCamera>>()
+        .iter(world)
+        .find(|(camera, _)| camera.is_active)
+        .map(|(camera, transform)| (camera.clone(), *transform))
+}
+
+fn paint_measurement(ui: &egui::Ui, a: egui::Pos2, b: egui::Pos2, distance: f32) {
+    let painter = ui.painter();
+    let stroke = egui::Stroke::new(2.0, RULER_COLOR);
+    painter.line_segment([a, b], stroke);
+    painter.circle_filled(a, 3.5, RULER_COLOR);
+    painter.circle_filled(b, 3.5, RULER_COLOR);
+    let label = painter.layout_no_wrap(
+        format!("{distance:.3}"),
+        egui::FontId::monospace(12.0),
+        egui::Color32::BLACK,
+    );
+    let rect = egui::Rect::from_center_size(a.lerp(b, 0.5) + egui::vec2(0.0, -12.0), label.size())
+        .expand(3.0);
+    painter.rect_filled(rect, 3.0, RULER_COLOR);
+    painter.galley(rect.min + egui::vec2(3.0, 3.0), label, egui::Color32::BLACK);
+}
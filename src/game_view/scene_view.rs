@@ -0,0 +1,194 @@
+//! # scene_view.rs
+//!
+//! # scene_view.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The Scene View panel: an Edit-mode viewport for cameras carrying `EditorCameraController`.
+//! Those cameras render into a dedicated texture while editing and are switched off during
+//! play, so the Game View shows only the game camera. The panel opens itself the first time
+//! such a camera appears and reports hover state for the camera controls.
+//!
+//! 场景视图面板：为带有 `EditorCameraController` 的相机提供编辑模式下的视口。这些相机在编辑时渲染到
+//! 专用纹理中，在播放期间关闭，从而游戏视图只显示游戏相机。当首次出现此类相机时面板会自动打开，并为
+//! 相机控制提供悬停状态。
+
+use super::{GameViewCamera, ViewZoom, compute_display_size};
+use crate::dock::{PanelSlot, TileLayoutState, WorkbenchPanel};
+use crate::editor_camera::EditorCameraController;
+use crate::mode::EditorMode;
+use bevy::camera::RenderTarget;
+use bevy::image::ImageSampler;
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+
+/// Panel ID of the Scene View.
+const SCENE_VIEW_ID: &str = "workbench_scene_view";
+
+/// Render target shared by all editor cameras.
+#[derive(Resource)]
+pub struct SceneViewState {
+    pub render_target: Handle<Image>,
+    pub egui_texture_id: Option<egui::TextureId>,
+    pub resolution: UVec2,
+}
+
+impl Default for SceneViewState {
+    fn default() -> Self {
+        Self {
+            render_target: Handle::default(),
+            egui_texture_id: None,
+            resolution: UVec2::new(1280, 720),
+        }
+    }
+}
+
+/// Hover state of the Scene View, read by the editor camera controls.
+#[derive(Resource, Default)]
+pub struct SceneViewFocus {
+    /// Whether the scene image is hovered.
+    pub hovered: bool,
+    /// Cursor position in render target coordinates (if pointer is over the image).
+    pub cursor_viewport_pos: Option<Vec2>,
+}
+
+/// Creates the scene view render target.
+pub(super) fn setup_scene_view_target(
+    mut images: ResMut<Assets<Image>>,
+    mut state: ResMut<SceneViewState>,
+) {
+    let mut image = Image::new_target_texture(
+        state.resolution.x,
+        state.resolution.y,
+        TextureFormat::Bgra8UnormSrgb,
+        Some(TextureFormat::Bgra8UnormSrgb),
+    );
+    image.sampler = ImageSampler::linear();
+    state.render_target = images.add(image);
+}
+
+/// Routes editor cameras into the scene view and runs them only in Edit mode.
+pub fn scene_view_camera_system(
+    mut commands: Commands,
+    state: Res<SceneViewState>,
+    mode: Res<State<EditorMode>>,
+    mut tile_state: ResMut<TileLayoutState>,
+    mut cameras: Query<(Entity, &mut Camera, Ref<EditorCameraController>), Without<GameViewCamera>>,
+) {
+    let editing = *mode.get() == EditorMode::Edit;
+    for (entity, mut camera, controller) in &mut cameras {
+        if controller.is_added() {
+            commands
+                .entity(entity)
+                .insert(RenderTarget::from(state.render_target.clone()));
+            tile_state.request_open_panel(SCENE_VIEW_ID);
+        }
+        if camera.is_active != editing {
+            camera.is_active = editing;
+        }
+    }
+}
+
+/// Built-in Scene View dock panel.
+pub struct SceneViewPanel;
+
+impl WorkbenchPanel for SceneViewPanel {
+    fn id(&self) -> &str {
+        SCENE_VIEW_ID
+    }
+
+    fn title(&self) -> String {
+        "Scene View".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        scene_view_panel_ui(ui, world);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Center
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+/// Registers the render target with egui on first use.
+fn scene_texture_id(world: &mut World) -> Option<(egui::TextureId, UVec2)> {
+    let state = world.get_resource::<SceneViewState>()?;
+    if let Some(id) = state.egui_texture_id {
+        return Some((id, state.resolution));
+    }
+    let handle = state.render_target.clone();
+    let id = world
+        .get_resource_mut::<bevy_egui::EguiUserTextures>()?
+        .add_image(bevy_egui::EguiTextureHandle::Strong(handle));
+    let mut state = world.resource_mut::<SceneViewState>();
+    state.egui_texture_id = Some(id);
+    Some((id, state.resolution))
+}
+
+fn scene_view_panel_ui(ui: &mut egui::Ui, world: &mut World) {
+    let mut hovered = false;
+    let mut cursor_viewport_pos = None;
+    let has_camera = world
+        .query_filtered::<(), With<EditorCameraController>>()
+        .iter(world)
+        .next()
+        .is_some();
+    let is_editing = world
+        .get_resource::<State<EditorMode>>()
+        .is_some_and(|m| *m.get() == EditorMode::Edit);
+
+    match scene_texture_id(world) {
+        Some((tex_id, res)) if has_camera && is_editing => {
+            ui.horizontal(|ui| {
+                ui.menu_button("▦", |ui| {
+                    super::overlay::overlay_menu_ui(ui, world, |o| &mut o.scene_view)
+                })
+                .response
+                .on_hover_text("Overlays");
+                ui.separator();
+                crate::snap::snap_toolbar_world_ui(ui, world);
+            });
+            let display_size = compute_display_size(ViewZoom::Auto, res, ui.available_size());
+            let response = ui
+                .centered_and_justified(|ui| {
+                    ui.image(egui::load::SizedTexture::new(tex_id, display_size))
+                })
+                .inner;
+            hovered = response.hovered();
+            let rect = response.rect;
+            cursor_viewport_pos = ui
+                .ctx()
+                .pointer_latest_pos()
+                .filter(|p| hovered && rect.contains(*p))
+                .map(|p| {
+                    let uv = (p - rect.min) / rect.size();
+                    Vec2::new(uv.x * res.x as f32, uv.y * res.y as f32)
+                });
+        }
+        _ => {
+            let hint = if has_camera {
+                "Scene View is paused while playing"
+            } else {
+                "Add an EditorCameraController to a camera to use the Scene View"
+            };
+            ui.centered_and_justified(|ui| ui.weak(hint));
+        }
+    }
+
+    if let Some(mut focus) = world.get_resource_mut::<SceneViewFocus>() {
+        focus.hovered = hovered;
+        focus.cursor_viewport_pos = cursor_viewport_pos;
+    }
+}
@@ -0,0 +1,131 @@
+//! Panel help: documentation panels ship through `WorkbenchPanel::help`, shown behind the
+//! "?" entry of their tab's context menu and browsable in the Help panel (Help ▸ Panel Help).
+
+use bevy::prelude::*;
+
+use crate::dock::{TileLayoutState, WorkbenchPanel};
+
+/// String ID of the Help panel.
+pub const HELP_PANEL: &str = "workbench_help";
+
+/// Help of one panel.
+#[derive(Debug, Clone)]
+pub struct HelpTopic {
+    pub panel_id: String,
+    pub title: String,
+    /// Markdown returned by [`WorkbenchPanel::help`].
+    pub text: String,
+}
+
+/// Help of every registered panel, collected while the Help panel is open.
+#[derive(Resource, Default)]
+pub struct PanelHelp {
+    topics: Vec<HelpTopic>,
+    /// Panel whose help the Help panel shows.
+    pub selected: Option<String>,
+}
+
+impl PanelHelp {
+    /// Panels that provide help, sorted by title.
+    pub fn topics(&self) -> &[HelpTopic] {
+        &self.topics
+    }
+
+    fn refresh(&mut self, state: &TileLayoutState) {
+        self.topics = state
+            .panels
+            .values()
+            .filter_map(|panel| {
+                Some(HelpTopic {
+                    panel_id: panel.id().to_string(),
+                    title: panel.title(),
+                    text: panel.help()?.to_string(),
+                })
+            })
+            .collect();
+        self.topics.sort_by(|a, b| a.title.cmp(&b.title));
+    }
+}
+
+/// Rebuilds the help topics while the Help panel is open. Called by the dock before it takes
+/// the panels out of [`TileLayoutState`] to draw them.
+pub(crate) fn refresh_panel_help(world: &mut World) {
+    world.resource_scope(|world, mut help: Mut<PanelHelp>| {
+        let state = world.resource::<TileLayoutState>();
+        if state.is_panel_visible(HELP_PANEL) {
+            help.refresh(state);
+        }
+    });
+}
+
+/// Open the Help panel on the help of `panel_id`.
+pub(crate) fn show_panel_help(world: &mut World, panel_id: String) {
+    world.resource_mut::<PanelHelp>().selected = Some(panel_id);
+    world
+        .resource_mut::<TileLayoutState>()
+        .request_open_panel(HELP_PANEL);
+}
+
+/// Draw a panel's help text.
+pub(crate) fn help_text_ui(ui: &mut egui::Ui, text: &str) {
+    crate::bench_ui::markdown(ui, text);
+}
+
+/// Browser for the help of every panel that provides one.
+#[derive(Default)]
+pub struct HelpPanel;
+
+impl WorkbenchPanel for HelpPanel {
+    fn id(&self) -> &str {
+        HELP_PANEL
+    }
+
+    fn title(&self) -> String {
+        "Help".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let mut help = world.resource_mut::<PanelHelp>();
+        if help.topics.is_empty() {
+            ui.weak("No panel provides help yet");
+            return;
+        }
+        let help = &mut *help;
+        egui::SidePanel::left("workbench_help_topics")
+            .resizable(true)
+            .default_width(160.0)
+            .show_inside(ui, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for topic in &help.topics {
+                        let selected = help.selected.as_deref() == Some(topic.panel_id.as_str());
+                        if ui.selectable_label(selected, &topic.title).clicked() {
+                            help.selected = Some(topic.panel_id.clone());
+                        }
+                    }
+                });
+            });
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            let topic = help
+                .topics
+                .iter()
+                .find(|topic| help.selected.as_deref() == Some(topic.panel_id.as_str()));
+            let Some(topic) = topic else {
+                ui.weak("Select a panel to read its help");
+                return;
+            };
+            ui.heading(&topic.title);
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| help_text_ui(ui, &topic.text));
+        });
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
@@ -1,14 +1,27 @@
 //! Internationalization support using Fluent.
+//!
+//! Without the `i18n` feature, Fluent is not compiled in: only English is offered and
+//! messages are read from the simple `key = value` lines of the FTL sources.
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+#[cfg(feature = "i18n")]
 pub use fluent_bundle::FluentArgs;
+#[cfg(feature = "i18n")]
 use fluent_bundle::FluentResource;
-use fluent_bundle::concurrent::FluentBundle;
+use std::collections::HashMap;
 use std::sync::Arc;
+#[cfg(feature = "i18n")]
 use unic_langid::LanguageIdentifier;
 
+#[cfg(feature = "i18n")]
+type Bundle = fluent_bundle::concurrent::FluentBundle<FluentResource>;
+/// Message ID → text, parsed from single-line FTL messages.
+#[cfg(not(feature = "i18n"))]
+type Bundle = std::collections::HashMap<String, String>;
+
 /// Supported interface languages.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Locale {
     En,
     ZhCn,
@@ -21,7 +34,10 @@ impl Default for Locale {
 }
 
 impl Locale {
+    #[cfg(feature = "i18n")]
     pub const ALL: &[Locale] = &[Locale::En, Locale::ZhCn];
+    #[cfg(not(feature = "i18n"))]
+    pub const ALL: &[Locale] = &[Locale::En];
 
     pub fn label(&self) -> &'static str {
         match self {
@@ -30,7 +46,13 @@ impl Locale {
         }
     }
 
+    /// Whether the interface text needs a CJK-capable font.
+    pub fn needs_cjk(&self) -> bool {
+        matches!(self, Locale::ZhCn)
+    }
+
     /// Detect the best locale from the system language setting.
+    #[cfg(feature = "i18n")]
     pub fn from_system() -> Self {
         if let Some(tag) = sys_locale::get_locale() {
             let lower = tag.to_lowercase();
@@ -41,6 +63,13 @@ impl Locale {
         Locale::En
     }
 
+    /// Always English when built without the `i18n` feature.
+    #[cfg(not(feature = "i18n"))]
+    pub fn from_system() -> Self {
+        Locale::En
+    }
+
+    #[cfg(feature = "i18n")]
     fn lang_id(&self) -> LanguageIdentifier {
         match self {
             Locale::En => "en".parse().unwrap(),
@@ -56,13 +85,35 @@ impl Locale {
     }
 }
 
+/// System parameter for panels and systems that only read translations; works without the
+/// `I18n` resource (e.g. headless apps), falling back to the message ID.
+#[derive(SystemParam)]
+pub struct I18nText<'w> {
+    i18n: Option<Res<'w, I18n>>,
+}
+
+impl I18nText<'_> {
+    /// Localized string by message ID, borrowed from the `I18n` message cache.
+    pub fn t_cached<'a>(&'a self, id: &'a str) -> &'a str {
+        self.i18n.as_deref().map_or(id, |i18n| i18n.t(id))
+    }
+
+    /// Whether the locale or the sources changed since this system last ran.
+    pub fn is_changed(&self) -> bool {
+        self.i18n.as_ref().is_some_and(|i18n| i18n.is_changed())
+    }
+}
+
 /// Resource providing localized strings.
 #[derive(Resource)]
 pub struct I18n {
-    bundle: Arc<FluentBundle<FluentResource>>,
+    bundle: Arc<Bundle>,
     pub locale: Locale,
     /// Custom FTL sources registered by user panels (indexed by Locale).
     custom_sources: Vec<(Locale, String)>,
+    /// Formatted argument-free messages (locale → message ID → text), so `t` doesn't run
+    /// Fluent every frame. Kept across locale switches, dropped when a source is added.
+    messages: HashMap<Locale, HashMap<String, String>>,
 }
 
 impl Default for I18n {
@@ -74,10 +125,12 @@ impl Default for I18n {
 impl I18n {
     pub fn new(locale: Locale) -> Self {
         let bundle = Self::build_bundle(locale, &[]);
+        let messages = HashMap::from([(locale, Self::format_messages(&bundle, locale, &[]))]);
         Self {
             bundle: Arc::new(bundle),
             locale,
             custom_sources: Vec::new(),
+            messages,
         }
     }
 
@@ -85,33 +138,29 @@ impl I18n {
     /// The bundle is rebuilt immediately if the locale matches.
     pub fn add_custom_source(&mut self, locale: Locale, ftl: impl Into<String>) {
         self.custom_sources.push((locale, ftl.into()));
-        self.bundle = Arc::new(Self::build_bundle(self.locale, &self.custom_sources));
+        self.messages.remove(&locale);
+        self.rebuild();
     }
 
     /// Change the active locale.
     pub fn set_locale(&mut self, locale: Locale) {
         if self.locale != locale {
             self.locale = locale;
-            self.bundle = Arc::new(Self::build_bundle(locale, &self.custom_sources));
+            self.rebuild();
         }
     }
 
-    /// Get a localized string by message ID.
-    pub fn t(&self, id: &str) -> String {
-        let msg = self.bundle.get_message(id);
-        match msg {
-            Some(msg) => {
-                let pattern = msg.value().expect("message has no value");
-                let mut errors = vec![];
-                self.bundle
-                    .format_pattern(pattern, None, &mut errors)
-                    .to_string()
-            }
-            None => id.to_string(),
-        }
+    /// Get a localized string by message ID, borrowed from the message cache (the ID itself
+    /// when the message is missing). Call `.to_owned()` where a `String` is needed.
+    pub fn t<'a>(&'a self, id: &'a str) -> &'a str {
+        self.messages
+            .get(&self.locale)
+            .and_then(|messages| messages.get(id))
+            .map_or(id, String::as_str)
     }
 
     /// Get a localized string with named arguments.
+    #[cfg(feature = "i18n")]
     pub fn t_args(&self, id: &str, args: &fluent_bundle::FluentArgs) -> String {
         let msg = self.bundle.get_message(id);
         match msg {
@@ -126,11 +175,84 @@ impl I18n {
         }
     }
 
-    fn build_bundle(locale: Locale, custom: &[(Locale, String)]) -> FluentBundle<FluentResource> {
+    /// Get a localized string, filling its `{ $name }` placeables from `(name, value)` pairs.
+    /// Works with or without the `i18n` feature.
+    pub fn t_with(&self, id: &str, args: &[(&str, &str)]) -> String {
+        #[cfg(feature = "i18n")]
+        {
+            let mut fluent_args = FluentArgs::new();
+            for &(name, value) in args {
+                fluent_args.set(name, value.to_owned());
+            }
+            self.t_args(id, &fluent_args)
+        }
+        #[cfg(not(feature = "i18n"))]
+        {
+            args.iter()
+                .fold(self.t(id).to_owned(), |text, (name, value)| {
+                    text.replace(&format!("{{ ${name} }}"), value)
+                })
+        }
+    }
+
+    /// Rebuild the bundle for the current locale, formatting its messages unless cached.
+    fn rebuild(&mut self) {
+        let bundle = Self::build_bundle(self.locale, &self.custom_sources);
+        if !self.messages.contains_key(&self.locale) {
+            let messages = Self::format_messages(&bundle, self.locale, &self.custom_sources);
+            self.messages.insert(self.locale, messages);
+        }
+        self.bundle = Arc::new(bundle);
+    }
+
+    /// Built-in and custom FTL sources of `locale`.
+    fn sources(locale: Locale, custom: &[(Locale, String)]) -> impl Iterator<Item = &str> {
+        std::iter::once(locale.ftl_source()).chain(
+            custom
+                .iter()
+                .filter(move |(l, _)| *l == locale)
+                .map(|(_, s)| s.as_str()),
+        )
+    }
+
+    /// Every message of `locale`, formatted without arguments.
+    #[cfg(feature = "i18n")]
+    fn format_messages(
+        bundle: &Bundle,
+        locale: Locale,
+        custom: &[(Locale, String)],
+    ) -> HashMap<String, String> {
+        // Message definitions start a line with their identifier (terms start with `-`).
+        let ids = Self::sources(locale, custom)
+            .flat_map(str::lines)
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once('=').map(|(id, _)| id.trim()));
+        ids.filter_map(|id| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = vec![];
+            let text = bundle.format_pattern(pattern, None, &mut errors);
+            Some((id.to_string(), text.into_owned()))
+        })
+        .collect()
+    }
+
+    #[cfg(not(feature = "i18n"))]
+    fn format_messages(
+        bundle: &Bundle,
+        _locale: Locale,
+        _custom: &[(Locale, String)],
+    ) -> HashMap<String, String> {
+        bundle.clone()
+    }
+
+    #[cfg(feature = "i18n")]
+    fn build_bundle(locale: Locale, custom: &[(Locale, String)]) -> Bundle {
         let lang_id = locale.lang_id();
         let source = locale.ftl_source();
         let resource = FluentResource::try_new(source.to_string()).expect("valid FTL resource");
-        let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
+        let mut bundle = Bundle::new_concurrent(vec![lang_id]);
+        // egui fonts have no glyphs for the bidi isolation marks around placeables.
+        bundle.set_use_isolating(false);
         bundle.add_resource(resource).expect("add FTL resource");
 
         // Add custom sources matching this locale
@@ -144,4 +266,79 @@ impl I18n {
 
         bundle
     }
+
+    #[cfg(not(feature = "i18n"))]
+    fn build_bundle(locale: Locale, custom: &[(Locale, String)]) -> Bundle {
+        let mut bundle = Bundle::new();
+        for source in Self::sources(locale, custom) {
+            for line in source.lines() {
+                if line.starts_with(['#', ' ', '\t']) {
+                    continue;
+                }
+                if let Some((id, text)) = line.split_once('=') {
+                    bundle.insert(id.trim().to_string(), text.trim().to_string());
+                }
+            }
+        }
+        bundle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Message IDs defined in an FTL source.
+    fn ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once('=').map(|(id, _)| id.trim()))
+            .collect()
+    }
+
+    #[test]
+    fn looks_up_messages_per_locale() {
+        let mut i18n = I18n::new(Locale::En);
+        assert_eq!(i18n.t("menu-edit-undo"), "Undo");
+        i18n.set_locale(Locale::ZhCn);
+        assert_eq!(i18n.t("menu-edit-undo"), "撤销");
+        i18n.set_locale(Locale::En);
+        assert_eq!(i18n.t("menu-edit-undo"), "Undo");
+    }
+
+    #[test]
+    fn missing_message_falls_back_to_id() {
+        let i18n = I18n::new(Locale::En);
+        assert_eq!(i18n.t("no-such-message"), "no-such-message");
+    }
+
+    #[test]
+    fn custom_sources_apply_to_their_locale() {
+        let mut i18n = I18n::new(Locale::En);
+        i18n.add_custom_source(Locale::En, "my-panel = My Panel\n");
+        i18n.add_custom_source(Locale::ZhCn, "my-panel = 我的面板\n");
+        assert_eq!(i18n.t("my-panel"), "My Panel");
+        i18n.set_locale(Locale::ZhCn);
+        assert_eq!(i18n.t("my-panel"), "我的面板");
+    }
+
+    #[test]
+    fn fills_message_arguments() {
+        let mut i18n = I18n::new(Locale::En);
+        i18n.add_custom_source(Locale::En, "greeting = Hello, { $name }!\n");
+        assert_eq!(
+            i18n.t_with("greeting", &[("name", "Ferris")]),
+            "Hello, Ferris!"
+        );
+    }
+
+    #[test]
+    fn locales_define_the_same_messages() {
+        let mut en = ids(Locale::En.ftl_source());
+        let mut zh = ids(Locale::ZhCn.ftl_source());
+        en.sort_unstable();
+        zh.sort_unstable();
+        assert_eq!(en, zh);
+    }
 }
@@ -1,150 +1,79 @@
 //! Inspector panel: bridges bevy-inspector-egui for entity inspection.
 
-use bevy::ecs::component::ComponentId;
 use bevy::ecs::observer::Observer;
 use bevy::picking::pointer::PointerId;
 use bevy::prelude::*;
 use bevy::reflect::PartialReflect;
 use bevy::window::Monitor;
-use bevy_inspector_egui::bevy_inspector::{
-    self,
-    hierarchy::{Hierarchy, SelectedEntities},
-};
-
-use crate::dock::WorkbenchPanel;
+use bevy_inspector_egui::bevy_inspector::{self, hierarchy::SelectedEntities};
+use bevy_inspector_egui::inspector_egui_impls::InspectorEguiImpl;
+use bevy_inspector_egui::reflect_inspector::InspectorUi;
+use std::any::{Any, TypeId};
+use std::collections::HashSet;
+
+use crate::bench_ui::{Icon, icon};
+use crate::dock::{PanelSlot, WorkbenchPanel};
 use crate::i18n::I18n;
 
+mod hierarchy;
+mod locks;
+mod saved_selections;
+mod selection;
+mod undo;
+
+pub use locks::{
+    EditorLocked, LockedNames, apply_locked_names_system, load_locked_names_system,
+    lock_pickable_system,
+};
+pub use saved_selections::{SavedSelections, load_saved_selections_system};
+pub use selection::{SelectionChanged, selection_changed_system};
+pub(crate) use undo::InspectorUndoState;
+
 /// Marker component for entities created/managed by the workbench editor.
 /// These are hidden in the inspector hierarchy by default.
 #[derive(Component)]
 pub struct WorkbenchInternal;
 
-/// Resource tracking the currently selected entity for inspection.
+/// Resource tracking the currently selected entity for inspection. Changes to the selection are
+/// announced with [`SelectionChanged`].
 #[derive(Resource, Default)]
 pub struct InspectorSelection {
     pub selected: SelectedEntities,
     /// When true, show internal (workbench + Bevy) entities in the hierarchy.
     pub show_internal: bool,
+    /// Entities expanded in the hierarchy tree.
+    pub expanded: HashSet<Entity>,
+    /// Flattened rows of the hierarchy tree.
+    tree: hierarchy::HierarchyTree,
+    /// Entity to expand and scroll to the next time the hierarchy is drawn.
+    reveal: Option<Entity>,
 }
 
-/// Snapshot of an entity's reflected components (for undo).
-type ComponentSnapshot = Vec<(ComponentId, Box<dyn PartialReflect>)>;
-
-/// Clone a component snapshot (Box<dyn PartialReflect> uses clone_value()).
-fn clone_snapshot(snapshot: &ComponentSnapshot) -> ComponentSnapshot {
-    snapshot
-        .iter()
-        .map(|(id, val)| (*id, val.to_dynamic()))
-        .collect()
-}
-
-/// Tracks inspector editing for undo (baseline + debounce).
-#[derive(Resource, Default)]
-pub(crate) struct InspectorUndoState {
-    /// Entity being tracked.
-    tracked_entity: Option<Entity>,
-    /// Baseline snapshot taken when editing starts.
-    baseline: Option<ComponentSnapshot>,
-    /// Whether the mouse was pressed last frame (for drag detection).
-    was_pressing: bool,
-}
-
-/// Take a reflected snapshot of an entity's components.
-fn snapshot_entity(world: &World, entity: Entity) -> Option<ComponentSnapshot> {
-    let entity_ref = world.get_entity(entity).ok()?;
-    let type_registry = world.resource::<AppTypeRegistry>().clone();
-    let type_registry = type_registry.read();
-
-    let mut snapshot = Vec::new();
-    for &component_id in entity_ref.archetype().components() {
-        let Some(type_id) = world
-            .components()
-            .get_info(component_id)
-            .and_then(|info| info.type_id())
-        else {
-            continue;
-        };
-        let Some(registration) = type_registry.get(type_id) else {
-            continue;
-        };
-        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
-            continue;
-        };
-        if let Some(reflected) = reflect_component.reflect(entity_ref) {
-            snapshot.push((component_id, reflected.as_partial_reflect().to_dynamic()));
-        }
+impl InspectorSelection {
+    /// Select only `entity` and reveal it in the hierarchy.
+    pub fn select(&mut self, entity: Entity) {
+        self.selected.select_replace(entity);
+        self.reveal(entity);
     }
-    Some(snapshot)
-}
 
-/// Check if two snapshots differ.
-fn snapshots_differ(a: &ComponentSnapshot, b: &ComponentSnapshot) -> bool {
-    if a.len() != b.len() {
-        return true;
-    }
-    for ((id_a, val_a), (id_b, val_b)) in a.iter().zip(b.iter()) {
-        if id_a != id_b {
-            return true;
+    /// Replace the selection with `entities` and reveal the last one in the hierarchy.
+    pub fn select_many(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.selected.clear();
+        for entity in entities {
+            self.selected.select_maybe_add(entity, true);
         }
-        match val_a.reflect_partial_eq(val_b.as_ref()) {
-            Some(true) => {}
-            _ => return true,
-        }
-    }
-    false
-}
-
-/// Restore an entity's components from a snapshot.
-fn restore_snapshot(world: &mut World, entity: Entity, snapshot: &ComponentSnapshot) {
-    let type_registry = world.resource::<AppTypeRegistry>().clone();
-    let type_registry = type_registry.read();
-
-    for (component_id, value) in snapshot {
-        let Some(type_id) = world
-            .components()
-            .get_info(*component_id)
-            .and_then(|info| info.type_id())
-        else {
-            continue;
-        };
-        let Some(registration) = type_registry.get(type_id) else {
-            continue;
-        };
-        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
-            continue;
-        };
-        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
-            reflect_component.apply(&mut entity_mut, value.as_ref());
+        if let Some(&last) = self.selected.as_slice().last() {
+            self.reveal(last);
         }
     }
-}
-
-/// Undo action for inspector component changes (uses reflected snapshots).
-struct InspectorUndoAction {
-    entity: Entity,
-    before: ComponentSnapshot,
-    after: ComponentSnapshot,
-    desc: String,
-}
-
-impl crate::undo::UndoAction for InspectorUndoAction {
-    fn undo(&self, world: &mut World) {
-        restore_snapshot(world, self.entity, &self.before);
-    }
 
-    fn redo(&self, world: &mut World) {
-        restore_snapshot(world, self.entity, &self.after);
-    }
-
-    fn description(&self) -> &str {
-        &self.desc
+    /// Expand the hierarchy down to `entity` and scroll it into view, leaving the selection
+    /// unchanged. Applied the next time the inspector is drawn.
+    pub fn reveal(&mut self, entity: Entity) {
+        self.reveal = Some(entity);
     }
 }
 
-// InspectorUndoAction needs Send+Sync but Box<dyn PartialReflect> is Send+Sync already
-unsafe impl Send for InspectorUndoAction {}
-unsafe impl Sync for InspectorUndoAction {}
-
 /// Built-in inspector panel using bevy-inspector-egui.
 pub struct InspectorPanel;
 
@@ -171,6 +100,10 @@ impl WorkbenchPanel for InspectorPanel {
         true
     }
 
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Right
+    }
+
     fn closable(&self) -> bool {
         true
     }
@@ -182,16 +115,23 @@ fn inspector_panel_ui(ui: &mut egui::Ui, world: &mut World) {
         .remove_resource::<InspectorSelection>()
         .unwrap_or_default();
 
-    let (s_hierarchy, s_components, s_select_hint) = {
+    let (s_hierarchy, s_components, s_select_hint, s_locked) = {
         let i18n = world.get_resource::<I18n>();
-        let t = |id: &str| i18n.map_or_else(|| id.to_string(), |i| i.t(id));
+        let t = |id: &str| i18n.map_or(id, |i| i.t(id)).to_owned();
         (
             t("inspector-hierarchy"),
             t("inspector-components"),
             t("inspector-select-hint"),
+            t("inspector-locked"),
         )
     };
 
+    #[cfg(feature = "remote")]
+    if remote_mode_ui(ui, world, &s_hierarchy, &s_components, &s_select_hint) {
+        world.insert_resource(selected);
+        return;
+    }
+
     egui::SidePanel::left("inspector_hierarchy")
         .resizable(true)
         .default_width(180.0)
@@ -200,50 +140,79 @@ fn inspector_panel_ui(ui: &mut egui::Ui, world: &mut World) {
                 ui.heading(&s_hierarchy);
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.checkbox(&mut selected.show_internal, "🔧");
+                    saved_selections::selection_menu_ui(ui, world, &mut selected);
+                    #[cfg(feature = "remote")]
+                    remote_toggle_ui(ui, world);
                 });
             });
             ui.separator();
-            egui::ScrollArea::both().show(ui, |ui| {
-                let show_internal = selected.show_internal;
-                let mut hierarchy = Hierarchy {
-                    world,
-                    selected: &mut selected.selected,
-                    context_menu: None,
-                    shortcircuit_entity: None,
-                    extra_state: &mut (),
-                };
-                if show_internal {
-                    hierarchy.show::<()>(ui);
-                } else {
-                    hierarchy.show::<Without<WorkbenchInternal>>(ui);
-                }
-            });
+            hierarchy::hierarchy_ui(ui, world, &mut selected);
         });
 
     egui::CentralPanel::default().show_inside(ui, |ui| {
         ui.heading(&s_components);
         ui.separator();
         egui::ScrollArea::both().show(ui, |ui| {
-            inspector_components_ui(ui, world, &selected.selected, &s_select_hint);
+            inspector_components_ui(ui, world, &selected.selected, &s_select_hint, &s_locked);
         });
     });
 
     world.insert_resource(selected);
 }
 
+/// Draws the remote inspector in place of the local one while remote mode is on.
+#[cfg(feature = "remote")]
+fn remote_mode_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    s_hierarchy: &str,
+    s_components: &str,
+    s_select_hint: &str,
+) -> bool {
+    let Some(mut remote) = world.get_resource_mut::<crate::remote::RemoteInspector>() else {
+        return false;
+    };
+    if !remote.enabled {
+        return false;
+    }
+    crate::remote::remote_inspector_ui(ui, &mut remote, s_hierarchy, s_components, s_select_hint);
+    true
+}
+
+/// Button switching the inspector to remote mode.
+#[cfg(feature = "remote")]
+fn remote_toggle_ui(ui: &mut egui::Ui, world: &mut World) {
+    if let Some(mut remote) = world.get_resource_mut::<crate::remote::RemoteInspector>() {
+        ui.toggle_value(&mut remote.enabled, "🌐")
+            .on_hover_text("Remote mode");
+    }
+}
+
 /// Renders inspector components for selected entities.
 fn inspector_components_ui(
     ui: &mut egui::Ui,
     world: &mut World,
     selected: &SelectedEntities,
     s_select_hint: &str,
+    s_locked: &str,
 ) {
+    let locked = selected
+        .as_slice()
+        .iter()
+        .any(|&entity| world.get::<EditorLocked>(entity).is_some());
+    if locked {
+        ui.weak(format!("{} {s_locked}", icon(Icon::Lock)));
+    }
     match selected.as_slice() {
-        &[entity] => inspect_single_entity(ui, world, entity),
+        &[entity] => {
+            ui.add_enabled_ui(!locked, |ui| inspect_single_entity(ui, world, entity));
+        }
         entities if !entities.is_empty() => {
-            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                bevy_inspector::ui_for_entities_shared_components(world, entities, ui);
-            }));
+            ui.add_enabled_ui(!locked, |ui| {
+                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    bevy_inspector::ui_for_entities_shared_components(world, entities, ui);
+                }));
+            });
         }
         _ => {
             ui.weak(s_select_hint);
@@ -260,30 +229,20 @@ fn inspect_single_entity(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
 
         if undo_state.tracked_entity != Some(entity) {
             undo_state.tracked_entity = Some(entity);
-            undo_state.baseline = snapshot_entity(world, entity);
+            undo_state.rebaseline(world, entity);
             undo_state.was_pressing = false;
         }
 
-        let pressing = ui.input(|i| i.pointer.any_pressed());
+        // Held for the whole drag, so one drag becomes one undo entry on release.
+        let pressing = ui.input(|i| i.pointer.any_down());
         bevy_inspector::ui_for_entity(world, entity, ui);
 
         if undo_state.was_pressing
             && !pressing
-            && let Some(baseline) = &undo_state.baseline
-            && let Some(current) = snapshot_entity(world, entity)
-            && snapshots_differ(baseline, &current)
+            && let Some(action) = undo_state.take_edits(world, entity)
+            && let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>()
         {
-            let before = clone_snapshot(baseline);
-            let desc = format!("Modify entity {entity:?}");
-            if let Some(mut undo_stack) = world.get_resource_mut::<crate::undo::UndoStack>() {
-                undo_stack.push(InspectorUndoAction {
-                    entity,
-                    before,
-                    after: current,
-                    desc,
-                });
-            }
-            undo_state.baseline = snapshot_entity(world, entity);
+            undo_stack.push(action);
         }
         undo_state.was_pressing = pressing;
 
@@ -291,7 +250,7 @@ fn inspect_single_entity(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
     }));
     if result.is_err() {
         ui.colored_label(
-            egui::Color32::YELLOW,
+            crate::theme::palette(ui.ctx()).warn,
             "⚠ Some components could not be reflected",
         );
         if world.get_resource::<InspectorUndoState>().is_none() {
@@ -300,6 +259,93 @@ fn inspect_single_entity(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
     }
 }
 
+/// Types edited with the [`bench_ui`](crate::bench_ui) Euler-angle widgets in the inspector.
+trait EulerEdit: Reflect + Clone {
+    fn edit(&mut self, ui: &mut egui::Ui) -> bool;
+}
+
+impl EulerEdit for Quat {
+    fn edit(&mut self, ui: &mut egui::Ui) -> bool {
+        crate::bench_ui::rotation(ui, "", self)
+    }
+}
+
+impl EulerEdit for Transform {
+    fn edit(&mut self, ui: &mut egui::Ui) -> bool {
+        crate::bench_ui::transform_fields(ui, self)
+    }
+}
+
+fn euler_ui<T: EulerEdit>(
+    value: &mut dyn Any,
+    ui: &mut egui::Ui,
+    _options: &dyn Any,
+    id: egui::Id,
+    _env: InspectorUi<'_, '_>,
+) -> bool {
+    let Some(value) = value.downcast_mut::<T>() else {
+        return false;
+    };
+    ui.push_id(id, |ui| value.edit(ui)).inner
+}
+
+fn euler_ui_readonly<T: EulerEdit>(
+    value: &dyn Any,
+    ui: &mut egui::Ui,
+    _options: &dyn Any,
+    id: egui::Id,
+    _env: InspectorUi<'_, '_>,
+) {
+    let Some(value) = value.downcast_ref::<T>() else {
+        return;
+    };
+    let mut copy = value.clone();
+    ui.add_enabled_ui(false, |ui| ui.push_id(id, |ui| copy.edit(ui)));
+}
+
+/// Edits the first value and applies the result to every selected entity.
+fn euler_ui_many<T: EulerEdit>(
+    ui: &mut egui::Ui,
+    _options: &dyn Any,
+    id: egui::Id,
+    _env: InspectorUi<'_, '_>,
+    values: &mut [&mut dyn PartialReflect],
+    projector: &dyn Fn(&mut dyn PartialReflect) -> &mut dyn PartialReflect,
+) -> bool {
+    let Some(first) = values.first_mut() else {
+        return false;
+    };
+    let Some(mut edited) = projector(&mut **first).try_downcast_ref::<T>().cloned() else {
+        return false;
+    };
+    if !ui.push_id(id, |ui| edited.edit(ui)).inner {
+        return false;
+    }
+    for value in values.iter_mut() {
+        projector(&mut **value).apply(edited.as_partial_reflect());
+    }
+    true
+}
+
+/// Override bevy-inspector-egui's `Quat` and `Transform` editors with Euler angles in degrees.
+pub(crate) fn register_euler_inspectors(app: &mut App) {
+    app.register_type::<Quat>().register_type::<Transform>();
+    let registry = app.world().resource::<AppTypeRegistry>().clone();
+    let mut registry = registry.write();
+    register_euler_impl::<Quat>(&mut registry);
+    register_euler_impl::<Transform>(&mut registry);
+}
+
+fn register_euler_impl<T: EulerEdit>(registry: &mut bevy::reflect::TypeRegistry) {
+    if let Some(registration) = registry.get_mut(TypeId::of::<T>()) {
+        registration.insert(InspectorEguiImpl::new(
+            euler_ui::<T>,
+            euler_ui_readonly::<T>,
+            euler_ui_many::<T>,
+        ));
+    }
+}
+
 /// Marks Bevy-internal entities (Window, Monitor, Pointer, Observer) with
 /// [`WorkbenchInternal`] so the inspector hides them by default.
 pub fn mark_internal_entities_system(
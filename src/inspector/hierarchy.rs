@@ -0,0 +1,552 @@
+//! # hierarchy.rs
+//!
+//! # hierarchy.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The inspector's entity hierarchy. Each row is a collapsible tree node with a visibility
+//! toggle (flips `Visibility` between `Hidden` and `Inherited`) and a lock toggle
+//! (`EditorLocked`) in front of the entity name. Clicks support Ctrl (toggle) and Shift (range)
+//! selection over the rows currently visible in the tree. Double-clicking a name (or F2 with the
+//! inspector focused) renames the entity inline, inserting `Name` if needed, as one undo step.
+//! With the inspector focused, the hierarchy keybindings move a keyboard cursor over the rows
+//! (arrow keys by default), expand or collapse the row under it, and select it with Enter.
+//! The tree is virtualized: rows come from a flattened list of the expanded hierarchy and only
+//! those scrolled into view are laid out; expanded entities are kept in `InspectorSelection`.
+//! An entity passed to `InspectorSelection::select` or `reveal` has its ancestors expanded and is
+//! scrolled into view the next time the tree is drawn; the keyboard cursor stays where it was.
+//!
+//! 检查器中的实体层级。每一行是一个可折叠的树节点，在实体名称前带有可见性开关（在 `Hidden` 与
+//! `Inherited` 之间切换 `Visibility`）和锁定开关（`EditorLocked`）。点击支持 Ctrl（切换）和
+//! Shift（范围）选择，范围基于树中当前可见的行。双击名称（或在检查器获得焦点时按 F2）可就地重命名
+//! 实体，必要时插入 `Name` 组件，并记录为一个撤销步骤。检查器获得焦点时，层级快捷键（默认为方向键）
+//! 在各行之间移动键盘光标、展开或折叠光标所在行，并按 Enter 选中该实体。
+//! 层级树是虚拟化的：各行来自展开后层级的扁平列表，只有滚动到视野内的行才会布局；
+//! 已展开的实体保存在 `InspectorSelection` 中。
+//! 传给 `InspectorSelection::select` 或 `reveal` 的实体会在下次绘制层级树时展开其祖先并滚动到可见处；
+//! 键盘光标保持不动。
+
+use super::InspectorSelection;
+use super::locks::{EditorLocked, rename_locked, set_locked};
+use crate::bench_ui::{Icon, icon};
+use crate::dock::FocusedPanel;
+use crate::i18n::I18n;
+use crate::keybind::{KeyBindings, KeyInput};
+use crate::undo::{UndoAction, UndoStack};
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_inspector::guess_entity_name;
+use bevy_inspector_egui::bevy_inspector::hierarchy::{SelectedEntities, SelectionMode};
+use std::collections::{HashMap, HashSet};
+
+mod tree;
+
+pub(crate) use tree::HierarchyTree;
+use tree::TreeRow;
+
+/// A row interaction, applied after the tree has been drawn.
+enum RowAction {
+    Select(Entity, SelectionMode),
+    SetOpen(Entity, bool),
+    ToggleVisibility(Entity),
+    ToggleLock(Entity),
+    Rename(Entity, String),
+}
+
+/// Inline rename in progress (kept in egui temp memory between frames).
+#[derive(Clone)]
+struct RenameState {
+    entity: Entity,
+    text: String,
+    /// The text the field started with; committing it unchanged does nothing.
+    original: String,
+    focus_pending: bool,
+}
+
+/// A hierarchy navigation keybinding.
+#[derive(Clone, Copy)]
+enum NavKey {
+    Up,
+    Down,
+    Expand,
+    Collapse,
+    Select,
+}
+
+/// Per-frame state collected while drawing rows.
+struct Rows {
+    actions: Vec<RowAction>,
+    renaming: Option<RenameState>,
+    /// Row under the keyboard cursor (kept in egui temp memory between frames).
+    cursor: Option<Entity>,
+    /// Name label of each drawn row.
+    labels: HashMap<Entity, egui::Response>,
+    /// Cursor row that is scrolled out of view and must be revealed next frame.
+    reveal: Option<Entity>,
+    strings: RowStrings,
+}
+
+/// Localized tooltips and accessibility labels of a row.
+struct RowStrings {
+    expand: String,
+    toggle_visibility: String,
+    lock: String,
+}
+
+impl RowStrings {
+    fn new(i18n: Option<&I18n>) -> Self {
+        let t = |id: &str| i18n.map_or(id, |i| i.t(id)).to_owned();
+        Self {
+            expand: t("inspector-expand"),
+            toggle_visibility: t("inspector-toggle-visibility"),
+            lock: t("inspector-lock-hint"),
+        }
+    }
+}
+
+/// Open the rows leading down to `entity`, root first.
+fn expand_ancestors(
+    world: &World,
+    tree: &mut HierarchyTree,
+    expanded: &mut HashSet<Entity>,
+    entity: Entity,
+) {
+    let mut ancestors = Vec::new();
+    let mut current = entity;
+    while let Some(child_of) = world.get::<ChildOf>(current) {
+        current = child_of.parent();
+        ancestors.push(current);
+    }
+    for &ancestor in ancestors.iter().rev() {
+        tree.set_open(world, expanded, ancestor, true);
+    }
+}
+
+/// Draws the entity tree and applies selection, visibility and lock changes.
+/// Only the rows in view are laid out.
+pub(super) fn hierarchy_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    selection: &mut InspectorSelection,
+) {
+    let InspectorSelection {
+        selected,
+        show_internal,
+        expanded,
+        tree,
+        reveal: requested,
+    } = selection;
+    tree.refresh(world, expanded, *show_internal);
+    let requested = requested.take();
+    if let Some(entity) = requested {
+        expand_ancestors(world, tree, expanded, entity);
+    }
+    let rename_id = ui.make_persistent_id("workbench_hierarchy_rename");
+    let cursor_id = ui.make_persistent_id("workbench_hierarchy_cursor");
+    let reveal_id = ui.make_persistent_id("workbench_hierarchy_reveal");
+    let mut rows = Rows {
+        actions: Vec::new(),
+        renaming: ui.data(|d| d.get_temp::<RenameState>(rename_id)),
+        cursor: ui.data(|d| d.get_temp::<Entity>(cursor_id)),
+        labels: HashMap::new(),
+        reveal: None,
+        strings: RowStrings::new(world.get_resource::<I18n>()),
+    };
+    let inspector_focused = world
+        .get_resource::<FocusedPanel>()
+        .is_some_and(|f| f.is("workbench_inspector"));
+    if let &[entity] = selected.as_slice()
+        && inspector_focused
+        && !ui.ctx().wants_keyboard_input()
+        && ui.input(|i| i.key_pressed(egui::Key::F2))
+    {
+        rows.renaming = Some(RenameState::new(world, entity));
+    }
+
+    let row_height = ui.spacing().interact_size.y;
+    let mut scroll = egui::ScrollArea::both();
+    // A keyboard reveal also focuses the row; a requested one only scrolls to it.
+    let reveal = ui.data(|d| d.get_temp::<Entity>(reveal_id));
+    if let Some(index) = requested
+        .or(reveal)
+        .and_then(|entity| tree.position(entity))
+    {
+        let spacing = ui.spacing().item_spacing.y;
+        scroll = scroll.vertical_scroll_offset(index as f32 * (row_height + spacing));
+    }
+    let mut stale = false;
+    scroll.show_rows(ui, row_height, tree.rows().len(), |ui, range| {
+        for row in &tree.rows()[range] {
+            if world.get_entity(row.entity).is_err() {
+                stale = true;
+                continue;
+            }
+            let open = expanded.contains(&row.entity);
+            entity_row_ui(ui, world, selected, &mut rows, *row, open);
+        }
+    });
+    if stale {
+        tree.invalidate();
+    }
+    if let Some(label) = reveal.and_then(|entity| rows.labels.get(&entity)) {
+        label.request_focus();
+    }
+
+    // Drop a rename whose row is no longer in the tree (collapsed parent, despawned entity).
+    let renaming = rows
+        .renaming
+        .take()
+        .filter(|r| tree.position(r.entity).is_some());
+    let renaming_active = renaming.is_some();
+    ui.data_mut(|d| match renaming {
+        Some(state) => d.insert_temp(rename_id, state),
+        None => d.remove::<RenameState>(rename_id),
+    });
+    if inspector_focused
+        && !renaming_active
+        && rows.navigable(ui)
+        && let Some(key) = nav_key_pressed(world)
+    {
+        rows.navigate(tree.rows(), expanded, selected, key);
+    }
+    ui.data_mut(|d| {
+        match rows.cursor {
+            Some(cursor) => d.insert_temp(cursor_id, cursor),
+            None => d.remove::<Entity>(cursor_id),
+        }
+        match rows.reveal {
+            Some(entity) => d.insert_temp(reveal_id, entity),
+            None => d.remove::<Entity>(reveal_id),
+        }
+    });
+
+    for action in rows.actions {
+        match action {
+            RowAction::Select(entity, mode) => {
+                selected.select(mode, entity, |from, to| range(tree.rows(), from, to));
+            }
+            RowAction::SetOpen(entity, open) => tree.set_open(world, expanded, entity, open),
+            RowAction::ToggleVisibility(entity) => toggle_visibility(world, entity),
+            RowAction::ToggleLock(entity) => {
+                let locked = world.get::<EditorLocked>(entity).is_some();
+                set_locked(world, entity, !locked);
+            }
+            RowAction::Rename(entity, name) => rename_entity(world, entity, name),
+        }
+    }
+}
+
+impl RenameState {
+    fn new(world: &World, entity: Entity) -> Self {
+        let text = world
+            .get::<Name>(entity)
+            .map_or_else(|| guess_entity_name(world, entity), |n| n.to_string());
+        Self {
+            entity,
+            original: text.clone(),
+            text,
+            focus_pending: true,
+        }
+    }
+}
+
+impl Rows {
+    /// Keyboard focus is on nothing, the inspector itself or a row, not on an input field.
+    fn navigable(&self, ui: &egui::Ui) -> bool {
+        ui.ctx().memory(|m| m.focused()).is_none_or(|id| {
+            id == crate::dock::pane_focus_id("workbench_inspector")
+                || self.labels.values().any(|label| label.id == id)
+        })
+    }
+
+    /// Move the cursor, open or close its row, or select it.
+    fn navigate(
+        &mut self,
+        tree: &[TreeRow],
+        expanded: &HashSet<Entity>,
+        selected: &SelectedEntities,
+        key: NavKey,
+    ) {
+        let Some(last) = tree.len().checked_sub(1) else {
+            return;
+        };
+        // The cursor, or the last selected row if the cursor's row isn't in the tree.
+        let current = [self.cursor, selected.as_slice().last().copied()]
+            .into_iter()
+            .flatten()
+            .find_map(|entity| tree.iter().position(|row| row.entity == entity));
+        let target = match (key, current) {
+            (NavKey::Up, Some(i)) => i.saturating_sub(1),
+            (NavKey::Down, Some(i)) => (i + 1).min(last),
+            (NavKey::Expand, Some(i)) => match tree[i] {
+                row if row.has_children && !expanded.contains(&row.entity) => {
+                    self.actions.push(RowAction::SetOpen(row.entity, true));
+                    i
+                }
+                row if row.has_children => (i + 1).min(last),
+                _ => i,
+            },
+            (NavKey::Collapse, Some(i)) => match tree[i] {
+                row if row.has_children && expanded.contains(&row.entity) => {
+                    self.actions.push(RowAction::SetOpen(row.entity, false));
+                    i
+                }
+                // Jump to the parent row.
+                row => tree[..i]
+                    .iter()
+                    .rposition(|r| r.depth < row.depth)
+                    .unwrap_or(i),
+            },
+            (NavKey::Select, Some(i)) => {
+                self.actions
+                    .push(RowAction::Select(tree[i].entity, SelectionMode::Replace));
+                i
+            }
+            (_, None) => 0,
+        };
+        let entity = tree[target].entity;
+        self.cursor = Some(entity);
+        match self.labels.get(&entity) {
+            Some(label) => {
+                label.request_focus();
+                label.scroll_to_me(None);
+            }
+            None => self.reveal = Some(entity),
+        }
+    }
+}
+
+/// The hierarchy navigation binding pressed this frame, if any.
+fn nav_key_pressed(world: &World) -> Option<NavKey> {
+    let input = KeyInput::from_world(world)?;
+    let bindings = world.get_resource::<KeyBindings>()?;
+    [
+        (&bindings.hierarchy_up, NavKey::Up),
+        (&bindings.hierarchy_down, NavKey::Down),
+        (&bindings.hierarchy_expand, NavKey::Expand),
+        (&bindings.hierarchy_collapse, NavKey::Collapse),
+        (&bindings.hierarchy_select, NavKey::Select),
+    ]
+    .into_iter()
+    .find(|(slot, _)| slot.just_pressed(input))
+    .map(|(_, key)| key)
+}
+
+/// One tree row: indentation, expand arrow (for rows with children) and the row header.
+fn entity_row_ui(
+    ui: &mut egui::Ui,
+    world: &World,
+    selected: &SelectedEntities,
+    rows: &mut Rows,
+    row: TreeRow,
+    open: bool,
+) {
+    ui.horizontal(|ui| {
+        ui.add_space(row.depth as f32 * ui.spacing().indent);
+        let size = egui::vec2(ui.spacing().icon_width, ui.spacing().interact_size.y);
+        if !row.has_children {
+            ui.allocate_space(size);
+        } else {
+            let caret = if open {
+                Icon::CaretDown
+            } else {
+                Icon::CaretRight
+            };
+            let response = ui.add_sized(size, egui::Button::new(icon(caret)).frame(false));
+            response.widget_info(|| {
+                egui::WidgetInfo::selected(
+                    egui::WidgetType::CollapsingHeader,
+                    true,
+                    open,
+                    &rows.strings.expand,
+                )
+            });
+            if response.clicked() {
+                rows.actions.push(RowAction::SetOpen(row.entity, !open));
+            }
+        }
+        row_header_ui(ui, world, selected, rows, row.entity);
+    });
+}
+
+fn row_header_ui(
+    ui: &mut egui::Ui,
+    world: &World,
+    selected: &SelectedEntities,
+    rows: &mut Rows,
+    entity: Entity,
+) {
+    ui.spacing_mut().item_spacing.x = 2.0;
+    match world.get::<Visibility>(entity) {
+        Some(visibility) => {
+            let hidden = *visibility == Visibility::Hidden;
+            let glyph = icon(if hidden { Icon::Hidden } else { Icon::Visible });
+            if icon_toggle(ui, glyph, !hidden, &rows.strings.toggle_visibility).clicked() {
+                rows.actions.push(RowAction::ToggleVisibility(entity));
+            }
+        }
+        None => {
+            ui.add_space(ui.spacing().interact_size.y);
+        }
+    }
+    let locked = world.get::<EditorLocked>(entity).is_some();
+    let glyph = icon(if locked { Icon::Lock } else { Icon::Unlock });
+    if icon_toggle(ui, glyph, locked, &rows.strings.lock).clicked() {
+        rows.actions.push(RowAction::ToggleLock(entity));
+    }
+
+    if rows.renaming.as_ref().is_some_and(|r| r.entity == entity) {
+        rename_field_ui(ui, rows);
+        return;
+    }
+    let response = ui.selectable_label(selected.contains(entity), guess_entity_name(world, entity));
+    if response.double_clicked() {
+        rows.renaming = Some(RenameState::new(world, entity));
+    } else if response.clicked() {
+        let (ctrl, shift) = ui.input(|i| (i.modifiers.command, i.modifiers.shift));
+        let mode = SelectionMode::from_ctrl_shift(ctrl, shift);
+        rows.actions.push(RowAction::Select(entity, mode));
+        rows.cursor = Some(entity);
+    }
+    rows.labels.insert(entity, response);
+}
+
+/// Inline name editor: Enter or clicking away commits, Esc cancels.
+fn rename_field_ui(ui: &mut egui::Ui, rows: &mut Rows) {
+    let Some(state) = rows.renaming.as_mut() else {
+        return;
+    };
+    let response = ui.add(egui::TextEdit::singleline(&mut state.text).desired_width(120.0));
+    if state.focus_pending {
+        response.request_focus();
+        state.focus_pending = false;
+        return;
+    }
+    if response.lost_focus() {
+        let text = state.text.trim();
+        if text != state.original && !ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            rows.actions
+                .push(RowAction::Rename(state.entity, text.to_string()));
+        }
+        rows.renaming = None;
+    }
+}
+
+fn icon_toggle(ui: &mut egui::Ui, icon: &str, active: bool, tooltip: &str) -> egui::Response {
+    let color = if active {
+        ui.visuals().text_color()
+    } else {
+        ui.visuals().weak_text_color()
+    };
+    let response = ui.add(egui::Button::new(egui::RichText::new(icon).color(color)).frame(false));
+    response.widget_info(|| {
+        egui::WidgetInfo::selected(egui::WidgetType::Checkbox, true, active, tooltip)
+    });
+    response.on_hover_text(tooltip)
+}
+
+fn toggle_visibility(world: &mut World, entity: Entity) {
+    if let Some(mut visibility) = world.get_mut::<Visibility>(entity) {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+/// Undo action for an inline rename (`before` is `None` if the entity had no `Name`).
+struct RenameAction {
+    entity: Entity,
+    before: Option<Name>,
+    after: Name,
+    desc: String,
+}
+
+impl UndoAction for RenameAction {
+    fn undo(&self, world: &mut World) {
+        set_name(world, self.entity, self.before.clone());
+    }
+
+    fn redo(&self, world: &mut World) {
+        set_name(world, self.entity, Some(self.after.clone()));
+    }
+
+    fn description(&self) -> &str {
+        &self.desc
+    }
+}
+
+/// Set or remove an entity's `Name`, moving a persisted lock over to the new name without
+/// unlocking the entity.
+fn set_name(world: &mut World, entity: Entity, name: Option<Name>) {
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    let locked = entity_mut.contains::<EditorLocked>();
+    let old = entity_mut.get::<Name>().map(|n| n.as_str().to_string());
+    let new = name.as_ref().map(|n| n.as_str().to_string());
+    match name {
+        Some(name) => entity_mut.insert(name),
+        None => entity_mut.remove::<Name>(),
+    };
+    if locked {
+        rename_locked(world, old.as_deref(), new.as_deref());
+    }
+}
+
+fn rename_entity(world: &mut World, entity: Entity, name: String) {
+    let before = world.get::<Name>(entity).cloned();
+    if name.is_empty() || before.as_ref().is_some_and(|n| n.as_str() == name) {
+        return;
+    }
+    let after = Name::new(name);
+    set_name(world, entity, Some(after.clone()));
+    if let Some(mut undo_stack) = world.get_resource_mut::<UndoStack>() {
+        undo_stack.push(RenameAction {
+            entity,
+            desc: format!("Rename {entity:?} to \"{after}\""),
+            before,
+            after,
+        });
+    }
+}
+
+/// Rows between `from` and `to` (inclusive, either direction).
+fn range(tree: &[TreeRow], from: Entity, to: Entity) -> Vec<Entity> {
+    let (Some(a), Some(b)) = (
+        tree.iter().position(|row| row.entity == from),
+        tree.iter().position(|row| row.entity == to),
+    ) else {
+        return vec![to];
+    };
+    tree[a.min(b)..=a.max(b)]
+        .iter()
+        .map(|row| row.entity)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_ancestors_opens_root_first() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let parent = world.spawn(ChildOf(root)).id();
+        let entity = world.spawn(ChildOf(parent)).id();
+        let mut expanded = HashSet::new();
+        let mut tree = HierarchyTree::default();
+        tree.refresh(&mut world, &expanded, false);
+        assert_eq!(tree.position(entity), None);
+
+        expand_ancestors(&world, &mut tree, &mut expanded, entity);
+        // `parent` only has a row once `root` is open, so both opening means root went first.
+        assert_eq!(expanded, HashSet::from([root, parent]));
+        assert_eq!(
+            tree.position(entity),
+            Some(tree.position(parent).unwrap() + 1)
+        );
+    }
+}
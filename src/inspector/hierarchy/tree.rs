@@ -0,0 +1,291 @@
+//! # tree.rs
+//!
+//! # tree.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The entity hierarchy flattened into the list of rows the inspector can show: roots in entity
+//! order, followed by the children of expanded entities. Expanding or collapsing a row splices
+//! its subtree in or out of the list; the whole list is only rebuilt when entities are spawned or
+//! despawned, `Children` or `WorkbenchInternal` change, or internal entities are toggled.
+//!
+//! 将实体层级展平为检查器可显示的行列表：根实体按实体顺序排列，其后是已展开实体的子实体。
+//! 展开或折叠某一行时，只在列表中插入或移除其子树；只有在实体生成或销毁、`Children` 或
+//! `WorkbenchInternal` 发生变化，或切换内部实体显示时，才会完整重建整个列表。
+
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use crate::inspector::WorkbenchInternal;
+
+/// One row of the flattened hierarchy.
+#[derive(Clone, Copy)]
+pub(crate) struct TreeRow {
+    pub(crate) entity: Entity,
+    /// Nesting level (0 = root).
+    pub(crate) depth: usize,
+    /// Whether the entity has children shown in the tree.
+    pub(crate) has_children: bool,
+}
+
+/// The visible rows of the entity hierarchy, kept up to date incrementally.
+#[derive(Default)]
+pub(crate) struct HierarchyTree {
+    rows: Vec<TreeRow>,
+    show_internal: bool,
+    /// Counts and change tick of the last full build (`None` = rebuild).
+    built: Option<([usize; 3], Tick)>,
+}
+
+impl HierarchyTree {
+    pub(crate) fn rows(&self) -> &[TreeRow] {
+        &self.rows
+    }
+
+    /// Index of `entity`'s row, if it is in the tree.
+    pub(crate) fn position(&self, entity: Entity) -> Option<usize> {
+        self.rows.iter().position(|row| row.entity == entity)
+    }
+
+    /// Force a full rebuild on the next refresh.
+    pub(crate) fn invalidate(&mut self) {
+        self.built = None;
+    }
+
+    /// Rebuild the rows if the hierarchy changed since the last build.
+    pub(crate) fn refresh(
+        &mut self,
+        world: &mut World,
+        expanded: &HashSet<Entity>,
+        show_internal: bool,
+    ) {
+        let this_run = world.change_tick();
+        // Entities, roots and parents: catches despawns and removed `ChildOf` / `Children`,
+        // which leave no change tick behind.
+        let counts = [
+            world.query::<Entity>().iter(world).len(),
+            world
+                .query_filtered::<Entity, Without<ChildOf>>()
+                .iter(world)
+                .len(),
+            world
+                .query_filtered::<Entity, With<Children>>()
+                .iter(world)
+                .len(),
+        ];
+        let fresh = self.show_internal == show_internal
+            && self.built.is_some_and(|(built_counts, since)| {
+                built_counts == counts && !hierarchy_changed(world, since, this_run)
+            });
+        if fresh {
+            return;
+        }
+        self.show_internal = show_internal;
+        let mut roots: Vec<Entity> = world
+            .query_filtered::<Entity, Without<ChildOf>>()
+            .iter(world)
+            .filter(|&entity| self.shows(world, entity))
+            .collect();
+        roots.sort();
+        self.rows.clear();
+        for root in roots {
+            self.push_subtree(world, expanded, root, 0);
+        }
+        // Changes made later in this same run share its tick.
+        self.built = Some((counts, Tick::new(this_run.get().wrapping_sub(1))));
+    }
+
+    /// Expand or collapse the row of `entity`, splicing its subtree in or out.
+    pub(crate) fn set_open(
+        &mut self,
+        world: &World,
+        expanded: &mut HashSet<Entity>,
+        entity: Entity,
+        open: bool,
+    ) {
+        let Some(index) = self.position(entity) else {
+            return;
+        };
+        let row = self.rows[index];
+        if !row.has_children || expanded.contains(&entity) == open {
+            return;
+        }
+        if open {
+            expanded.insert(entity);
+            let mut subtree = Self {
+                show_internal: self.show_internal,
+                ..default()
+            };
+            for child in self.children(world, entity) {
+                subtree.push_subtree(world, expanded, child, row.depth + 1);
+            }
+            self.rows.splice(index + 1..index + 1, subtree.rows);
+        } else {
+            expanded.remove(&entity);
+            let end = self.rows[index + 1..]
+                .iter()
+                .position(|r| r.depth <= row.depth)
+                .map_or(self.rows.len(), |n| index + 1 + n);
+            self.rows.drain(index + 1..end);
+        }
+    }
+
+    fn shows(&self, world: &World, entity: Entity) -> bool {
+        self.show_internal || world.get::<WorkbenchInternal>(entity).is_none()
+    }
+
+    fn children(&self, world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<Children>(entity)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|&child| self.shows(world, child))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn push_subtree(
+        &mut self,
+        world: &World,
+        expanded: &HashSet<Entity>,
+        entity: Entity,
+        depth: usize,
+    ) {
+        let children = self.children(world, entity);
+        self.rows.push(TreeRow {
+            entity,
+            depth,
+            has_children: !children.is_empty(),
+        });
+        if expanded.contains(&entity) {
+            for child in children {
+                self.push_subtree(world, expanded, child, depth + 1);
+            }
+        }
+    }
+}
+
+/// Whether any `Children` or `WorkbenchInternal` changed after `since`.
+fn hierarchy_changed(world: &mut World, since: Tick, this_run: Tick) -> bool {
+    let changed = |tick: Tick| tick.is_newer_than(since, this_run);
+    world
+        .query::<Ref<Children>>()
+        .iter(world)
+        .any(|children| changed(children.last_changed()))
+        || world
+            .query::<Ref<WorkbenchInternal>>()
+            .iter(world)
+            .any(|internal| changed(internal.last_changed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(entity, depth)` of the rows among `entities`, in tree order.
+    fn rows_of(tree: &HierarchyTree, entities: &[Entity]) -> Vec<(Entity, usize)> {
+        tree.rows()
+            .iter()
+            .filter(|row| entities.contains(&row.entity))
+            .map(|row| (row.entity, row.depth))
+            .collect()
+    }
+
+    /// `root` with children `a` (which has child `leaf`) and `b`, plus a second root.
+    fn spawn_hierarchy(world: &mut World) -> [Entity; 5] {
+        let root = world.spawn_empty().id();
+        let a = world.spawn(ChildOf(root)).id();
+        let leaf = world.spawn(ChildOf(a)).id();
+        let b = world.spawn(ChildOf(root)).id();
+        let other = world.spawn_empty().id();
+        [root, a, leaf, b, other]
+    }
+
+    #[test]
+    fn collapsed_roots_only() {
+        let mut world = World::new();
+        let all @ [root, _, _, _, other] = spawn_hierarchy(&mut world);
+        let mut tree = HierarchyTree::default();
+        tree.refresh(&mut world, &HashSet::new(), false);
+        assert_eq!(rows_of(&tree, &all), [(root, 0), (other, 0)]);
+        assert!(tree.rows()[tree.position(root).unwrap()].has_children);
+        assert!(!tree.rows()[tree.position(other).unwrap()].has_children);
+    }
+
+    #[test]
+    fn expanded_entities_show_children_depth_first() {
+        let mut world = World::new();
+        let all @ [root, a, leaf, b, other] = spawn_hierarchy(&mut world);
+        let mut tree = HierarchyTree::default();
+        tree.refresh(&mut world, &HashSet::from([root, a]), false);
+        assert_eq!(
+            rows_of(&tree, &all),
+            [(root, 0), (a, 1), (leaf, 2), (b, 1), (other, 0)]
+        );
+    }
+
+    #[test]
+    fn set_open_splices_subtrees() {
+        let mut world = World::new();
+        let all @ [root, a, leaf, b, other] = spawn_hierarchy(&mut world);
+        let mut expanded = HashSet::from([a]);
+        let mut tree = HierarchyTree::default();
+        tree.refresh(&mut world, &expanded, false);
+        assert_eq!(rows_of(&tree, &all), [(root, 0), (other, 0)]);
+
+        // Opening the root brings back `a`'s remembered expansion.
+        tree.set_open(&world, &mut expanded, root, true);
+        assert_eq!(
+            rows_of(&tree, &all),
+            [(root, 0), (a, 1), (leaf, 2), (b, 1), (other, 0)]
+        );
+        tree.set_open(&world, &mut expanded, a, false);
+        assert_eq!(
+            rows_of(&tree, &all),
+            [(root, 0), (a, 1), (b, 1), (other, 0)]
+        );
+        assert!(!expanded.contains(&a));
+        // Leaves and rows outside the tree are ignored.
+        tree.set_open(&world, &mut expanded, b, true);
+        tree.set_open(&world, &mut expanded, leaf, true);
+        assert!(!expanded.contains(&b) && !expanded.contains(&leaf));
+    }
+
+    #[test]
+    fn internal_entities_only_with_show_internal() {
+        let mut world = World::new();
+        let root = world.spawn_empty().id();
+        let internal = world.spawn((WorkbenchInternal, ChildOf(root))).id();
+        let all = [root, internal];
+        let expanded = HashSet::from([root]);
+        let mut tree = HierarchyTree::default();
+        tree.refresh(&mut world, &expanded, false);
+        assert_eq!(rows_of(&tree, &all), [(root, 0)]);
+        assert!(!tree.rows()[tree.position(root).unwrap()].has_children);
+        tree.refresh(&mut world, &expanded, true);
+        assert_eq!(rows_of(&tree, &all), [(root, 0), (internal, 1)]);
+    }
+
+    #[test]
+    fn refresh_picks_up_spawns_and_despawns() {
+        let mut world = World::new();
+        let all @ [root, a, _, b, other] = spawn_hierarchy(&mut world);
+        let expanded = HashSet::from([root]);
+        let mut tree = HierarchyTree::default();
+        tree.refresh(&mut world, &expanded, false);
+        let late = world.spawn(ChildOf(root)).id();
+        assert!(world.despawn(other));
+        tree.refresh(&mut world, &expanded, false);
+        let mut watched = all.to_vec();
+        watched.push(late);
+        assert_eq!(
+            rows_of(&tree, &watched),
+            [(root, 0), (a, 1), (b, 1), (late, 1)]
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! # locks.rs
+//!
+//! # locks.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Editor-side entity locks. A locked entity carries `EditorLocked`: it is made unpickable (its
+//! previous `Pickable` is restored on unlock) and the inspector shows it read-only. Locks of
+//! named entities are persisted per workspace in `.workbench/locks.json` and re-applied when an
+//! entity with that name appears.
+//!
+//! 编辑器侧的实体锁定。被锁定的实体带有 `EditorLocked`：它将无法被拾取（解锁时恢复原有的
+//! `Pickable`），并在检查器中以只读方式显示。具名实体的锁定状态按工作区保存在
+//! `.workbench/locks.json` 中，并在具有该名称的实体出现时重新应用。
+
+use bevy::picking::Pickable;
+use bevy::prelude::*;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Editor-side lock: the entity can't be picked in the game view or edited in the inspector.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct EditorLocked;
+
+/// The `Pickable` an entity had before it was locked.
+#[derive(Component)]
+pub(crate) struct PickableBeforeLock(Option<Pickable>);
+
+/// Names of locked entities, persisted per workspace.
+#[derive(Resource)]
+pub struct LockedNames {
+    path: PathBuf,
+    names: BTreeSet<String>,
+}
+
+impl Default for LockedNames {
+    /// Default: `.workbench/locks.json` in the current working directory.
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(".workbench/locks.json"),
+            names: BTreeSet::new(),
+        }
+    }
+}
+
+impl LockedNames {
+    fn save(&self) {
+        let json = serde_json::to_string_pretty(&self.names).expect("serialize");
+        if let Err(e) = crate::storage::write_string(&self.path, &json) {
+            warn!("Failed to save {}: {e}", self.path.display());
+        }
+    }
+}
+
+/// Loads the persisted lock list.
+pub fn load_locked_names_system(mut locks: ResMut<LockedNames>) {
+    if let Some(names) = crate::storage::read_string(&locks.path)
+        .and_then(|content| serde_json::from_str(&content).ok())
+    {
+        locks.names = names;
+    }
+}
+
+/// Lock or unlock an entity, updating the persisted lock list for named entities.
+pub(crate) fn set_locked(world: &mut World, entity: Entity, locked: bool) {
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if locked {
+        entity_mut.insert(EditorLocked);
+    } else {
+        entity_mut.remove::<EditorLocked>();
+    }
+    let Some(name) = entity_mut.get::<Name>().map(|n| n.as_str().to_string()) else {
+        return;
+    };
+    let mut locks = world.resource_mut::<LockedNames>();
+    let changed = if locked {
+        locks.names.insert(name)
+    } else {
+        locks.names.remove(&name)
+    };
+    if changed {
+        locks.save();
+    }
+}
+
+/// Moves a persisted lock from `old` to `new` when a locked entity is renamed.
+pub(crate) fn rename_locked(world: &mut World, old: Option<&str>, new: Option<&str>) {
+    let mut locks = world.resource_mut::<LockedNames>();
+    let removed = old.is_some_and(|old| locks.names.remove(old));
+    let inserted = new.is_some_and(|new| locks.names.insert(new.to_string()));
+    if removed || inserted {
+        locks.save();
+    }
+}
+
+/// Re-applies persisted locks to newly named entities.
+pub fn apply_locked_names_system(
+    mut commands: Commands,
+    locks: Res<LockedNames>,
+    named: Query<(Entity, &Name), (Added<Name>, Without<EditorLocked>)>,
+) {
+    for (entity, name) in &named {
+        if locks.names.contains(name.as_str()) {
+            commands.entity(entity).insert(EditorLocked);
+        }
+    }
+}
+
+/// Makes locked entities unpickable and restores picking when they are unlocked.
+pub fn lock_pickable_system(
+    mut commands: Commands,
+    newly_locked: Query<(Entity, Option<&Pickable>), Added<EditorLocked>>,
+    mut unlocked: RemovedComponents<EditorLocked>,
+    before: Query<&PickableBeforeLock>,
+) {
+    for (entity, pickable) in &newly_locked {
+        commands
+            .entity(entity)
+            .insert((PickableBeforeLock(pickable.cloned()), Pickable::IGNORE));
+    }
+    for entity in unlocked.read() {
+        let Ok(PickableBeforeLock(previous)) = before.get(entity) else {
+            continue;
+        };
+        let Ok(mut entity) = commands.get_entity(entity) else {
+            continue;
+        };
+        match previous {
+            Some(pickable) => entity.insert(pickable.clone()),
+            None => entity.remove::<Pickable>(),
+        };
+        entity.remove::<PickableBeforeLock>();
+    }
+}
@@ -0,0 +1,318 @@
+//! # saved_selections.rs
+//!
+//! # saved_selections.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Saved selections and selection quick actions, reached from the star dropdown in the hierarchy
+//! header. The current multi-selection can be saved under a name and re-applied later; like
+//! locks, saved selections are persisted per workspace in `.workbench/selections.json` by entity
+//! `Name`, while unnamed entities are only remembered for the session. Re-applying prefers the
+//! saved entities that still exist and matches names only for the missing ones. The dropdown
+//! also offers "select all with component" for each component of the last selected entity.
+//!
+//! 保存的选择与选择快捷操作，通过层级面板标题栏的星标下拉菜单使用。当前的多选可以以名称保存并在
+//! 之后重新应用；与锁定一样，保存的选择按实体 `Name` 按工作区保存在 `.workbench/selections.json`
+//! 中，未命名的实体只在本次会话中记住。重新应用时优先使用仍然存在的已保存实体，只为缺失的实体按
+//! 名称匹配。下拉菜单还为最后选中实体的每个组件提供“选择所有带此组件的实体”。
+
+use bevy::ecs::component::ComponentId;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::{InspectorSelection, WorkbenchInternal};
+use crate::bench_ui::{Icon, icon};
+use crate::i18n::I18n;
+
+/// A saved selection.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct SavedSelection {
+    /// Names of the named entities (persisted).
+    names: Vec<String>,
+    /// Entities saved this session, including unnamed ones.
+    #[serde(skip)]
+    entities: Vec<Entity>,
+}
+
+/// Named multi-selections, persisted per workspace.
+#[derive(Resource)]
+pub struct SavedSelections {
+    path: PathBuf,
+    saved: BTreeMap<String, SavedSelection>,
+}
+
+impl Default for SavedSelections {
+    /// Default: `.workbench/selections.json` in the current working directory.
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(".workbench/selections.json"),
+            saved: BTreeMap::new(),
+        }
+    }
+}
+
+impl SavedSelections {
+    /// Names of the saved selections, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.saved.keys().map(String::as_str)
+    }
+
+    /// Save `entities` under `name`, replacing a selection with the same name.
+    pub fn save(&mut self, world: &World, name: impl Into<String>, entities: &[Entity]) {
+        let names = entities
+            .iter()
+            .filter_map(|&entity| world.get::<Name>(entity))
+            .map(|name| name.as_str().to_string())
+            .collect();
+        let selection = SavedSelection {
+            names,
+            entities: entities.to_vec(),
+        };
+        self.saved.insert(name.into(), selection);
+        self.write();
+    }
+
+    /// Delete the saved selection `name`.
+    pub fn remove(&mut self, name: &str) {
+        if self.saved.remove(name).is_some() {
+            self.write();
+        }
+    }
+
+    /// Existing entities of the saved selection `name`: those saved this session that still
+    /// exist, then, for the missing named ones, entities matched by name (each saved name at
+    /// most once).
+    pub fn entities(&self, world: &World, name: &str) -> Vec<Entity> {
+        let Some(saved) = self.saved.get(name) else {
+            return Vec::new();
+        };
+        let mut missing = saved.names.clone();
+        let mut entities = Vec::new();
+        for &entity in &saved.entities {
+            let Ok(entity_ref) = world.get_entity(entity) else {
+                continue;
+            };
+            entities.push(entity);
+            if let Some(name) = entity_ref.get::<Name>()
+                && let Some(i) = missing.iter().position(|n| n == name.as_str())
+            {
+                missing.swap_remove(i);
+            }
+        }
+        if missing.is_empty() {
+            return entities;
+        }
+        if let Some(mut named) = world.try_query::<(Entity, &Name)>() {
+            for (entity, entity_name) in named.iter(world) {
+                if entities.contains(&entity) {
+                    continue;
+                }
+                if let Some(i) = missing.iter().position(|n| n == entity_name.as_str()) {
+                    missing.swap_remove(i);
+                    entities.push(entity);
+                }
+            }
+        }
+        entities
+    }
+
+    fn write(&self) {
+        let json = serde_json::to_string_pretty(&self.saved).expect("serialize");
+        if let Err(e) = crate::storage::write_string(&self.path, &json) {
+            warn!("Failed to save {}: {e}", self.path.display());
+        }
+    }
+}
+
+/// Loads the persisted saved selections.
+pub fn load_saved_selections_system(mut saved: ResMut<SavedSelections>) {
+    if let Some(selections) = crate::storage::read_string(&saved.path)
+        .and_then(|content| serde_json::from_str(&content).ok())
+    {
+        saved.saved = selections;
+    }
+}
+
+/// Entities having the component `id`; workbench-internal ones only with `show_internal`.
+fn entities_with(world: &World, id: ComponentId, show_internal: bool) -> Vec<Entity> {
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(id))
+        .flat_map(|archetype| archetype.entities())
+        .map(|entity| entity.id())
+        .filter(|&entity| show_internal || world.get::<WorkbenchInternal>(entity).is_none())
+        .collect()
+}
+
+/// Components of `entity` as `(id, short name)`, sorted by name.
+fn components_of(world: &World, entity: Entity) -> Vec<(ComponentId, String)> {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    let mut components = Vec::new();
+    for &id in entity_ref.archetype().components() {
+        if let Some(info) = world.components().get_info(id) {
+            let name = info.name().to_string();
+            components.push((id, crate::world_stats::short_name(&name).to_string()));
+        }
+    }
+    components.sort_by(|a, b| a.1.cmp(&b.1));
+    components
+}
+
+/// Localized labels of the selection menu.
+struct SelectionStrings {
+    menu: String,
+    name_hint: String,
+    save: String,
+    empty: String,
+    delete: String,
+    select_all_with: String,
+}
+
+impl SelectionStrings {
+    fn new(world: &World) -> Self {
+        let i18n = world.get_resource::<I18n>();
+        let t = |id: &str| i18n.map_or(id, |i18n| i18n.t(id)).to_owned();
+        Self {
+            menu: t("inspector-saved-selections"),
+            name_hint: t("inspector-selection-name"),
+            save: t("inspector-save-selection"),
+            empty: t("inspector-no-saved-selections"),
+            delete: t("inspector-delete-saved-selection"),
+            select_all_with: t("inspector-select-all-with"),
+        }
+    }
+}
+
+/// Star dropdown of the hierarchy header: saved selections and select-by-component actions.
+pub(super) fn selection_menu_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    selection: &mut InspectorSelection,
+) {
+    let strings = SelectionStrings::new(world);
+    let name_id = ui.make_persistent_id("workbench_saved_selection_name");
+    ui.menu_button(icon(Icon::Star), |ui| {
+        let Some(mut saved) = world.remove_resource::<SavedSelections>() else {
+            return;
+        };
+        saved_list_ui(ui, world, &mut saved, selection, &strings);
+
+        let mut name: String = ui.data(|d| d.get_temp(name_id)).unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut name)
+                    .hint_text(&strings.name_hint)
+                    .desired_width(120.0),
+            );
+            let can_save = !name.trim().is_empty() && !selection.selected.as_slice().is_empty();
+            if ui
+                .add_enabled(can_save, egui::Button::new(&strings.save))
+                .clicked()
+            {
+                saved.save(world, name.trim(), selection.selected.as_slice());
+                name.clear();
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(name_id, name));
+        world.insert_resource(saved);
+
+        ui.separator();
+        select_by_component_ui(ui, world, selection, &strings);
+    })
+    .response
+    .on_hover_text(&strings.menu);
+}
+
+/// Saved selections: click to select, the close icon to delete.
+fn saved_list_ui(
+    ui: &mut egui::Ui,
+    world: &World,
+    saved: &mut SavedSelections,
+    selection: &mut InspectorSelection,
+    strings: &SelectionStrings,
+) {
+    let names: Vec<String> = saved.names().map(str::to_owned).collect();
+    if names.is_empty() {
+        ui.weak(&strings.empty);
+    }
+    for name in names {
+        ui.horizontal(|ui| {
+            if ui.button(&name).clicked() {
+                selection.select_many(saved.entities(world, &name));
+                ui.close();
+            }
+            if ui
+                .small_button(icon(Icon::Close))
+                .on_hover_text(&strings.delete)
+                .clicked()
+            {
+                saved.remove(&name);
+            }
+        });
+    }
+}
+
+/// "Select All With" submenu listing the components of the last selected entity.
+fn select_by_component_ui(
+    ui: &mut egui::Ui,
+    world: &World,
+    selection: &mut InspectorSelection,
+    strings: &SelectionStrings,
+) {
+    let components = selection
+        .selected
+        .as_slice()
+        .last()
+        .map(|&entity| components_of(world, entity))
+        .unwrap_or_default();
+    ui.add_enabled_ui(!components.is_empty(), |ui| {
+        ui.menu_button(&strings.select_all_with, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for (id, name) in components {
+                        if ui.button(name).clicked() {
+                            let entities = entities_with(world, id, selection.show_internal);
+                            selection.select_many(entities);
+                            ui.close();
+                        }
+                    }
+                });
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_entities_win_over_names_matched_once() {
+        let mut world = World::new();
+        let kept = world.spawn(Name::new("enemy")).id();
+        let gone = world.spawn(Name::new("enemy")).id();
+        world.despawn(gone);
+        let first = world.spawn(Name::new("enemy")).id();
+        let second = world.spawn(Name::new("enemy")).id();
+
+        let mut saved = SavedSelections::default();
+        saved.saved.insert(
+            "wave".into(),
+            SavedSelection {
+                names: vec!["enemy".into(), "enemy".into()],
+                entities: vec![kept, gone],
+            },
+        );
+        let entities = saved.entities(&world, "wave");
+        // The kept entity, plus one stand-in for the despawned one.
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0], kept);
+        assert!(entities[1] == first || entities[1] == second);
+    }
+}
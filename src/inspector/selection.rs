@@ -0,0 +1,63 @@
+//! # selection.rs
+//!
+//! # selection.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Selection change notifications. `selection_changed_system` compares the entities selected in
+//! `InspectorSelection` with those of the previous frame and, when they differ, sends a
+//! `SelectionChanged` message listing the added and removed entities. It is also triggered as an
+//! observer event, so custom panels (gizmos, curve editors) can react with either a
+//! `MessageReader` or `app.add_observer(|change: On<SelectionChanged>| ...)` whatever changed the
+//! selection: hierarchy clicks, picking, search or code.
+//!
+//! 选择变更通知。`selection_changed_system` 会把 `InspectorSelection` 中选中的实体与上一帧比较，
+//! 不同时发送一条列出新增与移除实体的 `SelectionChanged` 消息。它也会作为观察者事件触发，
+//! 因此无论选择是由层级点击、拾取、搜索还是代码修改的，自定义面板（如 gizmo、曲线编辑器）都可以用
+//! `MessageReader` 或 `app.add_observer(|change: On<SelectionChanged>| ...)` 作出响应。
+
+use bevy::prelude::*;
+
+use super::InspectorSelection;
+
+/// Sent when the entities selected in the inspector change; also triggered as an observer event.
+#[derive(Message, Event, Debug, Clone, PartialEq, Eq)]
+pub struct SelectionChanged {
+    /// Entities that became selected, in selection order.
+    pub added: Vec<Entity>,
+    /// Entities that are no longer selected.
+    pub removed: Vec<Entity>,
+}
+
+/// Sends [`SelectionChanged`] when the selection differs from the last frame's.
+pub fn selection_changed_system(
+    mut commands: Commands,
+    selection: Res<InspectorSelection>,
+    mut previous: Local<Vec<Entity>>,
+    mut changes: MessageWriter<SelectionChanged>,
+) {
+    let current = selection.selected.as_slice();
+    if !selection.is_changed() || current == previous.as_slice() {
+        return;
+    }
+    let change = SelectionChanged {
+        added: current
+            .iter()
+            .filter(|entity| !previous.contains(*entity))
+            .copied()
+            .collect(),
+        removed: previous
+            .iter()
+            .filter(|entity| !current.contains(*entity))
+            .copied()
+            .collect(),
+    };
+    *previous = current.to_vec();
+    if change.added.is_empty() && change.removed.is_empty() {
+        return;
+    }
+    changes.write(change.clone());
+    commands.trigger(change);
+}
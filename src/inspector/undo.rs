@@ -0,0 +1,240 @@
+//! # undo.rs
+//!
+//! # undo.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Undo tracking for inspector edits. A reflected baseline of the inspected entity is taken when
+//! it is selected; when the mouse is released, only components whose change ticks moved since the
+//! baseline are reflected again and compared, the differing ones become one undo action and are
+//! folded back into the baseline. A full snapshot is only retaken when components are added or
+//! removed.
+//!
+//! 检查器编辑的撤销跟踪。选中实体时为其拍摄一份反射基线；松开鼠标时，只重新反射并比较自基线以来
+//! 变更 tick 有变化的组件，其中确有差异的组件合并为一个撤销操作，并写回基线。只有在组件被添加或
+//! 移除时才重新拍摄完整快照。
+
+use bevy::ecs::archetype::ArchetypeId;
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::prelude::*;
+use bevy::reflect::{PartialReflect, TypeRegistry};
+
+/// Snapshot of an entity's reflected components (for undo).
+type ComponentSnapshot = Vec<(ComponentId, Box<dyn PartialReflect>)>;
+
+/// Clone a component snapshot (Box<dyn PartialReflect> uses clone_value()).
+fn clone_snapshot(snapshot: &ComponentSnapshot) -> ComponentSnapshot {
+    snapshot
+        .iter()
+        .map(|(id, val)| (*id, val.to_dynamic()))
+        .collect()
+}
+
+/// Tracks inspector editing for undo (baseline + debounce).
+#[derive(Resource, Default)]
+pub(crate) struct InspectorUndoState {
+    /// Entity being tracked.
+    pub(super) tracked_entity: Option<Entity>,
+    /// Baseline snapshot, updated in place as edits are recorded.
+    baseline: Option<ComponentSnapshot>,
+    /// Archetype of the tracked entity when the baseline was taken.
+    archetype: Option<ArchetypeId>,
+    /// Component changes after this tick are not folded into the baseline yet.
+    since: Tick,
+    /// Components changed since `since` (reused between frames).
+    changed: Vec<ComponentId>,
+    /// Whether the mouse was pressed last frame (for drag detection).
+    pub(super) was_pressing: bool,
+}
+
+impl InspectorUndoState {
+    /// Take a fresh baseline of `entity`.
+    pub(crate) fn rebaseline(&mut self, world: &World, entity: Entity) {
+        self.baseline = snapshot_entity(world, entity);
+        self.archetype = world.get_entity(entity).ok().map(|e| e.archetype().id());
+        // Inspector edits in this same run get the current tick, and `is_changed` only
+        // counts strictly newer ticks.
+        self.since = Tick::new(world.change_tick().get().wrapping_sub(1));
+    }
+
+    /// Fold an edit a tool made to `entity` outside the inspector into the baseline. For tools
+    /// that record their own undo action, so the inspector doesn't record the edit again.
+    pub(crate) fn absorb_edit(world: &mut World, entity: Entity) {
+        let Some(mut state) = world.remove_resource::<Self>() else {
+            return;
+        };
+        if state.tracked_entity == Some(entity) {
+            state.rebaseline(world, entity);
+        }
+        world.insert_resource(state);
+    }
+
+    /// Fold the edits made since the baseline into it, returning them as one undo action.
+    /// Only components whose change ticks moved are reflected again.
+    pub(crate) fn take_edits(
+        &mut self,
+        world: &World,
+        entity: Entity,
+    ) -> Option<InspectorUndoAction> {
+        let entity_ref = world.get_entity(entity).ok()?;
+        let desc = format!("Modify entity {entity:?}");
+        if self.archetype != Some(entity_ref.archetype().id()) {
+            // Components were added or removed: compare whole snapshots.
+            let before = self.baseline.take();
+            self.rebaseline(world, entity);
+            let (before, after) = (before?, clone_snapshot(self.baseline.as_ref()?));
+            return snapshots_differ(&before, &after).then_some(InspectorUndoAction {
+                entity,
+                before,
+                after,
+                desc,
+            });
+        }
+
+        let this_run = world.change_tick();
+        self.changed.clear();
+        self.changed.extend(
+            self.baseline
+                .iter()
+                .flatten()
+                .map(|(id, _)| *id)
+                .filter(|&id| {
+                    entity_ref
+                        .get_change_ticks_by_id(id)
+                        .is_some_and(|ticks| ticks.is_changed(self.since, this_run))
+                }),
+        );
+        self.since = Tick::new(this_run.get().wrapping_sub(1));
+        if self.changed.is_empty() {
+            return None;
+        }
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        for (id, value) in self.baseline.iter_mut().flatten() {
+            if !self.changed.contains(id) {
+                continue;
+            }
+            let Some(current) = reflect_component(world, &type_registry, entity_ref, *id) else {
+                continue;
+            };
+            if value.reflect_partial_eq(current) == Some(true) {
+                continue;
+            }
+            before.push((*id, std::mem::replace(value, current.to_dynamic())));
+            after.push((*id, current.to_dynamic()));
+        }
+        (!before.is_empty()).then_some(InspectorUndoAction {
+            entity,
+            before,
+            after,
+            desc,
+        })
+    }
+}
+
+/// Reflect one component of an entity, if its type is registered with `ReflectComponent`.
+fn reflect_component<'w>(
+    world: &World,
+    type_registry: &TypeRegistry,
+    entity_ref: EntityRef<'w>,
+    component_id: ComponentId,
+) -> Option<&'w dyn PartialReflect> {
+    let type_id = world
+        .components()
+        .get_info(component_id)
+        .and_then(|info| info.type_id())?;
+    let reflect_component = type_registry.get(type_id)?.data::<ReflectComponent>()?;
+    reflect_component
+        .reflect(entity_ref)
+        .map(|reflected| reflected.as_partial_reflect())
+}
+
+/// Take a reflected snapshot of an entity's components.
+fn snapshot_entity(world: &World, entity: Entity) -> Option<ComponentSnapshot> {
+    let entity_ref = world.get_entity(entity).ok()?;
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    let snapshot = entity_ref
+        .archetype()
+        .components()
+        .iter()
+        .filter_map(|&component_id| {
+            reflect_component(world, &type_registry, entity_ref, component_id)
+                .map(|reflected| (component_id, reflected.to_dynamic()))
+        })
+        .collect();
+    Some(snapshot)
+}
+
+/// Check if two snapshots differ.
+fn snapshots_differ(a: &ComponentSnapshot, b: &ComponentSnapshot) -> bool {
+    if a.len() != b.len() {
+        return true;
+    }
+    for ((id_a, val_a), (id_b, val_b)) in a.iter().zip(b.iter()) {
+        if id_a != id_b {
+            return true;
+        }
+        match val_a.reflect_partial_eq(val_b.as_ref()) {
+            Some(true) => {}
+            _ => return true,
+        }
+    }
+    false
+}
+
+/// Restore an entity's components from a snapshot.
+fn restore_snapshot(world: &mut World, entity: Entity, snapshot: &ComponentSnapshot) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    for (component_id, value) in snapshot {
+        let Some(type_id) = world
+            .components()
+            .get_info(*component_id)
+            .and_then(|info| info.type_id())
+        else {
+            continue;
+        };
+        let Some(registration) = type_registry.get(type_id) else {
+            continue;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            continue;
+        };
+        if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+            reflect_component.apply(&mut entity_mut, value.as_ref());
+        }
+    }
+}
+
+/// Undo action for inspector component changes (uses reflected snapshots).
+pub(crate) struct InspectorUndoAction {
+    entity: Entity,
+    before: ComponentSnapshot,
+    after: ComponentSnapshot,
+    desc: String,
+}
+
+impl crate::undo::UndoAction for InspectorUndoAction {
+    fn undo(&self, world: &mut World) {
+        restore_snapshot(world, self.entity, &self.before);
+    }
+
+    fn redo(&self, world: &mut World) {
+        restore_snapshot(world, self.entity, &self.after);
+    }
+
+    fn description(&self) -> &str {
+        &self.desc
+    }
+}
+
+// InspectorUndoAction needs Send+Sync but Box<dyn PartialReflect> is Send+Sync already
+unsafe impl Send for InspectorUndoAction {}
+unsafe impl Sync for InspectorUndoAction {}
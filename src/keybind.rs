@@ -2,71 +2,211 @@
 
 use bevy::prelude::*;
 
-/// A single key binding: a primary key plus optional modifiers.
-#[derive(Debug, Clone)]
+mod capture;
+mod chord;
+mod layout;
+mod scope;
+mod wheel;
+
+pub use capture::{EguiInputCapture, egui_input_capture_system};
+pub use chord::{BindingInput, CHORD_TIMEOUT, ChordState, KeyInput, chord_state_system};
+pub use layout::{KeyLayout, key_layout_system};
+pub use scope::BindScope;
+pub use wheel::{WheelNotch, wheel_notch_system};
+
+/// What presses a binding step: a key, a mouse button or a scroll gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BindTrigger {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// Scrolling in a direction. Combine it with a modifier (e.g. Ctrl+Wheel Up), since plain
+    /// scrolling also scrolls panels.
+    Wheel(WheelDirection),
+}
+
+/// Direction of a [`BindTrigger::Wheel`] gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WheelDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl WheelDirection {
+    /// The direction of a scroll `delta`, vertical first.
+    pub fn of(delta: Vec2) -> Option<Self> {
+        if delta.y > 0.0 {
+            Some(Self::Up)
+        } else if delta.y < 0.0 {
+            Some(Self::Down)
+        } else if delta.x > 0.0 {
+            Some(Self::Right)
+        } else if delta.x < 0.0 {
+            Some(Self::Left)
+        } else {
+            None
+        }
+    }
+}
+
+/// A single key binding: a key or mouse button plus optional modifiers, optionally followed by a
+/// second step to form a chord (e.g. Ctrl+K, Ctrl+S).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct KeyBind {
-    pub key: KeyCode,
+    pub trigger: BindTrigger,
     pub ctrl: bool,
     pub shift: bool,
     pub alt: bool,
+    /// Second step of a two-step chord.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub then: Option<Box<KeyBind>>,
 }
 
 impl KeyBind {
     /// Simple key without modifiers.
     pub const fn key(key: KeyCode) -> Self {
-        Self {
-            key,
-            ctrl: false,
-            shift: false,
-            alt: false,
-        }
+        Self::with_modifiers(BindTrigger::Key(key), false, false)
     }
 
     /// Ctrl + key.
     pub const fn ctrl(key: KeyCode) -> Self {
-        Self {
-            key,
-            ctrl: true,
-            shift: false,
-            alt: false,
-        }
+        Self::with_modifiers(BindTrigger::Key(key), true, false)
+    }
+
+    /// Shift + key.
+    pub const fn shift(key: KeyCode) -> Self {
+        Self::with_modifiers(BindTrigger::Key(key), false, true)
     }
 
     /// Ctrl + Shift + key.
     pub const fn ctrl_shift(key: KeyCode) -> Self {
+        Self::with_modifiers(BindTrigger::Key(key), true, true)
+    }
+
+    /// Ctrl + Alt + key.
+    pub const fn ctrl_alt(key: KeyCode) -> Self {
+        let mut bind = Self::ctrl(key);
+        bind.alt = true;
+        bind
+    }
+
+    /// Ctrl + Shift + Alt + key.
+    pub const fn ctrl_shift_alt(key: KeyCode) -> Self {
+        let mut bind = Self::ctrl_shift(key);
+        bind.alt = true;
+        bind
+    }
+
+    /// Mouse button without modifiers.
+    pub const fn mouse(button: MouseButton) -> Self {
+        Self::with_modifiers(BindTrigger::Mouse(button), false, false)
+    }
+
+    /// Ctrl + scroll in `direction`.
+    pub const fn ctrl_wheel(direction: WheelDirection) -> Self {
+        Self::with_modifiers(BindTrigger::Wheel(direction), true, false)
+    }
+
+    const fn with_modifiers(trigger: BindTrigger, ctrl: bool, shift: bool) -> Self {
         Self {
-            key,
-            ctrl: true,
-            shift: true,
+            trigger,
+            ctrl,
+            shift,
             alt: false,
+            then: None,
         }
     }
 
-    /// Check if this binding was just pressed.
-    pub fn just_pressed(&self, input: &ButtonInput<KeyCode>) -> bool {
-        if !input.just_pressed(self.key) {
-            return false;
-        }
-        let ctrl_ok = if self.ctrl {
-            input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight)
-        } else {
-            !input.pressed(KeyCode::ControlLeft) && !input.pressed(KeyCode::ControlRight)
-        };
-        let shift_ok = if self.shift {
-            input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight)
-        } else {
-            !input.pressed(KeyCode::ShiftLeft) && !input.pressed(KeyCode::ShiftRight)
-        };
-        let alt_ok = if self.alt {
-            input.pressed(KeyCode::AltLeft) || input.pressed(KeyCode::AltRight)
-        } else {
-            !input.pressed(KeyCode::AltLeft) && !input.pressed(KeyCode::AltRight)
+    /// Make this a chord: `KeyBind::ctrl(KeyCode::KeyK).then(KeyBind::ctrl(KeyCode::KeyS))`.
+    /// A modifier key followed by itself is a double tap: `KeyBind::key(KeyCode::ShiftLeft)
+    /// .then(KeyBind::key(KeyCode::ShiftLeft))`.
+    pub fn then(mut self, next: KeyBind) -> Self {
+        self.then = Some(Box::new(next));
+        self
+    }
+
+    /// The step for `trigger` with the modifiers currently held.
+    pub(crate) fn step(trigger: BindTrigger, input: &ButtonInput<KeyCode>) -> Self {
+        let held = |left, right| input.pressed(left) || input.pressed(right);
+        let mut step = Self {
+            trigger,
+            ctrl: held(KeyCode::ControlLeft, KeyCode::ControlRight),
+            shift: held(KeyCode::ShiftLeft, KeyCode::ShiftRight),
+            alt: held(KeyCode::AltLeft, KeyCode::AltRight),
+            then: None,
         };
-        ctrl_ok && shift_ok && alt_ok
+        // A modifier key does not modify itself.
+        match trigger {
+            BindTrigger::Key(KeyCode::ControlLeft | KeyCode::ControlRight) => step.ctrl = false,
+            BindTrigger::Key(KeyCode::ShiftLeft | KeyCode::ShiftRight) => step.shift = false,
+            BindTrigger::Key(KeyCode::AltLeft | KeyCode::AltRight) => step.alt = false,
+            _ => {}
+        }
+        step
     }
 
-    /// Human-readable label for UI display.
+    /// Whether this step is a lone modifier key.
+    pub(crate) fn is_modifier(&self) -> bool {
+        matches!(
+            self.trigger,
+            BindTrigger::Key(
+                KeyCode::ControlLeft
+                    | KeyCode::ControlRight
+                    | KeyCode::ShiftLeft
+                    | KeyCode::ShiftRight
+                    | KeyCode::AltLeft
+                    | KeyCode::AltRight
+            )
+        )
+    }
+
+    /// Whether the first steps of `self` and `other` are the same, ignoring `then`.
+    pub(crate) fn same_step(&self, other: &KeyBind) -> bool {
+        self.trigger == other.trigger
+            && self.ctrl == other.ctrl
+            && self.shift == other.shift
+            && self.alt == other.alt
+    }
+
+    /// Whether this step (ignoring `then`) was just pressed with exactly its modifiers.
+    fn step_just_pressed(&self, input: &KeyInput) -> bool {
+        input.trigger_just_pressed(self.trigger)
+            && self.same_step(&Self::step(self.trigger, input.keys))
+    }
+
+    /// Check if this binding was just pressed. A chord fires when its second step is pressed
+    /// right after the first; other bindings don't fire on a chord's second step.
+    pub fn just_pressed<'a>(&self, input: impl Into<KeyInput<'a>>) -> bool {
+        let input = input.into();
+        match &self.then {
+            None => input.resolving().is_none() && self.step_just_pressed(&input),
+            Some(second) => {
+                input.resolving().is_some_and(|first| first.same_step(self))
+                    && second.step_just_pressed(&input)
+            }
+        }
+    }
+
+    /// Check if this binding is held. Extra modifiers are allowed, so a held toggle keeps
+    /// working while other shortcuts are combined with it. Chords are never held.
+    pub fn pressed<'a>(&self, input: impl Into<KeyInput<'a>>) -> bool {
+        let input = input.into();
+        let held = |left, right| input.keys.pressed(left) || input.keys.pressed(right);
+        self.then.is_none()
+            && input.trigger_pressed(self.trigger)
+            && (!self.ctrl || held(KeyCode::ControlLeft, KeyCode::ControlRight))
+            && (!self.shift || held(KeyCode::ShiftLeft, KeyCode::ShiftRight))
+            && (!self.alt || held(KeyCode::AltLeft, KeyCode::AltRight))
+    }
+
+    /// Human-readable label for UI display, with US key names.
     pub fn label(&self) -> String {
+        self.label_with(None)
+    }
+
+    /// Human-readable label naming keys by the characters they type on `layout`, when known.
+    pub fn label_with(&self, layout: Option<&KeyLayout>) -> String {
         let mut parts = Vec::new();
         if self.ctrl {
             parts.push("Ctrl");
@@ -77,64 +217,147 @@ impl KeyBind {
         if self.alt {
             parts.push("Alt");
         }
-        parts.push(key_label(self.key));
-        parts.join("+")
+        let trigger = trigger_label(self.trigger, layout);
+        parts.push(&trigger);
+        let label = parts.join("+");
+        match &self.then {
+            Some(second) => format!("{label}, {}", second.label_with(layout)),
+            None => label,
+        }
     }
 }
 
 /// A keybinding slot that supports multiple alternative bindings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct KeyBindSlot {
     pub bindings: Vec<KeyBind>,
+    /// Where the bindings are active.
+    #[serde(default)]
+    pub scope: BindScope,
 }
 
 impl KeyBindSlot {
     pub fn single(bind: KeyBind) -> Self {
+        Self::from(vec![bind])
+    }
+
+    pub fn from(binds: Vec<KeyBind>) -> Self {
         Self {
-            bindings: vec![bind],
+            bindings: binds,
+            scope: BindScope::Global,
         }
     }
 
-    pub fn from(binds: Vec<KeyBind>) -> Self {
-        Self { bindings: binds }
+    /// Limit the slot to `scope`.
+    pub fn with_scope(mut self, scope: BindScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Check if any binding in this slot was just pressed while its scope is active.
+    pub fn just_pressed<'a>(&self, input: impl Into<KeyInput<'a>>) -> bool {
+        let input = input.into();
+        self.scope.is_active(input.focus)
+            && self
+                .bindings
+                .iter()
+                .any(|b| b.just_pressed(input) && !input.shadowed(b, &self.scope))
     }
 
-    /// Check if any binding in this slot was just pressed.
-    pub fn just_pressed(&self, input: &ButtonInput<KeyCode>) -> bool {
-        self.bindings.iter().any(|b| b.just_pressed(input))
+    /// Check if any binding in this slot is held while its scope is active.
+    pub fn pressed<'a>(&self, input: impl Into<KeyInput<'a>>) -> bool {
+        let input = input.into();
+        self.scope.is_active(input.focus) && self.bindings.iter().any(|b| b.pressed(input))
+    }
+
+    /// Whether a chord in this slot starts with `step`.
+    pub(crate) fn starts_chord(&self, step: &KeyBind) -> bool {
+        self.bindings
+            .iter()
+            .any(|b| b.then.is_some() && b.same_step(step))
+    }
+
+    /// Short label for menu hints: the first binding, if any.
+    pub fn hint(&self, layout: Option<&KeyLayout>) -> Option<String> {
+        self.bindings.first().map(|b| b.label_with(layout))
     }
 
     /// Human-readable label showing all alternatives.
     pub fn label(&self) -> String {
+        self.label_with(None)
+    }
+
+    /// Label showing all alternatives, with keys named for `layout`; see [`KeyBind::label_with`].
+    pub fn label_with(&self, layout: Option<&KeyLayout>) -> String {
         self.bindings
             .iter()
-            .map(|b| b.label())
+            .map(|b| b.label_with(layout))
             .collect::<Vec<_>>()
             .join(" / ")
     }
 }
 
-/// All configurable keybindings for the editor.
-#[derive(Resource, Debug, Clone)]
+/// All configurable keybindings for the editor, persisted in the settings file.
+#[derive(Resource, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct KeyBindings {
-    /// Undo (default: Ctrl+Z)
+    /// Undo (default: Ctrl+Z, not while the game view has focus)
     pub undo: KeyBindSlot,
-    /// Redo (default: Ctrl+Shift+Z)
+    /// Redo (default: Ctrl+Shift+Z, not while the game view has focus)
     pub redo: KeyBindSlot,
+    /// Undo the last layout change when layout undo is separate (default: Ctrl+Alt+Z)
+    pub layout_undo: KeyBindSlot,
+    /// Redo a layout change when layout undo is separate (default: Ctrl+Shift+Alt+Z)
+    pub layout_redo: KeyBindSlot,
     /// Play/Stop toggle (default: F5, Ctrl+P)
     pub play_stop: KeyBindSlot,
     /// Pause/Resume (default: F6, Ctrl+Shift+P)
     pub pause_resume: KeyBindSlot,
+    /// Focus the next panel in the dock (default: Ctrl+Tab)
+    pub next_panel: KeyBindSlot,
+    /// Focus the previous panel in the dock (default: Ctrl+Shift+Tab)
+    pub prev_panel: KeyBindSlot,
+    /// Maximize/restore the focused panel (default: Ctrl+Shift+M)
+    pub maximize_panel: KeyBindSlot,
+    /// Reopen the most recently closed panel (default: Ctrl+Shift+T)
+    pub reopen_panel: KeyBindSlot,
+    /// Toggle presentation mode (default: F11)
+    pub presentation_mode: KeyBindSlot,
+    /// Show or hide the keyboard shortcuts cheat sheet (default: Shift+/, i.e. "?")
+    pub shortcuts_help: KeyBindSlot,
+    /// Focus the Nth panel in the dock (default: Ctrl+1 … Ctrl+9)
+    pub focus_panel: Vec<KeyBindSlot>,
+    /// Hold to temporarily invert snapping (default: Ctrl)
+    pub snap_toggle: KeyBindSlot,
+    /// Move the hierarchy cursor up (default: ↑)
+    pub hierarchy_up: KeyBindSlot,
+    /// Move the hierarchy cursor down (default: ↓)
+    pub hierarchy_down: KeyBindSlot,
+    /// Expand the row under the hierarchy cursor, or step into it (default: →)
+    pub hierarchy_expand: KeyBindSlot,
+    /// Collapse the row under the hierarchy cursor, or step to its parent (default: ←)
+    pub hierarchy_collapse: KeyBindSlot,
+    /// Select the entity under the hierarchy cursor (default: Enter)
+    pub hierarchy_select: KeyBindSlot,
+    /// Match key bindings by the character they type on the current keyboard layout instead of
+    /// the physical key position (see [`KeyLayout`]).
+    pub match_logical: bool,
 }
 
 impl Default for KeyBindings {
     fn default() -> Self {
         Self {
-            undo: KeyBindSlot::from(vec![KeyBind::ctrl(KeyCode::KeyZ)]),
+            undo: KeyBindSlot::from(vec![KeyBind::ctrl(KeyCode::KeyZ)])
+                .with_scope(BindScope::Editor),
             redo: KeyBindSlot::from(vec![
                 KeyBind::ctrl_shift(KeyCode::KeyZ),
                 KeyBind::ctrl(KeyCode::KeyY),
-            ]),
+            ])
+            .with_scope(BindScope::Editor),
+            layout_undo: KeyBindSlot::single(KeyBind::ctrl_alt(KeyCode::KeyZ))
+                .with_scope(BindScope::Editor),
+            layout_redo: KeyBindSlot::single(KeyBind::ctrl_shift_alt(KeyCode::KeyZ))
+                .with_scope(BindScope::Editor),
             play_stop: KeyBindSlot::from(vec![
                 KeyBind::key(KeyCode::F5),
                 KeyBind::ctrl(KeyCode::KeyP),
@@ -143,10 +366,102 @@ impl Default for KeyBindings {
                 KeyBind::key(KeyCode::F6),
                 KeyBind::ctrl_shift(KeyCode::KeyP),
             ]),
+            next_panel: KeyBindSlot::single(KeyBind::ctrl(KeyCode::Tab)),
+            prev_panel: KeyBindSlot::single(KeyBind::ctrl_shift(KeyCode::Tab)),
+            maximize_panel: KeyBindSlot::single(KeyBind::ctrl_shift(KeyCode::KeyM)),
+            reopen_panel: KeyBindSlot::single(KeyBind::ctrl_shift(KeyCode::KeyT)),
+            presentation_mode: KeyBindSlot::single(KeyBind::key(KeyCode::F11)),
+            shortcuts_help: KeyBindSlot::single(KeyBind::shift(KeyCode::Slash))
+                .with_scope(BindScope::Editor),
+            focus_panel: [
+                KeyCode::Digit1,
+                KeyCode::Digit2,
+                KeyCode::Digit3,
+                KeyCode::Digit4,
+                KeyCode::Digit5,
+                KeyCode::Digit6,
+                KeyCode::Digit7,
+                KeyCode::Digit8,
+                KeyCode::Digit9,
+            ]
+            .into_iter()
+            .map(|key| KeyBindSlot::single(KeyBind::ctrl(key)))
+            .collect(),
+            snap_toggle: KeyBindSlot::from(vec![
+                KeyBind::key(KeyCode::ControlLeft),
+                KeyBind::key(KeyCode::ControlRight),
+            ])
+            .with_scope(BindScope::Editor),
+            hierarchy_up: hierarchy_slot(KeyCode::ArrowUp),
+            hierarchy_down: hierarchy_slot(KeyCode::ArrowDown),
+            hierarchy_expand: hierarchy_slot(KeyCode::ArrowRight),
+            hierarchy_collapse: hierarchy_slot(KeyCode::ArrowLeft),
+            hierarchy_select: hierarchy_slot(KeyCode::Enter),
+            match_logical: false,
         }
     }
 }
 
+/// A hierarchy navigation slot, active while the inspector has focus.
+fn hierarchy_slot(key: KeyCode) -> KeyBindSlot {
+    KeyBindSlot::single(KeyBind::key(key))
+        .with_scope(BindScope::PanelFocused("workbench_inspector".to_string()))
+}
+
+impl KeyBindings {
+    /// Every binding slot, in declaration order.
+    pub fn slots(&self) -> impl Iterator<Item = &KeyBindSlot> {
+        [
+            &self.undo,
+            &self.redo,
+            &self.layout_undo,
+            &self.layout_redo,
+            &self.play_stop,
+            &self.pause_resume,
+            &self.next_panel,
+            &self.prev_panel,
+            &self.maximize_panel,
+            &self.reopen_panel,
+            &self.presentation_mode,
+            &self.shortcuts_help,
+            &self.snap_toggle,
+            &self.hierarchy_up,
+            &self.hierarchy_down,
+            &self.hierarchy_expand,
+            &self.hierarchy_collapse,
+            &self.hierarchy_select,
+        ]
+        .into_iter()
+        .chain(&self.focus_panel)
+    }
+
+    /// The layout to name keys by in labels: none when bindings match logical keys, since
+    /// those already name the character they type.
+    pub fn display_layout<'a>(&self, layout: Option<&'a KeyLayout>) -> Option<&'a KeyLayout> {
+        layout.filter(|_| !self.match_logical)
+    }
+}
+
+fn trigger_label(trigger: BindTrigger, layout: Option<&KeyLayout>) -> String {
+    match trigger {
+        BindTrigger::Key(key) => match (layout.and_then(|l| l.char_of(key)), key_label(key)) {
+            (Some(c), _) => c.to_string(),
+            (None, "?") => format!("{key:?}"),
+            (None, label) => label.to_string(),
+        },
+        BindTrigger::Mouse(MouseButton::Left) => "Mouse Left".to_string(),
+        BindTrigger::Mouse(MouseButton::Right) => "Mouse Right".to_string(),
+        BindTrigger::Mouse(MouseButton::Middle) => "Mouse Middle".to_string(),
+        BindTrigger::Mouse(MouseButton::Back) => "Mouse Back".to_string(),
+        BindTrigger::Mouse(MouseButton::Forward) => "Mouse Forward".to_string(),
+        BindTrigger::Mouse(MouseButton::Other(n)) => format!("Mouse {n}"),
+        BindTrigger::Wheel(WheelDirection::Up) => "Wheel Up".to_string(),
+        BindTrigger::Wheel(WheelDirection::Down) => "Wheel Down".to_string(),
+        BindTrigger::Wheel(WheelDirection::Left) => "Wheel Left".to_string(),
+        BindTrigger::Wheel(WheelDirection::Right) => "Wheel Right".to_string(),
+    }
+}
+
 fn key_label(key: KeyCode) -> &'static str {
     match key {
         KeyCode::KeyA => "A",
@@ -185,6 +500,17 @@ fn key_label(key: KeyCode) -> &'static str {
         KeyCode::Digit7 => "7",
         KeyCode::Digit8 => "8",
         KeyCode::Digit9 => "9",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backslash => "\\",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Slash => "/",
+        KeyCode::Backquote => "`",
         KeyCode::F1 => "F1",
         KeyCode::F2 => "F2",
         KeyCode::F3 => "F3",
@@ -209,6 +535,9 @@ fn key_label(key: KeyCode) -> &'static str {
         KeyCode::ArrowDown => "↓",
         KeyCode::ArrowLeft => "←",
         KeyCode::ArrowRight => "→",
+        KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl",
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift",
+        KeyCode::AltLeft | KeyCode::AltRight => "Alt",
         _ => "?",
     }
 }
@@ -0,0 +1,57 @@
+//! # capture.rs
+//!
+//! # capture.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Tracks whether egui is using the keyboard or pointer, so editor bindings stay quiet while the
+//! user types in a text field (the console filter, a rename box) or drags a widget. The
+//! `EguiInputCapture` resource is refreshed at the end of every frame from all egui contexts, and
+//! binding matching skips key bindings while the keyboard is captured and mouse bindings while
+//! the pointer is.
+//!
+//! 跟踪 egui 是否正在使用键盘或指针，使用户在文本框（控制台过滤框、重命名框）中输入或拖动控件时
+//! 编辑器快捷键保持静默。`EguiInputCapture` 资源在每帧结束时根据所有 egui 上下文刷新；键盘被
+//! 占用时按键绑定不会匹配，指针被占用时鼠标绑定不会匹配。
+
+use bevy::prelude::*;
+use bevy_egui::EguiContext;
+
+use super::BindTrigger;
+
+/// Whether egui wants keyboard or pointer input (as of the last frame).
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct EguiInputCapture {
+    /// A text field or other widget has keyboard focus.
+    pub keyboard: bool,
+    /// A widget is being dragged or clicked.
+    pub pointer: bool,
+}
+
+impl EguiInputCapture {
+    /// Whether egui is using the input `trigger` comes from.
+    pub fn captures(&self, trigger: BindTrigger) -> bool {
+        match trigger {
+            BindTrigger::Key(_) => self.keyboard,
+            BindTrigger::Mouse(_) | BindTrigger::Wheel(_) => self.pointer,
+        }
+    }
+}
+
+/// Refreshes [`EguiInputCapture`] from every egui context.
+pub fn egui_input_capture_system(
+    mut contexts: Query<&mut EguiContext>,
+    mut capture: ResMut<EguiInputCapture>,
+) {
+    let mut next = EguiInputCapture::default();
+    for mut context in &mut contexts {
+        let ctx = context.get_mut();
+        next.keyboard |= ctx.wants_keyboard_input();
+        next.pointer |= ctx.is_using_pointer();
+    }
+    if capture.keyboard != next.keyboard || capture.pointer != next.pointer {
+        *capture = next;
+    }
+}
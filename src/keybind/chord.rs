@@ -0,0 +1,351 @@
+//! # chord.rs
+//!
+//! # chord.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Two-step chords (e.g. Ctrl+K, Ctrl+S) and the input bundle bindings are matched against.
+//! `chord_state_system` watches every pressed step: a step that starts one of the chords in
+//! `KeyBindings` becomes pending for `CHORD_TIMEOUT` seconds, and the next step resolves it.
+//! On the resolving frame only chords can fire, so a chord's second step does not also trigger a
+//! plain shortcut. Modifier presses leave a pending chord alone unless the chord started with a
+//! modifier itself, which is how double-tapped modifiers (Shift, Shift) are supported.
+//!
+//! 两步组合键（例如 Ctrl+K, Ctrl+S）以及用于匹配绑定的输入集合。`chord_state_system` 监视每次
+//! 按下的步骤：能开启 `KeyBindings` 中某个组合键的步骤会进入等待状态，持续 `CHORD_TIMEOUT`
+//! 秒，下一个步骤即完成该组合键。在完成组合键的那一帧只有组合键能触发，因此组合键的第二步不会
+//! 同时触发普通快捷键。按下修饰键不会打断等待中的组合键，除非该组合键本身以修饰键开始，
+//! 双击修饰键（Shift, Shift）正是借此实现的。
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use super::{
+    BindTrigger, EguiInputCapture, KeyBind, KeyBindings, KeyLayout, WheelDirection, WheelNotch,
+};
+use crate::dock::FocusedPanel;
+
+/// Seconds a chord's first step waits for the second one.
+pub const CHORD_TIMEOUT: f64 = 1.0;
+
+/// State of the chord currently being typed, maintained by [`chord_state_system`].
+#[derive(Resource, Debug, Default)]
+pub struct ChordState {
+    /// First step of a chord, and when it was pressed.
+    pending: Option<(KeyBind, f64)>,
+    /// First step of the chord completed this frame.
+    resolving: Option<KeyBind>,
+}
+
+impl ChordState {
+    /// The first step of a chord waiting for its second step (for "Ctrl+K…" hints).
+    pub fn pending(&self) -> Option<&KeyBind> {
+        self.pending.as_ref().map(|(step, _)| step)
+    }
+
+    /// The first step of the chord whose second step was pressed this frame.
+    pub fn resolving(&self) -> Option<&KeyBind> {
+        self.resolving.as_ref()
+    }
+}
+
+/// Everything bindings are matched against. Built from a bare `&ButtonInput<KeyCode>` it
+/// matches keyboard bindings only, chords never fire and only `Global` / `Editor` scopes are
+/// active.
+#[derive(Clone, Copy)]
+pub struct KeyInput<'a> {
+    pub keys: &'a ButtonInput<KeyCode>,
+    pub mouse: Option<&'a ButtonInput<MouseButton>>,
+    /// Wheel notch completed this frame, for wheel bindings.
+    pub wheel: Option<&'a WheelNotch>,
+    pub chords: Option<&'a ChordState>,
+    /// The focused panel, for binding scopes.
+    pub focus: Option<&'a FocusedPanel>,
+    /// All bindings, to let focused scopes take precedence.
+    pub bindings: Option<&'a KeyBindings>,
+    /// Input egui is using, which bindings ignore.
+    pub capture: Option<&'a EguiInputCapture>,
+    /// Characters of the keyboard layout, for `KeyBindings::match_logical`.
+    pub layout: Option<&'a KeyLayout>,
+}
+
+impl<'a> KeyInput<'a> {
+    /// Input from the world's resources, if there is a keyboard.
+    pub fn from_world(world: &'a World) -> Option<Self> {
+        Some(Self {
+            keys: world.get_resource::<ButtonInput<KeyCode>>()?,
+            mouse: world.get_resource::<ButtonInput<MouseButton>>(),
+            wheel: world.get_resource::<WheelNotch>(),
+            chords: world.get_resource::<ChordState>(),
+            focus: world.get_resource::<FocusedPanel>(),
+            bindings: world.get_resource::<KeyBindings>(),
+            capture: world.get_resource::<EguiInputCapture>(),
+            layout: world.get_resource::<KeyLayout>(),
+        })
+    }
+
+    /// Whether egui is using the input `trigger` comes from.
+    fn captured(&self, trigger: BindTrigger) -> bool {
+        self.capture.is_some_and(|c| c.captures(trigger))
+    }
+
+    pub(super) fn trigger_just_pressed(&self, trigger: BindTrigger) -> bool {
+        if self.captured(trigger) {
+            return false;
+        }
+        match trigger {
+            BindTrigger::Key(key) => match self.logical_layout() {
+                Some(layout) => self
+                    .keys
+                    .get_just_pressed()
+                    .any(|&pressed| layout.logical(pressed) == key),
+                None => self.keys.just_pressed(key),
+            },
+            BindTrigger::Mouse(button) => self.mouse.is_some_and(|m| m.just_pressed(button)),
+            BindTrigger::Wheel(direction) => self.scrolled(direction),
+        }
+    }
+
+    pub(super) fn trigger_pressed(&self, trigger: BindTrigger) -> bool {
+        if self.captured(trigger) {
+            return false;
+        }
+        match trigger {
+            BindTrigger::Key(key) => match self.logical_layout() {
+                Some(layout) => self
+                    .keys
+                    .get_pressed()
+                    .any(|&pressed| layout.logical(pressed) == key),
+                None => self.keys.pressed(key),
+            },
+            BindTrigger::Mouse(button) => self.mouse.is_some_and(|m| m.pressed(button)),
+            BindTrigger::Wheel(direction) => self.scrolled(direction),
+        }
+    }
+
+    /// The layout to match keys through, if bindings match logical keys.
+    pub(crate) fn logical_layout(&self) -> Option<&'a KeyLayout> {
+        self.layout
+            .filter(|_| self.bindings.is_some_and(|b| b.match_logical))
+    }
+
+    /// Whether a wheel notch in `direction` completed this frame.
+    fn scrolled(&self, direction: WheelDirection) -> bool {
+        self.wheel
+            .is_some_and(|wheel| wheel.notch() == Some(direction))
+    }
+
+    pub(super) fn resolving(&self) -> Option<&'a KeyBind> {
+        self.chords.and_then(ChordState::resolving)
+    }
+}
+
+impl<'a> From<&'a ButtonInput<KeyCode>> for KeyInput<'a> {
+    fn from(keys: &'a ButtonInput<KeyCode>) -> Self {
+        Self {
+            keys,
+            mouse: None,
+            wheel: None,
+            chords: None,
+            focus: None,
+            bindings: None,
+            capture: None,
+            layout: None,
+        }
+    }
+}
+
+/// System parameter for matching bindings with keyboard, mouse and chord state:
+/// `bindings.undo.just_pressed(&input)`.
+#[derive(SystemParam)]
+pub struct BindingInput<'w> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Option<Res<'w, ButtonInput<MouseButton>>>,
+    wheel: Option<Res<'w, WheelNotch>>,
+    chords: Option<Res<'w, ChordState>>,
+    focus: Option<Res<'w, FocusedPanel>>,
+    bindings: Option<Res<'w, KeyBindings>>,
+    capture: Option<Res<'w, EguiInputCapture>>,
+    layout: Option<Res<'w, KeyLayout>>,
+}
+
+impl<'a> From<&'a BindingInput<'_>> for KeyInput<'a> {
+    fn from(input: &'a BindingInput<'_>) -> Self {
+        Self {
+            keys: &input.keys,
+            mouse: input.mouse.as_deref(),
+            wheel: input.wheel.as_deref(),
+            chords: input.chords.as_deref(),
+            focus: input.focus.as_deref(),
+            bindings: input.bindings.as_deref(),
+            capture: input.capture.as_deref(),
+            layout: input.layout.as_deref(),
+        }
+    }
+}
+
+/// Tracks pending chords from the steps pressed this frame.
+pub fn chord_state_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Option<Res<ButtonInput<MouseButton>>>,
+    wheel: Option<Res<WheelNotch>>,
+    bindings: Option<Res<KeyBindings>>,
+    capture: Option<Res<EguiInputCapture>>,
+    layout: Option<Res<KeyLayout>>,
+    time: Res<Time<Real>>,
+    mut state: ResMut<ChordState>,
+) {
+    let now = time.elapsed_secs_f64();
+    state.resolving = None;
+    if state
+        .pending
+        .as_ref()
+        .is_some_and(|(_, since)| now - since > CHORD_TIMEOUT)
+    {
+        state.pending = None;
+    }
+    let pressed = keys
+        .get_just_pressed()
+        .map(|&key| match (&layout, bindings.as_deref()) {
+            (Some(layout), Some(b)) if b.match_logical => BindTrigger::Key(layout.logical(key)),
+            _ => BindTrigger::Key(key),
+        })
+        .chain(
+            mouse
+                .iter()
+                .flat_map(|m| m.get_just_pressed().map(|&b| BindTrigger::Mouse(b))),
+        )
+        .chain(
+            wheel
+                .and_then(|wheel| wheel.notch())
+                .map(BindTrigger::Wheel),
+        );
+    let captured = |trigger| capture.as_deref().is_some_and(|c| c.captures(trigger));
+    for trigger in pressed.filter(|&trigger| !captured(trigger)) {
+        let step = KeyBind::step(trigger, &keys);
+        if let Some((first, _)) = &state.pending {
+            // Modifiers pressed on the way to the second step don't count as one.
+            if step.is_modifier() && !first.is_modifier() {
+                continue;
+            }
+            state.resolving = state.pending.take().map(|(first, _)| first);
+        } else if bindings
+            .as_deref()
+            .is_some_and(|b| b.slots().any(|slot| slot.starts_chord(&step)))
+        {
+            state.pending = Some((step, now));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybind::KeyBindSlot;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    fn chord() -> KeyBind {
+        KeyBind::ctrl(KeyCode::KeyK).then(KeyBind::ctrl(KeyCode::KeyS))
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        let mut bindings = KeyBindings::default();
+        bindings.undo = KeyBindSlot::single(chord());
+        world.insert_resource(bindings);
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Time<Real>>();
+        world.init_resource::<ChordState>();
+        world
+    }
+
+    /// Start a frame: `press` goes down, `release` comes up, held keys stay held.
+    fn frame(world: &mut World, press: &[KeyCode], release: &[KeyCode]) {
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.clear();
+        for &key in release {
+            keys.release(key);
+        }
+        for &key in press {
+            keys.press(key);
+        }
+        world.run_system_once(chord_state_system).unwrap();
+    }
+
+    fn just_pressed(world: &World, bind: &KeyBind) -> bool {
+        bind.just_pressed(KeyInput::from_world(world).unwrap())
+    }
+
+    #[test]
+    fn chord_resolves_on_second_step() {
+        let mut world = setup();
+        frame(&mut world, &[KeyCode::ControlLeft], &[]);
+        assert!(world.resource::<ChordState>().pending().is_none());
+        frame(&mut world, &[KeyCode::KeyK], &[]);
+        assert_eq!(
+            world.resource::<ChordState>().pending(),
+            Some(&KeyBind::ctrl(KeyCode::KeyK))
+        );
+        assert!(!just_pressed(&world, &chord()));
+
+        frame(&mut world, &[KeyCode::KeyS], &[KeyCode::KeyK]);
+        let state = world.resource::<ChordState>();
+        assert!(state.pending().is_none());
+        assert_eq!(state.resolving(), Some(&KeyBind::ctrl(KeyCode::KeyK)));
+        assert!(just_pressed(&world, &chord()));
+        // The second step doesn't also fire a plain Ctrl+S.
+        assert!(!just_pressed(&world, &KeyBind::ctrl(KeyCode::KeyS)));
+
+        frame(&mut world, &[], &[KeyCode::KeyS]);
+        assert!(world.resource::<ChordState>().resolving().is_none());
+    }
+
+    #[test]
+    fn modifiers_do_not_break_a_pending_chord() {
+        let mut world = setup();
+        frame(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyK], &[]);
+        frame(&mut world, &[], &[KeyCode::ControlLeft, KeyCode::KeyK]);
+        frame(&mut world, &[KeyCode::ControlLeft], &[]);
+        assert!(world.resource::<ChordState>().pending().is_some());
+        frame(&mut world, &[KeyCode::KeyS], &[]);
+        assert!(just_pressed(&world, &chord()));
+    }
+
+    #[test]
+    fn wrong_second_step_cancels() {
+        let mut world = setup();
+        frame(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyK], &[]);
+        frame(&mut world, &[KeyCode::KeyX], &[KeyCode::KeyK]);
+        assert!(!just_pressed(&world, &chord()));
+        assert!(world.resource::<ChordState>().pending().is_none());
+        // Ctrl+S alone is then a plain press again.
+        frame(&mut world, &[KeyCode::KeyS], &[KeyCode::KeyX]);
+        assert!(!just_pressed(&world, &chord()));
+        assert!(just_pressed(&world, &KeyBind::ctrl(KeyCode::KeyS)));
+    }
+
+    #[test]
+    fn pending_chord_times_out() {
+        let mut world = setup();
+        frame(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyK], &[]);
+        world
+            .resource_mut::<Time<Real>>()
+            .advance_by(Duration::from_secs_f64(CHORD_TIMEOUT + 0.1));
+        frame(&mut world, &[KeyCode::KeyS], &[KeyCode::KeyK]);
+        assert!(!just_pressed(&world, &chord()));
+    }
+
+    #[test]
+    fn double_tapped_modifier() {
+        let mut world = setup();
+        let tap = KeyBind::key(KeyCode::ShiftLeft).then(KeyBind::key(KeyCode::ShiftLeft));
+        world.resource_mut::<KeyBindings>().redo = KeyBindSlot::single(tap.clone());
+        frame(&mut world, &[KeyCode::ShiftLeft], &[]);
+        frame(&mut world, &[], &[KeyCode::ShiftLeft]);
+        frame(&mut world, &[KeyCode::ShiftLeft], &[]);
+        assert!(just_pressed(&world, &tap));
+    }
+}
@@ -0,0 +1,125 @@
+//! # layout.rs
+//!
+//! # layout.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Keyboard layout awareness. `KeyCode`s name physical key positions after the US layout, so a
+//! binding to `KeyZ` is the key labeled "W" on a French keyboard, and keys without a US name
+//! have no readable label at all. `key_layout_system` learns the character each physical key
+//! types from keyboard events, which gives layout-correct labels (e.g. "É", "ß"). With
+//! `KeyBindings::match_logical`, bindings match the key that types their character instead of
+//! the physical position.
+//!
+//! 键盘布局感知。`KeyCode` 以美式布局命名物理按键位置，因此绑定到 `KeyZ` 的快捷键在法语键盘上
+//! 是标有 "W" 的按键，而没有美式名称的按键则根本没有可读标签。`key_layout_system` 从键盘事件中
+//! 学习每个物理按键输入的字符，从而得到符合布局的标签（例如 "É"、"ß"）。启用
+//! `KeyBindings::match_logical` 后，绑定会匹配输入其字符的按键，而不是物理位置。
+
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use super::key_label;
+
+/// Keys with a character on the US layout, for mapping characters back to key codes.
+const CHARACTER_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Minus,
+    KeyCode::Equal,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Backslash,
+    KeyCode::Semicolon,
+    KeyCode::Quote,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+    KeyCode::Backquote,
+];
+
+/// The characters typed by physical keys on the current keyboard layout, learned as keys are
+/// pressed.
+#[derive(Resource, Debug, Default)]
+pub struct KeyLayout {
+    chars: HashMap<KeyCode, String>,
+}
+
+impl KeyLayout {
+    /// The character `key` types (uppercased), once it has been pressed.
+    pub fn char_of(&self, key: KeyCode) -> Option<&str> {
+        self.chars.get(&key).map(String::as_str)
+    }
+
+    /// The key code whose US character `key` types on this layout, e.g. `KeyZ` for the key
+    /// labeled "W" on a French keyboard. Unknown keys map to themselves.
+    pub fn logical(&self, key: KeyCode) -> KeyCode {
+        self.char_of(key)
+            .and_then(|c| CHARACTER_KEYS.iter().find(|&&us| key_label(us) == c))
+            .copied()
+            .unwrap_or(key)
+    }
+}
+
+/// Learns the character of every key pressed without Shift or Alt.
+pub fn key_layout_system(
+    mut events: MessageReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut layout: ResMut<KeyLayout>,
+) {
+    let modified = keys.any_pressed([
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+    ]);
+    for event in events.read() {
+        if event.state != ButtonState::Pressed || modified {
+            continue;
+        }
+        if let Key::Character(c) = &event.logical_key {
+            let c = c.to_uppercase();
+            if layout.char_of(event.key_code) != Some(c.as_str()) {
+                layout.chars.insert(event.key_code, c);
+            }
+        }
+    }
+}
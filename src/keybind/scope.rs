@@ -0,0 +1,77 @@
+//! # scope.rs
+//!
+//! # scope.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Focus scopes for binding slots. A slot only fires while its scope is active, judged from the
+//! `FocusedPanel` resource: `Global` always, `Editor` unless the game view has focus (so typing
+//! in the game doesn't trigger editor shortcuts), and `GameViewFocused` / `PanelFocused` only
+//! while that panel has focus. When a focused-scope binding and a broader one share a key, the
+//! focused one wins, so the same key can mean different things in different panels.
+//!
+//! 按键槽位的焦点作用域。槽位只在其作用域激活时触发，依据 `FocusedPanel` 资源判断：`Global`
+//! 始终激活；`Editor` 在游戏视图获得焦点时不激活（因此在游戏中打字不会触发编辑器快捷键）；
+//! `GameViewFocused` / `PanelFocused` 仅在对应面板获得焦点时激活。当焦点作用域的绑定与范围更广
+//! 的绑定使用同一按键时，焦点作用域优先，因此同一按键可在不同面板中表示不同含义。
+
+use super::{KeyBind, KeyInput};
+use crate::dock::FocusedPanel;
+
+/// String ID of the game view panel.
+const GAME_VIEW_PANEL: &str = "workbench_game_view";
+
+/// Where a binding slot is active.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum BindScope {
+    /// Everywhere, including while the game view has focus.
+    #[default]
+    Global,
+    /// Everywhere except while the game view has focus.
+    Editor,
+    /// Only while the game view has focus.
+    GameViewFocused,
+    /// Only while the panel with this string ID has focus.
+    PanelFocused(String),
+}
+
+impl BindScope {
+    /// Whether the scope is active for `focus` (`None` = focus unknown).
+    pub fn is_active(&self, focus: Option<&FocusedPanel>) -> bool {
+        let game_view = focus.is_some_and(|f| f.is(GAME_VIEW_PANEL));
+        match self {
+            BindScope::Global => true,
+            BindScope::Editor => !game_view,
+            BindScope::GameViewFocused => game_view,
+            BindScope::PanelFocused(id) => focus.is_some_and(|f| f.is(id)),
+        }
+    }
+
+    /// Whether the scope is tied to a focused panel.
+    pub fn is_focused(&self) -> bool {
+        matches!(
+            self,
+            BindScope::GameViewFocused | BindScope::PanelFocused(_)
+        )
+    }
+}
+
+impl KeyInput<'_> {
+    /// Whether an active focused-scope binding with the same steps as `bind` takes precedence
+    /// over it in `scope`.
+    pub(super) fn shadowed(&self, bind: &KeyBind, scope: &BindScope) -> bool {
+        if scope.is_focused() {
+            return false;
+        }
+        let Some(bindings) = self.bindings else {
+            return false;
+        };
+        bindings
+            .slots()
+            .filter(|slot| slot.scope.is_focused() && slot.scope.is_active(self.focus))
+            .flat_map(|slot| &slot.bindings)
+            .any(|other| other == bind)
+    }
+}
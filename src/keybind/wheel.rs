@@ -0,0 +1,111 @@
+//! # wheel.rs
+//!
+//! # wheel.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Turns scrolling into discrete wheel notches for `BindTrigger::Wheel` bindings. A wheel or
+//! touchpad reports a scroll delta on every frame of a gesture; `wheel_notch_system` accumulates
+//! it and completes one notch per scrolled line (40 pixels of smooth scrolling), so a wheel
+//! binding fires once per notch instead of every frame the wheel moves. Reversing direction
+//! starts over. While the Keybindings panel is recording and the pointer is over its list, the
+//! wheel scrolls the list and produces no notches.
+//!
+//! 把滚动转换为离散的滚轮"格"，供 `BindTrigger::Wheel` 绑定使用。滚轮或触控板在一次手势的每一帧
+//! 都会报告滚动增量；`wheel_notch_system` 会累积这些增量，每滚动一行（平滑滚动为 40 像素）完成
+//! 一格，因此滚轮绑定每格只触发一次，而不是在滚轮转动的每一帧都触发。反向滚动会重新累积。
+//! 当快捷键面板正在录制且指针位于其列表上方时，滚轮用于滚动列表，不产生任何格。
+
+use bevy::input::mouse::{AccumulatedMouseScroll, MouseScrollUnit};
+use bevy::prelude::*;
+
+use super::WheelDirection;
+
+/// Pixels of smooth (touchpad) scrolling that make one notch.
+const PIXELS_PER_NOTCH: f32 = 40.0;
+
+/// Wheel notches completed by scrolling, maintained by [`wheel_notch_system`].
+#[derive(Resource, Debug, Default)]
+pub struct WheelNotch {
+    /// Scrolling not yet turned into a notch, in lines.
+    accumulated: Vec2,
+    /// Direction of the notch completed this frame.
+    notch: Option<WheelDirection>,
+    /// Set for the next frame while the wheel scrolls the recording Keybindings panel.
+    pub(crate) ignore_next: bool,
+}
+
+impl WheelNotch {
+    /// Direction of the notch completed this frame.
+    pub fn notch(&self) -> Option<WheelDirection> {
+        self.notch
+    }
+}
+
+/// Accumulates this frame's scrolling into [`WheelNotch`].
+pub fn wheel_notch_system(
+    scroll: Option<Res<AccumulatedMouseScroll>>,
+    mut wheel: ResMut<WheelNotch>,
+) {
+    wheel.notch = None;
+    if std::mem::take(&mut wheel.ignore_next) {
+        wheel.accumulated = Vec2::ZERO;
+        return;
+    }
+    let Some(scroll) = scroll else { return };
+    let lines = match scroll.unit {
+        MouseScrollUnit::Line => scroll.delta,
+        MouseScrollUnit::Pixel => scroll.delta / PIXELS_PER_NOTCH,
+    };
+    // A reversal on an axis starts that axis over.
+    let mut accumulated = wheel.accumulated;
+    if accumulated.x * lines.x < 0.0 {
+        accumulated.x = 0.0;
+    }
+    if accumulated.y * lines.y < 0.0 {
+        accumulated.y = 0.0;
+    }
+    accumulated += lines;
+    let whole = accumulated.trunc();
+    wheel.notch = WheelDirection::of(whole);
+    wheel.accumulated = accumulated - whole;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn scroll(world: &mut World, unit: MouseScrollUnit, y: f32) -> Option<WheelDirection> {
+        world.insert_resource(AccumulatedMouseScroll {
+            unit,
+            delta: Vec2::new(0.0, y),
+        });
+        world.run_system_once(wheel_notch_system).unwrap();
+        world.resource::<WheelNotch>().notch()
+    }
+
+    #[test]
+    fn fires_once_per_notch() {
+        let mut world = World::new();
+        world.init_resource::<WheelNotch>();
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Line, 1.0),
+            Some(WheelDirection::Up)
+        );
+        assert_eq!(scroll(&mut world, MouseScrollUnit::Line, 0.0), None);
+        // Smooth scrolling completes a notch only after a line's worth of pixels.
+        assert_eq!(scroll(&mut world, MouseScrollUnit::Pixel, -15.0), None);
+        assert_eq!(scroll(&mut world, MouseScrollUnit::Pixel, -15.0), None);
+        assert_eq!(
+            scroll(&mut world, MouseScrollUnit::Pixel, -15.0),
+            Some(WheelDirection::Down)
+        );
+        // Reversing drops the partial notch.
+        assert_eq!(scroll(&mut world, MouseScrollUnit::Pixel, 30.0), None);
+        world.resource_mut::<WheelNotch>().ignore_next = true;
+        assert_eq!(scroll(&mut world, MouseScrollUnit::Line, 1.0), None);
+    }
+}
@@ -10,21 +10,52 @@
 //! - No scene-based asset loading
 //! - No scene hierarchy by default
 
+pub mod accessibility;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod autosave;
 pub mod bench_ui;
 pub mod config;
 pub mod console;
+pub mod dialogs;
 pub mod dock;
+#[cfg(feature = "game-view")]
+pub mod editor_camera;
+pub mod events;
+#[cfg(feature = "file-dialogs")]
+pub mod file_dialog;
 pub mod font;
+#[cfg(feature = "game-view")]
 pub mod game_view;
+pub mod help;
 pub mod i18n;
 pub mod inspector;
 pub mod keybind;
 pub mod layout;
 pub mod menu_bar;
 pub mod mode;
+pub mod plot;
 pub mod prelude;
+pub mod presentation;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod schedule_graph;
+pub mod scrub;
+pub mod shortcuts;
+pub mod snap;
+pub mod states;
+pub mod storage;
+pub mod tasks;
+pub mod testing;
 pub mod theme;
+pub mod timeline;
 pub mod undo;
+pub mod welcome;
+pub mod window_title;
+mod workbench_app;
+pub mod world_stats;
+
+pub use workbench_app::WorkbenchApp;
 
 use bevy::prelude::*;
 use bevy_egui::{EguiGlobalSettings, EguiPlugin, EguiPrimaryContextPass};
@@ -41,7 +72,14 @@ pub struct WorkbenchConfig {
     pub show_toolbar: bool,
     /// Whether to enable the built-in GameView render-to-texture pipeline.
     /// Set to `false` if your app has its own preview/rendering setup.
+    /// Has no effect without the `game-view` feature.
     pub enable_game_view: bool,
+    /// Open the game in a separate OS window on Play instead of rendering it into the
+    /// Game View panel. Has no effect without the `game-view` feature.
+    pub play_in_window: bool,
+    /// Seconds between autosaves of layout, panel state and undo history used for
+    /// crash recovery (`None` disables autosave).
+    pub autosave_interval: Option<f32>,
 }
 
 impl Default for WorkbenchConfig {
@@ -52,6 +90,8 @@ impl Default for WorkbenchConfig {
             show_console: true,
             show_toolbar: true,
             enable_game_view: true,
+            play_in_window: false,
+            autosave_interval: Some(30.0),
         }
     }
 }
@@ -70,6 +110,7 @@ impl Plugin for WorkbenchPlugin {
         if !app.is_plugin_added::<bevy_inspector_egui::DefaultInspectorConfigPlugin>() {
             app.add_plugins(bevy_inspector_egui::DefaultInspectorConfigPlugin);
         }
+        inspector::register_euler_inspectors(app);
 
         // Disable auto PrimaryEguiContext — we assign it explicitly to the
         // window camera so the GameViewCamera doesn't accidentally steal it.
@@ -86,17 +127,44 @@ impl Plugin for WorkbenchPlugin {
             .insert_resource(config_path)
             .insert_resource(dock::LayoutPath::default())
             .init_state::<mode::EditorMode>()
-            .insert_resource(mode::ModeController::default())
+            .insert_resource(mode::ModeController {
+                hide_panels_on_play: settings.hide_panels_on_play,
+                per_mode_layouts: settings.per_mode_layouts,
+                unfocused: settings.unfocused_behavior,
+                ..default()
+            })
             .insert_resource(mode::GameClock::default())
+            .init_resource::<Time<mode::GameTime>>()
             .init_schedule(mode::GameSchedule)
-            .insert_resource(undo::UndoStack::default())
-            .init_resource::<keybind::KeyBindings>()
+            .insert_resource(undo::UndoStack {
+                separate_layout: settings.separate_layout_undo,
+                ..default()
+            })
+            .init_resource::<undo::LayoutUndoStack>()
+            .insert_resource(settings.keybindings.clone())
+            .init_resource::<keybind::ChordState>()
+            .init_resource::<keybind::EguiInputCapture>()
+            .init_resource::<keybind::KeyLayout>()
+            .init_resource::<keybind::WheelNotch>()
+            .init_resource::<snap::SnapSettings>()
+            .init_resource::<shortcuts::ShortcutRegistry>()
+            .init_resource::<help::PanelHelp>()
             .insert_resource(layout::LayoutState::new(self.config.layout))
             .insert_resource(dock::TileLayoutState::default())
+            .init_resource::<dock::FocusedPanel>()
+            .init_resource::<dock::PanelStyles>()
+            .init_resource::<dialogs::Dialogs>()
+            .init_resource::<tasks::Tasks>()
             .init_resource::<console::ConsoleState>()
             .insert_resource(inspector::InspectorSelection::default())
             .init_resource::<inspector::InspectorUndoState>()
+            .init_resource::<inspector::LockedNames>()
+            .init_resource::<inspector::SavedSelections>()
             .init_resource::<menu_bar::KeyRecordState>()
+            .init_resource::<world_stats::EntityCountHistory>()
+            .init_resource::<autosave::AutosaveDir>()
+            .init_resource::<autosave::AutosaveState>()
+            .init_resource::<autosave::UndoKinds>()
             .insert_resource(theme::ThemeState {
                 config: settings.theme.clone(),
                 ..Default::default()
@@ -104,23 +172,123 @@ impl Plugin for WorkbenchPlugin {
             .insert_resource(i18n::I18n::new(settings.locale))
             .insert_resource(font::FontState::default())
             .add_message::<menu_bar::MenuAction>()
+            .add_message::<mode::ModeTransitionRequest>()
+            .add_message::<inspector::SelectionChanged>()
+            .add_systems(Update, mode::mode_transition_system)
             .add_systems(Update, layout::detect_layout_system)
+            .add_systems(
+                Update,
+                menu_bar::menu_action_system.before(undo::undo_input_system),
+            )
             .add_systems(Update, undo::undo_input_system)
+            .add_systems(Update, undo::layout_undo_input_system)
+            .add_systems(Update, snap::snap_toggle_system)
+            .add_systems(PreUpdate, theme::system_theme_system)
+            .add_systems(Update, shortcuts::shortcuts_help_system)
+            .add_systems(Update, tasks::tasks_poll_system)
+            .add_systems(Last, dock::dock_windows_system)
             .add_systems(PreUpdate, assign_primary_egui_context_system)
             .add_systems(PreUpdate, console::console_drain_system)
-            .add_systems(PreUpdate, inspector::mark_internal_entities_system);
+            .add_systems(
+                PreUpdate,
+                (
+                    keybind::key_layout_system,
+                    keybind::wheel_notch_system,
+                    keybind::chord_state_system,
+                )
+                    .chain()
+                    .after(bevy::input::InputSystems),
+            )
+            .add_systems(PreUpdate, inspector::mark_internal_entities_system)
+            .add_systems(Startup, inspector::load_locked_names_system)
+            .add_systems(Startup, inspector::load_saved_selections_system)
+            .add_systems(
+                PreUpdate,
+                (
+                    inspector::apply_locked_names_system,
+                    inspector::lock_pickable_system,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, world_stats::entity_count_sample_system)
+            .add_systems(Last, inspector::selection_changed_system)
+            .add_systems(Last, keybind::egui_input_capture_system);
+
+        // Autosave and crash recovery
+        if self.config.autosave_interval.is_some() {
+            app.add_systems(Startup, autosave::autosave_startup_system)
+                .add_systems(Update, autosave::autosave_recovery_prompt_system)
+                .add_systems(
+                    Last,
+                    (autosave::autosave_system, autosave::autosave_exit_system).chain(),
+                );
+        }
+
+        app.world_mut()
+            .resource_mut::<console::ConsoleState>()
+            .apply_config(&settings.console);
 
         // Mode system (Play/Stop/Pause) — only when toolbar is enabled
         if self.config.show_toolbar {
-            app.add_systems(Update, mode::mode_input_system)
+            app.init_resource::<scrub::ScrubState>()
+                .init_schedule(scrub::EvaluateAtTime)
+                .add_systems(
+                    Update,
+                    mode::mode_input_system.before(mode::mode_transition_system),
+                )
+                .add_systems(
+                    Update,
+                    (
+                        scrub::scrub_track_system.run_if(in_state(mode::EditorMode::Play)),
+                        scrub::scrub_evaluate_system,
+                    ),
+                )
+                .add_systems(
+                    PreUpdate,
+                    console::console_pause_on_error_system.after(console::console_drain_system),
+                )
                 .add_systems(Update, mode::run_game_schedule_system)
+                .add_systems(
+                    Update,
+                    mode::disable_editor_only_system
+                        .run_if(not(in_state(mode::EditorMode::Edit)))
+                        .before(mode::run_game_schedule_system),
+                )
                 .add_systems(OnEnter(mode::EditorMode::Play), mode::on_enter_play)
                 .add_systems(
                     OnEnter(mode::EditorMode::Play),
-                    console::console_auto_clear_system,
+                    mode::disable_editor_only_system,
+                )
+                .add_systems(
+                    OnEnter(mode::EditorMode::Play),
+                    (
+                        console::console_auto_clear_system,
+                        scrub::scrub_reset_system.run_if(mode::on_fresh_play),
+                        dock::hide_panels_on_play_system.run_if(mode::on_fresh_play),
+                    )
+                        .chain()
+                        .before(mode::on_enter_play),
+                )
+                .add_systems(
+                    OnEnter(mode::EditorMode::Play),
+                    dock::mode_layout_system.before(dock::hide_panels_on_play_system),
+                )
+                .add_systems(OnEnter(mode::EditorMode::Pause), dock::mode_layout_system)
+                .add_systems(
+                    OnEnter(mode::EditorMode::Edit),
+                    dock::mode_layout_system.before(dock::restore_panels_on_stop_system),
                 )
                 .add_systems(OnEnter(mode::EditorMode::Pause), mode::on_enter_pause)
-                .add_systems(OnEnter(mode::EditorMode::Edit), mode::on_enter_edit);
+                .add_systems(OnEnter(mode::EditorMode::Edit), mode::on_enter_edit)
+                .add_observer(console::console_session_start_observer)
+                .add_observer(console::console_session_end_observer)
+                .add_systems(
+                    OnEnter(mode::EditorMode::Edit),
+                    (
+                        dock::restore_panels_on_stop_system,
+                        mode::enable_editor_only_system,
+                    ),
+                );
         }
 
         // UI systems must run in EguiPrimaryContextPass (bevy_egui 0.39 multi-pass mode)
@@ -129,89 +297,125 @@ impl Plugin for WorkbenchPlugin {
                 (
                     config::config_apply_system,
                     font::install_fonts_system,
-                    theme::apply_theme_system,
+                    theme::apply_theme_system.run_if(theme::theme_changed),
                 )
                     .chain(),
-                game_view::game_view_sync_system
-                    .run_if(resource_exists::<game_view::GameViewState>),
-                menu_bar::menu_bar_system,
+                menu_bar::menu_bar_system.run_if(presentation::not_presenting),
             )
                 .chain();
 
             if self.config.show_toolbar {
                 app.add_systems(
                     EguiPrimaryContextPass,
-                    (ui_systems, menu_bar::toolbar_system, dock::tiles_ui_system).chain(),
+                    (
+                        ui_systems,
+                        menu_bar::toolbar_system.run_if(presentation::not_presenting),
+                        tasks::task_status_bar_system.run_if(presentation::not_presenting),
+                        dock::tiles_ui_system,
+                        dialogs::dialogs_ui_system,
+                    )
+                        .chain(),
                 );
             } else {
                 app.add_systems(
                     EguiPrimaryContextPass,
-                    (ui_systems, dock::tiles_ui_system).chain(),
+                    (
+                        ui_systems,
+                        tasks::task_status_bar_system.run_if(presentation::not_presenting),
+                        dock::tiles_ui_system,
+                        dialogs::dialogs_ui_system,
+                    )
+                        .chain(),
                 );
             }
         }
 
         // Game view render-to-texture pipeline
+        #[cfg(feature = "game-view")]
         if self.config.enable_game_view {
-            app.add_plugins(game_view::GameViewPlugin);
+            app.add_plugins(game_view::GameViewPlugin).add_systems(
+                EguiPrimaryContextPass,
+                game_view::game_view_sync_system
+                    .run_if(resource_exists::<game_view::GameViewState>)
+                    .after(theme::apply_theme_system)
+                    .before(menu_bar::menu_bar_system),
+            );
+            app.add_systems(
+                Update,
+                editor_camera::editor_camera_system.run_if(in_state(mode::EditorMode::Edit)),
+            );
+            // Registered first so the scene view is the active center tab by default.
+            app.register_panel(game_view::SceneViewPanel);
+            app.register_panel(game_view::GameViewPanel::default());
         }
 
-        // Register built-in panels
-        if self.config.enable_game_view {
-            app.register_panel(game_view::GameViewPanel::default());
+        // Async file dialogs (layout save/load, font picker)
+        #[cfg(feature = "file-dialogs")]
+        app.init_resource::<file_dialog::FileDialogs>()
+            .add_message::<file_dialog::FileDialogResponse>()
+            .add_systems(
+                Update,
+                (
+                    file_dialog::file_dialog_poll_system,
+                    menu_bar::builtin_file_dialogs_system,
+                )
+                    .chain(),
+            );
+
+        // Remote inspector (Bevy Remote Protocol client)
+        #[cfg(feature = "remote")]
+        app.add_plugins(remote::RemoteInspectorPlugin);
+
+        // Audio preview
+        #[cfg(feature = "audio")]
+        {
+            app.add_plugins(audio::AudioPreviewPlugin);
+            app.register_panel(audio::AudioPanel::default());
         }
+
+        // Register built-in panels
         app.register_panel(inspector::InspectorPanel);
         if self.config.show_console {
             app.register_panel(console::ConsolePanel);
         }
         // Settings panel initialized with loaded values
-        let settings_panel = menu_bar::SettingsPanel {
-            edited_scale: settings.ui_scale,
-            edited_edit_theme: settings.theme.edit_theme,
-            edited_play_theme: settings.theme.play_theme,
-            edited_edit_brightness: settings.theme.edit_brightness,
-            edited_play_brightness: settings.theme.play_brightness,
-            edited_locale: settings.locale,
-            edited_font_path: settings.font.custom_font_path.clone(),
-            ..Default::default()
-        };
+        let settings_panel = menu_bar::SettingsPanel::from_settings(&settings);
         app.register_panel(settings_panel);
         app.register_panel(menu_bar::KeybindingsPanel);
-        app.register_panel(undo::UndoHistoryPanel);
-    }
-}
-
-/// Extension trait for registering custom panels with the app.
-pub trait WorkbenchApp {
-    /// Register a custom panel. The panel will be added to the dock layout.
-    fn register_panel(&mut self, panel: impl dock::WorkbenchPanel) -> &mut Self;
-
-    /// Register a custom section in the built-in Settings panel.
-    fn register_settings_section(&mut self, section: menu_bar::SettingsSection) -> &mut Self;
-}
-
-impl WorkbenchApp for App {
-    fn register_panel(&mut self, panel: impl dock::WorkbenchPanel) -> &mut Self {
-        let mut tile_state = self
-            .world_mut()
-            .get_resource_mut::<dock::TileLayoutState>()
-            .expect("WorkbenchPlugin must be added before registering panels");
-        tile_state.add_panel(Box::new(panel));
-        self
-    }
-
-    fn register_settings_section(&mut self, section: menu_bar::SettingsSection) -> &mut Self {
-        let mut tile_state = self
-            .world_mut()
-            .get_resource_mut::<dock::TileLayoutState>()
-            .expect("WorkbenchPlugin must be added before registering settings sections");
-        if let Some(panel) = tile_state.get_panel_mut::<menu_bar::SettingsPanel>("settings") {
-            panel.custom_sections.push(section);
+        app.register_panel(undo::UndoHistoryPanel::default());
+        app.register_panel(shortcuts::ShortcutsPanel::default());
+        app.register_panel(help::HelpPanel);
+        app.register_panel(welcome::WelcomePanel::default());
+        if settings.show_welcome {
+            app.add_systems(Update, welcome::open_welcome_system);
         }
-        self
+        app.register_panel(world_stats::WorldStatsPanel::default());
+        app.register_panel(schedule_graph::ScheduleGraphPanel::default());
+        schedule_graph::add_run_counters(app);
+        app.add_plugins((
+            plot::PlotPlugin,
+            timeline::TimelinePlugin,
+            presentation::PresentationPlugin,
+            accessibility::AccessibilityPlugin,
+            window_title::WindowTitlePlugin,
+        ));
+        app.register_states([
+            mode::EditorMode::Edit,
+            mode::EditorMode::Play,
+            mode::EditorMode::Pause,
+        ]);
     }
 }
 
+/// Filter excluding the render-to-texture game view and scene view cameras.
+#[cfg(feature = "game-view")]
+type NotGameViewCamera = (
+    Without<game_view::GameViewCamera>,
+    Without<editor_camera::EditorCameraController>,
+);
+#[cfg(not(feature = "game-view"))]
+type NotGameViewCamera = ();
+
 /// Assigns PrimaryEguiContext to the first active, window-targeting camera that
 /// doesn't have one yet. Runs every frame until assigned. This replaces bevy_egui's
 /// auto_create_primary_context so the GameViewCamera never steals it.
@@ -223,10 +427,7 @@ fn assign_primary_egui_context_system(
             Option<&bevy::camera::RenderTarget>,
             &bevy::camera::Camera,
         ),
-        (
-            Without<bevy_egui::PrimaryEguiContext>,
-            Without<game_view::GameViewCamera>,
-        ),
+        (Without<bevy_egui::PrimaryEguiContext>, NotGameViewCamera),
     >,
     existing: Query<(), With<bevy_egui::PrimaryEguiContext>>,
 ) {
@@ -4,19 +4,29 @@ use bevy::prelude::*;
 use bevy_egui::EguiContexts;
 
 use crate::dock::TileLayoutState;
-use crate::mode::EditorMode;
-use crate::theme::gray;
+use hints::{MenuHints, hinted};
 
+mod action;
+#[cfg(feature = "file-dialogs")]
+mod file_dialogs;
+mod hints;
 mod keybindings_panel;
 mod settings_panel;
+mod toolbar;
+mod undo_buttons;
 
+pub use action::{FileOp, MenuAction, menu_action_system};
+#[cfg(feature = "file-dialogs")]
+pub use file_dialogs::builtin_file_dialogs_system;
 pub(crate) use keybindings_panel::KeyRecordState;
 pub use keybindings_panel::KeybindingsPanel;
 pub use settings_panel::{SettingsPanel, SettingsSection};
+pub use toolbar::toolbar_system;
 
 /// A custom item to inject into a menu.
 pub struct MenuExtItem {
-    /// Unique identifier for this action (e.g., "open", "save").
+    /// Unique identifier for this action (e.g., "open", "save"), sent as
+    /// [`MenuAction::Custom`] when clicked.
     pub id: &'static str,
     /// Display label (e.g., "Open...", "Save").
     pub label: String,
@@ -47,52 +57,68 @@ pub struct MenuBarExtensions {
     pub info_text: Option<String>,
 }
 
-/// Message sent when a custom menu item is clicked.
-#[derive(Message)]
-pub struct MenuAction {
-    pub id: &'static str,
-}
+/// Alt + letter opening the File, Edit, View, Window and Help menus (fixed, whatever the
+/// locale). Custom menus use the first ASCII letter or digit of their label.
+const MENU_ACCELERATORS: [egui::Key; 5] = [
+    egui::Key::F,
+    egui::Key::E,
+    egui::Key::V,
+    egui::Key::W,
+    egui::Key::H,
+];
 
 /// System that renders the top menu bar.
 pub fn menu_bar_system(
     mut contexts: EguiContexts,
-    mut tile_state: ResMut<TileLayoutState>,
+    tile_state: Res<TileLayoutState>,
     i18n: Res<crate::i18n::I18n>,
-    mut undo_stack: ResMut<crate::undo::UndoStack>,
+    undo_stack: Res<crate::undo::UndoStack>,
     extensions: Option<Res<MenuBarExtensions>>,
+    bindings: Option<Res<crate::keybind::KeyBindings>>,
+    layout: Option<Res<crate::keybind::KeyLayout>>,
     mut menu_actions: MessageWriter<MenuAction>,
 ) {
     let Ok(ctx) = contexts.ctx_mut() else { return };
+    let hints = MenuHints::new(bindings.as_deref(), layout.as_deref());
+    let mut accelerator = menu_accelerator(ctx, extensions.as_deref());
+    let [file_key, edit_key, view_key, window_key, help_key] = MENU_ACCELERATORS.map(Some);
     egui::TopBottomPanel::top("workbench_menu_bar").show(ctx, |ui| {
         egui::MenuBar::new().ui(ui, |ui| {
             // Left side: menus
-            ui.menu_button(i18n.t("menu-file"), |ui| {
-                file_menu_ui(
-                    ui,
-                    &mut tile_state,
-                    &i18n,
-                    extensions.as_deref(),
-                    &mut menu_actions,
-                );
+            let file = ui.menu_button(i18n.t("menu-file"), |ui| {
+                file_menu_ui(ui, &i18n, extensions.as_deref(), &mut menu_actions);
             });
+            open_on_accelerator(&file.response, &mut accelerator, file_key);
 
-            ui.menu_button(i18n.t("menu-edit"), |ui| {
-                edit_menu_ui(ui, &i18n, &mut undo_stack, &mut tile_state);
+            let edit = ui.menu_button(i18n.t("menu-edit"), |ui| {
+                edit_menu_ui(ui, &i18n, &undo_stack, &hints, &mut menu_actions);
             });
+            open_on_accelerator(&edit.response, &mut accelerator, edit_key);
 
-            ui.menu_button(i18n.t("menu-view"), |ui| {
-                view_menu_ui(ui, &i18n, &mut tile_state);
+            let view = ui.menu_button(i18n.t("menu-view"), |ui| {
+                #[cfg(feature = "file-dialogs")]
+                file_dialogs::layout_file_menu_ui(ui, &i18n, &mut menu_actions);
+                view_menu_ui(ui, &i18n, &hints, &mut menu_actions);
             });
+            open_on_accelerator(&view.response, &mut accelerator, view_key);
 
             // Window menu — toggle panel visibility
             let panel_list = tile_state.panel_list();
-            ui.menu_button(i18n.t("menu-window"), |ui| {
-                window_menu_ui(ui, &mut tile_state, &panel_list);
+            let last_closed = tile_state.last_closed_panel().is_some();
+            let window = ui.menu_button(i18n.t("menu-window"), |ui| {
+                reopen_menu_item_ui(ui, &i18n, last_closed, &hints, &mut menu_actions);
+                window_menu_ui(ui, &panel_list, &mut menu_actions);
             });
+            open_on_accelerator(&window.response, &mut accelerator, window_key);
+
+            let help = ui.menu_button(i18n.t("menu-help"), |ui| {
+                help_menu_ui(ui, &i18n, &hints, &mut menu_actions);
+            });
+            open_on_accelerator(&help.response, &mut accelerator, help_key);
 
             // Custom top-level menus
             if let Some(ref ext) = extensions {
-                custom_menus_ui(ui, &ext.custom_menus, &mut menu_actions);
+                custom_menus_ui(ui, &ext.custom_menus, &mut menu_actions, &mut accelerator);
             }
 
             // Extension info text (e.g., project title)
@@ -108,28 +134,47 @@ pub fn menu_bar_system(
     // Secondary toolbar — centered Play/Pause/Stop
 }
 
-/// System that renders the Play/Pause/Stop toolbar.
-/// Only added when `WorkbenchConfig::show_toolbar` is `true`.
-pub fn toolbar_system(
-    mut contexts: EguiContexts,
-    current_mode: Res<State<EditorMode>>,
-    mut next_mode: ResMut<NextState<EditorMode>>,
-    i18n: Res<crate::i18n::I18n>,
-) {
-    let Ok(ctx) = contexts.ctx_mut() else { return };
+/// Consume an Alt accelerator pressed this frame, if it belongs to a menu.
+fn menu_accelerator(
+    ctx: &egui::Context,
+    extensions: Option<&MenuBarExtensions>,
+) -> Option<egui::Key> {
+    let custom = extensions
+        .into_iter()
+        .flat_map(|ext| &ext.custom_menus)
+        .filter(|menu| menu.enabled)
+        .filter_map(|menu| accelerator_key(&menu.label));
+    let keys: Vec<egui::Key> = MENU_ACCELERATORS.into_iter().chain(custom).collect();
+    ctx.input_mut(|i| {
+        keys.into_iter()
+            .find(|&key| i.consume_key(egui::Modifiers::ALT, key))
+    })
+}
 
-    let btn_fill = gray::S250;
-    egui::TopBottomPanel::top("workbench_toolbar").show(ctx, |ui| {
-        ui.horizontal_centered(|ui| {
-            toolbar_buttons_ui(ui, current_mode.get(), &mut next_mode, &i18n, btn_fill);
-        });
-    });
+/// Accelerator of a custom menu: the first ASCII letter or digit of its label.
+fn accelerator_key(label: &str) -> Option<egui::Key> {
+    let c = label.chars().find(char::is_ascii_alphanumeric)?;
+    egui::Key::from_name(&c.to_ascii_uppercase().to_string())
+}
+
+/// Open the menu of `response` and give its button keyboard focus (so arrow keys walk the
+/// items) if `key` was the accelerator pressed. The first matching menu wins.
+fn open_on_accelerator(
+    response: &egui::Response,
+    accelerator: &mut Option<egui::Key>,
+    key: Option<egui::Key>,
+) {
+    if key.is_none() || *accelerator != key {
+        return;
+    }
+    *accelerator = None;
+    response.request_focus();
+    egui::Popup::open_id(&response.ctx, egui::Popup::default_response_id(response));
 }
 
 /// File menu content, extracted to reduce nesting.
 fn file_menu_ui(
     ui: &mut egui::Ui,
-    tile_state: &mut TileLayoutState,
     i18n: &crate::i18n::I18n,
     extensions: Option<&MenuBarExtensions>,
     menu_actions: &mut MessageWriter<MenuAction>,
@@ -140,7 +185,7 @@ fn file_menu_ui(
                 .add_enabled(item.enabled, egui::Button::new(&item.label))
                 .clicked()
             {
-                menu_actions.write(MenuAction { id: item.id });
+                menu_actions.write(MenuAction::Custom(item.id.to_string()));
                 ui.close();
             }
         }
@@ -149,7 +194,7 @@ fn file_menu_ui(
         }
     }
     if ui.button(i18n.t("menu-file-settings")).clicked() {
-        tile_state.request_open_panel("settings");
+        menu_actions.write(MenuAction::OpenPanel("settings".to_string()));
         ui.close();
     }
 }
@@ -158,82 +203,119 @@ fn file_menu_ui(
 fn edit_menu_ui(
     ui: &mut egui::Ui,
     i18n: &crate::i18n::I18n,
-    undo_stack: &mut crate::undo::UndoStack,
-    tile_state: &mut TileLayoutState,
+    undo_stack: &crate::undo::UndoStack,
+    hints: &MenuHints,
+    menu_actions: &mut MessageWriter<MenuAction>,
 ) {
     let undo_label = if let Some(desc) = undo_stack.undo_description() {
         format!("{} ({})", i18n.t("menu-edit-undo"), desc)
     } else {
-        i18n.t("menu-edit-undo")
+        i18n.t("menu-edit-undo").to_owned()
     };
     if ui
-        .add_enabled(undo_stack.can_undo(), egui::Button::new(undo_label))
+        .add_enabled(
+            undo_stack.can_undo(),
+            hinted(egui::Button::new(undo_label), hints.undo.as_deref()),
+        )
         .clicked()
     {
-        undo_stack.undo_requested = true;
+        menu_actions.write(MenuAction::Undo);
         ui.close();
     }
     let redo_label = if let Some(desc) = undo_stack.redo_description() {
         format!("{} ({})", i18n.t("menu-edit-redo"), desc)
     } else {
-        i18n.t("menu-edit-redo")
+        i18n.t("menu-edit-redo").to_owned()
     };
     if ui
-        .add_enabled(undo_stack.can_redo(), egui::Button::new(redo_label))
+        .add_enabled(
+            undo_stack.can_redo(),
+            hinted(egui::Button::new(redo_label), hints.redo.as_deref()),
+        )
         .clicked()
     {
-        undo_stack.redo_requested = true;
+        menu_actions.write(MenuAction::Redo);
         ui.close();
     }
     ui.separator();
     if ui.button("Keybindings...").clicked() {
-        tile_state.request_open_panel("keybindings");
+        menu_actions.write(MenuAction::OpenPanel("keybindings".to_string()));
         ui.close();
     }
     if ui.button("Undo History").clicked() {
-        tile_state.request_open_panel("undo_history");
+        menu_actions.write(MenuAction::OpenPanel("undo_history".to_string()));
         ui.close();
     }
 }
 
 /// View menu content, extracted to reduce nesting.
-fn view_menu_ui(ui: &mut egui::Ui, i18n: &crate::i18n::I18n, tile_state: &mut TileLayoutState) {
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        if ui.button(i18n.t("menu-view-save-layout")).clicked() {
-            if let Some(path) = rfd::FileDialog::new()
-                .set_title(i18n.t("dialog-save-layout"))
-                .add_filter("JSON", &["json"])
-                .set_file_name("layout.json")
-                .save_file()
-            {
-                tile_state.layout_save_path = Some(path);
-            }
-            ui.close();
-        }
-        if ui.button(i18n.t("menu-view-load-layout")).clicked() {
-            if let Some(path) = rfd::FileDialog::new()
-                .set_title(i18n.t("dialog-load-layout"))
-                .add_filter("JSON", &["json"])
-                .pick_file()
-            {
-                tile_state.layout_load_path = Some(path);
-            }
-            ui.close();
-        }
-        ui.separator();
+fn view_menu_ui(
+    ui: &mut egui::Ui,
+    i18n: &crate::i18n::I18n,
+    hints: &MenuHints,
+    menu_actions: &mut MessageWriter<MenuAction>,
+) {
+    let presentation = hinted(
+        egui::Button::new(i18n.t("menu-view-presentation")),
+        hints.presentation.as_deref(),
+    );
+    if ui.add(presentation).clicked() {
+        menu_actions.write(MenuAction::TogglePresentation);
+        ui.close();
     }
     if ui.button(i18n.t("menu-view-reset-layout")).clicked() {
-        tile_state.layout_reset_requested = true;
+        menu_actions.write(MenuAction::ResetLayout);
+        ui.close();
+    }
+}
+
+/// Help menu content, extracted to reduce nesting.
+fn help_menu_ui(
+    ui: &mut egui::Ui,
+    i18n: &crate::i18n::I18n,
+    hints: &MenuHints,
+    menu_actions: &mut MessageWriter<MenuAction>,
+) {
+    let shortcuts = hinted(
+        egui::Button::new(i18n.t("menu-help-shortcuts")),
+        hints.shortcuts.as_deref(),
+    );
+    if ui.add(shortcuts).clicked() {
+        let id = crate::shortcuts::SHORTCUTS_PANEL.to_string();
+        menu_actions.write(MenuAction::OpenPanel(id));
+        ui.close();
+    }
+    if ui.button(i18n.t("menu-help-panels")).clicked() {
+        let id = crate::help::HELP_PANEL.to_string();
+        menu_actions.write(MenuAction::OpenPanel(id));
         ui.close();
     }
 }
 
+/// "Reopen Closed Panel" at the top of the Window menu, enabled while there is one to reopen.
+fn reopen_menu_item_ui(
+    ui: &mut egui::Ui,
+    i18n: &crate::i18n::I18n,
+    enabled: bool,
+    hints: &MenuHints,
+    menu_actions: &mut MessageWriter<MenuAction>,
+) {
+    let reopen = hinted(
+        egui::Button::new(i18n.t("menu-window-reopen")),
+        hints.reopen_panel.as_deref(),
+    );
+    if ui.add_enabled(enabled, reopen).clicked() {
+        menu_actions.write(MenuAction::ReopenClosedPanel);
+        ui.close();
+    }
+    ui.separator();
+}
+
 /// Window menu content, extracted to reduce nesting.
 fn window_menu_ui(
     ui: &mut egui::Ui,
-    tile_state: &mut TileLayoutState,
     panel_list: &[(String, String, bool)],
+    menu_actions: &mut MessageWriter<MenuAction>,
 ) {
     for (str_id, title, visible) in panel_list {
         let text = if *visible {
@@ -241,15 +323,16 @@ fn window_menu_ui(
         } else {
             egui::RichText::new(title).weak()
         };
-        if ui.button(text).clicked() {
-            if *visible
-                && let Some(&panel_id) = tile_state.panel_id_map.get(str_id.as_str())
-                && let Some(&tile_id) = tile_state.panel_tile_map.get(&panel_id)
-            {
-                tile_state.hide_tile(tile_id);
-            } else if !*visible {
-                tile_state.request_open_panel(str_id);
-            }
+        let response = ui.button(text);
+        response.widget_info(|| {
+            egui::WidgetInfo::selected(egui::WidgetType::Checkbox, true, *visible, title)
+        });
+        if response.clicked() {
+            menu_actions.write(if *visible {
+                MenuAction::HidePanel(str_id.clone())
+            } else {
+                MenuAction::OpenPanel(str_id.clone())
+            });
             ui.close();
         }
     }
@@ -260,12 +343,20 @@ fn custom_menus_ui(
     ui: &mut egui::Ui,
     menus: &[CustomMenu],
     menu_actions: &mut MessageWriter<MenuAction>,
+    accelerator: &mut Option<egui::Key>,
 ) {
     for menu in menus {
         ui.add_enabled_ui(menu.enabled, |ui| {
-            ui.menu_button(&menu.label, |ui| {
+            let response = ui.menu_button(&menu.label, |ui| {
                 custom_menu_items_ui(ui, &menu.items, menu_actions);
             });
+            if menu.enabled {
+                open_on_accelerator(
+                    &response.response,
+                    accelerator,
+                    accelerator_key(&menu.label),
+                );
+            }
         });
     }
 }
@@ -281,80 +372,8 @@ fn custom_menu_items_ui(
             .add_enabled(item.enabled, egui::Button::new(&item.label))
             .clicked()
         {
-            menu_actions.write(MenuAction { id: item.id });
+            menu_actions.write(MenuAction::Custom(item.id.to_string()));
             ui.close();
         }
     }
 }
-
-/// Toolbar buttons for Play/Pause/Stop, extracted to reduce nesting.
-fn toolbar_buttons_ui(
-    ui: &mut egui::Ui,
-    current_mode: &EditorMode,
-    next_mode: &mut NextState<EditorMode>,
-    i18n: &crate::i18n::I18n,
-    btn_fill: egui::Color32,
-) {
-    let button_w = 80.0;
-    let n_buttons: f32 = match current_mode {
-        EditorMode::Edit => 1.0,
-        _ => 2.0,
-    };
-    let total = button_w * n_buttons + 4.0 * (n_buttons - 1.0_f32).max(0.0);
-    let pad = ((ui.available_width() - total) / 2.0).max(0.0);
-    ui.add_space(pad);
-
-    match current_mode {
-        EditorMode::Edit => {
-            if ui
-                .add_sized(
-                    [button_w, 18.0],
-                    egui::Button::new(i18n.t("toolbar-play")).fill(btn_fill),
-                )
-                .clicked()
-            {
-                next_mode.set(EditorMode::Play);
-            }
-        }
-        EditorMode::Play => {
-            if ui
-                .add_sized(
-                    [button_w, 18.0],
-                    egui::Button::new(i18n.t("toolbar-pause")).fill(btn_fill),
-                )
-                .clicked()
-            {
-                next_mode.set(EditorMode::Pause);
-            }
-            if ui
-                .add_sized(
-                    [button_w, 18.0],
-                    egui::Button::new(i18n.t("toolbar-stop")).fill(btn_fill),
-                )
-                .clicked()
-            {
-                next_mode.set(EditorMode::Edit);
-            }
-        }
-        EditorMode::Pause => {
-            if ui
-                .add_sized(
-                    [button_w, 18.0],
-                    egui::Button::new(i18n.t("toolbar-resume")).fill(btn_fill),
-                )
-                .clicked()
-            {
-                next_mode.set(EditorMode::Play);
-            }
-            if ui
-                .add_sized(
-                    [button_w, 18.0],
-                    egui::Button::new(i18n.t("toolbar-stop")).fill(btn_fill),
-                )
-                .clicked()
-            {
-                next_mode.set(EditorMode::Edit);
-            }
-        }
-    }
-}
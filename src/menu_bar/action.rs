@@ -0,0 +1,104 @@
+//! # action.rs
+//!
+//! # action.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Menu clicks as data. The menu bar never changes workbench state itself: every item it draws
+//! writes a `MenuAction` message. `menu_action_system` applies the built-in actions (opening,
+//! hiding and reopening panels, undo / redo, presentation mode, layout reset and layout files)
+//! and re-triggers every action as an observer event, so user code can react with either a
+//! `MessageReader` or `app.add_observer(|action: On<MenuAction>| ...)`.
+//!
+//! 将菜单点击表示为数据。菜单栏自身从不修改 workbench 状态：它绘制的每个菜单项都会写入一条
+//! `MenuAction` 消息。`menu_action_system` 负责执行内置操作（打开、隐藏与重新打开面板、撤销 / 重做、
+//! 演示模式、重置布局以及布局文件），并把每个操作再作为观察者事件触发，因此用户代码既可以用
+//! `MessageReader` 读取，也可以通过 `app.add_observer(|action: On<MenuAction>| ...)` 响应。
+
+use bevy::prelude::*;
+
+use crate::dock::TileLayoutState;
+
+/// Layout file operations from the View menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOp {
+    /// Save the current layout to a file picked by the user.
+    SaveLayout,
+    /// Load a layout from a file picked by the user.
+    LoadLayout,
+}
+
+/// Sent when a menu item is clicked; also triggered as an observer event once handled.
+#[derive(Message, Event, Debug, Clone, PartialEq)]
+pub enum MenuAction {
+    /// Open (or focus) the panel with this string ID.
+    OpenPanel(String),
+    /// Hide the panel with this string ID, in the main window or a secondary one.
+    HidePanel(String),
+    /// Reopen the most recently closed panel where it was.
+    ReopenClosedPanel,
+    Undo,
+    Redo,
+    TogglePresentation,
+    /// Ask for confirmation, then restore the default layout.
+    ResetLayout,
+    /// Needs the `file-dialogs` feature; ignored without it.
+    FileOp(FileOp),
+    /// A custom item from `MenuBarExtensions` (its `MenuExtItem::id`).
+    Custom(String),
+}
+
+/// Applies built-in menu actions and triggers every action for observers.
+pub fn menu_action_system(
+    mut commands: Commands,
+    mut actions: MessageReader<MenuAction>,
+    mut tile_state: ResMut<TileLayoutState>,
+    mut undo_stack: ResMut<crate::undo::UndoStack>,
+    mut dialogs: ResMut<crate::dialogs::Dialogs>,
+    mut presentation: ResMut<crate::presentation::PresentationMode>,
+    i18n: Res<crate::i18n::I18n>,
+    #[cfg(feature = "file-dialogs")] mut file_dialogs: ResMut<crate::file_dialog::FileDialogs>,
+) {
+    for action in actions.read() {
+        match action {
+            MenuAction::OpenPanel(id) => tile_state.request_open_panel(id),
+            MenuAction::HidePanel(id) => {
+                tile_state.close_panel(id);
+            }
+            MenuAction::ReopenClosedPanel => tile_state.reopen_requested = true,
+            MenuAction::Undo => undo_stack.undo_requested = true,
+            MenuAction::Redo => undo_stack.redo_requested = true,
+            MenuAction::TogglePresentation => presentation.toggle_requested = true,
+            MenuAction::ResetLayout => confirm_layout_reset(&mut dialogs, &i18n),
+            #[cfg(feature = "file-dialogs")]
+            MenuAction::FileOp(op) => {
+                super::file_dialogs::request_layout_file(
+                    *op,
+                    &i18n,
+                    &tile_state,
+                    &mut file_dialogs,
+                );
+            }
+            #[cfg(not(feature = "file-dialogs"))]
+            MenuAction::FileOp(_) => {}
+            MenuAction::Custom(_) => {}
+        }
+        commands.trigger(action.clone());
+    }
+}
+
+fn confirm_layout_reset(dialogs: &mut crate::dialogs::Dialogs, i18n: &crate::i18n::I18n) {
+    dialogs.confirm(
+        i18n.t("menu-view-reset-layout"),
+        i18n.t("dialog-reset-layout-confirm"),
+        |world, ok| {
+            if ok {
+                world
+                    .resource_mut::<TileLayoutState>()
+                    .layout_reset_requested = true;
+            }
+        },
+    );
+}
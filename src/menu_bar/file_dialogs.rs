@@ -0,0 +1,125 @@
+//! # file_dialogs.rs
+//!
+//! # file_dialogs.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Built-in uses of the async file dialog service: the View menu's Save/Load Layout actions and
+//! the Settings panel's custom font picker. Requests go out through `FileDialogs`, and a system
+//! routes the matching responses back into the dock layout or the settings panel (and logs the
+//! outcome of console exports).
+//!
+//! 内置的异步文件对话框用法：View 菜单中的保存/加载布局项以及设置面板中的自定义字体选择器。
+//! 请求通过 `FileDialogs` 发出，并由一个系统将对应的响应分发回停靠布局或设置面板（并记录控制台导出的结果）。
+
+use super::{FileOp, MenuAction, SettingsPanel};
+use crate::dock::TileLayoutState;
+use crate::file_dialog::{FileDialogRequest, FileDialogResponse, FileDialogs};
+use bevy::prelude::*;
+
+/// File dialog IDs used by the built-in menus and settings panel.
+const SAVE_LAYOUT_DIALOG: &str = "workbench_save_layout";
+const LOAD_LAYOUT_DIALOG: &str = "workbench_load_layout";
+const FONT_DIALOG: &str = "workbench_font";
+
+/// Save/Load Layout items at the top of the View menu.
+pub(super) fn layout_file_menu_ui(
+    ui: &mut egui::Ui,
+    i18n: &crate::i18n::I18n,
+    menu_actions: &mut MessageWriter<MenuAction>,
+) {
+    if ui.button(i18n.t("menu-view-save-layout")).clicked() {
+        menu_actions.write(MenuAction::FileOp(FileOp::SaveLayout));
+        ui.close();
+    }
+    if ui.button(i18n.t("menu-view-load-layout")).clicked() {
+        menu_actions.write(MenuAction::FileOp(FileOp::LoadLayout));
+        ui.close();
+    }
+    ui.separator();
+}
+
+/// Open the file dialog of a layout file operation.
+pub(super) fn request_layout_file(
+    op: FileOp,
+    i18n: &crate::i18n::I18n,
+    tile_state: &TileLayoutState,
+    file_dialogs: &mut FileDialogs,
+) {
+    match op {
+        FileOp::SaveLayout => {
+            if let Some(json) = tile_state.layout_json() {
+                file_dialogs.request(
+                    FileDialogRequest::save(SAVE_LAYOUT_DIALOG, json.into_bytes())
+                        .with_title(i18n.t("dialog-save-layout"))
+                        .with_filter("JSON", &["json"])
+                        .with_file_name("layout.json"),
+                );
+            }
+        }
+        FileOp::LoadLayout => file_dialogs.request(
+            FileDialogRequest::open(LOAD_LAYOUT_DIALOG)
+                .with_title(i18n.t("dialog-load-layout"))
+                .with_filter("JSON", &["json"])
+                .reading_contents(),
+        ),
+    }
+}
+
+/// Opens file dialogs requested by built-in panels and applies their responses.
+pub fn builtin_file_dialogs_system(
+    mut responses: MessageReader<FileDialogResponse>,
+    mut file_dialogs: ResMut<FileDialogs>,
+    mut tile_state: ResMut<TileLayoutState>,
+    mut settings: ResMut<crate::config::WorkbenchSettings>,
+    config_path: Res<crate::config::ConfigPath>,
+) {
+    if let Some(panel) = tile_state.get_panel_mut::<SettingsPanel>("settings")
+        && std::mem::take(&mut panel.font_pick_requested)
+    {
+        file_dialogs.request(
+            FileDialogRequest::open(FONT_DIALOG).with_filter("Font", &["otf", "ttf", "ttc"]),
+        );
+    }
+
+    for response in responses.read() {
+        let Some(file) = &response.file else {
+            continue;
+        };
+        if let Some(error) = &file.error {
+            warn!("Failed to save {}: {error}", file.name);
+            continue;
+        }
+        if matches!(
+            response.id.as_str(),
+            SAVE_LAYOUT_DIALOG | LOAD_LAYOUT_DIALOG
+        ) && let Some(path) = &file.path
+        {
+            // Listed on the welcome panel.
+            settings.push_recent_layout(path.display().to_string());
+            settings.save(&config_path.0);
+        }
+        match response.id.as_str() {
+            SAVE_LAYOUT_DIALOG => info!("Layout saved to {}", file.name),
+            crate::console::EXPORT_DIALOG => info!("Console log exported to {}", file.name),
+            LOAD_LAYOUT_DIALOG => {
+                let content = file
+                    .contents
+                    .as_deref()
+                    .map(String::from_utf8_lossy)
+                    .unwrap_or_default();
+                tile_state.pending_layout_load = Some((content.into_owned(), file.name.clone()));
+            }
+            FONT_DIALOG => {
+                if let Some(path) = &file.path
+                    && let Some(panel) = tile_state.get_panel_mut::<SettingsPanel>("settings")
+                {
+                    panel.edited_font_path = Some(path.display().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! # hints.rs
+//!
+//! # hints.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Keybind hints for menu items. The labels are read from `KeyBindings` every frame the menu
+//! is drawn, so re-recording a shortcut in the Keybindings panel updates the menus right away,
+//! and they name keys after the keyboard layout like the panel does. Items show the first
+//! binding of their slot, right-aligned the way desktop menus do. Hints cover the built-in Edit
+//! and View menus only: the workbench has no Tools menu or command palette to annotate.
+//!
+//! 菜单项的快捷键提示。每次绘制菜单时都会从 `KeyBindings` 读取标签，因此在快捷键面板中重新录制
+//! 快捷键后菜单会立即更新，并且与面板一样按键盘布局命名按键。菜单项显示其槽位的第一个绑定，
+//! 并像桌面菜单那样右对齐。提示只覆盖内置的编辑和视图菜单：workbench 没有可标注的工具菜单
+//! 或命令面板。
+
+use crate::keybind::{KeyBindSlot, KeyBindings, KeyLayout};
+
+/// Shortcut labels of the built-in menu items that have a binding.
+#[derive(Default)]
+pub(super) struct MenuHints {
+    pub(super) undo: Option<String>,
+    pub(super) redo: Option<String>,
+    pub(super) presentation: Option<String>,
+    pub(super) reopen_panel: Option<String>,
+    pub(super) shortcuts: Option<String>,
+    pub(super) play_stop: Option<String>,
+    pub(super) pause_resume: Option<String>,
+}
+
+impl MenuHints {
+    pub(super) fn new(bindings: Option<&KeyBindings>, layout: Option<&KeyLayout>) -> Self {
+        let Some(bindings) = bindings else {
+            return Self::default();
+        };
+        let layout = bindings.display_layout(layout);
+        let hint = |slot: &KeyBindSlot| slot.hint(layout);
+        Self {
+            undo: hint(&bindings.undo),
+            redo: hint(&bindings.redo),
+            presentation: hint(&bindings.presentation_mode),
+            reopen_panel: hint(&bindings.reopen_panel),
+            shortcuts: hint(&bindings.shortcuts_help),
+            play_stop: hint(&bindings.play_stop),
+            pause_resume: hint(&bindings.pause_resume),
+        }
+    }
+}
+
+/// `button` with `hint` as its right-aligned shortcut text, if any.
+pub(super) fn hinted<'a>(button: egui::Button<'a>, hint: Option<&'a str>) -> egui::Button<'a> {
+    match hint {
+        Some(hint) => button.shortcut_text(hint),
+        None => button,
+    }
+}
@@ -13,7 +13,9 @@
 //! 实现了 `bevy_workbench` 的快捷键编辑面板。它负责管理重新录制快捷键时的临时状态，
 //! 并渲染需要访问 `World` 的 UI，让用户查看、替换、追加或重置各个按键槽位。
 
+use crate::bench_ui::{Icon, icon};
 use crate::dock::WorkbenchPanel;
+use crate::keybind::{BindTrigger, CHORD_TIMEOUT, KeyBind, KeyInput, KeyLayout, WheelNotch};
 use crate::theme::gray;
 use bevy::prelude::*;
 
@@ -27,6 +29,9 @@ pub(crate) struct KeyRecordState {
     pub(crate) recording: Option<String>,
     /// Which binding index within the slot (None = add new).
     pub(crate) recording_index: Option<usize>,
+    /// First step already recorded, and when; a second step within the chord timeout makes
+    /// the binding a chord.
+    first_step: Option<(crate::keybind::KeyBind, f64)>,
 }
 
 impl WorkbenchPanel for KeybindingsPanel {
@@ -44,41 +49,103 @@ impl WorkbenchPanel for KeybindingsPanel {
         let mut bindings = world
             .remove_resource::<crate::keybind::KeyBindings>()
             .unwrap_or_default();
+        let before = bindings.clone();
         let mut record_state = world
             .remove_resource::<KeyRecordState>()
             .unwrap_or_default();
 
         handle_key_recording(world, &mut record_state, &mut bindings);
+        let layout = bindings.display_layout(world.get_resource::<KeyLayout>());
 
+        let mut over_list = false;
         egui::Frame::NONE
             .inner_margin(egui::Margin::same(8))
             .show(ui, |ui| {
                 ui.heading("Keybindings");
                 ui.separator();
-                ui.label("Click a binding to re-record. Press Esc to cancel.");
+                ui.label(
+                    "Click a binding to re-record: press a key, Middle / Back / Forward mouse \
+                     button or scroll (outside this list). Press a second one right away to \
+                     record a chord. Press Esc to cancel.",
+                );
+                ui.checkbox(
+                    &mut bindings.match_logical,
+                    "Match keys by character (keyboard layout)",
+                )
+                .on_hover_text(
+                    "Bindings fire for the key that types their character instead of the key \
+                     at the same position on a US keyboard",
+                );
                 ui.add_space(4.0);
 
-                egui::Grid::new("keybind_grid")
-                    .num_columns(2)
-                    .spacing([12.0, 8.0])
+                let list = egui::ScrollArea::vertical()
+                    .id_salt("keybind_scroll")
                     .show(ui, |ui| {
-                        keybind_row(ui, "Undo", "undo", &mut bindings.undo, &mut record_state);
-                        keybind_row(ui, "Redo", "redo", &mut bindings.redo, &mut record_state);
-                        keybind_row(
-                            ui,
-                            "Play / Stop",
-                            "play_stop",
-                            &mut bindings.play_stop,
-                            &mut record_state,
-                        );
-                        keybind_row(
-                            ui,
-                            "Pause / Resume",
-                            "pause_resume",
-                            &mut bindings.pause_resume,
-                            &mut record_state,
-                        );
+                        egui::Grid::new("keybind_grid")
+                            .num_columns(2)
+                            .spacing([12.0, 8.0])
+                            .show(ui, |ui| {
+                                keybind_row(
+                                    ui,
+                                    "Undo",
+                                    "undo",
+                                    &mut bindings.undo,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                keybind_row(
+                                    ui,
+                                    "Redo",
+                                    "redo",
+                                    &mut bindings.redo,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                keybind_row(
+                                    ui,
+                                    "Undo Layout",
+                                    "layout_undo",
+                                    &mut bindings.layout_undo,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                keybind_row(
+                                    ui,
+                                    "Redo Layout",
+                                    "layout_redo",
+                                    &mut bindings.layout_redo,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                keybind_row(
+                                    ui,
+                                    "Play / Stop",
+                                    "play_stop",
+                                    &mut bindings.play_stop,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                keybind_row(
+                                    ui,
+                                    "Pause / Resume",
+                                    "pause_resume",
+                                    &mut bindings.pause_resume,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                keybind_row(
+                                    ui,
+                                    "Hold to Toggle Snap",
+                                    "snap_toggle",
+                                    &mut bindings.snap_toggle,
+                                    &mut record_state,
+                                    layout,
+                                );
+                                panel_focus_rows(ui, &mut bindings, &mut record_state, layout);
+                                hierarchy_rows(ui, &mut bindings, &mut record_state, layout);
+                            });
                     });
+                over_list = ui.rect_contains_pointer(list.inner_rect);
 
                 ui.separator();
                 if ui.button("Reset to Defaults").clicked() {
@@ -87,6 +154,16 @@ impl WorkbenchPanel for KeybindingsPanel {
                 }
             });
 
+        // While recording, the wheel over the list scrolls it instead of being recorded.
+        if over_list
+            && record_state.recording.is_some()
+            && let Some(mut wheel) = world.get_resource_mut::<WheelNotch>()
+        {
+            wheel.ignore_next = true;
+        }
+        if bindings != before {
+            save_bindings(world, &bindings);
+        }
         world.insert_resource(bindings);
         world.insert_resource(record_state);
     }
@@ -108,41 +185,46 @@ fn handle_key_recording(
     let Some(action) = record_state.recording.clone() else {
         return;
     };
-    let Some(input) = world.get_resource::<ButtonInput<KeyCode>>() else {
+    let Some(input) = KeyInput::from_world(world) else {
         return;
     };
 
-    if input.just_pressed(KeyCode::Escape) {
+    if input.keys.just_pressed(KeyCode::Escape) {
         record_state.recording = None;
         record_state.recording_index = None;
+        record_state.first_step = None;
         return;
     }
 
-    let Some(key) = find_just_pressed_key(input) else {
-        return;
-    };
-
-    let ctrl = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
-    let shift = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
-    let alt = input.pressed(KeyCode::AltLeft) || input.pressed(KeyCode::AltRight);
-    let new_bind = crate::keybind::KeyBind {
-        key,
-        ctrl,
-        shift,
-        alt,
-    };
-
-    let slot = match action.as_str() {
-        "undo" => &mut bindings.undo,
-        "redo" => &mut bindings.redo,
-        "play_stop" => &mut bindings.play_stop,
-        "pause_resume" => &mut bindings.pause_resume,
-        _ => {
-            record_state.recording = None;
+    let now = world
+        .get_resource::<Time<Real>>()
+        .map_or(0.0, |time| time.elapsed_secs_f64());
+    let new_bind = match (
+        find_just_pressed_trigger(&input),
+        record_state.first_step.take(),
+    ) {
+        (Some(trigger), first) => {
+            let step = KeyBind::step(trigger, input.keys);
+            match first {
+                Some((first, _)) => first.then(step),
+                None => {
+                    record_state.first_step = Some((step, now));
+                    return;
+                }
+            }
+        }
+        (None, Some((first, since))) if now - since > CHORD_TIMEOUT => first,
+        (None, first) => {
+            record_state.first_step = first;
             return;
         }
     };
 
+    let Some(slot) = binding_slot(bindings, &action) else {
+        record_state.recording = None;
+        return;
+    };
+
     if let Some(idx) = record_state.recording_index {
         if idx < slot.bindings.len() {
             slot.bindings[idx] = new_bind;
@@ -155,12 +237,126 @@ fn handle_key_recording(
     record_state.recording_index = None;
 }
 
+/// Look up the binding slot for a recording action ID.
+fn binding_slot<'a>(
+    bindings: &'a mut crate::keybind::KeyBindings,
+    action: &str,
+) -> Option<&'a mut crate::keybind::KeyBindSlot> {
+    match action {
+        "undo" => Some(&mut bindings.undo),
+        "redo" => Some(&mut bindings.redo),
+        "layout_undo" => Some(&mut bindings.layout_undo),
+        "layout_redo" => Some(&mut bindings.layout_redo),
+        "play_stop" => Some(&mut bindings.play_stop),
+        "pause_resume" => Some(&mut bindings.pause_resume),
+        "next_panel" => Some(&mut bindings.next_panel),
+        "prev_panel" => Some(&mut bindings.prev_panel),
+        "maximize_panel" => Some(&mut bindings.maximize_panel),
+        "reopen_panel" => Some(&mut bindings.reopen_panel),
+        "presentation_mode" => Some(&mut bindings.presentation_mode),
+        "shortcuts_help" => Some(&mut bindings.shortcuts_help),
+        "snap_toggle" => Some(&mut bindings.snap_toggle),
+        "hierarchy_up" => Some(&mut bindings.hierarchy_up),
+        "hierarchy_down" => Some(&mut bindings.hierarchy_down),
+        "hierarchy_expand" => Some(&mut bindings.hierarchy_expand),
+        "hierarchy_collapse" => Some(&mut bindings.hierarchy_collapse),
+        "hierarchy_select" => Some(&mut bindings.hierarchy_select),
+        _ => {
+            let index: usize = action.strip_prefix("focus_panel_")?.parse().ok()?;
+            bindings.focus_panel.get_mut(index.checked_sub(1)?)
+        }
+    }
+}
+
+/// Rows for panel cycling and direct panel focus bindings.
+fn panel_focus_rows(
+    ui: &mut egui::Ui,
+    bindings: &mut crate::keybind::KeyBindings,
+    record_state: &mut KeyRecordState,
+    layout: Option<&KeyLayout>,
+) {
+    let rows = [
+        ("Next Panel", "next_panel", &mut bindings.next_panel),
+        ("Previous Panel", "prev_panel", &mut bindings.prev_panel),
+        (
+            "Maximize Panel",
+            "maximize_panel",
+            &mut bindings.maximize_panel,
+        ),
+        (
+            "Reopen Closed Panel",
+            "reopen_panel",
+            &mut bindings.reopen_panel,
+        ),
+        (
+            "Presentation Mode",
+            "presentation_mode",
+            &mut bindings.presentation_mode,
+        ),
+        (
+            "Keyboard Shortcuts",
+            "shortcuts_help",
+            &mut bindings.shortcuts_help,
+        ),
+    ];
+    for (label, action_id, slot) in rows {
+        keybind_row(ui, label, action_id, slot, record_state, layout);
+    }
+    for (i, slot) in bindings.focus_panel.iter_mut().enumerate() {
+        let n = i + 1;
+        keybind_row(
+            ui,
+            &format!("Focus Panel {n}"),
+            &format!("focus_panel_{n}"),
+            slot,
+            record_state,
+            layout,
+        );
+    }
+}
+
+/// Rows for keyboard navigation of the inspector hierarchy.
+fn hierarchy_rows(
+    ui: &mut egui::Ui,
+    bindings: &mut crate::keybind::KeyBindings,
+    record_state: &mut KeyRecordState,
+    layout: Option<&KeyLayout>,
+) {
+    let rows = [
+        ("Hierarchy Up", "hierarchy_up", &mut bindings.hierarchy_up),
+        (
+            "Hierarchy Down",
+            "hierarchy_down",
+            &mut bindings.hierarchy_down,
+        ),
+        (
+            "Hierarchy Expand",
+            "hierarchy_expand",
+            &mut bindings.hierarchy_expand,
+        ),
+        (
+            "Hierarchy Collapse",
+            "hierarchy_collapse",
+            &mut bindings.hierarchy_collapse,
+        ),
+        (
+            "Hierarchy Select",
+            "hierarchy_select",
+            &mut bindings.hierarchy_select,
+        ),
+    ];
+    for (label, action_id, slot) in rows {
+        keybind_row(ui, label, action_id, slot, record_state, layout);
+    }
+}
+
 fn keybind_row(
     ui: &mut egui::Ui,
     label: &str,
     action_id: &str,
     slot: &mut crate::keybind::KeyBindSlot,
     record_state: &mut KeyRecordState,
+    layout: Option<&KeyLayout>,
 ) {
     ui.label(label);
     ui.horizontal(|ui| {
@@ -176,12 +372,12 @@ fn keybind_row(
 
             let recording_this = is_recording && record_state.recording_index == Some(i);
             let text = if recording_this {
-                egui::RichText::new("⏺ Press key...")
+                egui::RichText::new(recording_label(record_state, layout))
                     .monospace()
-                    .color(egui::Color32::YELLOW)
+                    .color(crate::theme::palette(ui.ctx()).warn)
                     .background_color(gray::S200)
             } else {
-                egui::RichText::new(bind.label())
+                egui::RichText::new(bind.label_with(layout))
                     .monospace()
                     .background_color(gray::S300)
             };
@@ -204,6 +400,50 @@ fn keybind_row(
     ui.end_row();
 }
 
+/// Prompt shown on the binding being recorded.
+fn recording_label(record_state: &KeyRecordState, layout: Option<&KeyLayout>) -> String {
+    match &record_state.first_step {
+        Some((first, _)) => format!("{} {}, …", icon(Icon::Record), first.label_with(layout)),
+        None => format!("{} Press key...", icon(Icon::Record)),
+    }
+}
+
+/// Store changed bindings in the settings file.
+fn save_bindings(world: &mut World, bindings: &crate::keybind::KeyBindings) {
+    let Some(mut settings) = world.get_resource_mut::<crate::config::WorkbenchSettings>() else {
+        return;
+    };
+    settings.keybindings = bindings.clone();
+    let settings = world.resource::<crate::config::WorkbenchSettings>();
+    if let Some(path) = world.get_resource::<crate::config::ConfigPath>() {
+        settings.save(&path.0);
+    }
+}
+
+/// The key, mouse button or scroll gesture recorded this frame. Left and right clicks are left to
+/// the UI, and keys are recorded by character when bindings match logical keys.
+fn find_just_pressed_trigger(input: &KeyInput) -> Option<BindTrigger> {
+    if let Some(key) = find_just_pressed_key(input.keys) {
+        let key = input
+            .logical_layout()
+            .map_or(key, |layout| layout.logical(key));
+        return Some(BindTrigger::Key(key));
+    }
+    let button = input.mouse.and_then(|mouse| {
+        mouse
+            .get_just_pressed()
+            .find(|button| !matches!(button, MouseButton::Left | MouseButton::Right))
+            .copied()
+    });
+    if let Some(button) = button {
+        return Some(BindTrigger::Mouse(button));
+    }
+    input
+        .wheel
+        .and_then(|wheel| wheel.notch())
+        .map(BindTrigger::Wheel)
+}
+
 fn find_just_pressed_key(input: &ButtonInput<KeyCode>) -> Option<KeyCode> {
     let non_modifier_keys = [
         KeyCode::KeyA,
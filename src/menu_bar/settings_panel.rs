@@ -7,13 +7,20 @@
 //! ## 模块概述
 //!
 //! Defines the settings tab that `bevy_workbench` embeds in its menu system. It stores
-//! the editable UI-facing copies of workbench settings and renders the controls used to tweak
-//! themes, scale, locale, font choice, and application-provided custom sections.
+//! the editable UI-facing copies of workbench settings and renders them in tabbed sections
+//! (Appearance, Input, Console, Game View, Advanced). Edits can be applied to the running
+//! editor without touching the settings file, saved, or reverted to the last saved values;
+//! fields and tabs that differ from the saved settings are marked with a dot.
 //!
-//! 定义了 `bevy_workbench` 菜单系统里的设置面板。它保存面向 UI 的可编辑设置副本，
-//! 并渲染用于调整主题、缩放、语言、字体选择以及应用侧自定义设置区块的控件。
+//! 定义了 `bevy_workbench` 菜单系统里的设置面板。它保存面向 UI 的可编辑设置副本，并按标签页
+//! 分区（外观、输入、控制台、游戏视图、高级）渲染。修改可以只应用到运行中的编辑器而不写入
+//! 设置文件，也可以保存，或还原为上次保存的值；与已保存设置不同的字段和标签页会以圆点标记。
 
+use crate::config::{SettingsRegistry, WorkbenchSettings};
 use crate::dock::WorkbenchPanel;
+use std::collections::BTreeMap;
+
+mod sections;
 
 /// Settings panel — displayed as a tab in the tile layout.
 /// A custom section to inject into the Settings panel.
@@ -27,6 +34,16 @@ pub struct SettingsSection {
 pub struct SettingsPanel {
     /// Edited scale value (not yet saved).
     pub edited_scale: f32,
+    /// Edited "follow monitor DPI" flag.
+    pub edited_dpi_aware: bool,
+    /// Edited compact toolbar flag.
+    pub edited_compact_toolbar: bool,
+    /// Edited window title format.
+    pub edited_window_title: String,
+    /// Edited per-window scale overrides.
+    pub edited_window_scales: BTreeMap<String, f32>,
+    /// Keys of the currently open workbench windows (refreshed every frame).
+    pub(crate) open_windows: Vec<String>,
     /// Edited edit-mode theme.
     pub edited_edit_theme: crate::theme::ThemePreset,
     /// Edited play-mode theme.
@@ -35,12 +52,46 @@ pub struct SettingsPanel {
     pub edited_edit_brightness: f32,
     /// Edited play-mode brightness.
     pub edited_play_brightness: f32,
+    /// Edited theme for the OS dark preference.
+    pub edited_system_dark_theme: crate::theme::ThemePreset,
+    /// Edited theme for the OS light preference.
+    pub edited_system_light_theme: crate::theme::ThemePreset,
+    /// Edited theme cross-fade duration in seconds.
+    pub edited_theme_transition: f32,
+    /// Edited reduced-motion flag.
+    pub edited_reduced_motion: bool,
+    /// Edited semantic color palette.
+    pub edited_palette: crate::theme::PalettePreset,
+    /// Edited "separate layout undo history" flag.
+    pub edited_separate_layout_undo: bool,
+    /// Edited accessibility configuration.
+    pub edited_accessibility: crate::accessibility::AccessibilityConfig,
     /// Edited interface language.
     pub edited_locale: crate::i18n::Locale,
+    /// Edited behavior of the game while the editor is unfocused.
+    pub edited_unfocused_behavior: crate::mode::UnfocusedBehavior,
     /// Edited custom font path (None = use embedded).
     pub edited_font_path: Option<String>,
+    /// Edited maximum number of console entries.
+    pub edited_max_log_entries: usize,
+    /// Edited maximum console memory, in bytes.
+    pub edited_max_log_bytes: usize,
+    /// Edited editor camera sensitivities.
+    #[cfg(feature = "game-view")]
+    pub edited_editor_camera: crate::editor_camera::EditorCameraSettings,
+    /// Set to true when user clicks Apply (runtime only, the settings file is left alone).
+    pub apply_requested: bool,
     /// Set to true when user clicks Save.
     pub save_requested: bool,
+    /// Set to true when user clicks Revert (back to the last saved settings).
+    pub revert_requested: bool,
+    /// Settings as last saved, for dirty markers and Revert.
+    pub(crate) saved: WorkbenchSettings,
+    /// Section currently shown.
+    pub(crate) tab: SettingsTab,
+    /// Set when the font button is clicked; opens a file dialog next frame.
+    #[cfg(feature = "file-dialogs")]
+    pub(crate) font_pick_requested: bool,
     /// Custom settings sections injected by downstream applications.
     pub custom_sections: Vec<SettingsSection>,
 }
@@ -49,18 +100,181 @@ impl Default for SettingsPanel {
     fn default() -> Self {
         Self {
             edited_scale: 1.0,
+            edited_dpi_aware: true,
+            edited_compact_toolbar: false,
+            edited_window_title: String::new(),
+            edited_window_scales: BTreeMap::new(),
+            open_windows: Vec::new(),
             edited_edit_theme: crate::theme::ThemePreset::default(),
             edited_play_theme: crate::theme::ThemePreset::Rerun,
             edited_edit_brightness: 1.0,
             edited_play_brightness: 0.6,
+            edited_palette: crate::theme::PalettePreset::Default,
+            edited_theme_transition: 0.0,
+            edited_reduced_motion: false,
+            edited_system_dark_theme: crate::theme::ThemePreset::Rerun,
+            edited_system_light_theme: crate::theme::ThemePreset::EguiLight,
+            edited_separate_layout_undo: true,
+            edited_accessibility: crate::accessibility::AccessibilityConfig::default(),
             edited_locale: crate::i18n::Locale::default(),
+            edited_unfocused_behavior: crate::mode::UnfocusedBehavior::default(),
             edited_font_path: None,
+            edited_max_log_entries: crate::console::ConsoleConfig::default().max_entries,
+            edited_max_log_bytes: crate::console::ConsoleConfig::default().max_bytes,
+            #[cfg(feature = "game-view")]
+            edited_editor_camera: crate::editor_camera::EditorCameraSettings::default(),
+            apply_requested: false,
             save_requested: false,
+            revert_requested: false,
+            saved: WorkbenchSettings::default(),
+            tab: SettingsTab::default(),
+            #[cfg(feature = "file-dialogs")]
+            font_pick_requested: false,
             custom_sections: Vec::new(),
         }
     }
 }
 
+impl SettingsPanel {
+    /// Panel editing `settings`, which are taken as the saved state.
+    pub(crate) fn from_settings(settings: &WorkbenchSettings) -> Self {
+        let mut panel = Self {
+            saved: settings.clone(),
+            ..Default::default()
+        };
+        panel.load_edits(settings);
+        panel
+    }
+
+    /// Reset the edited values to those of `settings`.
+    pub(crate) fn load_edits(&mut self, settings: &WorkbenchSettings) {
+        self.edited_scale = settings.ui_scale;
+        self.edited_dpi_aware = settings.dpi_aware;
+        self.edited_compact_toolbar = settings.compact_toolbar;
+        self.edited_window_title = settings.window_title.clone();
+        self.edited_window_scales = settings.window_scales.clone();
+        self.edited_edit_theme = settings.theme.edit_theme;
+        self.edited_play_theme = settings.theme.play_theme;
+        self.edited_edit_brightness = settings.theme.edit_brightness;
+        self.edited_play_brightness = settings.theme.play_brightness;
+        self.edited_palette = settings.theme.palette;
+        self.edited_theme_transition = settings.theme.transition_duration;
+        self.edited_reduced_motion = settings.theme.reduced_motion;
+        self.edited_system_dark_theme = settings.theme.system_dark_theme;
+        self.edited_system_light_theme = settings.theme.system_light_theme;
+        self.edited_separate_layout_undo = settings.separate_layout_undo;
+        self.edited_accessibility = settings.accessibility.clone();
+        self.edited_locale = settings.locale;
+        self.edited_unfocused_behavior = settings.unfocused_behavior;
+        self.edited_font_path = settings.font.custom_font_path.clone();
+        self.edited_max_log_entries = settings.console.max_entries;
+        self.edited_max_log_bytes = settings.console.max_bytes;
+        #[cfg(feature = "game-view")]
+        {
+            self.edited_editor_camera = settings.editor_camera.clone();
+        }
+    }
+
+    /// Write the edited values into `settings`. Settings the panel doesn't edit are kept.
+    pub(crate) fn write_edits(&self, settings: &mut WorkbenchSettings) {
+        settings.ui_scale = self.edited_scale;
+        settings.dpi_aware = self.edited_dpi_aware;
+        settings.compact_toolbar = self.edited_compact_toolbar;
+        settings.window_title = self.edited_window_title.clone();
+        settings.window_scales = self.edited_window_scales.clone();
+        settings.theme.edit_theme = self.edited_edit_theme;
+        settings.theme.play_theme = self.edited_play_theme;
+        settings.theme.edit_brightness = self.edited_edit_brightness;
+        settings.theme.play_brightness = self.edited_play_brightness;
+        settings.theme.palette = self.edited_palette;
+        settings.theme.transition_duration = self.edited_theme_transition;
+        settings.theme.reduced_motion = self.edited_reduced_motion;
+        settings.theme.system_dark_theme = self.edited_system_dark_theme;
+        settings.theme.system_light_theme = self.edited_system_light_theme;
+        settings.separate_layout_undo = self.edited_separate_layout_undo;
+        settings.accessibility = self.edited_accessibility.clone();
+        settings.locale = self.edited_locale;
+        settings.unfocused_behavior = self.edited_unfocused_behavior;
+        settings.font.custom_font_path = self.edited_font_path.clone();
+        settings.console.max_entries = self.edited_max_log_entries;
+        settings.console.max_bytes = self.edited_max_log_bytes;
+        #[cfg(feature = "game-view")]
+        {
+            settings.editor_camera = self.edited_editor_camera.clone();
+        }
+    }
+
+    /// Whether any field of `tab` differs from the saved settings.
+    fn tab_dirty(&self, tab: SettingsTab) -> bool {
+        let saved = &self.saved;
+        match tab {
+            SettingsTab::Appearance => {
+                self.edited_scale != saved.ui_scale
+                    || self.edited_dpi_aware != saved.dpi_aware
+                    || self.edited_compact_toolbar != saved.compact_toolbar
+                    || self.edited_window_title != saved.window_title
+                    || self.edited_window_scales != saved.window_scales
+                    || self.edited_edit_theme != saved.theme.edit_theme
+                    || self.edited_play_theme != saved.theme.play_theme
+                    || self.edited_edit_brightness != saved.theme.edit_brightness
+                    || self.edited_play_brightness != saved.theme.play_brightness
+                    || self.edited_palette != saved.theme.palette
+                    || self.edited_theme_transition != saved.theme.transition_duration
+                    || self.edited_reduced_motion != saved.theme.reduced_motion
+                    || self.edited_system_dark_theme != saved.theme.system_dark_theme
+                    || self.edited_system_light_theme != saved.theme.system_light_theme
+                    || self.edited_locale != saved.locale
+                    || self.edited_font_path != saved.font.custom_font_path
+            }
+            SettingsTab::Input => {
+                #[cfg(feature = "game-view")]
+                if self.edited_editor_camera != saved.editor_camera {
+                    return true;
+                }
+                self.edited_accessibility != saved.accessibility
+                    || self.edited_separate_layout_undo != saved.separate_layout_undo
+            }
+            SettingsTab::Console => {
+                self.edited_max_log_entries != saved.console.max_entries
+                    || self.edited_max_log_bytes != saved.console.max_bytes
+            }
+            SettingsTab::GameView => self.edited_unfocused_behavior != saved.unfocused_behavior,
+            SettingsTab::Advanced => false,
+        }
+    }
+}
+
+/// Sections of the Settings panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum SettingsTab {
+    #[default]
+    Appearance,
+    Input,
+    Console,
+    GameView,
+    Advanced,
+}
+
+impl SettingsTab {
+    const ALL: [SettingsTab; 5] = [
+        SettingsTab::Appearance,
+        SettingsTab::Input,
+        SettingsTab::Console,
+        SettingsTab::GameView,
+        SettingsTab::Advanced,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            SettingsTab::Appearance => "Appearance",
+            SettingsTab::Input => "Input",
+            SettingsTab::Console => "Console",
+            SettingsTab::GameView => "Game View",
+            SettingsTab::Advanced => "Advanced",
+        }
+    }
+}
+
 impl WorkbenchPanel for SettingsPanel {
     fn id(&self) -> &str {
         "settings"
@@ -70,12 +284,22 @@ impl WorkbenchPanel for SettingsPanel {
         "Settings".to_string()
     }
 
-    fn ui(&mut self, ui: &mut egui::Ui) {
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut bevy::prelude::World) {
+        let mut registry = world.remove_resource::<SettingsRegistry>();
         egui::Frame::NONE
             .inner_margin(egui::Margin::same(8))
             .show(ui, |ui| {
-                settings_panel_ui(self, ui);
+                settings_panel_ui(self, ui, registry.as_mut());
             });
+        if let Some(registry) = registry {
+            world.insert_resource(registry);
+        }
+    }
+
+    fn needs_world(&self) -> bool {
+        true
     }
 
     fn default_visible(&self) -> bool {
@@ -83,81 +307,115 @@ impl WorkbenchPanel for SettingsPanel {
     }
 }
 
-fn settings_panel_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+fn settings_panel_ui(
+    panel: &mut SettingsPanel,
+    ui: &mut egui::Ui,
+    mut registry: Option<&mut SettingsRegistry>,
+) {
+    let tools_dirty = registry.as_deref().is_some_and(SettingsRegistry::is_dirty);
+    let dirty = SettingsTab::ALL
+        .map(|tab| panel.tab_dirty(tab) || (tab == SettingsTab::Advanced && tools_dirty));
     ui.heading("Editor Settings");
+    ui.horizontal(|ui| {
+        for (tab, dirty) in SettingsTab::ALL.into_iter().zip(dirty) {
+            let label = dirty_text(ui, tab.label(), dirty);
+            ui.selectable_value(&mut panel.tab, tab, label);
+        }
+    });
     ui.separator();
 
-    egui::Grid::new("settings_grid")
-        .num_columns(2)
-        .spacing([12.0, 6.0])
-        .show(ui, |ui| {
-            ui.label("UI Scale:");
-            ui.add(egui::Slider::new(&mut panel.edited_scale, 0.5..=2.0).step_by(0.25));
-            ui.end_row();
-
-            ui.label("Edit Theme:");
-            egui::ComboBox::from_id_salt("edit_theme")
-                .selected_text(panel.edited_edit_theme.label())
-                .show_ui(ui, |ui| {
-                    for preset in crate::theme::ThemePreset::ALL {
-                        ui.selectable_value(&mut panel.edited_edit_theme, *preset, preset.label());
-                    }
-                });
-            ui.end_row();
-
-            ui.label("Edit Brightness:");
-            ui.add(egui::Slider::new(&mut panel.edited_edit_brightness, 0.2..=1.0).step_by(0.05));
-            ui.end_row();
-
-            ui.label("Play Theme:");
-            egui::ComboBox::from_id_salt("play_theme")
-                .selected_text(panel.edited_play_theme.label())
-                .show_ui(ui, |ui| {
-                    for preset in crate::theme::ThemePreset::ALL {
-                        ui.selectable_value(&mut panel.edited_play_theme, *preset, preset.label());
-                    }
-                });
-            ui.end_row();
-
-            ui.label("Play Brightness:");
-            ui.add(egui::Slider::new(&mut panel.edited_play_brightness, 0.2..=1.0).step_by(0.05));
-            ui.end_row();
-
-            ui.label("Language:");
-            egui::ComboBox::from_id_salt("locale")
-                .selected_text(panel.edited_locale.label())
-                .show_ui(ui, |ui| {
-                    for locale in crate::i18n::Locale::ALL {
-                        ui.selectable_value(&mut panel.edited_locale, *locale, locale.label());
-                    }
-                });
-            ui.end_row();
-
-            ui.label("Custom Font:");
-            let display = panel.edited_font_path.as_deref().unwrap_or("(embedded)");
-            #[cfg(not(target_arch = "wasm32"))]
-            if ui.button(display).clicked()
-                && let Some(path) = rfd::FileDialog::new()
-                    .add_filter("Font", &["otf", "ttf", "ttc"])
-                    .pick_file()
-            {
-                panel.edited_font_path = Some(path.display().to_string());
-            }
-            #[cfg(target_arch = "wasm32")]
-            {
-                ui.label(display);
-            }
-            ui.end_row();
-        });
+    match panel.tab {
+        SettingsTab::Appearance => sections::appearance_ui(panel, ui),
+        SettingsTab::Input => sections::input_ui(panel, ui),
+        SettingsTab::Console => sections::console_ui(panel, ui),
+        SettingsTab::GameView => sections::game_view_ui(panel, ui),
+        SettingsTab::Advanced => sections::advanced_ui(panel, ui, registry.as_deref_mut()),
+    }
 
     ui.separator();
-    if ui.button("Save").clicked() {
-        panel.save_requested = true;
+    let dirty = dirty.contains(&true);
+    ui.horizontal(|ui| {
+        if ui
+            .button("Apply")
+            .on_hover_text("Use these settings now without saving them")
+            .clicked()
+        {
+            panel.apply_requested = true;
+        }
+        if ui.button("Save").clicked() {
+            panel.save_requested = true;
+        }
+        if ui
+            .add_enabled(dirty, egui::Button::new("Revert"))
+            .on_hover_text("Go back to the last saved settings")
+            .clicked()
+        {
+            panel.revert_requested = true;
+        }
+    });
+}
+
+/// `text`, marked with a dot when `dirty` (differs from the saved settings).
+fn dirty_text(ui: &egui::Ui, text: &str, dirty: bool) -> egui::RichText {
+    if dirty {
+        egui::RichText::new(format!("{text} ●")).color(crate::theme::palette(ui.ctx()).warn)
+    } else {
+        egui::RichText::new(text)
     }
+}
+
+/// Grid label of a field, marked when it differs from the saved settings.
+fn field_label(ui: &mut egui::Ui, text: &str, dirty: bool) {
+    let text = dirty_text(ui, text, dirty);
+    let response = ui.label(text);
+    if dirty {
+        response.on_hover_text("Changed since the last save");
+    }
+}
 
-    for section in &mut panel.custom_sections {
-        ui.separator();
-        ui.heading(&section.label);
-        (section.ui_fn)(ui);
+/// Grid rows of per-window scale overrides, plus a menu to add one for an open window.
+fn window_scales_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    let mut remove = None;
+    for (window, scale) in &mut panel.edited_window_scales {
+        let dirty = panel.saved.window_scales.get(window) != Some(&*scale);
+        field_label(ui, &format!("Scale ({window}):"), dirty);
+        ui.horizontal(|ui| {
+            ui.add(egui::Slider::new(scale, 0.5..=2.0).step_by(0.25));
+            if ui
+                .small_button("×")
+                .on_hover_text("Remove override")
+                .clicked()
+            {
+                remove = Some(window.clone());
+            }
+        });
+        ui.end_row();
+    }
+    if let Some(window) = remove {
+        panel.edited_window_scales.remove(&window);
+    }
+    let missing: Vec<&String> = panel
+        .open_windows
+        .iter()
+        .filter(|w| !panel.edited_window_scales.contains_key(*w))
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    ui.label("");
+    let mut added = None;
+    ui.menu_button("+ Window Scale", |ui| {
+        for window in missing {
+            if ui.button(window).clicked() {
+                added = Some(window.clone());
+                ui.close();
+            }
+        }
+    });
+    ui.end_row();
+    if let Some(window) = added {
+        panel
+            .edited_window_scales
+            .insert(window, panel.edited_scale);
     }
 }
@@ -0,0 +1,361 @@
+//! # sections.rs
+//!
+//! # sections.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The tabs of the Settings panel, one function per section. Each draws a grid of the panel's
+//! edited values, with labels marked when a value differs from the saved settings. The
+//! Advanced tab hosts the custom sections and tool settings registered by the application.
+//!
+//! 设置面板的各个标签页，每个分区对应一个函数。每个函数都以网格绘制面板中正在编辑的值，当某个值
+//! 与已保存设置不同时会标记其标签。高级标签页承载应用注册的自定义设置区块与工具设置。
+
+use super::{SettingsPanel, field_label, window_scales_ui};
+use crate::config::SettingsRegistry;
+
+/// Bytes per MiB, for the console memory limit.
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Two-column grid of labeled fields.
+fn grid(id: &str, ui: &mut egui::Ui, add_contents: impl FnOnce(&mut egui::Ui)) {
+    egui::Grid::new(id)
+        .num_columns(2)
+        .spacing([12.0, 6.0])
+        .show(ui, add_contents);
+}
+
+/// Scale, themes, palette, language and font.
+pub(super) fn appearance_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    grid("settings_appearance", ui, |ui| {
+        let saved = &panel.saved;
+        field_label(ui, "UI Scale:", panel.edited_scale != saved.ui_scale);
+        ui.add(egui::Slider::new(&mut panel.edited_scale, 0.5..=2.0).step_by(0.25));
+        ui.end_row();
+
+        field_label(
+            ui,
+            "Follow Monitor DPI:",
+            panel.edited_dpi_aware != saved.dpi_aware,
+        );
+        ui.checkbox(&mut panel.edited_dpi_aware, "")
+            .on_hover_text("Multiply the UI scale by each monitor's DPI scale");
+        ui.end_row();
+
+        field_label(
+            ui,
+            "Compact Toolbar:",
+            panel.edited_compact_toolbar != saved.compact_toolbar,
+        );
+        ui.checkbox(&mut panel.edited_compact_toolbar, "")
+            .on_hover_text("Shorter toolbar with smaller Play/Pause/Stop buttons");
+        ui.end_row();
+
+        field_label(
+            ui,
+            "Window Title:",
+            panel.edited_window_title != saved.window_title,
+        );
+        ui.text_edit_singleline(&mut panel.edited_window_title)
+            .on_hover_text(
+                "{app}, {workspace} and {mode} are replaced; empty keeps the app's title",
+            );
+        ui.end_row();
+
+        window_scales_ui(panel, ui);
+        theme_rows_ui(panel, ui);
+
+        let saved = &panel.saved;
+        field_label(ui, "Language:", panel.edited_locale != saved.locale);
+        egui::ComboBox::from_id_salt("locale")
+            .selected_text(panel.edited_locale.label())
+            .show_ui(ui, |ui| {
+                for locale in crate::i18n::Locale::ALL {
+                    ui.selectable_value(&mut panel.edited_locale, *locale, locale.label());
+                }
+            });
+        ui.end_row();
+
+        field_label(
+            ui,
+            "Custom Font:",
+            panel.edited_font_path != saved.font.custom_font_path,
+        );
+        let display = panel.edited_font_path.as_deref().unwrap_or("(embedded)");
+        #[cfg(all(feature = "file-dialogs", not(target_arch = "wasm32")))]
+        if ui.button(display).clicked() {
+            panel.font_pick_requested = true;
+        }
+        #[cfg(not(all(feature = "file-dialogs", not(target_arch = "wasm32"))))]
+        {
+            ui.label(display);
+        }
+        ui.end_row();
+    });
+}
+
+/// Theme, brightness and palette rows.
+fn theme_rows_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    let saved = &panel.saved.theme;
+    field_label(
+        ui,
+        "Edit Theme:",
+        panel.edited_edit_theme != saved.edit_theme,
+    );
+    theme_combo_ui(ui, "edit_theme", &mut panel.edited_edit_theme, true);
+    ui.end_row();
+
+    field_label(
+        ui,
+        "Edit Brightness:",
+        panel.edited_edit_brightness != saved.edit_brightness,
+    );
+    ui.add(egui::Slider::new(&mut panel.edited_edit_brightness, 0.2..=1.0).step_by(0.05));
+    ui.end_row();
+
+    field_label(
+        ui,
+        "Play Theme:",
+        panel.edited_play_theme != saved.play_theme,
+    );
+    theme_combo_ui(ui, "play_theme", &mut panel.edited_play_theme, true);
+    ui.end_row();
+
+    field_label(
+        ui,
+        "Play Brightness:",
+        panel.edited_play_brightness != saved.play_brightness,
+    );
+    ui.add(egui::Slider::new(&mut panel.edited_play_brightness, 0.2..=1.0).step_by(0.05));
+    ui.end_row();
+
+    field_label(
+        ui,
+        "Theme Transition:",
+        panel.edited_theme_transition != saved.transition_duration,
+    );
+    ui.add(
+        egui::Slider::new(&mut panel.edited_theme_transition, 0.0..=1.0)
+            .step_by(0.05)
+            .suffix(" s"),
+    )
+    .on_hover_text("Cross-fade between the Edit and Play themes (0 = instant)");
+    ui.end_row();
+
+    field_label(
+        ui,
+        "Reduced Motion:",
+        panel.edited_reduced_motion != saved.reduced_motion,
+    );
+    ui.checkbox(&mut panel.edited_reduced_motion, "")
+        .on_hover_text("No shadows, animations or hover growth; lighter on weak GPUs");
+    ui.end_row();
+
+    let system = crate::theme::ThemePreset::System;
+    if panel.edited_edit_theme == system || panel.edited_play_theme == system {
+        field_label(
+            ui,
+            "System Dark Theme:",
+            panel.edited_system_dark_theme != saved.system_dark_theme,
+        );
+        theme_combo_ui(
+            ui,
+            "system_dark",
+            &mut panel.edited_system_dark_theme,
+            false,
+        );
+        ui.end_row();
+
+        field_label(
+            ui,
+            "System Light Theme:",
+            panel.edited_system_light_theme != saved.system_light_theme,
+        );
+        theme_combo_ui(
+            ui,
+            "system_light",
+            &mut panel.edited_system_light_theme,
+            false,
+        );
+        ui.end_row();
+    }
+
+    field_label(ui, "Color Palette:", panel.edited_palette != saved.palette);
+    egui::ComboBox::from_id_salt("palette")
+        .selected_text(panel.edited_palette.label())
+        .show_ui(ui, |ui| {
+            for preset in crate::theme::PalettePreset::ALL {
+                ui.selectable_value(&mut panel.edited_palette, *preset, preset.label());
+            }
+        });
+    ui.end_row();
+}
+
+/// Theme preset picker; `with_system` offers "Follow System".
+fn theme_combo_ui(
+    ui: &mut egui::Ui,
+    id: &str,
+    value: &mut crate::theme::ThemePreset,
+    with_system: bool,
+) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(value.label())
+        .show_ui(ui, |ui| {
+            for preset in crate::theme::ThemePreset::ALL {
+                if with_system || *preset != crate::theme::ThemePreset::System {
+                    ui.selectable_value(value, *preset, preset.label());
+                }
+            }
+        });
+}
+
+/// Keyboard focus outline, layout undo and editor camera sensitivities.
+pub(super) fn input_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    grid("settings_input", ui, |ui| {
+        let config = &mut panel.edited_accessibility;
+        field_label(ui, "Focus Outline:", *config != panel.saved.accessibility);
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut config.focus_outline, "")
+                .on_hover_text("Outline the widget with keyboard focus");
+            ui.add_enabled(
+                config.focus_outline,
+                egui::Slider::new(&mut config.focus_outline_width, 1.0..=4.0).step_by(0.5),
+            );
+        });
+        ui.end_row();
+
+        field_label(
+            ui,
+            "Separate Layout Undo:",
+            panel.edited_separate_layout_undo != panel.saved.separate_layout_undo,
+        );
+        ui.checkbox(&mut panel.edited_separate_layout_undo, "")
+            .on_hover_text("Undo panel layout changes with their own shortcut, not Undo");
+        ui.end_row();
+
+        #[cfg(feature = "game-view")]
+        camera_rows_ui(panel, ui);
+    });
+    ui.label(egui::RichText::new("Shortcuts are edited in Edit ▸ Keybindings…").weak());
+}
+
+/// Editor camera sensitivity rows.
+#[cfg(feature = "game-view")]
+fn camera_rows_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    let saved = &panel.saved.editor_camera;
+    let camera = &mut panel.edited_editor_camera;
+    let rows = [
+        (
+            "Camera Look:",
+            &mut camera.look_sensitivity,
+            saved.look_sensitivity,
+            0.0005..=0.02,
+        ),
+        (
+            "Camera Pan:",
+            &mut camera.pan_sensitivity,
+            saved.pan_sensitivity,
+            0.0002..=0.01,
+        ),
+        (
+            "Camera Zoom:",
+            &mut camera.zoom_sensitivity,
+            saved.zoom_sensitivity,
+            0.01..=0.5,
+        ),
+        (
+            "Fly Speed:",
+            &mut camera.fly_speed,
+            saved.fly_speed,
+            0.5..=50.0,
+        ),
+    ];
+    for (label, value, saved_value, range) in rows {
+        field_label(ui, label, *value != saved_value);
+        ui.add(egui::Slider::new(value, range).logarithmic(true));
+        ui.end_row();
+    }
+}
+
+/// Console buffer limits.
+pub(super) fn console_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    grid("settings_console", ui, |ui| {
+        let saved = &panel.saved.console;
+        field_label(
+            ui,
+            "Max Entries:",
+            panel.edited_max_log_entries != saved.max_entries,
+        );
+        ui.add(
+            egui::DragValue::new(&mut panel.edited_max_log_entries)
+                .range(100..=1_000_000)
+                .speed(100),
+        );
+        ui.end_row();
+
+        field_label(
+            ui,
+            "Max Memory:",
+            panel.edited_max_log_bytes != saved.max_bytes,
+        );
+        let mut mib = panel.edited_max_log_bytes as f64 / MIB;
+        let response = ui.add(
+            egui::DragValue::new(&mut mib)
+                .range(1.0..=1024.0)
+                .suffix(" MiB"),
+        );
+        if response.changed() {
+            panel.edited_max_log_bytes = (mib * MIB) as usize;
+        }
+        ui.end_row();
+    });
+}
+
+/// How the game runs alongside the editor.
+pub(super) fn game_view_ui(panel: &mut SettingsPanel, ui: &mut egui::Ui) {
+    grid("settings_game_view", ui, |ui| {
+        field_label(
+            ui,
+            "When Unfocused:",
+            panel.edited_unfocused_behavior != panel.saved.unfocused_behavior,
+        );
+        egui::ComboBox::from_id_salt("unfocused_behavior")
+            .selected_text(panel.edited_unfocused_behavior.label())
+            .show_ui(ui, |ui| {
+                for behavior in crate::mode::UnfocusedBehavior::ALL {
+                    ui.selectable_value(
+                        &mut panel.edited_unfocused_behavior,
+                        *behavior,
+                        behavior.label(),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("What the game does while the editor window is in the background");
+        ui.end_row();
+    });
+}
+
+/// Custom sections and tool settings registered by the application.
+pub(super) fn advanced_ui(
+    panel: &mut SettingsPanel,
+    ui: &mut egui::Ui,
+    registry: Option<&mut SettingsRegistry>,
+) {
+    let no_tools = registry.as_deref().is_none_or(SettingsRegistry::is_empty);
+    if panel.custom_sections.is_empty() && no_tools {
+        ui.label(egui::RichText::new("No additional settings").weak());
+    }
+    for (i, section) in panel.custom_sections.iter_mut().enumerate() {
+        if i > 0 {
+            ui.separator();
+        }
+        ui.heading(&section.label);
+        (section.ui_fn)(ui);
+    }
+    if let Some(registry) = registry {
+        registry.ui(ui);
+    }
+}
@@ -0,0 +1,267 @@
+//! # toolbar.rs
+//!
+//! # toolbar.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The Play/Pause/Stop toolbar under the menu bar. Undo and redo arrows sit on the left, the
+//! icon mode buttons and the `GameClock` elapsed time are centered on the bar, and icon toggles
+//! on the right switch the play-session options kept in `ModeController`: hiding panels,
+//! per-mode layouts, slow motion and muting audio. The clock scrubber joins them while paused.
+//! `WorkbenchSettings::compact_toolbar` shrinks the bar for small screens.
+//!
+//! 菜单栏下方的播放/暂停/停止工具栏。左侧是撤销与重做箭头，图标模式按钮与 `GameClock` 已运行时间在整条
+//! 工具栏上居中，右侧的图标开关切换保存在 `ModeController` 中的播放会话选项：隐藏面板、按模式布局、
+//! 慢动作与静音。暂停时还会显示时钟拖动条。`WorkbenchSettings::compact_toolbar` 可为小屏幕缩小工具栏。
+
+use super::hints::MenuHints;
+use super::undo_buttons;
+use crate::bench_ui::{Icon, icon};
+use crate::dock::TileLayoutState;
+use crate::mode::{EditorMode, ModeTransitionRequest};
+use crate::theme::gray;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+
+/// Height of the toolbar.
+const TOOLBAR_HEIGHT: f32 = 30.0;
+/// Height of the toolbar with `WorkbenchSettings::compact_toolbar`.
+const COMPACT_TOOLBAR_HEIGHT: f32 = 20.0;
+
+/// System that renders the Play/Pause/Stop toolbar.
+/// Only added when `WorkbenchConfig::show_toolbar` is `true`.
+pub fn toolbar_system(
+    mut contexts: EguiContexts,
+    current_mode: Res<State<EditorMode>>,
+    mut mode_requests: MessageWriter<ModeTransitionRequest>,
+    i18n: Res<crate::i18n::I18n>,
+    mut controller: ResMut<crate::mode::ModeController>,
+    mut settings: ResMut<crate::config::WorkbenchSettings>,
+    config_path: Res<crate::config::ConfigPath>,
+    mut tile_state: ResMut<TileLayoutState>,
+    mut clock: ResMut<crate::mode::GameClock>,
+    mut scrub: ResMut<crate::scrub::ScrubState>,
+    mut undo_stack: ResMut<crate::undo::UndoStack>,
+    bindings: Option<Res<crate::keybind::KeyBindings>>,
+    layout: Option<Res<crate::keybind::KeyLayout>>,
+) {
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    let hints = MenuHints::new(bindings.as_deref(), layout.as_deref());
+
+    let compact = settings.compact_toolbar;
+    let mut hide_panels = controller.hide_panels_on_play;
+    let mut per_mode = controller.per_mode_layouts;
+    #[cfg(feature = "audio")]
+    let mut mute_audio = controller.mute_audio;
+    let mut slow_motion = controller.slow_motion;
+    let height = if compact {
+        COMPACT_TOOLBAR_HEIGHT
+    } else {
+        TOOLBAR_HEIGHT
+    };
+    egui::TopBottomPanel::top("workbench_toolbar")
+        .exact_height(height)
+        .show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                if compact {
+                    ui.spacing_mut().button_padding.y = 0.0;
+                }
+                undo_buttons::undo_redo_buttons_ui(ui, &mut undo_stack, &hints, &i18n);
+                let mode = *current_mode.get();
+                let elapsed = clock.elapsed;
+                toolbar_buttons_ui(
+                    ui,
+                    mode,
+                    &mut mode_requests,
+                    &i18n,
+                    &hints,
+                    compact,
+                    elapsed,
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    icon_toggle_ui(
+                        ui,
+                        &mut hide_panels,
+                        icon(Icon::HidePanels),
+                        i18n.t("toolbar-hide-panels"),
+                    );
+                    icon_toggle_ui(
+                        ui,
+                        &mut per_mode,
+                        icon(Icon::Layout),
+                        i18n.t("toolbar-per-mode-layouts"),
+                    );
+                    icon_toggle_ui(
+                        ui,
+                        &mut slow_motion,
+                        icon(Icon::SlowMotion),
+                        i18n.t("toolbar-slow-motion"),
+                    );
+                    #[cfg(feature = "audio")]
+                    icon_toggle_ui(
+                        ui,
+                        &mut mute_audio,
+                        icon(Icon::Mute),
+                        i18n.t("toolbar-mute-audio"),
+                    );
+                    if *current_mode.get() == EditorMode::Pause {
+                        crate::scrub::scrub_bar_ui(
+                            ui,
+                            &mut clock,
+                            &mut scrub,
+                            i18n.t("toolbar-scrub"),
+                        );
+                    }
+                });
+            });
+        });
+
+    if hide_panels != controller.hide_panels_on_play {
+        controller.hide_panels_on_play = hide_panels;
+        settings.hide_panels_on_play = hide_panels;
+        settings.save(&config_path.0);
+        // Apply immediately when toggled mid-session.
+        match (hide_panels, current_mode.get()) {
+            (true, EditorMode::Play | EditorMode::Pause) => tile_state.enter_play_layout(),
+            (false, _) => tile_state.exit_play_layout(),
+            _ => {}
+        }
+    }
+    #[cfg(feature = "audio")]
+    if mute_audio != controller.mute_audio {
+        controller.mute_audio = mute_audio;
+    }
+    if slow_motion != controller.slow_motion {
+        controller.slow_motion = slow_motion;
+    }
+    if per_mode != controller.per_mode_layouts {
+        // Takes effect from the next mode transition.
+        controller.per_mode_layouts = per_mode;
+        settings.per_mode_layouts = per_mode;
+        settings.save(&config_path.0);
+    }
+}
+
+/// Icon-only toggle, named by its tooltip for screen readers.
+fn icon_toggle_ui(ui: &mut egui::Ui, value: &mut bool, icon: &str, label: &str) {
+    let response = ui.toggle_value(value, icon);
+    let selected = *value;
+    response.widget_info(|| {
+        egui::WidgetInfo::selected(egui::WidgetType::SelectableLabel, true, selected, label)
+    });
+    response.on_hover_text(label);
+}
+
+/// Width reserved for the game clock next to the mode buttons.
+const CLOCK_WIDTH: f32 = 72.0;
+
+/// Play/Pause/Stop icon buttons and the game clock, centered on the bar.
+fn toolbar_buttons_ui(
+    ui: &mut egui::Ui,
+    mode: EditorMode,
+    mode_requests: &mut MessageWriter<ModeTransitionRequest>,
+    i18n: &crate::i18n::I18n,
+    hints: &MenuHints,
+    compact: bool,
+    elapsed: f32,
+) {
+    let size = if compact {
+        egui::vec2(24.0, 16.0)
+    } else {
+        egui::vec2(32.0, 22.0)
+    };
+    let spacing = ui.spacing().item_spacing.x;
+    let total = size.x * 3.0 + spacing * 3.0 + CLOCK_WIDTH;
+    // Center on the whole bar, whatever sits to the left.
+    let pad = (ui.max_rect().center().x - total / 2.0 - ui.cursor().min.x).max(0.0);
+    ui.add_space(pad);
+
+    let running = mode != EditorMode::Edit;
+    let play_label = match mode {
+        EditorMode::Pause => i18n.t("toolbar-resume"),
+        _ => i18n.t("toolbar-play"),
+    };
+    let buttons = [
+        (Icon::Play, play_label, true, mode == EditorMode::Play),
+        (
+            Icon::Pause,
+            i18n.t("toolbar-pause"),
+            running,
+            mode == EditorMode::Pause,
+        ),
+        (Icon::Stop, i18n.t("toolbar-stop"), running, false),
+    ];
+    for (icon, label, enabled, active) in buttons {
+        let hint = match icon {
+            Icon::Pause => hints.pause_resume.as_deref(),
+            _ => hints.play_stop.as_deref(),
+        };
+        if mode_button_ui(ui, icon, label, hint, size, enabled, active).clicked() {
+            let target = match (icon, mode) {
+                (Icon::Stop, _) => EditorMode::Edit,
+                (Icon::Pause, EditorMode::Play) => EditorMode::Pause,
+                _ => EditorMode::Play,
+            };
+            if target != mode {
+                mode_requests.write(ModeTransitionRequest::new(target));
+            }
+        }
+    }
+
+    let clock = egui::RichText::new(format_clock(elapsed)).monospace();
+    let clock = if running { clock } else { clock.weak() };
+    ui.add_sized([CLOCK_WIDTH, size.y], egui::Label::new(clock))
+        .on_hover_text(i18n.t("toolbar-clock"));
+}
+
+/// One icon mode button, highlighted while its mode is `active` and named for screen readers.
+fn mode_button_ui(
+    ui: &mut egui::Ui,
+    icon: Icon,
+    label: &str,
+    hint: Option<&str>,
+    size: egui::Vec2,
+    enabled: bool,
+    active: bool,
+) -> egui::Response {
+    let mut button = egui::Button::new(icon.glyph())
+        .min_size(size)
+        .selected(active);
+    if !active {
+        button = button.fill(gray::S250);
+    }
+    let response = ui.add_enabled(enabled, button);
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, enabled, label));
+    let tooltip = match hint {
+        Some(hint) => format!("{label} ({hint})"),
+        None => label.to_string(),
+    };
+    response.on_hover_text(tooltip)
+}
+
+/// Game clock as `MM:SS.cc`.
+fn format_clock(elapsed: f32) -> String {
+    let centis = (elapsed.max(0.0) * 100.0) as u64;
+    format!(
+        "{:02}:{:02}.{:02}",
+        centis / 6000,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_formats_minutes_seconds_and_centiseconds() {
+        assert_eq!(format_clock(0.0), "00:00.00");
+        assert_eq!(format_clock(-3.0), "00:00.00");
+        assert_eq!(format_clock(61.25), "01:01.25");
+        assert_eq!(format_clock(3599.5), "59:59.50");
+        assert_eq!(format_clock(6000.0), "100:00.00");
+    }
+}
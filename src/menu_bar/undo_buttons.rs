@@ -0,0 +1,112 @@
+//! # undo_buttons.rs
+//!
+//! # undo_buttons.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Undo and redo buttons for the toolbar. Each arrow undoes or redoes one step, with a tooltip
+//! naming the action and its shortcut, and is followed by a small dropdown listing the last
+//! `HISTORY_DROPDOWN_LEN` actions in that direction. Clicking an entry jumps the stack past it
+//! through `UndoStack::jump_requested`, like the Undo History panel does.
+//!
+//! 工具栏上的撤销与重做按钮。每个箭头撤销或重做一步，提示中会显示操作名称及其快捷键；箭头后是
+//! 一个小下拉菜单，列出该方向上最近 `HISTORY_DROPDOWN_LEN` 个操作。点击条目会通过
+//! `UndoStack::jump_requested` 让撤销栈跳过该操作，与撤销历史面板的行为一致。
+
+use super::hints::MenuHints;
+use crate::bench_ui::{Icon, icon};
+use crate::i18n::I18n;
+use crate::undo::UndoStack;
+
+/// Number of actions listed in the undo and redo dropdowns.
+const HISTORY_DROPDOWN_LEN: usize = 10;
+
+/// Undo / redo arrows with their history dropdowns.
+pub(super) fn undo_redo_buttons_ui(
+    ui: &mut egui::Ui,
+    stack: &mut UndoStack,
+    hints: &MenuHints,
+    i18n: &I18n,
+) {
+    let undo = ui
+        .add_enabled(stack.can_undo(), egui::Button::new(icon(Icon::Undo)))
+        .on_hover_text(tooltip(
+            i18n.t("menu-edit-undo"),
+            stack.undo_description(),
+            hints.undo.as_deref(),
+        ));
+    if undo.clicked() {
+        stack.undo_requested = true;
+    }
+    ui.add_enabled_ui(stack.can_undo(), |ui| {
+        ui.menu_button(icon(Icon::CaretDown), |ui| undo_list_ui(ui, stack))
+            .response
+            .on_hover_text(i18n.t("toolbar-undo-history"));
+    });
+
+    let redo = ui
+        .add_enabled(stack.can_redo(), egui::Button::new(icon(Icon::Redo)))
+        .on_hover_text(tooltip(
+            i18n.t("menu-edit-redo"),
+            stack.redo_description(),
+            hints.redo.as_deref(),
+        ));
+    if redo.clicked() {
+        stack.redo_requested = true;
+    }
+    ui.add_enabled_ui(stack.can_redo(), |ui| {
+        ui.menu_button(icon(Icon::CaretDown), |ui| redo_list_ui(ui, stack))
+            .response
+            .on_hover_text(i18n.t("toolbar-redo-history"));
+    });
+}
+
+/// "Undo Move Entity (Ctrl+Z)".
+fn tooltip(verb: &str, description: Option<&str>, shortcut: Option<&str>) -> String {
+    let mut text = match description {
+        Some(description) => format!("{verb} {description}"),
+        None => verb.to_string(),
+    };
+    if let Some(shortcut) = shortcut {
+        text.push_str(&format!(" ({shortcut})"));
+    }
+    text
+}
+
+/// The last undo entries, newest first; clicking one undoes it and everything after it.
+fn undo_list_ui(ui: &mut egui::Ui, stack: &mut UndoStack) {
+    let entries: Vec<(usize, String)> = stack
+        .undo_history()
+        .into_iter()
+        .enumerate()
+        .rev()
+        .take(HISTORY_DROPDOWN_LEN)
+        .map(|(index, desc)| (index, desc.to_owned()))
+        .collect();
+    history_entries_ui(ui, stack, entries);
+}
+
+/// The next redo entries, next first; clicking one redoes everything up to it.
+fn redo_list_ui(ui: &mut egui::Ui, stack: &mut UndoStack) {
+    let current = stack.undo_count();
+    let entries: Vec<(usize, String)> = stack
+        .redo_history()
+        .into_iter()
+        .enumerate()
+        .take(HISTORY_DROPDOWN_LEN)
+        .map(|(i, desc)| (current + i + 1, desc.to_owned()))
+        .collect();
+    history_entries_ui(ui, stack, entries);
+}
+
+/// Rows of `(history index to jump to, description)`.
+fn history_entries_ui(ui: &mut egui::Ui, stack: &mut UndoStack, entries: Vec<(usize, String)>) {
+    for (target, desc) in entries {
+        if ui.button(desc).clicked() {
+            stack.jump_requested = Some(target);
+            ui.close();
+        }
+    }
+}
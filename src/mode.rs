@@ -1,7 +1,15 @@
 //! Editor mode state machine: Edit / Play / Pause.
 
+use bevy::ecs::entity_disabling::Disabled;
+use bevy::ecs::message::MessageCursor;
 use bevy::ecs::schedule::ScheduleLabel;
+use bevy::platform::collections::HashSet;
 use bevy::prelude::*;
+use bevy::window::WindowFocused;
+
+mod transition;
+
+pub use transition::{Decision, ModeGuard, ModeTransitionRequest, mode_transition_system};
 
 /// The current editor mode.
 #[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -15,6 +23,23 @@ pub enum EditorMode {
     Pause,
 }
 
+impl EditorMode {
+    /// Mode as shown in the window title: its localized name, with a play or pause sign.
+    pub fn title_label(&self, i18n: Option<&crate::i18n::I18n>) -> String {
+        let id = match self {
+            EditorMode::Edit => "mode-edit",
+            EditorMode::Play => "toolbar-play",
+            EditorMode::Pause => "toolbar-pause",
+        };
+        let name = i18n.map_or(id, |i18n| i18n.t(id));
+        match self {
+            EditorMode::Edit => name.to_owned(),
+            EditorMode::Play => format!("{name} ▶"),
+            EditorMode::Pause => format!("{name} ⏸"),
+        }
+    }
+}
+
 /// Schedule for game logic. Only runs during [`EditorMode::Play`].
 ///
 /// Users should add their game systems here instead of `Update`:
@@ -27,11 +52,119 @@ pub enum EditorMode {
 #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub struct GameSchedule;
 
+/// Marker for editor-only entities (debug helpers, preview rigs).
+///
+/// On Play they are disabled together with their descendants, so game systems in
+/// [`GameSchedule`] never see them and they are not rendered; Stop re-enables them.
+/// Unlike [`crate::inspector::WorkbenchInternal`], this does not affect the hierarchy.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct EditorOnly;
+
+/// Marks entities disabled by [`disable_editor_only_system`], so Stop only re-enables those and
+/// entities disabled before Play stay disabled.
+#[derive(Component)]
+pub(crate) struct DisabledForPlay;
+
 /// Resource controlling mode behavior.
 #[derive(Resource, Default)]
 pub struct ModeController {
     /// Whether to hide editor panels when entering Play mode.
+    /// The game view is maximized (or side panels hidden) and the
+    /// previous layout is restored on Stop.
     pub hide_panels_on_play: bool,
+    /// Whether each mode keeps its own dock layout, switched on mode transitions.
+    /// See [`TileLayoutState::set_mode_layout`](crate::dock::TileLayoutState::set_mode_layout).
+    pub per_mode_layouts: bool,
+    /// What [`GameSchedule`] does while no editor window has focus.
+    pub unfocused: UnfocusedBehavior,
+    /// Silence the game's audio during Play and Pause (`audio` feature); restored on Stop.
+    pub mute_audio: bool,
+    /// Run the game at [`ModeController::SLOW_MOTION_SCALE`] speed during Play.
+    pub slow_motion: bool,
+    /// Checked before every [`ModeTransitionRequest`]; see [`ModeController::add_guard`].
+    guards: Vec<ModeGuard>,
+}
+
+impl ModeController {
+    /// Relative game speed while [`ModeController::slow_motion`] is on.
+    pub const SLOW_MOTION_SCALE: f32 = 0.25;
+
+    /// Relative speed of the game ([`GameClock`] and the `Time` seen by [`GameSchedule`])
+    /// during a Play session. Editor systems keep running at normal speed.
+    pub fn time_scale(&self) -> f32 {
+        if self.slow_motion {
+            Self::SLOW_MOTION_SCALE
+        } else {
+            1.0
+        }
+    }
+}
+
+/// What happens to a running game while the editor is in the background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum UnfocusedBehavior {
+    /// Keep running [`GameSchedule`] every frame.
+    #[default]
+    KeepRunning,
+    /// Stop running [`GameSchedule`] and hold [`GameClock`] until a window regains focus.
+    Pause,
+    /// Run [`GameSchedule`] at most every [`UnfocusedBehavior::THROTTLE_INTERVAL`] seconds;
+    /// [`GameClock`] still advances by the full elapsed time.
+    Throttle,
+}
+
+impl UnfocusedBehavior {
+    /// All available behaviors.
+    pub const ALL: &[UnfocusedBehavior] = &[
+        UnfocusedBehavior::KeepRunning,
+        UnfocusedBehavior::Pause,
+        UnfocusedBehavior::Throttle,
+    ];
+
+    /// Real seconds between [`GameSchedule`] runs in [`UnfocusedBehavior::Throttle`].
+    pub const THROTTLE_INTERVAL: f32 = 0.1;
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            UnfocusedBehavior::KeepRunning => "Keep Running",
+            UnfocusedBehavior::Pause => "Pause",
+            UnfocusedBehavior::Throttle => "Throttle",
+        }
+    }
+}
+
+/// Window focus as seen by [`run_game_schedule_system`].
+#[derive(Default)]
+pub struct FocusTracker {
+    cursor: MessageCursor<WindowFocused>,
+    /// Windows that currently have focus; `None` until the first focus event.
+    focused: Option<HashSet<Entity>>,
+    /// Game time not yet handed to a throttled [`GameSchedule`] run.
+    throttled: f32,
+    /// Real (wall-clock) seconds since the last throttled run.
+    since_run: f32,
+}
+
+impl FocusTracker {
+    fn update(&mut self, world: &World) {
+        let Some(messages) = world.get_resource::<Messages<WindowFocused>>() else {
+            return;
+        };
+        for event in self.cursor.read(messages) {
+            let focused = self.focused.get_or_insert_default();
+            if event.focused {
+                focused.insert(event.window);
+            } else {
+                focused.remove(&event.window);
+            }
+        }
+    }
+
+    fn has_focus(&self) -> bool {
+        self.focused
+            .as_ref()
+            .is_none_or(|focused| !focused.is_empty())
+    }
 }
 
 /// Tracks elapsed time within the current game session.
@@ -42,6 +175,8 @@ pub struct GameClock {
     pub elapsed: f32,
     /// The previous editor mode (for distinguishing fresh Play vs Resume).
     pub(crate) previous_mode: EditorMode,
+    /// ID of the current (or last) Play session; 0 before the first one.
+    pub(crate) session_id: u64,
 }
 
 impl Default for GameClock {
@@ -49,27 +184,95 @@ impl Default for GameClock {
         Self {
             elapsed: 0.0,
             previous_mode: EditorMode::Edit,
+            session_id: 0,
         }
     }
 }
 
+/// Triggered on entering Play, both for a fresh Play and a Resume from Pause.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaySessionStarted {
+    /// Session number (1-based, increasing for the lifetime of the app).
+    pub session_id: u64,
+    /// `true` for a fresh Play from Edit, `false` for a Resume.
+    pub fresh: bool,
+}
+
+/// Triggered when a Play session returns to Edit (Stop).
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct PlaySessionEnded {
+    pub session_id: u64,
+    /// Game time played in the session, in seconds (excluding Pause).
+    pub duration: f32,
+}
+
+/// Context of the game's clock: `Time<GameTime>` advances by the frame time scaled with
+/// [`ModeController::time_scale`], only while [`GameSchedule`] runs. Systems in
+/// [`GameSchedule`] see it as `Res<Time>`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GameTime;
+
 /// Runs the [`GameSchedule`] when in [`EditorMode::Play`],
-/// advancing [`GameClock`] each frame.
-pub fn run_game_schedule_system(world: &mut World) {
+/// advancing [`GameClock`] and `Time<GameTime>` by the scaled frame time. While no window has
+/// focus, the schedule follows [`ModeController::unfocused`].
+pub fn run_game_schedule_system(world: &mut World, mut focus: Local<FocusTracker>) {
+    focus.update(world);
     let mode = world.resource::<State<EditorMode>>().get().to_owned();
-    if mode == EditorMode::Play {
-        let dt = world.resource::<Time>().delta_secs();
-        world.resource_mut::<GameClock>().elapsed += dt;
-        world.run_schedule(GameSchedule);
+    if mode != EditorMode::Play {
+        focus.throttled = 0.0;
+        focus.since_run = 0.0;
+        return;
     }
+    let scale = world.resource::<ModeController>().time_scale();
+    let dt = world.resource::<Time>().delta_secs() * scale;
+    // The interval is wall-clock time, independent of the time scale.
+    let real_dt = world.resource::<Time<Real>>().delta_secs();
+    let behavior = if focus.has_focus() {
+        UnfocusedBehavior::KeepRunning
+    } else {
+        world.resource::<ModeController>().unfocused
+    };
+    let dt = match behavior {
+        UnfocusedBehavior::KeepRunning => {
+            focus.since_run = 0.0;
+            dt + std::mem::take(&mut focus.throttled)
+        }
+        UnfocusedBehavior::Pause => return,
+        UnfocusedBehavior::Throttle => {
+            focus.throttled += dt;
+            focus.since_run += real_dt;
+            if focus.since_run < UnfocusedBehavior::THROTTLE_INTERVAL {
+                return;
+            }
+            focus.since_run = 0.0;
+            std::mem::take(&mut focus.throttled)
+        }
+    };
+    world.resource_mut::<GameClock>().elapsed += dt;
+    // Game systems read the scaled clock through `Res<Time>`; the editor's is put back after.
+    let game_time = {
+        let mut game_time = world.resource_mut::<Time<GameTime>>();
+        game_time.advance_by(std::time::Duration::from_secs_f32(dt));
+        game_time.as_generic()
+    };
+    let editor_time = std::mem::replace(&mut *world.resource_mut::<Time>(), game_time);
+    world.run_schedule(GameSchedule);
+    *world.resource_mut::<Time>() = editor_time;
 }
 
-/// Resets the [`GameClock`] when entering Play from Edit (not Resume from Pause).
-pub fn on_enter_play(mut clock: ResMut<GameClock>) {
-    if clock.previous_mode == EditorMode::Edit {
+/// Resets the [`GameClock`] when entering Play from Edit (not Resume from Pause) and triggers
+/// [`PlaySessionStarted`].
+pub fn on_enter_play(mut commands: Commands, mut clock: ResMut<GameClock>) {
+    let fresh = clock.previous_mode == EditorMode::Edit;
+    if fresh {
         clock.elapsed = 0.0;
+        clock.session_id += 1;
     }
     clock.previous_mode = EditorMode::Play;
+    commands.trigger(PlaySessionStarted {
+        session_id: clock.session_id,
+        fresh,
+    });
 }
 
 /// Tracks that we entered Pause.
@@ -77,11 +280,47 @@ pub fn on_enter_pause(mut clock: ResMut<GameClock>) {
     clock.previous_mode = EditorMode::Pause;
 }
 
-/// Tracks that we returned to Edit.
-pub fn on_enter_edit(mut clock: ResMut<GameClock>) {
+/// Tracks that we returned to Edit and triggers [`PlaySessionEnded`] after a session.
+pub fn on_enter_edit(mut commands: Commands, mut clock: ResMut<GameClock>) {
+    if clock.previous_mode != EditorMode::Edit {
+        commands.trigger(PlaySessionEnded {
+            session_id: clock.session_id,
+            duration: clock.elapsed,
+        });
+    }
     clock.previous_mode = EditorMode::Edit;
 }
 
+/// Disables [`EditorOnly`] entities and their descendants. Runs on entering Play and every
+/// frame outside Edit, so editor-only entities spawned mid-session are caught too.
+pub fn disable_editor_only_system(
+    mut commands: Commands,
+    roots: Query<Entity, With<EditorOnly>>,
+    children: Query<&Children, Allow<Disabled>>,
+    disabled: Query<(), With<Disabled>>,
+) {
+    for root in &roots {
+        for entity in std::iter::once(root).chain(children.iter_descendants(root)) {
+            // Descendants that were already disabled keep that state after Stop.
+            if !disabled.contains(entity) {
+                commands.entity(entity).insert((Disabled, DisabledForPlay));
+            }
+        }
+    }
+}
+
+/// Re-enables entities disabled by [`disable_editor_only_system`] when returning to Edit.
+pub fn enable_editor_only_system(
+    mut commands: Commands,
+    disabled: Query<Entity, (With<DisabledForPlay>, With<Disabled>)>,
+) {
+    for entity in &disabled {
+        commands
+            .entity(entity)
+            .remove::<(Disabled, DisabledForPlay)>();
+    }
+}
+
 /// Run condition: true only for fresh Play (from Edit), not Resume (from Pause).
 /// Use with `OnEnter(EditorMode::Play)` to gate game setup systems.
 pub fn on_fresh_play(clock: Res<GameClock>) -> bool {
@@ -90,9 +329,9 @@ pub fn on_fresh_play(clock: Res<GameClock>) -> bool {
 
 /// System that handles keyboard shortcuts for mode transitions.
 pub fn mode_input_system(
-    input: Res<ButtonInput<KeyCode>>,
+    input: super::keybind::BindingInput,
     current_mode: Res<State<EditorMode>>,
-    mut next_mode: ResMut<NextState<EditorMode>>,
+    mut requests: MessageWriter<ModeTransitionRequest>,
     bindings: Option<Res<super::keybind::KeyBindings>>,
 ) {
     let default_bindings = super::keybind::KeyBindings::default();
@@ -101,16 +340,18 @@ pub fn mode_input_system(
     // Play/Stop toggle
     if bindings.play_stop.just_pressed(&input) {
         match current_mode.get() {
-            EditorMode::Edit => next_mode.set(EditorMode::Play),
-            EditorMode::Play | EditorMode::Pause => next_mode.set(EditorMode::Edit),
+            EditorMode::Edit => requests.write(ModeTransitionRequest::new(EditorMode::Play)),
+            EditorMode::Play | EditorMode::Pause => {
+                requests.write(ModeTransitionRequest::new(EditorMode::Edit))
+            }
         }
     }
 
     // Pause/Resume
     if bindings.pause_resume.just_pressed(&input) {
         match current_mode.get() {
-            EditorMode::Play => next_mode.set(EditorMode::Pause),
-            EditorMode::Pause => next_mode.set(EditorMode::Play),
+            EditorMode::Play => requests.write(ModeTransitionRequest::new(EditorMode::Pause)),
+            EditorMode::Pause => requests.write(ModeTransitionRequest::new(EditorMode::Play)),
             _ => {}
         }
     }
@@ -0,0 +1,146 @@
+//! # transition.rs
+//!
+//! # transition.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Guarded mode transitions. The toolbar, the Play/Stop and Pause hotkeys and the game window
+//! send a `ModeTransitionRequest` instead of setting `NextState<EditorMode>` directly. Each
+//! request is checked against the guards registered on `ModeController`: the first guard that
+//! does not allow it can veto it, defer it to be checked again next frame, or ask the user to
+//! confirm (the transition then happens only on OK).
+//!
+//! 受守卫保护的模式切换。工具栏、播放/停止与暂停快捷键以及游戏窗口都会发送
+//! `ModeTransitionRequest`，而不是直接设置 `NextState<EditorMode>`。每个请求都会依次经过
+//! `ModeController` 上注册的守卫检查：第一个不允许该请求的守卫可以否决它、将其推迟到下一帧
+//! 再检查，或请求用户确认（只有点击确定后才会切换）。
+
+use bevy::ecs::message::MessageCursor;
+use bevy::prelude::*;
+
+use super::{EditorMode, ModeController};
+use crate::i18n::I18n;
+
+/// Asks for a switch to another editor mode, subject to the [`ModeController`] guards.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeTransitionRequest {
+    /// Target mode.
+    pub to: EditorMode,
+    /// Skip the guards (e.g. after the user confirmed).
+    pub force: bool,
+}
+
+impl ModeTransitionRequest {
+    /// A guarded request for `to`.
+    pub fn new(to: EditorMode) -> Self {
+        Self { to, force: false }
+    }
+
+    /// A request for `to` that bypasses the guards.
+    pub fn forced(to: EditorMode) -> Self {
+        Self { to, force: true }
+    }
+}
+
+/// A guard's answer to a mode transition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// Let the transition happen (or leave it to the next guard).
+    Allow,
+    /// Drop the request.
+    Veto,
+    /// Keep the request and check it again next frame, unless a newer request replaces it.
+    Defer,
+    /// Ask the user with this message; the transition happens if they confirm. Acts as
+    /// [`Decision::Veto`] when there is no `Dialogs` resource to ask with.
+    Confirm(String),
+}
+
+/// A mode transition guard: `(world, from, to) -> Decision`.
+pub type ModeGuard = Box<dyn Fn(&World, EditorMode, EditorMode) -> Decision + Send + Sync>;
+
+impl ModeController {
+    /// Register a guard consulted before every requested mode transition, in order of
+    /// registration. For example, to confirm stopping with unsaved changes:
+    ///
+    /// ```rust,ignore
+    /// controller.add_guard(|world, _from, to| {
+    ///     if to == EditorMode::Edit && world.resource::<Project>().dirty {
+    ///         Decision::Confirm("Unsaved changes will be lost. Stop anyway?".into())
+    ///     } else {
+    ///         Decision::Allow
+    ///     }
+    /// });
+    /// ```
+    pub fn add_guard(
+        &mut self,
+        guard: impl Fn(&World, EditorMode, EditorMode) -> Decision + Send + Sync + 'static,
+    ) {
+        self.guards.push(Box::new(guard));
+    }
+
+    /// The first non-`Allow` decision of the guards for `from -> to`.
+    pub fn decide(&self, world: &World, from: EditorMode, to: EditorMode) -> Decision {
+        self.guards
+            .iter()
+            .map(|guard| guard(world, from, to))
+            .find(|decision| *decision != Decision::Allow)
+            .unwrap_or(Decision::Allow)
+    }
+}
+
+/// Applies [`ModeTransitionRequest`]s that pass the guards. The newest request of a frame wins.
+pub fn mode_transition_system(
+    world: &mut World,
+    mut cursor: Local<MessageCursor<ModeTransitionRequest>>,
+    mut deferred: Local<Option<ModeTransitionRequest>>,
+) {
+    let newest = cursor
+        .read(world.resource::<Messages<ModeTransitionRequest>>())
+        .last()
+        .copied();
+    let Some(request) = newest.or_else(|| deferred.take()) else {
+        return;
+    };
+    *deferred = None;
+    let from = *world.resource::<State<EditorMode>>().get();
+    if request.to == from {
+        return;
+    }
+    let decision = if request.force {
+        Decision::Allow
+    } else {
+        world
+            .resource::<ModeController>()
+            .decide(world, from, request.to)
+    };
+    match decision {
+        Decision::Allow => world
+            .resource_mut::<NextState<EditorMode>>()
+            .set(request.to),
+        Decision::Veto => {}
+        Decision::Defer => *deferred = Some(request),
+        Decision::Confirm(message) => {
+            let title = world
+                .get_resource::<I18n>()
+                .map_or("dialog-confirm-mode-change", |i18n| {
+                    i18n.t("dialog-confirm-mode-change")
+                })
+                .to_owned();
+            let Some(mut dialogs) = world.get_resource_mut::<crate::dialogs::Dialogs>() else {
+                warn!(
+                    "Mode change to {:?} needs confirmation but there is no Dialogs resource; vetoed",
+                    request.to
+                );
+                return;
+            };
+            dialogs.confirm(title, message, move |world, ok| {
+                if ok {
+                    world.write_message(ModeTransitionRequest::forced(request.to));
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Plot panel: records numeric values from reflected component fields or user channels into
+//! ring buffers every frame and shows them as time-series plots.
+
+use bevy::prelude::*;
+use bevy::reflect::{GetPath, PartialReflect};
+use std::collections::{HashMap, VecDeque};
+
+use crate::WorkbenchApp;
+use crate::bench_ui::{self, PlotSeries};
+use crate::dock::{PanelSlot, WorkbenchPanel};
+use crate::inspector::InspectorSelection;
+
+/// Default number of samples kept per trace.
+const DEFAULT_CAPACITY: usize = 2000;
+/// Colors assigned to new traces in turn.
+const PALETTE: [egui::Color32; 6] = [
+    egui::Color32::from_rgb(0x4e, 0xa8, 0xde),
+    egui::Color32::from_rgb(0xe0, 0x7a, 0x3a),
+    egui::Color32::from_rgb(0x6c, 0xc6, 0x5c),
+    egui::Color32::from_rgb(0xd6, 0x4f, 0x8a),
+    egui::Color32::from_rgb(0xe8, 0xc5, 0x47),
+    egui::Color32::from_rgb(0x9a, 0x7c, 0xe0),
+];
+
+/// Records [`PlotHistory`] traces and registers [`PlotPanel`] (added by `WorkbenchPlugin`).
+pub struct PlotPlugin;
+
+impl Plugin for PlotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlotChannels>()
+            .init_resource::<PlotHistory>()
+            .add_systems(Last, plot_record_system);
+        app.register_panel(PlotPanel::default());
+    }
+}
+
+/// Values pushed by game code, recorded into the plot once per frame.
+///
+/// ```ignore
+/// fn track_speed(query: Query<&Velocity>, mut plot: ResMut<PlotChannels>) {
+///     for v in &query {
+///         plot.record("speed", v.0.length());
+///     }
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct PlotChannels {
+    pending: HashMap<String, f32>,
+}
+
+impl PlotChannels {
+    /// Record `value` on `channel` for this frame (the last value recorded in a frame wins).
+    /// A trace is created for the channel whenever it is recorded without one.
+    pub fn record(&mut self, channel: impl Into<String>, value: f32) {
+        self.pending.insert(channel.into(), value);
+    }
+}
+
+/// Where a trace reads its value from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlotSource {
+    /// A numeric field of a reflected component, e.g. `Transform` + `translation.y`.
+    Field {
+        entity: Entity,
+        component: String,
+        path: String,
+    },
+    /// A channel fed through [`PlotChannels::record`].
+    Channel(String),
+}
+
+/// One recorded value over time.
+pub struct PlotTrace {
+    pub label: String,
+    pub source: PlotSource,
+    pub color: egui::Color32,
+    /// `(real time in seconds, value)`, oldest first.
+    pub samples: VecDeque<(f32, f32)>,
+    /// Why the last read failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Traces recorded for [`PlotPanel`], sampled in `Last` regardless of panel visibility.
+#[derive(Resource)]
+pub struct PlotHistory {
+    pub traces: Vec<PlotTrace>,
+    /// When true, no samples are recorded.
+    pub paused: bool,
+    /// Maximum samples kept per trace.
+    pub capacity: usize,
+}
+
+impl Default for PlotHistory {
+    fn default() -> Self {
+        Self {
+            traces: Vec::new(),
+            paused: false,
+            capacity: DEFAULT_CAPACITY,
+        }
+    }
+}
+
+impl PlotHistory {
+    /// Add a trace reading from `source` (no-op if one already exists).
+    pub fn add(&mut self, label: impl Into<String>, source: PlotSource) {
+        if self.traces.iter().any(|t| t.source == source) {
+            return;
+        }
+        self.traces.push(PlotTrace {
+            label: label.into(),
+            source,
+            color: PALETTE[self.traces.len() % PALETTE.len()],
+            samples: VecDeque::new(),
+            error: None,
+        });
+    }
+
+    /// Drop all recorded samples, keeping the traces.
+    pub fn clear(&mut self) {
+        for trace in &mut self.traces {
+            trace.samples.clear();
+        }
+    }
+}
+
+/// Records one sample per trace per frame.
+fn plot_record_system(world: &mut World) {
+    let channels = std::mem::take(&mut world.resource_mut::<PlotChannels>().pending);
+    let Some(mut history) = world.remove_resource::<PlotHistory>() else {
+        return;
+    };
+    if !history.paused {
+        let mut channels: Vec<_> = channels.into_iter().collect();
+        channels.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, _) in &channels {
+            history.add(name.clone(), PlotSource::Channel(name.clone()));
+        }
+        let now = world.resource::<Time<Real>>().elapsed_secs();
+        let capacity = history.capacity.max(2);
+        for trace in &mut history.traces {
+            let value = match &trace.source {
+                PlotSource::Field {
+                    entity,
+                    component,
+                    path,
+                } => read_field(world, *entity, component, path),
+                PlotSource::Channel(name) => {
+                    Ok(channels.iter().find(|(n, _)| n == name).map(|(_, v)| *v))
+                }
+            };
+            match value {
+                Ok(Some(value)) => {
+                    trace.samples.push_back((now, value));
+                    trace.error = None;
+                }
+                Ok(None) => {}
+                Err(error) => trace.error = Some(error),
+            }
+            while trace.samples.len() > capacity {
+                trace.samples.pop_front();
+            }
+        }
+    }
+    world.insert_resource(history);
+}
+
+/// Read `component.path` of `entity` as a number.
+fn read_field(
+    world: &World,
+    entity: Entity,
+    component: &str,
+    path: &str,
+) -> Result<Option<f32>, String> {
+    let entity_ref = world
+        .get_entity(entity)
+        .map_err(|_| format!("{entity} despawned"))?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let reflect = crate::console::reflect_component(&registry, component)?;
+    let value = reflect
+        .reflect(entity_ref)
+        .ok_or_else(|| format!("{entity} has no `{component}`"))?;
+    let field = if path.is_empty() {
+        value.as_partial_reflect()
+    } else {
+        value
+            .as_partial_reflect()
+            .reflect_path(path)
+            .map_err(|e| e.to_string())?
+    };
+    numeric_value(field)
+        .map(Some)
+        .ok_or_else(|| format!("`{component}.{path}` is not a number"))
+}
+
+fn numeric_value(field: &dyn PartialReflect) -> Option<f32> {
+    macro_rules! read_as {
+        ($($ty:ty),*) => {$(
+            if let Some(value) = field.try_downcast_ref::<$ty>() {
+                return Some(*value as f32);
+            }
+        )*};
+    }
+    read_as!(f32, f64, i8, i16, i32, i64, u8, u16, u32, u64, usize, isize);
+    field.try_downcast_ref::<bool>().map(|&b| b as u8 as f32)
+}
+
+/// Built-in panel plotting [`PlotHistory`] traces, for tuning physics and animation parameters.
+#[derive(Default)]
+pub struct PlotPanel {
+    /// `Component.path` typed into the "add field" box.
+    new_field: String,
+}
+
+impl WorkbenchPanel for PlotPanel {
+    fn id(&self) -> &str {
+        "workbench_plot"
+    }
+
+    fn title(&self) -> String {
+        "Plot".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let selected = world
+            .get_resource::<InspectorSelection>()
+            .and_then(|s| s.selected.as_slice().first().copied());
+        let Some(mut history) = world.remove_resource::<PlotHistory>() else {
+            return;
+        };
+        self.toolbar_ui(ui, &mut history, selected);
+        ui.separator();
+        traces_ui(ui, &mut history);
+        let series: Vec<PlotSeries<'_>> = history
+            .traces
+            .iter()
+            .map(|t| PlotSeries {
+                name: &t.label,
+                color: t.color,
+                samples: &t.samples,
+            })
+            .collect();
+        bench_ui::plot_series(ui, "workbench_plot", &series);
+        world.insert_resource(history);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Bottom
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+impl PlotPanel {
+    fn toolbar_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        history: &mut PlotHistory,
+        selected: Option<Entity>,
+    ) {
+        ui.horizontal(|ui| {
+            let pause = if history.paused {
+                "▶ Resume"
+            } else {
+                "⏸ Pause"
+            };
+            if ui.button(pause).clicked() {
+                history.paused = !history.paused;
+            }
+            if ui.button("🗑 Clear").clicked() {
+                history.clear();
+            }
+            ui.add(
+                egui::DragValue::new(&mut history.capacity)
+                    .range(2..=100_000)
+                    .prefix("keep "),
+            );
+            ui.separator();
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_field)
+                    .hint_text("Transform.translation.y")
+                    .desired_width(180.0),
+            );
+            let add = ui
+                .add_enabled(selected.is_some(), egui::Button::new("+ Field"))
+                .on_hover_text("Plot this field of the selected entity")
+                .on_disabled_hover_text("Select an entity in the inspector");
+            if let Some(entity) = selected
+                && add.clicked()
+                && !self.new_field.trim().is_empty()
+            {
+                let spec = self.new_field.trim();
+                let (component, path) = spec.split_once('.').unwrap_or((spec, ""));
+                history.add(
+                    format!("{entity} {spec}"),
+                    PlotSource::Field {
+                        entity,
+                        component: component.to_string(),
+                        path: path.to_string(),
+                    },
+                );
+            }
+        });
+    }
+}
+
+/// Legend with per-trace remove buttons and read errors.
+fn traces_ui(ui: &mut egui::Ui, history: &mut PlotHistory) {
+    if history.traces.is_empty() {
+        ui.weak("No traces: add a field of the selected entity, or call PlotChannels::record");
+        return;
+    }
+    let mut remove = None;
+    ui.horizontal_wrapped(|ui| {
+        for (i, trace) in history.traces.iter().enumerate() {
+            let latest = trace.samples.back().map(|&(_, v)| v);
+            let text = match latest {
+                Some(v) => format!("{}: {v:.3}", trace.label),
+                None => trace.label.clone(),
+            };
+            let response = ui.label(egui::RichText::new(text).color(trace.color));
+            if let Some(error) = &trace.error {
+                ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                    .on_hover_text(error);
+            }
+            response.on_hover_text(format!("{:?}", trace.source));
+            if ui.small_button("×").clicked() {
+                remove = Some(i);
+            }
+            ui.separator();
+        }
+    });
+    if let Some(i) = remove {
+        history.traces.remove(i);
+    }
+}
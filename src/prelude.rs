@@ -3,21 +3,53 @@
 pub use crate::WorkbenchApp;
 pub use crate::WorkbenchConfig;
 pub use crate::WorkbenchPlugin;
+pub use crate::accessibility::AccessibilityConfig;
+#[cfg(feature = "audio")]
+pub use crate::audio::AudioPanel;
 pub use crate::bench_ui;
-pub use crate::config::WorkbenchSettings;
+pub use crate::config::{SettingsRegistry, ToolSettings, WorkbenchSettings};
 pub use crate::console::{ConsolePanel, console_log_layer};
-pub use crate::dock::{PanelSlot, TileLayoutState, WorkbenchPanel};
+pub use crate::dialogs::{Dialogs, ProgressToken};
+pub use crate::dock::{
+    FocusedPanel, LayoutBuilder, PanelSlot, PanelStyle, PanelStyles, SystemPanel, TileLayoutState,
+    WorkbenchPanel,
+};
+#[cfg(feature = "game-view")]
+pub use crate::editor_camera::EditorCameraController;
+pub use crate::events::{EventLog, EventsPanel};
+#[cfg(feature = "file-dialogs")]
+pub use crate::file_dialog::{FileDialogRequest, FileDialogResponse, FileDialogs};
 pub use crate::font::FontConfig;
+#[cfg(feature = "game-view")]
 pub use crate::game_view::{
-    ExternalGameCamera, GameViewCamera, GameViewFocus, GameViewPanel, GameViewPlugin,
-    GameViewState, ViewZoom,
+    BrushDab, ExternalGameCamera, GameViewBrushes, GameViewCamera, GameViewConfig, GameViewFocus,
+    GameViewPanel, GameViewPixels, GameViewPlugin, GameViewReadback, GameViewRuler, GameViewState,
+    GameWindow, InputRecorder, PointHandles, RectHandles, RectShape, SceneViewPanel, ViewZoom,
+    ViewportOverlays,
+};
+pub use crate::help::{HelpPanel, PanelHelp};
+pub use crate::i18n::{I18n, I18nText, Locale};
+pub use crate::inspector::{
+    EditorLocked, InspectorPanel, InspectorSelection, SavedSelections, SelectionChanged,
 };
-pub use crate::i18n::{I18n, Locale};
-pub use crate::inspector::InspectorPanel;
 pub use crate::layout::{LayoutMode, LayoutState};
 pub use crate::menu_bar::{
-    CustomMenu, MenuAction, MenuBarExtensions, MenuExtItem, SettingsSection,
+    CustomMenu, FileOp, MenuAction, MenuBarExtensions, MenuExtItem, SettingsSection,
+};
+pub use crate::mode::{
+    Decision, EditorMode, EditorOnly, GameClock, GameSchedule, GameTime, ModeController,
+    ModeTransitionRequest, PlaySessionEnded, PlaySessionStarted, UnfocusedBehavior, on_fresh_play,
 };
-pub use crate::mode::{EditorMode, GameClock, GameSchedule, ModeController, on_fresh_play};
-pub use crate::theme::{ThemeConfig, ThemePreset, ThemeState};
-pub use crate::undo::{UndoAction, UndoStack};
+pub use crate::plot::{PlotChannels, PlotPanel};
+pub use crate::presentation::PresentationMode;
+pub use crate::schedule_graph::ScheduleGraphPanel;
+pub use crate::scrub::EvaluateAtTime;
+pub use crate::shortcuts::{ShortcutRegistry, ShortcutsPanel};
+pub use crate::snap::SnapSettings;
+pub use crate::states::StatesPanel;
+pub use crate::tasks::Tasks;
+pub use crate::theme::{PalettePreset, SemanticPalette, ThemeConfig, ThemePreset, ThemeState};
+pub use crate::timeline::{SessionTimeline, TimelinePanel};
+pub use crate::undo::{LayoutUndoStack, SerializedAction, UndoAction, UndoStack};
+pub use crate::welcome::{WelcomePanel, WelcomeScreen};
+pub use crate::world_stats::WorldStatsPanel;
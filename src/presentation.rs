@@ -0,0 +1,114 @@
+//! Presentation mode: hides the menu bar, toolbar, status bar and panel chrome, leaving one
+//! panel (the game view by default) borderless fullscreen for demos. Toggled with F11.
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+
+use crate::dock::{FocusedPanel, TileLayoutState};
+use crate::keybind::{BindingInput, KeyBindings};
+
+/// Panel presented when [`PresentationMode::panel`] is unset and it is registered.
+const GAME_VIEW_PANEL: &str = "workbench_game_view";
+
+/// Toggles presentation mode on its key binding (added by `WorkbenchPlugin`).
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PresentationMode>()
+            .add_systems(Update, presentation_system);
+    }
+}
+
+/// Presentation mode state.
+#[derive(Resource, Default)]
+pub struct PresentationMode {
+    /// Panel to present; `None` picks the game view, or the focused panel without one.
+    pub panel: Option<String>,
+    /// Set to enter or leave presentation mode on the next frame.
+    pub toggle_requested: bool,
+    backup: Option<PresentationBackup>,
+}
+
+/// State restored when presentation mode ends.
+struct PresentationBackup {
+    maximized: Option<String>,
+    window_mode: Option<WindowMode>,
+}
+
+impl PresentationMode {
+    /// Whether the editor chrome is currently hidden.
+    pub fn is_active(&self) -> bool {
+        self.backup.is_some()
+    }
+}
+
+/// Run condition: true unless presentation mode is active.
+pub fn not_presenting(presentation: Option<Res<PresentationMode>>) -> bool {
+    !presentation.is_some_and(|p| p.is_active())
+}
+
+/// Whether presentation mode is active (for UI drawn with `&World`).
+pub fn is_presenting(world: &World) -> bool {
+    world
+        .get_resource::<PresentationMode>()
+        .is_some_and(PresentationMode::is_active)
+}
+
+fn presentation_system(
+    keys: BindingInput,
+    bindings: Res<KeyBindings>,
+    focused: Res<FocusedPanel>,
+    mut presentation: ResMut<PresentationMode>,
+    mut tiles: ResMut<TileLayoutState>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let toggle = std::mem::take(&mut presentation.toggle_requested)
+        || bindings.presentation_mode.just_pressed(&keys);
+    if !toggle {
+        return;
+    }
+    let mut window = windows.single_mut().ok();
+
+    if let Some(backup) = presentation.backup.take() {
+        match backup.maximized {
+            Some(panel) => {
+                if tiles.maximized_panel() != Some(panel.as_str()) {
+                    tiles.toggle_maximize(&panel);
+                }
+            }
+            None => tiles.restore_maximized(),
+        }
+        if let (Some(window), Some(mode)) = (window.as_mut(), backup.window_mode) {
+            window.mode = mode;
+        }
+        tiles.set_chromeless(false);
+        return;
+    }
+
+    let target = presentation
+        .panel
+        .clone()
+        .or_else(|| {
+            tiles
+                .panel_dyn_mut(GAME_VIEW_PANEL)
+                .map(|_| GAME_VIEW_PANEL.to_string())
+        })
+        .or_else(|| focused.0.clone());
+    let Some(target) = target else {
+        warn!("Presentation mode needs a game view or a focused panel");
+        return;
+    };
+    presentation.backup = Some(PresentationBackup {
+        maximized: tiles.maximized_panel().map(str::to_string),
+        window_mode: window.as_ref().map(|w| w.mode),
+    });
+    tiles.open_or_focus_panel(&target);
+    if tiles.maximized_panel() != Some(target.as_str()) {
+        tiles.toggle_maximize(&target);
+    }
+    tiles.set_chromeless(true);
+    if let Some(window) = window.as_mut() {
+        window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+    }
+}
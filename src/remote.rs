@@ -0,0 +1,313 @@
+//! Remote inspector: browses a running game over the Bevy Remote Protocol (`remote` feature).
+
+use bevy::prelude::*;
+use serde_json::{Value, json};
+use std::sync::{Mutex, mpsc};
+
+mod ui;
+
+pub(crate) use ui::remote_inspector_ui;
+
+/// Default BRP HTTP endpoint (matches `bevy::remote::http::DEFAULT_PORT`).
+pub const DEFAULT_REMOTE_URL: &str = "http://127.0.0.1:15702";
+
+const NAME_PATH: &str = "bevy_ecs::name::Name";
+const CHILD_OF_PATH: &str = "bevy_ecs::hierarchy::ChildOf";
+
+/// Plugin that adds the remote inspector connection and its polling system.
+pub struct RemoteInspectorPlugin;
+
+impl Plugin for RemoteInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RemoteInspector>()
+            .add_systems(PreUpdate, remote_poll_system);
+    }
+}
+
+/// Connection state of the remote inspector.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum RemoteStatus {
+    #[default]
+    Disconnected,
+    /// A request is in flight and no response has arrived yet.
+    Connecting,
+    Connected,
+    Error(String),
+}
+
+/// An entity reported by the remote world.
+#[derive(Debug, Clone)]
+pub struct RemoteEntity {
+    /// Entity bits as sent over BRP.
+    pub id: u64,
+    pub name: Option<String>,
+    pub parent: Option<u64>,
+}
+
+impl RemoteEntity {
+    /// Label shown in the remote hierarchy.
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("Entity {}", self.id),
+        }
+    }
+}
+
+/// Requests sent to the BRP worker thread.
+enum RemoteRequest {
+    /// Re-read the entity list.
+    Hierarchy,
+    /// Read all reflectable components of an entity.
+    Components(u64),
+    /// Replace one component value.
+    Insert(u64, String, Value),
+}
+
+/// Responses produced by the BRP worker thread.
+enum RemoteResponse {
+    Hierarchy(Vec<RemoteEntity>),
+    Components(u64, Vec<(String, Value)>),
+    Inserted(u64),
+    Error(String),
+}
+
+/// Resource holding the remote connection and the last data it returned.
+///
+/// When `enabled`, the inspector panel shows the remote world instead of the local one.
+#[derive(Resource)]
+pub struct RemoteInspector {
+    /// BRP HTTP endpoint of the running game.
+    pub url: String,
+    /// Whether the inspector panel shows the remote world.
+    pub enabled: bool,
+    /// Seconds between automatic hierarchy/component refreshes.
+    pub refresh_interval: f32,
+    pub status: RemoteStatus,
+    /// Entities of the remote world (in query order).
+    pub entities: Vec<RemoteEntity>,
+    /// Entity selected in the remote hierarchy.
+    pub selected: Option<u64>,
+    /// Components of `selected` as `(type path, JSON value)` pairs.
+    pub components: Vec<(String, Value)>,
+    since_refresh: f32,
+    /// Requests sent to the worker whose response has not arrived yet.
+    in_flight: usize,
+    sender: Option<mpsc::Sender<(String, RemoteRequest)>>,
+    receiver: Option<Mutex<mpsc::Receiver<RemoteResponse>>>,
+}
+
+impl Default for RemoteInspector {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_REMOTE_URL.to_string(),
+            enabled: false,
+            refresh_interval: 1.0,
+            status: RemoteStatus::Disconnected,
+            entities: Vec::new(),
+            selected: None,
+            components: Vec::new(),
+            since_refresh: 0.0,
+            in_flight: 0,
+            sender: None,
+            receiver: None,
+        }
+    }
+}
+
+impl RemoteInspector {
+    /// Start the worker thread (if needed) and request the remote hierarchy.
+    pub fn connect(&mut self) {
+        if self.sender.is_none() {
+            let (request_tx, request_rx) = mpsc::channel();
+            let (response_tx, response_rx) = mpsc::channel();
+            std::thread::Builder::new()
+                .name("workbench-remote".into())
+                .spawn(move || remote_worker(request_rx, response_tx))
+                .expect("failed to spawn remote inspector thread");
+            self.sender = Some(request_tx);
+            self.receiver = Some(Mutex::new(response_rx));
+        }
+        self.status = RemoteStatus::Connecting;
+        self.refresh();
+    }
+
+    /// Stop the worker thread and forget remote data.
+    pub fn disconnect(&mut self) {
+        self.sender = None;
+        self.receiver = None;
+        self.in_flight = 0;
+        self.status = RemoteStatus::Disconnected;
+        self.entities.clear();
+        self.components.clear();
+        self.selected = None;
+    }
+
+    /// Whether the worker thread is running.
+    pub fn is_connected(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    /// Request the hierarchy and the selected entity's components again.
+    ///
+    /// Does nothing while earlier requests are still outstanding, so a slow or unreachable
+    /// endpoint doesn't pile up requests in the worker queue.
+    pub fn refresh(&mut self) {
+        if self.in_flight > 0 {
+            return;
+        }
+        self.since_refresh = 0.0;
+        self.send(RemoteRequest::Hierarchy);
+        if let Some(entity) = self.selected {
+            self.send(RemoteRequest::Components(entity));
+        }
+    }
+
+    /// Select a remote entity and request its components.
+    pub fn select(&mut self, entity: u64) {
+        if self.selected != Some(entity) {
+            self.components.clear();
+        }
+        self.selected = Some(entity);
+        self.send(RemoteRequest::Components(entity));
+    }
+
+    /// Replace a component on a remote entity.
+    pub fn insert_component(&mut self, entity: u64, type_path: String, value: Value) {
+        self.send(RemoteRequest::Insert(entity, type_path, value));
+    }
+
+    fn send(&mut self, request: RemoteRequest) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if sender.send((self.url.clone(), request)).is_ok() {
+            self.in_flight += 1;
+        } else {
+            self.status = RemoteStatus::Error("remote worker stopped".to_string());
+            self.sender = None;
+        }
+    }
+
+    fn handle(&mut self, response: RemoteResponse) {
+        // The worker answers every request exactly once, errors included.
+        self.in_flight = self.in_flight.saturating_sub(1);
+        match response {
+            RemoteResponse::Hierarchy(entities) => {
+                self.status = RemoteStatus::Connected;
+                if self
+                    .selected
+                    .is_some_and(|s| !entities.iter().any(|e| e.id == s))
+                {
+                    self.selected = None;
+                    self.components.clear();
+                }
+                self.entities = entities;
+            }
+            RemoteResponse::Components(entity, components) => {
+                if self.selected == Some(entity) {
+                    self.components = components;
+                }
+            }
+            RemoteResponse::Inserted(entity) => self.send(RemoteRequest::Components(entity)),
+            RemoteResponse::Error(message) => self.status = RemoteStatus::Error(message),
+        }
+    }
+}
+
+/// Applies worker responses and triggers periodic refreshes.
+fn remote_poll_system(time: Res<Time>, mut remote: ResMut<RemoteInspector>) {
+    let responses: Vec<RemoteResponse> = match &remote.receiver {
+        Some(receiver) => receiver
+            .lock()
+            .map(|rx| rx.try_iter().collect())
+            .unwrap_or_default(),
+        None => return,
+    };
+    for response in responses {
+        remote.handle(response);
+    }
+    if !remote.enabled {
+        return;
+    }
+    remote.since_refresh += time.delta_secs();
+    if remote.since_refresh >= remote.refresh_interval {
+        remote.refresh();
+    }
+}
+
+/// Worker loop: performs blocking BRP calls off the main thread.
+fn remote_worker(
+    requests: mpsc::Receiver<(String, RemoteRequest)>,
+    responses: mpsc::Sender<RemoteResponse>,
+) {
+    for (url, request) in requests {
+        let response = match request {
+            RemoteRequest::Hierarchy => fetch_hierarchy(&url).map(RemoteResponse::Hierarchy),
+            RemoteRequest::Components(entity) => fetch_components(&url, entity)
+                .map(|components| RemoteResponse::Components(entity, components)),
+            RemoteRequest::Insert(entity, type_path, value) => {
+                let mut components = serde_json::Map::new();
+                components.insert(type_path, value);
+                let params = json!({ "entity": entity, "components": components });
+                brp_call(&url, "world.insert_components", params)
+                    .map(|_| RemoteResponse::Inserted(entity))
+            }
+        };
+        let response = response.unwrap_or_else(RemoteResponse::Error);
+        if responses.send(response).is_err() {
+            return;
+        }
+    }
+}
+
+/// Perform one JSON-RPC call and return its `result`.
+fn brp_call(url: &str, method: &str, params: Value) -> Result<Value, String> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let reply: Value = ureq::post(url)
+        .send_json(body)
+        .map_err(|e| e.to_string())?
+        .into_json()
+        .map_err(|e| e.to_string())?;
+    if let Some(error) = reply.get("error") {
+        let message = error.get("message").and_then(Value::as_str);
+        return Err(message.map_or_else(|| error.to_string(), str::to_string));
+    }
+    Ok(reply.get("result").cloned().unwrap_or(Value::Null))
+}
+
+fn fetch_hierarchy(url: &str) -> Result<Vec<RemoteEntity>, String> {
+    let params = json!({ "data": { "option": [NAME_PATH, CHILD_OF_PATH] } });
+    let rows = brp_call(url, "world.query", params)?;
+    let rows = rows.as_array().ok_or("unexpected world.query reply")?;
+    Ok(rows.iter().filter_map(parse_remote_entity).collect())
+}
+
+fn parse_remote_entity(row: &Value) -> Option<RemoteEntity> {
+    let id = row.get("entity")?.as_u64()?;
+    let components = row.get("components");
+    let name = components
+        .and_then(|c| c.get(NAME_PATH))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    // `ChildOf` is a newtype; depending on the serializer it arrives bare or wrapped.
+    let parent = components.and_then(|c| c.get(CHILD_OF_PATH)).and_then(|v| {
+        v.as_u64()
+            .or_else(|| v.get(0).and_then(Value::as_u64))
+            .or_else(|| v.get("0").and_then(Value::as_u64))
+    });
+    Some(RemoteEntity { id, name, parent })
+}
+
+fn fetch_components(url: &str, entity: u64) -> Result<Vec<(String, Value)>, String> {
+    let listed = brp_call(url, "world.list_components", json!({ "entity": entity }))?;
+    let params = json!({ "entity": entity, "components": listed, "strict": false });
+    let reply = brp_call(url, "world.get_components", params)?;
+    let mut components: Vec<(String, Value)> = reply
+        .get("components")
+        .and_then(Value::as_object)
+        .map(|map| map.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default();
+    components.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(components)
+}
@@ -0,0 +1,221 @@
+//! # ui.rs
+//!
+//! # ui.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Draws the remote mode of the inspector panel: a connection bar, the remote entity hierarchy
+//! built from `ChildOf` relations, and a JSON-backed component editor. Edited values are sent
+//! back to the game as whole-component inserts.
+//!
+//! 绘制检查器面板的远程模式：连接栏、根据 `ChildOf` 关系构建的远程实体层级，
+//! 以及基于 JSON 的组件编辑器。编辑后的值会以整个组件插入的方式发回游戏。
+
+use super::{RemoteEntity, RemoteInspector, RemoteStatus};
+use bevy::prelude::*;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Remote hierarchy + inspector, drawn in place of the local ones.
+pub(crate) fn remote_inspector_ui(
+    ui: &mut egui::Ui,
+    remote: &mut RemoteInspector,
+    s_hierarchy: &str,
+    s_components: &str,
+    s_select_hint: &str,
+) {
+    connection_bar_ui(ui, remote);
+    ui.separator();
+
+    egui::SidePanel::left("remote_inspector_hierarchy")
+        .resizable(true)
+        .default_width(180.0)
+        .show_inside(ui, |ui| {
+            ui.heading(s_hierarchy);
+            ui.separator();
+            egui::ScrollArea::both().show(ui, |ui| remote_hierarchy_ui(ui, remote));
+        });
+
+    egui::CentralPanel::default().show_inside(ui, |ui| {
+        ui.heading(s_components);
+        ui.separator();
+        egui::ScrollArea::both().show(ui, |ui| {
+            if remote.selected.is_some() {
+                remote_components_ui(ui, remote);
+            } else {
+                ui.weak(s_select_hint);
+            }
+        });
+    });
+}
+
+/// URL field, connect/disconnect and status.
+fn connection_bar_ui(ui: &mut egui::Ui, remote: &mut RemoteInspector) {
+    ui.horizontal(|ui| {
+        ui.toggle_value(&mut remote.enabled, "🌐")
+            .on_hover_text("Remote mode");
+        let connected = remote.is_connected();
+        ui.add_enabled(
+            !connected,
+            egui::TextEdit::singleline(&mut remote.url).desired_width(200.0),
+        );
+        if connected {
+            if ui.button("⏹ Disconnect").clicked() {
+                remote.disconnect();
+            }
+            if ui.button("🔄").on_hover_text("Refresh").clicked() {
+                remote.refresh();
+            }
+        } else if ui.button("🔌 Connect").clicked() {
+            remote.connect();
+        }
+        match &remote.status {
+            RemoteStatus::Disconnected => ui.weak("Disconnected"),
+            RemoteStatus::Connecting => ui.weak("Connecting..."),
+            RemoteStatus::Connected => ui.colored_label(
+                crate::theme::palette(ui.ctx()).success,
+                format!("● {}", remote.entities.len()),
+            ),
+            RemoteStatus::Error(e) => ui.colored_label(crate::theme::palette(ui.ctx()).error, e),
+        };
+    });
+}
+
+/// Remote entity tree, grouped by `ChildOf`.
+fn remote_hierarchy_ui(ui: &mut egui::Ui, remote: &mut RemoteInspector) {
+    let mut children: HashMap<Option<u64>, Vec<&RemoteEntity>> = HashMap::new();
+    for entity in &remote.entities {
+        children.entry(entity.parent).or_default().push(entity);
+    }
+    let mut clicked = None;
+    for root in children.get(&None).into_iter().flatten() {
+        entity_node_ui(ui, root, &children, remote.selected, &mut clicked);
+    }
+    if let Some(entity) = clicked {
+        remote.select(entity);
+    }
+}
+
+fn entity_node_ui(
+    ui: &mut egui::Ui,
+    entity: &RemoteEntity,
+    children: &HashMap<Option<u64>, Vec<&RemoteEntity>>,
+    selected: Option<u64>,
+    clicked: &mut Option<u64>,
+) {
+    let is_selected = selected == Some(entity.id);
+    let Some(kids) = children.get(&Some(entity.id)) else {
+        if ui.selectable_label(is_selected, entity.label()).clicked() {
+            *clicked = Some(entity.id);
+        }
+        return;
+    };
+    let id = ui.make_persistent_id(("remote_entity", entity.id));
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+        .show_header(ui, |ui| {
+            if ui.selectable_label(is_selected, entity.label()).clicked() {
+                *clicked = Some(entity.id);
+            }
+        })
+        .body(|ui| {
+            for kid in kids {
+                entity_node_ui(ui, kid, children, selected, clicked);
+            }
+        });
+}
+
+/// Components of the selected remote entity, editable as JSON values.
+fn remote_components_ui(ui: &mut egui::Ui, remote: &mut RemoteInspector) {
+    let Some(entity) = remote.selected else {
+        return;
+    };
+    let mut edited = None;
+    for (type_path, value) in &mut remote.components {
+        let short = type_path.rsplit("::").next().unwrap_or(type_path.as_str());
+        let changed = egui::CollapsingHeader::new(short)
+            .id_salt(type_path.as_str())
+            .default_open(true)
+            .show(ui, |ui| json_value_ui(ui, value))
+            .body_returned
+            .unwrap_or(false);
+        if changed {
+            edited = Some((type_path.clone(), value.clone()));
+        }
+    }
+    if let Some((type_path, value)) = edited {
+        remote.insert_component(entity, type_path, value);
+    }
+}
+
+/// Edit a JSON value in place. Returns true when the user committed a change.
+fn json_value_ui(ui: &mut egui::Ui, value: &mut Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            let mut changed = false;
+            for (key, child) in map.iter_mut() {
+                changed |= json_field_ui(ui, key, child);
+            }
+            changed
+        }
+        Value::Array(items) => {
+            let mut changed = false;
+            for (index, child) in items.iter_mut().enumerate() {
+                changed |= json_field_ui(ui, &index.to_string(), child);
+            }
+            changed
+        }
+        _ => json_scalar_ui(ui, value),
+    }
+}
+
+/// A labelled field: scalars inline, containers as a nested collapsible section.
+fn json_field_ui(ui: &mut egui::Ui, key: &str, value: &mut Value) -> bool {
+    if value.is_object() || value.is_array() {
+        return egui::CollapsingHeader::new(key)
+            .show(ui, |ui| json_value_ui(ui, value))
+            .body_returned
+            .unwrap_or(false);
+    }
+    ui.horizontal(|ui| {
+        ui.label(key);
+        json_scalar_ui(ui, value)
+    })
+    .inner
+}
+
+fn json_scalar_ui(ui: &mut egui::Ui, value: &mut Value) -> bool {
+    if value.is_f64() {
+        let mut v = value.as_f64().unwrap_or_default();
+        let response = ui.add(egui::DragValue::new(&mut v).speed(0.1));
+        return commit_number(value, serde_json::Number::from_f64(v), &response);
+    }
+    if value.is_number() {
+        let mut v = value.as_i64().unwrap_or_default();
+        let response = ui.add(egui::DragValue::new(&mut v));
+        return commit_number(value, Some(v.into()), &response);
+    }
+    match value {
+        Value::Bool(b) => ui.checkbox(b, "").changed(),
+        Value::String(s) => ui.text_edit_singleline(s).lost_focus(),
+        _ => {
+            ui.weak(value.to_string());
+            false
+        }
+    }
+}
+
+/// Store an edited number and report a commit once the drag/edit finishes.
+fn commit_number(
+    value: &mut Value,
+    number: Option<serde_json::Number>,
+    response: &egui::Response,
+) -> bool {
+    if response.changed()
+        && let Some(number) = number
+    {
+        *value = Value::Number(number);
+    }
+    response.drag_stopped() || (response.changed() && !response.dragged())
+}
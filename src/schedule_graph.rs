@@ -0,0 +1,362 @@
+//! Schedule graph panel: the systems of the main schedules as a searchable list or as a
+//! dependency graph, with run counters sampled from each system's last-run tick. A system that
+//! ran since the last sample counts every run of its schedule in between, so `FixedUpdate`
+//! systems count each fixed tick rather than once per frame.
+
+use bevy::ecs::component::Tick;
+use bevy::ecs::schedule::{InternedScheduleLabel, NodeId, ScheduleLabel, Schedules, SystemKey};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::bench_ui::{self, GraphLink, GraphNode, NodeGraph, PortRef};
+use crate::dock::WorkbenchPanel;
+use crate::mode::GameSchedule;
+
+/// Seconds between runs-per-second updates.
+const RATE_WINDOW: f32 = 1.0;
+/// Horizontal and vertical spacing of the graph layout, in graph units.
+const LAYER_SPACING: f32 = 220.0;
+const ROW_SPACING: f32 = 64.0;
+
+/// Schedules the panel can show.
+fn schedule_choices() -> [(&'static str, InternedScheduleLabel); 5] {
+    [
+        ("PreUpdate", PreUpdate.intern()),
+        ("Update", Update.intern()),
+        ("PostUpdate", PostUpdate.intern()),
+        ("FixedUpdate", FixedUpdate.intern()),
+        ("GameSchedule", GameSchedule.intern()),
+    ]
+}
+
+/// Runs of each schedule in [`schedule_choices`] (same order), counted by a system inside it.
+#[derive(Resource, Default)]
+pub(crate) struct ScheduleRuns([u64; 5]);
+
+fn count_schedule_run<const I: usize>(mut runs: ResMut<ScheduleRuns>) {
+    runs.0[I] += 1;
+}
+
+/// Adds the schedule run counters used by [`ScheduleGraphPanel`].
+pub(crate) fn add_run_counters(app: &mut App) {
+    app.init_resource::<ScheduleRuns>()
+        .add_systems(PreUpdate, count_schedule_run::<0>)
+        .add_systems(Update, count_schedule_run::<1>)
+        .add_systems(PostUpdate, count_schedule_run::<2>)
+        .add_systems(FixedUpdate, count_schedule_run::<3>)
+        .add_systems(GameSchedule, count_schedule_run::<4>);
+}
+
+#[derive(Default, PartialEq)]
+enum ScheduleView {
+    #[default]
+    List,
+    Graph,
+}
+
+/// One system row.
+struct SystemRow {
+    key: SystemKey,
+    name: String,
+    short_name: String,
+    last_run: Tick,
+    /// Whether the system ran since the previous sample.
+    ran: bool,
+    runs: u64,
+    window_runs: u64,
+    rate: f32,
+}
+
+/// Built-in panel for debugging system ordering without dumping dot files.
+#[derive(Default)]
+pub struct ScheduleGraphPanel {
+    schedule: usize,
+    view: ScheduleView,
+    filter: String,
+    systems: Vec<SystemRow>,
+    /// Explicit system-to-system ordering edges (indices into `systems`).
+    edges: Vec<(usize, usize)>,
+    graph: NodeGraph,
+    window_start: Option<f32>,
+    /// [`ScheduleRuns`] count of the shown schedule at the previous sample.
+    sampled_runs: Option<u64>,
+}
+
+impl WorkbenchPanel for ScheduleGraphPanel {
+    fn id(&self) -> &str {
+        "workbench_schedule_graph"
+    }
+
+    fn title(&self) -> String {
+        "Schedules".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        self.toolbar_ui(ui);
+        ui.separator();
+        if !self.sample(world) {
+            ui.weak("Schedule not found or not initialized yet");
+            return;
+        }
+        match self.view {
+            ScheduleView::List => self.list_ui(ui),
+            ScheduleView::Graph => self.graph_ui(ui),
+        }
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+impl ScheduleGraphPanel {
+    fn toolbar_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let choices = schedule_choices();
+            let previous = self.schedule;
+            egui::ComboBox::from_id_salt("schedule_graph_schedule")
+                .selected_text(choices[self.schedule].0)
+                .show_ui(ui, |ui| {
+                    for (i, (name, _)) in choices.iter().enumerate() {
+                        ui.selectable_value(&mut self.schedule, i, *name);
+                    }
+                });
+            if self.schedule != previous {
+                self.systems.clear();
+                self.sampled_runs = None;
+            }
+            ui.selectable_value(&mut self.view, ScheduleView::List, "List");
+            ui.selectable_value(&mut self.view, ScheduleView::Graph, "Graph");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.filter)
+                    .hint_text("Search systems")
+                    .desired_width(160.0),
+            );
+        });
+    }
+
+    /// Refreshes the system list when the schedule changed and updates run counters.
+    /// Returns false if the schedule isn't available.
+    fn sample(&mut self, world: &World) -> bool {
+        let (_, label) = schedule_choices()[self.schedule];
+        let Some(schedule) = world.resource::<Schedules>().get(label) else {
+            return false;
+        };
+        let Ok(systems) = schedule.systems() else {
+            return false;
+        };
+        let systems: Vec<_> = systems.collect();
+        let unchanged = systems.len() == self.systems.len()
+            && systems
+                .iter()
+                .zip(&self.systems)
+                .all(|((key, _), row)| *key == row.key);
+        if !unchanged {
+            self.systems = systems
+                .iter()
+                .map(|(key, system)| {
+                    SystemRow::new(*key, system.name().to_string(), system.get_last_run())
+                })
+                .collect();
+            self.edges = ordering_edges(schedule.graph(), &self.systems);
+            self.graph = layout_graph(&self.systems, &self.edges);
+        }
+
+        let schedule_runs = world
+            .get_resource::<ScheduleRuns>()
+            .map(|runs| runs.0[self.schedule]);
+        let ticks = match (schedule_runs, self.sampled_runs) {
+            (Some(now), Some(previous)) => now.saturating_sub(previous).max(1),
+            _ => 1,
+        };
+        self.sampled_runs = schedule_runs;
+        for (row, (_, system)) in self.systems.iter_mut().zip(&systems) {
+            let last_run = system.get_last_run();
+            row.ran = last_run != row.last_run;
+            if row.ran {
+                row.runs += ticks;
+                row.window_runs += ticks;
+                row.last_run = last_run;
+            }
+        }
+        let now = world.resource::<Time<Real>>().elapsed_secs();
+        let start = *self.window_start.get_or_insert(now);
+        if now - start >= RATE_WINDOW {
+            for row in &mut self.systems {
+                row.rate = row.window_runs as f32 / (now - start);
+                row.window_runs = 0;
+            }
+            self.window_start = Some(now);
+        }
+        true
+    }
+
+    fn matches(&self, row: &SystemRow) -> bool {
+        self.filter.is_empty()
+            || row
+                .name
+                .to_lowercase()
+                .contains(&self.filter.to_lowercase())
+    }
+
+    fn list_ui(&self, ui: &mut egui::Ui) {
+        egui::ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("schedule_graph_list")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("#");
+                    ui.strong("System");
+                    ui.strong("Runs/s");
+                    ui.strong("Runs");
+                    ui.end_row();
+                    for (i, row) in self.systems.iter().enumerate() {
+                        if !self.matches(row) {
+                            continue;
+                        }
+                        ui.weak(i.to_string());
+                        let text = egui::RichText::new(&row.short_name).monospace();
+                        let text = if row.ran { text } else { text.weak() };
+                        ui.label(text).on_hover_text(&row.name);
+                        ui.label(format!("{:.0}", row.rate));
+                        ui.label(row.runs.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn graph_ui(&mut self, ui: &mut egui::Ui) {
+        if self.graph.nodes.len() != self.systems.len() {
+            self.graph = layout_graph(&self.systems, &self.edges);
+        }
+        let highlight = ui.visuals().selection.bg_fill;
+        let filtering = !self.filter.is_empty();
+        for (node, row) in self.graph.nodes.iter_mut().zip(&self.systems) {
+            let matched = filtering
+                && row
+                    .name
+                    .to_lowercase()
+                    .contains(&self.filter.to_lowercase());
+            node.color = matched.then_some(highlight);
+        }
+        bench_ui::node_graph(ui, "schedule_graph", &mut self.graph);
+        // The graph is read-only: drop any links the user drew.
+        self.graph.links = graph_links(&self.edges);
+    }
+}
+
+impl SystemRow {
+    fn new(key: SystemKey, name: String, last_run: Tick) -> Self {
+        Self {
+            key,
+            short_name: short_name(&name),
+            name,
+            last_run,
+            ran: false,
+            runs: 0,
+            window_runs: 0,
+            rate: 0.0,
+        }
+    }
+}
+
+/// Direct `before`/`after` edges between systems (orderings through sets are not expanded).
+fn ordering_edges(
+    graph: &bevy::ecs::schedule::ScheduleGraph,
+    systems: &[SystemRow],
+) -> Vec<(usize, usize)> {
+    let index: HashMap<SystemKey, usize> = systems
+        .iter()
+        .enumerate()
+        .map(|(i, row)| (row.key, i))
+        .collect();
+    graph
+        .dependency()
+        .graph()
+        .all_edges()
+        .filter_map(|(from, to)| match (from, to) {
+            (NodeId::System(from), NodeId::System(to)) => {
+                Some((*index.get(&from)?, *index.get(&to)?))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn graph_links(edges: &[(usize, usize)]) -> Vec<GraphLink> {
+    edges
+        .iter()
+        .map(|&(from, to)| GraphLink {
+            from: PortRef {
+                node: bench_ui::NodeId(from as u64),
+                port: 0,
+            },
+            to: PortRef {
+                node: bench_ui::NodeId(to as u64),
+                port: 0,
+            },
+        })
+        .collect()
+}
+
+/// Lays systems out in columns by longest dependency path.
+fn layout_graph(systems: &[SystemRow], edges: &[(usize, usize)]) -> NodeGraph {
+    let mut layer = vec![0usize; systems.len()];
+    // Edges form a DAG, so at most `len` relaxation passes are needed.
+    for _ in 0..systems.len() {
+        let mut changed = false;
+        for &(from, to) in edges {
+            if layer[to] <= layer[from] {
+                layer[to] = layer[from] + 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut rows_per_layer: HashMap<usize, usize> = HashMap::new();
+    let nodes = systems
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let slot = rows_per_layer.entry(layer[i]).or_default();
+            let pos = egui::pos2(layer[i] as f32 * LAYER_SPACING, *slot as f32 * ROW_SPACING);
+            *slot += 1;
+            GraphNode::new(bench_ui::NodeId(i as u64), &row.short_name, pos)
+                .with_input("after", "order")
+                .with_output("before", "order")
+        })
+        .collect();
+    NodeGraph {
+        nodes,
+        links: graph_links(edges),
+        selected: Vec::new(),
+    }
+}
+
+/// Strips module paths: `my_game::systems::move_player<my_game::Player>` → `move_player<Player>`.
+fn short_name(full: &str) -> String {
+    let mut out = String::with_capacity(full.len());
+    let mut segment_start = 0;
+    let mut chars = full.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            out.truncate(segment_start);
+        } else {
+            out.push(c);
+            if !(c.is_alphanumeric() || c == '_') {
+                segment_start = out.len();
+            }
+        }
+    }
+    out
+}
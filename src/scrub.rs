@@ -0,0 +1,67 @@
+//! GameClock scrubber: while paused, drag [`GameClock::elapsed`] back and forth and re-run
+//! the [`EvaluateAtTime`] schedule, for animation tools that are pure functions of the clock.
+
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+
+use crate::mode::{EditorMode, GameClock};
+
+/// Schedule re-run whenever the toolbar scrubber moves [`GameClock::elapsed`] in Pause.
+///
+/// Systems here should derive state from the clock alone, so scrubbing is deterministic:
+/// ```rust,ignore
+/// app.add_systems(EvaluateAtTime, pose_from_clock);
+/// app.add_systems(GameSchedule, pose_from_clock);
+/// ```
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct EvaluateAtTime;
+
+/// Scrub range and pending re-evaluation.
+#[derive(Resource, Default)]
+pub struct ScrubState {
+    /// Furthest [`GameClock::elapsed`] reached in the current Play session (the bar's end).
+    pub end: f32,
+    /// Set when the scrubber moved and [`EvaluateAtTime`] hasn't run yet.
+    pending: bool,
+}
+
+/// Extends the scrub range while playing.
+pub fn scrub_track_system(clock: Res<GameClock>, mut scrub: ResMut<ScrubState>) {
+    scrub.end = scrub.end.max(clock.elapsed);
+}
+
+/// Resets the scrub range on fresh Play.
+pub fn scrub_reset_system(mut scrub: ResMut<ScrubState>) {
+    *scrub = ScrubState::default();
+}
+
+/// Runs [`EvaluateAtTime`] after the scrubber moved.
+pub fn scrub_evaluate_system(world: &mut World) {
+    let mut scrub = world.resource_mut::<ScrubState>();
+    if !std::mem::take(&mut scrub.pending) {
+        return;
+    }
+    if *world.resource::<State<EditorMode>>().get() == EditorMode::Pause {
+        let _ = world.try_run_schedule(EvaluateAtTime);
+    }
+}
+
+/// Toolbar slider over `0..=end`. Returns `true` if the clock moved.
+pub(crate) fn scrub_bar_ui(
+    ui: &mut egui::Ui,
+    clock: &mut GameClock,
+    scrub: &mut ScrubState,
+    tooltip: &str,
+) -> bool {
+    let end = scrub.end.max(clock.elapsed);
+    let changed = ui
+        .add(
+            egui::Slider::new(&mut clock.elapsed, 0.0..=end)
+                .suffix(" s")
+                .fixed_decimals(2),
+        )
+        .on_hover_text(tooltip)
+        .changed();
+    scrub.pending |= changed;
+    changed
+}
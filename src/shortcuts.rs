@@ -0,0 +1,247 @@
+//! Keyboard shortcuts cheat sheet: a searchable, dockable panel listing the current
+//! `KeyBindings` and the shortcuts tools register with `WorkbenchApp::register_shortcut`,
+//! grouped by category. Opened from Help ▸ Keyboard Shortcuts or with the `shortcuts_help`
+//! binding (default: Shift+/, i.e. "?").
+
+use bevy::prelude::*;
+
+use crate::dock::{TileLayoutState, WorkbenchPanel};
+use crate::keybind::{BindingInput, KeyBind, KeyBindSlot, KeyBindings, KeyLayout};
+
+/// String ID of the shortcuts panel.
+pub const SHORTCUTS_PANEL: &str = "workbench_shortcuts";
+
+/// A shortcut registered by a tool, listed in the cheat sheet.
+#[derive(Debug, Clone)]
+pub struct ShortcutEntry {
+    /// Group heading, e.g. "Tilemap".
+    pub category: String,
+    /// What the shortcut does.
+    pub name: String,
+    pub bind: KeyBind,
+}
+
+/// Shortcuts registered by tools. Listing a shortcut here doesn't bind it; the tool still
+/// matches the key itself.
+#[derive(Resource, Default)]
+pub struct ShortcutRegistry {
+    entries: Vec<ShortcutEntry>,
+}
+
+impl ShortcutRegistry {
+    /// List `bind` as `name` under `category`.
+    pub fn register(
+        &mut self,
+        category: impl Into<String>,
+        name: impl Into<String>,
+        bind: KeyBind,
+    ) {
+        self.entries.push(ShortcutEntry {
+            category: category.into(),
+            name: name.into(),
+            bind,
+        });
+    }
+
+    /// Registered shortcuts, in registration order.
+    pub fn entries(&self) -> &[ShortcutEntry] {
+        &self.entries
+    }
+}
+
+/// One cheat sheet row.
+struct Row {
+    category: String,
+    name: String,
+    keys: String,
+}
+
+impl Row {
+    fn matches(&self, query: &str) -> bool {
+        [&self.category, &self.name, &self.keys]
+            .iter()
+            .any(|text| text.to_lowercase().contains(query))
+    }
+}
+
+/// Searchable cheat sheet of every shortcut.
+#[derive(Default)]
+pub struct ShortcutsPanel {
+    query: String,
+}
+
+impl WorkbenchPanel for ShortcutsPanel {
+    fn id(&self) -> &str {
+        SHORTCUTS_PANEL
+    }
+
+    fn title(&self) -> String {
+        "Keyboard Shortcuts".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let rows = collect_rows(world);
+        ui.add(
+            egui::TextEdit::singleline(&mut self.query)
+                .hint_text("Search shortcuts")
+                .desired_width(f32::INFINITY),
+        );
+        ui.separator();
+        let query = self.query.trim().to_lowercase();
+        let rows: Vec<&Row> = rows.iter().filter(|row| row.matches(&query)).collect();
+        if rows.is_empty() {
+            ui.weak("No matching shortcuts");
+            return;
+        }
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let mut categories: Vec<&str> = Vec::new();
+            for row in &rows {
+                if !categories.contains(&row.category.as_str()) {
+                    categories.push(&row.category);
+                }
+            }
+            for category in categories {
+                ui.strong(category);
+                category_grid_ui(ui, category, &rows);
+                ui.add_space(6.0);
+            }
+        });
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+/// Name / keys grid of the rows in `category`.
+fn category_grid_ui(ui: &mut egui::Ui, category: &str, rows: &[&Row]) {
+    egui::Grid::new(("workbench_shortcuts", category))
+        .num_columns(2)
+        .striped(true)
+        .spacing([24.0, 4.0])
+        .show(ui, |ui| {
+            for row in rows.iter().filter(|row| row.category == category) {
+                ui.label(&row.name);
+                ui.monospace(&row.keys);
+                ui.end_row();
+            }
+        });
+}
+
+/// Built-in bindings followed by registered shortcuts, with keys named for the layout.
+fn collect_rows(world: &World) -> Vec<Row> {
+    let default_bindings;
+    let bindings = match world.get_resource::<KeyBindings>() {
+        Some(bindings) => bindings,
+        None => {
+            default_bindings = KeyBindings::default();
+            &default_bindings
+        }
+    };
+    let layout = bindings.display_layout(world.get_resource::<KeyLayout>());
+    let mut rows: Vec<Row> = builtin_slots(bindings)
+        .into_iter()
+        .filter(|(_, _, slot)| !slot.bindings.is_empty())
+        .map(|(category, name, slot)| Row {
+            category: category.to_string(),
+            name,
+            keys: slot.label_with(layout),
+        })
+        .collect();
+    if let Some(registry) = world.get_resource::<ShortcutRegistry>() {
+        rows.extend(registry.entries().iter().map(|entry| Row {
+            category: entry.category.clone(),
+            name: entry.name.clone(),
+            keys: entry.bind.label_with(layout),
+        }));
+    }
+    rows
+}
+
+/// The workbench's own binding slots as `(category, name, slot)`.
+fn builtin_slots(bindings: &KeyBindings) -> Vec<(&'static str, String, &KeyBindSlot)> {
+    let mut slots = vec![
+        ("Edit", "Undo".to_string(), &bindings.undo),
+        ("Edit", "Redo".to_string(), &bindings.redo),
+        ("Edit", "Undo Layout".to_string(), &bindings.layout_undo),
+        ("Edit", "Redo Layout".to_string(), &bindings.layout_redo),
+        (
+            "Edit",
+            "Hold to Toggle Snap".to_string(),
+            &bindings.snap_toggle,
+        ),
+        ("Play", "Play / Stop".to_string(), &bindings.play_stop),
+        ("Play", "Pause / Resume".to_string(), &bindings.pause_resume),
+        ("Panels", "Next Panel".to_string(), &bindings.next_panel),
+        ("Panels", "Previous Panel".to_string(), &bindings.prev_panel),
+        (
+            "Panels",
+            "Maximize Panel".to_string(),
+            &bindings.maximize_panel,
+        ),
+        (
+            "Panels",
+            "Reopen Closed Panel".to_string(),
+            &bindings.reopen_panel,
+        ),
+        (
+            "Panels",
+            "Presentation Mode".to_string(),
+            &bindings.presentation_mode,
+        ),
+        (
+            "Panels",
+            "Keyboard Shortcuts".to_string(),
+            &bindings.shortcuts_help,
+        ),
+    ];
+    slots.extend(
+        bindings
+            .focus_panel
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| ("Panels", format!("Focus Panel {}", i + 1), slot)),
+    );
+    slots.extend([
+        ("Hierarchy", "Up".to_string(), &bindings.hierarchy_up),
+        ("Hierarchy", "Down".to_string(), &bindings.hierarchy_down),
+        (
+            "Hierarchy",
+            "Expand".to_string(),
+            &bindings.hierarchy_expand,
+        ),
+        (
+            "Hierarchy",
+            "Collapse".to_string(),
+            &bindings.hierarchy_collapse,
+        ),
+        (
+            "Hierarchy",
+            "Select".to_string(),
+            &bindings.hierarchy_select,
+        ),
+    ]);
+    slots
+}
+
+/// Shows or hides the shortcuts panel on the `shortcuts_help` binding.
+pub fn shortcuts_help_system(
+    input: BindingInput,
+    bindings: Res<KeyBindings>,
+    mut tile_state: ResMut<TileLayoutState>,
+) {
+    if !bindings.shortcuts_help.just_pressed(&input) {
+        return;
+    }
+    if tile_state.is_panel_visible(SHORTCUTS_PANEL) {
+        tile_state.close_panel(SHORTCUTS_PANEL);
+    } else {
+        tile_state.request_open_panel(SHORTCUTS_PANEL);
+    }
+}
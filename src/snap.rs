@@ -0,0 +1,169 @@
+//! Shared snapping settings for transform tools.
+//!
+//! [`SnapSettings`] holds the translate, rotate and scale steps. Gizmos and custom tools call its
+//! `snap_*` helpers so everything snaps the same way; holding the snap toggle keybinding inverts
+//! the enabled flags for as long as it is held.
+
+use bevy::prelude::*;
+
+/// Grid, angle and scale snapping shared by all transform tools.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct SnapSettings {
+    pub translate_enabled: bool,
+    /// Translation step in world units.
+    pub translate_step: f32,
+    pub rotate_enabled: bool,
+    /// Rotation step in degrees.
+    pub rotate_degrees: f32,
+    pub scale_enabled: bool,
+    /// Scale step (per axis).
+    pub scale_step: f32,
+    /// Whether the snap toggle keybinding is held (maintained by the workbench).
+    pub inverted: bool,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self {
+            translate_enabled: false,
+            translate_step: 1.0,
+            rotate_enabled: false,
+            rotate_degrees: 15.0,
+            scale_enabled: false,
+            scale_step: 0.1,
+            inverted: false,
+        }
+    }
+}
+
+/// Round `value` to the nearest multiple of `step` (no-op for non-positive steps).
+pub fn snap_value(value: f32, step: f32) -> f32 {
+    if step > 0.0 {
+        (value / step).round() * step
+    } else {
+        value
+    }
+}
+
+impl SnapSettings {
+    /// Whether translation snapping currently applies (accounts for the held toggle).
+    pub fn translate_active(&self) -> bool {
+        self.translate_enabled != self.inverted
+    }
+
+    /// Whether rotation snapping currently applies.
+    pub fn rotate_active(&self) -> bool {
+        self.rotate_enabled != self.inverted
+    }
+
+    /// Whether scale snapping currently applies.
+    pub fn scale_active(&self) -> bool {
+        self.scale_enabled != self.inverted
+    }
+
+    /// Snap a position or translation delta to the translate step.
+    pub fn snap_translation(&self, value: Vec3) -> Vec3 {
+        if !self.translate_active() {
+            return value;
+        }
+        let step = self.translate_step;
+        Vec3::new(
+            snap_value(value.x, step),
+            snap_value(value.y, step),
+            snap_value(value.z, step),
+        )
+    }
+
+    /// Snap an angle (radians) to the rotate step.
+    pub fn snap_angle(&self, radians: f32) -> f32 {
+        if !self.rotate_active() {
+            return radians;
+        }
+        snap_value(radians, self.rotate_degrees.to_radians())
+    }
+
+    /// Snap each Euler angle (YXZ) of a rotation to the rotate step.
+    pub fn snap_rotation(&self, rotation: Quat) -> Quat {
+        if !self.rotate_active() {
+            return rotation;
+        }
+        let (y, x, z) = rotation.to_euler(EulerRot::YXZ);
+        Quat::from_euler(
+            EulerRot::YXZ,
+            self.snap_angle(y),
+            self.snap_angle(x),
+            self.snap_angle(z),
+        )
+    }
+
+    /// Snap a scale to the scale step.
+    pub fn snap_scale(&self, value: Vec3) -> Vec3 {
+        if !self.scale_active() {
+            return value;
+        }
+        let step = self.scale_step;
+        Vec3::new(
+            snap_value(value.x, step),
+            snap_value(value.y, step),
+            snap_value(value.z, step),
+        )
+    }
+}
+
+/// Tracks the held snap toggle keybinding.
+pub fn snap_toggle_system(
+    keys: crate::keybind::BindingInput,
+    bindings: Res<crate::keybind::KeyBindings>,
+    mut snap: ResMut<SnapSettings>,
+) {
+    let held = bindings.snap_toggle.pressed(&keys);
+    if snap.inverted != held {
+        snap.inverted = held;
+    }
+}
+
+/// Compact toolbar widget: one toggle + step field per snap kind. Returns true if changed.
+pub fn snap_toolbar_ui(ui: &mut egui::Ui, snap: &mut SnapSettings) -> bool {
+    let before = snap.clone();
+    snap_toggle(ui, "⊞", "Snap translation", &mut snap.translate_enabled);
+    ui.add(
+        egui::DragValue::new(&mut snap.translate_step)
+            .speed(0.05)
+            .range(0.001..=f32::MAX),
+    );
+    snap_toggle(ui, "⟳", "Snap rotation", &mut snap.rotate_enabled);
+    ui.add(
+        egui::DragValue::new(&mut snap.rotate_degrees)
+            .speed(0.5)
+            .range(0.1..=180.0)
+            .suffix("°"),
+    );
+    snap_toggle(ui, "⤢", "Snap scale", &mut snap.scale_enabled);
+    ui.add(
+        egui::DragValue::new(&mut snap.scale_step)
+            .speed(0.01)
+            .range(0.001..=f32::MAX),
+    );
+    *snap != before
+}
+
+/// [`snap_toolbar_ui`] on the world's [`SnapSettings`], writing back only on edits.
+pub(crate) fn snap_toolbar_world_ui(ui: &mut egui::Ui, world: &mut World) {
+    let Some(mut snap) = world.get_resource_mut::<SnapSettings>() else {
+        return;
+    };
+    let mut edited = snap.clone();
+    if snap_toolbar_ui(ui, &mut edited) {
+        *snap = edited;
+    }
+}
+
+fn snap_toggle(ui: &mut egui::Ui, icon: &str, tooltip: &str, enabled: &mut bool) {
+    if ui
+        .selectable_label(*enabled, icon)
+        .on_hover_text(tooltip)
+        .clicked()
+    {
+        *enabled = !*enabled;
+    }
+}
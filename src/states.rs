@@ -0,0 +1,113 @@
+//! States panel: current value of registered `States` types, with buttons to force transitions.
+
+use bevy::prelude::*;
+
+use crate::dock::WorkbenchPanel;
+
+/// Forces a transition to one variant.
+type SetState = Box<dyn Fn(&mut World) + Send + Sync>;
+
+/// A `States` type registered with [`crate::WorkbenchApp::register_states`].
+struct StatesEntry {
+    type_name: String,
+    current: fn(&World) -> Option<String>,
+    variants: Vec<(String, SetState)>,
+}
+
+/// Registered `States` types shown by [`StatesPanel`].
+#[derive(Resource, Default)]
+pub struct StatesRegistry {
+    entries: Vec<StatesEntry>,
+}
+
+impl StatesRegistry {
+    /// Register `S` with the variants offered as transition buttons.
+    pub fn register<S: States>(&mut self, variants: impl IntoIterator<Item = S>) {
+        let type_name = crate::events::short_type_name::<S>();
+        if self.entries.iter().any(|e| e.type_name == type_name) {
+            return;
+        }
+        let variants = variants
+            .into_iter()
+            .map(|variant| {
+                let label = format!("{variant:?}");
+                let set: SetState = Box::new(move |world: &mut World| {
+                    if let Some(mut next) = world.get_resource_mut::<NextState<S>>() {
+                        next.set(variant.clone());
+                    }
+                });
+                (label, set)
+            })
+            .collect();
+        self.entries.push(StatesEntry {
+            type_name,
+            current: |world| {
+                world
+                    .get_resource::<State<S>>()
+                    .map(|state| format!("{:?}", state.get()))
+            },
+            variants,
+        });
+    }
+}
+
+/// Built-in panel listing registered `States` and their current values.
+pub struct StatesPanel;
+
+impl WorkbenchPanel for StatesPanel {
+    fn id(&self) -> &str {
+        "workbench_states"
+    }
+
+    fn title(&self) -> String {
+        "States".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let Some(registry) = world.remove_resource::<StatesRegistry>() else {
+            return;
+        };
+        if registry.entries.is_empty() {
+            ui.weak("No states registered (use app.register_states)");
+        }
+        let mut transition = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("workbench_states_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, entry) in registry.entries.iter().enumerate() {
+                        ui.strong(&entry.type_name);
+                        let current = (entry.current)(world);
+                        match &current {
+                            Some(value) => ui.monospace(value),
+                            None => ui.weak("not initialized"),
+                        };
+                        ui.horizontal_wrapped(|ui| {
+                            for (j, (label, _)) in entry.variants.iter().enumerate() {
+                                let active = current.as_deref() == Some(label.as_str());
+                                if ui.selectable_label(active, label).clicked() && !active {
+                                    transition = Some((i, j));
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+        if let Some((i, j)) = transition {
+            (registry.entries[i].variants[j].1)(world);
+        }
+        world.insert_resource(registry);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
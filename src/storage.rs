@@ -0,0 +1,86 @@
+//! Key-value persistence for settings and layouts: files on native, `localStorage` on the web.
+//!
+//! Keys are the same project-relative paths on every platform (e.g. `.workbench/layout.json`),
+//! so code that saves and loads editor state doesn't need to care about the target.
+
+use std::path::Path;
+
+/// Read a stored text value, or `None` if it doesn't exist.
+pub fn read_string(path: &Path) -> Option<String> {
+    backend::read_string(path)
+}
+
+/// Store a text value, creating parent directories on native.
+pub fn write_string(path: &Path, content: &str) -> Result<(), String> {
+    backend::write_string(path, content)
+}
+
+/// Keep a copy of a value that could not be read next to it (`settings.toml.bak`), so it is
+/// not lost when defaults are saved over it.
+pub fn back_up(path: &Path, content: &str) {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = Path::new(&backup);
+    match write_string(backup, content) {
+        Ok(()) => bevy::log::warn!("Kept the unreadable file as {}", backup.display()),
+        Err(e) => bevy::log::warn!("Failed to back up {}: {e}", path.display()),
+    }
+}
+
+/// Delete a stored value (no-op if missing).
+pub fn remove(path: &Path) {
+    backend::remove(path);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::path::Path;
+
+    pub fn read_string(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    pub fn write_string(path: &Path, content: &str) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        std::fs::write(path, content).map_err(|e| e.to_string())
+    }
+
+    pub fn remove(path: &Path) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use std::path::Path;
+
+    /// Prefix keeping workbench entries apart from the page's own storage.
+    const KEY_PREFIX: &str = "bevy_workbench:";
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    fn key(path: &Path) -> String {
+        format!("{KEY_PREFIX}{}", path.display())
+    }
+
+    pub fn read_string(path: &Path) -> Option<String> {
+        local_storage()?.get_item(&key(path)).ok().flatten()
+    }
+
+    pub fn write_string(path: &Path, content: &str) -> Result<(), String> {
+        let storage = local_storage().ok_or("localStorage is not available")?;
+        storage
+            .set_item(&key(path), content)
+            .map_err(|e| format!("{e:?}"))
+    }
+
+    pub fn remove(path: &Path) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(&key(path));
+        }
+    }
+}
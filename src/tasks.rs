@@ -0,0 +1,146 @@
+//! Background task runner with progress and cancellation shown in a status bar.
+
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use bevy_egui::EguiContexts;
+use std::future::Future;
+
+use crate::dialogs::ProgressToken;
+
+/// A task spawned through [`Tasks`].
+struct RunningTask {
+    name: String,
+    progress: ProgressToken,
+    /// Dropping the task cancels it at its next `.await`.
+    task: Task<()>,
+}
+
+/// Resource for running work off the UI thread.
+///
+/// ```rust,ignore
+/// fn bake(mut tasks: ResMut<Tasks>) {
+///     tasks.spawn_with("Baking lightmaps", |progress| async move {
+///         for i in 0..100 {
+///             if progress.is_cancelled() { return; }
+///             progress.set_progress(i as f32 / 100.0);
+///             // ...
+///         }
+///     });
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct Tasks {
+    running: Vec<RunningTask>,
+}
+
+impl Tasks {
+    /// Spawn a future on the async compute pool. The returned token can cancel it.
+    pub fn spawn(
+        &mut self,
+        name: impl Into<String>,
+        future: impl Future<Output = ()> + Send + 'static,
+    ) -> ProgressToken {
+        self.spawn_with(name, |_| future)
+    }
+
+    /// Spawn a future that reports progress and checks cancellation through a [`ProgressToken`].
+    pub fn spawn_with<Fut>(
+        &mut self,
+        name: impl Into<String>,
+        make: impl FnOnce(ProgressToken) -> Fut,
+    ) -> ProgressToken
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        let progress = ProgressToken::new(name.clone());
+        let task = AsyncComputeTaskPool::get().spawn(make(progress.clone()));
+        self.running.push(RunningTask {
+            name,
+            progress: progress.clone(),
+            task,
+        });
+        progress
+    }
+
+    /// Number of tasks still running.
+    pub fn len(&self) -> usize {
+        self.running.len()
+    }
+
+    /// Whether no task is running.
+    pub fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
+
+    /// Request cancellation of every running task.
+    pub fn cancel_all(&mut self) {
+        for task in &self.running {
+            task.progress.cancel();
+        }
+    }
+}
+
+/// Removes finished tasks and drops (hard-cancels) cancelled ones.
+pub fn tasks_poll_system(mut tasks: ResMut<Tasks>) {
+    if tasks.running.is_empty() {
+        return;
+    }
+    tasks.running.retain(|t| {
+        if t.progress.is_cancelled() {
+            info!("Task cancelled: {}", t.name);
+            return false;
+        }
+        if t.task.is_finished() {
+            info!("Task finished: {}", t.name);
+            return false;
+        }
+        true
+    });
+}
+
+/// Status bar listing running tasks. Only shown while something is running.
+pub fn task_status_bar_system(mut contexts: EguiContexts, tasks: Res<Tasks>) {
+    if tasks.running.is_empty() {
+        return;
+    }
+    let Ok(ctx) = contexts.ctx_mut() else { return };
+    egui::TopBottomPanel::bottom("workbench_status_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            let first = &tasks.running[0];
+            task_row_ui(ui, first);
+            if tasks.running.len() > 1 {
+                ui.menu_button(format!("+{} more", tasks.running.len() - 1), |ui| {
+                    for task in &tasks.running[1..] {
+                        ui.horizontal(|ui| task_row_ui(ui, task));
+                    }
+                });
+            }
+        });
+    });
+    // Keep progress moving while tasks report from other threads.
+    ctx.request_repaint();
+}
+
+fn task_row_ui(ui: &mut egui::Ui, task: &RunningTask) {
+    let (fraction, message) = task.progress.snapshot();
+    match fraction {
+        Some(fraction) => {
+            ui.add(
+                egui::ProgressBar::new(fraction)
+                    .desired_width(120.0)
+                    .show_percentage(),
+            );
+        }
+        None => {
+            ui.spinner();
+        }
+    }
+    ui.label(&task.name);
+    if !message.is_empty() {
+        ui.weak(message);
+    }
+    if ui.small_button("✖").on_hover_text("Cancel").clicked() {
+        task.progress.cancel();
+    }
+}
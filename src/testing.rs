@@ -0,0 +1,311 @@
+//! Headless test harness for driving `WorkbenchPanel`s without a window.
+
+use bevy::prelude::*;
+
+use crate::dock::WorkbenchPanel;
+
+/// Default size of the simulated screen, in logical points.
+const DEFAULT_SCREEN_SIZE: egui::Vec2 = egui::vec2(1280.0, 720.0);
+
+/// Simulated time advanced per frame, in seconds.
+const FRAME_TIME: f64 = 1.0 / 60.0;
+
+/// Runs one panel inside an offscreen egui context, next to a Bevy [`App`].
+///
+/// Each [`step`](Self::step) updates the app once and then runs one egui pass that draws the
+/// panel full-screen, feeding it any queued input events. Text drawn by the pass is recorded so
+/// tests can locate widgets by their label.
+///
+/// ```ignore
+/// let mut harness = WorkbenchTestHarness::new(CounterPanel);
+/// harness.app.insert_resource(Counter(0));
+/// harness.step();
+/// harness.click_text("Increment");
+/// assert_eq!(harness.app.world().resource::<Counter>().0, 1);
+/// ```
+pub struct WorkbenchTestHarness {
+    /// The app the panel reads from and writes to.
+    pub app: App,
+    ctx: egui::Context,
+    panel: Box<dyn WorkbenchPanel>,
+    screen_size: egui::Vec2,
+    modifiers: egui::Modifiers,
+    pending: Vec<egui::Event>,
+    time: f64,
+    texts: Vec<(String, egui::Rect)>,
+    output: Option<egui::FullOutput>,
+}
+
+impl WorkbenchTestHarness {
+    /// Harness around a fresh `App` with [`MinimalPlugins`].
+    pub fn new(panel: impl WorkbenchPanel) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        Self::with_app(app, panel)
+    }
+
+    /// Harness around an app configured by the caller.
+    pub fn with_app(mut app: App, panel: impl WorkbenchPanel) -> Self {
+        app.finish();
+        app.cleanup();
+        Self {
+            app,
+            ctx: egui::Context::default(),
+            panel: Box::new(panel),
+            screen_size: DEFAULT_SCREEN_SIZE,
+            modifiers: egui::Modifiers::NONE,
+            pending: Vec::new(),
+            time: 0.0,
+            texts: Vec::new(),
+            output: None,
+        }
+    }
+
+    /// Change the simulated screen size (takes effect on the next step).
+    pub fn set_screen_size(&mut self, size: egui::Vec2) {
+        self.screen_size = size;
+    }
+
+    /// The offscreen egui context (e.g. to tweak style or memory).
+    pub fn ctx(&self) -> &egui::Context {
+        &self.ctx
+    }
+
+    /// The panel under test, downcast to its concrete type.
+    pub fn panel<T: WorkbenchPanel>(&self) -> Option<&T> {
+        (self.panel.as_ref() as &dyn std::any::Any).downcast_ref::<T>()
+    }
+
+    /// Mutable access to the panel under test.
+    pub fn panel_mut<T: WorkbenchPanel>(&mut self) -> Option<&mut T> {
+        (self.panel.as_mut() as &mut dyn std::any::Any).downcast_mut::<T>()
+    }
+
+    /// Output of the last egui pass.
+    pub fn output(&self) -> Option<&egui::FullOutput> {
+        self.output.as_ref()
+    }
+
+    /// Update the app once and run one egui pass with all queued input.
+    pub fn step(&mut self) {
+        self.app.update();
+
+        let raw_input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                self.screen_size,
+            )),
+            time: Some(self.time),
+            predicted_dt: FRAME_TIME as f32,
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.pending),
+            focused: true,
+            ..Default::default()
+        };
+        let panel = &mut self.panel;
+        let world = self.app.world_mut();
+        let output = self.ctx.run(raw_input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| draw_panel(ui, panel.as_mut(), world));
+        });
+
+        self.texts.clear();
+        for clipped in &output.shapes {
+            collect_texts(&clipped.shape, &mut self.texts);
+        }
+        self.output = Some(output);
+        self.time += FRAME_TIME;
+    }
+
+    /// Step `frames` times.
+    pub fn run_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.step();
+        }
+    }
+
+    /// Every piece of text drawn in the last pass, with its screen rectangle.
+    pub fn texts(&self) -> &[(String, egui::Rect)] {
+        &self.texts
+    }
+
+    /// Rectangle of the first drawn text containing `text`.
+    pub fn find_text(&self, text: &str) -> Option<egui::Rect> {
+        self.texts
+            .iter()
+            .find(|(drawn, _)| drawn.contains(text))
+            .map(|(_, rect)| *rect)
+    }
+
+    /// Whether any text containing `text` was drawn in the last pass.
+    pub fn has_text(&self, text: &str) -> bool {
+        self.find_text(text).is_some()
+    }
+
+    /// Hold or release keyboard modifiers for subsequent input.
+    pub fn set_modifiers(&mut self, modifiers: egui::Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Queue a raw egui event for the next step.
+    pub fn push_event(&mut self, event: egui::Event) {
+        self.pending.push(event);
+    }
+
+    /// Move the pointer to `pos`, stepping once so widgets register the hover.
+    pub fn hover(&mut self, pos: egui::Pos2) {
+        self.push_event(egui::Event::PointerMoved(pos));
+        self.step();
+    }
+
+    /// Click the primary button at `pos` (hover step + press/release step).
+    pub fn click_at(&mut self, pos: egui::Pos2) {
+        self.hover(pos);
+        self.push_pointer_button(pos, true);
+        self.push_pointer_button(pos, false);
+        self.step();
+    }
+
+    /// Click the widget whose label contains `text`.
+    ///
+    /// # Panics
+    /// Panics if no such text was drawn in the last pass.
+    pub fn click_text(&mut self, text: &str) {
+        let rect = self
+            .find_text(text)
+            .unwrap_or_else(|| panic!("no text containing `{text}` was drawn"));
+        self.click_at(rect.center());
+    }
+
+    /// Type `text` into the focused widget and step once.
+    pub fn type_text(&mut self, text: &str) {
+        self.push_event(egui::Event::Text(text.to_string()));
+        self.step();
+    }
+
+    /// Press and release `key` (with the current modifiers) and step once.
+    pub fn press_key(&mut self, key: egui::Key) {
+        for pressed in [true, false] {
+            self.push_event(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: self.modifiers,
+            });
+        }
+        self.step();
+    }
+
+    fn push_pointer_button(&mut self, pos: egui::Pos2, pressed: bool) {
+        self.push_event(egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: self.modifiers,
+        });
+    }
+}
+
+/// Draw the panel the same way the dock does.
+fn draw_panel(ui: &mut egui::Ui, panel: &mut dyn WorkbenchPanel, world: &mut World) {
+    if panel.needs_world() {
+        panel.ui_world(ui, world);
+    } else {
+        panel.ui(ui);
+    }
+}
+
+/// Collect text shapes (recursing into shape lists) with their screen rectangles.
+fn collect_texts(shape: &egui::Shape, out: &mut Vec<(String, egui::Rect)>) {
+    match shape {
+        egui::Shape::Text(text) => {
+            let rect = text.galley.rect.translate(text.pos.to_vec2());
+            out.push((text.galley.text().to_string(), rect));
+        }
+        egui::Shape::Vec(shapes) => {
+            for shape in shapes {
+                collect_texts(shape, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    /// Name submitted with Enter.
+    #[derive(Resource, Default)]
+    struct Submitted(Option<String>);
+
+    /// Panel with a counter button and a name field.
+    #[derive(Default)]
+    struct FormPanel {
+        name: String,
+    }
+
+    impl WorkbenchPanel for FormPanel {
+        fn id(&self) -> &str {
+            "form"
+        }
+
+        fn title(&self) -> String {
+            "Form".into()
+        }
+
+        fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+        fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+            let count = world.resource::<Counter>().0;
+            ui.label(format!("Count: {count}"));
+            if ui.button("Increment").clicked() {
+                world.resource_mut::<Counter>().0 += 1;
+            }
+            let response = ui.add(egui::TextEdit::singleline(&mut self.name).hint_text("Name"));
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                world.resource_mut::<Submitted>().0 = Some(self.name.clone());
+            }
+        }
+
+        fn needs_world(&self) -> bool {
+            true
+        }
+    }
+
+    fn harness() -> WorkbenchTestHarness {
+        let mut harness = WorkbenchTestHarness::new(FormPanel::default());
+        harness.app.init_resource::<Counter>();
+        harness.app.init_resource::<Submitted>();
+        harness.step();
+        harness
+    }
+
+    #[test]
+    fn clicking_a_button_changes_the_world() {
+        let mut harness = harness();
+        assert!(harness.has_text("Count: 0"));
+        assert!(harness.find_text("Missing").is_none());
+        harness.click_text("Increment");
+        harness.click_text("Increment");
+        assert_eq!(harness.app.world().resource::<Counter>().0, 2);
+        harness.step();
+        assert!(harness.has_text("Count: 2"));
+    }
+
+    #[test]
+    fn typed_text_reaches_the_focused_field() {
+        let mut harness = harness();
+        harness.click_text("Name");
+        harness.type_text("Ferris");
+        assert_eq!(harness.panel::<FormPanel>().unwrap().name, "Ferris");
+        assert!(harness.has_text("Ferris"));
+
+        harness.press_key(egui::Key::Enter);
+        let submitted = &harness.app.world().resource::<Submitted>().0;
+        assert_eq!(submitted.as_deref(), Some("Ferris"));
+    }
+}
@@ -1,11 +1,23 @@
 //! Theme system for the workbench editor.
 //!
 //! Supports built-in themes (Rerun dark, egui Dark/Light) and Catppuccin palette themes.
-//! Each mode (Edit vs Play/Pause) can have a different theme.
+//! Each mode (Edit vs Play/Pause) can have a different theme. Semantic colors come from a
+//! selectable (optionally colorblind-safe) palette, see [`palette`]. [`ThemePreset::System`]
+//! follows the OS dark/light preference. Theme switches can cross-fade, see
+//! [`ThemeConfig::transition_duration`]; [`ThemeConfig::reduced_motion`] strips shadows and
+//! animations from any theme.
 
 use bevy::prelude::*;
 use egui::{Color32, Stroke, Vec2, epaint::Shadow};
 
+mod palette;
+mod performance;
+mod system;
+mod transition;
+
+pub use palette::{PalettePreset, SemanticPalette, palette};
+pub use system::system_theme_system;
+
 /// Available theme presets.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum ThemePreset {
@@ -24,10 +36,13 @@ pub enum ThemePreset {
     CatppuccinFrappe,
     /// Catppuccin Latte (light).
     CatppuccinLatte,
+    /// Follow the OS: `ThemeConfig::system_dark_theme` or `system_light_theme`.
+    System,
 }
 
 impl ThemePreset {
     /// All available presets.
+    #[cfg(feature = "catppuccin")]
     pub const ALL: &[ThemePreset] = &[
         ThemePreset::Rerun,
         ThemePreset::EguiDark,
@@ -36,6 +51,15 @@ impl ThemePreset {
         ThemePreset::CatppuccinMacchiato,
         ThemePreset::CatppuccinFrappe,
         ThemePreset::CatppuccinLatte,
+        ThemePreset::System,
+    ];
+    /// All available presets.
+    #[cfg(not(feature = "catppuccin"))]
+    pub const ALL: &[ThemePreset] = &[
+        ThemePreset::Rerun,
+        ThemePreset::EguiDark,
+        ThemePreset::EguiLight,
+        ThemePreset::System,
     ];
 
     pub fn label(&self) -> &'static str {
@@ -47,6 +71,7 @@ impl ThemePreset {
             ThemePreset::CatppuccinMacchiato => "Catppuccin Macchiato",
             ThemePreset::CatppuccinFrappe => "Catppuccin Frappé",
             ThemePreset::CatppuccinLatte => "Catppuccin Latte",
+            ThemePreset::System => "Follow System",
         }
     }
 }
@@ -66,12 +91,32 @@ pub struct ThemeConfig {
     /// Brightness for Play/Pause mode (0.0–1.0, default 0.6).
     #[serde(default = "default_play_brightness")]
     pub play_brightness: f32,
+    /// Semantic color palette (log levels, status, selection).
+    #[serde(default)]
+    pub palette: PalettePreset,
+    /// Theme used by [`ThemePreset::System`] while the OS prefers dark.
+    #[serde(default)]
+    pub system_dark_theme: ThemePreset,
+    /// Theme used by [`ThemePreset::System`] while the OS prefers light.
+    #[serde(default = "default_system_light_theme")]
+    pub system_light_theme: ThemePreset,
+    /// Seconds to cross-fade between themes on a switch, e.g. Edit ↔ Play (0 = instant).
+    #[serde(default)]
+    pub transition_duration: f32,
+    /// Performance / reduced-motion profile: no shadows, animations, hover growth or theme
+    /// cross-fades, whatever the preset.
+    #[serde(default)]
+    pub reduced_motion: bool,
 }
 
 fn default_play_theme() -> ThemePreset {
     ThemePreset::Rerun
 }
 
+fn default_system_light_theme() -> ThemePreset {
+    ThemePreset::EguiLight
+}
+
 fn default_brightness() -> f32 {
     1.0
 }
@@ -87,6 +132,11 @@ impl Default for ThemeConfig {
             play_theme: ThemePreset::Rerun,
             edit_brightness: 1.0,
             play_brightness: 0.6,
+            palette: PalettePreset::Default,
+            system_dark_theme: ThemePreset::Rerun,
+            system_light_theme: ThemePreset::EguiLight,
+            transition_duration: 0.0,
+            reduced_motion: false,
         }
     }
 }
@@ -98,6 +148,10 @@ pub struct ThemeState {
     pub interact_size: Option<Vec2>,
     /// Current theme configuration.
     pub config: ThemeConfig,
+    /// Whether the OS prefers a light theme, for [`ThemePreset::System`].
+    pub system_light: bool,
+    /// Cross-fade in progress.
+    transition: Option<transition::ThemeTransition>,
 }
 
 impl ThemeState {
@@ -177,7 +231,10 @@ pub fn apply_theme_to_ctx(
     brightness: f32,
 ) {
     match preset {
-        ThemePreset::Rerun => apply_rerun_theme(ctx, interact_size_override, brightness),
+        // Unresolved `System` (see `ThemePreset::resolve`) uses the default theme.
+        ThemePreset::Rerun | ThemePreset::System => {
+            apply_rerun_theme(ctx, interact_size_override, brightness)
+        }
         ThemePreset::EguiDark => {
             ctx.set_visuals(egui::Visuals::dark());
             apply_brightness_and_overrides(ctx, interact_size_override, brightness);
@@ -186,25 +243,43 @@ pub fn apply_theme_to_ctx(
             ctx.set_visuals(egui::Visuals::light());
             apply_brightness_and_overrides(ctx, interact_size_override, brightness);
         }
-        ThemePreset::CatppuccinMocha => {
-            catppuccin_egui::set_theme(ctx, catppuccin_egui::MOCHA);
-            apply_brightness_and_overrides(ctx, interact_size_override, brightness);
-        }
-        ThemePreset::CatppuccinMacchiato => {
-            catppuccin_egui::set_theme(ctx, catppuccin_egui::MACCHIATO);
-            apply_brightness_and_overrides(ctx, interact_size_override, brightness);
-        }
-        ThemePreset::CatppuccinFrappe => {
-            catppuccin_egui::set_theme(ctx, catppuccin_egui::FRAPPE);
-            apply_brightness_and_overrides(ctx, interact_size_override, brightness);
-        }
-        ThemePreset::CatppuccinLatte => {
-            catppuccin_egui::set_theme(ctx, catppuccin_egui::LATTE);
-            apply_brightness_and_overrides(ctx, interact_size_override, brightness);
+        ThemePreset::CatppuccinMocha
+        | ThemePreset::CatppuccinMacchiato
+        | ThemePreset::CatppuccinFrappe
+        | ThemePreset::CatppuccinLatte => {
+            apply_catppuccin_theme(ctx, preset, interact_size_override, brightness);
         }
     }
 }
 
+#[cfg(feature = "catppuccin")]
+fn apply_catppuccin_theme(
+    ctx: &egui::Context,
+    preset: ThemePreset,
+    interact_size_override: Option<Vec2>,
+    brightness: f32,
+) {
+    let theme = match preset {
+        ThemePreset::CatppuccinMacchiato => catppuccin_egui::MACCHIATO,
+        ThemePreset::CatppuccinFrappe => catppuccin_egui::FRAPPE,
+        ThemePreset::CatppuccinLatte => catppuccin_egui::LATTE,
+        _ => catppuccin_egui::MOCHA,
+    };
+    catppuccin_egui::set_theme(ctx, theme);
+    apply_brightness_and_overrides(ctx, interact_size_override, brightness);
+}
+
+/// Without the `catppuccin` feature, saved Catppuccin presets fall back to the default theme.
+#[cfg(not(feature = "catppuccin"))]
+fn apply_catppuccin_theme(
+    ctx: &egui::Context,
+    _preset: ThemePreset,
+    interact_size_override: Option<Vec2>,
+    brightness: f32,
+) {
+    apply_rerun_theme(ctx, interact_size_override, brightness);
+}
+
 /// Apply brightness dimming and interact_size override on top of an existing style.
 fn apply_brightness_and_overrides(
     ctx: &egui::Context,
@@ -365,26 +440,79 @@ fn apply_rerun_theme(ctx: &egui::Context, interact_size_override: Option<Vec2>,
     ctx.set_style(style);
 }
 
-/// System that applies the theme to the egui context (once on startup, then on changes).
-pub fn apply_theme_system(
-    mut contexts: bevy_egui::EguiContexts,
+/// Inputs of the last theme application: preset, brightness, interact size, palette, reduced
+/// motion.
+type AppliedTheme = (ThemePreset, f32, Option<Vec2>, PalettePreset, bool);
+
+/// Run condition for [`apply_theme_system`]: the theme or the editor mode changed, a new egui
+/// context (the primary one or a secondary window's) appeared, or a theme transition is running.
+pub fn theme_changed(
     theme: Res<ThemeState>,
     mode: Res<State<crate::mode::EditorMode>>,
-    mut applied: Local<bool>,
-    mut prev_mode: Local<Option<crate::mode::EditorMode>>,
+    new_context: Query<(), Added<bevy_egui::EguiContext>>,
+) -> bool {
+    theme.is_changed() || theme.transition.is_some() || mode.is_changed() || !new_context.is_empty()
+}
+
+/// System that applies the theme to every egui context. Runs only when [`theme_changed`], and
+/// skips rebuilding the style when the result would be identical (e.g. Play ↔ Pause); contexts
+/// added since the last run get the current theme directly. With a
+/// [`ThemeConfig::transition_duration`] the new theme fades in over the old one.
+pub fn apply_theme_system(
+    mut contexts: Query<&mut bevy_egui::EguiContext>,
+    mut theme: ResMut<ThemeState>,
+    time: Res<Time<Real>>,
+    mode: Res<State<crate::mode::EditorMode>>,
+    mut applied: Local<Option<AppliedTheme>>,
 ) {
-    let mode_changed = *prev_mode != Some(*mode.get());
-    if *applied && !theme.is_changed() && !mode_changed {
-        return;
-    }
-    *prev_mode = Some(*mode.get());
-    let Ok(ctx) = contexts.ctx_mut() else { return };
     let (preset, brightness) = match mode.get() {
         crate::mode::EditorMode::Edit => (theme.config.edit_theme, theme.config.edit_brightness),
         crate::mode::EditorMode::Play | crate::mode::EditorMode::Pause => {
             (theme.config.play_theme, theme.config.play_brightness)
         }
     };
-    apply_theme_to_ctx(ctx, preset, theme.interact_size, brightness);
-    *applied = true;
+    let preset = preset.resolve(&theme.config, theme.system_light);
+    let inputs = (
+        preset,
+        brightness,
+        theme.interact_size,
+        theme.config.palette,
+        theme.config.reduced_motion,
+    );
+    let changed = *applied != Some(inputs);
+    let ctxs: Vec<(bool, egui::Context)> = contexts
+        .iter_mut()
+        .map(|mut context| (context.is_added(), context.get_mut().clone()))
+        .collect();
+    let Some((_, first)) = ctxs.first() else {
+        return;
+    };
+    // Fade only between two themes of a context that already showed one.
+    let mut from = None;
+    for (added, ctx) in &ctxs {
+        if !changed && !added {
+            continue;
+        }
+        if applied.is_some() && !added && from.is_none() {
+            from = Some(ctx.style().visuals.clone());
+        }
+        apply_theme_to_ctx(ctx, preset, theme.interact_size, brightness);
+        palette::apply_palette(ctx, theme.config.palette, brightness);
+        performance::apply_motion(ctx, theme.config.reduced_motion);
+    }
+    if changed {
+        *applied = Some(inputs);
+        let duration = theme.config.transition_duration;
+        let animate = duration > 0.0 && !theme.config.reduced_motion;
+        let to = first.style().visuals.clone();
+        theme.transition = from
+            .filter(|_| animate)
+            .map(|from| transition::ThemeTransition::new(from, to, duration));
+    }
+    let ctxs: Vec<egui::Context> = ctxs.into_iter().map(|(_, ctx)| ctx).collect();
+    if let Some(transition) = theme.transition.as_mut()
+        && !transition.step(&ctxs, time.delta_secs())
+    {
+        theme.transition = None;
+    }
 }
@@ -0,0 +1,158 @@
+//! # palette.rs
+//!
+//! # palette.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Semantic colors (success / warn / error / info / selection / link) shared by every module
+//! instead of hard-coded `Color32` values. The palette is resolved from a `PalettePreset` and the
+//! active theme whenever the theme is applied, dimmed like the rest of the theme, stored in the
+//! egui context and read back with [`palette`]. The colorblind-safe presets also recolor egui's
+//! own warning, error, hyperlink and selection visuals.
+//!
+//! 语义颜色（成功 / 警告 / 错误 / 信息 / 选中 / 链接），供各模块统一使用以取代硬编码的 `Color32`。
+//! 每次应用主题时，调色板根据 `PalettePreset` 和当前主题解析，并像主题其他部分一样按亮度变暗，
+//! 存入 egui 上下文，通过 [`palette`] 读取。色盲友好预设还会同时重设 egui 自身的警告、错误、
+//! 超链接和选中样式。
+
+use egui::Color32;
+
+/// Semantic color presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum PalettePreset {
+    /// The theme's own colors.
+    #[default]
+    Default,
+    /// Okabe–Ito colors, safe for red–green color blindness (deuteranopia / protanopia).
+    RedGreenSafe,
+    /// Colors that avoid blue–yellow pairs (tritanopia).
+    BlueYellowSafe,
+    /// Saturated, maximally distinct colors.
+    HighContrast,
+}
+
+impl PalettePreset {
+    /// All available presets.
+    pub const ALL: &[PalettePreset] = &[
+        PalettePreset::Default,
+        PalettePreset::RedGreenSafe,
+        PalettePreset::BlueYellowSafe,
+        PalettePreset::HighContrast,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PalettePreset::Default => "Default",
+            PalettePreset::RedGreenSafe => "Red–Green Safe",
+            PalettePreset::BlueYellowSafe => "Blue–Yellow Safe",
+            PalettePreset::HighContrast => "High Contrast",
+        }
+    }
+}
+
+/// Colors with a meaning, resolved for the active theme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SemanticPalette {
+    /// Success, connected, passed.
+    pub success: Color32,
+    /// Warnings and in-progress attention (recording, rebinding).
+    pub warn: Color32,
+    /// Errors and failures.
+    pub error: Color32,
+    /// Informational / debug output.
+    pub info: Color32,
+    /// Selection and focus highlights.
+    pub selection: Color32,
+    /// Hyperlinks.
+    pub link: Color32,
+}
+
+impl SemanticPalette {
+    /// Palette of `preset` on top of `visuals`.
+    pub fn new(preset: PalettePreset, visuals: &egui::Visuals) -> Self {
+        let rgb = Color32::from_rgb;
+        match preset {
+            PalettePreset::Default if visuals.dark_mode => Self {
+                success: Color32::GREEN,
+                warn: Color32::YELLOW,
+                error: Color32::RED,
+                info: Color32::LIGHT_BLUE,
+                selection: rgb(100, 180, 255),
+                link: visuals.hyperlink_color,
+            },
+            PalettePreset::Default => Self {
+                success: Color32::DARK_GREEN,
+                warn: visuals.warn_fg_color,
+                error: visuals.error_fg_color,
+                info: Color32::DARK_BLUE,
+                selection: rgb(0, 90, 230),
+                link: visuals.hyperlink_color,
+            },
+            PalettePreset::RedGreenSafe => Self {
+                success: rgb(0x00, 0x9e, 0x73),
+                warn: rgb(0xe6, 0x9f, 0x00),
+                error: rgb(0xd5, 0x5e, 0x00),
+                info: rgb(0x56, 0xb4, 0xe9),
+                selection: rgb(0x00, 0x72, 0xb2),
+                link: rgb(0x56, 0xb4, 0xe9),
+            },
+            PalettePreset::BlueYellowSafe => Self {
+                success: rgb(0x00, 0x9e, 0x73),
+                warn: rgb(0xcc, 0x79, 0xa7),
+                error: rgb(0xd5, 0x00, 0x00),
+                info: rgb(0x88, 0xcc, 0xee),
+                selection: rgb(0x44, 0xaa, 0x99),
+                link: rgb(0x88, 0xcc, 0xee),
+            },
+            PalettePreset::HighContrast => Self {
+                success: rgb(0x00, 0xff, 0x66),
+                warn: rgb(0xff, 0xd7, 0x00),
+                error: rgb(0xff, 0x33, 0x33),
+                info: rgb(0x00, 0xe5, 0xff),
+                selection: rgb(0xff, 0x00, 0xff),
+                link: rgb(0x00, 0xe5, 0xff),
+            },
+        }
+    }
+
+    fn dimmed(self, brightness: f32) -> Self {
+        let dim = |c| super::dim_color(c, brightness);
+        Self {
+            success: dim(self.success),
+            warn: dim(self.warn),
+            error: dim(self.error),
+            info: dim(self.info),
+            selection: dim(self.selection),
+            link: dim(self.link),
+        }
+    }
+}
+
+fn palette_id() -> egui::Id {
+    egui::Id::new("workbench_semantic_palette")
+}
+
+/// The semantic palette of `ctx` (the dark default before a theme was applied).
+pub fn palette(ctx: &egui::Context) -> SemanticPalette {
+    ctx.data(|d| d.get_temp(palette_id()))
+        .unwrap_or_else(|| SemanticPalette::new(PalettePreset::Default, &egui::Visuals::dark()))
+}
+
+/// Resolve `preset` for the style just applied to `ctx` and store it; non-default presets also
+/// recolor egui's warning, error, hyperlink and selection visuals.
+pub(super) fn apply_palette(ctx: &egui::Context, preset: PalettePreset, brightness: f32) {
+    let palette = SemanticPalette::new(preset, &ctx.style().visuals).dimmed(brightness.min(1.0));
+    if preset != PalettePreset::Default {
+        ctx.style_mut(|style| {
+            let visuals = &mut style.visuals;
+            visuals.warn_fg_color = palette.warn;
+            visuals.error_fg_color = palette.error;
+            visuals.hyperlink_color = palette.link;
+            visuals.selection.bg_fill = palette.selection.gamma_multiply(0.6);
+            visuals.selection.stroke.color = palette.selection;
+        });
+    }
+    ctx.data_mut(|d| d.insert_temp(palette_id(), palette));
+}
@@ -0,0 +1,45 @@
+//! # performance.rs
+//!
+//! # performance.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Reduced-motion / performance profile, applied on top of whichever preset is active when
+//! `ThemeConfig::reduced_motion` is set. It removes window and popup shadows (expensive blurs
+//! on weak integrated GPUs), turns off egui's animations and smooth scrolling, and stops
+//! widgets from growing on hover. Theme cross-fades are skipped as well.
+//!
+//! 减少动效 / 性能配置，在设置 `ThemeConfig::reduced_motion` 时叠加在当前任意预设之上。它会去除
+//! 窗口与弹出层阴影（在弱集成显卡上开销较大的模糊），关闭 egui 的动画与平滑滚动，并禁止控件在悬停时
+//! 放大。主题淡入淡出过渡同样会被跳过。
+
+use egui::epaint::Shadow;
+
+/// Strip shadows, animations and hover expansion from the context's current style, or with
+/// `reduced == false` restore egui's animations (presets only reset the visuals).
+pub(crate) fn apply_motion(ctx: &egui::Context, reduced: bool) {
+    let mut style = (*ctx.style()).clone();
+    if !reduced {
+        let defaults = egui::Style::default();
+        style.animation_time = defaults.animation_time;
+        style.scroll_animation = defaults.scroll_animation;
+        ctx.set_style(style);
+        return;
+    }
+    style.animation_time = 0.0;
+    style.scroll_animation = egui::style::ScrollAnimation::none();
+    style.visuals.window_shadow = Shadow::NONE;
+    style.visuals.popup_shadow = Shadow::NONE;
+    for widget in [
+        &mut style.visuals.widgets.noninteractive,
+        &mut style.visuals.widgets.inactive,
+        &mut style.visuals.widgets.hovered,
+        &mut style.visuals.widgets.active,
+        &mut style.visuals.widgets.open,
+    ] {
+        widget.expansion = 0.0;
+    }
+    ctx.set_style(style);
+}
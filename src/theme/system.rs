@@ -0,0 +1,102 @@
+//! # system.rs
+//!
+//! # system.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Follows the OS dark/light preference for `ThemePreset::System`. The preference comes from
+//! winit through the primary window: its `window_theme` when it is created and every
+//! `WindowThemeChanged` message afterwards. It is stored in `ThemeState::system_light`, so a
+//! switch of the OS appearance re-applies the theme at runtime with the configured dark or
+//! light preset.
+//!
+//! 为 `ThemePreset::System` 跟随操作系统的深色/浅色偏好。偏好由 winit 经主窗口提供：创建时读取其
+//! `window_theme`，之后读取每条 `WindowThemeChanged` 消息。结果存入 `ThemeState::system_light`，
+//! 因此切换系统外观时会在运行时以配置的深色或浅色预设重新应用主题。
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowTheme, WindowThemeChanged};
+
+use super::{ThemeConfig, ThemePreset, ThemeState};
+
+impl ThemePreset {
+    /// The preset `self` stands for: [`ThemePreset::System`] picks the dark or light preset of
+    /// `config` following the OS preference.
+    pub fn resolve(self, config: &ThemeConfig, system_light: bool) -> ThemePreset {
+        let preset = match self {
+            ThemePreset::System if system_light => config.system_light_theme,
+            ThemePreset::System => config.system_dark_theme,
+            preset => preset,
+        };
+        if preset == ThemePreset::System {
+            ThemePreset::default()
+        } else {
+            preset
+        }
+    }
+}
+
+/// Records the OS dark/light preference of the primary window in [`ThemeState`].
+pub fn system_theme_system(
+    mut changed: MessageReader<WindowThemeChanged>,
+    windows: Query<(Entity, Ref<Window>), With<PrimaryWindow>>,
+    mut theme: ResMut<ThemeState>,
+) {
+    let Ok((primary, window)) = windows.single() else {
+        changed.clear();
+        return;
+    };
+    let mut os_theme = window.is_added().then_some(window.window_theme).flatten();
+    for message in changed.read() {
+        if message.window == primary {
+            os_theme = Some(message.theme);
+        }
+    }
+    let Some(os_theme) = os_theme else {
+        return;
+    };
+    let light = os_theme == WindowTheme::Light;
+    if theme.system_light != light {
+        theme.system_light = light;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_follows_the_os_preference() {
+        let config = ThemeConfig {
+            system_dark_theme: ThemePreset::CatppuccinMocha,
+            system_light_theme: ThemePreset::CatppuccinLatte,
+            ..default()
+        };
+        assert_eq!(
+            ThemePreset::System.resolve(&config, false),
+            ThemePreset::CatppuccinMocha
+        );
+        assert_eq!(
+            ThemePreset::System.resolve(&config, true),
+            ThemePreset::CatppuccinLatte
+        );
+        assert_eq!(
+            ThemePreset::EguiDark.resolve(&config, true),
+            ThemePreset::EguiDark
+        );
+    }
+
+    #[test]
+    fn system_never_resolves_to_itself() {
+        let config = ThemeConfig {
+            system_light_theme: ThemePreset::System,
+            ..default()
+        };
+        assert_eq!(
+            ThemePreset::System.resolve(&config, true),
+            ThemePreset::default()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! # transition.rs
+//!
+//! # transition.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Cross-fade between two themes. When the applied theme changes (typically on an Edit ↔ Play
+//! switch) and `ThemeConfig::transition_duration` is positive, `apply_theme_system` starts a
+//! `ThemeTransition` from the visuals on screen to the new ones and steps it every frame, blending
+//! fills, strokes and text colors. Sizes, rounding and other non-color settings switch at once.
+//! A theme change during a transition starts the next one from the blended visuals.
+//!
+//! 两个主题之间的淡入淡出。当应用的主题发生变化（通常是编辑 ↔ 运行切换）且
+//! `ThemeConfig::transition_duration` 为正数时，`apply_theme_system` 会从当前显示的样式到新样式启动
+//! 一个 `ThemeTransition`，并逐帧推进，混合填充、描边与文字颜色。尺寸、圆角等非颜色设置立即切换。
+//! 过渡期间再次切换主题时，下一次过渡从混合后的样式开始。
+
+use egui::{Color32, Stroke, Visuals, style::WidgetVisuals};
+
+/// A running cross-fade of the egui visuals.
+#[derive(Debug, Clone)]
+pub(crate) struct ThemeTransition {
+    from: Visuals,
+    to: Visuals,
+    /// Length of the fade in seconds.
+    duration: f32,
+    elapsed: f32,
+}
+
+impl ThemeTransition {
+    pub(crate) fn new(from: Visuals, to: Visuals, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advance by `dt` seconds and show the blended visuals in every context. Returns `false`
+    /// once finished.
+    pub(crate) fn step(&mut self, contexts: &[egui::Context], dt: f32) -> bool {
+        self.elapsed += dt;
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        // Smoothstep easing.
+        let eased = t * t * (3.0 - 2.0 * t);
+        let visuals = lerp_visuals(&self.from, &self.to, eased);
+        for ctx in contexts {
+            let mut style = (*ctx.style()).clone();
+            style.visuals = visuals.clone();
+            ctx.set_style(style);
+        }
+        t < 1.0
+    }
+}
+
+fn lerp_color(from: Color32, to: Color32, t: f32) -> Color32 {
+    from.lerp_to_gamma(to, t)
+}
+
+fn lerp_stroke(from: Stroke, to: Stroke, t: f32) -> Stroke {
+    Stroke::new(to.width, lerp_color(from.color, to.color, t))
+}
+
+fn lerp_widget(from: &WidgetVisuals, to: &mut WidgetVisuals, t: f32) {
+    to.weak_bg_fill = lerp_color(from.weak_bg_fill, to.weak_bg_fill, t);
+    to.bg_fill = lerp_color(from.bg_fill, to.bg_fill, t);
+    to.bg_stroke = lerp_stroke(from.bg_stroke, to.bg_stroke, t);
+    to.fg_stroke = lerp_stroke(from.fg_stroke, to.fg_stroke, t);
+}
+
+/// `to` with its colors blended from `from` by `t`.
+fn lerp_visuals(from: &Visuals, to: &Visuals, t: f32) -> Visuals {
+    let mut out = to.clone();
+    out.faint_bg_color = lerp_color(from.faint_bg_color, to.faint_bg_color, t);
+    out.extreme_bg_color = lerp_color(from.extreme_bg_color, to.extreme_bg_color, t);
+    out.code_bg_color = lerp_color(from.code_bg_color, to.code_bg_color, t);
+    out.panel_fill = lerp_color(from.panel_fill, to.panel_fill, t);
+    out.window_fill = lerp_color(from.window_fill, to.window_fill, t);
+    out.window_stroke = lerp_stroke(from.window_stroke, to.window_stroke, t);
+    out.hyperlink_color = lerp_color(from.hyperlink_color, to.hyperlink_color, t);
+    out.warn_fg_color = lerp_color(from.warn_fg_color, to.warn_fg_color, t);
+    out.error_fg_color = lerp_color(from.error_fg_color, to.error_fg_color, t);
+    out.selection.bg_fill = lerp_color(from.selection.bg_fill, to.selection.bg_fill, t);
+    out.selection.stroke = lerp_stroke(from.selection.stroke, to.selection.stroke, t);
+    if let (Some(from), Some(to)) = (from.text_edit_bg_color, to.text_edit_bg_color) {
+        out.text_edit_bg_color = Some(lerp_color(from, to, t));
+    }
+    lerp_widget(
+        &from.widgets.noninteractive,
+        &mut out.widgets.noninteractive,
+        t,
+    );
+    lerp_widget(&from.widgets.inactive, &mut out.widgets.inactive, t);
+    lerp_widget(&from.widgets.hovered, &mut out.widgets.hovered, t);
+    lerp_widget(&from.widgets.active, &mut out.widgets.active, t);
+    lerp_widget(&from.widgets.open, &mut out.widgets.open, t);
+    out
+}
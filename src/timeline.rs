@@ -0,0 +1,319 @@
+//! Session timeline panel: mode changes, undo entries, panel open/close and logged errors
+//! along a time axis, clickable to jump the undo stack or reveal the log entry.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::WorkbenchApp;
+use crate::console::{ConsoleState, LogLevel};
+use crate::dock::{PanelSlot, TileLayoutState, WorkbenchPanel};
+use crate::mode::EditorMode;
+use crate::undo::UndoStack;
+
+/// Maximum number of events kept; the oldest are dropped first.
+const MAX_EVENTS: usize = 5000;
+/// Height of one lane of the timeline strip, in points.
+const LANE_HEIGHT: f32 = 14.0;
+
+/// Records [`SessionTimeline`] events and registers [`TimelinePanel`] (added by `WorkbenchPlugin`).
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SessionTimeline>()
+            .add_systems(Last, timeline_record_system);
+        app.register_panel(TimelinePanel::default());
+    }
+}
+
+/// What happened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineEventKind {
+    /// The editor entered a mode.
+    Mode(EditorMode),
+    /// An undo entry was pushed; jumping restores the state right after it, named by its
+    /// [`UndoStack::depth`].
+    Undo { depth: usize },
+    /// A panel was opened (`true`) or closed (`false`).
+    Panel { id: String, opened: bool },
+    /// An error was logged; `entry` is its number in [`ConsoleState::received_count`] order.
+    Error { entry: u64 },
+}
+
+impl TimelineEventKind {
+    /// Lane index and color on the timeline strip.
+    fn lane(&self, palette: &crate::theme::SemanticPalette) -> (usize, egui::Color32) {
+        match self {
+            Self::Mode(_) => (0, palette.info),
+            Self::Undo { .. } => (1, palette.success),
+            Self::Panel { .. } => (2, egui::Color32::GRAY),
+            Self::Error { .. } => (3, palette.error),
+        }
+    }
+}
+
+const LANES: [&str; 4] = ["Mode", "Undo", "Panels", "Errors"];
+
+/// One timeline event.
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    /// Real time since startup, in seconds.
+    pub time: f32,
+    pub kind: TimelineEventKind,
+    pub label: String,
+}
+
+/// Editor events of the current session, sampled in `Last`.
+#[derive(Resource, Default)]
+pub struct SessionTimeline {
+    pub events: Vec<TimelineEvent>,
+    last_undo_push: u64,
+    last_log_received: u64,
+    panels: HashMap<String, bool>,
+}
+
+impl SessionTimeline {
+    /// Append an event at `time`.
+    pub fn push(&mut self, time: f32, kind: TimelineEventKind, label: impl Into<String>) {
+        self.events.push(TimelineEvent {
+            time,
+            kind,
+            label: label.into(),
+        });
+        if self.events.len() > MAX_EVENTS {
+            self.events.drain(..self.events.len() - MAX_EVENTS);
+        }
+    }
+}
+
+fn timeline_record_system(
+    time: Res<Time<Real>>,
+    mut timeline: ResMut<SessionTimeline>,
+    mut transitions: MessageReader<StateTransitionEvent<EditorMode>>,
+    undo: Option<Res<UndoStack>>,
+    console: Option<Res<ConsoleState>>,
+    tiles: Option<Res<TileLayoutState>>,
+) {
+    let now = time.elapsed_secs();
+    for transition in transitions.read() {
+        if let Some(mode) = transition.entered
+            && transition.exited != transition.entered
+        {
+            timeline.push(now, TimelineEventKind::Mode(mode), format!("{mode:?}"));
+        }
+    }
+
+    if let Some(undo) = undo
+        && undo.push_count() != timeline.last_undo_push
+    {
+        let pushed = (undo.push_count() - timeline.last_undo_push) as usize;
+        timeline.last_undo_push = undo.push_count();
+        let history = undo.undo_history();
+        let pushed = pushed.min(history.len());
+        let first_depth = undo.depth() + 1 - pushed;
+        for (offset, label) in history[history.len() - pushed..].iter().enumerate() {
+            let kind = TimelineEventKind::Undo {
+                depth: first_depth + offset,
+            };
+            timeline.push(now, kind, *label);
+        }
+    }
+
+    if let Some(console) = console {
+        let received = console.received_count();
+        let new = (received - timeline.last_log_received).min(console.logs.len() as u64);
+        timeline.last_log_received = received;
+        let first = received - new;
+        let skip = console.logs.len() - new as usize;
+        for (offset, entry) in console.logs.iter().skip(skip).enumerate() {
+            if entry.level == LogLevel::Error {
+                let kind = TimelineEventKind::Error {
+                    entry: first + offset as u64,
+                };
+                timeline.push(now, kind, entry.message.clone());
+            }
+        }
+    }
+
+    if let Some(tiles) = tiles {
+        for (id, title, visible) in tiles.panel_list() {
+            let previous = timeline.panels.insert(id.clone(), visible);
+            if previous.is_some_and(|was| was != visible) {
+                let verb = if visible { "Opened" } else { "Closed" };
+                let kind = TimelineEventKind::Panel {
+                    id,
+                    opened: visible,
+                };
+                timeline.push(now, kind, format!("{verb} {title}"));
+            }
+        }
+    }
+}
+
+/// Built-in panel showing [`SessionTimeline`], for understanding "what did I just do".
+#[derive(Default)]
+pub struct TimelinePanel {
+    /// Event highlighted in the list (index into `events`).
+    selected: Option<usize>,
+}
+
+impl WorkbenchPanel for TimelinePanel {
+    fn id(&self) -> &str {
+        "workbench_timeline"
+    }
+
+    fn title(&self) -> String {
+        "Timeline".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let Some(timeline) = world.get_resource::<SessionTimeline>() else {
+            return;
+        };
+        let now = world.resource::<Time<Real>>().elapsed_secs();
+        let mut clicked = timeline_strip_ui(ui, timeline, now);
+        ui.separator();
+        let list_clicked = self.event_list_ui(ui, timeline);
+        clicked = clicked.or(list_clicked);
+        if let Some(index) = clicked {
+            self.selected = Some(index);
+            let kind = timeline.events[index].kind.clone();
+            activate(world, &kind);
+        }
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_slot(&self) -> PanelSlot {
+        PanelSlot::Bottom
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+impl TimelinePanel {
+    /// Newest-first event list. Returns the clicked event.
+    fn event_list_ui(&self, ui: &mut egui::Ui, timeline: &SessionTimeline) -> Option<usize> {
+        let mut clicked = None;
+        let row_height = ui.text_style_height(&egui::TextStyle::Body);
+        let count = timeline.events.len();
+        let palette = crate::theme::palette(ui.ctx());
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show_rows(ui, row_height, count, |ui, range| {
+                for row in range {
+                    let index = count - 1 - row;
+                    let event = &timeline.events[index];
+                    let (lane, color) = event.kind.lane(&palette);
+                    ui.horizontal(|ui| {
+                        ui.monospace(format_time(event.time));
+                        ui.colored_label(color, LANES[lane]);
+                        let response = ui
+                            .selectable_label(self.selected == Some(index), &event.label)
+                            .on_hover_text(hint(&event.kind));
+                        if response.clicked() {
+                            clicked = Some(index);
+                        }
+                    });
+                }
+            });
+        clicked
+    }
+}
+
+/// Lanes of event markers over the session so far. Returns the clicked event.
+fn timeline_strip_ui(ui: &mut egui::Ui, timeline: &SessionTimeline, now: f32) -> Option<usize> {
+    let label_width = 56.0;
+    let size = egui::vec2(ui.available_width(), LANE_HEIGHT * LANES.len() as f32);
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::click());
+    let rect = response.rect;
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let axis = egui::Rangef::new(rect.left() + label_width, rect.right() - 4.0);
+    let font = egui::FontId::proportional(10.0);
+    for (lane, name) in LANES.iter().enumerate() {
+        let y = rect.top() + (lane as f32 + 0.5) * LANE_HEIGHT;
+        painter.text(
+            egui::pos2(rect.left() + 4.0, y),
+            egui::Align2::LEFT_CENTER,
+            *name,
+            font.clone(),
+            ui.visuals().weak_text_color(),
+        );
+    }
+    let span = now.max(1.0);
+    let pointer = response.hover_pos();
+    let palette = crate::theme::palette(ui.ctx());
+    let mut hovered = None;
+    for (index, event) in timeline.events.iter().enumerate() {
+        let (lane, color) = event.kind.lane(&palette);
+        let x = egui::remap(event.time, 0.0..=span, axis.into());
+        let y = rect.top() + (lane as f32 + 0.5) * LANE_HEIGHT;
+        let center = egui::pos2(x, y);
+        painter.circle_filled(center, 3.0, color);
+        if pointer.is_some_and(|p| p.distance(center) < 5.0) {
+            hovered = Some(index);
+        }
+    }
+    let hovered_event = hovered.map(|i| &timeline.events[i]);
+    if let Some(event) = hovered_event {
+        response.clone().on_hover_text(format!(
+            "{} {}\n{}",
+            format_time(event.time),
+            event.label,
+            hint(&event.kind)
+        ));
+    }
+    hovered.filter(|_| response.clicked())
+}
+
+/// What clicking an event does.
+fn hint(kind: &TimelineEventKind) -> &'static str {
+    match kind {
+        TimelineEventKind::Undo { .. } => "Click to jump the undo stack to this entry",
+        TimelineEventKind::Error { .. } => "Click to reveal in the console",
+        TimelineEventKind::Panel { opened: true, .. } => "Click to focus the panel",
+        _ => "",
+    }
+}
+
+fn activate(world: &mut World, kind: &TimelineEventKind) {
+    match kind {
+        TimelineEventKind::Undo { depth } => {
+            if let Some(mut undo) = world.get_resource_mut::<UndoStack>()
+                && let Some(index) = undo.index_of_depth(*depth)
+            {
+                undo.jump_requested = Some(index);
+            }
+        }
+        TimelineEventKind::Error { entry } => {
+            let Some(mut console) = world.get_resource_mut::<ConsoleState>() else {
+                return;
+            };
+            let first_buffered = console.received_count() - console.logs.len() as u64;
+            let Some(index) = entry.checked_sub(first_buffered) else {
+                return;
+            };
+            console.reveal_entry(index as usize);
+            world
+                .resource_mut::<TileLayoutState>()
+                .open_or_focus_panel("workbench_console");
+        }
+        TimelineEventKind::Panel { id, opened: true } => {
+            world
+                .resource_mut::<TileLayoutState>()
+                .open_or_focus_panel(id);
+        }
+        _ => {}
+    }
+}
+
+/// `MM:SS.s` since startup.
+fn format_time(secs: f32) -> String {
+    format!("{:02}:{:04.1}", (secs / 60.0) as u32, secs % 60.0)
+}
@@ -2,7 +2,13 @@
 
 use bevy::ecs::component::Mutable;
 use bevy::prelude::*;
-use bevy_egui::egui;
+
+mod branches;
+mod history_panel;
+mod layout_history;
+
+pub use history_panel::UndoHistoryPanel;
+pub use layout_history::{LayoutUndoStack, layout_undo_input_system};
 
 /// Trait for undo/redo actions.
 pub trait UndoAction: Send + Sync + 'static {
@@ -12,6 +18,20 @@ pub trait UndoAction: Send + Sync + 'static {
     fn redo(&self, world: &mut World);
     /// Human-readable description for UI display.
     fn description(&self) -> &str;
+
+    /// Serialized form for autosave, or `None` if this action can't outlive the session.
+    /// Restoring needs a deserializer registered with
+    /// [`WorkbenchApp::register_undo_kind`](crate::WorkbenchApp::register_undo_kind).
+    fn serialize(&self) -> Option<SerializedAction> {
+        None
+    }
+}
+
+/// An undo action serialized for autosave: a registered `kind` plus its payload.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SerializedAction {
+    pub kind: String,
+    pub data: String,
 }
 
 /// Undo action for a component change on a mutable component.
@@ -143,6 +163,22 @@ pub struct UndoStack {
     pub redo_requested: bool,
     /// Set to request jumping to a specific history index.
     pub jump_requested: Option<usize>,
+    /// Set to request switching to a branch (position in [`UndoStack::branches`]).
+    pub branch_requested: Option<usize>,
+    /// Keep redo entries as a branch when a new action is pushed, instead of discarding them.
+    /// See [`UndoStack::switch_branch`].
+    pub branching: bool,
+    /// Record dock layout changes in the [`LayoutUndoStack`] instead of this stack, so undo only
+    /// steps through world and data edits (default: true).
+    pub separate_layout: bool,
+    /// Number of actions pushed since startup.
+    pushed: u64,
+    /// Number of actions dropped from the bottom of the stack by `max_history`.
+    trimmed: usize,
+    /// Named savepoints, by absolute history depth (`trimmed` + index).
+    checkpoints: Vec<(usize, String)>,
+    /// Stashed alternate timelines (branching mode).
+    branches: Vec<branches::Branch>,
 }
 
 impl Default for UndoStack {
@@ -154,6 +190,13 @@ impl Default for UndoStack {
             undo_requested: false,
             redo_requested: false,
             jump_requested: None,
+            branch_requested: None,
+            branching: false,
+            separate_layout: true,
+            pushed: 0,
+            trimmed: 0,
+            checkpoints: Vec::new(),
+            branches: Vec::new(),
         }
     }
 }
@@ -187,22 +230,66 @@ impl UndoStack {
 
     /// Push a custom undo action.
     pub fn push(&mut self, action: impl UndoAction) {
-        self.redo_stack.clear();
-        self.undo_stack.push(Box::new(action));
-        if self.undo_stack.len() > self.max_history {
-            self.undo_stack.remove(0);
-        }
+        self.push_boxed(Box::new(action));
     }
 
     /// Push a boxed undo action.
     pub fn push_boxed(&mut self, action: Box<dyn UndoAction>) {
-        self.redo_stack.clear();
+        if self.branching {
+            self.stash_redo();
+        } else {
+            self.redo_stack.clear();
+            // Checkpoints and branches along the discarded redo entries can't be reached any more.
+            let depth = self.trimmed + self.undo_stack.len();
+            self.checkpoints.retain(|(at, _)| *at <= depth);
+            self.branches.retain(|branch| branch.depth() <= depth);
+        }
+        self.pushed += 1;
         self.undo_stack.push(action);
         if self.undo_stack.len() > self.max_history {
             self.undo_stack.remove(0);
+            self.trimmed += 1;
+            let trimmed = self.trimmed;
+            self.checkpoints.retain(|(at, _)| *at >= trimmed);
+            self.trim_branches();
         }
     }
 
+    /// Mark the current state with a named savepoint, e.g. `checkpoint("Before import")` ahead
+    /// of a destructive bulk operation. It is shown in the Undo History panel and can be
+    /// returned to with [`UndoStack::revert_to_checkpoint`] while its history is kept.
+    pub fn checkpoint(&mut self, name: impl Into<String>) {
+        let depth = self.trimmed + self.undo_stack.len();
+        self.checkpoints.push((depth, name.into()));
+        self.checkpoints.sort_by_key(|(at, _)| *at);
+    }
+
+    /// Checkpoints as `(history index, name)`, oldest first. History indices are those of
+    /// [`UndoStack::jump_to`].
+    pub fn checkpoints(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.checkpoints
+            .iter()
+            .map(|(at, name)| (at - self.trimmed, name.as_str()))
+    }
+
+    /// History index of the newest checkpoint called `name`.
+    pub fn checkpoint_index(&self, name: &str) -> Option<usize> {
+        self.checkpoints()
+            .filter(|(_, checkpoint)| *checkpoint == name)
+            .map(|(index, _)| index)
+            .last()
+    }
+
+    /// Undo (or redo) back to the newest checkpoint called `name`. Returns `false` if there is
+    /// no such checkpoint.
+    pub fn revert_to_checkpoint(&mut self, name: &str, world: &mut World) -> bool {
+        let Some(index) = self.checkpoint_index(name) else {
+            return false;
+        };
+        self.jump_to(index, world);
+        true
+    }
+
     /// Undo the last action.
     pub fn undo(&mut self, world: &mut World) {
         if let Some(action) = self.undo_stack.pop() {
@@ -223,6 +310,13 @@ impl UndoStack {
     pub fn clear(&mut self) {
         self.undo_stack.clear();
         self.redo_stack.clear();
+        self.checkpoints.clear();
+        self.branches.clear();
+    }
+
+    /// Number of actions pushed since startup (not reduced by undo, trimming or clear).
+    pub fn push_count(&self) -> u64 {
+        self.pushed
     }
 
     /// Whether there are actions to undo.
@@ -245,6 +339,21 @@ impl UndoStack {
         self.redo_stack.len()
     }
 
+    /// Absolute depth of the current state: its history index plus the actions trimmed by
+    /// `max_history`. Unlike a history index it keeps naming the same entry as the stack is
+    /// trimmed.
+    pub fn depth(&self) -> usize {
+        self.trimmed + self.undo_stack.len()
+    }
+
+    /// History index (see [`UndoStack::jump_to`]) of an absolute [`UndoStack::depth`], if that
+    /// state is still in the history.
+    pub fn index_of_depth(&self, depth: usize) -> Option<usize> {
+        depth
+            .checked_sub(self.trimmed)
+            .filter(|&index| index <= self.undo_stack.len() + self.redo_stack.len())
+    }
+
     /// Description of the last undo-able action.
     pub fn undo_description(&self) -> Option<&str> {
         self.undo_stack.last().map(|a| a.description())
@@ -255,6 +364,19 @@ impl UndoStack {
         self.redo_stack.last().map(|a| a.description())
     }
 
+    /// Serialized trailing run of the undo stack (oldest first). Stops at the newest action
+    /// that can't be serialized, so restored history never skips a step.
+    pub(crate) fn serialize_tail(&self) -> Vec<SerializedAction> {
+        let mut tail: Vec<_> = self
+            .undo_stack
+            .iter()
+            .rev()
+            .map_while(|action| action.serialize())
+            .collect();
+        tail.reverse();
+        tail
+    }
+
     /// Returns descriptions of all undo entries (oldest first).
     pub fn undo_history(&self) -> Vec<&str> {
         self.undo_stack.iter().map(|a| a.description()).collect()
@@ -296,158 +418,200 @@ pub fn undo_input_system(world: &mut World) {
         .get_resource::<super::keybind::KeyBindings>()
         .cloned()
         .unwrap_or_default();
-    let input = world.resource::<ButtonInput<KeyCode>>();
+    let input = super::keybind::KeyInput::from_world(world);
 
-    let do_undo = bindings.undo.just_pressed(input);
-    let do_redo = bindings.redo.just_pressed(input);
+    let do_undo = input.is_some_and(|input| bindings.undo.just_pressed(input));
+    let do_redo = input.is_some_and(|input| bindings.redo.just_pressed(input));
 
     // Also check request flags from menu buttons
-    let (menu_undo, menu_redo, jump_target) = world
+    let requested = world
         .get_resource::<UndoStack>()
-        .map(|s| (s.undo_requested, s.redo_requested, s.jump_requested))
-        .unwrap_or_default();
-
-    let want_undo = do_undo || menu_undo;
-    let want_redo = do_redo || menu_redo;
-
-    if !want_undo && !want_redo && jump_target.is_none() {
+        .is_some_and(UndoStack::has_request);
+    if !do_undo && !do_redo && !requested {
         return;
     }
 
     let mut undo_stack = world.remove_resource::<UndoStack>();
     if let Some(ref mut stack) = undo_stack {
-        stack.undo_requested = false;
-        stack.redo_requested = false;
-        stack.jump_requested = None;
-
-        if let Some(target) = jump_target {
-            stack.jump_to(target, world);
-        } else if want_redo {
-            stack.redo(world);
-        } else if want_undo {
-            stack.undo(world);
-        }
+        stack.apply_requests(do_undo, do_redo, world);
     }
     if let Some(stack) = undo_stack {
         world.insert_resource(stack);
     }
 }
 
-/// Panel that shows undo/redo history as a clickable list.
-pub struct UndoHistoryPanel;
-
-impl crate::dock::WorkbenchPanel for UndoHistoryPanel {
-    fn id(&self) -> &str {
-        "undo_history"
+impl UndoStack {
+    /// Whether an undo, redo, jump or branch switch was requested from the UI.
+    fn has_request(&self) -> bool {
+        self.undo_requested
+            || self.redo_requested
+            || self.jump_requested.is_some()
+            || self.branch_requested.is_some()
+    }
+
+    /// Handle the requests from the UI, plus an undo / redo shortcut press, and clear them.
+    fn apply_requests(&mut self, undo: bool, redo: bool, world: &mut World) {
+        let want_undo = undo || std::mem::take(&mut self.undo_requested);
+        let want_redo = redo || std::mem::take(&mut self.redo_requested);
+        let jump_target = self.jump_requested.take();
+        let branch_target = self.branch_requested.take();
+
+        if let Some(branch) = branch_target {
+            self.switch_branch(branch, world);
+        } else if let Some(target) = jump_target {
+            self.jump_to(target, world);
+        } else if want_redo {
+            self.redo(world);
+        } else if want_undo {
+            self.undo(world);
+        }
     }
+}
 
-    fn title(&self) -> String {
-        "Undo History".to_string()
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn ui(&mut self, _ui: &mut egui::Ui) {}
+    #[derive(Resource, Default)]
+    struct Value(i32);
 
-    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
-        undo_history_panel_ui(ui, world);
+    /// Sets `Value` from `old` to `new`.
+    struct SetValue {
+        old: i32,
+        new: i32,
+        desc: String,
     }
 
-    fn needs_world(&self) -> bool {
-        true
-    }
+    impl UndoAction for SetValue {
+        fn undo(&self, world: &mut World) {
+            world.resource_mut::<Value>().0 = self.old;
+        }
+
+        fn redo(&self, world: &mut World) {
+            world.resource_mut::<Value>().0 = self.new;
+        }
 
-    fn default_visible(&self) -> bool {
-        false
+        fn description(&self) -> &str {
+            &self.desc
+        }
     }
-}
 
-/// Undo history panel UI, extracted to reduce nesting depth.
-fn undo_history_panel_ui(ui: &mut egui::Ui, world: &mut World) {
-    let Some(mut stack) = world.remove_resource::<UndoStack>() else {
-        ui.label("No undo stack");
-        return;
-    };
-
-    let undo_descs: Vec<String> = stack
-        .undo_stack
-        .iter()
-        .map(|a| a.description().to_string())
-        .collect();
-    let redo_descs: Vec<String> = stack
-        .redo_stack
-        .iter()
-        .rev()
-        .map(|a| a.description().to_string())
-        .collect();
-    let current_index = undo_descs.len();
-
-    egui::Frame::NONE
-        .inner_margin(egui::Margin::same(4))
-        .show(ui, |ui| {
-            ui.horizontal(|ui| {
-                ui.label(format!(
-                    "History: {} undo, {} redo",
-                    undo_descs.len(),
-                    redo_descs.len()
-                ));
-                if ui.small_button("Clear").clicked() {
-                    stack.clear();
-                }
-            });
-            ui.separator();
-
-            egui::ScrollArea::vertical()
-                .auto_shrink([false, false])
-                .show(ui, |ui| {
-                    undo_history_list_ui(ui, &mut stack, &undo_descs, &redo_descs, current_index);
-                });
+    fn set(stack: &mut UndoStack, world: &mut World, new: i32) {
+        let old = std::mem::replace(&mut world.resource_mut::<Value>().0, new);
+        stack.push(SetValue {
+            old,
+            new,
+            desc: format!("set {new}"),
         });
-
-    world.insert_resource(stack);
-}
-
-/// Renders the undo/redo history list entries.
-fn undo_history_list_ui(
-    ui: &mut egui::Ui,
-    stack: &mut UndoStack,
-    undo_descs: &[String],
-    redo_descs: &[String],
-    current_index: usize,
-) {
-    // Initial state
-    let is_current = current_index == 0;
-    let label = if is_current {
-        egui::RichText::new("▸ (initial state)")
-            .strong()
-            .color(egui::Color32::WHITE)
-    } else {
-        egui::RichText::new("  (initial state)").color(egui::Color32::GRAY)
-    };
-    if ui.selectable_label(is_current, label).clicked() && !is_current {
-        stack.jump_requested = Some(0);
-    }
-
-    // Undo entries (past actions)
-    for (i, desc) in undo_descs.iter().enumerate() {
-        let idx = i + 1;
-        let is_current = idx == current_index;
-        let label = if is_current {
-            egui::RichText::new(format!("▸ {desc}"))
-                .strong()
-                .color(egui::Color32::WHITE)
-        } else {
-            egui::RichText::new(format!("  {desc}"))
-        };
-        if ui.selectable_label(is_current, label).clicked() && !is_current {
-            stack.jump_requested = Some(idx);
-        }
     }
 
-    // Redo entries (future actions, grayed out)
-    for (i, desc) in redo_descs.iter().enumerate() {
-        let idx = current_index + 1 + i;
-        let label = egui::RichText::new(format!("  {desc}")).color(egui::Color32::from_gray(100));
-        if ui.selectable_label(false, label).clicked() {
-            stack.jump_requested = Some(idx);
-        }
+    fn value(world: &World) -> i32 {
+        world.resource::<Value>().0
+    }
+
+    fn setup() -> (UndoStack, World) {
+        let mut world = World::new();
+        world.init_resource::<Value>();
+        (UndoStack::default(), world)
+    }
+
+    #[test]
+    fn revert_to_checkpoint() {
+        let (mut stack, mut world) = setup();
+        set(&mut stack, &mut world, 1);
+        set(&mut stack, &mut world, 2);
+        stack.checkpoint("two");
+        set(&mut stack, &mut world, 3);
+        assert_eq!(stack.checkpoint_index("two"), Some(2));
+        assert!(stack.revert_to_checkpoint("two", &mut world));
+        assert_eq!(value(&world), 2);
+        assert_eq!((stack.undo_count(), stack.redo_count()), (2, 1));
+        // Reverting again from the other side redoes to it.
+        stack.jump_to(0, &mut world);
+        assert!(stack.revert_to_checkpoint("two", &mut world));
+        assert_eq!(value(&world), 2);
+        assert!(!stack.revert_to_checkpoint("missing", &mut world));
+    }
+
+    #[test]
+    fn push_drops_checkpoints_on_discarded_redo() {
+        let (mut stack, mut world) = setup();
+        set(&mut stack, &mut world, 1);
+        stack.checkpoint("one");
+        set(&mut stack, &mut world, 2);
+        stack.checkpoint("two");
+        stack.jump_to(1, &mut world);
+        set(&mut stack, &mut world, 5);
+        assert_eq!(stack.redo_count(), 0);
+        let checkpoints: Vec<_> = stack.checkpoints().collect();
+        assert_eq!(checkpoints, [(1, "one")]);
+    }
+
+    #[test]
+    fn trimming_shifts_checkpoint_indices() {
+        let (mut stack, mut world) = setup();
+        stack.max_history = 2;
+        stack.checkpoint("start");
+        set(&mut stack, &mut world, 1);
+        stack.checkpoint("one");
+        set(&mut stack, &mut world, 2);
+        set(&mut stack, &mut world, 3);
+        assert_eq!(stack.undo_history(), ["set 2", "set 3"]);
+        let checkpoints: Vec<_> = stack.checkpoints().collect();
+        assert_eq!(checkpoints, [(0, "one")]);
+        assert_eq!(stack.push_count(), 3);
+    }
+
+    #[test]
+    fn branching_keeps_and_switches_timelines() {
+        let (mut stack, mut world) = setup();
+        stack.branching = true;
+        set(&mut stack, &mut world, 1);
+        set(&mut stack, &mut world, 2);
+        stack.checkpoint("two");
+        stack.undo(&mut world);
+        set(&mut stack, &mut world, 3);
+        let branches: Vec<_> = stack.branches().collect();
+        assert_eq!(branches, [(1, "set 2", 1)]);
+        // The checkpoint went with its timeline.
+        assert_eq!(stack.checkpoint_index("two"), None);
+
+        stack.switch_branch(0, &mut world);
+        assert_eq!(value(&world), 2);
+        assert_eq!(stack.undo_history(), ["set 1", "set 2"]);
+        assert_eq!(stack.checkpoint_index("two"), Some(2));
+        let branches: Vec<_> = stack.branches().collect();
+        assert_eq!(branches, [(1, "set 3", 1)]);
+    }
+
+    #[test]
+    fn without_branching_redo_is_discarded() {
+        let (mut stack, mut world) = setup();
+        set(&mut stack, &mut world, 1);
+        set(&mut stack, &mut world, 2);
+        stack.undo(&mut world);
+        set(&mut stack, &mut world, 3);
+        assert_eq!(stack.branches().count(), 0);
+        stack.redo(&mut world);
+        assert_eq!(value(&world), 3);
+        stack.jump_to(0, &mut world);
+        assert_eq!(value(&world), 0);
+    }
+
+    #[test]
+    fn depth_survives_trimming() {
+        let (mut stack, mut world) = setup();
+        stack.max_history = 2;
+        set(&mut stack, &mut world, 1);
+        let after_one = stack.depth();
+        set(&mut stack, &mut world, 2);
+        let after_two = stack.depth();
+        assert_eq!(stack.index_of_depth(after_two), Some(2));
+        set(&mut stack, &mut world, 3);
+        assert_eq!(stack.index_of_depth(after_two), Some(1));
+        assert_eq!(stack.index_of_depth(after_one), Some(0));
+        set(&mut stack, &mut world, 4);
+        assert_eq!(stack.index_of_depth(after_one), None);
+        assert_eq!(stack.index_of_depth(stack.depth() + 1), None);
     }
 }
@@ -0,0 +1,102 @@
+//! # branches.rs
+//!
+//! # branches.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! Branching history for `UndoStack::branching`. Pushing an action while there are redo entries
+//! normally discards them; in branching mode the redo entries are stashed as a branch forked at
+//! the current history index instead, together with the branches and checkpoints further along
+//! that timeline. Switching to a branch stashes the current timeline in the same way and redoes
+//! to the tip of the chosen one, so every alternate timeline stays reachable.
+//!
+//! 为 `UndoStack::branching` 提供分支历史。通常在存在重做条目时推入新操作会丢弃这些条目；
+//! 分支模式下则把它们连同该时间线上更靠后的分支与检查点一起，作为在当前历史索引处分叉的分支
+//! 保存起来。切换到某个分支时，会以同样方式保存当前时间线，并重做到所选分支的末端，因此每条
+//! 备选时间线都始终可以回到。
+
+use bevy::prelude::*;
+
+use super::{UndoAction, UndoStack};
+
+/// A stashed alternate timeline.
+pub(super) struct Branch {
+    /// Absolute history depth it forks from.
+    depth: usize,
+    /// Its actions in redo order (the last one is redone first).
+    actions: Vec<Box<dyn UndoAction>>,
+    /// Branches forking off this timeline.
+    branches: Vec<Branch>,
+    /// Checkpoints on this timeline, by absolute depth.
+    checkpoints: Vec<(usize, String)>,
+}
+
+impl Branch {
+    pub(super) fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl UndoStack {
+    /// Stash the redo stack as a branch forked at the current depth.
+    pub(super) fn stash_redo(&mut self) {
+        if self.redo_stack.is_empty() {
+            return;
+        }
+        let depth = self.trimmed + self.undo_stack.len();
+        let actions = std::mem::take(&mut self.redo_stack);
+        let (branches, kept) = std::mem::take(&mut self.branches)
+            .into_iter()
+            .partition(|branch| branch.depth > depth);
+        self.branches = kept;
+        let (checkpoints, kept) = std::mem::take(&mut self.checkpoints)
+            .into_iter()
+            .partition(|(at, _)| *at > depth);
+        self.checkpoints = kept;
+        self.branches.push(Branch {
+            depth,
+            actions,
+            branches,
+            checkpoints,
+        });
+    }
+
+    /// Drop branches forking below the trimmed bottom of the stack.
+    pub(super) fn trim_branches(&mut self) {
+        let trimmed = self.trimmed;
+        self.branches.retain(|branch| branch.depth >= trimmed);
+    }
+
+    /// Stashed branches as `(fork history index, first action description, action count)`.
+    /// History indices are those of [`UndoStack::jump_to`].
+    pub fn branches(&self) -> impl Iterator<Item = (usize, &str, usize)> {
+        self.branches.iter().map(|branch| {
+            let description = branch.actions.last().map_or("", |a| a.description());
+            (
+                branch.depth - self.trimmed,
+                description,
+                branch.actions.len(),
+            )
+        })
+    }
+
+    /// Switch to the stashed branch at position `index` of [`UndoStack::branches`]: the current
+    /// timeline is stashed as a branch and the chosen one is redone to its tip.
+    pub fn switch_branch(&mut self, index: usize, world: &mut World) {
+        if index >= self.branches.len() {
+            return;
+        }
+        let branch = self.branches.remove(index);
+        let fork = branch.depth - self.trimmed;
+        self.jump_to(fork, world);
+        self.stash_redo();
+        let tip = fork + branch.actions.len();
+        self.redo_stack = branch.actions;
+        self.branches.extend(branch.branches);
+        self.checkpoints.extend(branch.checkpoints);
+        self.checkpoints.sort_by_key(|(at, _)| *at);
+        self.jump_to(tip, world);
+    }
+}
@@ -0,0 +1,264 @@
+//! # history_panel.rs
+//!
+//! # history_panel.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! The Undo History panel: the undo and redo stacks as one clickable list from the initial state
+//! to the newest redo entry. Clicking an entry jumps the stack to it. Checkpoints set with
+//! `UndoStack::checkpoint` are shown as flagged rows after the state they mark, with a button to
+//! revert to them. In branching mode, forks show a selector listing the alternate branches.
+//! While layout undo is separate, a toggle switches the list to the `LayoutUndoStack`.
+//!
+//! 撤销历史面板：把撤销栈和重做栈显示为一个从初始状态到最新重做条目的可点击列表。点击条目会
+//! 让撤销栈跳转到该位置。通过 `UndoStack::checkpoint` 设置的检查点以带旗标的行显示在其标记的
+//! 状态之后，并提供回退到该检查点的按钮。分支模式下，分叉点会显示列出备选分支的选择器。
+//! 布局撤销独立时，可通过切换按钮让列表改为显示 `LayoutUndoStack`。
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use super::{LayoutUndoStack, UndoStack};
+
+/// Panel that shows undo/redo history as a clickable list.
+#[derive(Default)]
+pub struct UndoHistoryPanel {
+    /// Show the layout history instead of the main one.
+    layout: bool,
+}
+
+impl crate::dock::WorkbenchPanel for UndoHistoryPanel {
+    fn id(&self) -> &str {
+        "undo_history"
+    }
+
+    fn title(&self) -> String {
+        "Undo History".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let separate = world
+            .get_resource::<UndoStack>()
+            .is_some_and(|stack| stack.separate_layout);
+        if separate {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.layout, false, "Edits");
+                ui.selectable_value(&mut self.layout, true, "Layout");
+            });
+        }
+        undo_history_panel_ui(ui, world, separate && self.layout);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+/// Snapshot of the stack for drawing, so the list can request jumps on the stack itself.
+struct HistoryRows {
+    undo_descs: Vec<String>,
+    redo_descs: Vec<String>,
+    /// `(history index, name)`, ordered by index.
+    checkpoints: Vec<(usize, String)>,
+    /// `(fork history index, first description, action count)`, by position in the stack.
+    branches: Vec<(usize, String, usize)>,
+}
+
+/// Take the main undo stack, or the layout one when `layout` is set.
+fn take_stack(world: &mut World, layout: bool) -> Option<UndoStack> {
+    if layout {
+        world
+            .remove_resource::<LayoutUndoStack>()
+            .map(|stack| stack.0)
+    } else {
+        world.remove_resource::<UndoStack>()
+    }
+}
+
+/// Put back a stack taken with [`take_stack`].
+fn return_stack(world: &mut World, stack: UndoStack, layout: bool) {
+    if layout {
+        world.insert_resource(LayoutUndoStack(stack));
+    } else {
+        world.insert_resource(stack);
+    }
+}
+
+/// Undo history panel UI, extracted to reduce nesting depth.
+fn undo_history_panel_ui(ui: &mut egui::Ui, world: &mut World, layout: bool) {
+    let Some(mut stack) = take_stack(world, layout) else {
+        ui.label("No undo stack");
+        return;
+    };
+
+    let rows = HistoryRows {
+        undo_descs: stack
+            .undo_history()
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+        redo_descs: stack
+            .redo_history()
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+        checkpoints: stack
+            .checkpoints()
+            .map(|(index, name)| (index, name.to_owned()))
+            .collect(),
+        branches: stack
+            .branches()
+            .map(|(index, desc, len)| (index, desc.to_owned(), len))
+            .collect(),
+    };
+    let mut clear_requested = false;
+
+    egui::Frame::NONE
+        .inner_margin(egui::Margin::same(4))
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "History: {} undo, {} redo",
+                    rows.undo_descs.len(),
+                    rows.redo_descs.len()
+                ));
+                if ui.small_button("Clear").clicked() {
+                    clear_requested = true;
+                }
+                if !layout {
+                    ui.checkbox(&mut stack.branching, "Keep branches")
+                        .on_hover_text("Keep redo steps as a branch when a new action is recorded");
+                }
+            });
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    undo_history_list_ui(ui, &mut stack, &rows);
+                });
+        });
+
+    return_stack(world, stack, layout);
+    if clear_requested
+        && let Some(mut dialogs) = world.get_resource_mut::<crate::dialogs::Dialogs>()
+    {
+        dialogs.confirm(
+            "Clear Undo History",
+            "All undo and redo steps will be discarded.",
+            move |world, ok| {
+                if !ok {
+                    return;
+                }
+                if layout {
+                    world.resource_mut::<LayoutUndoStack>().clear();
+                } else {
+                    world.resource_mut::<UndoStack>().clear();
+                }
+            },
+        );
+    }
+}
+
+/// Renders the undo/redo history list entries.
+fn undo_history_list_ui(ui: &mut egui::Ui, stack: &mut UndoStack, rows: &HistoryRows) {
+    let current_index = rows.undo_descs.len();
+
+    // Initial state
+    let is_current = current_index == 0;
+    let label = if is_current {
+        egui::RichText::new("▸ (initial state)")
+            .strong()
+            .color(egui::Color32::WHITE)
+    } else {
+        egui::RichText::new("  (initial state)").color(egui::Color32::GRAY)
+    };
+    if ui.selectable_label(is_current, label).clicked() && !is_current {
+        stack.jump_requested = Some(0);
+    }
+    checkpoint_rows_ui(ui, stack, rows, 0, current_index);
+    branch_row_ui(ui, stack, rows, 0);
+
+    // Undo entries (past actions)
+    for (i, desc) in rows.undo_descs.iter().enumerate() {
+        let idx = i + 1;
+        let is_current = idx == current_index;
+        let label = if is_current {
+            egui::RichText::new(format!("▸ {desc}"))
+                .strong()
+                .color(egui::Color32::WHITE)
+        } else {
+            egui::RichText::new(format!("  {desc}"))
+        };
+        if ui.selectable_label(is_current, label).clicked() && !is_current {
+            stack.jump_requested = Some(idx);
+        }
+        checkpoint_rows_ui(ui, stack, rows, idx, current_index);
+        branch_row_ui(ui, stack, rows, idx);
+    }
+
+    // Redo entries (future actions, grayed out)
+    for (i, desc) in rows.redo_descs.iter().enumerate() {
+        let idx = current_index + 1 + i;
+        let label = egui::RichText::new(format!("  {desc}")).color(egui::Color32::from_gray(100));
+        if ui.selectable_label(false, label).clicked() {
+            stack.jump_requested = Some(idx);
+        }
+        checkpoint_rows_ui(ui, stack, rows, idx, current_index);
+        branch_row_ui(ui, stack, rows, idx);
+    }
+}
+
+/// Flagged rows for the checkpoints set at history index `idx`.
+fn checkpoint_rows_ui(
+    ui: &mut egui::Ui,
+    stack: &mut UndoStack,
+    rows: &HistoryRows,
+    idx: usize,
+    current_index: usize,
+) {
+    for (_, name) in rows.checkpoints.iter().filter(|(index, _)| *index == idx) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("  ⚑ {name}")).italics());
+            let revert = ui
+                .add_enabled(idx != current_index, egui::Button::new("Revert").small())
+                .on_hover_text("Jump the undo stack back to this checkpoint");
+            if revert.clicked() {
+                stack.jump_requested = Some(idx);
+            }
+        });
+    }
+}
+
+/// Selector for the branches forking at history index `idx`, if any.
+fn branch_row_ui(ui: &mut egui::Ui, stack: &mut UndoStack, rows: &HistoryRows, idx: usize) {
+    let forks: Vec<_> = rows
+        .branches
+        .iter()
+        .enumerate()
+        .filter(|(_, (index, _, _))| *index == idx)
+        .collect();
+    if forks.is_empty() {
+        return;
+    }
+    let label = match forks.len() {
+        1 => "  ⑂ 1 other branch".to_string(),
+        n => format!("  ⑂ {n} other branches"),
+    };
+    ui.menu_button(label, |ui| {
+        for (position, (_, desc, len)) in forks {
+            if ui.button(format!("{desc} (+{len})")).clicked() {
+                stack.branch_requested = Some(position);
+                ui.close();
+            }
+        }
+    });
+}
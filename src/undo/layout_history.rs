@@ -0,0 +1,68 @@
+//! # layout_history.rs
+//!
+//! # layout_history.rs 文件
+//!
+//! ## Module Overview
+//!
+//! ## 模块概述
+//!
+//! A second undo history just for dock layout changes. While `UndoStack::separate_layout` is on
+//! (the default, saved as `WorkbenchSettings::separate_layout_undo`), closing, opening, moving
+//! and resetting panels are recorded in the `LayoutUndoStack` instead of the main stack, so
+//! Ctrl+Z only walks world and data edits. The layout history has its own bindings
+//! (`layout_undo` / `layout_redo`, Ctrl+Alt+Z and Ctrl+Shift+Alt+Z by default) and its own list
+//! in the Undo History panel.
+//!
+//! 专用于停靠布局变更的第二条撤销历史。当 `UndoStack::separate_layout` 开启时（默认开启，保存为
+//! `WorkbenchSettings::separate_layout_undo`），关闭、打开、移动和重置面板都会记录到
+//! `LayoutUndoStack` 而不是主撤销栈，因此 Ctrl+Z 只遍历世界与数据编辑。布局历史有自己的快捷键
+//! （`layout_undo` / `layout_redo`，默认 Ctrl+Alt+Z 与 Ctrl+Shift+Alt+Z），并在撤销历史面板中
+//! 拥有单独的列表。
+
+use bevy::prelude::*;
+
+use super::{UndoAction, UndoStack};
+
+/// Undo history of dock layout changes, used while [`UndoStack::separate_layout`] is on.
+/// Requests and jumps work as on the [`UndoStack`] it wraps.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct LayoutUndoStack(pub UndoStack);
+
+impl LayoutUndoStack {
+    /// Record a layout change in the layout history or the main [`UndoStack`], whichever
+    /// [`UndoStack::separate_layout`] picks.
+    pub(crate) fn record(world: &mut World, action: impl UndoAction) {
+        let separate = world
+            .get_resource::<UndoStack>()
+            .is_none_or(|stack| stack.separate_layout);
+        if separate && let Some(mut layout) = world.get_resource_mut::<LayoutUndoStack>() {
+            layout.push(action);
+        } else if let Some(mut undo_stack) = world.get_resource_mut::<UndoStack>() {
+            undo_stack.push(action);
+        }
+    }
+}
+
+/// System that handles the layout undo/redo shortcuts and requests from the Undo History panel.
+pub fn layout_undo_input_system(world: &mut World) {
+    let bindings = world
+        .get_resource::<crate::keybind::KeyBindings>()
+        .cloned()
+        .unwrap_or_default();
+    let input = crate::keybind::KeyInput::from_world(world);
+
+    let do_undo = input.is_some_and(|input| bindings.layout_undo.just_pressed(input));
+    let do_redo = input.is_some_and(|input| bindings.layout_redo.just_pressed(input));
+    let requested = world
+        .get_resource::<LayoutUndoStack>()
+        .is_some_and(|layout| layout.has_request());
+    if !do_undo && !do_redo && !requested {
+        return;
+    }
+
+    let Some(mut layout) = world.remove_resource::<LayoutUndoStack>() else {
+        return;
+    };
+    layout.apply_requests(do_undo, do_redo, world);
+    world.insert_resource(layout);
+}
@@ -0,0 +1,274 @@
+//! Welcome panel shown on startup: recent layouts, quick actions and tips. Tools can replace
+//! its content with their own start screen through [`WelcomeScreen`].
+
+use bevy::prelude::*;
+
+use crate::config::{ConfigPath, WorkbenchSettings};
+use crate::dock::{TileLayoutState, WorkbenchPanel};
+use crate::i18n::I18n;
+
+/// String ID of the welcome panel.
+pub const WELCOME_PANEL: &str = "welcome";
+
+/// Documentation opened by the Documentation link.
+const DOCS_URL: &str = "https://docs.rs/bevy_workbench";
+
+/// Skeleton copied by "Copy Panel Template".
+const PANEL_TEMPLATE: &str = r#"use bevy_workbench::prelude::*;
+
+pub struct MyPanel;
+
+impl WorkbenchPanel for MyPanel {
+    fn id(&self) -> &str {
+        "my_panel"
+    }
+
+    fn title(&self) -> String {
+        "My Panel".to_string()
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.label("Hello from my panel");
+    }
+}
+
+// app.register_panel(MyPanel);
+"#;
+
+/// Message IDs of the tips cycled by the default welcome screen (Markdown).
+const TIPS: &[&str] = &[
+    "welcome-tip-detach",
+    "welcome-tip-keybindings",
+    "welcome-tip-menu-keys",
+    "welcome-tip-undo-history",
+    "welcome-tip-apply",
+    "welcome-tip-settings",
+    "welcome-tip-help",
+];
+
+/// Localized strings of the default welcome screen.
+struct WelcomeStrings {
+    title: String,
+    start: String,
+    settings: String,
+    keybindings: String,
+    copy_template: String,
+    copy_template_hint: String,
+    docs: String,
+    recent_layouts: String,
+    recent_layouts_empty: String,
+    tip_label: String,
+    tip: String,
+    next_tip: String,
+    show_on_startup: String,
+}
+
+impl WelcomeStrings {
+    fn new(world: &World, tip: usize) -> Self {
+        let i18n = world.get_resource::<I18n>();
+        let t = |id: &str| i18n.map_or(id, |i18n| i18n.t(id)).to_owned();
+        Self {
+            title: t("welcome-title"),
+            start: t("welcome-start"),
+            settings: t("menu-file-settings"),
+            keybindings: t("welcome-open-keybindings"),
+            copy_template: t("welcome-copy-template"),
+            copy_template_hint: t("welcome-copy-template-hint"),
+            docs: t("welcome-docs"),
+            recent_layouts: t("welcome-recent-layouts"),
+            recent_layouts_empty: t("welcome-recent-layouts-empty"),
+            tip_label: t("welcome-tip"),
+            tip: t(TIPS[tip % TIPS.len()]),
+            next_tip: t("welcome-next-tip"),
+            show_on_startup: t("welcome-show-on-startup"),
+        }
+    }
+}
+
+/// Content of the welcome panel. Implement it and call
+/// [`crate::WorkbenchApp::set_welcome_screen`] to show your own start screen.
+pub trait WelcomeScreen: Send + Sync + 'static {
+    /// Draw the start screen.
+    fn ui(&mut self, ui: &mut egui::Ui, world: &mut World);
+
+    /// Tab title of the welcome panel.
+    fn title(&self) -> String {
+        "Welcome".to_string()
+    }
+}
+
+/// Panel showing a [`WelcomeScreen`], opened on startup unless disabled in the settings.
+pub struct WelcomePanel {
+    screen: Box<dyn WelcomeScreen>,
+}
+
+impl WelcomePanel {
+    /// Panel showing `screen`.
+    pub fn new(screen: impl WelcomeScreen) -> Self {
+        Self {
+            screen: Box::new(screen),
+        }
+    }
+
+    /// Replace the screen shown.
+    pub fn set_screen(&mut self, screen: impl WelcomeScreen) {
+        self.screen = Box::new(screen);
+    }
+}
+
+impl Default for WelcomePanel {
+    fn default() -> Self {
+        Self::new(DefaultWelcome::default())
+    }
+}
+
+impl WorkbenchPanel for WelcomePanel {
+    fn id(&self) -> &str {
+        WELCOME_PANEL
+    }
+
+    fn title(&self) -> String {
+        self.screen.title()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        self.screen.ui(ui, world);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+/// The built-in start screen: quick actions, recent layouts and tips.
+#[derive(Default)]
+pub struct DefaultWelcome {
+    /// Index of the tip shown.
+    tip: usize,
+    /// Localized tab title, once drawn.
+    title: Option<String>,
+}
+
+impl WelcomeScreen for DefaultWelcome {
+    fn ui(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let strings = WelcomeStrings::new(world, self.tip);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Frame::NONE
+                .inner_margin(egui::Margin::same(12))
+                .show(ui, |ui| {
+                    ui.heading(&strings.title);
+                    ui.add_space(8.0);
+                    quick_actions_ui(ui, world, &strings);
+                    ui.add_space(8.0);
+                    recent_layouts_ui(ui, world, &strings);
+                    ui.add_space(8.0);
+                    self.tips_ui(ui, &strings);
+                    ui.separator();
+                    show_on_startup_ui(ui, world, &strings);
+                });
+        });
+        self.title = Some(strings.title);
+    }
+
+    fn title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| "Welcome".to_string())
+    }
+}
+
+impl DefaultWelcome {
+    fn tips_ui(&mut self, ui: &mut egui::Ui, strings: &WelcomeStrings) {
+        ui.strong(&strings.tip_label);
+        crate::bench_ui::markdown(ui, &strings.tip);
+        if ui.small_button(&strings.next_tip).clicked() {
+            self.tip = (self.tip + 1) % TIPS.len();
+        }
+    }
+}
+
+/// Opens the welcome panel once the dock tree has been built (without an undo step).
+pub fn open_welcome_system(mut opened: Local<bool>, mut tile_state: ResMut<TileLayoutState>) {
+    if *opened || tile_state.tree.is_none() {
+        return;
+    }
+    *opened = true;
+    tile_state.open_or_focus_panel(WELCOME_PANEL);
+}
+
+/// Buttons opening settings and documentation, and copying a panel template.
+fn quick_actions_ui(ui: &mut egui::Ui, world: &mut World, strings: &WelcomeStrings) {
+    ui.strong(&strings.start);
+    ui.horizontal_wrapped(|ui| {
+        for (label, panel) in [
+            (&strings.settings, "settings"),
+            (&strings.keybindings, "keybindings"),
+        ] {
+            if ui.button(label).clicked() {
+                world
+                    .resource_mut::<TileLayoutState>()
+                    .request_open_panel(panel);
+            }
+        }
+        if ui
+            .button(&strings.copy_template)
+            .on_hover_text(&strings.copy_template_hint)
+            .clicked()
+        {
+            ui.ctx().copy_text(PANEL_TEMPLATE.to_string());
+        }
+        ui.hyperlink_to(&strings.docs, DOCS_URL);
+    });
+}
+
+/// Recently saved or loaded layout files; clicking one loads it.
+fn recent_layouts_ui(ui: &mut egui::Ui, world: &mut World, strings: &WelcomeStrings) {
+    ui.strong(&strings.recent_layouts);
+    let recent = world.resource::<WorkbenchSettings>().recent_layouts.clone();
+    if recent.is_empty() {
+        ui.weak(&strings.recent_layouts_empty);
+        return;
+    }
+    for path in recent {
+        let name = std::path::Path::new(&path)
+            .file_name()
+            .map_or(path.clone(), |name| name.to_string_lossy().into_owned());
+        if !ui.link(name).on_hover_text(&path).clicked() {
+            continue;
+        }
+        match crate::storage::read_string(std::path::Path::new(&path)) {
+            Some(content) => {
+                world.resource_mut::<TileLayoutState>().pending_layout_load = Some((content, path))
+            }
+            None => forget_recent_layout(world, &path),
+        }
+    }
+}
+
+/// Drop a recent layout that can no longer be read.
+fn forget_recent_layout(world: &mut World, path: &str) {
+    warn!("Layout {path} is no longer available");
+    let mut settings = world.resource_mut::<WorkbenchSettings>();
+    settings.recent_layouts.retain(|p| p != path);
+    save_settings(world);
+}
+
+/// Checkbox for [`WorkbenchSettings::show_welcome`].
+fn show_on_startup_ui(ui: &mut egui::Ui, world: &mut World, strings: &WelcomeStrings) {
+    let mut show = world.resource::<WorkbenchSettings>().show_welcome;
+    if ui.checkbox(&mut show, &strings.show_on_startup).changed() {
+        world.resource_mut::<WorkbenchSettings>().show_welcome = show;
+        save_settings(world);
+    }
+}
+
+fn save_settings(world: &World) {
+    let settings = world.resource::<WorkbenchSettings>();
+    if let Some(path) = world.get_resource::<ConfigPath>() {
+        settings.save(&path.0);
+    }
+}
@@ -0,0 +1,85 @@
+//! Window title: shows the editor mode and workspace name in the OS title bar, e.g.
+//! "MyTool — my_game — Play ▶", following `WorkbenchSettings::window_title`.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::config::WorkbenchSettings;
+use crate::i18n::I18n;
+use crate::mode::EditorMode;
+
+/// Keeps the primary window title in sync with the editor mode (added by `WorkbenchPlugin`).
+pub struct WindowTitlePlugin;
+
+impl Plugin for WindowTitlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, window_title_system);
+    }
+}
+
+/// Default for `WorkbenchSettings::window_title`.
+pub const DEFAULT_WINDOW_TITLE: &str = "{app} — {workspace} — {mode}";
+
+/// Title parts captured on the first frame.
+#[derive(Default)]
+pub struct TitleParts {
+    /// Title the app gave its primary window.
+    app: Option<String>,
+    /// Name of the working directory the workbench files live in.
+    workspace: String,
+}
+
+/// Expand `{app}`, `{workspace}` and `{mode}` in `format`.
+pub fn format_window_title(
+    format: &str,
+    app: &str,
+    workspace: &str,
+    mode: EditorMode,
+    i18n: Option<&I18n>,
+) -> String {
+    format
+        .replace("{app}", app)
+        .replace("{workspace}", workspace)
+        .replace("{mode}", &mode.title_label(i18n))
+}
+
+/// Rewrites the primary window title when the mode, the title format or the locale changes.
+/// An empty format restores the app's own title.
+pub fn window_title_system(
+    mode: Res<State<EditorMode>>,
+    settings: Res<WorkbenchSettings>,
+    i18n: Option<Res<I18n>>,
+    mut parts: Local<TitleParts>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = windows.single_mut() else {
+        return;
+    };
+    let first = parts.app.is_none();
+    if first {
+        parts.app = Some(window.title.clone());
+        parts.workspace = std::env::current_dir()
+            .ok()
+            .and_then(|dir| dir.file_name()?.to_str().map(str::to_string))
+            .unwrap_or_default();
+    }
+    let locale_changed = i18n.as_ref().is_some_and(|i18n| i18n.is_changed());
+    if !first && !mode.is_changed() && !settings.is_changed() && !locale_changed {
+        return;
+    }
+    let app = parts.app.as_deref().unwrap_or_default();
+    let title = if settings.window_title.is_empty() {
+        app.to_string()
+    } else {
+        format_window_title(
+            &settings.window_title,
+            app,
+            &parts.workspace,
+            *mode.get(),
+            i18n.as_deref(),
+        )
+    };
+    if window.title != title {
+        window.title = title;
+    }
+}
@@ -0,0 +1,337 @@
+//! `WorkbenchApp`: `App` extension methods for registering panels, settings sections, tool
+//! settings, the welcome screen, shortcuts, rect shapes, point lists, brushes, undo kinds, states
+//! and watched events.
+
+#[cfg(feature = "game-view")]
+use bevy::ecs::component::Mutable;
+use bevy::prelude::*;
+
+use crate::{autosave, config, dock, events, keybind, menu_bar, shortcuts, states, welcome};
+
+/// Extension trait for registering custom panels with the app.
+pub trait WorkbenchApp {
+    /// Register a custom panel. The panel will be added to the dock layout.
+    fn register_panel(&mut self, panel: impl dock::WorkbenchPanel) -> &mut Self;
+
+    /// Register a custom panel in an explicit slot of the default layout,
+    /// overriding [`dock::WorkbenchPanel::default_slot`].
+    fn register_panel_in(
+        &mut self,
+        panel: impl dock::WorkbenchPanel,
+        slot: dock::PanelSlot,
+    ) -> &mut Self;
+
+    /// Replace the slot-based default layout with a code-first description, used whenever no
+    /// saved layout exists: `app.set_default_layout(row([panel("a"), panel("b").share(3.0)]))`.
+    fn set_default_layout(&mut self, layout: dock::LayoutBuilder) -> &mut Self;
+
+    /// Register a panel drawn by a closure taking system params instead of `&mut World`:
+    /// `app.register_panel_systems("stats", |ui, q: Query<&Enemy>| { ... })`.
+    /// The params are fetched through a `SystemState` cached per panel.
+    fn register_panel_systems<M: 'static>(
+        &mut self,
+        id: impl Into<String>,
+        func: impl dock::PanelSystemFn<M>,
+    ) -> &mut Self;
+
+    /// Register a custom section in the built-in Settings panel.
+    fn register_settings_section(&mut self, section: menu_bar::SettingsSection) -> &mut Self;
+
+    /// Register tool settings `T`, persisted under `[tools.<namespace>]` in `settings.toml` and
+    /// edited in the Settings panel with `draw`:
+    /// `app.register_settings("my_tool", "My Tool", |ui, s: &mut MyToolSettings| { ... })`.
+    /// Read the applied value with [`config::SettingsRegistry::get`].
+    fn register_settings<T: config::ToolSettings>(
+        &mut self,
+        namespace: impl Into<String>,
+        label: impl Into<String>,
+        draw: impl FnMut(&mut egui::Ui, &mut T) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Replace the content of the welcome panel with your own start screen.
+    fn set_welcome_screen(&mut self, screen: impl welcome::WelcomeScreen) -> &mut Self;
+
+    /// Style the panel `panel_id` (e.g. a darker console), overriding its own
+    /// [`dock::WorkbenchPanel::style`].
+    fn set_panel_style(
+        &mut self,
+        panel_id: impl Into<String>,
+        style: dock::PanelStyle,
+    ) -> &mut Self;
+
+    /// Style every panel registered in `slot` that has no style of its own.
+    fn set_slot_style(&mut self, slot: dock::PanelSlot, style: dock::PanelStyle) -> &mut Self;
+
+    /// List a tool shortcut in the Keyboard Shortcuts panel under `category`.
+    /// This only documents the shortcut; the tool still handles the key itself.
+    fn register_shortcut(
+        &mut self,
+        category: impl Into<String>,
+        name: impl Into<String>,
+        bind: keybind::KeyBind,
+    ) -> &mut Self;
+
+    /// Make the rect shape `T` (e.g. a box collider) editable with the Game View rect handles,
+    /// named `name` in undo entries.
+    #[cfg(feature = "game-view")]
+    fn register_rect_shape<T: crate::game_view::RectShape>(
+        &mut self,
+        name: impl Into<String>,
+    ) -> &mut Self;
+
+    /// Make the `Vec<Vec2>` field at reflect path `path` of `T` (polygon collider points, path
+    /// waypoints, ...) editable with the Game View point tool, named `name` in undo entries.
+    /// `closed` lists connect their last point back to the first.
+    #[cfg(feature = "game-view")]
+    fn register_point_list<T: Component<Mutability = Mutable> + Reflect>(
+        &mut self,
+        name: impl Into<String>,
+        path: impl Into<String>,
+        closed: bool,
+    ) -> &mut Self;
+
+    /// Register a Game View paint brush, shown as `label` in the toolbar's brush selector.
+    /// `paint` gets one [`crate::game_view::BrushDab`] per dab, placed every `spacing` world
+    /// units along a drag (0 = once per frame):
+    /// `app.register_brush("Grass", 0.5, |world, dab| { ... })`.
+    #[cfg(feature = "game-view")]
+    fn register_brush(
+        &mut self,
+        label: impl Into<String>,
+        spacing: f32,
+        paint: impl FnMut(&mut World, &crate::game_view::BrushDab) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Register a deserializer for undo actions whose [`crate::undo::UndoAction::serialize`]
+    /// returns this `kind`, so they survive crash recovery.
+    fn register_undo_kind(
+        &mut self,
+        kind: impl Into<String>,
+        deserialize: autosave::UndoDeserializer,
+    ) -> &mut Self;
+
+    /// Show `S` in the States panel, with a button per variant to force a transition.
+    fn register_states<S: States>(&mut self, variants: impl IntoIterator<Item = S>) -> &mut Self;
+
+    /// Log every `M` message in the Events panel (registered on first use).
+    fn watch_message<M: Message + std::fmt::Debug>(&mut self) -> &mut Self;
+
+    /// Log every triggered `E` event in the Events panel (registered on first use).
+    fn watch_event<E: Event + std::fmt::Debug>(&mut self) -> &mut Self;
+}
+
+impl WorkbenchApp for App {
+    fn register_panel(&mut self, panel: impl dock::WorkbenchPanel) -> &mut Self {
+        let mut tile_state = self
+            .world_mut()
+            .get_resource_mut::<dock::TileLayoutState>()
+            .expect("WorkbenchPlugin must be added before registering panels");
+        tile_state.add_panel(Box::new(panel));
+        self
+    }
+
+    fn register_panel_in(
+        &mut self,
+        panel: impl dock::WorkbenchPanel,
+        slot: dock::PanelSlot,
+    ) -> &mut Self {
+        let mut tile_state = self
+            .world_mut()
+            .get_resource_mut::<dock::TileLayoutState>()
+            .expect("WorkbenchPlugin must be added before registering panels");
+        tile_state.add_panel_in(Box::new(panel), slot);
+        self
+    }
+
+    fn set_default_layout(&mut self, layout: dock::LayoutBuilder) -> &mut Self {
+        self.world_mut()
+            .get_resource_mut::<dock::TileLayoutState>()
+            .expect("WorkbenchPlugin must be added before setting the default layout")
+            .set_default_layout(layout);
+        self
+    }
+
+    fn register_panel_systems<M: 'static>(
+        &mut self,
+        id: impl Into<String>,
+        func: impl dock::PanelSystemFn<M>,
+    ) -> &mut Self {
+        self.register_panel(dock::SystemPanel::new(id, func))
+    }
+
+    fn register_settings_section(&mut self, section: menu_bar::SettingsSection) -> &mut Self {
+        let mut tile_state = self
+            .world_mut()
+            .get_resource_mut::<dock::TileLayoutState>()
+            .expect("WorkbenchPlugin must be added before registering settings sections");
+        if let Some(panel) = tile_state.get_panel_mut::<menu_bar::SettingsPanel>("settings") {
+            panel.custom_sections.push(section);
+        }
+        self
+    }
+
+    fn register_settings<T: config::ToolSettings>(
+        &mut self,
+        namespace: impl Into<String>,
+        label: impl Into<String>,
+        draw: impl FnMut(&mut egui::Ui, &mut T) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let namespace = namespace.into();
+        let stored = self
+            .world()
+            .get_resource::<config::WorkbenchSettings>()
+            .expect("WorkbenchPlugin must be added before registering settings")
+            .tools
+            .get(&namespace)
+            .cloned();
+        self.init_resource::<config::SettingsRegistry>();
+        self.world_mut()
+            .resource_mut::<config::SettingsRegistry>()
+            .register::<T>(namespace, label, stored.as_ref(), Box::new(draw));
+        self
+    }
+
+    fn set_welcome_screen(&mut self, screen: impl welcome::WelcomeScreen) -> &mut Self {
+        let mut tile_state = self
+            .world_mut()
+            .get_resource_mut::<dock::TileLayoutState>()
+            .expect("WorkbenchPlugin must be added before setting the welcome screen");
+        if let Some(panel) =
+            tile_state.get_panel_mut::<welcome::WelcomePanel>(welcome::WELCOME_PANEL)
+        {
+            panel.set_screen(screen);
+        }
+        self
+    }
+
+    fn set_panel_style(
+        &mut self,
+        panel_id: impl Into<String>,
+        style: dock::PanelStyle,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<dock::PanelStyles>()
+            .panels
+            .insert(panel_id.into(), style);
+        self
+    }
+
+    fn set_slot_style(&mut self, slot: dock::PanelSlot, style: dock::PanelStyle) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<dock::PanelStyles>()
+            .slots
+            .insert(slot, style);
+        self
+    }
+
+    fn register_shortcut(
+        &mut self,
+        category: impl Into<String>,
+        name: impl Into<String>,
+        bind: keybind::KeyBind,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_mut::<shortcuts::ShortcutRegistry>()
+            .expect("WorkbenchPlugin must be added before registering shortcuts")
+            .register(category, name, bind);
+        self
+    }
+
+    #[cfg(feature = "game-view")]
+    fn register_rect_shape<T: crate::game_view::RectShape>(
+        &mut self,
+        name: impl Into<String>,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<crate::game_view::RectHandles>()
+            .register::<T>(name);
+        self
+    }
+
+    #[cfg(feature = "game-view")]
+    fn register_point_list<T: Component<Mutability = Mutable> + Reflect>(
+        &mut self,
+        name: impl Into<String>,
+        path: impl Into<String>,
+        closed: bool,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<crate::game_view::PointHandles>()
+            .register::<T>(name, path, closed);
+        self
+    }
+
+    #[cfg(feature = "game-view")]
+    fn register_brush(
+        &mut self,
+        label: impl Into<String>,
+        spacing: f32,
+        paint: impl FnMut(&mut World, &crate::game_view::BrushDab) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<crate::game_view::GameViewBrushes>()
+            .register(label, spacing, paint);
+        self
+    }
+
+    fn register_undo_kind(
+        &mut self,
+        kind: impl Into<String>,
+        deserialize: autosave::UndoDeserializer,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<autosave::UndoKinds>()
+            .0
+            .insert(kind.into(), deserialize);
+        self
+    }
+
+    fn register_states<S: States>(&mut self, variants: impl IntoIterator<Item = S>) -> &mut Self {
+        self.init_resource::<states::StatesRegistry>();
+        self.world_mut()
+            .resource_mut::<states::StatesRegistry>()
+            .register(variants);
+        let registered = self
+            .world_mut()
+            .resource_mut::<dock::TileLayoutState>()
+            .get_panel_mut::<states::StatesPanel>("workbench_states")
+            .is_some();
+        if !registered {
+            self.register_panel(states::StatesPanel);
+        }
+        self
+    }
+
+    fn watch_message<M: Message + std::fmt::Debug>(&mut self) -> &mut Self {
+        if init_event_log(self, events::short_type_name::<M>()) {
+            self.add_systems(Last, events::record_messages_system::<M>);
+        }
+        self
+    }
+
+    fn watch_event<E: Event + std::fmt::Debug>(&mut self) -> &mut Self {
+        if init_event_log(self, events::short_type_name::<E>()) {
+            self.add_observer(events::record_event_observer::<E>);
+        }
+        self
+    }
+}
+
+/// Ensures the event log exists, registers `type_name` in it and adds the Events panel once.
+/// Returns false if `type_name` was already watched, so its recorder isn't added twice.
+fn init_event_log(app: &mut App, type_name: String) -> bool {
+    app.init_resource::<events::EventLog>();
+    let added = app
+        .world_mut()
+        .resource_mut::<events::EventLog>()
+        .watch(type_name);
+    let registered = app
+        .world_mut()
+        .resource_mut::<dock::TileLayoutState>()
+        .get_panel_mut::<events::EventsPanel>("workbench_events")
+        .is_some();
+    if !registered {
+        app.register_panel(events::EventsPanel);
+    }
+    added
+}
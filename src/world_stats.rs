@@ -0,0 +1,396 @@
+//! World statistics panel: entity count history, archetypes, component counts and resources.
+
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+use crate::dock::WorkbenchPanel;
+use crate::mode::EditorMode;
+
+/// Seconds between entity count samples.
+const SAMPLE_INTERVAL: f32 = 0.25;
+/// Number of samples kept in the history (~5 minutes).
+const MAX_SAMPLES: usize = 1200;
+/// Seconds between refreshes of the archetype/component/resource tables.
+const REFRESH_INTERVAL: f32 = 0.5;
+
+/// One entity count sample.
+#[derive(Debug, Clone, Copy)]
+pub struct EntityCountSample {
+    /// Real time since startup, in seconds.
+    pub time: f32,
+    pub count: usize,
+    /// Editor mode at sample time, used to mark Play sessions in the graph.
+    pub mode: EditorMode,
+}
+
+/// Rolling entity count history, sampled in `Last` regardless of panel visibility.
+#[derive(Resource, Default)]
+pub struct EntityCountHistory {
+    pub samples: VecDeque<EntityCountSample>,
+    last_sample: Option<f32>,
+}
+
+/// Total number of spawned entities across all archetypes.
+fn entity_count(world: &World) -> usize {
+    world.archetypes().iter().map(|a| a.len() as usize).sum()
+}
+
+/// Samples the entity count every [`SAMPLE_INTERVAL`] seconds.
+pub(crate) fn entity_count_sample_system(world: &mut World) {
+    let now = world.resource::<Time<Real>>().elapsed_secs();
+    let due = world
+        .resource::<EntityCountHistory>()
+        .last_sample
+        .is_none_or(|last| now - last >= SAMPLE_INTERVAL);
+    if !due {
+        return;
+    }
+    let count = entity_count(world);
+    let mode = world
+        .get_resource::<State<EditorMode>>()
+        .map_or(EditorMode::Edit, |s| *s.get());
+    let mut history = world.resource_mut::<EntityCountHistory>();
+    history.last_sample = Some(now);
+    history.samples.push_back(EntityCountSample {
+        time: now,
+        count,
+        mode,
+    });
+    while history.samples.len() > MAX_SAMPLES {
+        history.samples.pop_front();
+    }
+}
+
+/// Table shown below the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum StatsTab {
+    #[default]
+    Components,
+    Archetypes,
+    Resources,
+}
+
+/// Column a table is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SortKey {
+    Name,
+    #[default]
+    Count,
+    Size,
+}
+
+/// A row in one of the stats tables.
+#[derive(Debug, Clone)]
+struct StatsRow {
+    name: String,
+    /// Entities (components/archetypes) or 1 (resources).
+    count: usize,
+    /// Approximate bytes (inline size only; heap allocations are not counted).
+    size: usize,
+    /// Extra detail shown on hover.
+    detail: String,
+}
+
+/// Snapshot of the tables, refreshed every [`REFRESH_INTERVAL`] seconds.
+#[derive(Default)]
+struct StatsSnapshot {
+    components: Vec<StatsRow>,
+    archetypes: Vec<StatsRow>,
+    resources: Vec<StatsRow>,
+}
+
+impl StatsSnapshot {
+    fn collect(world: &World) -> Self {
+        let components_info = world.components();
+        let mut by_component: HashMap<String, StatsRow> = HashMap::new();
+        let mut archetypes = Vec::new();
+        for archetype in world.archetypes().iter() {
+            let len = archetype.len() as usize;
+            let mut names = Vec::new();
+            let mut row_size = 0;
+            for &id in archetype.components() {
+                let Some(info) = components_info.get_info(id) else {
+                    continue;
+                };
+                let name = info.name().to_string();
+                let size = info.layout().size();
+                row_size += size;
+                let row = by_component
+                    .entry(name.clone())
+                    .or_insert_with(|| StatsRow {
+                        name: name.clone(),
+                        count: 0,
+                        size: 0,
+                        detail: format!("{size} B per component"),
+                    });
+                row.count += len;
+                row.size += size * len;
+                names.push(short_name(&name).to_string());
+            }
+            if len == 0 {
+                continue;
+            }
+            names.sort();
+            archetypes.push(StatsRow {
+                name: format!("#{} ({} components)", archetype.id().index(), names.len()),
+                count: len,
+                size: row_size * len,
+                detail: names.join(", "),
+            });
+        }
+        let resources = world
+            .iter_resources()
+            .map(|(info, _)| StatsRow {
+                name: info.name().to_string(),
+                count: 1,
+                size: info.layout().size(),
+                detail: String::new(),
+            })
+            .collect();
+        Self {
+            components: by_component.into_values().collect(),
+            archetypes,
+            resources,
+        }
+    }
+
+    fn rows(&self, tab: StatsTab) -> &[StatsRow] {
+        match tab {
+            StatsTab::Components => &self.components,
+            StatsTab::Archetypes => &self.archetypes,
+            StatsTab::Resources => &self.resources,
+        }
+    }
+}
+
+/// Last path segment of a type name, keeping generics intact.
+pub(crate) fn short_name(name: &str) -> &str {
+    let base = name.split('<').next().unwrap_or(name);
+    let start = base.rfind("::").map_or(0, |i| i + 2);
+    &name[start..]
+}
+
+/// Human readable byte size.
+fn format_bytes(bytes: usize) -> String {
+    const KIB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b >= KIB * KIB {
+        format!("{:.1} MiB", b / (KIB * KIB))
+    } else if b >= KIB {
+        format!("{:.1} KiB", b / KIB)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+/// Built-in panel showing world statistics, useful to spot entity leaks between Play sessions.
+#[derive(Default)]
+pub struct WorldStatsPanel {
+    tab: StatsTab,
+    sort: SortKey,
+    ascending: bool,
+    filter: String,
+    snapshot: StatsSnapshot,
+    last_refresh: Option<f32>,
+    paused: bool,
+}
+
+impl WorkbenchPanel for WorldStatsPanel {
+    fn id(&self) -> &str {
+        "world_stats"
+    }
+
+    fn title(&self) -> String {
+        "World Stats".to_string()
+    }
+
+    fn ui(&mut self, _ui: &mut egui::Ui) {}
+
+    fn ui_world(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        let now = world.resource::<Time<Real>>().elapsed_secs();
+        let stale = self
+            .last_refresh
+            .is_none_or(|last| now - last >= REFRESH_INTERVAL);
+        if stale && !self.paused {
+            self.snapshot = StatsSnapshot::collect(world);
+            self.last_refresh = Some(now);
+        }
+
+        if let Some(history) = world.get_resource::<EntityCountHistory>() {
+            entity_graph_ui(ui, history);
+        }
+        ui.separator();
+        self.toolbar_ui(ui);
+        ui.separator();
+        self.table_ui(ui);
+    }
+
+    fn needs_world(&self) -> bool {
+        true
+    }
+
+    fn default_visible(&self) -> bool {
+        false
+    }
+}
+
+impl WorldStatsPanel {
+    fn toolbar_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let components = self.snapshot.components.len();
+            let archetypes = self.snapshot.archetypes.len();
+            let resources = self.snapshot.resources.len();
+            ui.selectable_value(
+                &mut self.tab,
+                StatsTab::Components,
+                format!("Components ({components})"),
+            );
+            ui.selectable_value(
+                &mut self.tab,
+                StatsTab::Archetypes,
+                format!("Archetypes ({archetypes})"),
+            );
+            ui.selectable_value(
+                &mut self.tab,
+                StatsTab::Resources,
+                format!("Resources ({resources})"),
+            );
+            ui.separator();
+            ui.add(
+                egui::TextEdit::singleline(&mut self.filter)
+                    .hint_text("Filter...")
+                    .desired_width(140.0),
+            );
+            let pause_label = if self.paused {
+                "▶ Resume"
+            } else {
+                "⏸ Freeze"
+            };
+            if ui.button(pause_label).clicked() {
+                self.paused = !self.paused;
+            }
+        });
+    }
+
+    /// Filtered and sorted rows of the current tab.
+    fn sorted_rows(&self) -> Vec<&StatsRow> {
+        let needle = self.filter.to_lowercase();
+        let mut rows: Vec<&StatsRow> = self
+            .snapshot
+            .rows(self.tab)
+            .iter()
+            .filter(|r| {
+                needle.is_empty()
+                    || r.name.to_lowercase().contains(&needle)
+                    || r.detail.to_lowercase().contains(&needle)
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            let ord = match self.sort {
+                SortKey::Name => short_name(&a.name).cmp(short_name(&b.name)),
+                SortKey::Count => a.count.cmp(&b.count),
+                SortKey::Size => a.size.cmp(&b.size),
+            };
+            if self.ascending { ord } else { ord.reverse() }
+        });
+        rows
+    }
+
+    fn header_button(&mut self, ui: &mut egui::Ui, label: &str, key: SortKey) {
+        let arrow = match (self.sort == key, self.ascending) {
+            (false, _) => "",
+            (true, true) => " ⏶",
+            (true, false) => " ⏷",
+        };
+        if ui.button(format!("{label}{arrow}")).clicked() {
+            if self.sort == key {
+                self.ascending = !self.ascending;
+            } else {
+                self.sort = key;
+                self.ascending = key == SortKey::Name;
+            }
+        }
+    }
+
+    fn table_ui(&mut self, ui: &mut egui::Ui) {
+        let count_label = match self.tab {
+            StatsTab::Resources => "Instances",
+            _ => "Entities",
+        };
+        let rows: Vec<StatsRow> = self.sorted_rows().into_iter().cloned().collect();
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::Grid::new("world_stats_table")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        self.header_button(ui, "Name", SortKey::Name);
+                        self.header_button(ui, count_label, SortKey::Count);
+                        self.header_button(ui, "Approx. size", SortKey::Size);
+                        ui.end_row();
+                        for row in &rows {
+                            stats_row_ui(ui, row);
+                        }
+                    });
+            });
+    }
+}
+
+fn stats_row_ui(ui: &mut egui::Ui, row: &StatsRow) {
+    let name = ui.label(short_name(&row.name));
+    if row.detail.is_empty() {
+        name.on_hover_text(&row.name);
+    } else {
+        name.on_hover_text(format!("{}\n{}", row.name, row.detail));
+    }
+    ui.label(row.count.to_string());
+    ui.label(format_bytes(row.size));
+    ui.end_row();
+}
+
+/// Line graph of the entity count history; Play samples are tinted.
+fn entity_graph_ui(ui: &mut egui::Ui, history: &EntityCountHistory) {
+    let current = history.samples.back().map_or(0, |s| s.count);
+    let peak = history.samples.iter().map(|s| s.count).max().unwrap_or(0);
+    ui.horizontal(|ui| {
+        ui.strong(format!("Entities: {current}"));
+        ui.weak(format!("peak {peak}"));
+    });
+
+    let desired = egui::vec2(ui.available_width(), 80.0);
+    let (rect, _) = ui.allocate_exact_size(desired, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let len = history.samples.len();
+    if len < 2 {
+        return;
+    }
+
+    let max = peak.max(1) as f32;
+    let step = rect.width() / (len - 1) as f32;
+    let point = |i: usize, count: usize| {
+        egui::pos2(
+            rect.left() + i as f32 * step,
+            rect.bottom() - (count as f32 / max) * (rect.height() - 4.0) - 2.0,
+        )
+    };
+    let play_tint = ui.visuals().selection.bg_fill.gamma_multiply(0.25);
+    for (i, sample) in history.samples.iter().enumerate() {
+        if sample.mode != EditorMode::Edit {
+            let x = rect.left() + i as f32 * step;
+            let column = egui::Rect::from_x_y_ranges(x..=x + step, rect.y_range());
+            painter.rect_filled(column, 0.0, play_tint);
+        }
+    }
+    let points: Vec<egui::Pos2> = history
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| point(i, s.count))
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.5, ui.visuals().hyperlink_color),
+    ));
+}